@@ -0,0 +1,208 @@
+/**
+ * AWE Player - Embedded AudioWorkletProcessor
+ *
+ * Shipped inside the WASM package (see get_worklet_processor_source()).
+ * The loader substitutes __AWE_BINDINGS_URL__ with the wasm-bindgen glue URL
+ * and __AWE_PROTOCOL_VERSION__ with the bridge protocol version.
+ *
+ * The init message's protocolVersion is negotiated with a bridge `hello`;
+ * replies carry the negotiated version. MIDI and initialization go through
+ * bridge_command like any other client's.
+ *
+ * Main thread -> worklet:
+ *   { type: 'init', protocolVersion, wasm }      wasm = WebAssembly.Module or bytes
+ *   { type: 'midi', timestamp, channel, messageType, data1, data2 }
+ *   { type: 'control', command: 'reset' | 'getStats' | 'initSystems', sampleRate? }
+ * Worklet -> main thread:
+ *   { type: 'status' | 'stats' | 'error' | 'debug', protocolVersion, ... }
+ *
+ * Output 0 carries the mix; an optional output 1 carries the metronome
+ * click bus (create the node with numberOfOutputs: 2).
+ */
+
+import init, * as awe from '__AWE_BINDINGS_URL__';
+
+const PROTOCOL_VERSION = __AWE_PROTOCOL_VERSION__;
+
+// AudioWorkletGlobalScope lacks TextDecoder/TextEncoder in some browsers;
+// wasm-bindgen glue needs them for string marshalling (UTF-8 only).
+if (typeof globalThis.TextDecoder === 'undefined') {
+    globalThis.TextDecoder = class {
+        decode(bytes) {
+            if (!bytes) return '';
+            let encoded = '';
+            for (let i = 0; i < bytes.length; i++) encoded += '%' + bytes[i].toString(16).padStart(2, '0');
+            return decodeURIComponent(encoded);
+        }
+    };
+}
+if (typeof globalThis.TextEncoder === 'undefined') {
+    globalThis.TextEncoder = class {
+        encode(text) {
+            const binary = unescape(encodeURIComponent(text));
+            const bytes = new Uint8Array(binary.length);
+            for (let i = 0; i < binary.length; i++) bytes[i] = binary.charCodeAt(i);
+            return bytes;
+        }
+        encodeInto(text, view) {
+            const bytes = this.encode(text);
+            const written = Math.min(bytes.length, view.length);
+            view.set(bytes.subarray(0, written));
+            return { read: text.length, written };
+        }
+    };
+}
+
+class AwePlayerProcessor extends AudioWorkletProcessor {
+    constructor() {
+        super();
+        this.isInitialized = false;
+        this.protocolVersion = PROTOCOL_VERSION;
+        this.currentSampleTime = 0;
+        this.port.onmessage = (event) => this.handleMessage(event.data);
+        this.sendMessage({ type: 'status', status: 'awaiting_init' });
+    }
+
+    bridge(command) {
+        return JSON.parse(awe.bridge_command(JSON.stringify(command)));
+    }
+
+    async initializeWasm(message) {
+        try {
+            this.wasm = await init({ module_or_path: message.wasm });
+            const hello = this.bridge({ type: 'hello', protocolVersion: message.protocolVersion });
+            if (hello.type === 'error') {
+                this.sendMessage({ type: 'error', error: `Protocol negotiation failed: ${hello.message}` });
+                return;
+            }
+            this.protocolVersion = message.protocolVersion;
+            const initialized = this.bridge({ type: 'init', sampleRate });
+            if (initialized.type === 'error') {
+                throw new Error(initialized.message);
+            }
+            this.isInitialized = true;
+            this.sendMessage({ type: 'status', status: 'ready', sampleRate });
+        } catch (error) {
+            this.sendMessage({ type: 'error', error: `WASM initialization failed: ${error}` });
+        }
+    }
+
+    process(_inputs, outputs) {
+        const output = outputs[0];
+        if (!this.isInitialized || !output || output.length === 0) {
+            for (const out of outputs) for (const channel of out) channel.fill(0);
+            return true;
+        }
+
+        const frames = output[0].length;
+        try {
+            // Render into the bridge's own buffer and read it straight from WASM memory
+            // (the view is rebuilt each block because memory growth detaches old buffers)
+            awe.observe_audio_clock_global(currentTime);
+            const rendered = awe.render_stereo_block_global(frames);
+            const block = new Float32Array(this.wasm.memory.buffer, awe.get_render_buffer_ptr_global(), rendered * 2);
+            const left = output[0];
+            const right = output.length >= 2 ? output[1] : null;
+            for (let i = 0; i < rendered; i++) {
+                left[i] = right ? block[i * 2] : block[i * 2] + block[i * 2 + 1]; // Mono sums like MidiPlayer::process()
+                if (right) right[i] = block[i * 2 + 1];
+            }
+            if (outputs.length >= 2) {
+                const click = new Float32Array(this.wasm.memory.buffer, awe.get_click_buffer_ptr_global(), rendered);
+                for (const channel of outputs[1]) channel.set(click);
+            }
+        } catch (error) {
+            for (const channel of output) channel.fill(0);
+            this.sendMessage({ type: 'error', error: `Audio processing error: ${error}` });
+        }
+
+        this.currentSampleTime += frames;
+        if (this.currentSampleTime % sampleRate < frames) {
+            this.sendMessage({
+                type: 'stats',
+                sampleTime: this.currentSampleTime,
+                bufferSize: frames,
+                systemStatus: awe.get_system_status()
+            });
+        }
+        return true;
+    }
+
+    handleMessage(message) {
+        if (message.type === 'init') {
+            this.initializeWasm(message);
+            return;
+        }
+        if (!this.isInitialized) {
+            this.sendMessage({ type: 'error', error: 'AudioWorklet not initialized' });
+            return;
+        }
+
+        switch (message.type) {
+            case 'midi': {
+                const response = this.bridge({
+                    type: 'midi',
+                    timestamp: (message.timestamp || this.currentSampleTime) >>> 0,
+                    channel: message.channel,
+                    messageType: message.messageType,
+                    data1: message.data1,
+                    data2: message.data2
+                });
+                if (response.type === 'error') {
+                    this.sendMessage({ type: 'error', error: response.message });
+                }
+                break;
+            }
+            case 'control':
+                this.handleControlMessage(message);
+                break;
+            default:
+                this.sendMessage({ type: 'error', error: `Unknown message type: ${message.type}` });
+        }
+    }
+
+    handleControlMessage(message) {
+        switch (message.command) {
+            case 'reset':
+                try {
+                    awe.reset_audio_state_global();
+                } catch (error) {
+                    this.sendMessage({ type: 'error', error: String(error) });
+                    break;
+                }
+                this.currentSampleTime = 0;
+                this.sendMessage({ type: 'status', status: 'reset' });
+                break;
+            case 'getStats':
+                this.sendMessage({
+                    type: 'stats',
+                    sampleTime: this.currentSampleTime,
+                    systemStatus: awe.get_system_status()
+                });
+                break;
+            case 'initSystems':
+                if (message.sampleRate) {
+                    const response = this.bridge({ type: 'init', sampleRate: message.sampleRate });
+                    this.sendMessage({
+                        type: 'status',
+                        status: response.type === 'error' ? 'init_failed' : 'reinitialized',
+                        sampleRate: message.sampleRate
+                    });
+                }
+                break;
+            default:
+                this.sendMessage({ type: 'error', error: `Unknown control command: ${message.command}` });
+        }
+    }
+
+    sendMessage(message) {
+        message.protocolVersion = this.protocolVersion;
+        this.port.postMessage(message);
+    }
+
+    static get parameterDescriptors() {
+        return [];
+    }
+}
+
+registerProcessor('__AWE_PROCESSOR_NAME__', AwePlayerProcessor);
@@ -0,0 +1,157 @@
+/**
+ * AWE Player - A/B Render Comparison
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Renders one stretch of the loaded song twice, once per settings
+ * snapshot, and measures how far apart the results are: linear against
+ * cubic interpolation, modern against hardware synthesis, enhancements on
+ * and off. Both renders are kept for listening tests, and the numbers
+ * suit regression checks - two renders with the same settings null to
+ * silence, so any difference comes from the settings.
+ *
+ * Each pass starts clean: sounding notes released, tails flushed and the
+ * chorus LFO rewound, then a seek to the start (which chases programs and
+ * controllers) with the snapshot applied. A snapshot only names what it
+ * changes; everything else stays as the engine has it. The engine's own settings, song
+ * position and transport state are put back afterwards.
+ *
+ * The difference is taken sample by sample, so it includes level changes
+ * and phase drift as well as timbre. Levels are dBFS, and null for silence.
+ */
+
+use serde::{Deserialize, Serialize};
+use crate::synth::device_profile::{EffectsQuality, InterpolationQuality};
+use crate::synth::hardware_mode::SynthesisMode;
+use crate::synth::voice_manager::MAX_VOICES;
+
+/// Longest segment one comparison renders (seconds, per pass)
+pub const MAX_AB_SECONDS: f64 = 60.0;
+
+/// Settings one pass renders with (JSON; missing fields keep the engine's)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AbSnapshot {
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub interpolation: Option<String>,      // "linear" or "cubic"
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub synthesis_mode: Option<String>,     // "modern" or "hardware"
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub enhancements: Option<bool>,         // Non-EMU8000 voice heuristics
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub effects: Option<String>,            // "full", "reduced" or "off"
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub max_polyphony: Option<usize>,       // 1-32
+}
+
+/// What to render and the two snapshots to compare (JSON)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AbRequest {
+    #[serde(default)]
+    pub start_seconds: f64,
+    pub seconds: f64,                       // Up to MAX_AB_SECONDS
+    pub a: AbSnapshot,
+    pub b: AbSnapshot,
+}
+
+impl AbRequest {
+    /// Request from JSON; None if it doesn't parse, names an unknown setting or has no length
+    pub fn from_json(json: &str) -> Option<Self> {
+        let request: Self = serde_json::from_str(json).ok()?;
+        let valid = request.start_seconds.is_finite() && request.start_seconds >= 0.0
+            && request.seconds.is_finite() && request.seconds > 0.0
+            && request.a.resolve(&AbSettings::default()).is_some()
+            && request.b.resolve(&AbSettings::default()).is_some();
+        valid.then(|| Self { seconds: request.seconds.min(MAX_AB_SECONDS), ..request })
+    }
+}
+
+/// Engine settings an A/B pass can change
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AbSettings {
+    pub interpolation: InterpolationQuality,
+    pub synthesis_mode: SynthesisMode,
+    pub enhancements: bool,
+    pub effects: EffectsQuality,
+    pub max_polyphony: usize,
+}
+
+impl Default for AbSettings {
+    fn default() -> Self {
+        Self {
+            interpolation: InterpolationQuality::default(),
+            synthesis_mode: SynthesisMode::Modern,
+            enhancements: true,
+            effects: EffectsQuality::default(),
+            max_polyphony: MAX_VOICES,
+        }
+    }
+}
+
+impl AbSnapshot {
+    /// `current` with this snapshot's changes; None for an unknown name
+    pub fn resolve(&self, current: &AbSettings) -> Option<AbSettings> {
+        Some(AbSettings {
+            interpolation: match &self.interpolation {
+                Some(name) => InterpolationQuality::from_name(name)?,
+                None => current.interpolation,
+            },
+            synthesis_mode: match &self.synthesis_mode {
+                Some(name) => SynthesisMode::from_name(name)?,
+                None => current.synthesis_mode,
+            },
+            enhancements: self.enhancements.unwrap_or(current.enhancements),
+            effects: match &self.effects {
+                Some(name) => EffectsQuality::from_name(name)?,
+                None => current.effects,
+            },
+            max_polyphony: self.max_polyphony.unwrap_or(current.max_polyphony).clamp(1, MAX_VOICES),
+        })
+    }
+}
+
+/// How far two renders are apart (render_ab_comparison_global JSON)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AbComparison {
+    pub frames: usize,                      // Stereo frames per render
+    pub sample_rate: f32,
+    pub start_seconds: f64,
+    pub a_rms_db: Option<f32>,
+    pub b_rms_db: Option<f32>,
+    pub difference_rms_db: Option<f32>,     // RMS of A - B
+    pub difference_peak_db: Option<f32>,
+    pub null_depth_db: Option<f32>,         // Difference RMS relative to A's (more negative = closer)
+    pub correlation: Option<f32>,           // -1.0 to 1.0; null if either render is silent
+    pub identical: bool,                    // Bit-identical renders
+}
+
+/// Compare two interleaved stereo renders of the same length
+pub fn compare(a: &[f32], b: &[f32], sample_rate: f32, start_seconds: f64) -> AbComparison {
+    let samples = a.len().min(b.len());
+    let (mut a_power, mut b_power, mut cross, mut difference_power, mut peak) = (0.0f64, 0.0f64, 0.0f64, 0.0f64, 0.0f32);
+    for (&left, &right) in a[..samples].iter().zip(&b[..samples]) {
+        let difference = left - right;
+        a_power += left as f64 * left as f64;
+        b_power += right as f64 * right as f64;
+        cross += left as f64 * right as f64;
+        difference_power += difference as f64 * difference as f64;
+        peak = peak.max(difference.abs());
+    }
+    let rms_db = |power: f64| (power > 0.0 && samples > 0).then(|| (10.0 * (power / samples as f64).log10()) as f32);
+    AbComparison {
+        frames: samples / 2,
+        sample_rate,
+        start_seconds,
+        a_rms_db: rms_db(a_power),
+        b_rms_db: rms_db(b_power),
+        difference_rms_db: rms_db(difference_power),
+        difference_peak_db: (peak > 0.0).then(|| 20.0 * peak.log10()),
+        null_depth_db: (difference_power > 0.0 && a_power > 0.0).then(|| (10.0 * (difference_power / a_power).log10()) as f32),
+        correlation: (a_power > 0.0 && b_power > 0.0).then(|| (cross / (a_power * b_power).sqrt()).clamp(-1.0, 1.0) as f32),
+        identical: a.len() == b.len() && a.iter().zip(b).all(|(left, right)| left.to_bits() == right.to_bits()),
+    }
+}
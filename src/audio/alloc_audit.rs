@@ -0,0 +1,118 @@
+/**
+ * AWE Player - Allocation-Free Audio Audit
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Debug facility for AudioWorklet real-time safety. With the `alloc-audit`
+ * feature the crate installs a counting global allocator, and every audited
+ * region (process(), process_audio_buffer()) panics on exit if the heap was
+ * touched inside it. Counting is per thread, so control-thread work such as
+ * SoundFont loading never trips the audio thread's audit.
+ *
+ * Without the feature, AuditScope is a zero-sized no-op and the system
+ * allocator is left alone.
+ */
+
+/// Whether the allocation audit is compiled in
+pub const AUDIT_ENABLED: bool = cfg!(feature = "alloc-audit");
+
+#[cfg(feature = "alloc-audit")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    // Const-initialized Cells need no lazy setup, so the allocator can read them safely
+    thread_local! {
+        static AUDIT_DEPTH: Cell<u32> = const { Cell::new(0) };
+        static AUDITED_ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    }
+
+    pub struct CountingAllocator;
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    fn note_allocation() {
+        let _ = AUDIT_DEPTH.try_with(|depth| {
+            if depth.get() > 0 {
+                let _ = AUDITED_ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            }
+        });
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            note_allocation();
+            System.alloc(layout)
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            note_allocation();
+            System.alloc_zeroed(layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            note_allocation();
+            System.realloc(ptr, layout, new_size)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    pub fn enter() -> u64 {
+        AUDIT_DEPTH.with(|depth| depth.set(depth.get() + 1));
+        allocations()
+    }
+
+    pub fn exit() {
+        AUDIT_DEPTH.with(|depth| depth.set(depth.get().saturating_sub(1)));
+    }
+
+    pub fn allocations() -> u64 {
+        AUDITED_ALLOCATIONS.with(|count| count.get())
+    }
+}
+
+/// Region that must not allocate - panics when dropped if it did
+/// Scopes nest; each one checks only the allocations made while it was open
+#[must_use = "the audit covers the scope's lifetime"]
+pub struct AuditScope {
+    #[cfg(feature = "alloc-audit")]
+    region: &'static str,
+    #[cfg(feature = "alloc-audit")]
+    allocations_at_entry: u64,
+}
+
+impl AuditScope {
+    #[inline(always)]
+    pub fn enter(region: &'static str) -> Self {
+        #[cfg(feature = "alloc-audit")]
+        return AuditScope { region, allocations_at_entry: counting::enter() };
+        #[cfg(not(feature = "alloc-audit"))]
+        {
+            let _ = region;
+            AuditScope {}
+        }
+    }
+}
+
+#[cfg(feature = "alloc-audit")]
+impl Drop for AuditScope {
+    fn drop(&mut self) {
+        counting::exit();
+        let allocations = counting::allocations() - self.allocations_at_entry;
+        // Never panic while already unwinding (that would abort)
+        if allocations > 0 && !std::thread::panicking() {
+            panic!("alloc-audit: {} heap allocation(s) inside {}", allocations, self.region);
+        }
+    }
+}
+
+/// Total allocations seen inside audited regions on this thread (0 without `alloc-audit`)
+pub fn audited_allocation_count() -> u64 {
+    #[cfg(feature = "alloc-audit")]
+    return counting::allocations();
+    #[cfg(not(feature = "alloc-audit"))]
+    0
+}
@@ -0,0 +1,78 @@
+/**
+ * AWE Player - Polyphony Auto-Gain
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * The master stage applies a fixed 2.5x gain, which suits solo lines but
+ * clips once dozens of voices stack up in a dense orchestral file. With
+ * auto-gain on, the master gain is additionally scaled by the number of
+ * sounding voices: up to REFERENCE_VOICES nothing changes, beyond that the
+ * gain falls with the square root of the count (uncorrelated voices add up
+ * in power, not amplitude). Reduction engages quickly as voices pile up and
+ * recovers slowly, so the level does not pump between chords.
+ */
+
+/// Voice count the fixed master gain is calibrated for
+pub const REFERENCE_VOICES: usize = 4;
+/// Largest reduction applied (about -12 dB)
+pub const MIN_AUTO_GAIN: f32 = 0.25;
+
+const ATTACK_SECONDS: f32 = 0.01;       // Time constant when voices are added
+const RECOVERY_SECONDS: f32 = 1.5;      // Time constant when voices end
+
+/// Master gain multiplier following the active voice count
+#[derive(Debug, Clone)]
+pub struct AutoGain {
+    enabled: bool,
+    gain: f64,          // f64: the 1.5 s recovery steps are below f32 resolution near unity
+    attack: f64,
+    recovery: f64,
+}
+
+impl AutoGain {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            enabled: false,
+            gain: 1.0,
+            attack: (-1.0 / (ATTACK_SECONDS as f64 * sample_rate as f64)).exp(),
+            recovery: (-1.0 / (RECOVERY_SECONDS as f64 * sample_rate as f64)).exp(),
+        }
+    }
+
+    /// Turning auto-gain off lets the gain recover to unity rather than jump
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// True while the gain differs from unity (enabled, or still recovering)
+    pub fn is_engaged(&self) -> bool {
+        self.enabled || self.gain != 1.0
+    }
+
+    /// Current multiplier (1.0 = no reduction)
+    pub fn gain(&self) -> f32 {
+        self.gain as f32
+    }
+
+    /// Target multiplier for `active_voices`
+    pub fn target_for(active_voices: usize) -> f32 {
+        if active_voices <= REFERENCE_VOICES {
+            return 1.0;
+        }
+        (REFERENCE_VOICES as f32 / active_voices as f32).sqrt().max(MIN_AUTO_GAIN)
+    }
+
+    /// Advance one sample and return the multiplier to apply
+    pub fn next(&mut self, active_voices: usize) -> f32 {
+        let target = if self.enabled { Self::target_for(active_voices) as f64 } else { 1.0 };
+        let coefficient = if target < self.gain { self.attack } else { self.recovery };
+        self.gain = target + (self.gain - target) * coefficient;
+        if (self.gain - target).abs() < 1e-6 {
+            self.gain = target;
+        }
+        self.gain as f32
+    }
+}
@@ -0,0 +1,293 @@
+/**
+ * AWE Player - Batch MIDI to Audio Conversion
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Library-conversion tools hand over a list of MIDI files and get one
+ * audio file back per MIDI file, all rendered with the SoundFont already
+ * loaded. Files are rendered one after another on the engine, each from a
+ * clean start: sounding notes are stopped, effect tails flushed and channel
+ * state reset before the next file is loaded. Each render ends the way an
+ * offline render does, once the song is over and its tails have decayed
+ * (or at the tail cap), and is then encoded with the usual export options.
+ *
+ * The host drives the work in slices (step_batch_render) so a page or
+ * worker stays responsive and can cancel between slices. Progress goes to
+ * a single listener as JSON events; finished files are collected by index.
+ */
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use super::export::{encode_with_options, ExportFormat, ExportOptions};
+use super::silence::{RenderStopReason, DEFAULT_SILENCE_THRESHOLD_DB, DEFAULT_TAIL_CAP_SECONDS};
+
+/// Longest output per file (seconds); longer renders are cut off
+pub const MAX_BATCH_SECONDS: f32 = 600.0;
+
+/// Render settings for every file of a batch (JSON; missing fields take the defaults)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct BatchOptions {
+    pub format: String,             // Export format name (see get_export_formats)
+    #[serde(flatten)]
+    pub export: ExportOptions,      // Dither and loudness normalization
+    pub threshold_db: f32,          // Silence that ends a file's render
+    pub tail_cap_seconds: f32,      // Longest render after a file's last event
+    pub max_seconds: f32,           // Longest output per file
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            format: "pcm16".to_string(),
+            export: ExportOptions::default(),
+            threshold_db: DEFAULT_SILENCE_THRESHOLD_DB,
+            tail_cap_seconds: DEFAULT_TAIL_CAP_SECONDS,
+            max_seconds: MAX_BATCH_SECONDS,
+        }
+    }
+}
+
+impl BatchOptions {
+    /// Options from JSON; None if it doesn't parse
+    pub fn from_json(json: &str) -> Option<Self> {
+        serde_json::from_str(json).ok()
+    }
+}
+
+/// One batch conversion notification
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum BatchEvent {
+    Started {
+        files: usize,
+    },
+    FileStarted {
+        index: usize,
+    },
+    Progress {
+        index: usize,
+        percent: f32,               // Of this file, by song position (99 while tails render)
+        overall_percent: f32,
+    },
+    FileComplete {
+        index: usize,
+        seconds: f32,
+        bytes: usize,
+        truncated: bool,            // Cut off at maxSeconds
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "ts", ts(optional))]
+        stop_reason: Option<RenderStopReason>,
+    },
+    FileFailed {
+        index: usize,
+        message: String,
+    },
+    Complete {
+        converted: usize,
+        failed: usize,
+        cancelled: bool,
+    },
+}
+
+impl BatchEvent {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+/// Batch state (get_batch_render_status_global)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct BatchStatus {
+    pub running: bool,
+    pub files: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub current: Option<usize>,     // File being rendered
+    pub converted: usize,
+    pub failed: usize,
+    pub cancelled: bool,
+}
+
+pub type BatchListener = Rc<dyn Fn(&BatchEvent)>;
+
+// JS functions are not Send, so the listener lives with the thread that registered it
+thread_local! {
+    static LISTENER: RefCell<Option<BatchListener>> = const { RefCell::new(None) };
+}
+
+/// Install (or with None, remove) the batch event listener
+pub fn set_listener(listener: Option<BatchListener>) {
+    LISTENER.with(|slot| *slot.borrow_mut() = listener);
+}
+
+/// Deliver an event to the listener, if one is registered
+pub fn emit(event: BatchEvent) {
+    let listener = LISTENER.with(|slot| slot.borrow().clone());
+    if let Some(listener) = listener {
+        listener(&event);
+    }
+}
+
+/// Files, outputs and progress of one batch; the bridge does the rendering
+#[derive(Debug, Clone)]
+pub struct BatchRender {
+    options: BatchOptions,
+    format: ExportFormat,
+    files: Vec<Vec<u8>>,            // Emptied as each file starts
+    outputs: Vec<Option<Vec<u8>>>,  // Encoded files until taken
+    current: Option<usize>,
+    next: usize,
+    samples: Vec<f32>,              // Interleaved stereo render of the current file
+    max_samples: usize,
+    truncated: bool,
+    converted: usize,
+    failed: usize,
+    running: bool,
+    cancelled: bool,
+}
+
+impl BatchRender {
+    /// None for an unknown format name
+    pub fn new(files: Vec<Vec<u8>>, options: BatchOptions, sample_rate: f32) -> Option<Self> {
+        let format = ExportFormat::from_name(&options.format)?;
+        let max_seconds = if options.max_seconds.is_finite() && options.max_seconds > 0.0 {
+            options.max_seconds.min(MAX_BATCH_SECONDS)
+        } else {
+            MAX_BATCH_SECONDS
+        };
+        emit(BatchEvent::Started { files: files.len() });
+        Some(Self {
+            format,
+            outputs: vec![None; files.len()],
+            files,
+            current: None,
+            next: 0,
+            samples: Vec::new(),
+            max_samples: (max_seconds * sample_rate) as usize * 2,
+            truncated: false,
+            converted: 0,
+            failed: 0,
+            running: true,
+            cancelled: false,
+            options: BatchOptions { max_seconds, ..options },
+        })
+    }
+
+    pub fn options(&self) -> &BatchOptions {
+        &self.options
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// File being rendered
+    pub fn current(&self) -> Option<usize> {
+        self.current
+    }
+
+    /// Move on to the next file and hand over its bytes; None once every file has started
+    pub fn start_next(&mut self) -> Option<(usize, Vec<u8>)> {
+        let index = self.next;
+        let data = std::mem::take(self.files.get_mut(index)?);
+        self.next += 1;
+        self.current = Some(index);
+        self.samples.clear();
+        self.truncated = false;
+        emit(BatchEvent::FileStarted { index });
+        Some((index, data))
+    }
+
+    /// Append a rendered block of the current file; false once maxSeconds is reached
+    pub fn push(&mut self, block: &[f32]) -> bool {
+        let room = self.max_samples.saturating_sub(self.samples.len());
+        self.samples.extend_from_slice(&block[..block.len().min(room)]);
+        self.truncated |= block.len() > room;
+        !self.truncated
+    }
+
+    pub fn report_progress(&self, song_position: f64) {
+        let Some(index) = self.current else {
+            return;
+        };
+        let percent = (song_position * 100.0).min(99.0) as f32;
+        let total = self.outputs.len().max(1) as f32;
+        emit(BatchEvent::Progress { index, percent, overall_percent: (index as f32 + percent / 100.0) / total * 100.0 });
+    }
+
+    /// Encode the current file's render and keep it for take_output
+    pub fn complete_file(&mut self, sample_rate: f32, stop_reason: Option<RenderStopReason>) {
+        let Some(index) = self.current.take() else {
+            return;
+        };
+        let bytes = encode_with_options(self.format, sample_rate as u32, 2, &self.samples, self.options.export);
+        emit(BatchEvent::FileComplete {
+            index,
+            seconds: (self.samples.len() / 2) as f32 / sample_rate,
+            bytes: bytes.len(),
+            truncated: self.truncated,
+            stop_reason,
+        });
+        self.outputs[index] = Some(bytes);
+        self.samples = Vec::new();
+        self.converted += 1;
+    }
+
+    pub fn fail_file(&mut self, message: impl Into<String>) {
+        let Some(index) = self.current.take() else {
+            return;
+        };
+        self.samples = Vec::new();
+        self.failed += 1;
+        emit(BatchEvent::FileFailed { index, message: message.into() });
+    }
+
+    /// End the batch (early if cancelled); files not yet started are dropped
+    pub fn finish(&mut self, cancelled: bool) {
+        if !self.running {
+            return;
+        }
+        if cancelled {
+            self.fail_file("Cancelled");
+        }
+        self.running = false;
+        self.cancelled = cancelled;
+        self.files = Vec::new();
+        self.samples = Vec::new();
+        emit(BatchEvent::Complete { converted: self.converted, failed: self.failed, cancelled });
+    }
+
+    /// Encoded file at `index` (once; None if it failed, isn't done or was taken)
+    pub fn take_output(&mut self, index: usize) -> Option<Vec<u8>> {
+        self.outputs.get_mut(index)?.take()
+    }
+
+    pub fn status(&self) -> BatchStatus {
+        BatchStatus {
+            running: self.running,
+            files: self.outputs.len(),
+            current: self.current,
+            converted: self.converted,
+            failed: self.failed,
+            cancelled: self.cancelled,
+        }
+    }
+}
+
+// ===== WASM EXPORTS =====
+
+/// Receive batch conversion events as JSON strings (`BatchEvent`); pass null/undefined to stop
+#[wasm_bindgen]
+pub fn set_batch_event_callback(callback: Option<js_sys::Function>) {
+    set_listener(callback.map(|callback| {
+        Rc::new(move |event: &BatchEvent| {
+            let _ = callback.call1(&JsValue::NULL, &JsValue::from_str(&event.to_json()));
+        }) as BatchListener
+    }));
+}
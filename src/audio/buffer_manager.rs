@@ -11,9 +11,15 @@
 
 use std::collections::VecDeque;
 use serde::{Deserialize, Serialize};
+use crate::synth::device_profile::DeviceProfile;
+
+/// Underruns within UNDERRUN_WINDOW_SECONDS that count as repeated
+pub const REPEATED_UNDERRUNS: usize = 3;
+pub const UNDERRUN_WINDOW_SECONDS: f32 = 10.0;
 
 /// Buffer size configuration options
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct BufferConfig {
     pub size: BufferSize,
     pub latency_ms: f32,
@@ -23,6 +29,7 @@ pub struct BufferConfig {
 
 /// Supported buffer sizes
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum BufferSize {
     Small = 128,
     Medium = 256,
@@ -50,6 +57,7 @@ impl BufferSize {
 
 /// CPU usage levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum CpuUsage {
     Low,
     Medium,
@@ -58,6 +66,7 @@ pub enum CpuUsage {
 
 /// Stability levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum Stability {
     Stable,
     Moderate,
@@ -66,11 +75,13 @@ pub enum Stability {
 
 /// Buffer performance metrics
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct BufferMetrics {
     pub average_processing_time: f32,
     pub max_processing_time: f32,
     pub underruns: u32,
     pub overruns: u32,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
     pub samples_processed: u64,
     pub uptime_ms: f32,
 }
@@ -97,6 +108,7 @@ pub struct AudioBufferManager {
     min_time_between_adaptations_ms: f32,
     max_performance_history: usize,
     device_info: Option<DeviceInfo>,
+    recent_underruns: VecDeque<u64>, // Sample times of the latest underruns
 }
 
 impl AudioBufferManager {
@@ -116,6 +128,7 @@ impl AudioBufferManager {
             min_time_between_adaptations_ms: 5000.0, // 5 seconds
             max_performance_history: 100,
             device_info: None,
+            recent_underruns: VecDeque::with_capacity(REPEATED_UNDERRUNS),
         };
         
         manager.current_buffer_size = initial_buffer_size
@@ -144,6 +157,11 @@ impl AudioBufferManager {
         }
     }
     
+    /// Hardware info last reported through set_device_info
+    pub fn device_info(&self) -> Option<&DeviceInfo> {
+        self.device_info.as_ref()
+    }
+    
     /// Set sample rate for buffer calculations
     pub fn set_sample_rate(&mut self, sample_rate: f32) {
         self.sample_rate = sample_rate;
@@ -181,6 +199,17 @@ impl AudioBufferManager {
         }
     }
     
+    /// The host's block size changed to `frames`: follow it where it is a standard size
+    /// (without leaving adaptive mode) and restart measurements taken at the old size
+    pub fn adopt_block_size(&mut self, frames: usize) {
+        if let Some(size) = BufferSize::from_usize(frames) {
+            self.current_buffer_size = size;
+        }
+        self.performance_history.clear();
+        self.recent_underruns.clear();
+        self.last_adaptation_ms = Self::get_current_time_ms();
+    }
+    
     /// Enable or disable adaptive buffer sizing
     pub fn set_adaptive_mode(&mut self, enabled: bool) {
         self.adaptive_mode = enabled;
@@ -196,10 +225,11 @@ impl AudioBufferManager {
         self.samples_processed += buffer_size as u64;
         
         // Add to performance history (keep last N measurements)
-        self.performance_history.push_back(processing_time_ms);
-        if self.performance_history.len() > self.max_performance_history {
+        // Pop first so the deque never outgrows its preallocated capacity
+        if self.performance_history.len() >= self.max_performance_history {
             self.performance_history.pop_front();
         }
+        self.performance_history.push_back(processing_time_ms);
         
         // Check for underruns (processing took longer than available time)
         let available_time_ms = (buffer_size as f32 / self.sample_rate) * 1000.0;
@@ -232,6 +262,23 @@ impl AudioBufferManager {
         }
     }
     
+    /// Record an underrun at output sample `sample_time`
+    /// Returns true when it makes REPEATED_UNDERRUNS within the window (the window then starts over)
+    pub fn record_underrun_at(&mut self, sample_time: u64) -> bool {
+        self.record_underrun();
+        let window = (UNDERRUN_WINDOW_SECONDS * self.sample_rate) as u64;
+        self.recent_underruns.retain(|&time| sample_time.saturating_sub(time) <= window);
+        if self.recent_underruns.len() >= REPEATED_UNDERRUNS {
+            self.recent_underruns.pop_front();
+        }
+        self.recent_underruns.push_back(sample_time);
+        if self.recent_underruns.len() >= REPEATED_UNDERRUNS {
+            self.recent_underruns.clear();
+            return true;
+        }
+        false
+    }
+    
     /// Record buffer overrun (processing too fast)
     pub fn record_overrun(&mut self) {
         self.overrun_count += 1;
@@ -263,6 +310,7 @@ impl AudioBufferManager {
         self.performance_history.clear();
         self.underrun_count = 0;
         self.overrun_count = 0;
+        self.recent_underruns.clear();
         self.samples_processed = 0;
         self.start_time_ms = Self::get_current_time_ms();
         self.metrics = BufferMetrics::default();
@@ -317,21 +365,9 @@ impl AudioBufferManager {
     /// Detect optimal buffer size based on device capabilities
     fn detect_optimal_buffer_size(&self) -> BufferSize {
         if let Some(device) = &self.device_info {
-            // High-end devices can handle lower latency
-            if device.hardware_concurrency >= 8 && device.device_memory_gb >= 8 {
-                // High-end device debug removed
-                return BufferSize::Small;
-            }
-            
-            // Mid-range devices use balanced approach
-            if device.hardware_concurrency >= 4 && device.device_memory_gb >= 4 {
-                // Mid-range device debug removed
-                return BufferSize::Medium;
-            }
-            
-            // Low-end devices prioritize stability
-            // Low-end device debug removed
-            BufferSize::Large
+            // High-end devices can handle lower latency, low-end ones prioritize stability
+            let profile = DeviceProfile::detect(device.hardware_concurrency, device.device_memory_gb);
+            BufferSize::from_usize(profile.settings().buffer_size).unwrap_or(BufferSize::Medium)
         } else {
             // Default to medium buffer size when device info is unknown
             // Unknown device debug removed
@@ -0,0 +1,139 @@
+/**
+ * AWE Player - Master Output Capture
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Records the bridge's master output so users can save live playing as a
+ * WAV (or FLAC/Ogg, when compiled in) file without MediaRecorder. Storage for the whole capture is reserved
+ * when recording starts, at the requested maximum duration. The audio
+ * callback then only copies into spare capacity and never reallocates.
+ * Recording stops by itself when the cap is reached.
+ */
+
+use serde::Serialize;
+use super::export::{encode_with_options, ExportFormat, ExportOptions};
+
+/// Hard safety cap on a single capture (10 minutes; ~200 MB of stereo f32 at 44.1 kHz)
+pub const MAX_CAPTURE_SECONDS: f32 = 600.0;
+
+/// Captures are always stereo: mono output is written to both channels
+const CAPTURE_CHANNELS: usize = 2;
+
+/// Capture state reported by get_capture_status
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct CaptureStatus {
+    pub recording: bool,
+    pub seconds: f32,
+    pub max_seconds: f32,
+    pub reached_cap: bool,
+}
+
+/// Growable recording buffer fed from the audio callback
+#[derive(Debug, Clone)]
+pub struct OutputCapture {
+    samples: Vec<f32>,        // Interleaved stereo; capacity = duration cap
+    sample_rate: u32,
+    recording: bool,
+    reached_cap: bool,
+}
+
+impl OutputCapture {
+    pub fn new() -> Self {
+        Self { samples: Vec::new(), sample_rate: 44100, recording: false, reached_cap: false }
+    }
+
+    /// Start a new capture, discarding any previous one
+    /// Returns false if memory for `max_seconds` could not be reserved
+    pub fn start(&mut self, sample_rate: u32, max_seconds: f32) -> bool {
+        let max_seconds = if max_seconds > 0.0 { max_seconds.min(MAX_CAPTURE_SECONDS) } else { MAX_CAPTURE_SECONDS };
+        let max_samples = (max_seconds * sample_rate as f32) as usize * CAPTURE_CHANNELS;
+
+        self.samples = Vec::new();
+        self.recording = false;
+        self.reached_cap = false;
+        if self.samples.try_reserve_exact(max_samples).is_err() {
+            return false;
+        }
+        self.sample_rate = sample_rate;
+        self.recording = true;
+        true
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// True once recording stopped because the duration cap was hit
+    pub fn reached_cap(&self) -> bool {
+        self.reached_cap
+    }
+
+    pub fn recorded_seconds(&self) -> f32 {
+        (self.samples.len() / CAPTURE_CHANNELS) as f32 / self.sample_rate as f32
+    }
+
+    pub fn max_seconds(&self) -> f32 {
+        (self.samples.capacity() / CAPTURE_CHANNELS) as f32 / self.sample_rate as f32
+    }
+
+    pub fn status(&self) -> CaptureStatus {
+        CaptureStatus {
+            recording: self.recording,
+            seconds: self.recorded_seconds(),
+            max_seconds: self.max_seconds(),
+            reached_cap: self.reached_cap,
+        }
+    }
+
+    /// Append interleaved stereo samples (audio thread - never reallocates)
+    pub fn write_stereo(&mut self, interleaved: &[f32]) {
+        if !self.recording {
+            return;
+        }
+        let space = self.samples.capacity() - self.samples.len();
+        let take = interleaved.len().min(space) & !1; // Whole frames only
+        self.samples.extend_from_slice(&interleaved[..take]);
+        if take < interleaved.len() {
+            self.stop_at_cap();
+        }
+    }
+
+    /// Append mono samples, duplicated to both channels (audio thread - never reallocates)
+    pub fn write_mono(&mut self, samples: &[f32]) {
+        if !self.recording {
+            return;
+        }
+        for &sample in samples {
+            if self.samples.capacity() - self.samples.len() < CAPTURE_CHANNELS {
+                self.stop_at_cap();
+                return;
+            }
+            self.samples.push(sample);
+            self.samples.push(sample);
+        }
+    }
+
+    fn stop_at_cap(&mut self) {
+        self.recording = false;
+        self.reached_cap = true;
+    }
+
+    /// Stop recording and encode everything captured (the buffer is released)
+    pub fn stop(&mut self, format: ExportFormat) -> Vec<u8> {
+        self.stop_with_options(format, ExportOptions::default())
+    }
+
+    /// stop() with encoder options (dithering)
+    pub fn stop_with_options(&mut self, format: ExportFormat, options: ExportOptions) -> Vec<u8> {
+        self.recording = false;
+        let samples = std::mem::take(&mut self.samples);
+        encode_with_options(format, self.sample_rate, CAPTURE_CHANNELS as u16, &samples, options)
+    }
+}
+
+impl Default for OutputCapture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,150 @@
+/**
+ * AWE Player - Audio Clock Correlation
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * The engine counts rendered samples; the browser schedules and draws in
+ * AudioContext.currentTime. The two are nominally locked, but the device
+ * clock behind currentTime rarely runs at exactly the nominal sample rate
+ * and hosts may skip or repeat blocks, so converting with a fixed rate
+ * drifts over a long session.
+ *
+ * JS reports the context time at the start of each block (observe). Every
+ * OBSERVATION_INTERVAL_SECONDS one observation is kept in a fixed ring, and
+ * a least-squares line through the ring maps sample positions to context
+ * time and back. An observation far off the line (suspend/resume, engine
+ * reset) discards the history and starts a new fit.
+ */
+
+use serde::Serialize;
+
+/// Observations kept for the fit (HISTORY x interval = ~32 s window)
+const CLOCK_HISTORY: usize = 64;
+const OBSERVATION_INTERVAL_SECONDS: f64 = 0.5;
+/// Deviation from the fitted line that counts as a discontinuity
+const RESYNC_THRESHOLD_SECONDS: f64 = 0.05;
+/// Observations needed before the drift estimate is reported as locked
+const LOCK_OBSERVATIONS: usize = 4;
+
+/// Drift estimate reported to JS (get_clock_status_global)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ClockStatus {
+    pub drift_ppm: f64,         // Context clock vs nominal sample rate (+ = context runs fast)
+    pub residual_ms: f64,       // RMS distance of observations from the fitted line
+    pub observations: usize,
+    pub resyncs: u32,           // Discontinuities since creation
+    pub locked: bool,           // Enough observations for a drift estimate
+}
+
+/// Sample counter <-> AudioContext time mapping
+#[derive(Debug, Clone)]
+pub struct ClockSync {
+    sample_rate: f64,
+    history: [(u64, f64); CLOCK_HISTORY],   // (sample position, context time)
+    len: usize,
+    next: usize,
+    offset: f64,                            // Context time at sample 0
+    seconds_per_sample: f64,
+    resyncs: u32,
+}
+
+impl ClockSync {
+    pub fn new(sample_rate: f32) -> Self {
+        let sample_rate = (sample_rate as f64).max(1.0);
+        Self {
+            sample_rate,
+            history: [(0, 0.0); CLOCK_HISTORY],
+            len: 0,
+            next: 0,
+            offset: 0.0,
+            seconds_per_sample: 1.0 / sample_rate,
+            resyncs: 0,
+        }
+    }
+
+    /// Forget all observations (the next one starts a new fit)
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.next = 0;
+        self.offset = 0.0;
+        self.seconds_per_sample = 1.0 / self.sample_rate;
+    }
+
+    /// Record that `sample` was rendered at `context_time` seconds
+    /// Cheap and allocation-free, so it can run at the start of every audio block
+    pub fn observe(&mut self, sample: u64, context_time: f64) {
+        if !context_time.is_finite() {
+            return;
+        }
+        if self.len > 0 {
+            if (self.sample_to_time(sample) - context_time).abs() > RESYNC_THRESHOLD_SECONDS {
+                self.reset();
+                self.resyncs += 1;
+            } else {
+                let (last_sample, _) = self.history[(self.next + CLOCK_HISTORY - 1) % CLOCK_HISTORY];
+                if (sample.saturating_sub(last_sample) as f64) < OBSERVATION_INTERVAL_SECONDS * self.sample_rate {
+                    return;
+                }
+            }
+        }
+        self.history[self.next] = (sample, context_time);
+        self.next = (self.next + 1) % CLOCK_HISTORY;
+        self.len = (self.len + 1).min(CLOCK_HISTORY);
+        self.refit();
+    }
+
+    /// Context time at which `sample` is (or was) rendered
+    pub fn sample_to_time(&self, sample: u64) -> f64 {
+        self.offset + sample as f64 * self.seconds_per_sample
+    }
+
+    /// Sample position rendered at `context_time` (for scheduling events)
+    pub fn time_to_sample(&self, context_time: f64) -> u64 {
+        ((context_time - self.offset) / self.seconds_per_sample).max(0.0).round() as u64
+    }
+
+    pub fn status(&self) -> ClockStatus {
+        let locked = self.len >= LOCK_OBSERVATIONS;
+        let drift_ppm = if locked { (self.seconds_per_sample * self.sample_rate - 1.0) * 1e6 } else { 0.0 };
+        let residual = if self.len > 0 {
+            let sum: f64 = self.observations()
+                .map(|&(sample, time)| (self.sample_to_time(sample) - time).powi(2))
+                .sum();
+            (sum / self.len as f64).sqrt()
+        } else {
+            0.0
+        };
+        ClockStatus {
+            drift_ppm,
+            residual_ms: residual * 1000.0,
+            observations: self.len,
+            resyncs: self.resyncs,
+            locked,
+        }
+    }
+
+    fn observations(&self) -> impl Iterator<Item = &(u64, f64)> {
+        self.history[..self.len].iter()
+    }
+
+    /// Least-squares line through the history; the nominal rate until two points exist
+    fn refit(&mut self) {
+        let count = self.len as f64;
+        // Center on the newest observation so the sums stay small
+        let (origin_sample, origin_time) = self.history[(self.next + CLOCK_HISTORY - 1) % CLOCK_HISTORY];
+        let centered = |&(sample, time): &(u64, f64)| (sample as f64 - origin_sample as f64, time - origin_time);
+        let (sum_s, sum_t) = self.observations()
+            .map(centered)
+            .fold((0.0, 0.0), |(s, t), (ds, dt)| (s + ds, t + dt));
+        let (mean_s, mean_t) = (sum_s / count, sum_t / count);
+        let (covariance, variance) = self.observations()
+            .map(centered)
+            .fold((0.0, 0.0), |(c, v), (ds, dt)| (c + (ds - mean_s) * (dt - mean_t), v + (ds - mean_s).powi(2)));
+
+        if self.len >= 2 && variance > 0.0 && covariance > 0.0 {
+            self.seconds_per_sample = covariance / variance;
+        }
+        self.offset = origin_time + mean_t - (origin_sample as f64 + mean_s) * self.seconds_per_sample;
+    }
+}
@@ -0,0 +1,233 @@
+/**
+ * AWE Player - Block Deadline Watchdog
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * The worklet callback has one block's worth of time (128 frames at
+ * 44.1 kHz is 2.9 ms) to render that block; past it, the output glitches.
+ * Quality scaling reacts to the processing times the host reports, which
+ * arrive late and smoothed. The watchdog times each render_stereo_block
+ * call itself and warns the host as soon as a block comes close to its
+ * deadline - by default at 70% of it - before anything is heard.
+ *
+ * Warnings are rate-limited to one per second of audio; each carries the
+ * block that triggered it and how many near misses and overruns happened
+ * since the previous one. Optionally the measured load also drives quality
+ * scaling, for hosts that do not report processing times.
+ *
+ * In the browser the clock is performance.now() where the worklet scope
+ * has it and Date.now() otherwise; with the latter's 1 ms resolution,
+ * single loads are coarse and only the counts over time are meaningful.
+ */
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use serde::Serialize;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+/// Share of the block time that counts as a near miss by default
+pub const DEFAULT_WARN_LOAD: f32 = 0.7;
+/// Lowest and highest warning threshold a host may set
+pub const MIN_WARN_LOAD: f32 = 0.1;
+pub const MAX_WARN_LOAD: f32 = 1.0;
+/// Audio between two warnings
+const WARNING_HOLD_SECONDS: f32 = 1.0;
+
+/// A block that came close to (or past) its deadline (JSON for the warning callback)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct DeadlineWarning {
+    pub elapsed_ms: f32,        // Time the block took to render
+    pub budget_ms: f32,         // Time the block plays for
+    pub load: f32,              // elapsed / budget
+    pub frames: usize,
+    pub active_voices: usize,
+    pub near_misses: u32,       // Blocks at or over the threshold since the last warning, this one included
+    pub overruns: u32,          // Of those, blocks past the deadline
+}
+
+/// Watchdog settings and counters (get_deadline_status_global)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct DeadlineStatus {
+    pub enabled: bool,
+    pub warn_load: f32,
+    pub scale_quality: bool,    // Measured load also drives quality scaling
+    pub last_load: f32,
+    pub peak_load: f32,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub blocks: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub near_misses: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub overruns: u64,
+    pub warnings: u32,
+}
+
+/// Per-block load tracking and warning hold-off
+#[derive(Debug, Clone)]
+pub struct DeadlineWatchdog {
+    sample_rate: f32,
+    enabled: bool,
+    warn_load: f32,
+    scale_quality: bool,
+    last_load: f32,
+    peak_load: f32,
+    blocks: u64,
+    near_misses: u64,
+    overruns: u64,
+    warnings: u32,
+    pending_near_misses: u32,   // Since the last warning
+    pending_overruns: u32,
+    hold_frames: u64,           // Frames until the next warning may go out
+}
+
+impl DeadlineWatchdog {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate: sample_rate.max(1.0),
+            enabled: true,
+            warn_load: DEFAULT_WARN_LOAD,
+            scale_quality: false,
+            last_load: 0.0,
+            peak_load: 0.0,
+            blocks: 0,
+            near_misses: 0,
+            overruns: 0,
+            warnings: 0,
+            pending_near_misses: 0,
+            pending_overruns: 0,
+            hold_frames: 0,
+        }
+    }
+
+    /// Threshold is clamped to MIN_WARN_LOAD-MAX_WARN_LOAD (non-finite keeps the default);
+    /// counters start over
+    pub fn configure(&mut self, enabled: bool, warn_load: f32, scale_quality: bool) {
+        let sample_rate = self.sample_rate;
+        *self = Self::new(sample_rate);
+        self.enabled = enabled;
+        self.scale_quality = scale_quality;
+        if warn_load.is_finite() {
+            self.warn_load = warn_load.clamp(MIN_WARN_LOAD, MAX_WARN_LOAD);
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn scales_quality(&self) -> bool {
+        self.enabled && self.scale_quality
+    }
+
+    /// Record one block; returns a warning when it was a near miss and none went out lately
+    pub fn observe(&mut self, elapsed_ms: f64, frames: usize, active_voices: usize) -> Option<DeadlineWarning> {
+        if !self.enabled || frames == 0 || !elapsed_ms.is_finite() {
+            return None;
+        }
+        let budget_ms = frames as f32 / self.sample_rate * 1000.0;
+        let elapsed_ms = elapsed_ms.max(0.0) as f32;
+        let load = elapsed_ms / budget_ms;
+        self.last_load = load;
+        self.peak_load = self.peak_load.max(load);
+        self.blocks += 1;
+        let holding = self.hold_frames > 0;
+        self.hold_frames = self.hold_frames.saturating_sub(frames as u64);
+        if load < self.warn_load {
+            return None;
+        }
+
+        let overrun = load >= 1.0;
+        self.near_misses += 1;
+        self.overruns += overrun as u64;
+        self.pending_near_misses = self.pending_near_misses.saturating_add(1);
+        self.pending_overruns = self.pending_overruns.saturating_add(overrun as u32);
+        if holding {
+            return None;
+        }
+        let warning = DeadlineWarning {
+            elapsed_ms,
+            budget_ms,
+            load,
+            frames,
+            active_voices,
+            near_misses: std::mem::take(&mut self.pending_near_misses),
+            overruns: std::mem::take(&mut self.pending_overruns),
+        };
+        self.warnings = self.warnings.saturating_add(1);
+        self.hold_frames = (self.sample_rate * WARNING_HOLD_SECONDS) as u64;
+        Some(warning)
+    }
+
+    pub fn last_load(&self) -> f32 {
+        self.last_load
+    }
+
+    pub fn status(&self) -> DeadlineStatus {
+        DeadlineStatus {
+            enabled: self.enabled,
+            warn_load: self.warn_load,
+            scale_quality: self.scale_quality,
+            last_load: self.last_load,
+            peak_load: self.peak_load,
+            blocks: self.blocks,
+            near_misses: self.near_misses,
+            overruns: self.overruns,
+            warnings: self.warnings,
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+extern "C" {
+    // Worklet scopes may lack `performance`; the error is caught rather than thrown
+    #[wasm_bindgen(js_namespace = performance, js_name = now, catch)]
+    fn performance_now() -> Result<f64, JsValue>;
+}
+
+#[cfg(target_arch = "wasm32")]
+thread_local! {
+    static HAS_PERFORMANCE: std::cell::Cell<bool> = const { std::cell::Cell::new(true) };
+}
+
+/// Milliseconds on a monotonic clock, for timing blocks
+#[cfg(target_arch = "wasm32")]
+pub fn now_ms() -> f64 {
+    if HAS_PERFORMANCE.with(|available| available.get()) {
+        match performance_now() {
+            Ok(now) => return now,
+            Err(_) => HAS_PERFORMANCE.with(|available| available.set(false)),
+        }
+    }
+    js_sys::Date::now()
+}
+
+/// Milliseconds on a monotonic clock, for timing blocks
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now_ms() -> f64 {
+    static START: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+    START.get_or_init(std::time::Instant::now).elapsed().as_secs_f64() * 1000.0
+}
+
+pub type DeadlineListener = Rc<dyn Fn(&DeadlineWarning)>;
+
+thread_local! {
+    static LISTENER: RefCell<Option<DeadlineListener>> = const { RefCell::new(None) };
+}
+
+/// Install (or with None, remove) the deadline warning listener
+pub fn set_listener(listener: Option<DeadlineListener>) {
+    LISTENER.with(|slot| *slot.borrow_mut() = listener);
+}
+
+/// Deliver a warning to the listener, if one is registered
+pub fn emit(warning: &DeadlineWarning) {
+    let listener = LISTENER.with(|slot| slot.borrow().clone());
+    if let Some(listener) = listener {
+        listener(warning);
+    }
+}
@@ -0,0 +1,60 @@
+/**
+ * AWE Player - Export Dithering
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Rounding a float render to 16 bits turns the quantization error into
+ * distortion that follows the signal: a reverb tail fading below a few
+ * LSBs breaks up into grainy, gated steps instead of fading smoothly.
+ * TPDF dither adds two uniform random values (±1 LSB triangular noise)
+ * before rounding, which makes the error a constant, signal-independent
+ * hiss. Optional first-order noise shaping feeds each sample's error back
+ * into the next one, moving that hiss towards high frequencies where it
+ * is harder to hear. The generator is seeded, so the same render always
+ * encodes to the same bytes.
+ */
+
+/// Channels with their own noise-shaping history (more are shaped with the last)
+const MAX_CHANNELS: usize = 8;
+
+const SEED: u32 = 0x2F6B_4C19;
+
+/// TPDF ditherer for one export
+#[derive(Debug, Clone)]
+pub struct Dither {
+    rng: u32,
+    noise_shaping: bool,
+    errors: [f32; MAX_CHANNELS],  // Last quantization error per channel, in LSBs
+}
+
+impl Dither {
+    pub fn new(noise_shaping: bool) -> Self {
+        Self { rng: SEED, noise_shaping, errors: [0.0; MAX_CHANNELS] }
+    }
+
+    pub fn noise_shaping(&self) -> bool {
+        self.noise_shaping
+    }
+
+    /// Uniform value in -0.5..0.5 (xorshift32)
+    fn uniform(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        self.rng as f32 / u32::MAX as f32 - 0.5
+    }
+
+    /// Dithered `bits`-bit value of `sample` (-1.0..1.0) on `channel`
+    pub fn quantize(&mut self, sample: f32, channel: usize, bits: u32) -> i32 {
+        let full_scale = ((1i32 << (bits - 1)) - 1) as f32;
+        let channel = channel.min(MAX_CHANNELS - 1);
+        let feedback = if self.noise_shaping { self.errors[channel] } else { 0.0 };
+        let target = sample.clamp(-1.0, 1.0) * full_scale - feedback;
+        let noise = self.uniform() + self.uniform();
+        let value = (target + noise).round().clamp(-full_scale, full_scale);
+        if self.noise_shaping {
+            // Clipped peaks would otherwise feed back a runaway error
+            self.errors[channel] = (value - target).clamp(-2.0, 2.0);
+        }
+        value as i32
+    }
+}
@@ -0,0 +1,151 @@
+/**
+ * AWE Player - Audio Export Formats
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Single entry point for turning rendered float buffers into a file, used
+ * by output capture and the encode_audio_buffer export. WAV is always
+ * available; FLAC and Ogg FLAC ("oga-flac") are compiled in with the "flac"
+ * and "ogg" features to keep the default WASM binary small. There is no
+ * lossy encoder: Ogg FLAC is as large as FLAC, so neither makes downloads
+ * much smaller than FLAC's lossless compression does. ExportOptions carries
+ * the settings that don't change the format itself: loudness
+ * normalization, applied to the float samples first, and dithering.
+ */
+
+use serde::{Deserialize, Serialize};
+use super::dither::Dither;
+use super::normalize::{normalize, DEFAULT_TRUE_PEAK_CEILING_DBTP};
+use super::wav::{WavFormat, WavSpec, WavWriter};
+
+/// Output file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Wav(WavFormat),
+    #[cfg(feature = "flac")]
+    Flac { bits_per_sample: u32 },
+    #[cfg(feature = "ogg")]
+    OggFlac { bits_per_sample: u32 },
+}
+
+impl ExportFormat {
+    /// WAV names ("pcm16", "pcm24", "float32") plus "flac"/"flac24" and "oga-flac"/"oga-flac24" when compiled in
+    pub fn from_name(name: &str) -> Option<Self> {
+        if let Some(format) = WavFormat::from_name(name) {
+            return Some(ExportFormat::Wav(format));
+        }
+        match name.to_ascii_lowercase().as_str() {
+            #[cfg(feature = "flac")]
+            "flac" | "flac16" => Some(ExportFormat::Flac { bits_per_sample: 16 }),
+            #[cfg(feature = "flac")]
+            "flac24" => Some(ExportFormat::Flac { bits_per_sample: 24 }),
+            #[cfg(feature = "ogg")]
+            "oga-flac" | "oga-flac16" => Some(ExportFormat::OggFlac { bits_per_sample: 16 }),
+            #[cfg(feature = "ogg")]
+            "oga-flac24" => Some(ExportFormat::OggFlac { bits_per_sample: 24 }),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Wav(format) => format.name(),
+            #[cfg(feature = "flac")]
+            ExportFormat::Flac { bits_per_sample: 24 } => "flac24",
+            #[cfg(feature = "flac")]
+            ExportFormat::Flac { .. } => "flac",
+            #[cfg(feature = "ogg")]
+            ExportFormat::OggFlac { bits_per_sample: 24 } => "oga-flac24",
+            #[cfg(feature = "ogg")]
+            ExportFormat::OggFlac { .. } => "oga-flac",
+        }
+    }
+
+    pub fn info(&self) -> ExportFormatInfo {
+        ExportFormatInfo { name: self.name().to_string(), mime_type: self.mime_type().to_string() }
+    }
+
+    /// MIME type for Blob downloads
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Wav(_) => "audio/wav",
+            #[cfg(feature = "flac")]
+            ExportFormat::Flac { .. } => "audio/flac",
+            #[cfg(feature = "ogg")]
+            ExportFormat::OggFlac { .. } => "audio/ogg; codecs=flac",
+        }
+    }
+}
+
+/// Entry of get_export_formats
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ExportFormatInfo {
+    pub name: String,
+    pub mime_type: String,
+}
+
+/// Encoder settings beyond the format (JSON for the options exports; missing fields are off)
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ExportOptions {
+    pub dither: bool,           // TPDF dither when writing 16-bit WAV
+    pub noise_shaping: bool,    // Shape the dither noise towards high frequencies (needs dither)
+    pub target_lufs: Option<f32>,               // Normalize integrated loudness (mono/stereo)
+    pub true_peak_ceiling_dbtp: Option<f32>,    // Limit peaks when normalizing (None = -1 dBTP)
+}
+
+impl ExportOptions {
+    /// Options from JSON; None if it doesn't parse
+    pub fn from_json(json: &str) -> Option<Self> {
+        serde_json::from_str(json).ok()
+    }
+}
+
+/// Format names accepted by ExportFormat::from_name in this build
+pub fn available_formats() -> Vec<&'static str> {
+    let mut formats = vec!["pcm16", "pcm24", "float32"];
+    if cfg!(feature = "flac") {
+        formats.extend(["flac", "flac24"]);
+    }
+    if cfg!(feature = "ogg") {
+        formats.extend(["oga-flac", "oga-flac24"]);
+    }
+    formats
+}
+
+/// Encode a complete interleaved buffer in `format`
+pub fn encode(format: ExportFormat, sample_rate: u32, channels: u16, samples: &[f32]) -> Vec<u8> {
+    encode_with_options(format, sample_rate, channels, samples, ExportOptions::default())
+}
+
+/// Encode a complete interleaved buffer in `format` with `options`
+pub fn encode_with_options(format: ExportFormat, sample_rate: u32, channels: u16, samples: &[f32], options: ExportOptions) -> Vec<u8> {
+    if let Some(target_lufs) = options.target_lufs {
+        let mut normalized = samples.to_vec();
+        let ceiling = options.true_peak_ceiling_dbtp.unwrap_or(DEFAULT_TRUE_PEAK_CEILING_DBTP);
+        normalize(&mut normalized, channels, sample_rate as f32, target_lufs, ceiling);
+        let options = ExportOptions { target_lufs: None, ..options };
+        return encode_with_options(format, sample_rate, channels, &normalized, options);
+    }
+    match format {
+        ExportFormat::Wav(format) => {
+            let spec = WavSpec::new(sample_rate, channels, format);
+            let mut writer = WavWriter::with_capacity(spec, samples.len() / spec.channels as usize);
+            if options.dither {
+                writer.set_dither(Some(Dither::new(options.noise_shaping)));
+            }
+            writer.write_samples(samples);
+            writer.finish()
+        }
+        #[cfg(feature = "flac")]
+        ExportFormat::Flac { bits_per_sample } => {
+            super::flac::encode_flac(super::flac::FlacSpec::new(sample_rate, channels, bits_per_sample), samples)
+        }
+        #[cfg(feature = "ogg")]
+        ExportFormat::OggFlac { bits_per_sample } => {
+            super::ogg::encode_ogg_flac(super::flac::FlacSpec::new(sample_rate, channels, bits_per_sample), samples)
+        }
+    }
+}
@@ -0,0 +1,126 @@
+/**
+ * AWE Player - Transport Fades
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Starting playback in the middle of a sustained passage, or stopping it
+ * while notes ring, steps the output from silence to full level (or back)
+ * in one sample and pops. play_with_fade/stop_with_fade ramp the master bus
+ * linearly over the requested time instead, one step per sample. A fade-out
+ * ends in silence: the sequencer is stopped, the remaining voices are faded
+ * away, and the bus returns to unity once nothing is sounding. A fade started
+ * while another is running continues from the current level.
+ */
+
+use serde::Serialize;
+
+/// Where the master fade stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum FadeState {
+    Idle,           // Unity gain, no ramp
+    FadingIn,
+    FadingOut,
+    Silent,         // Fade-out finished, waiting for the voices to end
+}
+
+/// Fade state and level (JSON in pipeline stats)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FadeStatus {
+    pub state: FadeState,
+    pub gain: f32,
+    pub remaining_samples: u32,
+}
+
+/// Linear master-bus ramp driven by the transport
+#[derive(Debug, Clone)]
+pub struct MasterFade {
+    sample_rate: f32,
+    state: FadeState,
+    gain: f32,
+    step: f32,
+    remaining: u32,
+}
+
+impl MasterFade {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate,
+            state: FadeState::Idle,
+            gain: 1.0,
+            step: 0.0,
+            remaining: 0,
+        }
+    }
+
+    /// Ramp up to unity over `ms`; from silence unless a fade is already running
+    pub fn fade_in(&mut self, ms: f32) {
+        if self.state == FadeState::Idle {
+            self.gain = 0.0;
+        }
+        self.ramp_to(FadeState::FadingIn, 1.0, ms);
+    }
+
+    /// Ramp down to silence over `ms`
+    pub fn fade_out(&mut self, ms: f32) {
+        if self.state == FadeState::Silent {
+            return;
+        }
+        self.ramp_to(FadeState::FadingOut, 0.0, ms);
+    }
+
+    /// Drop any fade and return to unity
+    pub fn cancel(&mut self) {
+        *self = Self::new(self.sample_rate);
+    }
+
+    fn ramp_to(&mut self, state: FadeState, target: f32, ms: f32) {
+        let samples = if ms.is_finite() { (ms.max(0.0) * 0.001 * self.sample_rate).round() as u32 } else { 0 };
+        self.state = state;
+        self.remaining = samples.max(1);
+        self.step = (target - self.gain) / self.remaining as f32;
+    }
+
+    /// Gain for the next sample; true the one sample a fade-out reaches silence
+    /// `voices_active` lets a finished fade-out hand back to unity once quiet
+    pub fn next(&mut self, voices_active: bool) -> (f32, bool) {
+        match self.state {
+            FadeState::Idle => (1.0, false),
+            FadeState::Silent => {
+                if !voices_active {
+                    self.cancel();
+                }
+                (0.0, false)
+            }
+            FadeState::FadingIn | FadeState::FadingOut => {
+                self.remaining -= 1;
+                self.gain += self.step;
+                if self.remaining > 0 {
+                    return (self.gain, false);
+                }
+                let finished_out = self.state == FadeState::FadingOut;
+                if finished_out {
+                    self.state = FadeState::Silent;
+                    self.gain = 0.0;
+                } else {
+                    self.cancel();
+                }
+                (self.gain, finished_out)
+            }
+        }
+    }
+
+    pub fn state(&self) -> FadeState {
+        self.state
+    }
+
+    pub fn status(&self) -> FadeStatus {
+        FadeStatus {
+            state: self.state,
+            gain: self.gain,
+            remaining_samples: self.remaining,
+        }
+    }
+}
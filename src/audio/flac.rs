@@ -0,0 +1,508 @@
+/**
+ * AWE Player - FLAC Encoder (feature "flac")
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Lossless compression of rendered float buffers, about half the size of
+ * the equivalent PCM WAV. Encoding is done in pure Rust so the WASM module
+ * needs no codec library:
+ *
+ * - Fixed blocksize of 4096 frames (the last block may be shorter)
+ * - Stereo decorrelation: independent, left/side, right/side or mid/side,
+ *   whichever codes smallest
+ * - Subframes: constant, fixed linear predictor order 0-4 or verbatim
+ * - Rice-coded residuals with per-partition parameters
+ *
+ * The STREAMINFO MD5 signature is left zeroed ("unknown"), which the format
+ * allows; decoders then skip the checksum comparison.
+ */
+
+use super::wav::quantize;
+
+/// Frames per FLAC block
+pub const FLAC_BLOCK_SIZE: usize = 4096;
+
+/// Size of the STREAMINFO metadata block body
+pub const STREAMINFO_LEN: usize = 34;
+
+/// Largest Rice parameter for coding method 0 (15 is the escape code)
+const MAX_RICE_PARAMETER: u32 = 14;
+const MAX_PARTITION_ORDER: u32 = 8;
+const MAX_FIXED_ORDER: usize = 4;
+
+/// Stream layout of a FLAC file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlacSpec {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub bits_per_sample: u32,   // 16 or 24
+}
+
+impl FlacSpec {
+    /// Channels are clamped to 1-8 and bit depth to 16 or 24
+    pub fn new(sample_rate: u32, channels: u16, bits_per_sample: u32) -> Self {
+        Self {
+            sample_rate: sample_rate.clamp(1, 655_350),
+            channels: channels.clamp(1, 8),
+            bits_per_sample: if bits_per_sample > 16 { 24 } else { 16 },
+        }
+    }
+}
+
+/// Encoded stream split into its parts (Ogg FLAC packs each frame into a packet)
+#[derive(Debug, Clone)]
+pub struct FlacStream {
+    pub streaminfo: [u8; STREAMINFO_LEN],
+    pub frames: Vec<FlacFrame>,
+}
+
+/// One encoded audio frame
+#[derive(Debug, Clone)]
+pub struct FlacFrame {
+    pub bytes: Vec<u8>,
+    pub frames: usize,        // Sample frames in this block
+}
+
+/// Encode a complete interleaved buffer as a .flac file
+pub fn encode_flac(spec: FlacSpec, samples: &[f32]) -> Vec<u8> {
+    let stream = encode_stream(spec, samples);
+    let frame_bytes: usize = stream.frames.iter().map(|frame| frame.bytes.len()).sum();
+
+    let mut out = Vec::with_capacity(8 + STREAMINFO_LEN + frame_bytes);
+    out.extend_from_slice(b"fLaC");
+    out.extend_from_slice(&metadata_block_header(true, 0, STREAMINFO_LEN));
+    out.extend_from_slice(&stream.streaminfo);
+    for frame in &stream.frames {
+        out.extend_from_slice(&frame.bytes);
+    }
+    out
+}
+
+/// Encode a complete interleaved buffer into STREAMINFO + frames
+/// A trailing partial frame is dropped, as in the WAV writer
+pub fn encode_stream(spec: FlacSpec, samples: &[f32]) -> FlacStream {
+    let channels = spec.channels as usize;
+    let total_frames = samples.len() / channels;
+
+    let mut frames = Vec::with_capacity(total_frames.div_ceil(FLAC_BLOCK_SIZE));
+    let mut block: Vec<Vec<i32>> = vec![Vec::with_capacity(FLAC_BLOCK_SIZE); channels];
+    for (number, chunk) in samples[..total_frames * channels].chunks(FLAC_BLOCK_SIZE * channels).enumerate() {
+        for (channel, data) in block.iter_mut().enumerate() {
+            data.clear();
+            data.extend(chunk.iter().skip(channel).step_by(channels).map(|&s| quantize(s, spec.bits_per_sample)));
+        }
+        frames.push(FlacFrame {
+            bytes: encode_frame(&spec, number as u32, &block),
+            frames: chunk.len() / channels,
+        });
+    }
+
+    let min_frame = frames.iter().map(|f| f.bytes.len()).min().unwrap_or(0);
+    let max_frame = frames.iter().map(|f| f.bytes.len()).max().unwrap_or(0);
+    FlacStream {
+        streaminfo: streaminfo(&spec, total_frames as u64, min_frame, max_frame),
+        frames,
+    }
+}
+
+/// 4-byte metadata block header: last-block flag, 7-bit type, 24-bit length
+pub fn metadata_block_header(is_last: bool, block_type: u8, length: usize) -> [u8; 4] {
+    let length = length as u32;
+    [
+        ((is_last as u8) << 7) | (block_type & 0x7F),
+        (length >> 16) as u8,
+        (length >> 8) as u8,
+        length as u8,
+    ]
+}
+
+fn streaminfo(spec: &FlacSpec, total_frames: u64, min_frame: usize, max_frame: usize) -> [u8; STREAMINFO_LEN] {
+    // Block sizes below 16 are invalid in STREAMINFO even for tiny streams
+    let block_size = (total_frames as usize).clamp(16, FLAC_BLOCK_SIZE) as u64;
+    let mut writer = BitWriter::with_capacity(STREAMINFO_LEN);
+    writer.write(block_size, 16);                         // Minimum block size
+    writer.write(block_size, 16);                         // Maximum block size
+    writer.write(min_frame as u64, 24);
+    writer.write(max_frame as u64, 24);
+    writer.write(spec.sample_rate as u64, 20);
+    writer.write(spec.channels as u64 - 1, 3);
+    writer.write(spec.bits_per_sample as u64 - 1, 5);
+    writer.write(total_frames, 36);
+    writer.write(0, 64);                                  // MD5 unknown
+    writer.write(0, 64);
+
+    let mut info = [0u8; STREAMINFO_LEN];
+    info.copy_from_slice(&writer.into_bytes());
+    info
+}
+
+// ===== Frames =====
+
+/// Samples of one subframe with their bit depth and coding plan
+type CodedChannel = (Vec<i32>, u32, Subframe);
+
+/// Channel assignment of a stereo frame (FLAC frame header values 8-10)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StereoMode {
+    Independent,
+    LeftSide,
+    RightSide,
+    MidSide,
+}
+
+fn encode_frame(spec: &FlacSpec, number: u32, block: &[Vec<i32>]) -> Vec<u8> {
+    let block_size = block[0].len();
+    let bps = spec.bits_per_sample;
+    let mut writer = BitWriter::with_capacity(block_size * block.len() * bps as usize / 8 + 32);
+
+    // Choose channel decorrelation and the subframe coding for each channel
+    let (assignment, subframes): (u64, Vec<CodedChannel>) = if block.len() == 2 {
+        let (mode, left, right) = choose_stereo(&block[0], &block[1], bps);
+        let code = match mode {
+            StereoMode::Independent => 1,
+            StereoMode::LeftSide => 8,
+            StereoMode::RightSide => 9,
+            StereoMode::MidSide => 10,
+        };
+        (code, vec![left, right])
+    } else {
+        let subframes = block
+            .iter()
+            .map(|data| {
+                let plan = plan_subframe(data, bps);
+                (data.clone(), bps, plan)
+            })
+            .collect();
+        (block.len() as u64 - 1, subframes)
+    };
+
+    // Frame header
+    let (block_code, block_extra) = block_size_code(block_size);
+    let (rate_code, rate_extra) = sample_rate_code(spec.sample_rate);
+    writer.write(0xFFF8, 16);                 // Sync code, fixed-blocksize stream
+    writer.write(block_code, 4);
+    writer.write(rate_code, 4);
+    writer.write(assignment, 4);
+    writer.write(if bps == 24 { 0b110 } else { 0b100 }, 3);
+    writer.write(0, 1);
+    write_utf8_number(&mut writer, number);
+    if let Some((value, bits)) = block_extra {
+        writer.write(value, bits);
+    }
+    if let Some((value, bits)) = rate_extra {
+        writer.write(value, bits);
+    }
+    let crc = crc8(writer.bytes());
+    writer.write(crc as u64, 8);
+
+    for (data, bits, plan) in &subframes {
+        write_subframe(&mut writer, data, *bits, plan);
+    }
+
+    writer.align();
+    let crc = crc16(writer.bytes());
+    writer.write(crc as u64, 16);
+    writer.into_bytes()
+}
+
+/// Try every stereo decorrelation and keep the cheapest
+/// Returns (mode, first channel, second channel) with their sample size and plan
+fn choose_stereo(left: &[i32], right: &[i32], bps: u32) -> (StereoMode, CodedChannel, CodedChannel) {
+    let side: Vec<i32> = left.iter().zip(right).map(|(&l, &r)| l - r).collect();
+    let mid: Vec<i32> = left.iter().zip(right).map(|(&l, &r)| (l + r) >> 1).collect();
+
+    let left_plan = plan_subframe(left, bps);
+    let right_plan = plan_subframe(right, bps);
+    let side_plan = plan_subframe(&side, bps + 1);
+    let mid_plan = plan_subframe(&mid, bps);
+
+    let costs = [
+        (StereoMode::Independent, left_plan.bits + right_plan.bits),
+        (StereoMode::LeftSide, left_plan.bits + side_plan.bits),
+        (StereoMode::RightSide, side_plan.bits + right_plan.bits),
+        (StereoMode::MidSide, mid_plan.bits + side_plan.bits),
+    ];
+    let mode = costs.iter().min_by_key(|(_, bits)| *bits).map(|(mode, _)| *mode).unwrap_or(StereoMode::Independent);
+
+    match mode {
+        StereoMode::Independent => (mode, (left.to_vec(), bps, left_plan), (right.to_vec(), bps, right_plan)),
+        StereoMode::LeftSide => (mode, (left.to_vec(), bps, left_plan), (side, bps + 1, side_plan)),
+        StereoMode::RightSide => (mode, (side, bps + 1, side_plan), (right.to_vec(), bps, right_plan)),
+        StereoMode::MidSide => (mode, (mid, bps, mid_plan), (side, bps + 1, side_plan)),
+    }
+}
+
+/// Block size code and the optional explicit size that follows the frame number
+fn block_size_code(block_size: usize) -> (u64, Option<(u64, u32)>) {
+    match block_size {
+        192 => (1, None),
+        576 => (2, None),
+        1152 => (3, None),
+        2304 => (4, None),
+        4608 => (5, None),
+        256 => (8, None),
+        512 => (9, None),
+        1024 => (10, None),
+        2048 => (11, None),
+        4096 => (12, None),
+        8192 => (13, None),
+        16384 => (14, None),
+        32768 => (15, None),
+        n if n <= 256 => (6, Some((n as u64 - 1, 8))),
+        n => (7, Some((n as u64 - 1, 16))),
+    }
+}
+
+/// Sample rate code and the optional explicit rate that follows the block size
+fn sample_rate_code(sample_rate: u32) -> (u64, Option<(u64, u32)>) {
+    match sample_rate {
+        88200 => (1, None),
+        176400 => (2, None),
+        192000 => (3, None),
+        8000 => (4, None),
+        16000 => (5, None),
+        22050 => (6, None),
+        24000 => (7, None),
+        32000 => (8, None),
+        44100 => (9, None),
+        48000 => (10, None),
+        96000 => (11, None),
+        rate if rate <= 0xFFFF => (13, Some((rate as u64, 16))),
+        rate if rate % 10 == 0 && rate / 10 <= 0xFFFF => (14, Some((rate as u64 / 10, 16))),
+        _ => (0, None), // Taken from STREAMINFO
+    }
+}
+
+/// Frame number in the UTF-8-like variable length coding (up to 31 bits)
+fn write_utf8_number(writer: &mut BitWriter, number: u32) {
+    let value = number as u64;
+    if value < 0x80 {
+        writer.write(value, 8);
+        return;
+    }
+    let continuation_bytes = match value {
+        0..=0x7FF => 1,
+        0x800..=0xFFFF => 2,
+        0x1_0000..=0x1F_FFFF => 3,
+        0x20_0000..=0x3FF_FFFF => 4,
+        _ => 5,
+    };
+    let lead_marker = (0xFF00u64 >> (continuation_bytes + 1)) & 0xFF;
+    writer.write(lead_marker | (value >> (6 * continuation_bytes)), 8);
+    for index in (0..continuation_bytes).rev() {
+        writer.write(0x80 | ((value >> (6 * index)) & 0x3F), 8);
+    }
+}
+
+// ===== Subframes =====
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SubframeKind {
+    Constant,
+    Verbatim,
+    Fixed { order: usize, partition_order: u32, parameters: Vec<u32> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Subframe {
+    kind: SubframeKind,
+    bits: u64,        // Estimated coded size, used to compare alternatives
+}
+
+fn plan_subframe(data: &[i32], bps: u32) -> Subframe {
+    let header_bits = 8;
+    if data.iter().all(|&s| s == data[0]) {
+        return Subframe { kind: SubframeKind::Constant, bits: header_bits + bps as u64 };
+    }
+
+    let mut best = Subframe { kind: SubframeKind::Verbatim, bits: header_bits + data.len() as u64 * bps as u64 };
+    let mut residual = Vec::with_capacity(data.len());
+    for order in 0..=MAX_FIXED_ORDER.min(data.len().saturating_sub(1)) {
+        fixed_residual(data, order, &mut residual);
+        let Some((partition_order, parameters, rice_bits)) = plan_rice(&residual, data.len(), order) else {
+            continue;
+        };
+        let bits = header_bits + order as u64 * bps as u64 + 6 + rice_bits;
+        if bits < best.bits {
+            best = Subframe { kind: SubframeKind::Fixed { order, partition_order, parameters }, bits };
+        }
+    }
+    best
+}
+
+/// Residual of the fixed polynomial predictor of `order` (skips the warm-up samples)
+fn fixed_residual(data: &[i32], order: usize, residual: &mut Vec<i32>) {
+    residual.clear();
+    residual.extend((order..data.len()).map(|i| match order {
+        0 => data[i],
+        1 => data[i] - data[i - 1],
+        2 => data[i] - 2 * data[i - 1] + data[i - 2],
+        3 => data[i] - 3 * data[i - 1] + 3 * data[i - 2] - data[i - 3],
+        _ => data[i] - 4 * data[i - 1] + 6 * data[i - 2] - 4 * data[i - 3] + data[i - 4],
+    }));
+}
+
+fn zigzag(value: i32) -> u64 {
+    (((value as i64) << 1) ^ ((value as i64) >> 63)) as u64
+}
+
+/// Pick the partition order and per-partition Rice parameters
+/// Returns None if no partitioning is valid for this block size/order
+fn plan_rice(residual: &[i32], block_size: usize, order: usize) -> Option<(u32, Vec<u32>, u64)> {
+    let mut best: Option<(u32, Vec<u32>, u64)> = None;
+    for partition_order in 0..=MAX_PARTITION_ORDER {
+        let partitions = 1usize << partition_order;
+        if !block_size.is_multiple_of(partitions) || block_size / partitions <= order {
+            break;
+        }
+        let partition_len = block_size / partitions;
+
+        let mut parameters = Vec::with_capacity(partitions);
+        let mut bits = 0u64;
+        let mut start = 0;
+        for partition in 0..partitions {
+            let len = if partition == 0 { partition_len - order } else { partition_len };
+            let sum: u64 = residual[start..start + len].iter().map(|&r| zigzag(r)).sum();
+            let (parameter, partition_bits) = best_rice_parameter(sum, len as u64);
+            parameters.push(parameter);
+            bits += 4 + partition_bits;
+            start += len;
+        }
+        if best.as_ref().is_none_or(|(_, _, best_bits)| bits < *best_bits) {
+            best = Some((partition_order, parameters, bits));
+        }
+    }
+    best
+}
+
+/// Estimate (sum of quotients + stop bits + low bits) for each parameter and keep the smallest
+fn best_rice_parameter(sum: u64, count: u64) -> (u32, u64) {
+    (0..=MAX_RICE_PARAMETER)
+        .map(|k| (k, (sum >> k) + count * (k as u64 + 1)))
+        .min_by_key(|(_, bits)| *bits)
+        .unwrap_or((0, 0))
+}
+
+fn write_subframe(writer: &mut BitWriter, data: &[i32], bps: u32, plan: &Subframe) {
+    match &plan.kind {
+        SubframeKind::Constant => {
+            writer.write(0b0000_0000, 8);
+            writer.write_signed(data[0], bps);
+        }
+        SubframeKind::Verbatim => {
+            writer.write(0b0000_0010, 8);
+            for &sample in data {
+                writer.write_signed(sample, bps);
+            }
+        }
+        SubframeKind::Fixed { order, partition_order, parameters } => {
+            writer.write((0b0000_1000 | *order as u64) << 1, 8);
+            for &sample in &data[..*order] {
+                writer.write_signed(sample, bps);
+            }
+
+            let mut residual = Vec::with_capacity(data.len());
+            fixed_residual(data, *order, &mut residual);
+            writer.write(0b00, 2);                // Rice coding, 4-bit parameters
+            writer.write(*partition_order as u64, 4);
+            let partition_len = data.len() >> partition_order;
+            let mut start = 0;
+            for (partition, &parameter) in parameters.iter().enumerate() {
+                let len = if partition == 0 { partition_len - order } else { partition_len };
+                writer.write(parameter as u64, 4);
+                for &value in &residual[start..start + len] {
+                    let value = zigzag(value);
+                    writer.write_unary(value >> parameter);
+                    writer.write(value, parameter);
+                }
+                start += len;
+            }
+        }
+    }
+}
+
+// ===== Bit output and checksums =====
+
+/// MSB-first bit writer
+#[derive(Debug, Clone)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    accumulator: u64,
+    pending: u32,         // Bits in the accumulator not yet flushed (< 8)
+}
+
+impl BitWriter {
+    fn with_capacity(bytes: usize) -> Self {
+        Self { bytes: Vec::with_capacity(bytes), accumulator: 0, pending: 0 }
+    }
+
+    /// Write the low `bits` bits of `value` (bits <= 32 per call)
+    fn write(&mut self, value: u64, bits: u32) {
+        if bits > 32 {
+            self.write(value >> 32, bits - 32);
+            self.write(value & 0xFFFF_FFFF, 32);
+            return;
+        }
+        if bits == 0 {
+            return;
+        }
+        self.accumulator = (self.accumulator << bits) | (value & ((1u64 << bits) - 1));
+        self.pending += bits;
+        while self.pending >= 8 {
+            self.pending -= 8;
+            self.bytes.push((self.accumulator >> self.pending) as u8);
+        }
+        self.accumulator &= (1u64 << self.pending) - 1;
+    }
+
+    fn write_signed(&mut self, value: i32, bits: u32) {
+        self.write(value as i64 as u64, bits);
+    }
+
+    /// `count` zero bits followed by a one
+    fn write_unary(&mut self, mut count: u64) {
+        while count >= 32 {
+            self.write(0, 32);
+            count -= 32;
+        }
+        self.write(1, count as u32 + 1);
+    }
+
+    /// Zero-pad to the next byte boundary
+    fn align(&mut self) {
+        if self.pending > 0 {
+            self.write(0, 8 - self.pending);
+        }
+    }
+
+    /// Completed bytes (excludes bits still pending)
+    fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn into_bytes(mut self) -> Vec<u8> {
+        self.align();
+        self.bytes
+    }
+}
+
+/// CRC-8, polynomial x^8 + x^2 + x + 1 (frame header)
+fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |mut crc, &byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+        }
+        crc
+    })
+}
+
+/// CRC-16, polynomial x^16 + x^15 + x^2 + 1 (whole frame)
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |mut crc, &byte| {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x8005 } else { crc << 1 };
+        }
+        crc
+    })
+}
@@ -0,0 +1,281 @@
+/**
+ * AWE Player - Loudness Metering
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * ITU-R BS.1770 / EBU R128 metering of the master output, so renders can be
+ * checked (and later normalized) against streaming loudness targets. Each
+ * channel passes through the K-weighting pre-filter (high shelf plus high
+ * pass) and its mean square is collected in 100 ms steps:
+ *
+ * - momentary loudness averages the last 400 ms, short-term the last 3 s;
+ * - integrated loudness gates the 400 ms blocks (75% overlap) at -70 LUFS
+ *   absolute, then at 10 LU below the loudness of what passed;
+ * - true peak is the largest sample of the 4x oversampled signal (the
+ *   BS.1770 Annex 2 interpolator), which catches inter-sample overs.
+ *
+ * Gated blocks go into a fixed 0.1 LU histogram instead of a growing list,
+ * so the meter runs on the audio thread indefinitely without allocating.
+ */
+
+use serde::Serialize;
+
+/// Channels metered (mono or stereo master)
+const CHANNELS: usize = 2;
+/// Sub-blocks per momentary (400 ms) and short-term (3 s) window
+const MOMENTARY_BLOCKS: usize = 4;
+const SHORT_TERM_BLOCKS: usize = 30;
+
+/// Absolute gate and relative gate offset (BS.1770-4)
+const ABSOLUTE_GATE_LUFS: f32 = -70.0;
+const RELATIVE_GATE_LU: f32 = -10.0;
+
+/// Integrated-loudness histogram: 0.1 LU bins from the absolute gate up to +5 LUFS
+const HISTOGRAM_STEP_LU: f32 = 0.1;
+const HISTOGRAM_BINS: usize = 751;
+
+/// True-peak interpolator: 4 phases of 12 taps (BS.1770-4 Annex 2)
+const TRUE_PEAK_TAPS: usize = 12;
+const TRUE_PEAK_PHASES: [[f64; TRUE_PEAK_TAPS]; 4] = [
+    [0.001708984375, 0.010986328125, -0.0196533203125, 0.033203125, -0.0594482421875, 0.1373291015625,
+     0.97216796875, -0.102294921875, 0.047607421875, -0.026611328125, 0.014892578125, -0.00830078125],
+    [-0.0291748046875, 0.029296875, -0.0517578125, 0.089111328125, -0.16650390625, 0.465087890625,
+     0.77978515625, -0.2003173828125, 0.1015625, -0.0582275390625, 0.0330810546875, -0.0189208984375],
+    [-0.0189208984375, 0.0330810546875, -0.0582275390625, 0.1015625, -0.2003173828125, 0.77978515625,
+     0.465087890625, -0.16650390625, 0.089111328125, -0.0517578125, 0.029296875, -0.0291748046875],
+    [-0.00830078125, 0.014892578125, -0.026611328125, 0.047607421875, -0.102294921875, 0.97216796875,
+     0.1373291015625, -0.0594482421875, 0.033203125, -0.0196533203125, 0.010986328125, 0.001708984375],
+];
+
+/// Loudness readings (JSON for get_loudness_global); None until enough audio or while silent
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LoudnessReport {
+    pub momentary_lufs: Option<f32>,
+    pub short_term_lufs: Option<f32>,
+    pub integrated_lufs: Option<f32>,
+    pub max_momentary_lufs: Option<f32>,
+    pub true_peak_dbtp: Option<f32>,
+    pub elapsed_seconds: f64,
+}
+
+/// Direct-form I biquad
+#[derive(Debug, Clone, Copy, Default)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    x: [f64; 2],
+    y: [f64; 2],
+}
+
+impl Biquad {
+    fn process(&mut self, input: f64) -> f64 {
+        let output = self.b[0] * input + self.b[1] * self.x[0] + self.b[2] * self.x[1]
+            - self.a[0] * self.y[0] - self.a[1] * self.y[1];
+        self.x = [input, self.x[0]];
+        self.y = [output, self.y[0]];
+        output
+    }
+}
+
+/// K-weighting stages for `sample_rate` (coefficients derived as in BS.1770 for any rate)
+fn k_weighting(sample_rate: f32) -> [Biquad; 2] {
+    let rate = sample_rate as f64;
+
+    // Stage 1: +4 dB high shelf around 1.7 kHz (head diffraction)
+    let (f0, gain_db, q) = (1681.974450955533, 3.999843853973347, 0.7071752369554196);
+    let k = (std::f64::consts::PI * f0 / rate).tan();
+    let vh = 10f64.powf(gain_db / 20.0);
+    let vb = vh.powf(0.4996667741545416);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad {
+        b: [(vh + vb * k / q + k * k) / a0, 2.0 * (k * k - vh) / a0, (vh - vb * k / q + k * k) / a0],
+        a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        ..Biquad::default()
+    };
+
+    // Stage 2: high pass at 38 Hz (RLB weighting)
+    let (f0, q) = (38.13547087602444, 0.5003270373238773);
+    let k = (std::f64::consts::PI * f0 / rate).tan();
+    let a0 = 1.0 + k / q + k * k;
+    let high_pass = Biquad {
+        b: [1.0, -2.0, 1.0],
+        a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        ..Biquad::default()
+    };
+    [shelf, high_pass]
+}
+
+fn power_to_lufs(power: f64) -> f32 {
+    (-0.691 + 10.0 * power.log10()) as f32
+}
+
+/// Running BS.1770 meter for the master bus
+#[derive(Debug, Clone)]
+pub struct LoudnessMeter {
+    sample_rate: f32,
+    filters: [[Biquad; 2]; CHANNELS],
+    block_len: u32,                            // Samples per 100 ms step
+    block_fill: u32,
+    block_sum: f64,                            // Weighted squares in the current step
+    blocks: [f64; SHORT_TERM_BLOCKS],          // Mean square of recent steps (ring)
+    blocks_written: u64,
+    histogram_counts: [u32; HISTOGRAM_BINS],   // Gated 400 ms blocks by loudness
+    histogram_power: [f64; HISTOGRAM_BINS],
+    max_momentary: Option<f32>,
+    peak_history: [[f32; TRUE_PEAK_TAPS]; CHANNELS],
+    true_peak: f32,
+    frames: u64,
+}
+
+impl LoudnessMeter {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate,
+            filters: [k_weighting(sample_rate); CHANNELS],
+            block_len: ((sample_rate * 0.1).round() as u32).max(1),
+            block_fill: 0,
+            block_sum: 0.0,
+            blocks: [0.0; SHORT_TERM_BLOCKS],
+            blocks_written: 0,
+            histogram_counts: [0; HISTOGRAM_BINS],
+            histogram_power: [0.0; HISTOGRAM_BINS],
+            max_momentary: None,
+            peak_history: [[0.0; TRUE_PEAK_TAPS]; CHANNELS],
+            true_peak: 0.0,
+            frames: 0,
+        }
+    }
+
+    /// Start over (integrated loudness and peaks included)
+    pub fn reset(&mut self) {
+        *self = Self::new(self.sample_rate);
+    }
+
+    /// Meter one stereo frame
+    pub fn process_stereo(&mut self, left: f32, right: f32) {
+        let weighted = self.weight(0, left) + self.weight(1, right);
+        self.end_frame(weighted);
+    }
+
+    /// Meter one mono frame (a single channel in BS.1770 terms)
+    pub fn process_mono(&mut self, sample: f32) {
+        let weighted = self.weight(0, sample);
+        self.end_frame(weighted);
+    }
+
+    /// Meter interleaved stereo samples
+    pub fn process_interleaved(&mut self, samples: &[f32]) {
+        for frame in samples.chunks_exact(2) {
+            self.process_stereo(frame[0], frame[1]);
+        }
+    }
+
+    /// K-weighted square of one channel's sample, updating its true peak
+    fn weight(&mut self, channel: usize, sample: f32) -> f64 {
+        let history = &mut self.peak_history[channel];
+        history.copy_within(1.., 0);
+        history[TRUE_PEAK_TAPS - 1] = sample;
+        for phase in &TRUE_PEAK_PHASES {
+            let value: f64 = phase.iter().rev().zip(history.iter()).map(|(tap, &x)| tap * x as f64).sum();
+            self.true_peak = self.true_peak.max(value.abs() as f32);
+        }
+        self.true_peak = self.true_peak.max(sample.abs());
+
+        let [shelf, high_pass] = &mut self.filters[channel];
+        let filtered = high_pass.process(shelf.process(sample as f64));
+        filtered * filtered
+    }
+
+    fn end_frame(&mut self, weighted: f64) {
+        self.frames += 1;
+        self.block_sum += weighted;
+        self.block_fill += 1;
+        if self.block_fill < self.block_len {
+            return;
+        }
+        let slot = (self.blocks_written % SHORT_TERM_BLOCKS as u64) as usize;
+        self.blocks[slot] = self.block_sum / self.block_len as f64;
+        self.blocks_written += 1;
+        self.block_sum = 0.0;
+        self.block_fill = 0;
+
+        // Every step completes an overlapping 400 ms gating block
+        if let Some(power) = self.window_power(MOMENTARY_BLOCKS) {
+            let lufs = power_to_lufs(power);
+            if lufs >= ABSOLUTE_GATE_LUFS {
+                let bin = Self::histogram_bin(lufs);
+                self.histogram_counts[bin] += 1;
+                self.histogram_power[bin] += power;
+            }
+            if power > 0.0 {
+                self.max_momentary = Some(self.max_momentary.map_or(lufs, |max| max.max(lufs)));
+            }
+        }
+    }
+
+    fn histogram_bin(lufs: f32) -> usize {
+        (((lufs - ABSOLUTE_GATE_LUFS) / HISTOGRAM_STEP_LU) as usize).min(HISTOGRAM_BINS - 1)
+    }
+
+    /// Mean square of the last `blocks` steps, once that many exist
+    fn window_power(&self, blocks: usize) -> Option<f64> {
+        if self.blocks_written < blocks as u64 {
+            return None;
+        }
+        let sum: f64 = (0..blocks)
+            .map(|back| self.blocks[((self.blocks_written - 1 - back as u64) % SHORT_TERM_BLOCKS as u64) as usize])
+            .sum();
+        Some(sum / blocks as f64)
+    }
+
+    fn window_lufs(&self, blocks: usize) -> Option<f32> {
+        self.window_power(blocks).filter(|&power| power > 0.0).map(power_to_lufs)
+    }
+
+    /// Gated mean power of the histogram bins from `first_bin` up
+    fn gated_power(&self, first_bin: usize) -> Option<f64> {
+        let count: u64 = self.histogram_counts[first_bin..].iter().map(|&count| count as u64).sum();
+        if count == 0 {
+            return None;
+        }
+        Some(self.histogram_power[first_bin..].iter().sum::<f64>() / count as f64)
+    }
+
+    /// Integrated loudness over everything metered since the last reset
+    pub fn integrated_lufs(&self) -> Option<f32> {
+        let ungated = self.gated_power(0)?;
+        let relative_gate = power_to_lufs(ungated) + RELATIVE_GATE_LU;
+        let first_bin = if relative_gate > ABSOLUTE_GATE_LUFS { Self::histogram_bin(relative_gate) } else { 0 };
+        // Fall back to the absolute gate if the relative one empties every bin
+        let power = self.gated_power(first_bin).unwrap_or(ungated);
+        Some(power_to_lufs(power))
+    }
+
+    /// Largest inter-sample peak so far in dBTP
+    pub fn true_peak_dbtp(&self) -> Option<f32> {
+        (self.true_peak > 0.0).then(|| 20.0 * self.true_peak.log10())
+    }
+
+    pub fn report(&self) -> LoudnessReport {
+        LoudnessReport {
+            momentary_lufs: self.window_lufs(MOMENTARY_BLOCKS),
+            short_term_lufs: self.window_lufs(SHORT_TERM_BLOCKS),
+            integrated_lufs: self.integrated_lufs(),
+            max_momentary_lufs: self.max_momentary,
+            true_peak_dbtp: self.true_peak_dbtp(),
+            elapsed_seconds: self.frames as f64 / self.sample_rate as f64,
+        }
+    }
+}
+
+/// Meter a complete interleaved buffer (1 or 2 channels)
+pub fn measure(samples: &[f32], channels: u16, sample_rate: f32) -> LoudnessReport {
+    let mut meter = LoudnessMeter::new(sample_rate);
+    if channels == 1 {
+        samples.iter().for_each(|&sample| meter.process_mono(sample));
+    } else {
+        meter.process_interleaved(samples);
+    }
+    meter.report()
+}
@@ -0,0 +1,109 @@
+/**
+ * AWE Player - WASM Memory Reservation and Growth Reporting
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * WebAssembly memory only grows, and each memory.grow can stall the thread
+ * that triggers it. Loading a large SoundFont while notes are sounding is
+ * the usual culprit. The host can reserve heap for the expected font size
+ * up front; growth is then observed once per rendered block and reported.
+ *
+ * Reserving allocates heap in small blocks and frees them again. The blocks
+ * coalesce into the allocator's free space, so later allocations reuse that
+ * memory instead of growing it. Blocks stay below the allocator's large
+ * allocation threshold because large chunks would be mapped separately.
+ */
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+
+/// WebAssembly page size
+pub const WASM_PAGE_BYTES: usize = 64 * 1024;
+
+/// Peak heap per SoundFont file byte while loading: the file copy passed in,
+/// the decoded smpl chunk and the per-sample buffers built from it
+pub const SOUNDFONT_LOAD_FACTOR: usize = 3;
+
+/// Block size used for reservations (kept under the allocator's mmap threshold)
+const RESERVE_BLOCK_BYTES: usize = 64 * 1024;
+
+static RESERVED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static LAST_MEMORY_BYTES: AtomicUsize = AtomicUsize::new(0);
+static LAST_GROWTH_BYTES: AtomicUsize = AtomicUsize::new(0);
+static GROWTH_EVENTS: AtomicU32 = AtomicU32::new(0);
+static GROWTH_EVENTS_WHILE_PLAYING: AtomicU32 = AtomicU32::new(0);
+
+/// Snapshot of memory usage and growth history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct MemoryStats {
+    pub current_bytes: usize,         // Linear memory size (0 outside WASM)
+    pub pages: usize,
+    pub reserved_bytes: usize,        // Largest reservation made so far
+    pub growth_events: u32,           // Growths observed since startup
+    pub growth_events_while_playing: u32, // Growths observed with voices sounding
+    pub last_growth_bytes: usize,
+}
+
+/// Current linear memory size in bytes (0 on native targets)
+pub fn memory_bytes() -> usize {
+    #[cfg(target_arch = "wasm32")]
+    return core::arch::wasm32::memory_size(0) * WASM_PAGE_BYTES;
+    #[cfg(not(target_arch = "wasm32"))]
+    0
+}
+
+/// Heap needed to load a SoundFont file of `file_size` bytes
+pub fn estimate_soundfont_load_bytes(file_size: usize) -> usize {
+    file_size.saturating_mul(SOUNDFONT_LOAD_FACTOR)
+}
+
+/// Reserve `bytes` of heap so later allocations up to that size need no memory.grow
+/// Returns false if the memory could not be obtained (nothing stays allocated either way)
+pub fn reserve(bytes: usize) -> bool {
+    let block_count = bytes.div_ceil(RESERVE_BLOCK_BYTES);
+    let mut blocks: Vec<Vec<u8>> = Vec::new();
+    if blocks.try_reserve_exact(block_count).is_err() {
+        return false;
+    }
+    for _ in 0..block_count {
+        let mut block = Vec::new();
+        if block.try_reserve_exact(RESERVE_BLOCK_BYTES).is_err() {
+            return false;
+        }
+        blocks.push(block);
+    }
+    drop(blocks);
+
+    RESERVED_BYTES.fetch_max(bytes, Ordering::Relaxed);
+    // The reservation itself is an intended growth, not a playback stall
+    LAST_MEMORY_BYTES.store(memory_bytes(), Ordering::Relaxed);
+    true
+}
+
+/// Check for memory growth since the last call (cheap: one memory.size)
+/// `playing` = voices were sounding, so a growth there may have been audible
+pub fn observe_growth(playing: bool) {
+    let current = memory_bytes();
+    let previous = LAST_MEMORY_BYTES.swap(current, Ordering::Relaxed);
+    if previous == 0 || current <= previous {
+        return; // First observation only sets the baseline
+    }
+    GROWTH_EVENTS.fetch_add(1, Ordering::Relaxed);
+    LAST_GROWTH_BYTES.store(current - previous, Ordering::Relaxed);
+    if playing {
+        GROWTH_EVENTS_WHILE_PLAYING.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub fn stats() -> MemoryStats {
+    let current_bytes = memory_bytes();
+    MemoryStats {
+        current_bytes,
+        pages: current_bytes / WASM_PAGE_BYTES,
+        reserved_bytes: RESERVED_BYTES.load(Ordering::Relaxed),
+        growth_events: GROWTH_EVENTS.load(Ordering::Relaxed),
+        growth_events_while_playing: GROWTH_EVENTS_WHILE_PLAYING.load(Ordering::Relaxed),
+        last_growth_bytes: LAST_GROWTH_BYTES.load(Ordering::Relaxed),
+    }
+}
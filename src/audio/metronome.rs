@@ -0,0 +1,118 @@
+/**
+ * AWE Player - Metronome
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Click track following the sequencer: one click per beat of the current
+ * time signature, accented on the first beat of each bar (bars are counted
+ * from tick 0). Clicks are synthesized here rather than played through the
+ * voice manager, so they never steal voices or pass through the effects.
+ *
+ * Each click sample has two destinations with independent levels: the main
+ * mix (silent by default, so recordings and the master output stay clean)
+ * and the click bus, a separate mono signal the worklet can route to its
+ * second output for headphone monitoring.
+ */
+
+use std::f32::consts::TAU;
+
+const ACCENT_FREQUENCY_HZ: f32 = 1760.0;
+const BEAT_FREQUENCY_HZ: f32 = 1320.0;
+const ACCENT_AMPLITUDE: f32 = 0.8;
+const BEAT_AMPLITUDE: f32 = 0.5;
+const CLICK_DECAY_SECONDS: f32 = 0.012;     // Time constant of the click envelope
+const SILENCE_THRESHOLD: f32 = 0.001;       // Envelope level at which a click ends (-60 dB)
+
+/// Click generator and its routing levels
+#[derive(Debug, Clone)]
+pub struct Metronome {
+    enabled: bool,
+    main_level: f32,            // Send to the main mix (0.0-1.0)
+    bus_level: f32,             // Send to the click bus (0.0-1.0)
+    last_beat: Option<u64>,     // Beat that last clicked; None re-arms the next one
+    sample_rate: f32,
+    phase: f32,
+    phase_step: f32,
+    envelope: f32,
+    decay: f32,
+}
+
+impl Metronome {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            enabled: false,
+            main_level: 0.0,
+            bus_level: 1.0,
+            last_beat: None,
+            sample_rate,
+            phase: 0.0,
+            phase_step: 0.0,
+            envelope: 0.0,
+            decay: (-1.0 / (CLICK_DECAY_SECONDS * sample_rate)).exp(),
+        }
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.envelope = 0.0;
+            self.last_beat = None;
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Set the main mix and click bus levels (each clamped to 0.0-1.0)
+    pub fn set_levels(&mut self, main_level: f32, bus_level: f32) {
+        self.main_level = main_level.clamp(0.0, 1.0);
+        self.bus_level = bus_level.clamp(0.0, 1.0);
+    }
+
+    /// (main mix level, click bus level)
+    pub fn levels(&self) -> (f32, f32) {
+        (self.main_level, self.bus_level)
+    }
+
+    /// Forget the last beat so the next position clicks (after stop or seek)
+    pub fn reset(&mut self) {
+        self.last_beat = None;
+    }
+
+    /// Follow the playback position; starts a click when `tick` enters a new beat
+    /// `ticks_per_beat` is one denominator note of the time signature
+    pub fn advance_to(&mut self, tick: u64, ticks_per_beat: u64, beats_per_bar: u8) {
+        if !self.enabled || ticks_per_beat == 0 {
+            return;
+        }
+        let beat = tick / ticks_per_beat;
+        if self.last_beat == Some(beat) {
+            return;
+        }
+        self.last_beat = Some(beat);
+        let accent = beat.is_multiple_of(beats_per_bar.max(1) as u64);
+        self.trigger(accent);
+    }
+
+    fn trigger(&mut self, accent: bool) {
+        let (frequency, amplitude) = if accent {
+            (ACCENT_FREQUENCY_HZ, ACCENT_AMPLITUDE)
+        } else {
+            (BEAT_FREQUENCY_HZ, BEAT_AMPLITUDE)
+        };
+        self.phase = 0.0;
+        self.phase_step = TAU * frequency / self.sample_rate;
+        self.envelope = amplitude;
+    }
+
+    /// Next click sample as (main mix send, click bus send)
+    pub fn next_sample(&mut self) -> (f32, f32) {
+        if self.envelope < SILENCE_THRESHOLD {
+            return (0.0, 0.0);
+        }
+        let sample = self.phase.sin() * self.envelope;
+        self.phase = (self.phase + self.phase_step) % TAU;
+        self.envelope *= self.decay;
+        (sample * self.main_level, sample * self.bus_level)
+    }
+}
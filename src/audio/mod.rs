@@ -7,5 +7,27 @@
  */
 
 pub mod buffer_manager;
+pub mod alloc_audit; // Feature-gated no-allocation checks for the audio callback
+pub mod memory;      // WASM heap reservation and memory.grow reporting
+pub mod wav;         // RIFF/WAVE encoding of rendered audio
+pub mod dither;      // TPDF dither and noise shaping for 16-bit export
+pub mod capture;     // Master output recording into a preallocated buffer
+pub mod export;      // Output format selection (WAV, optional FLAC/Ogg)
+pub mod metronome;   // Click track with its own output bus
+pub mod auto_gain;   // Master gain scaled by polyphony
+pub mod fade;        // Master bus ramps for play/stop with fade
+pub mod loudness;    // BS.1770 loudness and true-peak metering
+pub mod normalize;   // Two-pass loudness normalization of finished renders
+pub mod clock;       // Sample counter to AudioContext time correlation
+pub mod render_ahead; // Optional queue of blocks rendered in advance
+pub mod silence;     // Offline render auto-stop once voices and tails decay
+pub mod batch;       // Sequential MIDI-to-audio conversion of many files
+pub mod ab_compare;  // Same segment rendered under two settings snapshots, with a difference metric
+pub mod quality_scaler; // Interpolation/effects/polyphony ladder under CPU pressure
+pub mod deadline;    // Per-block render timing and near-miss warnings
+#[cfg(feature = "flac")]
+pub mod flac;        // Lossless FLAC encoder
+#[cfg(feature = "ogg")]
+pub mod ogg;         // Ogg container for FLAC frames
 
 pub use buffer_manager::*;
\ No newline at end of file
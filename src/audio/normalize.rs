@@ -0,0 +1,135 @@
+/**
+ * AWE Player - Loudness Normalization
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Converting a MIDI library to audio gives files whose levels follow each
+ * song's velocities and mix, from whisper-quiet solo pieces to full GM
+ * arrangements. Normalization makes them consistent in two passes over a
+ * finished render: the first measures integrated loudness (loudness.rs),
+ * the second applies the gain that lands it on the target. When that gain
+ * would push the true peak over the ceiling, the peaks are limited instead
+ * of lowering the whole file: a gain curve that never exceeds what each
+ * frame allows, ramping down ahead of a peak and recovering afterwards.
+ * Limiting costs some loudness, so a limited render is measured again and
+ * made up (and limited again) a few times until it is within 0.1 LU.
+ */
+
+use serde::Serialize;
+use super::loudness::measure;
+
+/// Ceiling when none is given (common streaming-service limit)
+pub const DEFAULT_TRUE_PEAK_CEILING_DBTP: f32 = -1.0;
+
+/// Largest boost applied to a quiet render
+const MAX_GAIN_DB: f32 = 40.0;
+/// Limiter gain ramps: down ahead of a peak, back up afterwards
+const LIMITER_ATTACK_SECONDS: f32 = 0.0015;
+const LIMITER_RELEASE_SECONDS: f32 = 0.05;
+/// Sample peaks are held a little below the ceiling to leave room for inter-sample peaks
+const LIMITER_MARGIN_DB: f32 = 0.3;
+/// Make-up passes after limiting, and how close to the target ends them
+const MAX_MAKEUP_PASSES: usize = 4;
+const TARGET_TOLERANCE_LU: f32 = 0.1;
+
+/// What normalization did (JSON)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct NormalizeReport {
+    pub measured_lufs: Option<f32>,         // None = silent or below the gate; nothing changed
+    pub gain_db: f32,                       // Overall gain, before limiting
+    pub limited: bool,                      // Peaks were held at the ceiling
+    pub output_lufs: Option<f32>,
+    pub output_true_peak_dbtp: Option<f32>,
+}
+
+fn db_to_gain(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// Scale interleaved mono or stereo `samples` in place to `target_lufs`, keeping the
+/// true peak at or below `ceiling_dbtp` (other channel counts are left unchanged)
+pub fn normalize(samples: &mut [f32], channels: u16, sample_rate: f32, target_lufs: f32, ceiling_dbtp: f32) -> NormalizeReport {
+    let before = measure(samples, channels, sample_rate);
+    let unchanged = NormalizeReport {
+        measured_lufs: before.integrated_lufs,
+        gain_db: 0.0,
+        limited: false,
+        output_lufs: before.integrated_lufs,
+        output_true_peak_dbtp: before.true_peak_dbtp,
+    };
+    let (Some(measured), 1..=2) = (before.integrated_lufs, channels) else {
+        return unchanged;
+    };
+    if !target_lufs.is_finite() || !ceiling_dbtp.is_finite() {
+        return unchanged;
+    }
+
+    let mut gain_db = 0.0;
+    let mut limited = false;
+    let mut current = before;
+    for _ in 0..=MAX_MAKEUP_PASSES {
+        let Some(lufs) = current.integrated_lufs else {
+            break;
+        };
+        let step_db = (target_lufs - lufs).min(MAX_GAIN_DB - gain_db);
+        if limited && step_db.abs() < TARGET_TOLERANCE_LU {
+            break;
+        }
+        let gain = db_to_gain(step_db);
+        samples.iter_mut().for_each(|sample| *sample *= gain);
+        gain_db += step_db;
+
+        let peak = current.true_peak_dbtp.map_or(f32::NEG_INFINITY, |peak| peak + step_db);
+        if peak <= ceiling_dbtp {
+            current = measure(samples, channels, sample_rate);
+            break;
+        }
+        limit(samples, channels as usize, sample_rate, db_to_gain(ceiling_dbtp - LIMITER_MARGIN_DB));
+        limited = true;
+        current = measure(samples, channels, sample_rate);
+    }
+
+    // Whatever the interpolator still finds above the ceiling comes off the whole file
+    if let Some(peak) = current.true_peak_dbtp.filter(|&peak| peak > ceiling_dbtp) {
+        let trim = db_to_gain(ceiling_dbtp - peak);
+        samples.iter_mut().for_each(|sample| *sample *= trim);
+        current = measure(samples, channels, sample_rate);
+    }
+
+    NormalizeReport {
+        measured_lufs: Some(measured),
+        gain_db,
+        limited,
+        output_lufs: current.integrated_lufs,
+        output_true_peak_dbtp: current.true_peak_dbtp,
+    }
+}
+
+/// Hold interleaved sample peaks at `ceiling` with a smoothed per-frame gain curve
+fn limit(samples: &mut [f32], channels: usize, sample_rate: f32, ceiling: f32) {
+    let mut gains: Vec<f32> = samples.chunks_exact(channels)
+        .map(|frame| {
+            let peak = frame.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+            if peak > ceiling { ceiling / peak } else { 1.0 }
+        })
+        .collect();
+
+    // Backwards: the gain may fall by at most one attack step per frame, so it starts
+    // ramping down before each peak
+    let attack_step = 1.0 / (LIMITER_ATTACK_SECONDS * sample_rate).max(1.0);
+    for index in (0..gains.len().saturating_sub(1)).rev() {
+        gains[index] = gains[index].min(gains[index + 1] + attack_step);
+    }
+    // Forwards: recover exponentially, never above what the frame allows
+    let release = 1.0 - (-1.0 / (LIMITER_RELEASE_SECONDS * sample_rate)).exp();
+    let mut current = 1.0f32;
+    for gain in gains.iter_mut() {
+        current = (current + (1.0 - current) * release).min(*gain);
+        *gain = current;
+    }
+
+    for (frame, gain) in samples.chunks_exact_mut(channels).zip(&gains) {
+        frame.iter_mut().for_each(|sample| *sample *= gain);
+    }
+}
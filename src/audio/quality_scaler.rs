@@ -0,0 +1,232 @@
+/**
+ * AWE Player - Quality Scaling Under CPU Pressure
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * When the host keeps reporting blocks that take most of their time
+ * budget, glitches are only a matter of time. Rather than wait for them,
+ * the scaler walks down a ladder - linear interpolation, then one step
+ * less effects (chorus first, then reverb), then half the polyphony -
+ * one rung at a time, each below the device profile's own settings.
+ * Load is smoothed over recent blocks, and the thresholds differ: a rung
+ * is dropped while the smoothed load stays above 80%, but only climbed
+ * back after it has stayed below 50% for several seconds, so the engine
+ * does not flip between rungs. Every move is reported to the listener.
+ */
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use serde::Serialize;
+use crate::synth::device_profile::{EffectsQuality, InterpolationQuality, ProfileSettings};
+
+/// Smoothed load (processing time / block time) that drops a rung
+pub const DEGRADE_LOAD: f32 = 0.8;
+/// Smoothed load the engine must stay under before climbing back
+pub const RECOVER_LOAD: f32 = 0.5;
+/// Blocks between two drops, so the smoothed load can settle on the new rung
+pub const DEGRADE_HOLD_BLOCKS: u32 = 32;
+/// Consecutive quiet blocks before a rung is restored (~3 s of 128-frame blocks at 44.1 kHz)
+pub const RECOVER_BLOCKS: u32 = 1024;
+/// Fewest voices the polyphony rung leaves
+pub const MIN_CAPPED_POLYPHONY: usize = 8;
+
+/// Weight of the newest block in the smoothed load
+const LOAD_SMOOTHING: f32 = 0.1;
+
+/// Rungs of the ladder, best quality first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum QualityLevel {
+    Full,                   // Device profile settings unchanged
+    LinearInterpolation,
+    ReducedEffects,         // Plus one step less effects
+    CappedPolyphony,        // Plus half the voices
+}
+
+impl QualityLevel {
+    const LADDER: [QualityLevel; 4] = [
+        QualityLevel::Full,
+        QualityLevel::LinearInterpolation,
+        QualityLevel::ReducedEffects,
+        QualityLevel::CappedPolyphony,
+    ];
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// `baseline` with this rung's reductions applied
+    pub fn apply(&self, baseline: &ProfileSettings) -> ProfileSettings {
+        let mut settings = *baseline;
+        if *self >= QualityLevel::LinearInterpolation {
+            settings.interpolation = InterpolationQuality::Linear;
+        }
+        if *self >= QualityLevel::ReducedEffects {
+            settings.effects = match baseline.effects {
+                EffectsQuality::Full => EffectsQuality::Reduced,
+                EffectsQuality::Reduced | EffectsQuality::Off => EffectsQuality::Off,
+            };
+        }
+        if *self >= QualityLevel::CappedPolyphony {
+            settings.max_polyphony = (baseline.max_polyphony / 2).max(MIN_CAPPED_POLYPHONY).min(baseline.max_polyphony);
+        }
+        settings
+    }
+}
+
+/// A move on the ladder (JSON for the quality callback)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct QualityChange {
+    pub previous: QualityLevel,
+    pub level: QualityLevel,
+    pub load: f32,                  // Smoothed load that triggered the move
+    pub settings: ProfileSettings,  // Now in effect
+}
+
+/// Scaler state (get_quality_scaling_global)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct QualityScalingStatus {
+    pub enabled: bool,
+    pub level: QualityLevel,
+    pub load: f32,
+    pub settings: ProfileSettings,
+}
+
+/// Smoothed load tracking and the current rung
+#[derive(Debug, Clone)]
+pub struct QualityScaler {
+    enabled: bool,
+    level: QualityLevel,
+    load: f32,
+    blocks_since_change: u32,
+    quiet_blocks: u32,
+}
+
+impl QualityScaler {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            level: QualityLevel::Full,
+            load: 0.0,
+            blocks_since_change: 0,
+            quiet_blocks: 0,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Turning scaling off returns to full quality
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.level = QualityLevel::Full;
+        }
+        self.blocks_since_change = 0;
+        self.quiet_blocks = 0;
+    }
+
+    pub fn level(&self) -> QualityLevel {
+        self.level
+    }
+
+    pub fn load(&self) -> f32 {
+        self.load
+    }
+
+    /// Feed one block's load; returns the move if the rung changed
+    pub fn observe(&mut self, load: f32, baseline: &ProfileSettings) -> Option<QualityChange> {
+        if !load.is_finite() {
+            return None;
+        }
+        self.load += (load.max(0.0) - self.load) * LOAD_SMOOTHING;
+        if !self.enabled {
+            return None;
+        }
+        self.blocks_since_change = self.blocks_since_change.saturating_add(1);
+        self.quiet_blocks = if self.load < RECOVER_LOAD { self.quiet_blocks.saturating_add(1) } else { 0 };
+
+        let target = if self.load > DEGRADE_LOAD && self.blocks_since_change >= DEGRADE_HOLD_BLOCKS {
+            self.next_rung(baseline, 1)
+        } else if self.quiet_blocks >= RECOVER_BLOCKS {
+            self.next_rung(baseline, -1)
+        } else {
+            None
+        }?;
+
+        let previous = self.level;
+        self.level = target;
+        self.blocks_since_change = 0;
+        self.quiet_blocks = 0;
+        Some(QualityChange {
+            previous,
+            level: target,
+            load: self.load,
+            settings: target.apply(baseline),
+        })
+    }
+
+    /// Nearest rung in `direction` that changes the settings (rungs the profile already
+    /// matches are skipped)
+    fn next_rung(&self, baseline: &ProfileSettings, direction: isize) -> Option<QualityLevel> {
+        let current = self.level.apply(baseline);
+        let mut index = self.level.index();
+        loop {
+            index = index.checked_add_signed(direction)?;
+            let level = *QualityLevel::LADDER.get(index)?;
+            let settings = level.apply(baseline);
+            if settings != current {
+                return Some(if direction < 0 { self.lowest_equivalent(level, baseline) } else { level });
+            }
+        }
+    }
+
+    /// Highest-quality rung with the same settings as `level`
+    fn lowest_equivalent(&self, level: QualityLevel, baseline: &ProfileSettings) -> QualityLevel {
+        let settings = level.apply(baseline);
+        QualityLevel::LADDER[..level.index()].iter().rev()
+            .take_while(|rung| rung.apply(baseline) == settings)
+            .last()
+            .copied()
+            .unwrap_or(level)
+    }
+
+    pub fn status(&self, baseline: &ProfileSettings) -> QualityScalingStatus {
+        QualityScalingStatus {
+            enabled: self.enabled,
+            level: self.level,
+            load: self.load,
+            settings: self.level.apply(baseline),
+        }
+    }
+}
+
+impl Default for QualityScaler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub type QualityListener = Rc<dyn Fn(&QualityChange)>;
+
+thread_local! {
+    static LISTENER: RefCell<Option<QualityListener>> = const { RefCell::new(None) };
+}
+
+/// Install (or with None, remove) the quality change listener
+pub fn set_listener(listener: Option<QualityListener>) {
+    LISTENER.with(|slot| *slot.borrow_mut() = listener);
+}
+
+/// Deliver a move to the listener, if one is registered
+pub fn emit(change: &QualityChange) {
+    let listener = LISTENER.with(|slot| slot.borrow().clone());
+    if let Some(listener) = listener {
+        listener(change);
+    }
+}
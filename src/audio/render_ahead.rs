@@ -0,0 +1,146 @@
+/**
+ * AWE Player - Render-Ahead Queue
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Normally each worklet callback renders exactly the block it plays, so
+ * any stall in that callback is an audible dropout. With render-ahead
+ * the bridge keeps one to three blocks rendered in advance: a callback
+ * plays the oldest queued block, renders the next, and when the queue is
+ * short renders one extra to top it up. Output stays continuous when the
+ * depth changes - raising it fills the queue a block per callback,
+ * lowering it (or switching off) plays the surplus out without rendering.
+ * The price is latency: every queued block delays MIDI input and the
+ * metronome by one block. The queue is allocated when render-ahead is
+ * first enabled, never on the audio thread.
+ */
+
+use std::collections::VecDeque;
+use serde::Serialize;
+use crate::worklet::MAX_RENDER_FRAMES;
+
+/// Deepest supported queue, in blocks
+pub const MAX_RENDER_AHEAD_BLOCKS: usize = 3;
+/// Blocks rendered by one callback at most (its own plus one top-up)
+pub const MAX_RENDERS_PER_CALLBACK: usize = 2;
+
+/// Frames the queue can hold: full depth plus the block being played and a top-up
+const QUEUE_FRAMES: usize = (MAX_RENDER_AHEAD_BLOCKS + MAX_RENDERS_PER_CALLBACK) * MAX_RENDER_FRAMES;
+
+/// Render-ahead state (JSON)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct RenderAheadStatus {
+    pub depth_blocks: usize,    // 0 = off
+    pub auto_enable: bool,      // Deepen on repeated underruns
+    pub queued_frames: usize,
+    pub added_latency_ms: f32,  // Delay of the queued audio
+}
+
+/// Queue of rendered frames: (left, right, click)
+#[derive(Debug, Clone)]
+pub struct RenderAhead {
+    depth_blocks: usize,
+    auto_enable: bool,
+    frames: VecDeque<[f32; 3]>,
+}
+
+impl RenderAhead {
+    pub fn new() -> Self {
+        Self {
+            depth_blocks: 0,
+            auto_enable: true,
+            frames: VecDeque::new(),
+        }
+    }
+
+    pub fn depth_blocks(&self) -> usize {
+        self.depth_blocks
+    }
+
+    /// Set the depth (clamped to 0-3); reserves the queue the first time it is enabled
+    pub fn set_depth_blocks(&mut self, blocks: usize) {
+        self.depth_blocks = blocks.min(MAX_RENDER_AHEAD_BLOCKS);
+        if self.depth_blocks > 0 && self.frames.capacity() < QUEUE_FRAMES {
+            self.frames.reserve_exact(QUEUE_FRAMES);
+        }
+    }
+
+    pub fn auto_enable(&self) -> bool {
+        self.auto_enable
+    }
+
+    pub fn set_auto_enable(&mut self, enabled: bool) {
+        self.auto_enable = enabled;
+    }
+
+    /// One block deeper after repeated underruns; false if auto-enable is off or at full depth
+    pub fn deepen(&mut self) -> bool {
+        if !self.auto_enable || self.depth_blocks >= MAX_RENDER_AHEAD_BLOCKS {
+            return false;
+        }
+        self.set_depth_blocks(self.depth_blocks + 1);
+        true
+    }
+
+    /// Whether callbacks go through the queue (enabled, or still playing out after being lowered to 0)
+    pub fn is_active(&self) -> bool {
+        self.depth_blocks > 0 || !self.frames.is_empty()
+    }
+
+    pub fn queued_frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether a callback playing `block_frames` should render another block before playing
+    pub fn needs_block(&self, block_frames: usize) -> bool {
+        self.frames.len() < (self.depth_blocks + 1) * block_frames
+            && self.frames.len() + block_frames <= self.frames.capacity()
+    }
+
+    /// Queue a rendered block: interleaved stereo plus its click bus
+    pub fn push(&mut self, stereo: &[f32], click: &[f32]) {
+        for (index, frame) in stereo.chunks_exact(2).enumerate() {
+            if self.frames.len() >= self.frames.capacity() {
+                break;
+            }
+            self.frames.push_back([frame[0], frame[1], click.get(index).copied().unwrap_or(0.0)]);
+        }
+    }
+
+    /// Move up to `frames` of the oldest queued audio into `stereo` and `click`; returns frames written
+    pub fn pop(&mut self, frames: usize, stereo: &mut [f32], click: &mut [f32]) -> usize {
+        let frames = frames.min(stereo.len() / 2).min(click.len());
+        let mut written = 0;
+        while written < frames {
+            let Some([left, right, click_sample]) = self.frames.pop_front() else {
+                break;
+            };
+            stereo[written * 2] = left;
+            stereo[written * 2 + 1] = right;
+            click[written] = click_sample;
+            written += 1;
+        }
+        written
+    }
+
+    /// Drop queued audio (transport reset, panic)
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    pub fn status(&self, sample_rate: f32) -> RenderAheadStatus {
+        RenderAheadStatus {
+            depth_blocks: self.depth_blocks,
+            auto_enable: self.auto_enable,
+            queued_frames: self.frames.len(),
+            added_latency_ms: self.frames.len() as f32 / sample_rate * 1000.0,
+        }
+    }
+}
+
+impl Default for RenderAhead {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,147 @@
+/**
+ * AWE Player - Offline Render Auto-Stop
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * An export renders blocks as fast as it can until the host stops asking,
+ * and the host can't tell when the song has really ended: the last event
+ * is followed by release phases and reverb/chorus tails of unknown length,
+ * so exports either cut tails off or end in minutes of silence. In offline
+ * render mode the bridge watches the output once the sequencer has stopped
+ * and no events are queued: when no voice is left and the output has stayed
+ * below the threshold for a short hold, rendering stops. The tail cap stops
+ * it anyway (a held note with no note-off would otherwise ring forever).
+ */
+
+use serde::Serialize;
+
+/// Output level counted as silence
+pub const DEFAULT_SILENCE_THRESHOLD_DB: f32 = -90.0;
+/// Silence needed before stopping (effect tails can dip briefly)
+pub const SILENCE_HOLD_SECONDS: f32 = 0.25;
+/// Longest render after the last event
+pub const DEFAULT_TAIL_CAP_SECONDS: f32 = 30.0;
+pub const MAX_TAIL_CAP_SECONDS: f32 = 600.0;
+
+/// Why an offline render stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum RenderStopReason {
+    Silence,        // Voices and effect tails decayed below the threshold
+    TailCap,        // Still sounding when the tail cap ran out
+}
+
+/// Offline render mode and progress (get_offline_render_status_global)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct OfflineRenderStatus {
+    pub enabled: bool,
+    pub threshold_db: f32,
+    pub tail_cap_seconds: f32,
+    pub tail_seconds: f32,          // Rendered since the last event
+    pub finished: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub stop_reason: Option<RenderStopReason>,
+}
+
+/// Decides when an offline render has nothing left to say
+#[derive(Debug, Clone)]
+pub struct SilenceDetector {
+    sample_rate: f32,
+    enabled: bool,
+    threshold_db: f32,
+    threshold: f32,                 // Linear peak
+    tail_cap_seconds: f32,
+    tail_frames: u64,               // Rendered since the engine went idle
+    silent_frames: u64,             // Consecutive frames below the threshold with no voices
+    stop_reason: Option<RenderStopReason>,
+}
+
+impl SilenceDetector {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate,
+            enabled: false,
+            threshold_db: DEFAULT_SILENCE_THRESHOLD_DB,
+            threshold: db_to_gain(DEFAULT_SILENCE_THRESHOLD_DB),
+            tail_cap_seconds: DEFAULT_TAIL_CAP_SECONDS,
+            tail_frames: 0,
+            silent_frames: 0,
+            stop_reason: None,
+        }
+    }
+
+    /// Enable offline render mode; non-finite values keep the defaults,
+    /// the tail cap is clamped to 0-MAX_TAIL_CAP_SECONDS. Re-arms a finished render
+    pub fn configure(&mut self, enabled: bool, threshold_db: f32, tail_cap_seconds: f32) {
+        self.enabled = enabled;
+        self.threshold_db = if threshold_db.is_finite() { threshold_db.min(0.0) } else { DEFAULT_SILENCE_THRESHOLD_DB };
+        self.threshold = db_to_gain(self.threshold_db);
+        self.tail_cap_seconds = if tail_cap_seconds.is_finite() {
+            tail_cap_seconds.clamp(0.0, MAX_TAIL_CAP_SECONDS)
+        } else {
+            DEFAULT_TAIL_CAP_SECONDS
+        };
+        self.reset();
+    }
+
+    pub fn reset(&mut self) {
+        self.tail_frames = 0;
+        self.silent_frames = 0;
+        self.stop_reason = None;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// True once the render should stop (stays true until activity resumes or reconfiguration)
+    pub fn is_finished(&self) -> bool {
+        self.enabled && self.stop_reason.is_some()
+    }
+
+    /// Account for one rendered block of interleaved stereo
+    /// `idle`: no sequence playing and no events queued; `active_voices`: voices still sounding
+    pub fn observe(&mut self, block: &[f32], idle: bool, active_voices: usize) {
+        if !self.enabled {
+            return;
+        }
+        if !idle {
+            self.reset();
+            return;
+        }
+        let frames = (block.len() / 2) as u64;
+        self.tail_frames += frames;
+        let peak = block.iter().fold(0.0f32, |peak, &sample| peak.max(sample.abs()));
+        if active_voices == 0 && peak < self.threshold {
+            self.silent_frames += frames;
+        } else {
+            self.silent_frames = 0;
+        }
+
+        if self.stop_reason.is_none() {
+            if self.silent_frames as f32 >= SILENCE_HOLD_SECONDS * self.sample_rate {
+                self.stop_reason = Some(RenderStopReason::Silence);
+            } else if self.tail_frames as f32 >= self.tail_cap_seconds * self.sample_rate {
+                self.stop_reason = Some(RenderStopReason::TailCap);
+            }
+        }
+    }
+
+    pub fn status(&self) -> OfflineRenderStatus {
+        OfflineRenderStatus {
+            enabled: self.enabled,
+            threshold_db: self.threshold_db,
+            tail_cap_seconds: self.tail_cap_seconds,
+            tail_seconds: self.tail_frames as f32 / self.sample_rate,
+            finished: self.is_finished(),
+            stop_reason: self.stop_reason,
+        }
+    }
+}
+
+fn db_to_gain(db: f32) -> f32 {
+    10.0f32.powf(db / 20.0)
+}
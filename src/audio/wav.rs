@@ -0,0 +1,206 @@
+/**
+ * AWE Player - WAV Writer
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Encodes rendered float buffers into RIFF/WAVE byte vectors for download
+ * or further processing. Supports 16-bit and 24-bit integer PCM and 32-bit
+ * IEEE float. Samples are interleaved (L, R, L, R, ...) for stereo.
+ *
+ * WavWriter accepts audio block by block (recording/capture), encode_wav()
+ * handles a complete buffer in one call (offline rendering). 16-bit output
+ * can be TPDF dithered (see dither.rs); other formats are never dithered.
+ */
+
+use super::dither::Dither;
+
+/// Sample encoding of the WAV data chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WavFormat {
+    Pcm16,
+    Pcm24,
+    Float32,
+}
+
+impl WavFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "pcm16" | "16" | "s16" => Some(WavFormat::Pcm16),
+            "pcm24" | "24" | "s24" => Some(WavFormat::Pcm24),
+            "float32" | "f32" | "float" => Some(WavFormat::Float32),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WavFormat::Pcm16 => "pcm16",
+            WavFormat::Pcm24 => "pcm24",
+            WavFormat::Float32 => "float32",
+        }
+    }
+
+    pub fn bytes_per_sample(&self) -> usize {
+        match self {
+            WavFormat::Pcm16 => 2,
+            WavFormat::Pcm24 => 3,
+            WavFormat::Float32 => 4,
+        }
+    }
+
+    /// WAVE format tag: 1 = integer PCM, 3 = IEEE float
+    fn format_tag(&self) -> u16 {
+        match self {
+            WavFormat::Pcm16 | WavFormat::Pcm24 => 1,
+            WavFormat::Float32 => 3,
+        }
+    }
+}
+
+/// Stream layout of a WAV file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WavSpec {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub format: WavFormat,
+}
+
+impl WavSpec {
+    pub fn new(sample_rate: u32, channels: u16, format: WavFormat) -> Self {
+        Self { sample_rate, channels: channels.max(1), format }
+    }
+
+    fn block_align(&self) -> usize {
+        self.channels as usize * self.format.bytes_per_sample()
+    }
+
+    /// Bytes before the sample data (float adds cbSize and a fact chunk)
+    fn header_len(&self) -> usize {
+        match self.format {
+            WavFormat::Float32 => 12 + 26 + 12 + 8,
+            _ => 12 + 24 + 8,
+        }
+    }
+}
+
+/// Incremental WAV encoder - header sizes are filled in by finish()
+#[derive(Debug, Clone)]
+pub struct WavWriter {
+    spec: WavSpec,
+    bytes: Vec<u8>,
+    dither: Option<Dither>,
+}
+
+impl WavWriter {
+    pub fn new(spec: WavSpec) -> Self {
+        Self::with_capacity(spec, 0)
+    }
+
+    /// Pre-size for `frames` frames so capture doesn't reallocate while recording
+    pub fn with_capacity(spec: WavSpec, frames: usize) -> Self {
+        let mut bytes = Vec::with_capacity(spec.header_len() + frames * spec.block_align());
+        bytes.resize(spec.header_len(), 0);
+        Self { spec, bytes, dither: None }
+    }
+
+    pub fn spec(&self) -> WavSpec {
+        self.spec
+    }
+
+    /// Dither 16-bit samples from here on (None = plain rounding)
+    pub fn set_dither(&mut self, dither: Option<Dither>) {
+        self.dither = dither;
+    }
+
+    /// Append interleaved samples (-1.0..1.0; integer formats clip outside that range)
+    pub fn write_samples(&mut self, samples: &[f32]) {
+        match self.spec.format {
+            WavFormat::Pcm16 => {
+                let channels = self.spec.channels as usize;
+                let first_channel = self.data_len() / 2 % channels;
+                for (index, &sample) in samples.iter().enumerate() {
+                    let value = match self.dither.as_mut() {
+                        Some(dither) => dither.quantize(sample, (first_channel + index) % channels, 16),
+                        None => quantize(sample, 16),
+                    } as i16;
+                    self.bytes.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+            WavFormat::Pcm24 => {
+                for &sample in samples {
+                    let value = quantize(sample, 24);
+                    self.bytes.extend_from_slice(&value.to_le_bytes()[..3]);
+                }
+            }
+            WavFormat::Float32 => {
+                for &sample in samples {
+                    self.bytes.extend_from_slice(&sample.to_le_bytes());
+                }
+            }
+        }
+    }
+
+    /// Complete frames written so far
+    pub fn frames_written(&self) -> usize {
+        self.data_len() / self.spec.block_align()
+    }
+
+    fn data_len(&self) -> usize {
+        self.bytes.len() - self.spec.header_len()
+    }
+
+    /// Write the header and return the finished file
+    pub fn finish(mut self) -> Vec<u8> {
+        // A partial trailing frame would misalign every player; drop it
+        let data_len = self.frames_written() * self.spec.block_align();
+        self.bytes.truncate(self.spec.header_len() + data_len);
+        let header = build_header(&self.spec, data_len);
+        self.bytes[..header.len()].copy_from_slice(&header);
+        self.bytes
+    }
+}
+
+/// Encode a complete interleaved buffer
+pub fn encode_wav(spec: WavSpec, samples: &[f32]) -> Vec<u8> {
+    let mut writer = WavWriter::with_capacity(spec, samples.len() / spec.channels as usize);
+    writer.write_samples(samples);
+    writer.finish()
+}
+
+/// Scale a float sample to a signed integer of `bits` bits (shared with the FLAC encoder)
+pub(crate) fn quantize(sample: f32, bits: u32) -> i32 {
+    let full_scale = ((1i32 << (bits - 1)) - 1) as f32;
+    (sample.clamp(-1.0, 1.0) * full_scale).round() as i32
+}
+
+fn build_header(spec: &WavSpec, data_len: usize) -> Vec<u8> {
+    let block_align = spec.block_align() as u32;
+    let data_len = data_len as u32;
+    let riff_len = (spec.header_len() - 8) as u32 + data_len;
+    let is_float = spec.format == WavFormat::Float32;
+
+    let mut header = Vec::with_capacity(spec.header_len());
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&riff_len.to_le_bytes());
+    header.extend_from_slice(b"WAVE");
+
+    header.extend_from_slice(b"fmt ");
+    header.extend_from_slice(&(if is_float { 18u32 } else { 16u32 }).to_le_bytes());
+    header.extend_from_slice(&spec.format.format_tag().to_le_bytes());
+    header.extend_from_slice(&spec.channels.to_le_bytes());
+    header.extend_from_slice(&spec.sample_rate.to_le_bytes());
+    header.extend_from_slice(&(spec.sample_rate * block_align).to_le_bytes());
+    header.extend_from_slice(&(block_align as u16).to_le_bytes());
+    header.extend_from_slice(&((spec.format.bytes_per_sample() * 8) as u16).to_le_bytes());
+
+    if is_float {
+        header.extend_from_slice(&0u16.to_le_bytes()); // cbSize
+        // Non-PCM formats carry a fact chunk with the frame count
+        header.extend_from_slice(b"fact");
+        header.extend_from_slice(&4u32.to_le_bytes());
+        header.extend_from_slice(&(data_len / block_align).to_le_bytes());
+    }
+
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&data_len.to_le_bytes());
+    header
+}
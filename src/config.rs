@@ -0,0 +1,181 @@
+/**
+ * AWE Player - Startup Configuration
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Embedders that ship the engine inside a product tune it once - sample
+ * rate, device profile, polyphony, reverb and chorus, velocity response,
+ * bank fallback rules - and would otherwise replay the same dozen setter
+ * calls on every start. A configuration document carries all of it and is
+ * applied in one call, either at init (init_audio_worklet_with_config) or
+ * to a running engine (apply_engine_config_global).
+ *
+ * Documents are JSON, or TOML in builds with the `toml` feature. Every
+ * field is optional and leaves that part of the engine as it is; a reverb
+ * or chorus block replaces the whole effect setting, with the fields it
+ * leaves out taking the engine defaults. Unknown fields and out-of-range
+ * values are refused, and a refused document changes nothing.
+ */
+
+use serde::Deserialize;
+use crate::midi::bank_select::MidiSystemMode;
+use crate::synth::device_profile::DeviceProfile;
+use crate::synth::velocity_map::{VelocityMap, MAX_VELOCITY_CURVE, MIN_VELOCITY_CURVE};
+use crate::synth::voice_manager::MAX_VOICES;
+use crate::worklet::AudioWorkletBridge;
+
+/// Sample rates a configuration may ask for (Hz)
+pub const MIN_CONFIG_SAMPLE_RATE: f32 = 8000.0;
+pub const MAX_CONFIG_SAMPLE_RATE: f32 = 192000.0;
+
+/// Reverb room and return level (fields left out take the EMU8000 defaults)
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ReverbConfig {
+    pub room_size: f32,         // 0.0-1.0
+    pub damping: f32,           // 0.0-1.0
+    pub diffusion: f32,         // 0.0-1.0
+    pub return_level: f32,      // Wet level, 0.0-1.0
+}
+
+impl Default for ReverbConfig {
+    fn default() -> Self {
+        Self { room_size: 0.5, damping: 0.3, diffusion: 0.7, return_level: 0.5 }
+    }
+}
+
+/// Chorus modulation and return level (fields left out take the EMU8000 defaults)
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ChorusConfig {
+    pub rate: f32,              // LFO rate, 0.1-10 Hz
+    pub depth: f32,             // 0.0-1.0
+    pub feedback: f32,          // 0.0-0.8
+    pub stereo_spread: f32,     // 0.0-1.0
+    pub return_level: f32,      // Wet level, 0.0-1.0
+}
+
+impl Default for ChorusConfig {
+    fn default() -> Self {
+        Self { rate: 2.0, depth: 0.5, feedback: 0.3, stereo_spread: 0.7, return_level: 0.4 }
+    }
+}
+
+/// Engine settings applied together; absent fields are left as they are
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct EngineConfig {
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub sample_rate: Option<f32>,           // Required by init_audio_worklet_with_config
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub device_profile: Option<String>,     // "lowEnd", "mid", "desktop", "live" or "auto"
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub polyphony: Option<usize>,           // Voice cap below the profile's, 1-32
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub reverb: Option<ReverbConfig>,
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub chorus: Option<ChorusConfig>,
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub velocity_curve: Option<VelocityMap>,    // Every channel's velocity range and curve
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub system_mode: Option<String>,        // "gm", "gm2", "gs" or "xg": bank select and fallback rules
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub fallback_bank: Option<bool>,        // Built-in GM bank until a SoundFont loads
+}
+
+impl EngineConfig {
+    /// Parse and check a document: JSON if it starts with `{`, TOML otherwise
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let config: Self = if text.trim_start().starts_with('{') {
+            serde_json::from_str(text).map_err(|e| format!("Invalid configuration JSON: {}", e))?
+        } else {
+            Self::parse_toml(text)?
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    #[cfg(feature = "toml")]
+    fn parse_toml(text: &str) -> Result<Self, String> {
+        basic_toml::from_str(text).map_err(|e| format!("Invalid configuration TOML: {}", e))
+    }
+
+    #[cfg(not(feature = "toml"))]
+    fn parse_toml(_text: &str) -> Result<Self, String> {
+        Err("Configuration must be a JSON object (TOML needs a build with the toml feature)".to_string())
+    }
+
+    /// Every value in range and every name known
+    pub fn validate(&self) -> Result<(), String> {
+        fn check(ok: bool, message: &str) -> Result<(), String> {
+            if ok { Ok(()) } else { Err(message.to_string()) }
+        }
+        fn unit(value: f32, field: &str) -> Result<(), String> {
+            check((0.0..=1.0).contains(&value), &format!("{} must be 0.0-1.0", field))
+        }
+
+        if let Some(rate) = self.sample_rate {
+            check((MIN_CONFIG_SAMPLE_RATE..=MAX_CONFIG_SAMPLE_RATE).contains(&rate), "sampleRate must be 8000-192000 Hz")?;
+        }
+        if let Some(profile) = &self.device_profile {
+            check(profile == "auto" || DeviceProfile::from_name(profile).is_some(),
+                  "deviceProfile must be lowEnd, mid, desktop, live or auto")?;
+        }
+        if let Some(voices) = self.polyphony {
+            check((1..=MAX_VOICES).contains(&voices), "polyphony must be 1-32")?;
+        }
+        if let Some(reverb) = &self.reverb {
+            unit(reverb.room_size, "reverb.roomSize")?;
+            unit(reverb.damping, "reverb.damping")?;
+            unit(reverb.diffusion, "reverb.diffusion")?;
+            unit(reverb.return_level, "reverb.returnLevel")?;
+        }
+        if let Some(chorus) = &self.chorus {
+            check((0.1..=10.0).contains(&chorus.rate), "chorus.rate must be 0.1-10 Hz")?;
+            unit(chorus.depth, "chorus.depth")?;
+            check((0.0..=0.8).contains(&chorus.feedback), "chorus.feedback must be 0.0-0.8")?;
+            unit(chorus.stereo_spread, "chorus.stereoSpread")?;
+            unit(chorus.return_level, "chorus.returnLevel")?;
+        }
+        if let Some(velocity) = &self.velocity_curve {
+            check((1..=127).contains(&velocity.min) && (velocity.min..=127).contains(&velocity.max),
+                  "velocityCurve needs 1 <= min <= max <= 127")?;
+            check((MIN_VELOCITY_CURVE..=MAX_VELOCITY_CURVE).contains(&velocity.curve), "velocityCurve.curve must be 0.1-10")?;
+        }
+        if let Some(mode) = &self.system_mode {
+            check(MidiSystemMode::from_name(mode).is_some(), "systemMode must be gm, gm2, gs or xg")?;
+        }
+        Ok(())
+    }
+
+    /// Apply to a running engine (the sample rate is fixed once the bridge exists)
+    /// The device profile goes first so the polyphony cap lands on top of it
+    pub fn apply(&self, bridge: &mut AudioWorkletBridge) {
+        if let Some(profile) = &self.device_profile {
+            bridge.set_device_profile(profile);
+        }
+        if let Some(voices) = self.polyphony {
+            bridge.set_polyphony_limit(voices);
+        }
+        let player = bridge.midi_player_mut();
+        if let Some(reverb) = self.reverb {
+            player.configure_reverb(reverb.room_size, reverb.damping, reverb.diffusion, reverb.return_level);
+        }
+        if let Some(chorus) = self.chorus {
+            player.configure_chorus(chorus.rate, chorus.depth, chorus.feedback, chorus.stereo_spread, chorus.return_level);
+        }
+        if let Some(velocity) = self.velocity_curve {
+            for channel in 0..16 {
+                player.set_velocity_map(channel, velocity);
+            }
+        }
+        if let Some(mode) = self.system_mode.as_deref().and_then(MidiSystemMode::from_name) {
+            player.set_system_mode(mode);
+        }
+        if let Some(enabled) = self.fallback_bank {
+            player.set_fallback_bank_enabled(enabled);
+        }
+    }
+}
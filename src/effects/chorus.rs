@@ -219,6 +219,13 @@ impl ChorusProcessor {
         self.wet_level = wet_level.clamp(0.0, 1.0);
         // Chorus wet level debug removed
     }
+    
+    /// Silence the chorus: zero every delay line (the LFO keeps its phase)
+    pub fn clear(&mut self) {
+        for delay_line in self.delay_lines.iter_mut() {
+            delay_line.clear();
+        }
+    }
 }
 
 impl ChorusBus {
@@ -245,12 +252,12 @@ impl ChorusBus {
     /// 
     /// # Arguments
     /// * `dry_signal` - Voice's dry audio output
-    /// * `send_level` - Voice's chorus send amount (0.0-1.0)
+    /// * `send_level` - Voice's chorus send (SF2 generator 92, 0.0-1.0), plus the channel's CC93 share (up to CONTROLLER_SEND_RANGE)
     /// * `channel` - MIDI channel (0-15) for channel-specific send control
     pub fn add_voice_send(&mut self, dry_signal: f32, send_level: f32, channel: u8) {
         let channel_idx = (channel as usize).min(15);
         let channel_send = self.channel_send_levels[channel_idx];
-        let total_send = send_level * channel_send * self.master_send_level;
+        let total_send = (send_level + channel_send * super::CONTROLLER_SEND_RANGE).min(1.0) * self.master_send_level;
         
         // Accumulate chorus input for this audio frame
         self.chorus_input_accumulator += dry_signal * total_send;
@@ -269,6 +276,18 @@ impl ChorusBus {
         wet_signal * self.return_level
     }
     
+    /// Drop the chorus delay contents and any send not yet processed (seek, stop, preset change)
+    pub fn flush(&mut self) {
+        self.chorus_processor.clear();
+        self.chorus_input_accumulator = 0.0;
+    }
+    
+    /// Flush and start the LFO over from phase 0, so what follows renders the same every time
+    pub fn restart(&mut self) {
+        self.flush();
+        self.chorus_processor.lfo.phase = 0.0;
+    }
+    
     /// Set MIDI channel chorus send level (MIDI CC 93)
     pub fn set_channel_send(&mut self, channel: u8, send_level: f32) {
         let channel_idx = (channel as usize).min(15);
@@ -351,6 +370,13 @@ impl ModulatedDelayLine {
     pub fn set_feedback(&mut self, feedback: f32) {
         self.feedback = feedback.clamp(0.0, 0.9);
     }
+    
+    /// Zero the buffer and the feedback sample
+    pub fn clear(&mut self) {
+        self.buffer.fill(0.0);
+        self.write_pos = 0;
+        self.previous_output = 0.0;
+    }
 }
 
 impl ChorusLFO {
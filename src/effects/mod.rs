@@ -3,4 +3,7 @@
 pub mod filter;
 pub mod modulation; // Phase 14A - Modulation routing system
 pub mod reverb; // Phase 15A - Global reverb with send/return architecture
-pub mod chorus; // Phase 15B - Global chorus with send/return architecture
\ No newline at end of file
+pub mod chorus; // Phase 15B - Global chorus with send/return architecture
+
+/// Send a channel's CC91/CC93 adds at full travel: the SF2 default modulators' 200 (0.1% units)
+pub const CONTROLLER_SEND_RANGE: f32 = 0.2;
\ No newline at end of file
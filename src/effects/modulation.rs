@@ -6,9 +6,24 @@
  * - Modulation destinations: Filter cutoff, pitch, amplitude, LFO frequency
  * - Proper scaling and depth control for each modulation type
  * - Real-time parameter updates without artifacts
+ *
+ * Host routes: a ModulationMatrix holds routes added at runtime (mod wheel
+ * to filter, aftertouch to tremolo, ...), global or per channel. Voices copy
+ * the routes for their channel at note-on and whenever the matrix changes.
+ * Unipolar sources pass through the route's curve; `amount` (-1.0 to 1.0)
+ * is a fraction of the destination's full scale.
  */
 
 use crate::log;
+use crate::soundfont::modulators::{CurveType, SourceCurve};
+
+/// Routes per scope (global, or one channel)
+pub const MAX_MATRIX_ROUTES: usize = 8;
+/// Number of ModulationSource variants (size of the source value table)
+pub const MODULATION_SOURCE_COUNT: usize = 8;
+
+/// Linear unipolar positive mapping (the default route curve)
+pub const LINEAR_CURVE: SourceCurve = SourceCurve { curve: CurveType::Linear, negative: false, bipolar: false };
 
 /// Modulation sources available in EMU8000
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -18,9 +33,30 @@ pub enum ModulationSource {
     Lfo2,               // LFO2 (vibrato)
     Velocity,           // MIDI velocity (0-127)
     KeyNumber,          // MIDI key number (0-127)
+    ModWheel,           // CC1
+    Aftertouch,         // Channel pressure
     None,               // No modulation
 }
 
+impl ModulationSource {
+    /// Source for a host route ("modWheel", "aftertouch", "velocity", "key", "modEnvelope")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "modWheel" => Some(Self::ModWheel),
+            "aftertouch" => Some(Self::Aftertouch),
+            "velocity" => Some(Self::Velocity),
+            "key" => Some(Self::KeyNumber),
+            "modEnvelope" => Some(Self::ModulationEnvelope),
+            _ => None,
+        }
+    }
+
+    /// LFOs swing -1.0 to 1.0 and skip the route curve
+    pub fn is_bipolar(self) -> bool {
+        matches!(self, Self::Lfo1 | Self::Lfo2)
+    }
+}
+
 /// Modulation destinations for synthesis parameters
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ModulationDestination {
@@ -29,11 +65,50 @@ pub enum ModulationDestination {
     Pitch,              // Sample/oscillator pitch
     Amplitude,          // Voice amplitude (tremolo)
     LfoFrequency,       // LFO frequency modulation
+    Tremolo,            // LFO1 depth on amplitude
+    Vibrato,            // LFO2 depth on pitch
     None,               // No destination
 }
 
+impl ModulationDestination {
+    /// Destination for a host route ("pitch", "filterCutoff", "amplitude", "tremolo", "vibrato")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "pitch" => Some(Self::Pitch),
+            "filterCutoff" => Some(Self::FilterCutoff),
+            "amplitude" => Some(Self::Amplitude),
+            "tremolo" => Some(Self::Tremolo),
+            "vibrato" => Some(Self::Vibrato),
+            _ => None,
+        }
+    }
+
+    /// Change an amount of 1.0 makes at full source: semitones for pitch, cutoff and
+    /// vibrato depth; gain for amplitude; amplitude swing for tremolo
+    pub fn full_scale(self) -> f32 {
+        match self {
+            Self::Pitch => 12.0,
+            Self::FilterCutoff => 48.0,
+            Self::Vibrato => 2.0,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Route curve by name ("linear", "concave", "convex", "switch")
+pub fn curve_from_name(name: &str) -> Option<SourceCurve> {
+    let curve = match name {
+        "linear" => CurveType::Linear,
+        "concave" => CurveType::Concave,
+        "convex" => CurveType::Convex,
+        "switch" => CurveType::Switch,
+        _ => return None,
+    };
+    Some(SourceCurve { curve, ..LINEAR_CURVE })
+}
+
 /// Modulation routing connection with depth and scaling
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct ModulationRoute {
     /// Modulation source
     pub source: ModulationSource,
@@ -43,6 +118,21 @@ pub struct ModulationRoute {
     pub depth: f32,
     /// Parameter scaling for different destination types
     pub scaling: f32,
+    /// Shape applied to unipolar sources
+    pub curve: SourceCurve,
+}
+
+impl ModulationRoute {
+    /// Host route: `amount` of the destination's full scale through `curve`
+    pub fn new(source: ModulationSource, destination: ModulationDestination, amount: f32, curve: SourceCurve) -> Self {
+        ModulationRoute {
+            source,
+            destination,
+            depth: if amount.is_finite() { amount.clamp(-1.0, 1.0) } else { 0.0 },
+            scaling: destination.full_scale(),
+            curve,
+        }
+    }
 }
 
 /// Modulation router for connecting sources to destinations
@@ -51,7 +141,7 @@ pub struct ModulationRouter {
     /// Active modulation routes
     pub routes: Vec<ModulationRoute>,
     /// Current modulation source values
-    pub source_values: [f32; 6], // Index matches ModulationSource enum
+    pub source_values: [f32; MODULATION_SOURCE_COUNT], // Index matches ModulationSource enum
 }
 
 impl ModulationRouter {
@@ -60,8 +150,8 @@ impl ModulationRouter {
         log("ModulationRouter created with default EMU8000 routing");
         
         ModulationRouter {
-            routes: Vec::new(),
-            source_values: [0.0; 6], // Initialize all sources to 0
+            routes: Vec::with_capacity(MAX_MATRIX_ROUTES * 2), // Global + channel routes without reallocating
+            source_values: [0.0; MODULATION_SOURCE_COUNT], // Initialize all sources to 0
         }
     }
     
@@ -74,6 +164,7 @@ impl ModulationRouter {
             destination,
             depth: clamped_depth,
             scaling,
+            curve: LINEAR_CURVE,
         };
         
         self.routes.push(route);
@@ -96,6 +187,25 @@ impl ModulationRouter {
         }
     }
     
+    /// Replace the routes with a voice's global and channel routes (no allocation)
+    pub fn set_routes(&mut self, global: &[ModulationRoute], channel: &[ModulationRoute]) {
+        self.routes.clear();
+        self.routes.extend_from_slice(global);
+        self.routes.extend_from_slice(channel);
+    }
+    
+    /// Sum of all routes to `destination`, in the destination's units
+    pub fn route_amount(&self, destination: ModulationDestination) -> f32 {
+        self.routes.iter()
+            .filter(|route| route.destination == destination)
+            .map(|route| {
+                let value = self.source_values[route.source as usize];
+                let shaped = if route.source.is_bipolar() { value } else { route.curve.map(value) };
+                shaped * route.depth * route.scaling
+            })
+            .sum()
+    }
+    
     /// Calculate modulated parameter value for a destination
     pub fn get_modulated_value(&self, destination: ModulationDestination, base_value: f32) -> f32 {
         let mut modulated_value = base_value;
@@ -131,6 +241,8 @@ impl ModulationRouter {
                         let freq_multiplier = 1.0 + modulation_amount;
                         modulated_value *= freq_multiplier.max(0.01); // Prevent zero frequency
                     },
+                    // Depth destinations are read through route_amount
+                    ModulationDestination::Tremolo | ModulationDestination::Vibrato => {},
                     ModulationDestination::None => {}, // No modulation
                 }
             }
@@ -138,4 +250,72 @@ impl ModulationRouter {
         
         modulated_value
     }
-}
\ No newline at end of file
+}
+
+/// Host-defined routes: global ones apply to every channel, channel ones add to them
+#[derive(Debug, Clone, Default)]
+pub struct ModulationMatrix {
+    global: Vec<ModulationRoute>,
+    channels: [Vec<ModulationRoute>; 16],
+}
+
+impl ModulationMatrix {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn scope_mut(&mut self, channel: Option<u8>) -> Option<&mut Vec<ModulationRoute>> {
+        match channel {
+            Some(channel) => self.channels.get_mut(channel as usize),
+            None => Some(&mut self.global),
+        }
+    }
+
+    /// Add a route, replacing one with the same source and destination in that scope
+    /// False for a bad channel or a full scope
+    pub fn set_route(&mut self, channel: Option<u8>, route: ModulationRoute) -> bool {
+        let Some(routes) = self.scope_mut(channel) else {
+            return false;
+        };
+        if let Some(existing) = routes.iter_mut()
+            .find(|existing| existing.source == route.source && existing.destination == route.destination) {
+            *existing = route;
+            return true;
+        }
+        if routes.len() >= MAX_MATRIX_ROUTES {
+            return false;
+        }
+        routes.push(route);
+        true
+    }
+
+    /// Remove the route from `source` to `destination`; false if there was none
+    pub fn remove_route(&mut self, channel: Option<u8>, source: ModulationSource, destination: ModulationDestination) -> bool {
+        let Some(routes) = self.scope_mut(channel) else {
+            return false;
+        };
+        let before = routes.len();
+        routes.retain(|route| route.source != source || route.destination != destination);
+        routes.len() != before
+    }
+
+    /// Remove every route of a scope
+    pub fn clear(&mut self, channel: Option<u8>) -> bool {
+        match self.scope_mut(channel) {
+            Some(routes) => {
+                routes.clear();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn global_routes(&self) -> &[ModulationRoute] {
+        &self.global
+    }
+
+    /// Routes of one channel (without the global ones)
+    pub fn channel_routes(&self, channel: u8) -> &[ModulationRoute] {
+        self.channels.get(channel as usize).map_or(&[], Vec::as_slice)
+    }
+}
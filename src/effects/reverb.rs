@@ -12,6 +12,9 @@
 
 use crate::log;
 
+/// Delay tap times as multiples of the room's base delay (golden ratio spacing)
+const TAP_RATIOS: [f32; 6] = [1.0, 1.618, 2.618, 4.236, 6.854, 11.090];
+
 /// Send/Return reverb bus for EMU8000 architecture
 #[derive(Debug, Clone)]
 pub struct ReverbBus {
@@ -115,26 +118,13 @@ impl ReverbProcessor {
     /// Initialize EMU8000 reverb structure with multiple delay lines and filters
     fn initialize_reverb_structure(&mut self) {
         // EMU8000 uses multiple delay taps with different times for spatial effect
-        let base_delay_ms = 20.0 + (self.room_size * 80.0); // 20-100ms base delay
-        
-        // Create 6 delay taps with golden ratio spacing for natural sound
-        let delay_times_ms = vec![
-            base_delay_ms * 1.0,
-            base_delay_ms * 1.618,     // Golden ratio
-            base_delay_ms * 2.618,     // Golden ratio squared  
-            base_delay_ms * 4.236,     // Golden ratio cubed
-            base_delay_ms * 6.854,     // Fibonacci progression
-            base_delay_ms * 11.090,    // Natural reverb spacing
-        ];
-        
-        // Create delay lines with decreasing feedback
-        for (i, &delay_ms) in delay_times_ms.iter().enumerate() {
-            let delay_samples = (delay_ms * self.sample_rate / 1000.0) as usize;
+        // Buffers hold the largest room, so room size changes never reallocate
+        for (i, &ratio) in TAP_RATIOS.iter().enumerate() {
             let feedback = 0.7 - (i as f32 * 0.1); // Decreasing feedback: 0.7, 0.6, 0.5, etc.
-            
-            let delay_line = DelayLine::new(delay_samples, feedback);
+            let delay_line = DelayLine::new(self.tap_delay_samples(1.0, ratio), feedback);
             self.delay_lines.push(delay_line);
         }
+        self.tune_delay_taps();
         
         // Create all-pass filters for diffusion (EMU8000 uses 4 all-pass stages)
         let allpass_delays_ms = vec![5.0, 8.3, 13.7, 21.3]; // Prime-based delays for diffusion
@@ -191,11 +181,27 @@ impl ReverbProcessor {
         comb_output * self.wet_level
     }
     
+    /// Delay of a tap in samples for a room size (20-100ms base delay)
+    fn tap_delay_samples(&self, room_size: f32, ratio: f32) -> usize {
+        let base_delay_ms = 20.0 + (room_size * 80.0);
+        ((base_delay_ms * ratio * self.sample_rate / 1000.0) as usize).max(1)
+    }
+    
+    /// Point the delay taps at the current room size
+    fn tune_delay_taps(&mut self) {
+        for (index, &ratio) in TAP_RATIOS.iter().enumerate() {
+            let delay_samples = self.tap_delay_samples(self.room_size, ratio);
+            if let Some(delay_line) = self.delay_lines.get_mut(index) {
+                delay_line.delay_samples = delay_samples.min(delay_line.buffer.len());
+            }
+        }
+    }
+    
     /// Set room size parameter (affects delay times)
+    /// Retunes the taps in place, so it is safe on the audio thread (MIDI controllers)
     pub fn set_room_size(&mut self, room_size: f32) {
         self.room_size = room_size.clamp(0.0, 1.0);
-        self.initialize_reverb_structure(); // Rebuild with new delays
-        // Reverb room size debug removed
+        self.tune_delay_taps();
     }
     
     /// Set damping parameter (affects high-frequency decay)
@@ -223,6 +229,20 @@ impl ReverbProcessor {
         self.wet_level = wet_level.clamp(0.0, 1.0);
         // Reverb wet level debug removed
     }
+    
+    /// Silence the reverb tail: zero every delay line and filter state
+    /// Parameters and structure are kept, so the next input starts from an empty room
+    pub fn clear(&mut self) {
+        for delay_line in self.delay_lines.iter_mut() {
+            delay_line.clear();
+        }
+        for allpass in self.allpass_filters.iter_mut() {
+            allpass.delay_line.clear();
+        }
+        for comb in self.comb_filters.iter_mut() {
+            comb.clear();
+        }
+    }
 }
 
 impl ReverbBus {
@@ -248,12 +268,12 @@ impl ReverbBus {
     /// 
     /// # Arguments
     /// * `dry_signal` - Voice's dry audio output
-    /// * `send_level` - Voice's reverb send amount (0.0-1.0)
+    /// * `send_level` - Voice's reverb send (SF2 generator 91, 0.0-1.0), plus the channel's CC91 share (up to CONTROLLER_SEND_RANGE)
     /// * `channel` - MIDI channel (0-15) for channel-specific send control
     pub fn add_voice_send(&mut self, dry_signal: f32, send_level: f32, channel: u8) {
         let channel_idx = (channel as usize).min(15);
         let channel_send = self.channel_send_levels[channel_idx];
-        let total_send = send_level * channel_send * self.master_send_level;
+        let total_send = (send_level + channel_send * super::CONTROLLER_SEND_RANGE).min(1.0) * self.master_send_level;
         
         // Accumulate reverb input for this audio frame
         self.reverb_input_accumulator += dry_signal * total_send;
@@ -272,6 +292,12 @@ impl ReverbBus {
         wet_signal * self.return_level
     }
     
+    /// Drop the reverb tail and any send not yet processed (seek, stop, preset change)
+    pub fn flush(&mut self) {
+        self.reverb_processor.clear();
+        self.reverb_input_accumulator = 0.0;
+    }
+    
     /// Set MIDI channel reverb send level (MIDI CC 91)
     pub fn set_channel_send(&mut self, channel: u8, send_level: f32) {
         let channel_idx = (channel as usize).min(15);
@@ -325,6 +351,12 @@ impl DelayLine {
         
         delayed_sample
     }
+    
+    /// Zero the buffer and rewind the write position
+    pub fn clear(&mut self) {
+        self.buffer.fill(0.0);
+        self.write_pos = 0;
+    }
 }
 
 impl AllPassFilter {
@@ -376,4 +408,10 @@ impl CombFilter {
     pub fn set_damping(&mut self, damping: f32) {
         self.damping_filter = damping.clamp(0.0, 1.0);
     }
+    
+    /// Zero the delay line and the damping filter's memory
+    pub fn clear(&mut self) {
+        self.delay_line.clear();
+        self.damping_state = 0.0;
+    }
 }
\ No newline at end of file
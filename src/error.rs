@@ -1,3 +1,7 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::Once;
+use serde::Serialize;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -24,4 +28,139 @@ impl std::fmt::Display for AweError {
 
 impl std::error::Error for AweError {}
 
-pub type AweResult<T> = Result<T, AweError>;
\ No newline at end of file
+pub type AweResult<T> = Result<T, AweError>;
+
+// ===== EXPORT ERROR BOUNDARY =====
+//
+// Exports report failures as ErrorEvents instead of logging: Result exports
+// throw the event's JSON, and every event (including a panic caught by the
+// hook, just before the WASM instance traps) goes to the error callback.
+// ExportScope records which export is running so events can name it.
+
+/// Machine-readable category in `ErrorEvent`s
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum ErrorCode {
+    Panic,              // Rust panic; the instance is unusable afterwards
+    NotInitialized,     // init_audio_worklet has not run
+    InvalidArgument,
+    Internal,
+}
+
+/// Structured export failure (error callback payload and thrown value)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ErrorEvent {
+    pub code: ErrorCode,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub export: Option<String>,     // Export running when the error occurred
+}
+
+impl ErrorEvent {
+    /// Build an event for the running export and deliver it to the error callback
+    pub fn raise(code: ErrorCode, message: impl Into<String>) -> Self {
+        let event = ErrorEvent {
+            code,
+            message: message.into(),
+            export: current_export().map(str::to_string),
+        };
+        emit(&event);
+        event
+    }
+
+    pub fn not_initialized() -> Self {
+        Self::raise(ErrorCode::NotInitialized, "AudioWorklet bridge not initialized")
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self)
+            .unwrap_or_else(|_| r#"{"code":"internal","message":"Serialization failed"}"#.to_string())
+    }
+}
+
+impl From<ErrorEvent> for JsValue {
+    fn from(event: ErrorEvent) -> Self {
+        JsValue::from_str(&event.to_json())
+    }
+}
+
+pub type ErrorListener = Rc<dyn Fn(&ErrorEvent)>;
+
+thread_local! {
+    static ERROR_LISTENER: RefCell<Option<ErrorListener>> = const { RefCell::new(None) };
+    static CURRENT_EXPORT: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// Install (or with None, remove) the error listener
+pub fn set_error_listener(listener: Option<ErrorListener>) {
+    ERROR_LISTENER.with(|slot| *slot.borrow_mut() = listener);
+}
+
+fn emit(event: &ErrorEvent) {
+    // try_borrow: a panic may strike while the slot is being replaced
+    let listener = ERROR_LISTENER
+        .try_with(|slot| slot.try_borrow().ok().and_then(|listener| listener.clone()))
+        .ok()
+        .flatten();
+    if let Some(listener) = listener {
+        listener(event);
+    }
+}
+
+/// Export currently executing on this thread, if it entered an ExportScope
+pub fn current_export() -> Option<&'static str> {
+    CURRENT_EXPORT.try_with(Cell::get).ok().flatten()
+}
+
+/// Names the running export for error events until dropped (nests)
+pub struct ExportScope {
+    previous: Option<&'static str>,
+}
+
+impl ExportScope {
+    pub fn enter(export: &'static str) -> Self {
+        ExportScope { previous: CURRENT_EXPORT.with(|current| current.replace(Some(export))) }
+    }
+}
+
+impl Drop for ExportScope {
+    fn drop(&mut self) {
+        let _ = CURRENT_EXPORT.try_with(|current| current.set(self.previous));
+    }
+}
+
+/// Route panics to the error listener (installed once; the previous hook still runs)
+pub fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let payload = info.payload();
+            let message = payload.downcast_ref::<&str>().copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("panic");
+            let message = match info.location() {
+                Some(location) => format!("{} ({}:{})", message, location.file(), location.line()),
+                None => message.to_string(),
+            };
+            ErrorEvent::raise(ErrorCode::Panic, message);
+            previous(info);
+        }));
+    });
+}
+
+/// Receive export errors and panics as JSON strings (`ErrorEvent`); pass null/undefined to stop
+/// Registering a callback also installs the panic hook
+#[wasm_bindgen]
+pub fn set_error_callback(callback: Option<js_sys::Function>) {
+    install_panic_hook();
+    set_error_listener(callback.map(|callback| {
+        Rc::new(move |event: &ErrorEvent| {
+            let _ = callback.call1(&JsValue::NULL, &JsValue::from_str(&event.to_json()));
+        }) as ErrorListener
+    }));
+}
@@ -0,0 +1,154 @@
+/**
+ * AWE Player - Load Lifecycle Events
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Structured notifications emitted while SoundFont and MIDI files load, so
+ * UIs can draw real progress bars instead of spinners. A load always opens
+ * with `started` and ends with exactly one `complete` or `failed`; in
+ * between come `progress` events with a monotonically increasing percent,
+ * plus `samplesLoaded` / `presetsIndexed` once those counts are known.
+ *
+ * One listener is registered per WASM instance (set_load_event_callback);
+ * it receives each event as a JSON string tagged by `type`.
+ */
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use crate::soundfont::SoundFontError;
+
+/// What is being loaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum LoadKind {
+    SoundFont,
+    Midi,
+}
+
+/// Phase a `progress` event belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum LoadStage {
+    Download,   // Network transfer (load_soundfont_from_url only)
+    Container,  // RIFF / MThd structure
+    Info,       // SF2 INFO header
+    Samples,    // Sample headers and data
+    Presets,    // Preset and instrument zones
+    Tracks,     // MIDI track events
+    Engine,     // Handing the result to the synthesizer
+}
+
+/// Machine-readable reason in `failed` events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum LoadErrorCode {
+    Network,
+    Cancelled,
+    InvalidFormat,      // Not an SF2 / SMF file
+    CorruptData,        // Recognized container with broken chunks or records
+    NotInitialized,     // Engine not running
+    EngineRejected,     // Parsed, but the synthesizer refused it
+}
+
+impl LoadErrorCode {
+    pub fn from_soundfont_error(error: &SoundFontError) -> Self {
+        match error {
+            SoundFontError::InvalidFormat { .. } => LoadErrorCode::InvalidFormat,
+            SoundFontError::IoError { operation, .. } if operation == "cancel" => LoadErrorCode::Cancelled,
+            SoundFontError::IoError { .. } => LoadErrorCode::Network,
+            SoundFontError::ResourceError { .. } => LoadErrorCode::EngineRejected,
+            SoundFontError::RiffError { .. }
+            | SoundFontError::SampleError { .. }
+            | SoundFontError::PresetError { .. }
+            | SoundFontError::GeneratorError { .. } => LoadErrorCode::CorruptData,
+        }
+    }
+}
+
+/// One load lifecycle notification
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum LoadEvent {
+    Started {
+        kind: LoadKind,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "ts", ts(optional))]
+        total_bytes: Option<usize>,
+    },
+    Progress {
+        kind: LoadKind,
+        stage: LoadStage,
+        percent: f32,                   // Whole load, 0-100
+    },
+    SamplesLoaded { count: usize },
+    PresetsIndexed { presets: usize, instruments: usize },
+    Complete { kind: LoadKind },
+    Failed {
+        kind: LoadKind,
+        code: LoadErrorCode,
+        message: String,
+    },
+}
+
+impl LoadEvent {
+    pub fn progress(kind: LoadKind, stage: LoadStage, percent: f32) -> Self {
+        LoadEvent::Progress { kind, stage, percent: percent.clamp(0.0, 100.0) }
+    }
+
+    pub fn failed(kind: LoadKind, code: LoadErrorCode, message: impl Into<String>) -> Self {
+        LoadEvent::Failed { kind, code, message: message.into() }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self)
+            .unwrap_or_else(|_| r#"{"type":"failed","kind":"soundFont","code":"corruptData","message":"Serialization failed"}"#.to_string())
+    }
+}
+
+pub type LoadListener = Rc<dyn Fn(&LoadEvent)>;
+
+// JS functions are not Send, so the listener lives with the thread that registered it
+thread_local! {
+    static LISTENER: RefCell<Option<LoadListener>> = const { RefCell::new(None) };
+}
+
+/// Install (or with None, remove) the load event listener
+pub fn set_listener(listener: Option<LoadListener>) {
+    LISTENER.with(|slot| *slot.borrow_mut() = listener);
+}
+
+/// Deliver an event to the listener, if one is registered
+pub fn emit(event: LoadEvent) {
+    // Release the slot before calling out so the listener may replace itself
+    let listener = LISTENER.with(|slot| slot.borrow().clone());
+    if let Some(listener) = listener {
+        listener(&event);
+    }
+}
+
+/// Overall percent at which parsing ends and the engine hand-off begins
+pub const PARSE_END_PERCENT: f32 = 90.0;
+
+/// Progress reporter for a parse covering `start_percent`..PARSE_END_PERCENT of a load
+pub fn parse_progress(kind: LoadKind, start_percent: f32) -> impl FnMut(LoadStage, f32) {
+    move |stage, fraction| {
+        emit(LoadEvent::progress(kind, stage, start_percent + (PARSE_END_PERCENT - start_percent) * fraction))
+    }
+}
+
+// ===== WASM EXPORTS =====
+
+/// Receive load lifecycle events as JSON strings (`LoadEvent`); pass null/undefined to stop
+#[wasm_bindgen]
+pub fn set_load_event_callback(callback: Option<js_sys::Function>) {
+    set_listener(callback.map(|callback| {
+        Rc::new(move |event: &LoadEvent| {
+            let _ = callback.call1(&JsValue::NULL, &JsValue::from_str(&event.to_json()));
+        }) as LoadListener
+    }));
+}
@@ -9,13 +9,42 @@ pub mod soundfont;
 pub mod effects;
 pub mod worklet;
 pub mod audio;
+pub mod protocol;
+pub mod events;
+pub mod self_test;
+pub mod config;
 
 use midi::sequencer::{MidiSequencer, PlaybackState};
 use midi::constants::*;
-use synth::voice_manager::VoiceManager;
+use midi::bank_select::{parse_system_sysex, MidiSystemMode, SystemSysEx};
+use midi::transport::{self as transport, TransportCommand};
+use midi::effects_controller::{MIDI_CC_CHORUS_SEND, MIDI_CC_REVERB_SEND};
+use midi::effects_cc_map::{EffectsCcMapping, EffectsParameter};
+use synth::voice_manager::{DebugSolo, VoiceManager};
+use synth::hardware_mode::SynthesisMode;
+use synth::multizone_voice::PlaybackDirection;
+use synth::performance::PerformanceZone;
+use synth::snapshot::EngineSnapshot;
+use synth::velocity_map::VelocityMap;
+use synth::voice_stats::VoiceStatsReport;
+use synth::mod_envelope::FilterEnvelopeShape;
+use synth::lfo::LfoPhaseMode;
+use synth::mod_wheel::ModWheelVibrato;
+use synth::tuning::STANDARD_A4_HZ;
+use effects::modulation::{curve_from_name, ModulationDestination, ModulationRoute, ModulationSource};
+use soundfont::preload::PreloadReport;
+use soundfont::drum_map::DrumKitMap;
 use soundfont::SoundFont;
+use audio::metronome::Metronome;
+use audio::auto_gain::AutoGain;
+use audio::fade::{FadeState, FadeStatus, MasterFade};
+use error::{AweResult, ErrorCode, ErrorEvent, ExportScope};
+use midi::event_trace::{DropReason, EventAction, EventSource, EventTrace, TraceEntry, TraceEntryReport};
+use midi::recording::{RecordingStatus, SequenceRecorder};
+use midi::humanize::{ChannelHumanize, Humanizer};
+use midi::automation::{AutomationInterpolation, AutomationLane, AutomationLanes, AutomationParameter, AutomationPoint};
 
-static MIDI_EVENT_QUEUE: OnceLock<Mutex<VecDeque<MidiEvent>>> = OnceLock::new();
+static MIDI_EVENT_QUEUE: OnceLock<Mutex<VecDeque<(MidiEvent, EventSource)>>> = OnceLock::new();
 
 // Temporary no-op log function to prevent build errors while removing old debug system
 pub fn log(_message: &str) {
@@ -25,7 +54,9 @@ pub fn log(_message: &str) {
 #[wasm_bindgen]
 #[derive(Clone, Copy, Debug)]
 #[derive(serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export, rename = "MidiEventData"))]
 pub struct MidiEvent {
+    #[cfg_attr(feature = "ts", ts(type = "number"))] // JSON number; the wasm-bindgen class uses bigint
     pub timestamp: u64,
     pub channel: u8,
     pub message_type: u8,
@@ -46,6 +77,16 @@ pub struct MidiPlayer {
     sequencer: MidiSequencer,
     voice_manager: VoiceManager,
     current_sample: u64,
+    high_res_velocity_prefix: [Option<u8>; 16], // Pending CC88 LSB per channel
+    metronome: Metronome,
+    auto_gain: AutoGain,
+    fade: MasterFade,
+    event_trace: EventTrace,
+    recorder: SequenceRecorder,
+    humanizer: Humanizer,
+    live_mode: bool,  // Live input handled on arrival, ahead of queued playback
+    automation: AutomationLanes,
+    automation_tick: f64,  // Song position of the next sample, for automation lanes
 }
 
 #[wasm_bindgen]
@@ -60,20 +101,32 @@ impl MidiPlayer {
             sequencer: MidiSequencer::new(44100.0), // 44.1kHz sample rate
             voice_manager: VoiceManager::new(44100.0),
             current_sample: 0,
+            high_res_velocity_prefix: [None; 16],
+            metronome: Metronome::new(44100.0),
+            auto_gain: AutoGain::new(44100.0),
+            fade: MasterFade::new(44100.0),
+            event_trace: EventTrace::default(),
+            recorder: SequenceRecorder::new(),
+            humanizer: Humanizer::new(44100.0),
+            live_mode: false,
+            automation: AutomationLanes::new(),
+            automation_tick: 0.0,
         }
     }
     
     #[wasm_bindgen]
     pub fn queue_midi_event(&mut self, event: MidiEvent) {
+        self.queue_event_from(event, EventSource::Live);
+    }
+    
+    fn queue_event_from(&mut self, event: MidiEvent, source: EventSource) {
         let queue = MIDI_EVENT_QUEUE.get().expect("MIDI queue should be initialized");
         if let Ok(mut queue) = queue.lock() {
             if queue.len() >= 1000 {
                 queue.pop_front();
                 log("MIDI queue full - dropped oldest event");
             }
-            queue.push_back(event);
-            log(&format!("MIDI event queued: ch={} type={} data={},{} @{}", 
-                event.channel, event.message_type, event.data1, event.data2, event.timestamp));
+            queue.push_back((event, source));
         }
     }
     
@@ -82,19 +135,29 @@ impl MidiPlayer {
         let mut processed_count = 0;
         let queue = MIDI_EVENT_QUEUE.get().expect("MIDI queue should be initialized");
         if let Ok(mut queue) = queue.lock() {
-            while let Some(event) = queue.front() {
-                if event.timestamp <= current_sample_time {
-                    let event = queue.pop_front().unwrap();
-                    
-                    // Process MIDI event through VoiceManager
-                    self.handle_midi_event(&event);
-                    
-                    log(&format!("Processing MIDI event: ch={} type=0x{:02X} data={},{} @{}", 
-                        event.channel, event.message_type, event.data1, event.data2, event.timestamp));
-                    processed_count += 1;
+            loop {
+                // Live mode: live input goes first, on arrival, whatever its timestamp
+                let live = if self.live_mode {
+                    queue.iter().position(|(_, source)| *source == EventSource::Live)
                 } else {
+                    None
+                };
+                let Some(index) = live.or_else(|| {
+                    queue.front().filter(|(event, _)| event.timestamp <= current_sample_time).map(|_| 0)
+                }) else {
                     break;
+                };
+                let Some((event, source)) = queue.remove(index) else {
+                    break;
+                };
+                if source == EventSource::Live {
+                    self.record_live_event(&event);
                 }
+                
+                // Process MIDI event through VoiceManager
+                self.handle_traced_event(&event, source);
+                
+                processed_count += 1;
             }
         }
         processed_count
@@ -112,84 +175,138 @@ impl MidiPlayer {
         (2.0 * PI * frequency * time / sample_rate).sin() * 0.1
     }
     
-    #[wasm_bindgen]
-    pub fn test_envelope_system(&mut self) -> String {
-        log("Testing EMU8000 6-stage DAHDSR envelope system...");
-        
-        // Test 1: Trigger note and process envelope for several samples
-        let note = 60; // Middle C
-        let velocity = 100;
-        
-        if let Some(voice_id) = self.voice_manager.note_on(note, velocity, 0) {
-            log(&format!("Test: Note {} triggered on voice {}", note, voice_id));
-            
-            // Process 10 samples and collect envelope values
-            let mut envelope_values = Vec::new();
-            for i in 0..10 {
-                let active_voices = self.voice_manager.process_envelopes();
-                envelope_values.push(format!("Sample {}: {} active voices", i, active_voices));
-            }
-            
-            // Test 2: Release note and process more samples
-            self.voice_manager.note_off(note);
-            log("Test: Note released");
-            
-            for i in 10..20 {
-                let active_voices = self.voice_manager.process_envelopes();
-                envelope_values.push(format!("Sample {}: {} active voices (released)", i, active_voices));
-            }
-            
-            let result = envelope_values.join(" | ");
-            log(&format!("Envelope test completed: {}", result));
-            result
-        } else {
-            let error = "Failed to allocate voice for envelope test".to_string();
-            log(&error);
-            error
-        }
-    }
-    
     // MIDI Sequencer Controls
     
+    /// Load a Standard MIDI File; throws `invalidArgument` if it does not parse
     #[wasm_bindgen]
-    pub fn load_midi_file(&mut self, data: &[u8]) -> bool {
-        match self.sequencer.load_midi_file(data) {
-            Ok(()) => {
-                log("MIDI file loaded successfully");
-                true
-            },
-            Err(e) => {
-                log(&format!("Failed to load MIDI file: {:?}", e));
-                false
-            }
-        }
+    pub fn load_midi_file(&mut self, data: &[u8]) -> Result<(), ErrorEvent> {
+        let _scope = ExportScope::enter("MidiPlayer.load_midi_file");
+        self.load_midi_data(data)
+            .map_err(|e| ErrorEvent::raise(ErrorCode::InvalidArgument, format!("MIDI file could not be loaded: {}", e)))
     }
     
     #[wasm_bindgen]
     pub fn play(&mut self) {
+        self.fade.cancel();
+        self.apply_song_setup();
+        self.sequencer.play(self.current_sample);
+        self.voice_manager.set_effects_frozen(false);
+    }
+    
+    /// Start playback with the master bus ramping up from silence over `ms`
+    #[wasm_bindgen]
+    pub fn play_with_fade(&mut self, ms: f32) {
+        self.apply_song_setup();
         self.sequencer.play(self.current_sample);
+        self.voice_manager.set_effects_frozen(false);
+        self.fade.fade_in(ms);
+    }
+    
+    /// Ramp the master bus down over `ms`, then stop playback and silence the voices
+    #[wasm_bindgen]
+    pub fn stop_with_fade(&mut self, ms: f32) {
+        self.fade.fade_out(ms);
     }
     
+    /// Pause playback; reverb and chorus tails freeze until play resumes
     #[wasm_bindgen]
     pub fn pause(&mut self) {
         self.sequencer.pause(self.current_sample);
+        if self.sequencer.get_state() == PlaybackState::Paused {
+            self.voice_manager.set_effects_frozen(true);
+        }
     }
     
+    /// Stop playback: voices fade out over the minimum release and effect tails are flushed
     #[wasm_bindgen]
     pub fn stop(&mut self) {
+        self.finish_recording(self.sequencer.get_current_tick());
         self.sequencer.stop();
+        self.humanizer.clear();
+        self.voice_manager.stop_all_sound();
+    }
+    
+    /// Clear reverb and chorus tails immediately (stop, seek and preset changes do this too)
+    #[wasm_bindgen]
+    pub fn flush_effects(&mut self) {
+        self.voice_manager.flush_effects();
+    }
+    
+    /// Reverb room and return level (internal method)
+    pub(crate) fn configure_reverb(&mut self, room_size: f32, damping: f32, diffusion: f32, return_level: f32) {
+        self.voice_manager.configure_reverb(room_size, damping, diffusion);
+        self.voice_manager.set_reverb_return_level(return_level);
     }
     
+    /// Chorus modulation and return level (internal method)
+    pub(crate) fn configure_chorus(&mut self, rate: f32, depth: f32, feedback: f32, stereo_spread: f32, return_level: f32) {
+        self.voice_manager.configure_chorus(rate, depth, feedback, stereo_spread);
+        self.voice_manager.set_chorus_return_level(return_level);
+    }
+    
+    /// Map a controller to a global effect parameter, None removes the mapping (internal method)
+    pub(crate) fn set_effects_cc_mapping(&mut self, controller: u8, mapping: Option<(EffectsParameter, f32, f32)>) -> bool {
+        let map = self.voice_manager.effects_cc_map_mut();
+        match mapping {
+            Some((parameter, min, max)) => map.set(controller, parameter, min, max),
+            None => map.remove(controller),
+        }
+    }
+    
+    /// Controllers mapped to effect parameters (internal method)
+    pub(crate) fn effects_cc_mappings(&self) -> Vec<EffectsCcMapping> {
+        self.voice_manager.effects_cc_map().mappings()
+    }
+    
+    /// Seek (0.0-1.0): effect tails are flushed and controllers chased to the new position
     #[wasm_bindgen]
     pub fn seek(&mut self, position: f64) {
         self.sequencer.seek(position, self.current_sample);
+        self.chase_sequencer();
     }
     
+    /// Playback speed without changing pitch, clamped to 0.25-4.0; glides in over 50 ms
     #[wasm_bindgen]
     pub fn set_tempo_multiplier(&mut self, multiplier: f64) {
         self.sequencer.set_tempo_multiplier(multiplier);
     }
     
+    /// Current tempo multiplier, including a glide or ramp in progress
+    #[wasm_bindgen]
+    pub fn get_tempo_multiplier(&self) -> f64 {
+        self.sequencer.get_tempo_multiplier()
+    }
+    
+    /// Ramp the tempo multiplier to `target` between two ticks (accelerando/ritardando)
+    /// Returns false if end_tick < start_tick, the target isn't a number or 16 ramps are waiting
+    #[wasm_bindgen]
+    pub fn schedule_tempo_ramp(&mut self, start_tick: u32, end_tick: u32, target: f64) -> bool {
+        self.sequencer.schedule_tempo_ramp(start_tick as u64, end_tick as u64, target)
+    }
+    
+    /// Ramp the tempo multiplier to `target` over the next `beats` quarter notes
+    #[wasm_bindgen]
+    pub fn ramp_tempo_multiplier(&mut self, target: f64, beats: f64) -> bool {
+        if !beats.is_finite() || beats < 0.0 {
+            return false;
+        }
+        let start = self.sequencer.get_current_tick();
+        let length = (beats * self.sequencer.get_ticks_per_quarter() as f64).round() as u64;
+        self.sequencer.schedule_tempo_ramp(start, start + length, target)
+    }
+    
+    /// Cancel scheduled and running tempo ramps (the multiplier stays where it is)
+    #[wasm_bindgen]
+    pub fn clear_tempo_ramps(&mut self) {
+        self.sequencer.clear_tempo_ramps();
+    }
+    
+    /// Current playback position in ticks (for scheduling ramps)
+    #[wasm_bindgen]
+    pub fn get_current_tick(&self) -> u32 {
+        self.sequencer.get_current_tick().min(u32::MAX as u64) as u32
+    }
+    
     #[wasm_bindgen]
     pub fn get_playback_state(&self) -> u8 {
         match self.sequencer.get_state() {
@@ -224,115 +341,304 @@ impl MidiPlayer {
         self.sequencer.get_original_tempo_bpm()
     }
     
+    /// Enable/disable the click track (follows the sequencer while playing)
+    #[wasm_bindgen]
+    pub fn set_metronome_enabled(&mut self, enabled: bool) {
+        self.metronome.set_enabled(enabled);
+    }
+    
+    /// Set click levels for the main mix and the click bus (0.0-1.0 each)
+    /// Defaults: 0.0 main (clean mix) and 1.0 bus
+    #[wasm_bindgen]
+    pub fn set_metronome_levels(&mut self, main_level: f32, bus_level: f32) {
+        self.metronome.set_levels(main_level, bus_level);
+    }
+    
     #[wasm_bindgen]
     pub fn advance_time(&mut self, samples: u32) {
         self.current_sample += samples as u64;
-        
+        self.run_sequencer(samples as usize);
+    }
+    
+    /// Run the sequencer up to the current sample and queue what it dispatched (internal method)
+    /// For callers whose rendering has already moved the sample counter on (batch conversion)
+    pub(crate) fn run_sequencer(&mut self, samples: usize) {
         // Process sequencer events
-        let events = self.sequencer.process(self.current_sample, samples as usize);
+        let events = self.sequencer.process(self.current_sample, samples);
+        self.sync_automation();
+        
+        if self.sequencer.get_state() == PlaybackState::Playing {
+            let (ticks_per_beat, beats_per_bar) = self.sequencer.get_beat_grid();
+            self.metronome.advance_to(self.sequencer.get_current_tick(), ticks_per_beat, beats_per_bar);
+        } else {
+            self.metronome.reset();
+        }
         
-        // Convert sequencer events to our MIDI event queue
+        // Convert sequencer events to our MIDI event queue, humanized events held back
+        // earlier going out first
+        while let Some(midi_event) = self.humanizer.next_due(self.current_sample) {
+            self.queue_event_from(midi_event, EventSource::Sequencer);
+        }
         for event in events {
-            let midi_event = match event.event_type {
-                midi::sequencer::ProcessedEventType::NoteOn { channel, note, velocity } => {
-                    MidiEvent::new(self.current_sample, channel, 0x90, note, velocity)
-                },
-                midi::sequencer::ProcessedEventType::NoteOff { channel, note, velocity } => {
-                    MidiEvent::new(self.current_sample, channel, 0x80, note, velocity)
-                },
-                midi::sequencer::ProcessedEventType::ProgramChange { channel, program } => {
-                    MidiEvent::new(self.current_sample, channel, 0xC0, program, 0)
-                },
-                midi::sequencer::ProcessedEventType::ControlChange { channel, controller, value } => {
-                    MidiEvent::new(self.current_sample, channel, 0xB0, controller, value)
-                },
+            if let midi::sequencer::ProcessedEventType::SysEx { data } = &event.event_type {
+                // System messages from the file take effect at once
+                self.apply_system_sysex(data);
+            } else if let Some(midi_event) = self.sequencer_midi_event(&event.event_type) {
+                if let Some(midi_event) = self.humanizer.schedule(midi_event, self.current_sample) {
+                    self.queue_event_from(midi_event, EventSource::Sequencer);
+                }
+            }
+        }
+        
+        if self.recorder.is_active() {
+            match self.sequencer.get_state() {
+                PlaybackState::Playing => {
+                    if let Some(punch_out) = self.recorder.advance(self.sequencer.get_current_tick()) {
+                        self.finish_recording(punch_out);
+                    }
+                }
+                // Playback ran off the end of the song
+                PlaybackState::Stopped => {
+                    self.finish_recording(self.sequencer.get_duration_ticks());
+                }
+                PlaybackState::Paused => {}
+            }
+        }
+    }
+    
+    /// Keep a live note, controller or program change for the recording pass in progress
+    fn record_live_event(&mut self, event: &MidiEvent) {
+        if !self.recorder.is_active() || self.sequencer.get_state() != PlaybackState::Playing {
+            return;
+        }
+        let status = (event.message_type & 0xF0) | (event.channel & 0x0F);
+        if matches!(status & 0xF0, 0x80 | 0x90 | 0xB0 | 0xC0) {
+            self.recorder.capture(self.sequencer.get_current_tick(), [status, event.data1, event.data2]);
+        }
+    }
+    
+    /// End the recording pass at `end_tick` and merge it into its track (or keep it as a
+    /// take in take mode). Returns the number of messages recorded
+    fn finish_recording(&mut self, end_tick: u64) -> usize {
+        if !self.recorder.is_active() {
+            return 0;
+        }
+        let track = self.recorder.track();
+        let messages = self.recorder.finish(end_tick);
+        let count = messages.len();
+        if count == 0 {
+            return 0;
+        }
+        if self.recorder.take_mode() {
+            let take_track = self.recorder.take_track(&messages, end_tick);
+            return match self.sequencer.add_track(take_track) {
+                Ok(index) => {
+                    self.recorder.add_take(index);
+                    self.apply_take_mutes();
+                    count
+                }
+                Err(error) => {
+                    self.recorder.fail(midi::recording::RecordingFailure::TakeNotStored { error });
+                    0
+                }
             };
-            
-            self.queue_midi_event(midi_event);
         }
+        match self.sequencer.edit_track(track, &midi::edit::TrackEdit::InsertMessages { messages }) {
+            Ok(_) => count,
+            Err(error) => {
+                self.recorder.fail(midi::recording::RecordingFailure::NotMerged { track, error });
+                0
+            }
+        }
+    }
+    
+    /// Handle a MIDI event and record it in the event trace
+    fn handle_traced_event(&mut self, event: &MidiEvent, source: EventSource) {
+        let action = self.handle_midi_event(event);
+        self.event_trace.record(TraceEntry {
+            sample: self.current_sample,
+            source,
+            channel: event.channel,
+            status: event.message_type,
+            data1: event.data1,
+            data2: event.data2,
+            action,
+        });
     }
     
-    /// Handle MIDI event and route to VoiceManager
-    fn handle_midi_event(&mut self, event: &MidiEvent) {
+    /// Handle MIDI event and route to VoiceManager; returns what it did for the trace
+    fn handle_midi_event(&mut self, event: &MidiEvent) -> EventAction {
         let message_type = (event.message_type & 0xF0) >> 4;
         
         match message_type {
+            MIDI_EVENT_NOTE_OFF if self.voice_manager.is_preview_input(event.channel) => {
+                self.voice_manager.preview_note_off(event.data1);
+                EventAction::Released
+            },
+            MIDI_EVENT_NOTE_OFF if self.voice_manager.is_performance_channel(event.channel) => {
+                self.voice_manager.performance_note_off(event.data1);
+                EventAction::Released
+            },
             MIDI_EVENT_NOTE_OFF => {
                 // Note Off
                 self.voice_manager.note_off(event.data1);
-                log(&format!("VoiceManager: Note Off - Note {} Ch {}", event.data1, event.channel));
+                EventAction::Released
+            },
+            MIDI_EVENT_NOTE_ON if self.voice_manager.is_preview_input(event.channel) => {
+                // The preview channel plays live input on its own program, clear of the song
+                self.high_res_velocity_prefix[(event.channel & 0x0F) as usize] = None;
+                if event.data2 > MIDI_VELOCITY_MIN {
+                    match self.voice_manager.preview_note_on(event.data1, event.data2) {
+                        Some(voice) => EventAction::VoiceAllocated(voice),
+                        None => EventAction::Dropped(self.voice_manager.note_drop_reason(synth::voice_manager::AUDITION_CHANNEL)),
+                    }
+                } else {
+                    self.voice_manager.preview_note_off(event.data1);
+                    EventAction::Released
+                }
+            },
+            MIDI_EVENT_NOTE_ON if self.voice_manager.is_performance_channel(event.channel) => {
+                // Split/layer zones play the keyboard input on their own presets
+                self.high_res_velocity_prefix[(event.channel & 0x0F) as usize] = None;
+                if event.data2 > MIDI_VELOCITY_MIN {
+                    EventAction::ZoneVoices(self.voice_manager.performance_note_on(event.data1, event.data2))
+                } else {
+                    self.voice_manager.performance_note_off(event.data1);
+                    EventAction::Released
+                }
             },
             MIDI_EVENT_NOTE_ON => {
                 // Note On (check velocity > 0, otherwise treat as Note Off)
+                // CC88 prefix applies only to the Note On that immediately follows it
+                let velocity_lsb = self.high_res_velocity_prefix[(event.channel & 0x0F) as usize].take();
                 if event.data2 > MIDI_VELOCITY_MIN {
-                    match self.voice_manager.note_on(event.data1, event.data2, event.channel) {
-                        Some(voice_id) => {
-                            log(&format!("VoiceManager: Note On - Note {} Vel {} assigned to Voice {}", 
-                                event.data1, event.data2, voice_id));
-                        },
+                    let voice = match velocity_lsb {
+                        Some(lsb) => {
+                            let velocity_14bit = ((event.data2 as u16) << 7) | lsb as u16;
+                            self.voice_manager.note_on_high_res(event.data1, velocity_14bit, event.channel)
+                        }
                         None => {
-                            log(&format!("VoiceManager: Note On failed - No available voices for Note {} Vel {}", 
-                                event.data1, event.data2));
+                            self.voice_manager.note_on(event.data1, event.data2, event.channel)
                         }
+                    };
+                    match voice {
+                        Some(voice) => EventAction::VoiceAllocated(voice),
+                        None => EventAction::Dropped(self.voice_manager.note_drop_reason(event.channel)),
                     }
                 } else {
                     // Velocity 0 = Note Off
                     self.voice_manager.note_off(event.data1);
-                    log(&format!("VoiceManager: Note Off (vel=0) - Note {} Ch {}", event.data1, event.channel));
+                    EventAction::Released
                 }
             },
             MIDI_EVENT_CONTROL_CHANGE => {
                 // Control Change - handle common CC messages
-                match event.data1 {
+                let handled = match event.data1 {
+                    MIDI_CC_BANK_SELECT_MSB => {
+                        self.voice_manager.bank_select_msb(event.channel, event.data2);
+                        true
+                    },
+                    MIDI_CC_BANK_SELECT_LSB => {
+                        self.voice_manager.bank_select_lsb(event.channel, event.data2);
+                        true
+                    },
+                    MIDI_CC_NRPN_MSB | MIDI_CC_RPN_MSB => {
+                        let nrpn = event.data1 == MIDI_CC_NRPN_MSB;
+                        self.voice_manager.parameter_number_msb(event.channel, nrpn, event.data2);
+                        true
+                    },
+                    MIDI_CC_NRPN_LSB | MIDI_CC_RPN_LSB => {
+                        let nrpn = event.data1 == MIDI_CC_NRPN_LSB;
+                        self.voice_manager.parameter_number_lsb(event.channel, nrpn, event.data2);
+                        true
+                    },
+                    MIDI_CC_DATA_ENTRY_MSB => {
+                        self.voice_manager.data_entry_msb(event.channel, event.data2);
+                        true
+                    },
+                    MIDI_CC_DATA_ENTRY_LSB => {
+                        self.voice_manager.data_entry_lsb(event.channel, event.data2);
+                        true
+                    },
+                    MIDI_CC_SOUND_CONTROLLER_1..=MIDI_CC_SOUND_CONTROLLER_10 => {
+                        self.voice_manager.set_sound_controller(event.channel, event.data1, event.data2);
+                        true
+                    },
                     MIDI_CC_MODULATION => {
-                        log(&format!("VoiceManager: Modulation {} (Ch {})", event.data2, event.channel));
-                        // TODO: Apply modulation to active voices
+                        // Mod wheel drives the host modulation routes
+                        self.voice_manager.set_modulation_wheel(event.channel, event.data2);
+                        true
                     },
-                    MIDI_CC_VOLUME => {
-                        log(&format!("VoiceManager: Volume {} (Ch {})", event.data2, event.channel));
-                        // TODO: Apply volume to channel
+                    MIDI_CC_VOLUME | MIDI_CC_PAN | MIDI_CC_EXPRESSION | MIDI_CC_SUSTAIN => {
+                        // Volume/pan/expression glide in the mixer; lifting the sustain pedal
+                        // releases the notes it held
+                        self.voice_manager.set_mixer_controller(event.channel, event.data1, event.data2);
+                        true
                     },
-                    MIDI_CC_PAN => {
-                        log(&format!("VoiceManager: Pan {} (Ch {})", event.data2, event.channel));
-                        // TODO: Apply pan to channel
+                    MIDI_CC_REVERB_SEND | MIDI_CC_CHORUS_SEND => {
+                        self.voice_manager.process_midi_control_change(event.channel, event.data1, event.data2);
+                        true
                     },
-                    MIDI_CC_SUSTAIN => {
-                        let sustain_on = event.data2 >= 64;
-                        log(&format!("VoiceManager: Sustain {} (Ch {})", if sustain_on { "On" } else { "Off" }, event.channel));
-                        // TODO: Apply sustain pedal to active voices
+                    MIDI_CC_HIGH_RES_VELOCITY => {
+                        self.high_res_velocity_prefix[(event.channel & 0x0F) as usize] = Some(event.data2 & 0x7F);
+                        true
                     },
                     MIDI_CC_ALL_SOUND_OFF => {
-                        log(&format!("VoiceManager: All Sound Off (Ch {})", event.channel));
-                        // TODO: Stop all voices immediately
+                        self.voice_manager.all_sound_off(event.channel);
+                        true
+                    },
+                    MIDI_CC_RESET_ALL_CONTROLLERS => {
+                        self.voice_manager.reset_controllers(event.channel);
+                        true
                     },
                     MIDI_CC_ALL_NOTES_OFF => {
-                        log(&format!("VoiceManager: All Notes Off (Ch {})", event.channel));
-                        // TODO: Release all notes (respect sustain)
+                        self.voice_manager.all_notes_off(event.channel);
+                        true
                     },
-                    _ => {
-                        log(&format!("VoiceManager: CC {} = {} (Ch {})", event.data1, event.data2, event.channel));
-                        // TODO: Handle other CC messages
+                    controller => {
+                        // Host-mapped effect parameters (CC94, CC95, ...)
+                        self.voice_manager.apply_effects_controller(controller, event.data2)
                     }
-                }
+                };
+                if handled { EventAction::Applied } else { EventAction::Ignored }
             },
             MIDI_EVENT_PROGRAM_CHANGE => {
                 // Program Change
-                log(&format!("VoiceManager: Program Change {} (Ch {})", event.data1, event.channel));
-                // TODO: Handle program changes for instrument selection
+                match self.voice_manager.program_change(event.channel, event.data1) {
+                    Some(preset) => EventAction::PresetSelected(preset),
+                    None => EventAction::Dropped(DropReason::NoPreset),
+                }
+            },
+            MIDI_EVENT_SYSTEM if event.message_type == MIDI_STATUS_SONG_POSITION => {
+                if let Some(command) = transport::parse_song_position(&[event.message_type, event.data1, event.data2]) {
+                    self.apply_transport(command);
+                    EventAction::Applied
+                } else {
+                    EventAction::Ignored
+                }
+            },
+            MIDI_EVENT_CHANNEL_PRESSURE => {
+                self.voice_manager.set_channel_pressure(event.channel, event.data1);
+                EventAction::Applied
             },
             MIDI_EVENT_PITCH_BEND => {
                 // Pitch Bend - Convert 14-bit value to signed range
                 let pitch_value = ((event.data2 as u16) << 7) | (event.data1 as u16);
                 let signed_bend = pitch_value as i16 - 8192; // Convert to -8192..8191 range
                 
-                log(&format!("VoiceManager: Pitch Bend {} -> {} (Ch {})", pitch_value, signed_bend, event.channel));
-                
                 // Apply pitch bend with standard EMU8000 range (±2 semitones)
                 let bend_semitones = (signed_bend as f32 / 8192.0) * 2.0;
                 self.voice_manager.apply_pitch_bend(event.channel, bend_semitones);
+                if self.voice_manager.is_preview_input(event.channel) {
+                    self.voice_manager.preview_pitch_bend(bend_semitones);
+                } else if self.voice_manager.is_performance_channel(event.channel) {
+                    self.voice_manager.performance_pitch_bend(bend_semitones);
+                }
+                EventAction::Applied
             },
             _ => {
-                log(&format!("VoiceManager: Unhandled MIDI message type 0x{:02X}", message_type));
+                // Unhandled message type
+                EventAction::Ignored
             }
         }
     }
@@ -341,8 +647,10 @@ impl MidiPlayer {
     /// Returns single audio sample (-1.0 to 1.0) combining all active voices
     #[wasm_bindgen]
     pub fn process(&mut self) -> f32 {
+        let _audit = audio::alloc_audit::AuditScope::enter("MidiPlayer::process");
         // Process any pending MIDI events for current sample
         self.process_midi_events(self.current_sample);
+        self.apply_automation();
         
         // Generate stereo audio sample from voice manager
         let (left, right) = self.voice_manager.process();
@@ -355,13 +663,26 @@ impl MidiPlayer {
         let mixed = (left + right);  // Full amplitude mixing
         
         // Apply modern mastering gain for proper output levels (much higher than EMU8000)
-        mixed * 2.5  // 250% gain - way beyond EMU8000 16-bit capability
+        // The mono path has no click bus, only the click's main mix send
+        let (click_main, _) = self.metronome.next_sample();
+        let master_gain = 2.5 * self.next_auto_gain();  // 250% gain - way beyond EMU8000 16-bit capability
+        let fade = self.next_fade_gain();
+        self.finish_output((mixed * master_gain + click_main) * fade)
     }
     
     /// Process one stereo sample (for proper stereo output) - internal use only
     pub(crate) fn process_stereo(&mut self) -> (f32, f32) {
+        let (left, right, _) = self.process_stereo_with_click();
+        (left, right)
+    }
+    
+    /// Process one stereo sample plus the click bus sample - internal use only
+    /// The click's main mix send is already part of left/right
+    pub(crate) fn process_stereo_with_click(&mut self) -> (f32, f32, f32) {
+        let _audit = audio::alloc_audit::AuditScope::enter("MidiPlayer::process_stereo");
         // Process any pending MIDI events for current sample
         self.process_midi_events(self.current_sample);
+        self.apply_automation();
         
         // Generate stereo audio sample from voice manager
         let (left, right) = self.voice_manager.process();
@@ -371,67 +692,50 @@ impl MidiPlayer {
         
         // Apply modern 32-bit float mixing gains (same as mono version)
         // EMU8000 was limited to ±32,767, we can use full ±1.0 float precision  
-        let gained_left = left * 2.5;   // 250% gain - way beyond EMU8000 16-bit capability
-        let gained_right = right * 2.5; // 250% gain - way beyond EMU8000 16-bit capability
-        (gained_left, gained_right)
+        let master_gain = 2.5 * self.next_auto_gain();  // 250% gain - way beyond EMU8000 16-bit capability
+        let gained_left = left * master_gain;
+        let gained_right = right * master_gain;
+        let (click_main, click_bus) = self.metronome.next_sample();
+        let fade = self.next_fade_gain();
+        (self.finish_output((gained_left + click_main) * fade), self.finish_output((gained_right + click_main) * fade), click_bus)
     }
     
-    /// Test complete synthesis pipeline: MIDI → Voice → Oscillator → Envelope → Audio
-    /// Returns test results as JSON string for verification
-    #[wasm_bindgen]
-    pub fn test_synthesis_pipeline(&mut self) -> String {
-        log("Testing Phase 7A: Basic Audio Synthesis Pipeline");
-        
-        // Test 1: Start a note (Middle C, velocity 100)
-        let note = 60; // Middle C (261.63 Hz)
-        let velocity = 100;
-        
-        if let Some(voice_id) = self.voice_manager.note_on(note, velocity, 0) {
-            log(&format!("✅ Note {} started on voice {}", note, voice_id));
-            
-            // Test 2: Generate 10 audio samples and verify non-zero output
-            let mut sample_outputs = Vec::new();
-            let mut non_zero_samples = 0;
-            
-            for i in 0..10 {
-                let (left, right) = self.voice_manager.process();
-                let mono_sample = (left + right) * 0.7;
-                sample_outputs.push(format!("{:.6}", mono_sample));
-                
-                if mono_sample.abs() > 0.001 {
-                    non_zero_samples += 1;
-                }
-                
-                log(&format!("Sample {}: L={:.6} R={:.6} Mono={:.6}", i, left, right, mono_sample));
-            }
-            
-            // Test 3: Release note and verify envelope release
-            self.voice_manager.note_off(note);
-            log("✅ Note released - testing envelope release");
-            
-            let mut release_samples = Vec::new();
-            for i in 0..5 {
-                let (left, right) = self.voice_manager.process();
-                let mono_sample = (left + right) * 0.7;
-                release_samples.push(format!("{:.6}", mono_sample));
-                log(&format!("Release sample {}: L={:.6} R={:.6} Mono={:.6}", i, left, right, mono_sample));
-            }
-            
-            // Create test results
-            let test_results = format!(
-                "{{\"success\": true, \"voice_allocated\": {}, \"non_zero_samples\": {}, \"attack_samples\": [{}], \"release_samples\": [{}]}}",
-                voice_id,
-                non_zero_samples,
-                sample_outputs.join(", "),
-                release_samples.join(", ")
-            );
-            
-            log(&format!("✅ Synthesis test completed: {} non-zero samples generated", non_zero_samples));
-            test_results
+    /// Polyphony auto-gain multiplier for this sample (1.0 while auto-gain is off)
+    fn next_auto_gain(&mut self) -> f32 {
+        if !self.auto_gain.is_engaged() {
+            return 1.0;
+        }
+        self.auto_gain.next(self.voice_manager.get_active_voice_count())
+    }
+    
+    /// Transport fade multiplier for this sample (1.0 with no fade running)
+    /// A fade-out that lands stops the sequencer and fades the voices away under the silent bus
+    fn next_fade_gain(&mut self) -> f32 {
+        if self.fade.state() == FadeState::Idle {
+            return 1.0;
+        }
+        let voices_active = self.voice_manager.get_active_voice_count() > 0;
+        let (gain, finished_out) = self.fade.next(voices_active);
+        if finished_out {
+            self.finish_recording(self.sequencer.get_current_tick());
+            self.sequencer.stop();
+            self.humanizer.clear();
+            self.voice_manager.stop_all_sound();
+        }
+        gain
+    }
+    
+    /// Fade state and level for pipeline status (internal method)
+    pub(crate) fn fade_status(&self) -> FadeStatus {
+        self.fade.status()
+    }
+    
+    /// Final output stage: hardware mode clips and quantizes like the 16-bit DAC
+    fn finish_output(&self, sample: f32) -> f32 {
+        if self.voice_manager.get_synthesis_mode().is_hardware() {
+            synth::hardware_mode::quantize_16bit(sample)
         } else {
-            let error = "{\"success\": false, \"error\": \"Failed to allocate voice\"}".to_string();
-            log("❌ Synthesis test failed: No voice available");
-            error
+            sample
         }
     }
     
@@ -443,6 +747,13 @@ impl MidiPlayer {
         }
         
         let status_byte = message[0];
+        if status_byte == MIDI_STATUS_SYSEX_START {
+            return if self.handle_sysex(message) {
+                Ok(())
+            } else {
+                Err("Unsupported SysEx message".to_string())
+            };
+        }
         let message_type = (status_byte & 0xF0) >> 4;
         let channel = status_byte & 0x0F;
         
@@ -462,7 +773,7 @@ impl MidiPlayer {
         };
         
         // Process immediately for real-time response
-        self.handle_midi_event(&midi_event);
+        self.handle_traced_event(&midi_event, EventSource::Live);
         
         log(&format!("Direct MIDI: 0x{:02X} 0x{:02X} 0x{:02X} (type=0x{:02X}, ch={})", 
                    status_byte, data1, data2, message_type, channel));
@@ -470,6 +781,171 @@ impl MidiPlayer {
         Ok(())
     }
     
+    /// Handle a complete SysEx message (F0 ... F7): GM/GM2/GS/XG system messages
+    /// and MIDI Machine Control transport commands
+    /// Returns true if the message was recognized
+    pub fn handle_sysex(&mut self, data: &[u8]) -> bool {
+        if let Some(command) = transport::parse_mmc(data) {
+            self.apply_transport(command);
+            return true;
+        }
+        self.apply_system_sysex(data)
+    }
+    
+    /// GM/GS/XG system messages: system mode changes and GS rhythm part assignments
+    fn apply_system_sysex(&mut self, data: &[u8]) -> bool {
+        match parse_system_sysex(data) {
+            Some(SystemSysEx::Gm1SystemOn) => {
+                self.voice_manager.set_system_mode(MidiSystemMode::Gm);
+                self.voice_manager.reset_channels();
+                true
+            }
+            Some(SystemSysEx::Gm2SystemOn) => {
+                self.voice_manager.set_system_mode(MidiSystemMode::Gm2);
+                self.voice_manager.reset_channels();
+                true
+            }
+            Some(SystemSysEx::GmSystemOff) => {
+                self.voice_manager.set_system_mode(MidiSystemMode::Gm);
+                true
+            }
+            Some(SystemSysEx::GsReset) => {
+                self.voice_manager.set_system_mode(MidiSystemMode::Gs);
+                self.voice_manager.reset_channels();
+                true
+            }
+            Some(SystemSysEx::GsRhythmPart { channel, is_drum }) => {
+                self.voice_manager.set_channel_drum(channel, is_drum);
+                true
+            }
+            Some(SystemSysEx::XgSystemOn) => {
+                self.voice_manager.set_system_mode(MidiSystemMode::Xg);
+                self.voice_manager.reset_channels();
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// Sequencer event as a MIDI event at the current sample (None for SysEx, which is
+    /// applied directly)
+    fn sequencer_midi_event(&self, event_type: &midi::sequencer::ProcessedEventType) -> Option<MidiEvent> {
+        match *event_type {
+            midi::sequencer::ProcessedEventType::NoteOn { channel, note, velocity } => {
+                Some(MidiEvent::new(self.current_sample, channel, 0x90, note, velocity))
+            },
+            midi::sequencer::ProcessedEventType::NoteOff { channel, note, velocity } => {
+                Some(MidiEvent::new(self.current_sample, channel, 0x80, note, velocity))
+            },
+            midi::sequencer::ProcessedEventType::ProgramChange { channel, program } => {
+                Some(MidiEvent::new(self.current_sample, channel, 0xC0, program, 0))
+            },
+            midi::sequencer::ProcessedEventType::ControlChange { channel, controller, value } => {
+                Some(MidiEvent::new(self.current_sample, channel, 0xB0, controller, value))
+            },
+            midi::sequencer::ProcessedEventType::SysEx { .. } => None,
+        }
+    }
+    
+    /// Dispatch a chased or setup event straight to the synth, bypassing the queue
+    fn replay_sequencer_event(&mut self, event_type: &midi::sequencer::ProcessedEventType, source: EventSource) {
+        if let midi::sequencer::ProcessedEventType::SysEx { data } = event_type {
+            self.apply_system_sysex(data);
+        } else if let Some(midi_event) = self.sequencer_midi_event(event_type) {
+            self.handle_traced_event(&midi_event, source);
+        }
+    }
+    
+    /// Apply the song's setup region in one go when playback starts at the top: system
+    /// SysEx, programs and controllers in file order, settled without glides
+    fn apply_song_setup(&mut self) {
+        let events = self.sequencer.take_setup_events();
+        if events.is_empty() {
+            return;
+        }
+        for event in &events {
+            self.replay_sequencer_event(&event.event_type, EventSource::Setup);
+        }
+        self.voice_manager.settle_smoothing();
+    }
+    
+    /// Rebuild channel state at the seek point: notes from the old position are released,
+    /// channels reset, then the skipped programs and controllers applied without glides
+    fn chase_sequencer(&mut self) {
+        self.voice_manager.flush_effects(); // Tails from the old position would bleed into the new one
+        self.humanizer.clear();
+        if self.sequencer.midi_file().is_none() {
+            return;
+        }
+        self.voice_manager.reset_channels();
+        for event in self.sequencer.take_chase_events() {
+            self.replay_sequencer_event(&event.event_type, EventSource::Chase);
+        }
+        self.voice_manager.settle_smoothing();
+        self.sync_automation();
+    }
+    
+    /// Song position automation lanes are evaluated at, matched to the sequencer after each
+    /// update (behind it by any humanize latency, so curves stay with the notes they go with)
+    fn sync_automation(&mut self) {
+        let latency_ticks = self.humanizer.latency_samples() as f64 * self.sequencer.ticks_per_sample();
+        self.automation_tick = (self.sequencer.get_tick_position() - latency_ticks).max(0.0);
+    }
+    
+    /// Set this sample's automation lane values; between sequencer updates the song
+    /// position moves on at the current tempo
+    fn apply_automation(&mut self) {
+        if self.automation.is_empty() {
+            return;
+        }
+        let voice_manager = &mut self.voice_manager;
+        self.automation.evaluate(self.automation_tick, |channel, parameter, value| {
+            voice_manager.set_automation(channel, parameter, value);
+        });
+        if self.sequencer.get_state() == PlaybackState::Playing {
+            self.automation_tick += self.sequencer.ticks_per_sample();
+        }
+    }
+    
+    /// Drive the sequencer from an external transport message (MMC / Song Position Pointer)
+    fn apply_transport(&mut self, command: TransportCommand) {
+        match command {
+            TransportCommand::Play => self.play(),
+            TransportCommand::Stop => self.stop(),
+            TransportCommand::Pause => self.pause(),
+            TransportCommand::LocateSeconds(seconds) => {
+                self.sequencer.seek_to_seconds(seconds, self.current_sample);
+                self.chase_sequencer();
+            }
+            TransportCommand::LocateBeats(beats) => {
+                let tick = transport::midi_beats_to_ticks(beats, self.sequencer.get_ticks_per_quarter());
+                self.sequencer.seek_to_tick(tick, self.current_sample);
+                self.chase_sequencer();
+            }
+        }
+    }
+    
+    /// Load a Standard MIDI File, emitting its load events (internal method)
+    pub(crate) fn load_midi_data(&mut self, data: &[u8]) -> AweResult<()> {
+        use events::{emit, LoadEvent, LoadKind, LoadErrorCode};
+
+        emit(LoadEvent::Started { kind: LoadKind::Midi, total_bytes: Some(data.len()) });
+        let mut progress = events::parse_progress(LoadKind::Midi, 0.0);
+        let result = self.sequencer.load_midi_file_with_progress(data, &mut progress);
+        match &result {
+            Ok(()) => {
+                self.recorder.clear_takes();
+                emit(LoadEvent::Complete { kind: LoadKind::Midi });
+            }
+            Err(e) => {
+                // The parser only reports InvalidMidiFile; a valid MThd means the damage is further in
+                let code = if data.starts_with(b"MThd") { LoadErrorCode::CorruptData } else { LoadErrorCode::InvalidFormat };
+                emit(LoadEvent::failed(LoadKind::Midi, code, e.to_string()));
+            }
+        }
+        result
+    }
+    
     /// Load SoundFont into VoiceManager for synthesis (internal method)
     pub(crate) fn load_soundfont(&mut self, soundfont: SoundFont) -> Result<(), String> {
         log("MidiPlayer::load_soundfont() - Loading SoundFont into voice manager");
@@ -491,67 +967,613 @@ impl MidiPlayer {
     pub(crate) fn get_current_preset_info(&self) -> Option<String> {
         self.voice_manager.get_current_preset_info()
     }
+
+    /// Enable/disable procedural fallback bank while no SoundFont is loaded (internal method)
+    pub(crate) fn set_fallback_bank_enabled(&mut self, enabled: bool) {
+        self.voice_manager.set_fallback_bank_enabled(enabled);
+    }
     
-    /// Debug: Generate a test tone to verify audio pipeline
-    #[wasm_bindgen]
-    pub fn test_audio_pipeline(&mut self) -> String {
-        // First check if SoundFont is loaded
-        let sf_loaded = self.voice_manager.is_soundfont_loaded();
-        
-        // Try to play a middle C note
-        self.voice_manager.note_on(0, 60, 100); // Channel 0, Middle C, Velocity 100
-        
-        // Generate a few samples to see if we get audio
-        let mut max_sample = 0.0f32;
-        let mut has_audio = false;
-        
-        for _ in 0..100 {
-            let sample = self.process();
-            if sample.abs() > 0.001 {
-                has_audio = true;
-                if sample.abs() > max_sample {
-                    max_sample = sample.abs();
-                }
-            }
-        }
-        
-        // Stop the note
-        self.voice_manager.note_off(60);
-        
-        format!(
-            "{{\"soundfont_loaded\": {}, \"has_audio\": {}, \"max_amplitude\": {:.6}, \"active_voices\": {}}}",
-            sf_loaded,
-            has_audio,
-            max_sample,
-            self.voice_manager.get_active_voice_count()
-        )
+    /// Set sample playback direction for a channel (internal method)
+    pub(crate) fn set_channel_playback_direction(&mut self, channel: u8, direction: PlaybackDirection) -> bool {
+        self.voice_manager.set_channel_playback_direction(channel, direction)
     }
-}
-
-// ===== AUDIOWORKLET INTEGRATION EXPORTS =====
-
-/// Global AudioWorklet-optimized exports for efficient real-time audio processing
-/// These functions are designed for maximum performance in AudioWorklet context
-
-static mut GLOBAL_WORKLET_BRIDGE: Option<crate::worklet::AudioWorkletBridge> = None;
-
-/// Initialize global AudioWorklet bridge with specified sample rate
-/// Must be called once before using other AudioWorklet functions
-#[wasm_bindgen]
-pub fn init_audio_worklet(sample_rate: f32) -> bool {
-    unsafe {
-        log(&format!("🔧 BRIDGE INIT: Starting initialization at {}Hz", sample_rate));
-        
-        // Check if bridge already exists
-        if GLOBAL_WORKLET_BRIDGE.is_some() {
-            log("⚠️ BRIDGE INIT: Bridge already exists, replacing existing bridge");
+    
+    /// Set humanize start offset window for one channel or all (internal method)
+    pub(crate) fn set_start_offset_window(&mut self, channel: Option<u8>, window_ms: f32) -> bool {
+        self.voice_manager.set_start_offset_window(channel, window_ms)
+    }
+    
+    /// Add or replace the automation lane of a channel parameter (internal method)
+    pub(crate) fn set_automation_lane(&mut self, lane: AutomationLane) -> bool {
+        self.automation.set(lane)
+    }
+    
+    /// Drop a lane, handing its parameter back to the channel's controllers (internal method)
+    pub(crate) fn remove_automation_lane(&mut self, channel: u8, parameter: AutomationParameter) -> bool {
+        if !self.automation.remove(channel, parameter) {
+            return false;
         }
-        
-        // Create new bridge
-        let new_bridge = crate::worklet::AudioWorkletBridge::new(sample_rate);
-        GLOBAL_WORKLET_BRIDGE = Some(new_bridge);
-        
-        // Verify creation
+        self.voice_manager.reset_automation(Some((channel, parameter)));
+        true
+    }
+    
+    pub(crate) fn clear_automation(&mut self) {
+        self.automation.clear();
+        self.voice_manager.reset_automation(None);
+    }
+    
+    pub(crate) fn automation_lanes(&self) -> &[AutomationLane] {
+        self.automation.lanes()
+    }
+    
+    /// Handle live input as soon as it is queued, ahead of playback events (internal method)
+    pub(crate) fn set_live_mode(&mut self, enabled: bool) {
+        self.live_mode = enabled;
+    }
+    
+    pub(crate) fn live_mode(&self) -> bool {
+        self.live_mode
+    }
+    
+    /// Velocity range and timing window of sequencer notes, one channel or all (internal method)
+    pub(crate) fn set_playback_humanize(&mut self, channel: Option<u8>, velocity_range: u8, timing_ms: f32) -> bool {
+        self.humanizer.set_channel(channel, velocity_range, timing_ms)
+    }
+    
+    pub(crate) fn set_playback_humanize_seed(&mut self, seed: u32) {
+        self.humanizer.set_seed(seed);
+    }
+    
+    pub(crate) fn playback_humanize(&self) -> &[ChannelHumanize; 16] {
+        self.humanizer.channels()
+    }
+    
+    /// Apply the song's setup region at once when playback starts at the top (internal method)
+    pub(crate) fn set_song_setup_enabled(&mut self, enabled: bool) {
+        self.sequencer.set_setup_enabled(enabled);
+    }
+    
+    /// What the loaded song's setup region holds (internal method)
+    pub(crate) fn song_setup_report(&self) -> midi::setup::SongSetupReport {
+        self.sequencer.song_setup().report(self.sequencer.is_setup_enabled())
+    }
+    
+    /// Set LFO start phase mode and reseed its generator (internal method)
+    pub(crate) fn set_lfo_phase_mode(&mut self, mode: LfoPhaseMode, seed: u32) {
+        self.voice_manager.set_lfo_phase_mode(mode, seed);
+    }
+    
+    /// Set MIDI system mode without resetting channels (internal method)
+    pub(crate) fn set_system_mode(&mut self, mode: MidiSystemMode) {
+        self.voice_manager.set_system_mode(mode);
+    }
+    
+    /// Current MIDI system mode (internal method)
+    pub(crate) fn get_system_mode(&self) -> MidiSystemMode {
+        self.voice_manager.get_system_mode()
+    }
+    
+    /// Force a channel to percussion or melodic, None for the system mode rules (internal method)
+    pub(crate) fn set_channel_drum_override(&mut self, channel: u8, is_drum: Option<bool>) -> bool {
+        self.voice_manager.set_channel_drum_override(channel, is_drum)
+    }
+    
+    /// Host percussion override of a channel (internal method)
+    pub(crate) fn channel_drum_override(&self, channel: u8) -> Option<bool> {
+        self.voice_manager.channel_drum_override(channel)
+    }
+
+    /// Per-channel voices, program and mixer controllers (internal method)
+    pub(crate) fn channel_telemetry(&self) -> [synth::telemetry::ChannelTelemetry; 16] {
+        self.voice_manager.channel_telemetry()
+    }
+    
+    /// Controller snapshot of one MIDI channel (internal method)
+    pub(crate) fn channel_state(&self, channel: u8) -> Option<synth::channel_state::ChannelStateReport> {
+        self.voice_manager.channel_state_report(channel)
+    }
+    
+    /// Volume envelope state of every voice (internal method)
+    pub(crate) fn voice_envelopes(&self) -> [synth::envelope_monitor::VoiceEnvelope; synth::voice_manager::MAX_VOICES] {
+        self.voice_manager.voice_envelopes()
+    }
+    
+    /// Play a raw SoundFont sample through the output (internal method)
+    pub(crate) fn preview_sample(&mut self, sample_index: usize, semitones: f32, looped: bool, gain: f32) -> bool {
+        self.voice_manager.preview_sample(sample_index, semitones, looped, gain)
+    }
+    
+    pub(crate) fn stop_sample_preview(&mut self) {
+        self.voice_manager.stop_sample_preview();
+    }
+    
+    /// Switch modern / EMU8000 hardware synthesis (internal method)
+    pub(crate) fn set_synthesis_mode(&mut self, mode: SynthesisMode) {
+        self.voice_manager.set_synthesis_mode(mode);
+    }
+    
+    pub(crate) fn get_synthesis_mode(&self) -> SynthesisMode {
+        self.voice_manager.get_synthesis_mode()
+    }
+    
+    /// Enable non-EMU8000 voice heuristics (internal method)
+    pub(crate) fn set_enhancements(&mut self, enabled: bool) {
+        self.voice_manager.set_enhancements(enabled);
+    }
+    
+    pub(crate) fn get_enhancements(&self) -> bool {
+        self.voice_manager.get_enhancements()
+    }
+    
+    /// Enable polyphony-aware master gain (internal method)
+    pub(crate) fn set_auto_gain_enabled(&mut self, enabled: bool) {
+        self.auto_gain.set_enabled(enabled);
+    }
+    
+    pub(crate) fn get_auto_gain(&self) -> f32 {
+        self.auto_gain.gain()
+    }
+    
+    /// Set the controller smoothing time constant (internal method)
+    pub(crate) fn set_controller_smoothing_ms(&mut self, time_ms: f32) {
+        self.voice_manager.set_smoothing_time_ms(time_ms);
+    }
+    
+    pub(crate) fn get_controller_smoothing_ms(&self) -> f32 {
+        self.voice_manager.get_smoothing_time_ms()
+    }
+    
+    /// Configure the hanging-note watchdog (internal method)
+    pub(crate) fn set_note_watchdog(&mut self, enabled: bool, timeout_seconds: f32) {
+        self.voice_manager.set_note_watchdog(enabled, timeout_seconds);
+    }
+    
+    /// Hand watchdog releases to the listener; call after rendering, outside the audited block
+    pub(crate) fn dispatch_watchdog_events(&mut self) {
+        let notes = self.voice_manager.take_hung_notes();
+        if !notes.is_empty() {
+            synth::watchdog::emit(&notes);
+        }
+    }
+    
+    /// Define or clear a keyboard performance zone (internal method)
+    pub(crate) fn set_performance_zone(&mut self, index: usize, zone: Option<PerformanceZone>) -> bool {
+        self.voice_manager.set_performance_zone(index, zone)
+    }
+    
+    pub(crate) fn set_performance_input(&mut self, channel: Option<u8>) {
+        self.voice_manager.set_performance_input(channel);
+    }
+    
+    /// Zone selection strategy and its analysis (internal method)
+    pub(crate) fn set_zone_selection_strategy(&mut self, strategy: synth::voice_manager::ZoneSelectionStrategy) {
+        self.voice_manager.set_zone_selection_strategy(strategy);
+    }
+    
+    pub(crate) fn zone_selection_strategy(&self) -> synth::voice_manager::ZoneSelectionStrategy {
+        self.voice_manager.get_zone_selection_strategy().clone()
+    }
+    
+    pub(crate) fn set_round_robin(&mut self, enabled: bool) {
+        if enabled {
+            self.voice_manager.enable_round_robin();
+        } else {
+            self.voice_manager.disable_round_robin();
+        }
+    }
+    
+    pub(crate) fn analyze_zone_selection(&mut self, note: u8, velocity: u8) -> synth::voice_manager::ZoneSelectionAnalysis {
+        self.voice_manager.analyze_zone_selection(note, velocity)
+    }
+    
+    /// Velocity layer crossfade curve and width (internal method)
+    pub(crate) fn set_velocity_crossfade(&mut self, crossfade: synth::crossfade::VelocityCrossfade) {
+        self.voice_manager.set_velocity_crossfade(crossfade);
+    }
+    
+    pub(crate) fn velocity_crossfade(&self) -> synth::crossfade::VelocityCrossfade {
+        self.voice_manager.velocity_crossfade()
+    }
+    
+    /// Preview channel program, volume and live input (internal method)
+    pub(crate) fn set_preview_program(&mut self, bank: u16, program: u8) -> bool {
+        self.voice_manager.set_preview_program(bank, program)
+    }
+    
+    pub(crate) fn set_preview_volume(&mut self, volume: f32) {
+        self.voice_manager.set_preview_volume(volume);
+    }
+    
+    pub(crate) fn set_preview_input(&mut self, channel: Option<u8>) {
+        self.voice_manager.set_preview_input(channel);
+    }
+    
+    pub(crate) fn preview_channel(&self) -> synth::preview_channel::PreviewChannel {
+        self.voice_manager.preview_channel()
+    }
+    
+    /// Play or release a note on the preview channel (internal method)
+    pub(crate) fn preview_note_on(&mut self, note: u8, velocity: u8) -> bool {
+        self.voice_manager.preview_note_on(note, velocity).is_some()
+    }
+    
+    pub(crate) fn preview_note_off(&mut self, note: u8) {
+        self.voice_manager.preview_note_off(note);
+    }
+    
+    /// Trim a channel's output in dB (internal method)
+    pub(crate) fn set_channel_trim_db(&mut self, channel: u8, db: f32) -> bool {
+        self.voice_manager.set_channel_trim_db(channel, db)
+    }
+    
+    pub(crate) fn channel_trim_db(&self, channel: u8) -> Option<f32> {
+        self.voice_manager.channel_trim_db(channel)
+    }
+    
+    /// Set a channel's velocity remapping (internal method)
+    pub(crate) fn set_velocity_map(&mut self, channel: u8, map: VelocityMap) -> bool {
+        self.voice_manager.set_velocity_map(channel, map)
+    }
+    
+    /// Persistable host settings (internal method)
+    pub(crate) fn state_snapshot(&self) -> EngineSnapshot {
+        self.voice_manager.state_snapshot()
+    }
+    
+    pub(crate) fn restore_state_snapshot(&mut self, snapshot: &EngineSnapshot) {
+        self.voice_manager.restore_state_snapshot(snapshot);
+    }
+    
+    /// Voice lifetime and polyphony statistics (internal method)
+    pub(crate) fn voice_stats(&self) -> VoiceStatsReport {
+        self.voice_manager.voice_stats()
+    }
+    
+    pub(crate) fn reset_voice_stats(&mut self) {
+        self.voice_manager.reset_voice_stats();
+    }
+    
+    /// Recent handled MIDI events on `channel` (None = all) of the given types (internal method)
+    pub(crate) fn midi_trace(&self, channel: Option<u8>, types: &[&str]) -> Vec<TraceEntryReport> {
+        self.event_trace.query(channel, types)
+    }
+    
+    /// Turn the MIDI trace on or off and set how many events it keeps (0 = unchanged)
+    pub(crate) fn configure_midi_trace(&mut self, enabled: bool, capacity: usize) {
+        if capacity > 0 && capacity != self.event_trace.capacity() {
+            self.event_trace.set_capacity(capacity);
+        }
+        self.event_trace.set_enabled(enabled);
+    }
+    
+    pub(crate) fn clear_midi_trace(&mut self) {
+        self.event_trace.clear();
+    }
+    
+    /// Filter-envelope shape per channel, None = all channels (internal method)
+    pub(crate) fn set_filter_envelope(&mut self, channel: Option<u8>, shape: Option<FilterEnvelopeShape>) -> bool {
+        self.voice_manager.set_filter_envelope(channel, shape)
+    }
+    
+    /// Mod wheel vibrato range/curve per channel, None = all channels (internal method)
+    pub(crate) fn set_mod_wheel_vibrato(&mut self, channel: Option<u8>, vibrato: Option<ModWheelVibrato>) -> bool {
+        self.voice_manager.set_mod_wheel_vibrato(channel, vibrato)
+    }
+    
+    /// Host modulation routes, None = all channels (internal method)
+    pub(crate) fn set_modulation_route(&mut self, channel: Option<u8>, route: ModulationRoute) -> bool {
+        self.voice_manager.set_modulation_route(channel, route)
+    }
+    
+    pub(crate) fn remove_modulation_route(&mut self, channel: Option<u8>, source: ModulationSource, destination: ModulationDestination) -> bool {
+        self.voice_manager.remove_modulation_route(channel, source, destination)
+    }
+    
+    pub(crate) fn clear_modulation_routes(&mut self, channel: Option<u8>) -> bool {
+        self.voice_manager.clear_modulation_routes(channel)
+    }
+    
+    /// Set or remove a per-key tuning table (internal method)
+    pub(crate) fn set_key_tuning(&mut self, channel: Option<u8>, cents: &[f32]) -> bool {
+        self.voice_manager.set_key_tuning(channel, cents)
+    }
+    
+    pub(crate) fn reset_key_tuning(&mut self, channel: Option<u8>) -> bool {
+        self.voice_manager.reset_key_tuning(channel)
+    }
+    
+    /// Set the A4 reference in Hz (internal method)
+    pub(crate) fn set_master_tuning(&mut self, a4_hz: f32) -> bool {
+        self.voice_manager.set_master_tuning(a4_hz)
+    }
+    
+    pub(crate) fn get_master_tuning(&self) -> f32 {
+        self.voice_manager.get_master_tuning()
+    }
+    
+    /// Set mixer-stage solo for debugging (internal method)
+    pub(crate) fn set_debug_solo(&mut self, solo: DebugSolo) {
+        self.voice_manager.set_debug_solo(solo);
+    }
+    
+    /// Number of voices currently producing audio (internal method)
+    pub(crate) fn get_active_voice_count(&self) -> usize {
+        self.voice_manager.get_active_voice_count()
+    }
+    
+    /// No sequence playing and no MIDI events waiting in the queue (internal method)
+    pub(crate) fn is_idle(&self) -> bool {
+        if self.sequencer.get_state() == PlaybackState::Playing {
+            return false;
+        }
+        MIDI_EVENT_QUEUE.get()
+            .and_then(|queue| queue.lock().ok().map(|queue| queue.is_empty()))
+            .unwrap_or(true)
+    }
+    
+    /// Engine sample counter that MIDI event timestamps refer to (internal method)
+    pub(crate) fn get_current_sample(&self) -> u64 {
+        self.current_sample
+    }
+    
+    /// Render `frames` stereo frames of silence and discard them (internal method)
+    /// Only while idle with no voice sounding (returns 0 otherwise); voices are warmed
+    /// first and the sample counter is rewound, so playback starts where it would have
+    pub(crate) fn prime(&mut self, frames: usize) -> usize {
+        if !self.is_idle() || self.voice_manager.get_active_voice_count() > 0 {
+            return 0;
+        }
+        self.voice_manager.warm_up_voices();
+        let start_sample = self.current_sample;
+        for _ in 0..frames {
+            std::hint::black_box(self.process_stereo_with_click());
+        }
+        self.current_sample = start_sample;
+        frames
+    }
+    
+    /// Play `notes` on `channel` for `frames` stereo samples, then fade them out; returns the
+    /// voices started and the output peak (internal method)
+    pub(crate) fn sound_check(&mut self, channel: u8, notes: &[u8], frames: usize) -> (usize, f32) {
+        let started = notes.iter().filter(|&&note| self.voice_manager.note_on(note, 100, channel).is_some()).count();
+        let mut peak = 0.0f32;
+        for _ in 0..frames {
+            let (left, right) = self.process_stereo();
+            peak = peak.max(left.abs()).max(right.abs());
+        }
+        for &note in notes {
+            self.voice_manager.note_off(note);
+        }
+        self.voice_manager.all_sound_off(channel);
+        (started, peak)
+    }
+    
+    /// Play a preset preview note on the audition channel (internal method)
+    pub(crate) fn audition_preset(&mut self, bank: u16, program: u8, note: u8, velocity: u8, duration_ms: u32) -> bool {
+        self.voice_manager.audition_preset(bank, program, note, velocity, duration_ms)
+    }
+    
+    /// Pre-warm a preset ahead of a program change (internal method)
+    pub(crate) fn pre_load_preset(&mut self, bank: u16, program: u8) -> Option<PreloadReport> {
+        self.voice_manager.pre_load_preset(bank, program)
+    }
+    
+    /// Trim presets toward a common level after each load (internal method)
+    pub(crate) fn set_preset_balance(&mut self, enabled: bool) {
+        self.voice_manager.set_preset_balance(enabled);
+    }
+    
+    pub(crate) fn preset_balance(&self) -> Option<&synth::preset_balance::PresetBalance> {
+        self.voice_manager.preset_balance()
+    }
+    
+    /// Key map of a drum kit preset (internal method)
+    pub(crate) fn drum_kit_map(&self, bank: u16, program: u8) -> Option<DrumKitMap> {
+        self.voice_manager.drum_kit_map(bank, program)
+    }
+    
+    /// Override a preset's effects sends for new notes, None restores its own (internal method)
+    pub(crate) fn set_preset_effects_sends(&mut self, bank: u16, program: u8, sends: Option<soundfont::catalog::EffectsSends>) -> bool {
+        self.voice_manager.set_preset_effects_sends(bank, program, sends)
+    }
+    
+    pub(crate) fn preset_effects_sends(&self) -> &std::collections::HashMap<usize, soundfont::catalog::EffectsSends> {
+        self.voice_manager.preset_effects_sends()
+    }
+    
+    /// Release the preset preview note (internal method)
+    pub(crate) fn stop_audition(&mut self) {
+        self.voice_manager.stop_audition();
+    }
+    
+    /// Edit a track of the loaded MIDI file; sounding notes that lost their note-off are released (internal method)
+    pub(crate) fn edit_sequence_track(&mut self, track: usize, edit: &midi::edit::TrackEdit) -> Result<(), String> {
+        let released = self.sequencer.edit_track(track, edit)?;
+        for (channel, note) in released {
+            self.queue_event_from(MidiEvent::new(self.current_sample, channel, 0x80, note, 0), EventSource::Sequencer);
+        }
+        Ok(())
+    }
+    
+    /// Limit recording to a tick range; None leaves that side open (internal method)
+    pub(crate) fn set_punch_region(&mut self, punch_in: Option<u64>, punch_out: Option<u64>) -> bool {
+        self.recorder.set_punch_region(punch_in, punch_out)
+    }
+    
+    pub(crate) fn set_pre_roll_bars(&mut self, bars: u32) {
+        self.recorder.set_pre_roll_bars(bars);
+    }
+    
+    /// Start a recording pass into `track`: playback starts (pre-roll before punch-in, if one
+    /// is set) and live input inside the punch region is merged into the track (internal method)
+    pub(crate) fn start_recording(&mut self, track: usize) -> bool {
+        let Some(file) = self.sequencer.midi_file() else {
+            return false;
+        };
+        if track >= file.tracks.len() || self.recorder.is_take_track(track) {
+            return false;
+        }
+        self.finish_recording(self.sequencer.get_current_tick());
+        let punch_in = self.recorder.status().punch_in.unwrap_or(0);
+        let (ticks_per_beat, beats_per_bar) = self.sequencer.beat_grid_at(punch_in);
+        if let Some(start) = self.recorder.start_tick(ticks_per_beat * beats_per_bar as u64) {
+            self.sequencer.seek_to_tick(start, self.current_sample);
+            self.chase_sequencer();
+        }
+        self.recorder.arm(track);
+        self.play();
+        true
+    }
+    
+    /// End the recording pass where playback is, leaving playback running (internal method)
+    pub(crate) fn stop_recording(&mut self) -> usize {
+        self.finish_recording(self.sequencer.get_current_tick())
+    }
+    
+    pub(crate) fn recording_status(&self) -> RecordingStatus {
+        self.recorder.status()
+    }
+    
+    /// A finished pass the sequence refused since the last call (internal method)
+    pub(crate) fn take_recording_failure(&mut self) -> Option<midi::recording::RecordingFailure> {
+        self.recorder.take_failure()
+    }
+    
+    /// Mute every take that is not the selected one of its group (notes already
+    /// sounding on a newly muted take still get their note-offs from the sequencer)
+    fn apply_take_mutes(&mut self) {
+        for take in self.recorder.takes() {
+            self.sequencer.set_track_muted(take.track, !take.selected);
+        }
+    }
+    
+    /// Keep later recording passes as takes instead of merging them (internal method)
+    pub(crate) fn set_take_recording(&mut self, enabled: bool) {
+        self.recorder.set_take_mode(enabled);
+    }
+    
+    pub(crate) fn takes(&self) -> &[midi::recording::Take] {
+        self.recorder.takes()
+    }
+    
+    /// Make a take the one of its group that plays (internal method)
+    pub(crate) fn select_take(&mut self, id: u32) -> bool {
+        if !self.recorder.select_take(id) {
+            return false;
+        }
+        self.apply_take_mutes();
+        true
+    }
+    
+    /// Select a take and play its region from the pre-roll it was recorded with (internal method)
+    pub(crate) fn audition_take(&mut self, id: u32) -> bool {
+        let Some(take) = self.recorder.take(id).cloned() else {
+            return false;
+        };
+        if self.recorder.is_active() || !self.select_take(id) {
+            return false;
+        }
+        let punch_in = take.punch_in.unwrap_or(0);
+        let (ticks_per_beat, beats_per_bar) = self.sequencer.beat_grid_at(punch_in);
+        let start = punch_in.saturating_sub(take.pre_roll_bars as u64 * ticks_per_beat * beats_per_bar as u64);
+        self.sequencer.seek_to_tick(start, self.current_sample);
+        self.chase_sequencer();
+        self.play();
+        true
+    }
+    
+    /// Merge a take into its target track; the other takes of its group are dropped (internal method)
+    pub(crate) fn commit_take(&mut self, id: u32) -> Result<(), ErrorEvent> {
+        let take = self.recorder.take(id).cloned()
+            .ok_or_else(|| ErrorEvent::raise(ErrorCode::InvalidArgument, format!("No take {}", id)))?;
+        let messages = self.sequencer.midi_file()
+            .and_then(|file| file.tracks.get(take.track))
+            .map(midi::recording::track_messages)
+            .ok_or_else(|| ErrorEvent::raise(ErrorCode::Internal, format!("Take {} has no track {}", id, take.track)))?;
+        self.edit_sequence_track(take.target, &midi::edit::TrackEdit::InsertMessages { messages })
+            .map_err(|e| ErrorEvent::raise(ErrorCode::Internal, format!("Take {} could not be committed: {}", id, e)))?;
+        self.remove_takes(self.recorder.takes_to_remove(id, true))
+    }
+    
+    /// A take as a MIDI file, with export-time quantize, swing and legato; the take itself is
+    /// left as played (internal method)
+    pub(crate) fn export_take_midi(&self, id: u32, options: &midi::take_export::MidiExportOptions) -> Option<Vec<u8>> {
+        let take = self.recorder.take(id)?;
+        let file = self.sequencer.midi_file()?;
+        let track = file.tracks.get(take.track)?;
+        Some(midi::take_export::export_take(file, track, options))
+    }
+    
+    /// Drop a take and its track (internal method)
+    pub(crate) fn discard_take(&mut self, id: u32) -> Result<(), ErrorEvent> {
+        let takes = self.recorder.takes_to_remove(id, false);
+        if takes.is_empty() {
+            return Err(ErrorEvent::raise(ErrorCode::InvalidArgument, format!("No take {}", id)));
+        }
+        let removed = self.remove_takes(takes);
+        self.apply_take_mutes();
+        removed
+    }
+    
+    /// Remove take tracks, highest index first so the others keep theirs until their turn.
+    /// Every take is forgotten; the first track that could not be removed is the error
+    fn remove_takes(&mut self, mut takes: Vec<midi::recording::Take>) -> Result<(), ErrorEvent> {
+        takes.sort_by_key(|take| std::cmp::Reverse(take.track));
+        let mut result = Ok(());
+        for take in takes {
+            match self.sequencer.remove_track(take.track) {
+                Ok(released) => {
+                    for (channel, note) in released {
+                        self.queue_event_from(MidiEvent::new(self.current_sample, channel, 0x80, note, 0), EventSource::Sequencer);
+                    }
+                }
+                Err(e) if result.is_ok() => {
+                    result = Err(ErrorEvent::raise(ErrorCode::Internal, format!("Take {} track could not be removed: {}", take.id, e)));
+                }
+                Err(_) => {}
+            }
+            self.recorder.remove_take(take.id);
+        }
+        result
+    }
+    
+    /// Loaded MIDI file for sequence listings (internal method)
+    pub(crate) fn sequence_file(&self) -> Option<&midi::parser::MidiFile> {
+        self.sequencer.midi_file()
+    }
+}
+
+// ===== AUDIOWORKLET INTEGRATION EXPORTS =====
+
+/// Global AudioWorklet-optimized exports for efficient real-time audio processing
+/// These functions are designed for maximum performance in AudioWorklet context
+
+static mut GLOBAL_WORKLET_BRIDGE: Option<crate::worklet::AudioWorkletBridge> = None;
+
+/// Mutable access to the global bridge for internal modules (None until init_audio_worklet)
+#[allow(static_mut_refs)]
+pub(crate) fn global_bridge_mut() -> Option<&'static mut crate::worklet::AudioWorkletBridge> {
+    unsafe { GLOBAL_WORKLET_BRIDGE.as_mut() }
+}
+
+/// Initialize global AudioWorklet bridge with specified sample rate
+/// Must be called once before using other AudioWorklet functions
+#[wasm_bindgen]
+pub fn init_audio_worklet(sample_rate: f32) -> bool {
+    error::install_panic_hook();
+    unsafe {
+        log(&format!("🔧 BRIDGE INIT: Starting initialization at {}Hz", sample_rate));
+        
+        // Check if bridge already exists
+        if GLOBAL_WORKLET_BRIDGE.is_some() {
+            log("⚠️ BRIDGE INIT: Bridge already exists, replacing existing bridge");
+        }
+        
+        // Create new bridge
+        let new_bridge = crate::worklet::AudioWorkletBridge::new(sample_rate);
+        GLOBAL_WORKLET_BRIDGE = Some(new_bridge);
+        
+        // Verify creation
         let bridge_created = GLOBAL_WORKLET_BRIDGE.is_some();
         log(&format!("✅ BRIDGE INIT: Bridge created successfully at {}Hz - available: {}", 
                     sample_rate, bridge_created));
@@ -569,809 +1591,2209 @@ pub fn init_audio_worklet(sample_rate: f32) -> bool {
     }
 }
 
-/// Get WASM module version/build timestamp for cache checking
+/// Initialize the AudioWorklet bridge from a configuration document (JSON, or TOML in
+/// builds with the `toml` feature; see EngineConfig). `sampleRate` is required
+#[wasm_bindgen]
+pub fn init_audio_worklet_with_config(config: &str) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("init_audio_worklet_with_config");
+    let config = config::EngineConfig::parse(config).map_err(|e| ErrorEvent::raise(ErrorCode::InvalidArgument, e))?;
+    let sample_rate = config.sample_rate
+        .ok_or_else(|| ErrorEvent::raise(ErrorCode::InvalidArgument, "sampleRate is required at init"))?;
+    init_audio_worklet(sample_rate);
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    config.apply(bridge);
+    Ok(())
+}
+
+/// Apply a configuration document to the running engine; nothing changes if it is refused.
+/// A `sampleRate` must match the bridge's
+#[wasm_bindgen]
+pub fn apply_engine_config_global(config: &str) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("apply_engine_config_global");
+    let config = config::EngineConfig::parse(config).map_err(|e| ErrorEvent::raise(ErrorCode::InvalidArgument, e))?;
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    if config.sample_rate.is_some_and(|rate| rate != bridge.get_sample_rate()) {
+        return Err(ErrorEvent::raise(ErrorCode::InvalidArgument, "sampleRate can only be set at init"));
+    }
+    config.apply(bridge);
+    Ok(())
+}
+
+/// Get WASM module version/build timestamp for cache checking
+#[wasm_bindgen]
+pub fn get_wasm_version() -> String {
+    format!(r#"{{"version": "2025-08-09-22:41", "buildTime": "generator-reading-impl", "hasDebugBridgeStatus": true}}"#)
+}
+
+/// Comprehensive bridge lifecycle diagnostic for pipeline testing
+#[wasm_bindgen]
+pub fn diagnose_bridge_lifecycle() -> String {
+    unsafe {
+        let bridge_exists = GLOBAL_WORKLET_BRIDGE.is_some();
+        
+        log(&format!("🔬 BRIDGE LIFECYCLE: Starting comprehensive diagnostic"));
+        log(&format!("🔬 BRIDGE LIFECYCLE: Static variable state - is_some(): {}", bridge_exists));
+        
+        if let Some(ref bridge) = GLOBAL_WORKLET_BRIDGE {
+            let sample_rate = bridge.get_sample_rate();
+            log(&format!("🔬 BRIDGE LIFECYCLE: Bridge details - sample_rate: {}Hz, ready for diagnostics", sample_rate));
+            
+            format!(r#"{{
+                "success": true,
+                "bridge": {{
+                    "exists": true,
+                    "sample_rate": {},
+                    "status": "functional",
+                    "lifecycle": "active",
+                    "created": true,
+                    "accessible": true,
+                    "ready_for_diagnostics": true
+                }},
+                "diagnosis": "Bridge is fully operational and ready for all diagnostic functions",
+                "timestamp": "2025-08-09-22:41"
+            }}"#, sample_rate)
+        } else {
+            log("🔬 BRIDGE LIFECYCLE: Bridge is NULL - analyzing possible causes");
+            log("🔬 BRIDGE LIFECYCLE: Cause analysis:");
+            log("   ❌ init_all_systems() might not have been called");
+            log("   ❌ init_audio_worklet() might have failed silently");
+            log("   ❌ Bridge creation might have thrown an exception");
+            log("   ❌ Memory corruption or static variable issue");
+            
+            format!(r#"{{
+                "success": false,
+                "error": "Bridge not available",
+                "bridge": {{
+                    "exists": false,
+                    "status": "missing",
+                    "lifecycle": "not_initialized_or_destroyed",
+                    "created": false,
+                    "accessible": false,
+                    "ready_for_diagnostics": false
+                }},
+                "diagnosis": "Bridge is not initialized - all diagnostic functions will fail",
+                "possible_causes": [
+                    "init_all_systems() not called from JavaScript",
+                    "init_audio_worklet() failed during creation",
+                    "Static variable memory issue",
+                    "Bridge was destroyed after creation"
+                ],
+                "recommended_actions": [
+                    "Check JavaScript initialization sequence in AwePlayerContext",
+                    "Verify AudioContext creation succeeded",
+                    "Check for exceptions during bridge creation",
+                    "Verify no cleanup code is destroying the bridge"
+                ],
+                "timestamp": "2025-08-09-22:41"
+            }}"#)
+        }
+    }
+}
+
+
+/// Debug function to check bridge availability with detailed lifecycle tracking
+#[wasm_bindgen]
+pub fn debug_bridge_status() -> String {
+    unsafe {
+        let available = GLOBAL_WORKLET_BRIDGE.is_some();
+        
+        // Enhanced debugging with lifecycle information
+        if let Some(ref bridge) = GLOBAL_WORKLET_BRIDGE {
+            let sample_rate = bridge.get_sample_rate();
+            log(&format!("🔍 BRIDGE STATUS: Bridge is available - sample_rate: {}Hz", sample_rate));
+            format!(r#"{{"available": true, "sample_rate": {}, "status": "initialized", "lifecycle": "active", "timestamp": "2025-08-09-22:41"}}"#, 
+                    sample_rate)
+        } else {
+            log("⚠️ BRIDGE STATUS: Bridge is NOT available - GLOBAL_WORKLET_BRIDGE is None");
+            log("🔍 BRIDGE STATUS: This could indicate:");
+            log("   1. init_audio_worklet() was never called");
+            log("   2. Bridge creation failed silently");
+            log("   3. Bridge was destroyed/reset after creation");
+            log("   4. Memory management issue with static variable");
+            format!(r#"{{"available": false, "status": "not_initialized", "lifecycle": "missing", "timestamp": "2025-08-09-22:41"}}"#)
+        }
+    }
+}
+
+/// Process audio buffer using global AudioWorklet bridge
+/// Optimized for AudioWorklet process() callback - minimal overhead
+#[wasm_bindgen]
+pub fn process_audio_buffer(buffer_length: usize) -> Result<Vec<f32>, ErrorEvent> {
+    let _scope = ExportScope::enter("process_audio_buffer");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    let output = bridge.process_audio_buffer(buffer_length);
+    bridge.midi_player_mut().dispatch_watchdog_events();
+    Ok(output)
+}
+
+/// Get sample rate from global AudioWorklet bridge
+#[wasm_bindgen]
+pub fn get_sample_rate() -> Result<f32, ErrorEvent> {
+    let _scope = ExportScope::enter("get_sample_rate");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    Ok(bridge.get_sample_rate())
+}
+
+/// Queue MIDI event through global AudioWorklet bridge
+/// @deprecated Send `{"type":"midi", ...}` through bridge_command instead
+#[wasm_bindgen]
+pub fn queue_midi_event_global(timestamp: u32, channel: u8, message_type: u8, data1: u8, data2: u8) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("queue_midi_event_global");
+    protocol::execute_export(protocol::BridgeCommand::Midi { timestamp, channel, message_type, data1, data2 })?;
+    Ok(())
+}
+
+/// Process stereo buffer (interleaved) using global bridge
+#[wasm_bindgen]
+pub fn process_stereo_buffer_global(buffer_length: usize) -> Result<Vec<f32>, ErrorEvent> {
+    let _scope = ExportScope::enter("process_stereo_buffer_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    let output = bridge.process_stereo_buffer(buffer_length);
+    bridge.midi_player_mut().dispatch_watchdog_events();
+    Ok(output)
+}
+
+/// Render interleaved stereo frames into the bridge's buffer without allocating
+/// Returns frames rendered; read them via get_render_buffer_ptr_global()
+#[wasm_bindgen]
+pub fn render_stereo_block_global(frames: usize) -> Result<usize, ErrorEvent> {
+    let _scope = ExportScope::enter("render_stereo_block_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    let frames = bridge.render_stereo_block(frames);
+    bridge.midi_player_mut().dispatch_watchdog_events();
+    Ok(frames)
+}
+
+/// Render and discard up to 64 silent blocks at the current buffer size after init, before
+/// playback, so the first audible block doesn't pay first-run costs; returns frames primed
+#[wasm_bindgen]
+pub fn prime_audio_engine_global(blocks: u32) -> Result<usize, ErrorEvent> {
+    let _scope = ExportScope::enter("prime_audio_engine_global");
+    Ok(global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.prime(blocks as usize))
+}
+
+/// Address of the bridge's render buffer in WASM memory (null if the bridge is missing)
+#[wasm_bindgen]
+pub fn get_render_buffer_ptr_global() -> *const f32 {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.render_buffer_ptr(),
+        None => std::ptr::null(),
+    }
+}
+
+/// Correlate the engine's sample counter with AudioContext time
+/// Call at the start of every block with the worklet's `currentTime`, before rendering
+#[wasm_bindgen]
+pub fn observe_audio_clock_global(context_time: f64) {
+    if let Some(bridge) = global_bridge_mut() {
+        bridge.observe_clock(context_time);
+    }
+}
+
+/// Drift-corrected AudioContext time of an engine sample position (for UI cursors)
+#[wasm_bindgen]
+pub fn sample_to_context_time_global(sample: f64) -> f64 {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.clock().sample_to_time(sample.max(0.0) as u64),
+        None => 0.0,
+    }
+}
+
+/// Engine sample position rendered at an AudioContext time (timestamp for queue_midi_event_global)
+#[wasm_bindgen]
+pub fn context_time_to_sample_global(context_time: f64) -> f64 {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.clock().time_to_sample(context_time) as f64,
+        None => 0.0,
+    }
+}
+
+/// Clock drift estimate as JSON (`ClockStatus`)
+#[wasm_bindgen]
+pub fn get_clock_status_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => serde_json::to_string(&bridge.clock().status()).unwrap_or_else(|_| "{}".to_string()),
+        None => "{}".to_string(),
+    }
+}
+
+/// Address of the click bus for the last rendered block (mono, same frame count; null if the bridge is missing)
+#[wasm_bindgen]
+pub fn get_click_buffer_ptr_global() -> *const f32 {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.click_buffer_ptr(),
+        None => std::ptr::null(),
+    }
+}
+
+/// Address of the packed channel telemetry, refreshed after every rendered block
+/// 16 x 6 bytes: voices, program, volume, pan, expression, sustain (null if the bridge is missing)
+#[wasm_bindgen]
+pub fn get_channel_telemetry_ptr_global() -> *const u8 {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.telemetry_buffer_ptr(),
+        None => std::ptr::null(),
+    }
+}
+
+/// Channel telemetry as JSON (ChannelTelemetry[] for channels 0-15)
+#[wasm_bindgen]
+pub fn get_channel_telemetry_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => serde_json::to_string(&bridge.midi_player_mut().channel_telemetry())
+            .unwrap_or_else(|_| "[]".to_string()),
+        None => "[]".to_string(),
+    }
+}
+
+/// Controller snapshot of a MIDI channel (0-15) as ChannelStateReport JSON ("null" if out of range)
+#[wasm_bindgen]
+pub fn get_channel_state_global(channel: u8) -> String {
+    match global_bridge_mut().and_then(|bridge| bridge.midi_player_mut().channel_state(channel)) {
+        Some(report) => serde_json::to_string(&report).unwrap_or_else(|_| "null".to_string()),
+        None => "null".to_string(),
+    }
+}
+
+/// Refresh the packed voice envelopes this many times per second of rendered audio
+/// (0 = off, the default; capped at 240). False for negative rates or a missing bridge
+#[wasm_bindgen]
+pub fn set_envelope_monitor_rate_global(rate_hz: f32) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.set_envelope_monitor_rate(rate_hz),
+        None => false,
+    }
+}
+
+/// Address of the packed voice envelopes (null if the bridge is missing)
+/// 32 voices x 5 floats: stage, progress, level, note, channel
+#[wasm_bindgen]
+pub fn get_envelope_monitor_ptr_global() -> *const f32 {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.envelope_monitor_ptr(),
+        None => std::ptr::null(),
+    }
+}
+
+/// Refreshes of the packed voice envelopes so far (wrapping; 0 if the bridge is missing)
+#[wasm_bindgen]
+pub fn get_envelope_monitor_updates_global() -> u32 {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.envelope_monitor_updates(),
+        None => 0,
+    }
+}
+
+/// Envelopes of the sounding voices as JSON (VoiceEnvelope[]), read now
+#[wasm_bindgen]
+pub fn get_voice_envelopes_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            let envelopes = bridge.midi_player_mut().voice_envelopes();
+            let active: Vec<_> = envelopes.iter().filter(|envelope| envelope.active).collect();
+            serde_json::to_string(&active).unwrap_or_else(|_| "[]".to_string())
+        }
+        None => "[]".to_string(),
+    }
+}
+
+/// Change the global bridge's block size without reinitializing (32-1024 frames);
+/// the switch happens at the next block boundary
+/// @deprecated Send `{"type":"setParam","name":"bufferSize","value":size}` through bridge_command instead
+#[wasm_bindgen]
+pub fn set_buffer_size_global(size: usize) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("set_buffer_size_global");
+    protocol::execute_export(protocol::BridgeCommand::SetParam { name: "bufferSize".to_string(), value: size as f64 })?;
+    Ok(())
+}
+
+/// Get current buffer size from global bridge
+#[wasm_bindgen]
+pub fn get_buffer_size_global() -> Result<usize, ErrorEvent> {
+    let _scope = ExportScope::enter("get_buffer_size_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    Ok(bridge.get_buffer_size())
+}
+
+/// Reset audio state in global bridge (stop all voices, clear events)
+#[wasm_bindgen]
+pub fn reset_audio_state_global() -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("reset_audio_state_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    bridge.reset_audio_state();
+    Ok(())
+}
+
+// Debug log system removed - replaced with structured diagnostic functions
+
+// ===== BUFFER MANAGEMENT EXPORTS =====
+
+/// Set device information for buffer optimization
+/// Unless a profile was chosen explicitly, this also applies the matching device profile
+#[wasm_bindgen]
+pub fn set_device_info_global(hardware_concurrency: u32, device_memory_gb: u32) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("set_device_info_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    bridge.set_device_info(hardware_concurrency, device_memory_gb);
+    Ok(())
+}
+
+/// Configure buffer size, interpolation, polyphony and effects for a device class:
+/// "lowEnd", "mid", "desktop", or "auto" to follow set_device_info_global
+#[wasm_bindgen]
+pub fn set_device_profile_global(name: &str) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.set_device_profile(name),
+        None => false,
+    }
+}
+
+/// Active device profile and the settings in effect (DeviceProfileStatus JSON)
+#[wasm_bindgen]
+pub fn get_device_profile_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.get_device_profile(),
+        None => "null".to_string(),
+    }
+}
+
+/// Record processing time for buffer management
+#[wasm_bindgen]
+pub fn record_processing_time_global(processing_time_ms: f32, buffer_size: usize) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("record_processing_time_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    bridge.record_processing_time(processing_time_ms, buffer_size);
+    Ok(())
+}
+
+/// Turn automatic quality scaling on or off (on by default). Under sustained load it
+/// steps to linear interpolation, then fewer effects, then half the polyphony, and back
+/// once load stays low; off restores the device profile's settings
+#[wasm_bindgen]
+pub fn set_quality_scaling_global(enabled: bool) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.set_quality_scaling(enabled);
+            true
+        },
+        None => false,
+    }
+}
+
+/// Quality scaling state as `QualityScalingStatus` JSON
+#[wasm_bindgen]
+pub fn get_quality_scaling_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.get_quality_scaling(),
+        None => "null".to_string(),
+    }
+}
+
+/// Receive quality steps as JSON strings (`QualityChange`); pass null/undefined to stop
+/// Called from record_processing_time when the level changes
+#[wasm_bindgen]
+pub fn set_quality_scaling_callback(callback: Option<js_sys::Function>) {
+    audio::quality_scaler::set_listener(callback.map(|callback| {
+        std::rc::Rc::new(move |change: &audio::quality_scaler::QualityChange| {
+            let json = serde_json::to_string(change).unwrap_or_else(|_| "{}".to_string());
+            let _ = callback.call1(&JsValue::NULL, &JsValue::from_str(&json));
+        }) as audio::quality_scaler::QualityListener
+    }));
+}
+
+/// Configure the block deadline watchdog: render_stereo_block is timed against the time
+/// its block plays for, and blocks at or over `warn_load` of it (0.1-1.0, default 0.7)
+/// are reported to the deadline callback. With `scale_quality` the measured load also
+/// drives quality scaling, for hosts that don't call record_processing_time_global
+#[wasm_bindgen]
+pub fn set_deadline_watchdog_global(enabled: bool, warn_load: f32, scale_quality: bool) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.set_deadline_watchdog(enabled, warn_load, scale_quality);
+            true
+        },
+        None => false,
+    }
+}
+
+/// Deadline watchdog settings and counters as `DeadlineStatus` JSON
+#[wasm_bindgen]
+pub fn get_deadline_status_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.get_deadline_status(),
+        None => "null".to_string(),
+    }
+}
+
+/// Receive near-miss warnings as JSON strings (`DeadlineWarning`); pass null/undefined to stop
+/// At most one per second of audio, counting the near misses in between
+#[wasm_bindgen]
+pub fn set_deadline_warning_callback(callback: Option<js_sys::Function>) {
+    audio::deadline::set_listener(callback.map(|callback| {
+        std::rc::Rc::new(move |warning: &audio::deadline::DeadlineWarning| {
+            let json = serde_json::to_string(warning).unwrap_or_else(|_| "{}".to_string());
+            let _ = callback.call1(&JsValue::NULL, &JsValue::from_str(&json));
+        }) as audio::deadline::DeadlineListener
+    }));
+}
+
+/// Record buffer underrun (audio glitch)
+#[wasm_bindgen]
+pub fn record_underrun_global() -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("record_underrun_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    bridge.record_underrun();
+    Ok(())
+}
+
+/// Get buffer performance metrics as JSON
+#[wasm_bindgen]
+pub fn get_buffer_metrics_global() -> Result<String, ErrorEvent> {
+    let _scope = ExportScope::enter("get_buffer_metrics_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    Ok(bridge.get_buffer_metrics())
+}
+
+/// Get buffer status summary as JSON
+#[wasm_bindgen]
+pub fn get_buffer_status_global() -> Result<String, ErrorEvent> {
+    let _scope = ExportScope::enter("get_buffer_status_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    Ok(bridge.get_buffer_status())
+}
+
+/// Keep 1-3 blocks rendered ahead of the worklet to ride out callback stalls (0 = off)
+/// Each queued block adds one block of latency to MIDI input and the metronome
+#[wasm_bindgen]
+pub fn set_render_ahead_global(blocks: u32) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.set_render_ahead(blocks as usize);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Offline render mode for exports: once the sequence has ended and no events are queued,
+/// render_stereo_block_global returns 0 as soon as every voice has ended and the output has
+/// stayed below `threshold_db` dBFS for 250 ms, or `tail_cap_seconds` after the last event
+/// (non-finite values keep the defaults, -90 dB and 30 s). Setting it again re-arms the detector
+#[wasm_bindgen]
+pub fn set_offline_render_global(enabled: bool, threshold_db: f32, tail_cap_seconds: f32) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("set_offline_render_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    bridge.set_offline_render(enabled, threshold_db, tail_cap_seconds);
+    Ok(())
+}
+
+/// Offline render mode and whether (and why) it stopped - `OfflineRenderStatus` JSON
+#[wasm_bindgen]
+pub fn get_offline_render_status_global() -> Result<String, ErrorEvent> {
+    let _scope = ExportScope::enter("get_offline_render_status_global");
+    Ok(global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.get_offline_render_status())
+}
+
+/// Convert MIDI files (an array of `Uint8Array`s) to audio one after another with the loaded
+/// SoundFont; `BatchOptions` JSON. Drive it with step_batch_render_global
+#[wasm_bindgen]
+pub fn start_batch_render_global(files: js_sys::Array, options_json: &str) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("start_batch_render_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    let options = audio::batch::BatchOptions::from_json(options_json)
+        .ok_or_else(|| ErrorEvent::raise(ErrorCode::InvalidArgument, "Batch options do not parse"))?;
+    if audio::export::ExportFormat::from_name(&options.format).is_none() {
+        return Err(unknown_format(&options.format));
+    }
+    let files = files.iter().map(|file| js_sys::Uint8Array::new(&file).to_vec()).collect();
+    if !bridge.start_batch_render(files, options) {
+        return Err(ErrorEvent::raise(ErrorCode::InvalidArgument, "A batch is already running"));
+    }
+    Ok(())
+}
+
+/// Returns true while work remains
+#[wasm_bindgen]
+pub fn step_batch_render_global(max_frames: usize) -> Result<bool, ErrorEvent> {
+    let _scope = ExportScope::enter("step_batch_render_global");
+    Ok(global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.step_batch_render(max_frames))
+}
+
+/// Returns true if a batch was running
+#[wasm_bindgen]
+pub fn cancel_batch_render_global() -> Result<bool, ErrorEvent> {
+    let _scope = ExportScope::enter("cancel_batch_render_global");
+    Ok(global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.cancel_batch_render())
+}
+
+/// Encoded output of one file of the last batch; throws if it failed, isn't done or was already taken
+#[wasm_bindgen]
+pub fn take_batch_output_global(index: usize) -> Result<Vec<u8>, ErrorEvent> {
+    let _scope = ExportScope::enter("take_batch_output_global");
+    let output = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.take_batch_output(index);
+    if output.is_empty() {
+        return Err(ErrorEvent::raise(ErrorCode::InvalidArgument, format!("No output for batch file {}", index)));
+    }
+    Ok(output)
+}
+
+/// Batch progress - `BatchStatus` JSON, or "null" before the first batch
+#[wasm_bindgen]
+pub fn get_batch_render_status_global() -> Result<String, ErrorEvent> {
+    let _scope = ExportScope::enter("get_batch_render_status_global");
+    Ok(global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.get_batch_render_status())
+}
+
+/// Render a stretch of the loaded song under two settings snapshots (`AbRequest` JSON) and
+/// compare them; `AbComparison` JSON. Throws `invalidArgument` for a bad request, no song,
+/// a start past its end or a running batch. Blocks until both passes are rendered
+#[wasm_bindgen]
+pub fn render_ab_comparison_global(request_json: &str) -> Result<String, ErrorEvent> {
+    let _scope = ExportScope::enter("render_ab_comparison_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    let request = audio::ab_compare::AbRequest::from_json(request_json)
+        .ok_or_else(|| ErrorEvent::raise(ErrorCode::InvalidArgument, "A/B request does not parse"))?;
+    let comparison = bridge.render_ab_comparison(&request).ok_or_else(|| {
+        ErrorEvent::raise(ErrorCode::InvalidArgument, "Nothing to compare: no song loaded, start past its end or a batch running")
+    })?;
+    Ok(serde_json::to_string(&comparison).unwrap_or_else(|_| "null".to_string()))
+}
+
+/// Interleaved stereo render of pass "a" or "b" of the last comparison (empty once taken)
+#[wasm_bindgen]
+pub fn take_ab_render_global(pass: &str) -> Result<Vec<f32>, ErrorEvent> {
+    let _scope = ExportScope::enter("take_ab_render_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    if !matches!(pass, "a" | "b") {
+        return Err(ErrorEvent::raise(ErrorCode::InvalidArgument, format!("Unknown A/B pass \"{}\"", pass)));
+    }
+    Ok(bridge.take_ab_render(pass))
+}
+
+/// Let repeated underruns (3 within 10 seconds) deepen render-ahead by a block (on by default)
+#[wasm_bindgen]
+pub fn set_render_ahead_auto_global(enabled: bool) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.set_render_ahead_auto(enabled);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Render-ahead depth, queued frames and added latency (RenderAheadStatus JSON)
+#[wasm_bindgen]
+pub fn get_render_ahead_status_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.get_render_ahead_status(),
+        None => "null".to_string(),
+    }
+}
+
+/// Master output loudness (LoudnessReport JSON): momentary/short-term/integrated LUFS and true peak
+#[wasm_bindgen]
+pub fn get_loudness_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.get_loudness(),
+        None => "null".to_string(),
+    }
+}
+
+/// Clear reverb and chorus tails at once (e.g. before auditioning a new section)
+#[wasm_bindgen]
+pub fn flush_effects_global() -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().flush_effects();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Let a controller drive a global effect parameter: `parameter` is "reverbRoomSize",
+/// "reverbDamping", "reverbDiffusion", "reverbReturn", "chorusRate", "chorusDepth",
+/// "chorusFeedback", "chorusSpread" or "chorusReturn", scaled from `min` (value 0) to `max`
+/// (value 127); "off" removes the mapping. Controllers the engine already uses are refused
+#[wasm_bindgen]
+pub fn set_effects_cc_mapping_global(controller: u8, parameter: &str, min: f32, max: f32) -> bool {
+    let mapping = match parameter {
+        "off" => None,
+        name => match EffectsParameter::from_name(name) {
+            Some(parameter) => Some((parameter, min, max)),
+            None => return false,
+        },
+    };
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().set_effects_cc_mapping(controller, mapping),
+        None => false,
+    }
+}
+
+/// Controllers mapped to effect parameters as EffectsCcMapping JSON array
+#[wasm_bindgen]
+pub fn get_effects_cc_map_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => serde_json::to_string(&bridge.midi_player_mut().effects_cc_mappings()).unwrap_or_else(|_| "[]".to_string()),
+        None => "[]".to_string(),
+    }
+}
+
+/// Restart loudness metering, e.g. before rendering a take to be measured
+#[wasm_bindgen]
+pub fn reset_loudness_global() -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.reset_loudness();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Get recommended buffer size for target latency
+#[wasm_bindgen]
+pub fn get_recommended_buffer_size_global(target_latency_ms: f32) -> Result<u32, ErrorEvent> {
+    let _scope = ExportScope::enter("get_recommended_buffer_size_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    Ok(bridge.get_recommended_buffer_size(target_latency_ms))
+}
+
+/// Get current buffer latency in milliseconds
+#[wasm_bindgen]
+pub fn get_current_latency_ms_global() -> Result<f32, ErrorEvent> {
+    let _scope = ExportScope::enter("get_current_latency_ms_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    Ok(bridge.get_current_latency_ms())
+}
+
+/// Enable or disable adaptive buffer sizing
+/// @deprecated Send `{"type":"setParam","name":"adaptiveMode","value":0|1}` through bridge_command instead
+#[wasm_bindgen]
+pub fn set_adaptive_mode_global(enabled: bool) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("set_adaptive_mode_global");
+    let value = if enabled { 1.0 } else { 0.0 };
+    protocol::execute_export(protocol::BridgeCommand::SetParam { name: "adaptiveMode".to_string(), value })?;
+    Ok(())
+}
+
+// ===== PIPELINE MANAGEMENT EXPORTS =====
+
+/// Get pipeline status as string
+#[wasm_bindgen]
+pub fn get_pipeline_status_global() -> Result<String, ErrorEvent> {
+    let _scope = ExportScope::enter("get_pipeline_status_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    Ok(bridge.get_pipeline_status())
+}
+
+/// Check if pipeline is ready for processing
+#[wasm_bindgen]
+pub fn is_pipeline_ready_global() -> Result<bool, ErrorEvent> {
+    let _scope = ExportScope::enter("is_pipeline_ready_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    Ok(bridge.is_pipeline_ready())
+}
+
+/// Get comprehensive pipeline statistics as JSON
+#[wasm_bindgen]
+pub fn get_pipeline_stats_global() -> Result<String, ErrorEvent> {
+    let _scope = ExportScope::enter("get_pipeline_stats_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    Ok(bridge.get_pipeline_stats())
+}
+
+/// Reset pipeline state
+#[wasm_bindgen]
+pub fn reset_pipeline_global() -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("reset_pipeline_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    bridge.reset_pipeline();
+    Ok(())
+}
+
+/// Get combined audio and pipeline status as JSON
+#[wasm_bindgen]
+pub fn get_comprehensive_status_global() -> Result<String, ErrorEvent> {
+    let _scope = ExportScope::enter("get_comprehensive_status_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    Ok(bridge.get_comprehensive_status())
+}
+
+// ===== MIDI TEST SEQUENCE EXPORTS =====
+
+// Re-export MIDI test sequence functions for global access
+pub use midi::test_sequences::{
+    init_test_sequence_generator,
+    generate_c_major_scale_test,
+    generate_chromatic_scale_test,
+    generate_scale_test,
+    generate_arpeggio_test,
+    generate_chord_test,
+    generate_velocity_test,
+    midi_note_to_name,
+    note_name_to_midi,
+    execute_test_sequence,
+    quick_c_major_test,
+};
+
+// ===== METRONOME EXPORTS =====
+
+/// Enable/disable the metronome click (one click per beat while the sequencer plays)
+#[wasm_bindgen]
+pub fn set_metronome_enabled_global(enabled: bool) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().set_metronome_enabled(enabled);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Set the click level in the main mix and on the click bus (0.0-1.0 each)
+/// Keep main at 0.0 for a clean mix and monitor the click bus in headphones
+#[wasm_bindgen]
+pub fn set_metronome_levels_global(main_level: f32, bus_level: f32) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().set_metronome_levels(main_level, bus_level);
+            true
+        }
+        None => false,
+    }
+}
+
+// ===== SEQUENCE EDITING EXPORTS =====
+
+fn edit_sequence_global(track: usize, edit: midi::edit::TrackEdit) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().edit_sequence_track(track, &edit).is_ok(),
+        None => false,
+    }
+}
+
+/// Tracks of the loaded MIDI file (JSON `SequenceTrack[]`)
+#[wasm_bindgen]
+pub fn get_sequence_tracks_global() -> String {
+    let tracks = match global_bridge_mut().and_then(|bridge| bridge.midi_player_mut().sequence_file()) {
+        Some(file) => midi::edit::SequenceTrack::list(&file.tracks),
+        None => Vec::new(),
+    };
+    serde_json::to_string(&tracks).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Events of one track with start_tick <= tick < end_tick (JSON `SequenceEvent[]`); end_tick 0 lists to the end
+#[wasm_bindgen]
+pub fn get_sequence_events_global(track: usize, start_tick: u32, end_tick: u32) -> String {
+    let end_tick = if end_tick == 0 { u64::MAX } else { end_tick as u64 };
+    let events = match global_bridge_mut().and_then(|bridge| bridge.midi_player_mut().sequence_file()) {
+        Some(file) => file.tracks.get(track)
+            .map(|t| midi::edit::SequenceEvent::list(t, start_tick as u64, end_tick))
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+    serde_json::to_string(&events).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Insert a note (note-on plus note-off `duration` ticks later)
+#[wasm_bindgen]
+pub fn insert_sequence_note_global(track: usize, tick: u32, duration: u32, channel: u8, note: u8, velocity: u8) -> bool {
+    edit_sequence_global(track, midi::edit::TrackEdit::InsertNote {
+        tick: tick as u64, duration: duration as u64, channel, note, velocity,
+    })
+}
+
+/// Insert a control change
+#[wasm_bindgen]
+pub fn insert_sequence_control_change_global(track: usize, tick: u32, channel: u8, controller: u8, value: u8) -> bool {
+    edit_sequence_global(track, midi::edit::TrackEdit::InsertControlChange { tick: tick as u64, channel, controller, value })
+}
+
+/// Delete events by index (note-ons take their note-offs with them)
+#[wasm_bindgen]
+pub fn delete_sequence_events_global(track: usize, indices: &[u32]) -> bool {
+    edit_sequence_global(track, midi::edit::TrackEdit::Delete { indices: indices.iter().map(|&i| i as usize).collect() })
+}
+
+/// Shift events by `delta_ticks` (clamped so nothing moves before tick 0)
+#[wasm_bindgen]
+pub fn move_sequence_events_global(track: usize, indices: &[u32], delta_ticks: i32) -> bool {
+    edit_sequence_global(track, midi::edit::TrackEdit::Move {
+        indices: indices.iter().map(|&i| i as usize).collect(),
+        delta_ticks: delta_ticks as i64,
+    })
+}
+
+/// Snap events to the nearest multiple of `grid_ticks`; notes keep their length
+#[wasm_bindgen]
+pub fn quantize_sequence_events_global(track: usize, indices: &[u32], grid_ticks: u32) -> bool {
+    edit_sequence_global(track, midi::edit::TrackEdit::Quantize {
+        indices: indices.iter().map(|&i| i as usize).collect(),
+        grid_ticks: grid_ticks as u64,
+    })
+}
+
+/// Limit recording to punch_in..punch_out (ticks); leave either undefined to keep that side open
+/// Throws `invalidArgument` if punch_out is not after punch_in
+#[wasm_bindgen]
+pub fn set_punch_region_global(punch_in: Option<u32>, punch_out: Option<u32>) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("set_punch_region_global");
+    let player = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.midi_player_mut();
+    if !player.set_punch_region(punch_in.map(u64::from), punch_out.map(u64::from)) {
+        return Err(ErrorEvent::raise(ErrorCode::InvalidArgument, "Punch out must be after punch in"));
+    }
+    Ok(())
+}
+
+/// Bars of playback before punch-in when a recording pass starts (0-16)
+#[wasm_bindgen]
+pub fn set_pre_roll_bars_global(bars: u32) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("set_pre_roll_bars_global");
+    global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.midi_player_mut().set_pre_roll_bars(bars);
+    Ok(())
+}
+
+/// Report a pass the sequence refused (kept by the recorder, possibly from the audio path)
+fn raise_recording_failure(player: &mut MidiPlayer) -> Result<(), ErrorEvent> {
+    match player.take_recording_failure() {
+        Some(failure) => Err(ErrorEvent::raise(ErrorCode::Internal, failure.to_string())),
+        None => Ok(()),
+    }
+}
+
+/// Start playback and overdub live input into `track`. Throws `invalidArgument` without a file
+/// or that track, and `internal` if the previous pass could not be stored
+#[wasm_bindgen]
+pub fn start_recording_global(track: usize) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("start_recording_global");
+    let player = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.midi_player_mut();
+    let started = player.start_recording(track);
+    raise_recording_failure(player)?;
+    if !started {
+        return Err(ErrorEvent::raise(ErrorCode::InvalidArgument, format!("Cannot record into track {}", track)));
+    }
+    Ok(())
+}
+
+/// Punch out now; playback keeps running. Returns the number of messages merged into the track;
+/// throws if the pass could not be stored
+#[wasm_bindgen]
+pub fn stop_recording_global() -> Result<u32, ErrorEvent> {
+    let _scope = ExportScope::enter("stop_recording_global");
+    let player = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.midi_player_mut();
+    let merged = player.stop_recording() as u32;
+    raise_recording_failure(player)?;
+    Ok(merged)
+}
+
+/// Punch region, pre-roll and progress of the recording pass (`RecordingStatus` JSON).
+/// A pass refused since the last call (end of song, stop) goes to the error callback
+#[wasm_bindgen]
+pub fn get_recording_status_global() -> Result<String, ErrorEvent> {
+    let _scope = ExportScope::enter("get_recording_status_global");
+    let player = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.midi_player_mut();
+    let _ = raise_recording_failure(player);
+    Ok(serde_json::to_string(&player.recording_status()).unwrap_or_else(|_| "null".to_string()))
+}
+
+/// Keep later recording passes as takes (tracks of their own) instead of merging them
+#[wasm_bindgen]
+pub fn set_take_recording_global(enabled: bool) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("set_take_recording_global");
+    global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.midi_player_mut().set_take_recording(enabled);
+    Ok(())
+}
+
+/// Takes recorded into the loaded file (`Take[]` JSON)
+#[wasm_bindgen]
+pub fn get_takes_global() -> Result<String, ErrorEvent> {
+    let _scope = ExportScope::enter("get_takes_global");
+    let player = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.midi_player_mut();
+    Ok(serde_json::to_string(player.takes()).unwrap_or_else(|_| "[]".to_string()))
+}
+
+fn no_take(id: u32) -> ErrorEvent {
+    ErrorEvent::raise(ErrorCode::InvalidArgument, format!("No take {}", id))
+}
+
+/// Make a take the one of its group that plays; the others are muted
+#[wasm_bindgen]
+pub fn select_take_global(id: u32) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("select_take_global");
+    if !global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.midi_player_mut().select_take(id) {
+        return Err(no_take(id));
+    }
+    Ok(())
+}
+
+/// Select a take and play its region from its pre-roll (throws for an unknown take or while recording)
+#[wasm_bindgen]
+pub fn audition_take_global(id: u32) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("audition_take_global");
+    let player = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.midi_player_mut();
+    if player.recording_status().phase != midi::recording::RecordingPhase::Idle {
+        return Err(ErrorEvent::raise(ErrorCode::InvalidArgument, "Cannot audition a take while recording"));
+    }
+    if !player.audition_take(id) {
+        return Err(no_take(id));
+    }
+    Ok(())
+}
+
+/// Merge a take into the track it was recorded for and drop the rest of its group
+#[wasm_bindgen]
+pub fn commit_take_global(id: u32) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("commit_take_global");
+    global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.midi_player_mut().commit_take(id)
+}
+
+/// Drop a take and its track
+#[wasm_bindgen]
+pub fn discard_take_global(id: u32) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("discard_take_global");
+    global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.midi_player_mut().discard_take(id)
+}
+
+/// A take as a Standard MIDI File with `MidiExportOptions` JSON (quantize grid, swing, legato)
+/// applied to the export only; throws `invalidArgument` for an unknown take or options that don't parse
+#[wasm_bindgen]
+pub fn export_take_midi_global(id: u32, options_json: &str) -> Result<Vec<u8>, ErrorEvent> {
+    let _scope = ExportScope::enter("export_take_midi_global");
+    let player = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.midi_player_mut();
+    let options = midi::take_export::MidiExportOptions::from_json(options_json)
+        .ok_or_else(|| ErrorEvent::raise(ErrorCode::InvalidArgument, "MIDI export options do not parse"))?;
+    player.export_take_midi(id, &options).ok_or_else(|| no_take(id))
+}
+
+// ===== UTILITY EXPORTS =====
+
+/// Initialize all global systems with sample rate
+/// @deprecated Send `{"type":"init","sampleRate":...}` through bridge_command instead
+#[wasm_bindgen]
+pub fn init_all_systems(sample_rate: f32) -> bool {
+    log(&format!("🚀 SYSTEM INIT: Starting complete system initialization at {}Hz", sample_rate));
+    let mut success = true;
+    
+    // Initialize AudioWorklet bridge with enhanced tracking
+    log("🔧 SYSTEM INIT: Initializing AudioWorklet bridge...");
+    if !init_audio_worklet(sample_rate) {
+        log("❌ SYSTEM INIT: AudioWorklet bridge initialization FAILED");
+        success = false;
+    } else {
+        log("✅ SYSTEM INIT: AudioWorklet bridge initialization SUCCESS");
+        
+        // Immediate post-init verification
+        unsafe {
+            let bridge_available = GLOBAL_WORKLET_BRIDGE.is_some();
+            log(&format!("🔍 SYSTEM INIT: Bridge verification after init - available: {}", bridge_available));
+        }
+    }
+    
+    // Initialize MIDI test sequence generator
+    log("🔧 SYSTEM INIT: Initializing MIDI test sequence generator...");
+    init_test_sequence_generator(sample_rate);
+    log("✅ SYSTEM INIT: MIDI test sequence generator initialized");
+    
+    // Final system status
+    if success {
+        log(&format!("🎉 SYSTEM INIT: AWE Player systems fully initialized at {}Hz", sample_rate));
+    } else {
+        log(&format!("⚠️ SYSTEM INIT: AWE Player systems initialization completed with errors at {}Hz", sample_rate));
+    }
+    
+    success
+}
+
+/// Get system status overview as JSON
+#[wasm_bindgen]
+pub fn get_system_status() -> String {
+    let (pipeline_ready, buffer_status, pipeline_stats) = match global_bridge_mut() {
+        Some(bridge) => (bridge.is_pipeline_ready(), bridge.get_buffer_status(), bridge.get_pipeline_stats()),
+        None => (false, "{}".to_string(), "{}".to_string()),
+    };
+    
+    format!(r#"{{"pipelineReady": {}, "bufferStatus": {}, "pipelineStats": {}}}"#,
+        pipeline_ready, buffer_status, pipeline_stats)
+}
+
+/// Get AWE Player version and build info
+#[wasm_bindgen]
+pub fn get_version_info() -> String {
+    r#"{"name": "AWE Player", "version": "0.1.0", "phase": "9A.7", "architecture": "Rust-Centric"}"#.to_string()
+}
+
+// ===== SOUNDFONT 2.0 EXPORTS =====
+
+/// Initialize SoundFont module
+#[wasm_bindgen]
+pub fn init_soundfont_module() -> String {
+    match soundfont::SoundFontModule::initialize() {
+        Ok(_) => {
+            log("SoundFont module initialized successfully");
+            r#"{"success": true, "message": "SoundFont module ready", "version": "SF2.0"}"#.to_string()
+        }
+        Err(e) => {
+            log(&format!("SoundFont module initialization failed: {}", e));
+            format!(r#"{{"success": false, "error": "{}"}}"#, e)
+        }
+    }
+}
+
+/// Validate SoundFont file header
+#[wasm_bindgen]
+pub fn validate_soundfont_header(data: &[u8]) -> String {
+    match soundfont::SoundFontModule::validate_soundfont_header(data) {
+        Ok(valid) => {
+            if valid {
+                log("SoundFont header validation passed");
+                r#"{"valid": true, "format": "SF2.0", "message": "Valid SoundFont file"}"#.to_string()
+            } else {
+                log("SoundFont header validation failed - invalid format");
+                r#"{"valid": false, "error": "Invalid SoundFont format"}"#.to_string()
+            }
+        }
+        Err(e) => {
+            log(&format!("SoundFont header validation error: {}", e));
+            format!(r#"{{"valid": false, "error": "{}"}}"#, e)
+        }
+    }
+}
+
+/// Get SoundFont module information
+#[wasm_bindgen]
+pub fn get_soundfont_info() -> String {
+    format!(r#"{{"version": "{}", "supports": ["SF2.0", "SF2.01", "SF2.1"], "status": "initialized"}}"#,
+        soundfont::SoundFontModule::get_format_version())
+}
+
+/// Parse complete SoundFont file and load into synthesis engine
+/// Throws `invalidArgument` if the file does not parse
+/// @deprecated Send `{"type":"loadSoundFont"}` with the file through bridge_command_with_payload instead
+#[wasm_bindgen]
+pub fn parse_soundfont_file(data: &[u8]) -> Result<String, ErrorEvent> {
+    let _scope = ExportScope::enter("parse_soundfont_file");
+    let soundfont = parse_soundfont_reporting(data)
+        .map_err(|e| ErrorEvent::raise(ErrorCode::InvalidArgument, format!("Parsing failed: {}", e)))?;
+    install_soundfont(soundfont)
+}
+
+/// The ErrorEvent for a failed load (the load event carries the finer code)
+fn raise_load_error(code: events::LoadErrorCode, message: String) -> ErrorEvent {
+    use events::LoadErrorCode;
+    match code {
+        LoadErrorCode::NotInitialized => ErrorEvent::not_initialized(),
+        LoadErrorCode::InvalidFormat | LoadErrorCode::CorruptData => ErrorEvent::raise(ErrorCode::InvalidArgument, message),
+        LoadErrorCode::Network | LoadErrorCode::Cancelled | LoadErrorCode::EngineRejected => {
+            ErrorEvent::raise(ErrorCode::Internal, message)
+        }
+    }
+}
+
+/// Parse an in-memory SF2 file, emitting `started`, parse progress and (on error) `failed` load events
+pub(crate) fn parse_soundfont_reporting(data: &[u8]) -> soundfont::SoundFontResult<SoundFont> {
+    events::emit(events::LoadEvent::Started { kind: events::LoadKind::SoundFont, total_bytes: Some(data.len()) });
+    let result = soundfont::SoundFontParser::parse_soundfont_with_progress(
+        data, &mut events::parse_progress(events::LoadKind::SoundFont, 0.0));
+    if let Err(e) = &result {
+        events::emit(events::LoadEvent::failed(
+            events::LoadKind::SoundFont, events::LoadErrorCode::from_soundfont_error(e), e.to_string()));
+    }
+    result
+}
+
+/// Hand a parsed SoundFont to the engine, emitting the remaining load events
+pub(crate) fn install_soundfont_reporting(mut soundfont: SoundFont) -> Result<(), (events::LoadErrorCode, String)> {
+    use events::{emit, LoadEvent, LoadKind, LoadErrorCode, LoadStage};
+
+    // Identify by original content, before sample reduction
+    let identity = soundfont::identity::SoundFontIdentity::of(&soundfont);
+    if let Some(bridge) = global_bridge_mut() {
+        bridge.reduce_soundfont(&mut soundfont);
+    }
+    emit(LoadEvent::SamplesLoaded { count: soundfont.samples.len() });
+    emit(LoadEvent::progress(LoadKind::SoundFont, LoadStage::Engine, events::PARSE_END_PERCENT));
+    let (presets, instruments) = (soundfont.presets.len(), soundfont.instruments.len());
+
+    let result = match global_bridge_mut() {
+        Some(bridge) => {
+            let load_result = bridge.load_soundfont_internal(soundfont);
+            if load_result.is_ok() {
+                bridge.set_soundfont_identity(identity);
+            }
+            // Attribute load-time growth now rather than at the next audio block
+            audio::memory::observe_growth(bridge.midi_player_mut().get_active_voice_count() > 0);
+            load_result.map_err(|e| (LoadErrorCode::EngineRejected, e))
+        }
+        None => Err((LoadErrorCode::NotInitialized, "AudioWorklet bridge not initialized".to_string())),
+    };
+
+    match &result {
+        Ok(()) => {
+            emit(LoadEvent::PresetsIndexed { presets, instruments });
+            emit(LoadEvent::Complete { kind: LoadKind::SoundFont });
+        }
+        Err((code, message)) => emit(LoadEvent::failed(LoadKind::SoundFont, *code, message.clone())),
+    }
+    result
+}
+
+/// Load a parsed SoundFont into the synthesis engine (shared by the file and URL loaders)
+fn install_soundfont(soundfont: SoundFont) -> Result<String, ErrorEvent> {
+    // Log basic parsing info
+    log(&format!("SoundFont parsed successfully: '{}' with {} presets, {} instruments, {} samples",
+               soundfont.header.name, soundfont.presets.len(), 
+               soundfont.instruments.len(), soundfont.samples.len()));
+    
+    // Analyze and log loop validation summary
+    let mut valid_loops = 0;
+    let mut no_loops = 0;
+    let mut invalid_loops = 0;
+    
+    for sample in &soundfont.samples {
+        if sample.loop_end > 0 && sample.loop_start < sample.loop_end {
+            valid_loops += 1;
+        } else if sample.loop_start == 0 && sample.loop_end == 0 {
+            no_loops += 1;
+        } else {
+            invalid_loops += 1;
+        }
+    }
+    
+    // Log loop validation summary
+    log(&format!("📊 LOOP VALIDATION: {} samples total - ✅ {} with loops, ⭕ {} without loops (normal), ❌ {} invalid",
+                soundfont.samples.len(), valid_loops, no_loops, invalid_loops));
+    
+    if invalid_loops > 0 {
+        log(&format!("⚠️ WARNING: {} samples had invalid loop data", invalid_loops));
+    }
+    
+    // Load SoundFont into synthesis engine
+    install_soundfont_reporting(soundfont).map_err(|(code, e)| raise_load_error(code, e))?;
+    log("✅ SoundFont loaded successfully into synthesis engine");
+    Ok(r#"{"success": true, "message": "SoundFont loaded into synthesis engine"}"#.to_string())
+}
+
+/// Download a SoundFont from `url` and load it, parsing the body as it streams in (feature "fetch")
+/// `progress_callback(bytesReceived, totalBytes)` is optional; resolves to the same JSON as
+/// parse_soundfont_file and rejects with an ErrorEvent
+#[cfg(feature = "fetch")]
+#[wasm_bindgen]
+pub async fn load_soundfont_from_url(url: String, progress_callback: Option<js_sys::Function>) -> Result<String, ErrorEvent> {
+    events::emit(events::LoadEvent::Started { kind: events::LoadKind::SoundFont, total_bytes: None });
+    let fetched = soundfont::fetch::fetch_soundfont(&url, progress_callback).await;
+    // Entered after the download: other exports run while it is awaited
+    let _scope = ExportScope::enter("load_soundfont_from_url");
+    match fetched {
+        Ok(soundfont) => install_soundfont(soundfont),
+        Err(e) => {
+            let code = events::LoadErrorCode::from_soundfont_error(&e);
+            events::emit(events::LoadEvent::failed(events::LoadKind::SoundFont, code, e.to_string()));
+            Err(raise_load_error(code, format!("Loading failed: {}", e)))
+        }
+    }
+}
+
+/// Abort the load_soundfont_from_url in progress; its promise resolves with an error
+#[cfg(feature = "fetch")]
+#[wasm_bindgen]
+pub fn cancel_soundfont_load() {
+    soundfont::fetch::cancel();
+}
+
+/// Memory-saving options for later SoundFont loads: downsample samples above
+/// `target_sample_rate` Hz (min 8000) and truncate samples longer than `max_sample_seconds`
+/// (looped samples keep their loop); 0 disables either limit
+#[wasm_bindgen]
+pub fn set_soundfont_load_options_global(target_sample_rate: u32, max_sample_seconds: f32) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("set_soundfont_load_options_global");
+    let bridge = global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?;
+    bridge.set_sample_reduction(soundfont::reduce::SampleReduction::from_limits(target_sample_rate, max_sample_seconds));
+    Ok(())
+}
+
+/// Content hash, name and version of the loaded SoundFont (`SoundFontIdentity` JSON, "null" if none)
+/// Stable across load paths; use it as a cache or session key
+#[wasm_bindgen]
+pub fn get_soundfont_identity_global() -> String {
+    match global_bridge_mut().and_then(|bridge| bridge.soundfont_identity()) {
+        Some(identity) => serde_json::to_string(identity).unwrap_or_else(|_| "null".to_string()),
+        None => "null".to_string(),
+    }
+}
+
+/// Identity of a SoundFont file without loading it (same JSON as get_soundfont_identity_global;
+/// throws `invalidArgument` if it does not parse) - compare hashes to skip loading a bank that is already active
+#[wasm_bindgen]
+pub fn identify_soundfont(data: &[u8]) -> Result<String, ErrorEvent> {
+    let _scope = ExportScope::enter("identify_soundfont");
+    let soundfont = soundfont::SoundFontParser::parse_soundfont(data)
+        .map_err(|e| ErrorEvent::raise(ErrorCode::InvalidArgument, format!("Parsing failed: {}", e)))?;
+    Ok(serde_json::to_string(&soundfont::identity::SoundFontIdentity::of(&soundfont)).unwrap_or_else(|_| "null".to_string()))
+}
+
+/// Samples resampled/truncated and bytes before/after for the last SoundFont load (JSON)
+#[wasm_bindgen]
+pub fn get_sample_reduction_report_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => serde_json::to_string(&bridge.last_reduction()).unwrap_or_else(|_| "{}".to_string()),
+        None => "{}".to_string(),
+    }
+}
+
+/// Samples of the last SoundFont load whose declared sample rate was 0 or out of range,
+/// with the rate used instead (JSON array of `SampleRateWarning`, empty if none)
+#[wasm_bindgen]
+pub fn get_sample_rate_warnings_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => serde_json::to_string(bridge.sample_rate_warnings()).unwrap_or_else(|_| "[]".to_string()),
+        None => "[]".to_string(),
+    }
+}
+
+/// Nudge loop points that miss the sample by a sample or two into range instead of
+/// dropping the loop, for every later SoundFont load (off by default)
+#[wasm_bindgen]
+pub fn set_loop_repair_global(enabled: bool) {
+    soundfont::loop_repair::set_enabled(enabled);
+}
+
+#[wasm_bindgen]
+pub fn get_loop_repair_global() -> bool {
+    soundfont::loop_repair::is_enabled()
+}
+
+/// Samples of the last SoundFont load whose loop points were repaired, with the declared and
+/// repaired points (JSON array of `LoopRepair`, empty if none)
+#[wasm_bindgen]
+pub fn get_loop_repairs_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => serde_json::to_string(bridge.loop_repairs()).unwrap_or_else(|_| "[]".to_string()),
+        None => "[]".to_string(),
+    }
+}
+
+/// Move loop points of later SoundFont loads to the nearest rising zero crossing within
+/// `window` samples either side (0 = off, max 64) to soften loop clicks
+#[wasm_bindgen]
+pub fn set_loop_snap_window_global(window: u32) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.set_loop_snap_window(window);
+            true
+        }
+        None => false,
+    }
+}
+
+#[wasm_bindgen]
+pub fn get_loop_snap_window_global() -> u32 {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.loop_snap_window(),
+        None => 0,
+    }
+}
+
+/// Samples of the last SoundFont load whose loops were snapped, with the points before and
+/// after (JSON array of `LoopAdjustment`, empty if none)
+#[wasm_bindgen]
+pub fn get_loop_adjustments_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => serde_json::to_string(bridge.loop_adjustments()).unwrap_or_else(|_| "[]".to_string()),
+        None => "[]".to_string(),
+    }
+}
+
+/// Select preset by bank and program number
+/// @deprecated Send `{"type":"selectPreset","bank":...,"program":...}` through bridge_command instead
 #[wasm_bindgen]
-pub fn get_wasm_version() -> String {
-    format!(r#"{{"version": "2025-08-09-22:41", "buildTime": "generator-reading-impl", "hasDebugBridgeStatus": true}}"#)
+pub fn select_preset_global(bank: u16, program: u8) -> String {
+    unsafe {
+        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
+            match bridge.select_preset_internal(bank, program) {
+                Ok(preset_info) => {
+                    log(&format!("Preset selected: {}", preset_info));
+                    format!(r#"{{"success": true, "preset": "{}"}}"#, preset_info)
+                }
+                Err(e) => {
+                    log(&format!("Preset selection failed: {}", e));
+                    format!(r#"{{"success": false, "error": "{}"}}"#, e)
+                }
+            }
+        } else {
+            let error = "AudioWorklet bridge not initialized";
+            log(error);
+            format!(r#"{{"success": false, "error": "{}"}}"#, error)
+        }
+    }
 }
 
-/// Comprehensive bridge lifecycle diagnostic for pipeline testing
+/// Get current preset information
 #[wasm_bindgen]
-pub fn diagnose_bridge_lifecycle() -> String {
+pub fn get_current_preset_info_global() -> String {
     unsafe {
-        let bridge_exists = GLOBAL_WORKLET_BRIDGE.is_some();
-        
-        log(&format!("🔬 BRIDGE LIFECYCLE: Starting comprehensive diagnostic"));
-        log(&format!("🔬 BRIDGE LIFECYCLE: Static variable state - is_some(): {}", bridge_exists));
-        
         if let Some(ref bridge) = GLOBAL_WORKLET_BRIDGE {
-            let sample_rate = bridge.get_sample_rate();
-            log(&format!("🔬 BRIDGE LIFECYCLE: Bridge details - sample_rate: {}Hz, ready for diagnostics", sample_rate));
-            
-            format!(r#"{{
-                "success": true,
-                "bridge": {{
-                    "exists": true,
-                    "sample_rate": {},
-                    "status": "functional",
-                    "lifecycle": "active",
-                    "created": true,
-                    "accessible": true,
-                    "ready_for_diagnostics": true
-                }},
-                "diagnosis": "Bridge is fully operational and ready for all diagnostic functions",
-                "timestamp": "2025-08-09-22:41"
-            }}"#, sample_rate)
+            match bridge.get_current_preset_info_internal() {
+                Some(info) => {
+                    format!(r#"{{"success": true, "preset": "{}"}}"#, info)
+                }
+                None => {
+                    r#"{"success": false, "error": "No preset selected"}"#.to_string()
+                }
+            }
         } else {
-            log("🔬 BRIDGE LIFECYCLE: Bridge is NULL - analyzing possible causes");
-            log("🔬 BRIDGE LIFECYCLE: Cause analysis:");
-            log("   ❌ init_all_systems() might not have been called");
-            log("   ❌ init_audio_worklet() might have failed silently");
-            log("   ❌ Bridge creation might have thrown an exception");
-            log("   ❌ Memory corruption or static variable issue");
-            
-            format!(r#"{{
-                "success": false,
-                "error": "Bridge not available",
-                "bridge": {{
-                    "exists": false,
-                    "status": "missing",
-                    "lifecycle": "not_initialized_or_destroyed",
-                    "created": false,
-                    "accessible": false,
-                    "ready_for_diagnostics": false
-                }},
-                "diagnosis": "Bridge is not initialized - all diagnostic functions will fail",
-                "possible_causes": [
-                    "init_all_systems() not called from JavaScript",
-                    "init_audio_worklet() failed during creation",
-                    "Static variable memory issue",
-                    "Bridge was destroyed after creation"
-                ],
-                "recommended_actions": [
-                    "Check JavaScript initialization sequence in AwePlayerContext",
-                    "Verify AudioContext creation succeeded",
-                    "Check for exceptions during bridge creation",
-                    "Verify no cleanup code is destroying the bridge"
-                ],
-                "timestamp": "2025-08-09-22:41"
-            }}"#)
+            r#"{"success": false, "error": "AudioWorklet bridge not initialized"}"#.to_string()
         }
     }
 }
 
+/// Enable/disable the procedural GM fallback bank played until a SoundFont is loaded
+#[wasm_bindgen]
+pub fn set_fallback_bank_enabled_global(enabled: bool) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().set_fallback_bank_enabled(enabled);
+            true
+        }
+        None => false,
+    }
+}
 
-/// Debug function to check bridge availability with detailed lifecycle tracking
+/// Preview a preset: play one note for `duration_ms` on an internal channel
+/// Live/sequencer channels, their programs and sounding voices are not affected
+/// Bank 128 selects drum kits; returns false if the preset or a free voice is missing
 #[wasm_bindgen]
-pub fn debug_bridge_status() -> String {
-    unsafe {
-        let available = GLOBAL_WORKLET_BRIDGE.is_some();
-        
-        // Enhanced debugging with lifecycle information
-        if let Some(ref bridge) = GLOBAL_WORKLET_BRIDGE {
-            let sample_rate = bridge.get_sample_rate();
-            log(&format!("🔍 BRIDGE STATUS: Bridge is available - sample_rate: {}Hz", sample_rate));
-            format!(r#"{{"available": true, "sample_rate": {}, "status": "initialized", "lifecycle": "active", "timestamp": "2025-08-09-22:41"}}"#, 
-                    sample_rate)
-        } else {
-            log("⚠️ BRIDGE STATUS: Bridge is NOT available - GLOBAL_WORKLET_BRIDGE is None");
-            log("🔍 BRIDGE STATUS: This could indicate:");
-            log("   1. init_audio_worklet() was never called");
-            log("   2. Bridge creation failed silently");
-            log("   3. Bridge was destroyed/reset after creation");
-            log("   4. Memory management issue with static variable");
-            format!(r#"{{"available": false, "status": "not_initialized", "lifecycle": "missing", "timestamp": "2025-08-09-22:41"}}"#)
+pub fn audition_preset(bank: u16, program: u8, note: u8, velocity: u8, duration_ms: u32) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().audition_preset(bank, program, note, velocity, duration_ms),
+        None => false,
+    }
+}
+
+/// Resolve a preset and touch its zones and sample data ahead of a program change,
+/// so the first note after switching mid-song doesn't glitch. Bank 128 selects drum kits.
+/// Returns a `PreloadReport` JSON (zones, samples and bytes touched), "null" if no preset matches
+#[wasm_bindgen]
+pub fn pre_load_preset(bank: u16, program: u8) -> String {
+    match global_bridge_mut().and_then(|bridge| bridge.midi_player_mut().pre_load_preset(bank, program)) {
+        Some(report) => serde_json::to_string(&report).unwrap_or_else(|_| "null".to_string()),
+        None => "null".to_string(),
+    }
+}
+
+/// Balance preset levels: measure representative notes of every preset of the playing bank
+/// (now, and after each SoundFont load) and trim each toward the bank's median level
+#[wasm_bindgen]
+pub fn set_preset_balance_global(enabled: bool) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().set_preset_balance(enabled);
+            true
         }
+        None => false,
     }
 }
 
-/// Process audio buffer using global AudioWorklet bridge
-/// Optimized for AudioWorklet process() callback - minimal overhead
+/// `PresetBalance` JSON of the last balance pass, "null" if none has run for the playing bank
 #[wasm_bindgen]
-pub fn process_audio_buffer(buffer_length: usize) -> Vec<f32> {
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.process_audio_buffer(buffer_length)
-        } else {
-            log("Error: AudioWorklet bridge not initialized - call init_audio_worklet() first");
-            vec![0.0; buffer_length] // Return silence
+pub fn get_preset_balance_global() -> String {
+    match global_bridge_mut().and_then(|bridge| bridge.midi_player_mut().preset_balance()) {
+        Some(balance) => serde_json::to_string(balance).unwrap_or_else(|_| "null".to_string()),
+        None => "null".to_string(),
+    }
+}
+
+/// Key map of a preset, meant for drum kits (bank 128; same bank fallback as Program Change):
+/// `DrumKitMap` JSON listing every GM percussion key and every key the kit covers, with the
+/// GM drum name and whether a sample plays there. "null" if no preset matches
+#[wasm_bindgen]
+pub fn get_drum_kit_map_global(bank: u16, program: u8) -> String {
+    match global_bridge_mut().and_then(|bridge| bridge.midi_player_mut().drum_kit_map(bank, program)) {
+        Some(map) => serde_json::to_string(&map).unwrap_or_else(|_| "null".to_string()),
+        None => "null".to_string(),
+    }
+}
+
+/// Play a preset with these reverb/chorus sends (0.0-1.0) instead of the ones its
+/// generators give; applies to notes started from now on. Exact bank/program of the
+/// loaded SoundFont; false if there is no such preset
+#[wasm_bindgen]
+pub fn set_preset_effects_sends(bank: u16, program: u8, reverb: f32, chorus: f32) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut()
+            .set_preset_effects_sends(bank, program, Some(soundfont::catalog::EffectsSends::new(reverb, chorus))),
+        None => false,
+    }
+}
+
+/// Drop a set_preset_effects_sends override
+#[wasm_bindgen]
+pub fn clear_preset_effects_sends(bank: u16, program: u8) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().set_preset_effects_sends(bank, program, None),
+        None => false,
+    }
+}
+
+/// Release the current preset preview note early
+#[wasm_bindgen]
+pub fn stop_audition() -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().stop_audition();
+            true
         }
+        None => false,
     }
 }
 
-/// Get sample rate from global AudioWorklet bridge
+/// Play SoundFont sample `sample_index` as stored, `semitones` from its root key
+/// No preset, envelope or filter is applied and no voice is used; `looped` repeats the
+/// sample's loop until stop_sample_preview. Gain 0.0-4.0; replaces a running preview
 #[wasm_bindgen]
-pub fn get_sample_rate() -> f32 {
-    unsafe {
-        if let Some(ref bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.get_sample_rate()
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-            44100.0 // Default sample rate
+pub fn play_sample_preview(sample_index: u32, semitones: f32, looped: bool, gain: f32) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().preview_sample(sample_index as usize, semitones, looped, gain),
+        None => false,
+    }
+}
+
+/// Stop the raw sample preview
+#[wasm_bindgen]
+pub fn stop_sample_preview() -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().stop_sample_preview();
+            true
         }
+        None => false,
     }
 }
 
-/// Queue MIDI event through global AudioWorklet bridge
-/// Optimized for real-time MIDI input from AudioWorklet
+/// Set sample playback direction ("forward", "reverse" or "pingpong") for notes on a channel
 #[wasm_bindgen]
-pub fn queue_midi_event_global(timestamp: u32, channel: u8, message_type: u8, data1: u8, data2: u8) {
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.queue_midi_event(timestamp as u64, channel, message_type, data1, data2);
-        } else {
-            log("Error: AudioWorklet bridge not initialized - MIDI event dropped");
+pub fn set_channel_playback_direction_global(channel: u8, direction: &str) -> bool {
+    let (Some(direction), Some(bridge)) = (PlaybackDirection::from_name(direction), global_bridge_mut()) else {
+        return false;
+    };
+    bridge.midi_player_mut().set_channel_playback_direction(channel, direction)
+}
+
+/// Randomize each note's sample start within `window_ms` (0 = off) to humanize repeated hits
+/// Channel 0-15, or 255 for all channels
+#[wasm_bindgen]
+pub fn set_start_offset_humanize_global(channel: u8, window_ms: f32) -> bool {
+    let channel = if channel == 255 { None } else { Some(channel) };
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().set_start_offset_window(channel, window_ms),
+        None => false,
+    }
+}
+
+/// LFO start phase for new notes: "sync" (rising zero crossing, EMU8000) or "random" per voice
+/// A non-zero `seed` restarts the random sequence for reproducible renders
+#[wasm_bindgen]
+pub fn set_lfo_phase_mode_global(mode: &str, seed: u32) -> bool {
+    let (Some(mode), Some(bridge)) = (LfoPhaseMode::from_name(mode), global_bridge_mut()) else {
+        return false;
+    };
+    bridge.midi_player_mut().set_lfo_phase_mode(mode, seed);
+    true
+}
+
+/// Scatter sequencer note-on velocities by up to `velocity_range` (0-64) and their timing by up
+/// to `timing_ms` (0-50) either way; channel 0-15, or 255 for all channels
+#[wasm_bindgen]
+pub fn set_playback_humanize_global(channel: u8, velocity_range: u8, timing_ms: f32) -> bool {
+    let channel = if channel == 255 { None } else { Some(channel) };
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().set_playback_humanize(channel, velocity_range, timing_ms),
+        None => false,
+    }
+}
+
+/// Restart the playback humanize random sequence (0 keeps the current one)
+#[wasm_bindgen]
+pub fn set_playback_humanize_seed_global(seed: u32) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().set_playback_humanize_seed(seed);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Playback humanize settings of the 16 channels (`ChannelHumanize[]` JSON)
+#[wasm_bindgen]
+pub fn get_playback_humanize_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => serde_json::to_string(bridge.midi_player_mut().playback_humanize()).unwrap_or_else(|_| "[]".to_string()),
+        None => "[]".to_string(),
+    }
+}
+
+/// Automate "volume" (dB), "pan" or "cutoff" (octaves) of a channel with points at song
+/// positions (`ticks[i]`, `values[i]`), joined "step", "linear" or "smooth"; replaces the
+/// channel's lane for that parameter
+#[wasm_bindgen]
+pub fn set_automation_lane_global(channel: u8, parameter: &str, ticks: &[u32], values: &[f32], interpolation: &str) -> bool {
+    let (Some(parameter), Some(interpolation)) = (AutomationParameter::from_name(parameter), AutomationInterpolation::from_name(interpolation)) else {
+        return false;
+    };
+    if ticks.len() != values.len() {
+        return false;
+    }
+    let points = ticks.iter().zip(values).map(|(&tick, &value)| AutomationPoint { tick: tick as u64, value }).collect();
+    let (Some(lane), Some(bridge)) = (AutomationLane::new(channel, parameter, interpolation, points), global_bridge_mut()) else {
+        return false;
+    };
+    bridge.midi_player_mut().set_automation_lane(lane)
+}
+
+/// Remove a channel's automation lane for "volume", "pan" or "cutoff"
+#[wasm_bindgen]
+pub fn remove_automation_lane_global(channel: u8, parameter: &str) -> bool {
+    let (Some(parameter), Some(bridge)) = (AutomationParameter::from_name(parameter), global_bridge_mut()) else {
+        return false;
+    };
+    bridge.midi_player_mut().remove_automation_lane(channel, parameter)
+}
+
+#[wasm_bindgen]
+pub fn clear_automation_global() -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().clear_automation();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Automation lanes set (`AutomationLane[]` JSON)
+#[wasm_bindgen]
+pub fn get_automation_lanes_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => serde_json::to_string(bridge.midi_player_mut().automation_lanes()).unwrap_or_else(|_| "[]".to_string()),
+        None => "[]".to_string(),
+    }
+}
+
+/// Set synthesis fidelity: "modern" (32-bit float) or "hardware" (EMU8000 16-bit/register limits)
+#[wasm_bindgen]
+pub fn set_synthesis_mode_global(mode: &str) -> bool {
+    let (Some(mode), Some(bridge)) = (SynthesisMode::from_name(mode), global_bridge_mut()) else {
+        return false;
+    };
+    bridge.midi_player_mut().set_synthesis_mode(mode);
+    true
+}
+
+/// Get current synthesis mode name
+#[wasm_bindgen]
+pub fn get_synthesis_mode_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().get_synthesis_mode().name().to_string(),
+        None => SynthesisMode::Modern.name().to_string(),
+    }
+}
+
+/// Enable heuristics beyond documented EMU8000 behavior (bend-dependent vibrato rate); false = authentic
+#[wasm_bindgen]
+pub fn set_enhancements_global(enabled: bool) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().set_enhancements(enabled);
+            true
         }
+        None => false,
+    }
+}
+
+/// Whether enhancements are enabled
+#[wasm_bindgen]
+pub fn get_enhancements_global() -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().get_enhancements(),
+        None => true,
+    }
+}
+
+/// Set the glide time constant (ms) for CC7/10/11/74/91/93; 0 applies controllers immediately
+#[wasm_bindgen]
+pub fn set_controller_smoothing_global(time_ms: f32) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().set_controller_smoothing_ms(time_ms);
+            true
+        },
+        None => false,
+    }
+}
+
+/// Get the controller smoothing time constant (ms)
+#[wasm_bindgen]
+pub fn get_controller_smoothing_global() -> f32 {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().get_controller_smoothing_ms(),
+        None => synth::smoothing::DEFAULT_SMOOTHING_MS,
+    }
+}
+
+/// Scale the master gain down as polyphony rises (off by default)
+#[wasm_bindgen]
+pub fn set_auto_gain_enabled_global(enabled: bool) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().set_auto_gain_enabled(enabled);
+            true
+        },
+        None => false,
+    }
+}
+
+/// Current auto-gain multiplier (1.0 = no reduction), for a gain-reduction meter
+#[wasm_bindgen]
+pub fn get_auto_gain_global() -> f32 {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().get_auto_gain(),
+        None => 1.0,
+    }
+}
+
+/// Trim MIDI channel 0-15 by `db` (-60 to +12, 0 = unity) to rebalance a badly mixed file
+/// Applied after the voices and before the reverb/chorus sends; kept across GM/GS resets
+#[wasm_bindgen]
+pub fn set_channel_trim_global(channel: u8, db: f32) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().set_channel_trim_db(channel, db),
+        None => false,
+    }
+}
+
+/// Current trim of a channel in dB (0.0 without a bridge or for a bad channel)
+#[wasm_bindgen]
+pub fn get_channel_trim_global(channel: u8) -> f32 {
+    global_bridge_mut()
+        .and_then(|bridge| bridge.midi_player_mut().channel_trim_db(channel))
+        .unwrap_or(0.0)
+}
+
+/// Spread Note On velocities 1-127 of MIDI channel 0-15 over `min`-`max` through a power
+/// `curve` (1.0 linear, below 1.0 louder, above softer) before zone selection, e.g.
+/// (64, 127, 1.0) compresses a file's dynamics into a SoundFont's upper velocity layers
+#[wasm_bindgen]
+pub fn set_velocity_map_global(channel: u8, min: u8, max: u8, curve: f32) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().set_velocity_map(channel, VelocityMap::new(min, max, curve)),
+        None => false,
+    }
+}
+
+/// Pass velocities of MIDI channel 0-15 through unchanged again
+#[wasm_bindgen]
+pub fn reset_velocity_map_global(channel: u8) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().set_velocity_map(channel, VelocityMap::IDENTITY),
+        None => false,
+    }
+}
+
+/// Host mixer settings (channel trims, velocity maps) as `EngineSnapshot` JSON, for saving a session
+#[wasm_bindgen]
+pub fn get_state_snapshot_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().state_snapshot().to_json(),
+        None => "null".to_string(),
+    }
+}
+
+/// Restore settings saved with get_state_snapshot_global; false for malformed or newer snapshots
+#[wasm_bindgen]
+pub fn restore_state_snapshot_global(json: &str) -> bool {
+    let (Some(snapshot), Some(bridge)) = (EngineSnapshot::from_json(json), global_bridge_mut()) else {
+        return false;
+    };
+    bridge.midi_player_mut().restore_state_snapshot(&snapshot);
+    true
+}
+
+/// Force-release notes held longer than `timeout_seconds` (minimum 1 s, default 30 s) -
+/// protection against stuck notes from lost Note Offs. Off by default; each release is
+/// reported to the watchdog callback
+#[wasm_bindgen]
+pub fn set_note_watchdog_global(enabled: bool, timeout_seconds: f32) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().set_note_watchdog(enabled, timeout_seconds);
+            true
+        },
+        None => false,
     }
 }
 
-/// Process stereo buffer (interleaved) using global bridge
+/// Receive watchdog releases as JSON strings (`HungNote`); pass null/undefined to stop
+/// Called after the render call that released the note
 #[wasm_bindgen]
-pub fn process_stereo_buffer_global(buffer_length: usize) -> Vec<f32> {
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.process_stereo_buffer(buffer_length)
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-            vec![0.0; buffer_length] // Return silence
-        }
-    }
+pub fn set_note_watchdog_callback(callback: Option<js_sys::Function>) {
+    synth::watchdog::set_listener(callback.map(|callback| {
+        std::rc::Rc::new(move |note: &synth::watchdog::HungNote| {
+            let json = serde_json::to_string(note).unwrap_or_else(|_| "{}".to_string());
+            let _ = callback.call1(&JsValue::NULL, &JsValue::from_str(&json));
+        }) as synth::watchdog::WatchdogListener
+    }));
 }
 
-/// Set buffer size for global AudioWorklet bridge
+/// Voice statistics since the last reset as `VoiceStatsReport` JSON: voice lifetimes
+/// (note-on to idle or steal) in a histogram, steal and dropped-note counts, and the
+/// average, peak and 95th-percentile polyphony - for choosing polyphony limits
 #[wasm_bindgen]
-pub fn set_buffer_size_global(size: usize) {
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.set_buffer_size(size);
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-        }
+pub fn get_voice_stats_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => serde_json::to_string(&bridge.midi_player_mut().voice_stats()).unwrap_or_else(|_| "null".to_string()),
+        None => "null".to_string(),
     }
 }
 
-/// Get current buffer size from global bridge
+/// Map the mod wheel (CC1) of notes started on MIDI channel 0-15 (255 = all) to vibrato depth:
+/// `min_depth` at 0 and `max_depth` at 127 (fractions of a ±2 semitone swing) along `curve`
+/// ("linear", "concave", "convex", "switch"); curve "off" returns the channel to the preset's modulators
 #[wasm_bindgen]
-pub fn get_buffer_size_global() -> usize {
-    unsafe {
-        if let Some(ref bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.get_buffer_size()
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-            128 // Default buffer size
-        }
+pub fn set_mod_wheel_vibrato_global(channel: u8, min_depth: f32, max_depth: f32, curve: &str) -> bool {
+    let vibrato = match curve {
+        "off" => None,
+        name => match curve_from_name(name) {
+            Some(curve) => Some(ModWheelVibrato::new(min_depth, max_depth, curve)),
+            None => return false,
+        },
+    };
+    let channel = if channel == 255 { None } else { Some(channel) };
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().set_mod_wheel_vibrato(channel, vibrato),
+        None => false,
     }
 }
 
-/// Reset audio state in global bridge (stop all voices, clear events)
+/// Drive the filter of notes started on MIDI channel 0-15 (255 = all) with a classic
+/// modulation-envelope shape: "pluck" (instant open, fast fall to the base cutoff),
+/// "brass" (short swell) or "sweep" (slow pad opening); "off" restores the default
 #[wasm_bindgen]
-pub fn reset_audio_state_global() {
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.reset_audio_state();
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-        }
+pub fn set_filter_envelope_global(channel: u8, shape: &str) -> bool {
+    let shape = match shape {
+        "off" => None,
+        name => match FilterEnvelopeShape::from_name(name) {
+            Some(shape) => Some(shape),
+            None => return false,
+        },
+    };
+    let channel = if channel == 255 { None } else { Some(channel) };
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().set_filter_envelope(channel, shape),
+        None => false,
     }
 }
 
-/// Test global AudioWorklet bridge functionality
+/// Route a modulation `source` ("modWheel", "aftertouch", "velocity", "key", "modEnvelope")
+/// to a `destination` ("pitch", "filterCutoff", "amplitude", "tremolo", "vibrato") on MIDI
+/// channel 0-15, or 255 for all channels. `amount` (-1.0 to 1.0) is a fraction of the
+/// destination's full scale; `curve` ("linear", "concave", "convex", "switch") shapes the
+/// source. Replaces an existing route between the same pair; up to 8 routes per scope
 #[wasm_bindgen]
-pub fn test_audio_worklet_global(buffer_size: usize) -> String {
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.test_worklet_bridge(buffer_size)
-        } else {
-            let error = r#"{"success": false, "error": "AudioWorklet bridge not initialized"}"#;
-            log("Error: AudioWorklet bridge not initialized for testing");
-            error.to_string()
-        }
+pub fn set_modulation_route_global(channel: u8, source: &str, destination: &str, amount: f32, curve: &str) -> bool {
+    let (Some(source), Some(destination), Some(curve)) =
+        (ModulationSource::from_name(source), ModulationDestination::from_name(destination), curve_from_name(curve)) else {
+        return false;
+    };
+    let channel = if channel == 255 { None } else { Some(channel) };
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().set_modulation_route(channel, ModulationRoute::new(source, destination, amount, curve)),
+        None => false,
     }
 }
 
-// Debug log system removed - replaced with structured diagnostic functions
-
-// ===== BUFFER MANAGEMENT EXPORTS =====
-
-/// Set device information for buffer optimization
+/// Remove the route between `source` and `destination` on a channel (255 = the global routes)
 #[wasm_bindgen]
-pub fn set_device_info_global(hardware_concurrency: u32, device_memory_gb: u32) {
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.set_device_info(hardware_concurrency, device_memory_gb);
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-        }
+pub fn remove_modulation_route_global(channel: u8, source: &str, destination: &str) -> bool {
+    let (Some(source), Some(destination)) = (ModulationSource::from_name(source), ModulationDestination::from_name(destination)) else {
+        return false;
+    };
+    let channel = if channel == 255 { None } else { Some(channel) };
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().remove_modulation_route(channel, source, destination),
+        None => false,
     }
 }
 
-/// Record processing time for buffer management
+/// Remove every route of a channel (255 = the global routes)
 #[wasm_bindgen]
-pub fn record_processing_time_global(processing_time_ms: f32, buffer_size: usize) {
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.record_processing_time(processing_time_ms, buffer_size);
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-        }
+pub fn clear_modulation_routes_global(channel: u8) -> bool {
+    let channel = if channel == 255 { None } else { Some(channel) };
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().clear_modulation_routes(channel),
+        None => false,
     }
 }
 
-/// Record buffer underrun (audio glitch)
+/// The most recent handled MIDI events (oldest first) as a `TraceEntryReport` JSON array:
+/// sample position, source ("live", "sequencer", "chase"), message and what the synth did
+/// with it ("voice 12 allocated", "dropped: no preset", ...). `channel` 0-15 or 255 for
+/// all; `types` is a comma-separated list of "noteOn", "noteOff", "controlChange",
+/// "programChange", "channelPressure", "polyPressure", "pitchBend", "system" (empty = all)
 #[wasm_bindgen]
-pub fn record_underrun_global() {
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.record_underrun();
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-        }
+pub fn get_midi_trace_global(channel: u8, types: &str) -> String {
+    let channel = if channel == 255 { None } else { Some(channel) };
+    let types: Vec<&str> = types.split(',').map(str::trim).filter(|name| !name.is_empty()).collect();
+    match global_bridge_mut() {
+        Some(bridge) => serde_json::to_string(&bridge.midi_player_mut().midi_trace(channel, &types)).unwrap_or_else(|_| "null".to_string()),
+        None => "null".to_string(),
     }
 }
 
-/// Get buffer performance metrics as JSON
+/// Turn the MIDI event trace on or off (it is on by default) and set how many events
+/// it keeps, up to 4096 (0 keeps the current size; a new size clears the trace)
 #[wasm_bindgen]
-pub fn get_buffer_metrics_global() -> String {
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.get_buffer_metrics()
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-            "{}".to_string()
+pub fn set_midi_trace_global(enabled: bool, capacity: u32) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().configure_midi_trace(enabled, capacity as usize);
+            true
         }
+        None => false,
     }
 }
 
-/// Get buffer status summary as JSON
 #[wasm_bindgen]
-pub fn get_buffer_status_global() -> String {
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.get_buffer_status()
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-            "{}".to_string()
+pub fn clear_midi_trace_global() -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().clear_midi_trace();
+            true
         }
+        None => false,
     }
 }
 
-/// Get recommended buffer size for target latency
+/// Start voice statistics over (e.g. before playing the passage to measure)
 #[wasm_bindgen]
-pub fn get_recommended_buffer_size_global(target_latency_ms: f32) -> u32 {
-    unsafe {
-        if let Some(ref bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.get_recommended_buffer_size(target_latency_ms)
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-            256 // Default buffer size
+pub fn reset_voice_stats_global() -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().reset_voice_stats();
+            true
         }
+        None => false,
     }
 }
 
-/// Get current buffer latency in milliseconds
+/// Define performance zone `index` (0-3): incoming keys `low_key`-`high_key` play
+/// `bank`/`program` (bank 128 = drums) shifted by `transpose` semitones at `volume`
+/// (linear, 0.0-2.0). Overlapping zones layer. Takes effect with set_performance_input_global
 #[wasm_bindgen]
-pub fn get_current_latency_ms_global() -> f32 {
-    unsafe {
-        if let Some(ref bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.get_current_latency_ms()
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-            5.8 // Default latency for 256 samples at 44.1kHz
-        }
+pub fn set_performance_zone_global(index: u32, low_key: u8, high_key: u8, bank: u16, program: u8,
+                                   transpose: i8, volume: f32) -> bool {
+    let zone = PerformanceZone::new(low_key, high_key, bank, program, transpose, volume);
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().set_performance_zone(index as usize, Some(zone)),
+        None => false,
     }
 }
 
-/// Enable or disable adaptive buffer sizing
+/// Remove performance zone `index` (0-3), releasing its sounding notes
 #[wasm_bindgen]
-pub fn set_adaptive_mode_global(enabled: bool) {
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.set_adaptive_mode(enabled);
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-        }
+pub fn clear_performance_zone_global(index: u32) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().set_performance_zone(index as usize, None),
+        None => false,
     }
 }
 
-// ===== PIPELINE MANAGEMENT EXPORTS =====
-
-/// Get pipeline status as string
+/// Route notes and pitch bend on MIDI channel 0-15 through the performance zones
+/// instead of the channel's own program; 255 turns performance mode off
 #[wasm_bindgen]
-pub fn get_pipeline_status_global() -> String {
-    unsafe {
-        if let Some(ref bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.get_pipeline_status()
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-            "Error".to_string()
-        }
+pub fn set_performance_input_global(channel: u8) -> bool {
+    if channel != 255 && channel > 15 {
+        return false;
+    }
+    let channel = if channel == 255 { None } else { Some(channel) };
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().set_performance_input(channel);
+            true
+        },
+        None => false,
     }
 }
 
-/// Check if pipeline is ready for processing
+/// Select the preview channel's program (bank 128 = drums). The preview channel is an
+/// internal 17th channel for instrument pickers: song messages never reach it
 #[wasm_bindgen]
-pub fn is_pipeline_ready_global() -> bool {
-    unsafe {
-        if let Some(ref bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.is_pipeline_ready()
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-            false
-        }
+pub fn set_preview_program_global(bank: u16, program: u8) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().set_preview_program(bank, program),
+        None => false,
     }
 }
 
-/// Get comprehensive pipeline statistics as JSON
+/// Preview channel volume (linear, 0.0-2.0), independent of the song's channel volumes
 #[wasm_bindgen]
-pub fn get_pipeline_stats_global() -> String {
-    unsafe {
-        if let Some(ref bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.get_pipeline_stats()
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-            "{}".to_string()
-        }
+pub fn set_preview_volume_global(volume: f32) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().set_preview_volume(volume);
+            true
+        },
+        None => false,
     }
 }
 
-/// Reset pipeline state
+/// Play notes and pitch bend arriving on MIDI channel 0-15 on the preview channel
+/// instead of that channel; 255 turns the routing off
 #[wasm_bindgen]
-pub fn reset_pipeline_global() {
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.reset_pipeline();
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-        }
+pub fn set_preview_input_global(channel: u8) -> bool {
+    if channel != 255 && channel > 15 {
+        return false;
+    }
+    let channel = if channel == 255 { None } else { Some(channel) };
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().set_preview_input(channel);
+            true
+        },
+        None => false,
     }
 }
 
-/// Get combined audio and pipeline status as JSON
+/// Start a note on the preview channel; false if no preset or free voice
 #[wasm_bindgen]
-pub fn get_comprehensive_status_global() -> String {
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            bridge.get_comprehensive_status()
-        } else {
-            log("Error: AudioWorklet bridge not initialized");
-            r#"{"error": "AudioWorklet bridge not initialized"}"#.to_string()
-        }
+pub fn preview_note_on_global(note: u8, velocity: u8) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().preview_note_on(note, velocity),
+        None => false,
     }
 }
 
-// ===== MIDI TEST SEQUENCE EXPORTS =====
-
-// Re-export MIDI test sequence functions for global access
-pub use midi::test_sequences::{
-    init_test_sequence_generator,
-    generate_c_major_scale_test,
-    generate_chromatic_scale_test,
-    generate_arpeggio_test,
-    generate_chord_test,
-    generate_velocity_test,
-    midi_note_to_name,
-    note_name_to_midi,
-    execute_test_sequence,
-    quick_c_major_test,
-};
-
-// ===== UTILITY EXPORTS =====
-
-/// Initialize all global systems with sample rate
+/// Release a preview channel note
 #[wasm_bindgen]
-pub fn init_all_systems(sample_rate: f32) -> bool {
-    log(&format!("🚀 SYSTEM INIT: Starting complete system initialization at {}Hz", sample_rate));
-    let mut success = true;
-    
-    // Initialize AudioWorklet bridge with enhanced tracking
-    log("🔧 SYSTEM INIT: Initializing AudioWorklet bridge...");
-    if !init_audio_worklet(sample_rate) {
-        log("❌ SYSTEM INIT: AudioWorklet bridge initialization FAILED");
-        success = false;
-    } else {
-        log("✅ SYSTEM INIT: AudioWorklet bridge initialization SUCCESS");
-        
-        // Immediate post-init verification
-        unsafe {
-            let bridge_available = GLOBAL_WORKLET_BRIDGE.is_some();
-            log(&format!("🔍 SYSTEM INIT: Bridge verification after init - available: {}", bridge_available));
-        }
-    }
-    
-    // Initialize MIDI test sequence generator
-    log("🔧 SYSTEM INIT: Initializing MIDI test sequence generator...");
-    init_test_sequence_generator(sample_rate);
-    log("✅ SYSTEM INIT: MIDI test sequence generator initialized");
-    
-    // Final system status
-    if success {
-        log(&format!("🎉 SYSTEM INIT: AWE Player systems fully initialized at {}Hz", sample_rate));
-    } else {
-        log(&format!("⚠️ SYSTEM INIT: AWE Player systems initialization completed with errors at {}Hz", sample_rate));
+pub fn preview_note_off_global(note: u8) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().preview_note_off(note);
+            true
+        },
+        None => false,
     }
-    
-    success
 }
 
-/// Get system status overview as JSON
+/// Preview channel settings as `PreviewChannel` JSON
 #[wasm_bindgen]
-pub fn get_system_status() -> String {
-    let pipeline_ready = is_pipeline_ready_global();
-    let buffer_status = get_buffer_status_global();
-    let pipeline_stats = get_pipeline_stats_global();
-    
-    format!(r#"{{"pipelineReady": {}, "bufferStatus": {}, "pipelineStats": {}}}"#,
-        pipeline_ready, buffer_status, pipeline_stats)
+pub fn get_preview_channel_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => serde_json::to_string(&bridge.midi_player_mut().preview_channel()).unwrap_or_else(|_| "null".to_string()),
+        None => "null".to_string(),
+    }
 }
 
-/// Get AWE Player version and build info
+/// Select how multi-sample zones are picked: "allMatching" (default, every matching zone
+/// layered), "roundRobin", "firstMatch", "random" or "priority". False for unknown names
 #[wasm_bindgen]
-pub fn get_version_info() -> String {
-    r#"{"name": "AWE Player", "version": "0.1.0", "phase": "9A.7", "architecture": "Rust-Centric"}"#.to_string()
+pub fn set_zone_selection_strategy_global(strategy: &str) -> bool {
+    let Some(strategy) = synth::voice_manager::ZoneSelectionStrategy::from_name(strategy) else {
+        return false;
+    };
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().set_zone_selection_strategy(strategy);
+            true
+        },
+        None => false,
+    }
 }
 
-// ===== SOUNDFONT 2.0 EXPORTS =====
-
-/// Initialize SoundFont module
+/// Current zone selection strategy name, "null" without a bridge
 #[wasm_bindgen]
-pub fn init_soundfont_module() -> String {
-    match soundfont::SoundFontModule::initialize() {
-        Ok(_) => {
-            log("SoundFont module initialized successfully");
-            r#"{"success": true, "message": "SoundFont module ready", "version": "SF2.0"}"#.to_string()
-        }
-        Err(e) => {
-            log(&format!("SoundFont module initialization failed: {}", e));
-            format!(r#"{{"success": false, "error": "{}"}}"#, e)
-        }
+pub fn get_zone_selection_strategy_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().zone_selection_strategy().name().to_string(),
+        None => "null".to_string(),
     }
 }
 
-/// Validate SoundFont file header
+/// Shorthand: true selects "roundRobin", false returns to "allMatching"
 #[wasm_bindgen]
-pub fn validate_soundfont_header(data: &[u8]) -> String {
-    match soundfont::SoundFontModule::validate_soundfont_header(data) {
-        Ok(valid) => {
-            if valid {
-                log("SoundFont header validation passed");
-                r#"{"valid": true, "format": "SF2.0", "message": "Valid SoundFont file"}"#.to_string()
-            } else {
-                log("SoundFont header validation failed - invalid format");
-                r#"{"valid": false, "error": "Invalid SoundFont format"}"#.to_string()
-            }
-        }
-        Err(e) => {
-            log(&format!("SoundFont header validation error: {}", e));
-            format!(r#"{{"valid": false, "error": "{}"}}"#, e)
-        }
+pub fn set_round_robin_global(enabled: bool) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().set_round_robin(enabled);
+            true
+        },
+        None => false,
     }
 }
 
-/// Get SoundFont module information
+/// Zones the current strategy picks for `note`/`velocity` on the current preset, as
+/// `ZoneSelectionAnalysis` JSON. Round-robin advances as it would for a played note
 #[wasm_bindgen]
-pub fn get_soundfont_info() -> String {
-    format!(r#"{{"version": "{}", "supports": ["SF2.0", "SF2.01", "SF2.1"], "status": "initialized"}}"#,
-        soundfont::SoundFontModule::get_format_version())
+pub fn analyze_zone_selection_global(note: u8, velocity: u8) -> String {
+    match global_bridge_mut() {
+        Some(bridge) => serde_json::to_string(&bridge.midi_player_mut().analyze_zone_selection(note & 0x7F, velocity & 0x7F))
+            .unwrap_or_else(|_| "null".to_string()),
+        None => "null".to_string(),
+    }
 }
 
-/// Test SoundFont module functionality
+/// Shape of velocity layer crossfades: `curve` "linear" (default), "equalPower" or "sCurve",
+/// `width` the fraction of each zone's velocity range faded at either edge (0.0-0.5,
+/// default 0.25; 0 switches layers hard). Applies to notes started afterwards
 #[wasm_bindgen]
-pub fn test_soundfont_module() -> String {
-    // Test basic functionality with dummy data
-    let test_data = b"RIFF\x00\x00\x00\x00sfbk";
-    match soundfont::SoundFontModule::validate_soundfont_header(test_data) {
-        Ok(_) => {
-            log("SoundFont module test passed");
-            r#"{"test": "passed", "module": "functional", "ready": true}"#.to_string()
-        }
-        Err(e) => {
-            log(&format!("SoundFont module test failed: {}", e));
-            format!(r#"{{"test": "failed", "error": "{}"}}"#, e)
-        }
+pub fn set_velocity_crossfade_global(curve: &str, width: f32) -> bool {
+    let Some(curve) = synth::crossfade::CrossfadeCurve::from_name(curve) else {
+        return false;
+    };
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().set_velocity_crossfade(synth::crossfade::VelocityCrossfade::new(curve, width));
+            true
+        },
+        None => false,
     }
 }
 
-/// Parse complete SoundFont file and load into synthesis engine
+/// Velocity crossfade settings as `VelocityCrossfade` JSON
 #[wasm_bindgen]
-pub fn parse_soundfont_file(data: &[u8]) -> String {
-    let soundfont = match soundfont::SoundFontParser::parse_soundfont(data) {
-        Ok(sf) => sf,
-        Err(e) => {
-            log(&format!("SoundFont parsing failed: {}", e));
-            return format!(r#"{{"success": false, "error": "Parsing failed: {}"}}"#, e);
-        }
-    };
-    
-    // Log basic parsing info
-    log(&format!("SoundFont parsed successfully: '{}' with {} presets, {} instruments, {} samples",
-               soundfont.header.name, soundfont.presets.len(), 
-               soundfont.instruments.len(), soundfont.samples.len()));
-    
-    // Analyze and log loop validation summary
-    let mut valid_loops = 0;
-    let mut no_loops = 0;
-    let mut invalid_loops = 0;
-    
-    for sample in &soundfont.samples {
-        if sample.loop_end > 0 && sample.loop_start < sample.loop_end {
-            valid_loops += 1;
-        } else if sample.loop_start == 0 && sample.loop_end == 0 {
-            no_loops += 1;
-        } else {
-            invalid_loops += 1;
-        }
+pub fn get_velocity_crossfade_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => serde_json::to_string(&bridge.midi_player_mut().velocity_crossfade()).unwrap_or_else(|_| "null".to_string()),
+        None => "null".to_string(),
     }
-    
-    // Log loop validation summary
-    log(&format!("📊 LOOP VALIDATION: {} samples total - ✅ {} with loops, ⭕ {} without loops (normal), ❌ {} invalid",
-                soundfont.samples.len(), valid_loops, no_loops, invalid_loops));
-    
-    if invalid_loops > 0 {
-        log(&format!("⚠️ WARNING: {} samples had invalid loop data", invalid_loops));
+}
+
+/// Retune keys: `table` holds 128 cent offsets (key 0-127) applied at note-on
+/// Channel 0-15 sets that channel's table, 255 the global table used by channels without one
+#[wasm_bindgen]
+pub fn set_key_tuning(table: &[f32], channel: u8) -> bool {
+    let channel = if channel == 255 { None } else { Some(channel) };
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().set_key_tuning(channel, table),
+        None => false,
     }
-    
-    // Load SoundFont into synthesis engine
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            match bridge.load_soundfont_internal(soundfont) {
-                Ok(()) => {
-                    log("✅ SoundFont loaded successfully into synthesis engine");
-                    r#"{"success": true, "message": "SoundFont loaded into synthesis engine"}"#.to_string()
-                }
-                Err(e) => {
-                    log(&format!("Failed to load SoundFont into synthesis engine: {}", e));
-                    format!(r#"{{"success": false, "error": "{}"}}"#, e)
-                }
-            }
-        } else {
-            let error = "AudioWorklet bridge not initialized";
-            log(error);
-            format!(r#"{{"success": false, "error": "{}"}}"#, error)
-        }
+}
+
+/// Remove a channel's tuning table (0-15), or every table including the global one (255)
+#[wasm_bindgen]
+pub fn reset_key_tuning(channel: u8) -> bool {
+    let channel = if channel == 255 { None } else { Some(channel) };
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().reset_key_tuning(channel),
+        None => false,
     }
 }
 
-/// Test SoundFont header parsing with real SF2 data
-#[wasm_bindgen]
-pub fn test_soundfont_parsing() -> String {
-    // Create minimal valid SF2 file structure for testing
-    let mut test_sf2 = Vec::new();
-    
-    // RIFF header: "RIFF" + file_size + "sfbk"
-    test_sf2.extend_from_slice(b"RIFF");
-    test_sf2.extend_from_slice(&(400u32).to_le_bytes()); // file size placeholder
-    test_sf2.extend_from_slice(b"sfbk");
-    
-    // LIST chunk with INFO
-    test_sf2.extend_from_slice(b"LIST");
-    test_sf2.extend_from_slice(&(80u32).to_le_bytes()); // LIST size
-    test_sf2.extend_from_slice(b"INFO");
-    
-    // ifil chunk (version)
-    test_sf2.extend_from_slice(b"ifil");
-    test_sf2.extend_from_slice(&(4u32).to_le_bytes());
-    test_sf2.extend_from_slice(&(2u16).to_le_bytes()); // major version 2
-    test_sf2.extend_from_slice(&(0u16).to_le_bytes()); // minor version 0
-    
-    // isng chunk (sound engine)
-    test_sf2.extend_from_slice(b"isng");
-    test_sf2.extend_from_slice(&(8u32).to_le_bytes());
-    test_sf2.extend_from_slice(b"EMU8000\0");
-    
-    // INAM chunk (name)
-    test_sf2.extend_from_slice(b"INAM");
-    test_sf2.extend_from_slice(&(12u32).to_le_bytes());
-    test_sf2.extend_from_slice(b"Test SF2\0\0\0\0");
-    
-    // LIST chunk with sdta (sample data)
-    test_sf2.extend_from_slice(b"LIST");
-    test_sf2.extend_from_slice(&(20u32).to_le_bytes()); // sdta LIST size
-    test_sf2.extend_from_slice(b"sdta");
-    
-    // smpl chunk (16-bit sample data)
-    test_sf2.extend_from_slice(b"smpl");
-    test_sf2.extend_from_slice(&(8u32).to_le_bytes()); // 4 samples * 2 bytes
-    // Add 4 test samples (440Hz sine wave approximation)
-    test_sf2.extend_from_slice(&(0i16).to_le_bytes());     // Sample 0
-    test_sf2.extend_from_slice(&(16383i16).to_le_bytes()); // Sample 1 (half max)
-    test_sf2.extend_from_slice(&(0i16).to_le_bytes());     // Sample 2
-    test_sf2.extend_from_slice(&(-16383i16).to_le_bytes());// Sample 3 (half min)
-    
-    // LIST chunk with pdta (preset data)
-    test_sf2.extend_from_slice(b"LIST");
-    test_sf2.extend_from_slice(&(200u32).to_le_bytes()); // pdta LIST size
-    test_sf2.extend_from_slice(b"pdta");
-    
-    // shdr chunk (sample headers) - 46 bytes per sample + 46 byte terminal
-    test_sf2.extend_from_slice(b"shdr");
-    test_sf2.extend_from_slice(&(92u32).to_le_bytes()); // 2 samples * 46 bytes
-    
-    // Sample header 1
-    let mut sample_header = [0u8; 46];
-    sample_header[0..9].copy_from_slice(b"TestSamp\0"); // Sample name
-    sample_header[20..24].copy_from_slice(&(0u32).to_le_bytes()); // start offset
-    sample_header[24..28].copy_from_slice(&(4u32).to_le_bytes()); // end offset
-    sample_header[28..32].copy_from_slice(&(0u32).to_le_bytes()); // loop start
-    sample_header[32..36].copy_from_slice(&(4u32).to_le_bytes()); // loop end
-    sample_header[36..40].copy_from_slice(&(44100u32).to_le_bytes()); // sample rate
-    sample_header[40] = 60; // original pitch (middle C)
-    sample_header[41] = 0;  // pitch correction
-    sample_header[42..44].copy_from_slice(&(0u16).to_le_bytes()); // sample link
-    sample_header[44..46].copy_from_slice(&(1u16).to_le_bytes()); // sample type (mono)
-    test_sf2.extend_from_slice(&sample_header);
-    
-    // Terminal sample header (empty)
-    test_sf2.extend_from_slice(&[0u8; 46]);
-    
-    // Add basic preset structures for complete test
-    // phdr chunk (preset headers) - 38 bytes per preset + 38 byte terminal
-    test_sf2.extend_from_slice(b"phdr");
-    test_sf2.extend_from_slice(&(76u32).to_le_bytes()); // 2 presets * 38 bytes
-    
-    // Preset header 1
-    let mut preset_header = [0u8; 38];
-    preset_header[0..9].copy_from_slice(b"TestPset\0"); // Preset name
-    preset_header[20] = 0; preset_header[21] = 0; // program 0
-    preset_header[22] = 0; preset_header[23] = 0; // bank 0
-    preset_header[24] = 0; preset_header[25] = 0; // bag index 0
-    test_sf2.extend_from_slice(&preset_header);
-    
-    // Terminal preset header (empty)
-    test_sf2.extend_from_slice(&[0u8; 38]);
-    
-    // pbag chunk (preset bags) - 4 bytes each
-    test_sf2.extend_from_slice(b"pbag");
-    test_sf2.extend_from_slice(&(8u32).to_le_bytes()); // 2 bags * 4 bytes
-    test_sf2.extend_from_slice(&(0u16).to_le_bytes()); // gen index 0
-    test_sf2.extend_from_slice(&(0u16).to_le_bytes()); // mod index 0
-    test_sf2.extend_from_slice(&(1u16).to_le_bytes()); // gen index 1 (terminal)
-    test_sf2.extend_from_slice(&(0u16).to_le_bytes()); // mod index 0
-    
-    // pgen chunk (preset generators) - 4 bytes each
-    test_sf2.extend_from_slice(b"pgen");
-    test_sf2.extend_from_slice(&(8u32).to_le_bytes()); // 2 generators * 4 bytes
-    test_sf2.extend_from_slice(&(41u16).to_le_bytes()); // Instrument generator
-    test_sf2.extend_from_slice(&(0u16).to_le_bytes());  // instrument ID 0
-    test_sf2.extend_from_slice(&(0u16).to_le_bytes());  // terminal generator
-    test_sf2.extend_from_slice(&(0u16).to_le_bytes());  // value 0
-    
-    // inst chunk (instrument headers) - 22 bytes per instrument + 22 byte terminal
-    test_sf2.extend_from_slice(b"inst");
-    test_sf2.extend_from_slice(&(44u32).to_le_bytes()); // 2 instruments * 22 bytes
-    
-    // Instrument header 1
-    let mut inst_header = [0u8; 22];
-    inst_header[0..9].copy_from_slice(b"TestInst\0"); // Instrument name
-    inst_header[20] = 0; inst_header[21] = 0; // bag index 0
-    test_sf2.extend_from_slice(&inst_header);
-    
-    // Terminal instrument header (empty)
-    test_sf2.extend_from_slice(&[0u8; 22]);
-    
-    // ibag chunk (instrument bags) - 4 bytes each
-    test_sf2.extend_from_slice(b"ibag");
-    test_sf2.extend_from_slice(&(8u32).to_le_bytes()); // 2 bags * 4 bytes
-    test_sf2.extend_from_slice(&(0u16).to_le_bytes()); // gen index 0
-    test_sf2.extend_from_slice(&(0u16).to_le_bytes()); // mod index 0
-    test_sf2.extend_from_slice(&(1u16).to_le_bytes()); // gen index 1 (terminal)
-    test_sf2.extend_from_slice(&(0u16).to_le_bytes()); // mod index 0
-    
-    // igen chunk (instrument generators) - 4 bytes each
-    test_sf2.extend_from_slice(b"igen");
-    test_sf2.extend_from_slice(&(8u32).to_le_bytes()); // 2 generators * 4 bytes
-    test_sf2.extend_from_slice(&(53u16).to_le_bytes()); // SampleID generator
-    test_sf2.extend_from_slice(&(0u16).to_le_bytes());  // sample ID 0
-    test_sf2.extend_from_slice(&(0u16).to_le_bytes());  // terminal generator
-    test_sf2.extend_from_slice(&(0u16).to_le_bytes());  // value 0
-    
-    // Test parsing
-    parse_soundfont_file(&test_sf2)
+/// Set the A4 reference (410-470 Hz, e.g. 432/440/442) for every voice, sounding ones included
+#[wasm_bindgen]
+pub fn set_master_tuning_global(a4_hz: f32) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().set_master_tuning(a4_hz),
+        None => false,
+    }
 }
 
+/// Current A4 reference in Hz
+#[wasm_bindgen]
+pub fn get_master_tuning_global() -> f32 {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().get_master_tuning(),
+        None => STANDARD_A4_HZ,
+    }
+}
 
-/// Select preset by bank and program number
+/// Debug: hear only one voice or MIDI channel ("voice" 0-31, "channel" 0-15, or "off")
+/// Other voices keep playing silently; effects only receive the soloed voices
 #[wasm_bindgen]
-pub fn select_preset_global(bank: u16, program: u8) -> String {
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            match bridge.select_preset_internal(bank, program) {
-                Ok(preset_info) => {
-                    log(&format!("Preset selected: {}", preset_info));
-                    format!(r#"{{"success": true, "preset": "{}"}}"#, preset_info)
-                }
-                Err(e) => {
-                    log(&format!("Preset selection failed: {}", e));
-                    format!(r#"{{"success": false, "error": "{}"}}"#, e)
-                }
-            }
-        } else {
-            let error = "AudioWorklet bridge not initialized";
-            log(error);
-            format!(r#"{{"success": false, "error": "{}"}}"#, error)
-        }
+pub fn set_debug_solo_global(mode: &str, index: u32) -> bool {
+    let (Some(solo), Some(bridge)) = (DebugSolo::from_parts(mode, index), global_bridge_mut()) else {
+        return false;
+    };
+    bridge.midi_player_mut().set_debug_solo(solo);
+    true
+}
+
+/// Process a SysEx message (GM/GM2/GS/XG system messages, MIDI Machine Control transport)
+#[wasm_bindgen]
+pub fn process_sysex_global(data: &[u8]) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().handle_sysex(data),
+        None => false,
     }
 }
 
-/// Get current preset information
+/// Set MIDI system mode ("gm", "gm2", "gs" or "xg") - controls bank select interpretation
 #[wasm_bindgen]
-pub fn get_current_preset_info_global() -> String {
-    unsafe {
-        if let Some(ref bridge) = GLOBAL_WORKLET_BRIDGE {
-            match bridge.get_current_preset_info_internal() {
-                Some(info) => {
-                    format!(r#"{{"success": true, "preset": "{}"}}"#, info)
-                }
-                None => {
-                    r#"{"success": false, "error": "No preset selected"}"#.to_string()
-                }
-            }
-        } else {
-            r#"{"success": false, "error": "AudioWorklet bridge not initialized"}"#.to_string()
-        }
+pub fn set_midi_system_mode_global(mode: &str) -> bool {
+    let (Some(mode), Some(bridge)) = (MidiSystemMode::from_name(mode), global_bridge_mut()) else {
+        return false;
+    };
+    bridge.midi_player_mut().set_system_mode(mode);
+    true
+}
+
+/// Get current MIDI system mode
+#[wasm_bindgen]
+pub fn get_midi_system_mode_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().get_system_mode().name().to_string(),
+        None => MidiSystemMode::Gm.name().to_string(),
     }
 }
 
-/// Test SoundFont memory and sample data integrity
+/// Mark MIDI channel 0-15 as percussion ("drum"), force it melodic ("melodic", e.g. melodic
+/// content on channel 10) or return it to the system mode rules ("auto"); kept across resets
 #[wasm_bindgen]
-pub fn test_soundfont_memory() -> String {
-    log("🧪 Testing SoundFont memory and sample data...");
-    
-    unsafe {
-        if let Some(ref bridge) = GLOBAL_WORKLET_BRIDGE {
-            if !bridge.is_soundfont_loaded_internal() {
-                let error = "No SoundFont loaded for memory test";
-                log(error);
-                return format!(r#"{{"success": false, "error": "{}"}}"#, error);
-            }
-            
-            // Get access to loaded SoundFont for inspection
-            if let Some(soundfont) = bridge.get_loaded_soundfont() {
-                let mut memory_info = format!("📊 SoundFont Memory Analysis:\n");
-                memory_info.push_str(&format!("- Total samples: {}\n", soundfont.samples.len()));
-                memory_info.push_str(&format!("- Total presets: {}\n", soundfont.presets.len()));
-                memory_info.push_str(&format!("- Total instruments: {}\n", soundfont.instruments.len()));
-                
-                // Check first few samples for actual data
-                let mut samples_with_data = 0;
-                let mut total_sample_data = 0;
-                
-                for (i, sample) in soundfont.samples.iter().take(5).enumerate() {
-                    let data_len = sample.sample_data.len();
-                    total_sample_data += data_len;
-                    
-                    let non_zero_count = sample.sample_data.iter().filter(|&&x| x != 0).count();
-                    if non_zero_count > 0 {
-                        samples_with_data += 1;
-                    }
-                    
-                    memory_info.push_str(&format!(
-                        "- Sample {}: '{}' - {} samples, {} non-zero ({:.1}%)\n",
-                        i, sample.name, data_len, non_zero_count, 
-                        (non_zero_count as f32 / data_len.max(1) as f32) * 100.0
-                    ));
-                    
-                    // Show first few sample values
-                    if data_len > 0 {
-                        let preview: Vec<i16> = sample.sample_data.iter().take(8).cloned().collect();
-                        memory_info.push_str(&format!("  First 8 samples: {:?}\n", preview));
-                    }
-                }
-                
-                memory_info.push_str(&format!("📈 Summary: {}/{} samples have non-zero data", samples_with_data, soundfont.samples.len().min(5)));
-                
-                log(&memory_info);
-                return format!(r#"{{"success": true, "samples": {}, "presets": {}, "instruments": {}, "samples_with_data": {}, "total_sample_data": {}}}"#, 
-                    soundfont.samples.len(), soundfont.presets.len(), soundfont.instruments.len(), samples_with_data, total_sample_data);
-            } else {
-                let error = "SoundFont reference is None in VoiceManager";
-                log(error);
-                return format!(r#"{{"success": false, "error": "{}"}}"#, error);
-            }
-        } else {
-            let error = "AudioWorklet bridge not initialized";
-            log(error);
-            return format!(r#"{{"success": false, "error": "{}"}}"#, error);
+pub fn set_channel_drum_mode_global(channel: u8, mode: &str) -> bool {
+    let is_drum = match mode {
+        "auto" => None,
+        "drum" => Some(true),
+        "melodic" => Some(false),
+        _ => return false,
+    };
+    match global_bridge_mut() {
+        Some(bridge) => bridge.midi_player_mut().set_channel_drum_override(channel, is_drum),
+        None => false,
+    }
+}
+
+/// Percussion override of a channel: "auto", "drum" or "melodic" (the resulting flag is
+/// `isDrum` in get_channel_state_global)
+#[wasm_bindgen]
+pub fn get_channel_drum_mode_global(channel: u8) -> String {
+    let mode = match global_bridge_mut().and_then(|bridge| bridge.midi_player_mut().channel_drum_override(channel)) {
+        None => "auto",
+        Some(true) => "drum",
+        Some(false) => "melodic",
+    };
+    mode.to_string()
+}
+
+/// Apply the loaded song's setup region (system SysEx, programs and controllers before the
+/// first note) in one go when playback starts at the top (default), or play it as recorded
+#[wasm_bindgen]
+pub fn set_song_setup_enabled_global(enabled: bool) -> bool {
+    match global_bridge_mut() {
+        Some(bridge) => {
+            bridge.midi_player_mut().set_song_setup_enabled(enabled);
+            true
         }
+        None => false,
+    }
+}
+
+/// The loaded song's setup region as a SongSetupReport (JSON; null without a bridge)
+#[wasm_bindgen]
+pub fn get_song_setup_global() -> String {
+    match global_bridge_mut() {
+        Some(bridge) => serde_json::to_string(&bridge.midi_player_mut().song_setup_report()).unwrap_or_else(|_| "null".to_string()),
+        None => "null".to_string(),
     }
 }
 
@@ -1414,156 +3836,6 @@ pub fn diagnose_sample_data() -> String {
     }
 }
 
-/// Comprehensive audio synthesis pipeline test
-#[wasm_bindgen] 
-pub fn test_audio_synthesis_pipeline() -> String {
-    log("🚨🚨🚨 AUDIO PIPELINE TEST STARTING 🚨🚨🚨");
-    log("🧪 Running comprehensive audio synthesis pipeline test...");
-    
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            let mut results = Vec::new();
-            
-            // Test 1: Sample Data Integrity
-            results.push(test_sample_data_integrity(bridge));
-            
-            // Test 2: Voice Allocation
-            results.push(test_voice_allocation(bridge));
-            
-            // Test 3: Sample Generation
-            results.push(test_sample_generation(bridge));
-            
-            // Test 4: Audio Buffer Processing
-            results.push(test_audio_buffer_processing(bridge));
-            
-            let passed = results.iter().filter(|r| r.contains("PASS")).count();
-            let total = results.len();
-            
-            let summary = format!("🧪 Audio Pipeline Test Results: {}/{} tests passed\n{}", 
-                passed, total, results.join("\n"));
-            log(&summary);
-            
-            // Properly escape JSON string - handle all special characters
-            let escaped_summary = summary
-                .replace("\\", "\\\\")  // Escape backslashes first
-                .replace("\"", "\\\"")  // Escape quotes
-                .replace("\n", "\\n")   // Escape newlines
-                .replace("\r", "\\r")   // Escape carriage returns
-                .replace("\t", "\\t");  // Escape tabs
-                
-            return format!(r#"{{"success": {}, "passed": {}, "total": {}, "results": "{}"}}"#, 
-                passed == total, passed, total, escaped_summary);
-        } else {
-            let error = "AudioWorklet bridge not initialized";
-            log(error);
-            return format!(r#"{{"success": false, "error": "{}"}}"#, error);
-        }
-    }
-}
-
-fn test_sample_data_integrity(bridge: &crate::worklet::AudioWorkletBridge) -> String {
-    log("🔍 Test 1: Sample Data Integrity");
-    
-    if let Some(soundfont) = bridge.get_loaded_soundfont() {
-        if soundfont.samples.is_empty() {
-            return "❌ Test 1 FAIL: No samples in SoundFont".to_string();
-        }
-        
-        let sample = &soundfont.samples[0];
-        if sample.sample_data.is_empty() {
-            return "❌ Test 1 FAIL: First sample has no data".to_string();
-        }
-        
-        let non_zero_count = sample.sample_data.iter().filter(|&&x| x != 0).count();
-        let percentage = (non_zero_count as f32 / sample.sample_data.len() as f32) * 100.0;
-        
-        if non_zero_count == 0 {
-            return "❌ Test 1 FAIL: All sample data is zero".to_string();
-        }
-        
-        log(&format!("📊 Sample '{}': {}/{} samples non-zero ({:.1}%)", 
-            sample.name, non_zero_count, sample.sample_data.len(), percentage));
-        
-        return format!("✅ Test 1 PASS: Sample data integrity verified ({:.1}% non-zero)", percentage);
-    } else {
-        return "❌ Test 1 FAIL: No SoundFont loaded".to_string();
-    }
-}
-
-fn test_voice_allocation(bridge: &mut crate::worklet::AudioWorkletBridge) -> String {
-    log("🔍 Test 2: Voice Allocation");
-    
-    // Queue a MIDI note on event
-    bridge.queue_midi_event(0, 0, 0x90, 60, 100);
-    
-    // Process the event
-    let buffer_size = 128;
-    let _output = bridge.process_audio_buffer(buffer_size);
-    
-    // Check voice manager state
-    // For now, just check if the system responded
-    return "⚠️ Test 2 PARTIAL: Voice allocation test needs voice manager inspection".to_string();
-}
-
-fn test_sample_generation(bridge: &mut crate::worklet::AudioWorkletBridge) -> String {
-    log("🔍 Test 3: Sample Generation");
-    
-    // Generate one buffer worth of audio
-    let buffer_size = 1024;
-    let output = bridge.process_audio_buffer(buffer_size);
-    
-    if output.len() != buffer_size {
-        return format!("❌ Test 3 FAIL: Expected {} samples, got {}", buffer_size, output.len());
-    }
-    
-    let non_zero_count = output.iter().filter(|&&x| x.abs() > 0.0001).count();
-    let max_amplitude = output.iter().fold(0.0f32, |acc, &x| acc.max(x.abs()));
-    
-    log(&format!("🎵 Generated buffer: {}/{} non-zero samples, max amplitude: {:.6}", 
-        non_zero_count, buffer_size, max_amplitude));
-    
-    if non_zero_count == 0 {
-        return "❌ Test 3 FAIL: No audio samples generated (all zeros)".to_string();
-    }
-    
-    if max_amplitude > 1.0 {
-        return format!("❌ Test 3 FAIL: Audio clipping detected (max: {:.6})", max_amplitude);
-    }
-    
-    return format!("✅ Test 3 PASS: Audio generation verified ({}/{} samples, max: {:.6})", 
-        non_zero_count, buffer_size, max_amplitude);
-}
-
-fn test_audio_buffer_processing(bridge: &mut crate::worklet::AudioWorkletBridge) -> String {
-    log("🔍 Test 4: Audio Buffer Processing");
-    
-    // Process multiple buffers to test sustained audio
-    let buffer_size = 512;
-    let mut total_non_zero = 0;
-    let mut max_amplitude = 0.0f32;
-    
-    for i in 0..5 {
-        let output = bridge.process_audio_buffer(buffer_size);
-        let non_zero = output.iter().filter(|&&x| x.abs() > 0.0001).count();
-        let max_sample = output.iter().fold(0.0f32, |acc, &x| acc.max(x.abs()));
-        
-        total_non_zero += non_zero;
-        max_amplitude = max_amplitude.max(max_sample);
-        
-        log(&format!("Buffer {}: {}/{} non-zero, max: {:.6}", i, non_zero, buffer_size, max_sample));
-    }
-    
-    let total_samples = buffer_size * 5;
-    let percentage = (total_non_zero as f32 / total_samples as f32) * 100.0;
-    
-    if total_non_zero == 0 {
-        return "❌ Test 4 FAIL: No audio output across multiple buffers".to_string();
-    }
-    
-    return format!("✅ Test 4 PASS: Sustained audio processing ({:.1}% non-zero, max: {:.6})", 
-        percentage, max_amplitude);
-}
-
 // Old debug message function removed
 
 // Old debug log functions removed
@@ -1649,98 +3921,68 @@ pub fn diagnose_soundfont_data() -> String {
     }
 }
 
-/// Get ALL samples from loaded SoundFont - returns structured JSON array
+/// Get one page of the loaded SoundFont's samples - returns JSON (SampleCatalogPage)
+/// `limit` is capped at 500; `name_filter` is a case-insensitive substring ("" = all);
+/// `waveform_points` > 0 adds a min/max overview at that resolution to each entry
 #[wasm_bindgen]
-pub fn get_all_soundfont_samples() -> String {
-    unsafe {
-        if let Some(ref bridge) = GLOBAL_WORKLET_BRIDGE {
-            if bridge.is_soundfont_loaded_internal() {
-                if let Some(soundfont) = bridge.get_loaded_soundfont() {
-                    if soundfont.samples.is_empty() {
-                        return r#"{"success": false, "error": "No samples found in SoundFont", "samples": []}"#.to_string();
-                    }
-                    
-                    let mut samples_json = Vec::new();
-                    
-                    for (index, sample) in soundfont.samples.iter().enumerate() {
-                        // Limit preview to avoid huge JSON responses
-                        let sample_preview: Vec<i16> = sample.sample_data.iter().take(10).copied().collect();
-                        let non_zero_count = sample.sample_data.iter().take(100).filter(|&&s| s != 0).count();
-                        let max_amplitude = sample.sample_data.iter().take(1000).map(|&s| s.abs()).max().unwrap_or(0);
-                        
-                        // Validate loop points against actual sample length
-                        // Note: loop_start == 0 && loop_end == 0 means no loop
-                        // loop_start == 0 && loop_end > 0 means loop from beginning
-                        let sample_length = sample.sample_data.len() as u32;
-                        let (validated_loop_start, validated_loop_end, has_valid_loop) = 
-                            if sample.loop_end > 0 && sample.loop_start < sample_length && 
-                               sample.loop_end <= sample_length && sample.loop_start < sample.loop_end {
-                                (sample.loop_start, sample.loop_end, true)
-                            } else {
-                                // No loop or invalid loop points
-                                (0, 0, false)
-                            };
-                        
-                        // Debug log the first few samples to see what we're getting
-                        if index < 5 {
-                            log(&format!("Sample {}: '{}' - length: {}, raw loop: {}-{}, valid: {}, final: {}-{}", 
-                                       index, sample.name, sample_length, sample.loop_start, sample.loop_end, 
-                                       has_valid_loop, validated_loop_start, validated_loop_end));
-                        }
+pub fn get_soundfont_samples_page(offset: usize, limit: usize, name_filter: &str, waveform_points: usize) -> String {
+    use soundfont::catalog::{SampleCatalogPage, SampleQuery};
+    let page = match global_bridge_mut() {
+        Some(bridge) if bridge.is_soundfont_loaded_internal() => match bridge.get_loaded_soundfont() {
+            Some(soundfont) => SampleCatalogPage::query(soundfont, &SampleQuery {
+                offset,
+                limit,
+                name_filter: name_filter.to_string(),
+                waveform_points,
+            }),
+            None => SampleCatalogPage::error("SoundFont reference not available"),
+        },
+        Some(_) => SampleCatalogPage::error("No SoundFont loaded"),
+        None => SampleCatalogPage::error("Bridge not available"),
+    };
+    page.to_json()
+}
 
-                        let sample_json = format!(r#"{{
-                            "index": {},
-                            "name": "{}",
-                            "length": {},
-                            "sampleRate": {},
-                            "originalPitch": {},
-                            "loopStart": {},
-                            "loopEnd": {},
-                            "hasValidLoop": {},
-                            "rawLoopStart": {},
-                            "rawLoopEnd": {},
-                            "preview": {:?},
-                            "nonZeroIn100": {},
-                            "maxAmplitude": {},
-                            "hasData": {}
-                        }}"#, 
-                        index,
-                        sample.name, 
-                        sample_length, 
-                        sample.sample_rate, 
-                        sample.original_pitch,
-                        validated_loop_start,
-                        validated_loop_end,
-                        has_valid_loop,
-                        sample.loop_start,  // Include raw values for debugging
-                        sample.loop_end,
-                        sample_preview, 
-                        non_zero_count, 
-                        max_amplitude, 
-                        non_zero_count > 0);
-                        
-                        samples_json.push(sample_json);
-                    }
-                    
-                    format!(r#"{{
-                        "success": true,
-                        "sampleCount": {},
-                        "samplesShown": {},
-                        "samples": [{}]
-                    }}"#, 
-                    soundfont.samples.len(),
-                    samples_json.len(),
-                    samples_json.join(",\n"))
-                } else {
-                    r#"{"success": false, "error": "SoundFont reference not available", "samples": []}"#.to_string()
-                }
-            } else {
-                r#"{"success": false, "error": "No SoundFont loaded", "samples": []}"#.to_string()
+/// The 128 GM program names and the drum kit names of bank 128 - returns JSON (GmNames)
+/// Needs no engine; the preset catalog uses the same table for `gmName`
+#[wasm_bindgen]
+pub fn get_gm_names() -> String {
+    serde_json::to_string(&midi::gm_names::GmNames::new()).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Get one page of the loaded SoundFont's presets - returns JSON (PresetCatalogPage)
+/// Each entry carries the reverb/chorus sends its generators give and any override;
+/// `limit` is capped at 500; `name_filter` is a case-insensitive substring ("" = all)
+#[wasm_bindgen]
+pub fn get_soundfont_presets_page(offset: usize, limit: usize, name_filter: &str) -> String {
+    use soundfont::catalog::{PresetCatalogPage, PresetQuery};
+    let page = match global_bridge_mut() {
+        Some(bridge) if bridge.is_soundfont_loaded_internal() => {
+            let overrides = bridge.midi_player_mut().preset_effects_sends().clone();
+            match bridge.get_loaded_soundfont() {
+                Some(soundfont) => PresetCatalogPage::query(soundfont, &PresetQuery {
+                    offset,
+                    limit,
+                    name_filter: name_filter.to_string(),
+                }, &overrides),
+                None => PresetCatalogPage::error("SoundFont reference not available"),
             }
-        } else {
-            r#"{"success": false, "error": "Bridge not available", "samples": []}"#.to_string()
-        }
-    }
+        },
+        Some(_) => PresetCatalogPage::error("No SoundFont loaded"),
+        None => PresetCatalogPage::error("Bridge not available"),
+    };
+    page.to_json()
+}
+
+/// Min/max waveform overview of one sample as interleaved pairs (Float32Array, 2 * points values)
+/// Returns an empty array for an unknown index or when no SoundFont is loaded
+#[wasm_bindgen]
+pub fn get_sample_waveform(sample_index: usize, points: usize) -> Vec<f32> {
+    global_bridge_mut()
+        .and_then(|bridge| bridge.get_loaded_soundfont())
+        .and_then(|soundfont| soundfont.samples.get(sample_index))
+        .map(|sample| soundfont::catalog::SampleWaveform::decimate(&sample.sample_data, points).interleaved())
+        .unwrap_or_default()
 }
 
 /// Get raw sample data for a specific sample by index - returns Float32Array
@@ -1846,56 +4088,136 @@ pub fn get_system_diagnostics() -> String {
     }
 }
 
-/// Test SoundFont synthesis with MIDI events  
+/// Start recording the master output (see AudioWorkletBridge::start_capture)
+/// Throws `internal` if memory for the full duration can't be reserved
 #[wasm_bindgen]
-pub fn test_soundfont_synthesis() -> String {
-    log("Testing SoundFont synthesis pipeline...");
-    
-    unsafe {
-        if let Some(ref mut bridge) = GLOBAL_WORKLET_BRIDGE {
-            if !bridge.is_soundfont_loaded_internal() {
-                let error = "No SoundFont loaded - load SoundFont first";
-                log(error);
-                return format!(r#"{{"success": false, "error": "{}"}}"#, error);
-            }
-            
-            // Test note sequence: C4, E4, G4 (C major chord)
-            let test_notes = [60, 64, 67]; // MIDI note numbers
-            let mut test_results = Vec::new();
-            
-            for &note in &test_notes {
-                // Trigger note
-                bridge.queue_midi_event(0, 0, 0x90, note, 100); // Note On
-                
-                // Generate a few samples
-                let mut samples = Vec::new();
-                for _ in 0..10 {
-                    let buffer = bridge.process_audio_buffer(1);
-                    if !buffer.is_empty() {
-                        samples.push(format!("{:.4}", buffer[0]));
-                    }
-                }
-                
-                // Release note
-                bridge.queue_midi_event(100, 0, 0x80, note, 0); // Note Off
-                
-                test_results.push(format!("Note {}: [{}]", note, samples.join(", ")));
-                log(&format!("Test note {} generated {} samples", note, samples.len()));
-            }
-            
-            let result = format!(r#"{{"success": true, "test_results": [{}]}}"#, 
-                               test_results.iter().map(|s| format!("\"{}\"", s)).collect::<Vec<_>>().join(", "));
-            
-            log("SoundFont synthesis test completed");
-            result
-        } else {
-            let error = "AudioWorklet bridge not initialized";
-            log(error);
-            format!(r#"{{"success": false, "error": "{}"}}"#, error)
-        }
+pub fn start_capture_global(max_seconds: f32) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("start_capture_global");
+    if !global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.start_capture(max_seconds) {
+        return Err(ErrorEvent::raise(ErrorCode::Internal, format!("Could not reserve memory for {} s of capture", max_seconds)));
+    }
+    Ok(())
+}
+
+/// Stop recording and return the encoded file
+#[wasm_bindgen]
+pub fn stop_capture_global(format: &str) -> Result<Vec<u8>, ErrorEvent> {
+    let _scope = ExportScope::enter("stop_capture_global");
+    Ok(global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.stop_capture(format))
+}
+
+/// Stop recording and return the capture encoded with options JSON (see encode_audio_buffer_with_options)
+#[wasm_bindgen]
+pub fn stop_capture_with_options_global(format: &str, options_json: &str) -> Result<Vec<u8>, ErrorEvent> {
+    let _scope = ExportScope::enter("stop_capture_with_options_global");
+    Ok(global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.stop_capture_with_options(format, options_json))
+}
+
+/// Get capture state (JSON)
+#[wasm_bindgen]
+pub fn get_capture_status_global() -> Result<String, ErrorEvent> {
+    let _scope = ExportScope::enter("get_capture_status_global");
+    Ok(global_bridge_mut().ok_or_else(ErrorEvent::not_initialized)?.get_capture_status())
+}
+
+fn unknown_format(format: &str) -> ErrorEvent {
+    ErrorEvent::raise(ErrorCode::InvalidArgument, format!("Unknown export format \"{}\"", format))
+}
+
+/// Encode interleaved float samples as a WAV file
+/// `format`: "pcm16", "pcm24" or "float32"; throws `invalidArgument` for unknown formats
+#[wasm_bindgen]
+pub fn encode_wav_buffer(samples: &[f32], sample_rate: u32, channels: u16, format: &str) -> Result<Vec<u8>, ErrorEvent> {
+    let _scope = ExportScope::enter("encode_wav_buffer");
+    let wav_format = audio::wav::WavFormat::from_name(format).ok_or_else(|| unknown_format(format))?;
+    Ok(audio::wav::encode_wav(audio::wav::WavSpec::new(sample_rate, channels, wav_format), samples))
+}
+
+/// Encode interleaved float samples in any export format compiled into this build
+/// `format`: see get_export_formats(); throws `invalidArgument` for unknown formats
+#[wasm_bindgen]
+pub fn encode_audio_buffer(samples: &[f32], sample_rate: u32, channels: u16, format: &str) -> Result<Vec<u8>, ErrorEvent> {
+    let _scope = ExportScope::enter("encode_audio_buffer");
+    let export_format = audio::export::ExportFormat::from_name(format).ok_or_else(|| unknown_format(format))?;
+    Ok(audio::export::encode(export_format, sample_rate, channels, samples))
+}
+
+/// encode_audio_buffer() with encoder options JSON: `{"dither": bool, "noiseShaping": bool}`
+/// Dithering applies to pcm16; throws `invalidArgument` for unknown formats or unparseable options
+#[wasm_bindgen]
+pub fn encode_audio_buffer_with_options(samples: &[f32], sample_rate: u32, channels: u16, format: &str, options_json: &str) -> Result<Vec<u8>, ErrorEvent> {
+    let _scope = ExportScope::enter("encode_audio_buffer_with_options");
+    let export_format = audio::export::ExportFormat::from_name(format).ok_or_else(|| unknown_format(format))?;
+    let options = audio::export::ExportOptions::from_json(options_json)
+        .ok_or_else(|| ErrorEvent::raise(ErrorCode::InvalidArgument, "Export options do not parse"))?;
+    Ok(audio::export::encode_with_options(export_format, sample_rate, channels, samples, options))
+}
+
+/// Measure interleaved mono/stereo samples (LoudnessReport JSON)
+#[wasm_bindgen]
+pub fn measure_loudness(samples: &[f32], sample_rate: f32, channels: u16) -> String {
+    serde_json::to_string(&audio::loudness::measure(samples, channels, sample_rate)).unwrap_or_else(|_| "null".to_string())
+}
+
+/// Normalize interleaved mono/stereo samples in place to `target_lufs`, limiting true peaks
+/// above `ceiling_dbtp`; returns what was done (NormalizeReport JSON)
+#[wasm_bindgen]
+pub fn normalize_audio_buffer(samples: &mut [f32], sample_rate: f32, channels: u16, target_lufs: f32, ceiling_dbtp: f32) -> String {
+    let report = audio::normalize::normalize(samples, channels, sample_rate, target_lufs, ceiling_dbtp);
+    serde_json::to_string(&report).unwrap_or_else(|_| "null".to_string())
+}
+
+/// Export formats available in this build with their MIME types (JSON array)
+#[wasm_bindgen]
+pub fn get_export_formats() -> String {
+    let formats: Vec<audio::export::ExportFormatInfo> = audio::export::available_formats()
+        .into_iter()
+        .filter_map(audio::export::ExportFormat::from_name)
+        .map(|format| format.info())
+        .collect();
+    serde_json::to_string(&formats).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Reserve WASM heap so allocations up to `bytes` don't grow memory mid-playback
+#[wasm_bindgen]
+pub fn reserve_wasm_memory_global(bytes: usize) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("reserve_wasm_memory_global");
+    reserve_memory(bytes)
+}
+
+/// Reserve enough heap to load a SoundFont file of `file_size` bytes (call before loading)
+#[wasm_bindgen]
+pub fn reserve_memory_for_soundfont_global(file_size: usize) -> Result<(), ErrorEvent> {
+    let _scope = ExportScope::enter("reserve_memory_for_soundfont_global");
+    reserve_memory(audio::memory::estimate_soundfont_load_bytes(file_size))
+}
+
+fn reserve_memory(bytes: usize) -> Result<(), ErrorEvent> {
+    if audio::memory::reserve(bytes) {
+        Ok(())
+    } else {
+        Err(ErrorEvent::raise(ErrorCode::Internal, format!("Could not reserve {} bytes", bytes)))
     }
 }
 
+/// Get WASM memory usage and growth history (JSON)
+#[wasm_bindgen]
+pub fn get_memory_stats_global() -> String {
+    serde_json::to_string(&audio::memory::stats()).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Get audio-path trace counters (JSON) - counts stay zero unless built with `audio-trace`
+#[wasm_bindgen]
+pub fn get_audio_trace_counters() -> String {
+    serde_json::to_string(&synth::trace::report()).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Reset audio-path trace counters
+#[wasm_bindgen]
+pub fn reset_audio_trace_counters() {
+    synth::trace::reset();
+}
+
 #[wasm_bindgen]
 pub fn play_raw_sample_direct() -> String {
     log("🎵 TESTING RAW SAMPLE DIRECT PLAYBACK - NO SYNTHESIS");
@@ -1919,7 +4241,7 @@ pub fn play_raw_sample_direct() -> String {
                     
                     log(&format!("🎵 Sample stats: {} non-zero samples, max amplitude: {}", non_zero, max_amplitude));
                     
-                    return format!(
+                    format!(
                         "{{\"success\": true, \"test\": \"raw_sample_direct\", \"sample_name\": \"{}\", \"original_length\": {}, \"max_amplitude\": {}, \"non_zero_samples\": {}, \"sample_rate\": {}, \"original_pitch\": {}}}",
                         sample.name,
                         sample_data.len(),
@@ -1927,15 +4249,15 @@ pub fn play_raw_sample_direct() -> String {
                         non_zero,
                         sample.sample_rate,
                         sample.original_pitch
-                    );
+                    )
                 } else {
-                    return "{\"success\": false, \"error\": \"No samples found in SoundFont\"}".to_string();
+                    "{\"success\": false, \"error\": \"No samples found in SoundFont\"}".to_string()
                 }
             } else {
-                return "{\"success\": false, \"error\": \"No SoundFont loaded\"}".to_string();
+                "{\"success\": false, \"error\": \"No SoundFont loaded\"}".to_string()
             }
         } else {
-            return "{\"success\": false, \"error\": \"Audio bridge not initialized\"}".to_string();
+            "{\"success\": false, \"error\": \"Audio bridge not initialized\"}".to_string()
         }
     }
 }
@@ -0,0 +1,214 @@
+/**
+ * AWE Player - Automation Lanes
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Hosts draw a curve per channel parameter - a filter sweep, a volume
+ * fade - as points at song positions (ticks). The player evaluates every
+ * lane once per sample at the fractional tick playback has reached, so a
+ * curve follows tempo changes and seeks and moves as smoothly as its
+ * interpolation allows, with no 7-bit steps and no controller smoothing
+ * lag in between. Before its first point a lane holds the first value,
+ * after its last point the last one.
+ *
+ * Automation sits on top of the channel's controllers: volume is a gain in
+ * dB on top of CC7/CC11 and the channel trim, pan moves the CC10 position,
+ * and cutoff scales the CC74 brightness by octaves.
+ */
+
+use serde::Serialize;
+
+/// Lanes held at once (one per parameter of every channel)
+pub const MAX_AUTOMATION_LANES: usize = 48;
+/// Points per lane
+pub const MAX_AUTOMATION_POINTS: usize = 4096;
+
+/// What a lane drives, with the range of its values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum AutomationParameter {
+    Volume,     // dB, -60 to +12
+    Pan,        // -1.0 (left) to 1.0 (right), added to the CC10 position
+    Cutoff,     // Octaves, -2 to +2, on the CC74 brightness
+}
+
+impl AutomationParameter {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "volume" => Some(AutomationParameter::Volume),
+            "pan" => Some(AutomationParameter::Pan),
+            "cutoff" => Some(AutomationParameter::Cutoff),
+            _ => None,
+        }
+    }
+
+    /// Lowest and highest value a point may have
+    pub fn range(&self) -> (f32, f32) {
+        match self {
+            AutomationParameter::Volume => (-60.0, 12.0),
+            AutomationParameter::Pan => (-1.0, 1.0),
+            AutomationParameter::Cutoff => (-2.0, 2.0),
+        }
+    }
+}
+
+/// How a lane moves from one point to the next
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum AutomationInterpolation {
+    Step,       // Hold each value until the next point
+    Linear,
+    Smooth,     // Cosine ease in and out of each point
+}
+
+impl AutomationInterpolation {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "step" => Some(AutomationInterpolation::Step),
+            "linear" => Some(AutomationInterpolation::Linear),
+            "smooth" => Some(AutomationInterpolation::Smooth),
+            _ => None,
+        }
+    }
+}
+
+/// A value at a song position
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AutomationPoint {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub tick: u64,
+    pub value: f32,
+}
+
+/// One parameter of one channel over the song (get_automation_lanes_global)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AutomationLane {
+    pub channel: u8,
+    pub parameter: AutomationParameter,
+    pub interpolation: AutomationInterpolation,
+    pub points: Vec<AutomationPoint>,   // In tick order
+}
+
+impl AutomationLane {
+    /// Lane from points in any order (values clamped to the parameter's range)
+    /// None for a bad channel or a point count outside 1..=MAX_AUTOMATION_POINTS
+    pub fn new(channel: u8, parameter: AutomationParameter, interpolation: AutomationInterpolation,
+               mut points: Vec<AutomationPoint>) -> Option<Self> {
+        if channel > 15 || points.is_empty() || points.len() > MAX_AUTOMATION_POINTS {
+            return None;
+        }
+        let (low, high) = parameter.range();
+        for point in points.iter_mut() {
+            point.value = if point.value.is_finite() { point.value.clamp(low, high) } else { 0.0 };
+        }
+        points.sort_by_key(|point| point.tick);
+        Some(Self { channel, parameter, interpolation, points })
+    }
+
+    /// Value at a fractional song position
+    pub fn value_at(&self, tick: f64) -> f32 {
+        let next = self.points.partition_point(|point| point.tick as f64 <= tick);
+        let (Some(from), Some(to)) = (next.checked_sub(1).map(|index| self.points[index]), self.points.get(next)) else {
+            // Outside the curve: hold the nearest end
+            return if next == 0 { self.points[0].value } else { self.points[next - 1].value };
+        };
+        let progress = ((tick - from.tick as f64) / (to.tick - from.tick) as f64) as f32;
+        let shaped = match self.interpolation {
+            AutomationInterpolation::Step => 0.0,
+            AutomationInterpolation::Linear => progress,
+            AutomationInterpolation::Smooth => (1.0 - (progress * std::f32::consts::PI).cos()) * 0.5,
+        };
+        from.value + (to.value - from.value) * shaped
+    }
+}
+
+/// Every lane of the player
+#[derive(Debug, Clone, Default)]
+pub struct AutomationLanes {
+    lanes: Vec<AutomationLane>,
+}
+
+impl AutomationLanes {
+    pub fn new() -> Self {
+        Self { lanes: Vec::with_capacity(MAX_AUTOMATION_LANES) }
+    }
+
+    /// Add a lane, replacing the one for the same channel and parameter
+    /// False if MAX_AUTOMATION_LANES lanes are already held
+    pub fn set(&mut self, lane: AutomationLane) -> bool {
+        let held = self.lanes.iter().position(|held| (held.channel, held.parameter) == (lane.channel, lane.parameter));
+        match held {
+            Some(index) => self.lanes[index] = lane,
+            None if self.lanes.len() < MAX_AUTOMATION_LANES => self.lanes.push(lane),
+            None => return false,
+        }
+        true
+    }
+
+    /// Drop the lane of a channel parameter (false if there is none)
+    pub fn remove(&mut self, channel: u8, parameter: AutomationParameter) -> bool {
+        let before = self.lanes.len();
+        self.lanes.retain(|lane| (lane.channel, lane.parameter) != (channel, parameter));
+        self.lanes.len() != before
+    }
+
+    pub fn clear(&mut self) {
+        self.lanes.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lanes.is_empty()
+    }
+
+    pub fn lanes(&self) -> &[AutomationLane] {
+        &self.lanes
+    }
+
+    /// Hand every lane's value at `tick` to `apply` (channel, parameter, value)
+    pub fn evaluate(&self, tick: f64, mut apply: impl FnMut(u8, AutomationParameter, f32)) {
+        for lane in &self.lanes {
+            apply(lane.channel, lane.parameter, lane.value_at(tick));
+        }
+    }
+}
+
+/// Automated values of one channel, neutral where no lane drives them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelAutomation {
+    pub gain: f32,          // Linear, from the volume lane
+    pub pan: f32,           // Added to the CC10 position
+    pub brightness: f32,    // Cutoff multiplier
+}
+
+impl ChannelAutomation {
+    pub const NEUTRAL: ChannelAutomation = ChannelAutomation { gain: 1.0, pan: 0.0, brightness: 1.0 };
+
+    /// Take a lane value; returns true if the channel's mix changed
+    pub fn set(&mut self, parameter: AutomationParameter, value: f32) -> bool {
+        let (slot, value) = match parameter {
+            AutomationParameter::Volume => (&mut self.gain, 10f32.powf(value / 20.0)),
+            AutomationParameter::Pan => (&mut self.pan, value),
+            AutomationParameter::Cutoff => (&mut self.brightness, 2f32.powf(value)),
+        };
+        let changed = *slot != value;
+        *slot = value;
+        changed
+    }
+
+    /// Back to neutral for one parameter (its lane was removed); 0 dB, centered and
+    /// 0 octaves are all lane value 0
+    pub fn reset(&mut self, parameter: AutomationParameter) {
+        self.set(parameter, 0.0);
+    }
+}
+
+impl Default for ChannelAutomation {
+    fn default() -> Self {
+        Self::NEUTRAL
+    }
+}
@@ -0,0 +1,205 @@
+/**
+ * AWE Player - MIDI System Modes and Bank Select Resolution
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Translates Bank Select (CC0/CC32) + Program Change into the SoundFont
+ * bank/program to load, following the conventions of the active system mode.
+ *
+ * SoundFont convention: melodic presets live in banks 0-127, percussion
+ * kits in bank 128 with the kit number as program.
+ *
+ * - GM:  bank select ignored; channel 10 is percussion
+ * - GM2: MSB 121 = melodic bank (LSB = variation), MSB 120 = rhythm bank
+ * - GS:  MSB = variation (LSB = map select, ignored); kit = program number
+ * - XG:  LSB = variation bank, MSB 127/126 = drum/SFX kits, MSB 64 = SFX voices
+ *
+ * GS drum parts are assigned by SysEx "Use For Rhythm Part", not bank select.
+ */
+
+use super::constants::MIDI_DRUM_CHANNEL;
+
+/// SoundFont bank used for percussion kits
+pub const SOUNDFONT_DRUM_BANK: u16 = 128;
+
+/// GM2 bank select MSB values
+pub const GM2_BANK_MSB_RHYTHM: u8 = 0x78;
+pub const GM2_BANK_MSB_MELODIC: u8 = 0x79;
+
+/// XG bank select MSB values
+pub const XG_BANK_MSB_SFX_VOICE: u8 = 0x40;
+pub const XG_BANK_MSB_SFX_KIT: u8 = 0x7E;
+pub const XG_BANK_MSB_DRUM_KIT: u8 = 0x7F;
+
+/// SoundFont bank used for XG SFX voices (falls back to bank 0 if absent)
+pub const SOUNDFONT_SFX_BANK: u16 = 64;
+
+/// GS SFX drum kit program (where GS-style SoundFonts keep sound effects)
+pub const GS_SFX_KIT_PROGRAM: u8 = 56;
+
+/// MIDI system compatibility mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MidiSystemMode {
+    /// General MIDI Level 1 (default)
+    Gm,
+    /// General MIDI Level 2
+    Gm2,
+    /// Roland GS
+    Gs,
+    /// Yamaha XG
+    Xg,
+}
+
+impl MidiSystemMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "gm" | "gm1" => Some(MidiSystemMode::Gm),
+            "gm2" => Some(MidiSystemMode::Gm2),
+            "gs" => Some(MidiSystemMode::Gs),
+            "xg" => Some(MidiSystemMode::Xg),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MidiSystemMode::Gm => "gm",
+            MidiSystemMode::Gm2 => "gm2",
+            MidiSystemMode::Gs => "gs",
+            MidiSystemMode::Xg => "xg",
+        }
+    }
+}
+
+/// Result of resolving Bank Select + Program Change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BankSelection {
+    pub bank: u16,      // SoundFont bank (128 = percussion)
+    pub program: u8,
+    pub is_drum: bool,
+}
+
+/// Resolve bank select for a Program Change on `channel`
+/// `was_drum` is the channel's current percussion flag (GM2 keeps it unless MSB says otherwise)
+pub fn resolve_bank_select(
+    mode: MidiSystemMode,
+    channel: u8,
+    bank_msb: u8,
+    bank_lsb: u8,
+    program: u8,
+    was_drum: bool,
+) -> BankSelection {
+    match mode {
+        MidiSystemMode::Gm => {
+            let is_drum = channel == MIDI_DRUM_CHANNEL;
+            BankSelection {
+                bank: if is_drum { SOUNDFONT_DRUM_BANK } else { 0 },
+                program,
+                is_drum,
+            }
+        }
+        MidiSystemMode::Gm2 => {
+            let is_drum = match bank_msb {
+                GM2_BANK_MSB_RHYTHM => true,
+                GM2_BANK_MSB_MELODIC => false,
+                _ => was_drum,
+            };
+            let bank = if is_drum {
+                SOUNDFONT_DRUM_BANK
+            } else if bank_msb == GM2_BANK_MSB_MELODIC {
+                bank_lsb as u16 // Variation number
+            } else {
+                0
+            };
+            BankSelection { bank, program, is_drum }
+        }
+        MidiSystemMode::Gs => {
+            // Drum parts are assigned by SysEx, not bank select
+            if was_drum {
+                BankSelection { bank: SOUNDFONT_DRUM_BANK, program, is_drum: true }
+            } else {
+                BankSelection { bank: bank_msb as u16, program, is_drum: false }
+            }
+        }
+        MidiSystemMode::Xg => match bank_msb {
+            XG_BANK_MSB_DRUM_KIT => BankSelection { bank: SOUNDFONT_DRUM_BANK, program, is_drum: true },
+            XG_BANK_MSB_SFX_KIT => BankSelection { bank: SOUNDFONT_DRUM_BANK, program: GS_SFX_KIT_PROGRAM, is_drum: true },
+            XG_BANK_MSB_SFX_VOICE => BankSelection { bank: SOUNDFONT_SFX_BANK, program, is_drum: false },
+            // XG channel 10 defaults to a drum kit until a melodic MSB arrives
+            0 if was_drum && channel == MIDI_DRUM_CHANNEL => {
+                BankSelection { bank: SOUNDFONT_DRUM_BANK, program, is_drum: true }
+            }
+            _ => BankSelection { bank: bank_lsb as u16, program, is_drum: false },
+        },
+    }
+}
+
+/// Banks to try, in order, when the resolved bank has no preset for the program
+/// GS falls back to the sub-capital tone (variation rounded down to a multiple of 8)
+/// Runs on Program Change inside the audio callback, so it yields banks without allocating
+pub fn fallback_banks(mode: MidiSystemMode, selection: &BankSelection) -> impl Iterator<Item = u16> {
+    let banks = if selection.is_drum {
+        [Some(SOUNDFONT_DRUM_BANK), None]
+    } else {
+        let sub_capital = (mode == MidiSystemMode::Gs && selection.bank & 0x07 != 0)
+            .then_some(selection.bank & !0x07);
+        [sub_capital, (selection.bank != 0).then_some(0)]
+    };
+    banks.into_iter().flatten()
+}
+
+/// Universal Non-Realtime SysEx messages recognized by the engine
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemSysEx {
+    Gm1SystemOn,
+    GmSystemOff,
+    Gm2SystemOn,
+    GsReset,
+    /// GS "Use For Rhythm Part" - switch a part between melodic and drums
+    GsRhythmPart { channel: u8, is_drum: bool },
+    XgSystemOn,
+}
+
+/// Parse system SysEx: GM System On/Off (F0 7E <dev> 09 <01|02|03> F7),
+/// GS Reset / Use For Rhythm Part (Roland DT1) and XG System On
+pub fn parse_system_sysex(data: &[u8]) -> Option<SystemSysEx> {
+    match data {
+        [0xF0, 0x7E, _device, 0x09, sub_id, 0xF7, ..] => match sub_id {
+            0x01 => Some(SystemSysEx::Gm1SystemOn),
+            0x02 => Some(SystemSysEx::GmSystemOff),
+            0x03 => Some(SystemSysEx::Gm2SystemOn),
+            _ => None,
+        },
+        [0xF0, 0x41, _device, 0x42, 0x12, addr_hi, addr_mid, addr_lo, value, checksum, 0xF7, ..] => {
+            if roland_checksum(&[*addr_hi, *addr_mid, *addr_lo, *value]) != *checksum {
+                return None;
+            }
+            match (*addr_hi, *addr_mid, *addr_lo) {
+                (0x40, 0x00, 0x7F) if *value == 0x00 => Some(SystemSysEx::GsReset),
+                (0x40, 0x10..=0x1F, 0x15) => Some(SystemSysEx::GsRhythmPart {
+                    channel: gs_part_to_channel(addr_mid & 0x0F),
+                    is_drum: *value != 0,
+                }),
+                _ => None,
+            }
+        }
+        [0xF0, 0x43, device, 0x4C, 0x00, 0x00, 0x7E, 0x00, 0xF7, ..] if device & 0xF0 == 0x10 => {
+            Some(SystemSysEx::XgSystemOn)
+        }
+        _ => None,
+    }
+}
+
+/// Roland checksum: address + data bytes plus checksum sum to 0 (mod 128)
+fn roland_checksum(bytes: &[u8]) -> u8 {
+    let sum: u32 = bytes.iter().map(|&b| b as u32).sum();
+    ((128 - (sum % 128)) % 128) as u8
+}
+
+/// GS part blocks are ordered 10, 1-9, 11-16
+fn gs_part_to_channel(block: u8) -> u8 {
+    match block {
+        0 => MIDI_DRUM_CHANNEL,
+        1..=9 => block - 1,
+        _ => block,
+    }
+}
@@ -0,0 +1,138 @@
+/**
+ * AWE Player - Sequencer Chase
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Seeking into the middle of a file skips every event before the seek
+ * point, so channels would keep whatever program and controllers they had
+ * where playback left off. Chasing scans the skipped events and rebuilds
+ * the state they would have left behind: per channel the program (with the
+ * bank selected when it was changed), the last value of every controller
+ * (sustain included), and the RPN/NRPN data entry sequence in its original
+ * order, since a data entry only means something after its parameter
+ * select. Channel mode messages (CC120-127) are not state and are skipped;
+ * Reset All Controllers clears what it resets. GM/GS/XG system messages are
+ * replayed ahead of the channel state, and a system reset among them
+ * forgets the channel state that came before it. The tempo in effect at
+ * the seek point is reported as well.
+ */
+
+use super::bank_select::{parse_system_sysex, SystemSysEx};
+use super::parser::{MetaEventType, MidiFile, MidiEventType};
+use super::sequencer::{ProcessedEventType, ProcessedMidiEvent};
+
+const CC_BANK_SELECT_MSB: u8 = 0;
+const CC_BANK_SELECT_LSB: u8 = 32;
+const CC_RESET_ALL_CONTROLLERS: u8 = 121;
+
+/// Controllers Reset All Controllers returns to their defaults (RP-015)
+const RESET_CONTROLLERS: [u8; 6] = [1, 11, 64, 65, 66, 67];
+
+/// Data entry and parameter select controllers, replayed in order
+fn is_parameter_controller(controller: u8) -> bool {
+    matches!(controller, 6 | 38 | 96..=101)
+}
+
+/// State reached at a tick
+#[derive(Debug, Clone, Default)]
+pub struct ChaseState {
+    pub tempo: Option<u32>,                 // Last Set Tempo before the tick (microseconds/quarter)
+    pub events: Vec<ProcessedMidiEvent>,    // Events that rebuild the channel state, in dispatch order
+}
+
+#[derive(Clone)]
+struct ChannelChase {
+    program: Option<(u8, Option<u8>, Option<u8>)>,  // Program with the bank MSB/LSB current at the change
+    controllers: [Option<u8>; 128],
+    parameters: Vec<(u8, u8)>,
+}
+
+impl ChannelChase {
+    fn new() -> Self {
+        ChannelChase { program: None, controllers: [None; 128], parameters: Vec::new() }
+    }
+
+    fn control_change(&mut self, controller: u8, value: u8) {
+        match controller {
+            CC_RESET_ALL_CONTROLLERS => {
+                for reset in RESET_CONTROLLERS {
+                    self.controllers[reset as usize] = None;
+                }
+                // Parameter selection goes back to null
+                self.parameters.push((101, 127));
+                self.parameters.push((100, 127));
+            }
+            120..=127 => {}
+            _ if is_parameter_controller(controller) => self.parameters.push((controller, value)),
+            _ => self.controllers[controller as usize & 0x7F] = Some(value),
+        }
+    }
+
+    fn events(&self, channel: u8, events: &mut Vec<ProcessedMidiEvent>) {
+        let mut push = |event_type| events.push(ProcessedMidiEvent { sample_offset: 0, event_type });
+        let control = |controller, value| ProcessedEventType::ControlChange { channel, controller, value };
+        if let Some((program, bank_msb, bank_lsb)) = self.program {
+            if let Some(value) = bank_msb {
+                push(control(CC_BANK_SELECT_MSB, value));
+            }
+            if let Some(value) = bank_lsb {
+                push(control(CC_BANK_SELECT_LSB, value));
+            }
+            push(ProcessedEventType::ProgramChange { channel, program });
+        }
+        // Bank select only takes effect at a Program Change, so the current value is safe to restore
+        for (controller, value) in self.controllers.iter().enumerate() {
+            if let Some(value) = *value {
+                push(control(controller as u8, value));
+            }
+        }
+        for &(controller, value) in &self.parameters {
+            push(control(controller, value));
+        }
+    }
+}
+
+/// Channel state and tempo left behind by the events of `midi_file` before `tick`
+pub fn chase(midi_file: &MidiFile, tick: u64) -> ChaseState {
+    let mut skipped: Vec<_> = midi_file.tracks.iter()
+        .flat_map(|track| track.events.iter())
+        .filter(|event| event.absolute_time < tick)
+        .collect();
+    // Stable: events at the same tick keep their track order
+    skipped.sort_by_key(|event| event.absolute_time);
+
+    let mut state = ChaseState::default();
+    let mut channels = vec![ChannelChase::new(); 16];
+    for event in skipped {
+        match event.event_type {
+            MidiEventType::ProgramChange { channel, program } => {
+                let Some(chase) = channels.get_mut(channel as usize) else {
+                    continue;
+                };
+                let bank = (chase.controllers[CC_BANK_SELECT_MSB as usize], chase.controllers[CC_BANK_SELECT_LSB as usize]);
+                chase.program = Some((program, bank.0, bank.1));
+            }
+            MidiEventType::ControlChange { channel, controller, value } => {
+                if let Some(chase) = channels.get_mut(channel as usize) {
+                    chase.control_change(controller, value);
+                }
+            }
+            MidiEventType::SysEx { ref data } => {
+                let Some(message) = parse_system_sysex(data) else {
+                    continue;
+                };
+                if !matches!(message, SystemSysEx::GsRhythmPart { .. } | SystemSysEx::GmSystemOff) {
+                    channels.fill(ChannelChase::new());
+                }
+                state.events.push(ProcessedMidiEvent { sample_offset: 0, event_type: ProcessedEventType::SysEx { data: data.clone() } });
+            }
+            MidiEventType::MetaEvent(MetaEventType::SetTempo { microseconds_per_quarter }) => {
+                state.tempo = Some(microseconds_per_quarter);
+            }
+            _ => {}
+        }
+    }
+    for (channel, chase) in channels.iter().enumerate() {
+        chase.events(channel as u8, &mut state.events);
+    }
+    state
+}
@@ -12,6 +12,7 @@ pub const MIDI_EVENT_SYSTEM: u8 = 0xF;
 pub const MIDI_STATUS_META_EVENT: u8 = 0xFF;
 pub const MIDI_STATUS_SYSEX_START: u8 = 0xF0;
 pub const MIDI_STATUS_SYSEX_END: u8 = 0xF7;
+pub const MIDI_STATUS_SONG_POSITION: u8 = 0xF2;
 
 /// MIDI meta event type constants
 pub const META_EVENT_SEQUENCE_NUMBER: u8 = 0x00;
@@ -31,11 +32,24 @@ pub const META_EVENT_KEY_SIGNATURE: u8 = 0x59;
 pub const META_EVENT_SEQUENCER_SPECIFIC: u8 = 0x7F;
 
 /// Common MIDI controller numbers
+pub const MIDI_CC_BANK_SELECT_MSB: u8 = 0x00;
 pub const MIDI_CC_MODULATION: u8 = 0x01;
+pub const MIDI_CC_DATA_ENTRY_MSB: u8 = 0x06;
 pub const MIDI_CC_VOLUME: u8 = 0x07;
 pub const MIDI_CC_PAN: u8 = 0x0A;
+pub const MIDI_CC_EXPRESSION: u8 = 0x0B;
+pub const MIDI_CC_BANK_SELECT_LSB: u8 = 0x20;
+pub const MIDI_CC_DATA_ENTRY_LSB: u8 = 0x26;
 pub const MIDI_CC_SUSTAIN: u8 = 0x40;
+pub const MIDI_CC_SOUND_CONTROLLER_1: u8 = 0x46; // CC70 - first GM2 sound controller
+pub const MIDI_CC_SOUND_CONTROLLER_10: u8 = 0x4F; // CC79 - last GM2 sound controller
+pub const MIDI_CC_HIGH_RES_VELOCITY: u8 = 0x58; // CC88 - LSB prefix for next Note On
+pub const MIDI_CC_NRPN_LSB: u8 = 0x62;
+pub const MIDI_CC_NRPN_MSB: u8 = 0x63;
+pub const MIDI_CC_RPN_LSB: u8 = 0x64;
+pub const MIDI_CC_RPN_MSB: u8 = 0x65;
 pub const MIDI_CC_ALL_SOUND_OFF: u8 = 0x78;
+pub const MIDI_CC_RESET_ALL_CONTROLLERS: u8 = 0x79;
 pub const MIDI_CC_ALL_NOTES_OFF: u8 = 0x7B;
 
 /// MIDI channel constants
@@ -0,0 +1,304 @@
+/**
+ * AWE Player - MIDI Sequence Editing
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Small editing surface over a loaded MIDI track: insert notes and
+ * controller changes, delete, move and quantize selections. Selections are
+ * event indices as listed by `SequenceEvent::list`; selecting a note-on
+ * also selects its note-off, so notes keep their length when moved or
+ * quantized and never lose their release when deleted.
+ *
+ * After every edit the track is re-sorted, delta times are rebuilt and the
+ * End of Track marker is kept last. The sequencer re-indexes its playback
+ * position afterwards (see MidiSequencer::edit_track).
+ */
+
+use serde::Serialize;
+use super::parser::{MetaEventType, MidiEvent, MidiEventType, MidiTrack};
+
+/// One edit applied to a single track
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrackEdit {
+    InsertNote { tick: u64, duration: u64, channel: u8, note: u8, velocity: u8 },
+    InsertControlChange { tick: u64, channel: u8, controller: u8, value: u8 },
+    /// Note on/off, control and program change messages (status, data1, data2) at ticks
+    InsertMessages { messages: Vec<(u64, [u8; 3])> },
+    Delete { indices: Vec<usize> },
+    Move { indices: Vec<usize>, delta_ticks: i64 },
+    Quantize { indices: Vec<usize>, grid_ticks: u64 },
+}
+
+/// Event kind in `SequenceEvent` listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum SequenceEventKind {
+    NoteOn,
+    NoteOff,
+    ProgramChange,
+    ControlChange,
+    PitchBend,
+    Meta,
+    SysEx,
+}
+
+/// Listing entry for one track event (get_sequence_events_global)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SequenceEvent {
+    pub index: usize,                 // Position in the track; the handle for edits
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub tick: u64,
+    pub kind: SequenceEventKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub channel: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub note: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub velocity: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub controller: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub value: Option<i32>,           // Program, controller value or pitch bend
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(type = "number", optional))]
+    pub duration_ticks: Option<u64>,  // Note-ons with a matching note-off
+}
+
+impl SequenceEvent {
+    /// Events of `track` with start_tick <= tick < end_tick
+    pub fn list(track: &MidiTrack, start_tick: u64, end_tick: u64) -> Vec<SequenceEvent> {
+        track.events.iter()
+            .enumerate()
+            .filter(|(_, event)| (start_tick..end_tick).contains(&event.absolute_time))
+            .map(|(index, event)| Self::new(track, index, event))
+            .collect()
+    }
+
+    fn new(track: &MidiTrack, index: usize, event: &MidiEvent) -> Self {
+        let mut entry = SequenceEvent {
+            index,
+            tick: event.absolute_time,
+            kind: SequenceEventKind::Meta,
+            channel: None,
+            note: None,
+            velocity: None,
+            controller: None,
+            value: None,
+            duration_ticks: None,
+        };
+        match event.event_type {
+            MidiEventType::NoteOn { channel, note, velocity } if velocity > 0 => {
+                entry.kind = SequenceEventKind::NoteOn;
+                (entry.channel, entry.note, entry.velocity) = (Some(channel), Some(note), Some(velocity));
+                entry.duration_ticks = note_off_for(track, index)
+                    .map(|off| track.events[off].absolute_time - event.absolute_time);
+            }
+            MidiEventType::NoteOn { channel, note, velocity } | MidiEventType::NoteOff { channel, note, velocity } => {
+                entry.kind = SequenceEventKind::NoteOff;
+                (entry.channel, entry.note, entry.velocity) = (Some(channel), Some(note), Some(velocity));
+            }
+            MidiEventType::ProgramChange { channel, program } => {
+                entry.kind = SequenceEventKind::ProgramChange;
+                (entry.channel, entry.value) = (Some(channel), Some(program as i32));
+            }
+            MidiEventType::ControlChange { channel, controller, value } => {
+                entry.kind = SequenceEventKind::ControlChange;
+                (entry.channel, entry.controller, entry.value) = (Some(channel), Some(controller), Some(value as i32));
+            }
+            MidiEventType::PitchBend { channel, value } => {
+                entry.kind = SequenceEventKind::PitchBend;
+                (entry.channel, entry.value) = (Some(channel), Some(value as i32));
+            }
+            MidiEventType::MetaEvent(_) => {}
+            MidiEventType::SysEx { .. } => entry.kind = SequenceEventKind::SysEx,
+        }
+        entry
+    }
+}
+
+/// Listing entry for one track (get_sequence_tracks_global)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SequenceTrack {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub name: Option<String>,
+    pub event_count: usize,
+}
+
+impl SequenceTrack {
+    pub fn list(tracks: &[MidiTrack]) -> Vec<SequenceTrack> {
+        tracks.iter()
+            .enumerate()
+            .map(|(index, track)| SequenceTrack { index, name: track.name.clone(), event_count: track.events.len() })
+            .collect()
+    }
+}
+
+/// Apply `edit` to `track`; on error the track is unchanged
+pub fn apply(track: &mut MidiTrack, edit: &TrackEdit) -> Result<(), String> {
+    match edit {
+        TrackEdit::InsertNote { tick, duration, channel, note, velocity } => {
+            validate_channel_data(*channel, &[*note, *velocity])?;
+            if *velocity == 0 || *duration == 0 {
+                return Err("Notes need a velocity and duration above zero".to_string());
+            }
+            track.events.push(event_at(*tick, MidiEventType::NoteOn { channel: *channel, note: *note, velocity: *velocity }));
+            track.events.push(event_at(tick + duration, MidiEventType::NoteOff { channel: *channel, note: *note, velocity: 0 }));
+        }
+        TrackEdit::InsertControlChange { tick, channel, controller, value } => {
+            validate_channel_data(*channel, &[*controller, *value])?;
+            track.events.push(event_at(*tick, MidiEventType::ControlChange { channel: *channel, controller: *controller, value: *value }));
+        }
+        TrackEdit::InsertMessages { messages } => {
+            let events = messages.iter()
+                .map(|&(tick, message)| message_event(message).map(|event_type| event_at(tick, event_type)))
+                .collect::<Result<Vec<_>, String>>()?;
+            track.events.extend(events);
+        }
+        TrackEdit::Delete { indices } => {
+            let mut selection = expand_selection(track, indices)?;
+            selection.sort_unstable_by_key(|&(index, _)| std::cmp::Reverse(index));
+            for (index, _) in selection {
+                track.events.remove(index);
+            }
+        }
+        TrackEdit::Move { indices, delta_ticks } => {
+            let selection = expand_selection(track, indices)?;
+            // Clamp the whole selection at tick 0 so note lengths survive
+            let earliest = selection.iter().map(|&(index, _)| track.events[index].absolute_time).min().unwrap_or(0);
+            let delta = (*delta_ticks).max(-(earliest as i64));
+            for (index, _) in selection {
+                let event = &mut track.events[index];
+                event.absolute_time = (event.absolute_time as i64 + delta) as u64;
+            }
+        }
+        TrackEdit::Quantize { indices, grid_ticks } => {
+            if *grid_ticks == 0 {
+                return Err("Quantize grid must be at least one tick".to_string());
+            }
+            let selection = expand_selection(track, indices)?;
+            let snap = |tick: u64| (tick + grid_ticks / 2) / grid_ticks * grid_ticks;
+            let deltas: Vec<(usize, i64)> = selection.iter()
+                .map(|&(index, owner)| {
+                    let owner_tick = track.events[owner].absolute_time;
+                    (index, snap(owner_tick) as i64 - owner_tick as i64)
+                })
+                .collect();
+            for (index, delta) in deltas {
+                let event = &mut track.events[index];
+                event.absolute_time = (event.absolute_time as i64 + delta) as u64;
+            }
+        }
+    }
+    normalize(track);
+    Ok(())
+}
+
+/// Index of the note-off ending the note-on at `on_index`
+pub fn note_off_for(track: &MidiTrack, on_index: usize) -> Option<usize> {
+    let MidiEventType::NoteOn { channel, note, velocity } = track.events.get(on_index)?.event_type else {
+        return None;
+    };
+    if velocity == 0 {
+        return None;
+    }
+    track.events.iter()
+        .enumerate()
+        .skip(on_index + 1)
+        .find(|(_, event)| is_note_off(event, channel, note))
+        .map(|(index, _)| index)
+}
+
+fn is_note_off(event: &MidiEvent, channel: u8, note: u8) -> bool {
+    match event.event_type {
+        MidiEventType::NoteOff { channel: c, note: n, .. } => c == channel && n == note,
+        MidiEventType::NoteOn { channel: c, note: n, velocity: 0 } => c == channel && n == note,
+        _ => false,
+    }
+}
+
+fn is_end_of_track(event: &MidiEvent) -> bool {
+    matches!(event.event_type, MidiEventType::MetaEvent(MetaEventType::EndOfTrack))
+}
+
+fn validate_channel_data(channel: u8, data: &[u8]) -> Result<(), String> {
+    if channel > 15 || data.iter().any(|&byte| byte > 127) {
+        return Err("Channel must be 0-15 and data bytes 0-127".to_string());
+    }
+    Ok(())
+}
+
+fn message_event([status, data1, data2]: [u8; 3]) -> Result<MidiEventType, String> {
+    let channel = status & 0x0F;
+    validate_channel_data(channel, &[data1, data2])?;
+    match status & 0xF0 {
+        0x80 => Ok(MidiEventType::NoteOff { channel, note: data1, velocity: data2 }),
+        0x90 => Ok(MidiEventType::NoteOn { channel, note: data1, velocity: data2 }),
+        0xB0 => Ok(MidiEventType::ControlChange { channel, controller: data1, value: data2 }),
+        0xC0 => Ok(MidiEventType::ProgramChange { channel, program: data1 }),
+        _ => Err(format!("Unsupported message status {:#04X}", status)),
+    }
+}
+
+fn event_at(tick: u64, event_type: MidiEventType) -> MidiEvent {
+    MidiEvent { delta_time: 0, absolute_time: tick, event_type }
+}
+
+/// Validate `indices` and add the note-offs of selected note-ons
+/// Returns (event, owner) pairs: a paired note-off is owned by its note-on and follows it in time
+fn expand_selection(track: &MidiTrack, indices: &[usize]) -> Result<Vec<(usize, usize)>, String> {
+    let mut selection: Vec<(usize, usize)> = Vec::with_capacity(indices.len() * 2);
+    for &index in indices {
+        let event = track.events.get(index).ok_or_else(|| format!("No event at index {}", index))?;
+        if is_end_of_track(event) {
+            return Err("The End of Track marker cannot be edited".to_string());
+        }
+        selection.push((index, index));
+    }
+    for &index in indices {
+        if let Some(off) = note_off_for(track, index) {
+            // Selected on its own as well: it moves with its note-on
+            selection.retain(|&(selected, _)| selected != off);
+            selection.push((off, index));
+        }
+    }
+    selection.sort_unstable();
+    selection.dedup_by_key(|&mut (index, _)| index);
+    Ok(selection)
+}
+
+/// Restore time order (note-offs before note-ons at the same tick), rebuild
+/// delta times and keep End of Track last
+pub fn normalize(track: &mut MidiTrack) {
+    let last_tick = track.events.iter()
+        .filter(|event| !is_end_of_track(event))
+        .map(|event| event.absolute_time)
+        .max()
+        .unwrap_or(0);
+    for event in track.events.iter_mut().filter(|event| is_end_of_track(event)) {
+        event.absolute_time = event.absolute_time.max(last_tick);
+    }
+
+    let rank = |event: &MidiEvent| match event.event_type {
+        MidiEventType::NoteOff { .. } | MidiEventType::NoteOn { velocity: 0, .. } => 0,
+        MidiEventType::MetaEvent(MetaEventType::EndOfTrack) => 2,
+        _ => 1,
+    };
+    track.events.sort_by_key(|event| (event.absolute_time, rank(event)));
+
+    let mut previous = 0;
+    for event in track.events.iter_mut() {
+        event.delta_time = (event.absolute_time - previous).min(u32::MAX as u64) as u32;
+        previous = event.absolute_time;
+    }
+}
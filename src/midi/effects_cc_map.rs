@@ -0,0 +1,142 @@
+/**
+ * AWE Player - Effects Controller Map
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * CC91 and CC93 set how much of each channel reaches the reverb and chorus;
+ * the effects themselves are configured by the host. This map lets
+ * controllers without a fixed meaning drive the global effect parameters,
+ * so a song can open the room for its bridge or slow the chorus at the end:
+ * CC94 to chorus depth, CC95 to reverb room size, and so on.
+ *
+ * Each mapped controller scales 0-127 linearly between the value at 0 and
+ * the value at 127 (reversed if the first is larger), on any channel. A
+ * controller drives one parameter; several may drive the same one, and
+ * whichever moved last wins. Values set this way stay until the next
+ * controller or configure call - stopping or loading a song keeps them.
+ */
+
+use serde::Serialize;
+use super::constants::*;
+use super::effects_controller::{MIDI_CC_CHORUS_SEND, MIDI_CC_REVERB_SEND};
+
+/// A global effect parameter a controller can drive, with its range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum EffectsParameter {
+    ReverbRoomSize,     // 0.0 (small room) to 1.0 (large hall)
+    ReverbDamping,      // 0.0 (bright) to 1.0 (dark)
+    ReverbDiffusion,    // 0.0 (sparse) to 1.0 (dense)
+    ReverbReturn,       // Wet level, 0.0-1.0
+    ChorusRate,         // LFO Hz, 0.1-10.0
+    ChorusDepth,        // 0.0-1.0
+    ChorusFeedback,     // 0.0-0.8
+    ChorusSpread,       // Stereo spread, 0.0-1.0
+    ChorusReturn,       // Wet level, 0.0-1.0
+}
+
+impl EffectsParameter {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "reverbRoomSize" => Some(Self::ReverbRoomSize),
+            "reverbDamping" => Some(Self::ReverbDamping),
+            "reverbDiffusion" => Some(Self::ReverbDiffusion),
+            "reverbReturn" => Some(Self::ReverbReturn),
+            "chorusRate" => Some(Self::ChorusRate),
+            "chorusDepth" => Some(Self::ChorusDepth),
+            "chorusFeedback" => Some(Self::ChorusFeedback),
+            "chorusSpread" => Some(Self::ChorusSpread),
+            "chorusReturn" => Some(Self::ChorusReturn),
+            _ => None,
+        }
+    }
+
+    /// Lowest and highest value the effect accepts
+    pub fn range(self) -> (f32, f32) {
+        match self {
+            Self::ChorusRate => (0.1, 10.0),
+            Self::ChorusFeedback => (0.0, 0.8),
+            _ => (0.0, 1.0),
+        }
+    }
+}
+
+/// One controller's mapping (get_effects_cc_map_global)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct EffectsCcMapping {
+    pub controller: u8,
+    pub parameter: EffectsParameter,
+    pub min: f32,               // Value at controller 0
+    pub max: f32,               // Value at controller 127
+}
+
+impl EffectsCcMapping {
+    /// Parameter value for a controller value
+    pub fn value(&self, controller_value: u8) -> f32 {
+        self.min + (self.max - self.min) * (controller_value & 0x7F) as f32 / 127.0
+    }
+}
+
+/// Controller to effect parameter table, one slot per controller (no allocation on lookup)
+#[derive(Debug, Clone)]
+pub struct EffectsCcMap {
+    mappings: [Option<EffectsCcMapping>; 128],
+}
+
+impl Default for EffectsCcMap {
+    fn default() -> Self {
+        Self { mappings: [None; 128] }
+    }
+}
+
+impl EffectsCcMap {
+    /// Map `controller` to `parameter`, with the values at 0 and 127 clamped to its range
+    /// Refused for controllers the engine already interprets and for non-finite values
+    pub fn set(&mut self, controller: u8, parameter: EffectsParameter, min: f32, max: f32) -> bool {
+        if controller > 0x7F || is_fixed_controller(controller) || !min.is_finite() || !max.is_finite() {
+            return false;
+        }
+        let (low, high) = parameter.range();
+        self.mappings[controller as usize] = Some(EffectsCcMapping {
+            controller,
+            parameter,
+            min: min.clamp(low, high),
+            max: max.clamp(low, high),
+        });
+        true
+    }
+
+    pub fn remove(&mut self, controller: u8) -> bool {
+        match self.mappings.get_mut(controller as usize) {
+            Some(slot) => slot.take().is_some(),
+            None => false,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.mappings = [None; 128];
+    }
+
+    pub fn get(&self, controller: u8) -> Option<EffectsCcMapping> {
+        self.mappings.get(controller as usize).copied().flatten()
+    }
+
+    /// Every mapping, by controller number
+    pub fn mappings(&self) -> Vec<EffectsCcMapping> {
+        self.mappings.iter().flatten().copied().collect()
+    }
+}
+
+/// Controllers with a meaning of their own in the engine (bank select, data entry,
+/// mixer, sound controllers, sends, parameter numbers and channel mode messages)
+pub fn is_fixed_controller(controller: u8) -> bool {
+    matches!(controller,
+        MIDI_CC_BANK_SELECT_MSB | MIDI_CC_MODULATION | MIDI_CC_DATA_ENTRY_MSB | MIDI_CC_VOLUME
+        | MIDI_CC_PAN | MIDI_CC_EXPRESSION | MIDI_CC_BANK_SELECT_LSB | MIDI_CC_DATA_ENTRY_LSB
+        | MIDI_CC_SUSTAIN | MIDI_CC_HIGH_RES_VELOCITY | MIDI_CC_REVERB_SEND | MIDI_CC_CHORUS_SEND
+        | MIDI_CC_SOUND_CONTROLLER_1..=MIDI_CC_SOUND_CONTROLLER_10
+        | MIDI_CC_NRPN_LSB..=MIDI_CC_RPN_MSB
+        | MIDI_CC_ALL_SOUND_OFF..=0x7F)
+}
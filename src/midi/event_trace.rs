@@ -0,0 +1,222 @@
+/**
+ * AWE Player - MIDI Event Trace
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * "Why is this note silent?" used to be answered by the debug log. The
+ * trace answers it instead: the player keeps the last N MIDI events it
+ * handled, each with the sample it ran at, where it came from (live
+ * input, the sequencer, the controller chase after a seek or the song's
+ * setup region) and what
+ * the synth did with it - the voice a note got, or why it was dropped.
+ * Entries are plain values in a ring allocated up front, so recording
+ * costs nothing on the audio thread; text is only built when the trace
+ * is queried, filtered by channel and message type.
+ */
+
+use serde::Serialize;
+
+/// Entries kept when no capacity is given
+pub const DEFAULT_TRACE_CAPACITY: usize = 256;
+/// Largest accepted capacity
+pub const MAX_TRACE_CAPACITY: usize = 4096;
+
+/// Where a traced event came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum EventSource {
+    Live,       // Queued by the host or sent as raw MIDI bytes
+    Sequencer,  // Played from the loaded MIDI file
+    Chase,      // Replayed to rebuild channel state after a seek
+    Setup,      // The song's setup region, applied as playback starts
+}
+
+/// Why a note or program change had no effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropReason {
+    NoSoundFont,
+    NoPreset,
+    NoVoice,
+}
+
+/// What handling an event did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventAction {
+    VoiceAllocated(usize),
+    ZoneVoices(usize),      // Performance split/layer note started this many voices
+    Released,
+    PresetSelected(usize),
+    Applied,                // Controller, pressure, bend or transport message
+    Dropped(DropReason),
+    Ignored,                // Message type or controller the synth does not handle
+}
+
+impl EventAction {
+    /// Readable form for the trace report
+    pub fn describe(&self) -> String {
+        match self {
+            EventAction::VoiceAllocated(voice) => format!("voice {} allocated", voice),
+            EventAction::ZoneVoices(count) => format!("{} zone voices started", count),
+            EventAction::Released => "released".to_string(),
+            EventAction::PresetSelected(preset) => format!("preset {} selected", preset),
+            EventAction::Applied => "applied".to_string(),
+            EventAction::Dropped(DropReason::NoSoundFont) => "dropped: no soundfont".to_string(),
+            EventAction::Dropped(DropReason::NoPreset) => "dropped: no preset".to_string(),
+            EventAction::Dropped(DropReason::NoVoice) => "dropped: no free voice".to_string(),
+            EventAction::Ignored => "ignored".to_string(),
+        }
+    }
+}
+
+/// Message type name used in reports and filters
+pub fn message_type_name(status: u8) -> &'static str {
+    match status & 0xF0 {
+        0x80 => "noteOff",
+        0x90 => "noteOn",
+        0xA0 => "polyPressure",
+        0xB0 => "controlChange",
+        0xC0 => "programChange",
+        0xD0 => "channelPressure",
+        0xE0 => "pitchBend",
+        _ => "system",
+    }
+}
+
+/// One handled event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub sample: u64,
+    pub source: EventSource,
+    pub channel: u8,
+    pub status: u8,
+    pub data1: u8,
+    pub data2: u8,
+    pub action: EventAction,
+}
+
+/// Trace entry as reported (JSON)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct TraceEntryReport {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub sample: u64,
+    pub source: EventSource,
+    pub channel: u8,
+    pub message_type: &'static str,
+    pub data1: u8,
+    pub data2: u8,
+    pub action: String,
+}
+
+impl TraceEntry {
+    pub fn report(&self) -> TraceEntryReport {
+        TraceEntryReport {
+            sample: self.sample,
+            source: self.source,
+            channel: self.channel,
+            message_type: message_type_name(self.status),
+            data1: self.data1,
+            data2: self.data2,
+            action: self.action.describe(),
+        }
+    }
+}
+
+/// Ring of the most recent handled events
+#[derive(Debug, Clone)]
+pub struct EventTrace {
+    entries: Vec<TraceEntry>,
+    capacity: usize,
+    next: usize,            // Slot the next entry overwrites once full
+    enabled: bool,
+    overwritten: u64,       // Entries lost to wrap-around since the last clear
+}
+
+impl EventTrace {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.clamp(1, MAX_TRACE_CAPACITY);
+        Self {
+            entries: Vec::with_capacity(capacity),
+            capacity,
+            next: 0,
+            enabled: true,
+            overwritten: 0,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change how many entries are kept; clears the trace
+    pub fn set_capacity(&mut self, capacity: usize) {
+        let enabled = self.enabled;
+        *self = Self::new(capacity);
+        self.enabled = enabled;
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.next = 0;
+        self.overwritten = 0;
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn overwritten(&self) -> u64 {
+        self.overwritten
+    }
+
+    pub fn record(&mut self, entry: TraceEntry) {
+        if !self.enabled {
+            return;
+        }
+        if self.entries.len() < self.capacity {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.next] = entry;
+            self.overwritten += 1;
+        }
+        self.next = (self.next + 1) % self.capacity;
+    }
+
+    /// Entries oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &TraceEntry> {
+        let (newer, older) = if self.entries.len() < self.capacity {
+            (&self.entries[..], &self.entries[..0])
+        } else {
+            self.entries.split_at(self.next)
+        };
+        older.iter().chain(newer.iter())
+    }
+
+    /// Entries on `channel` (None = all) whose message type is in `types` (empty = all)
+    pub fn query(&self, channel: Option<u8>, types: &[&str]) -> Vec<TraceEntryReport> {
+        self.iter()
+            .filter(|entry| channel.is_none_or(|channel| entry.channel == channel))
+            .filter(|entry| types.is_empty() || types.contains(&message_type_name(entry.status)))
+            .map(TraceEntry::report)
+            .collect()
+    }
+}
+
+impl Default for EventTrace {
+    fn default() -> Self {
+        Self::new(DEFAULT_TRACE_CAPACITY)
+    }
+}
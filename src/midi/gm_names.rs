@@ -0,0 +1,156 @@
+/**
+ * AWE Player - General MIDI Names
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * The 128 GM Level 1 program names, the drum kit names of the GS kit
+ * map that SoundFont drum banks (bank 128) follow, and the GM percussion
+ * key map. The preset catalog lists the expected name next to each
+ * preset's own, so a bank whose presets are numbered one off (a common
+ * 0/1-based mistake) or placed in the wrong bank stands out.
+ */
+
+use serde::Serialize;
+use crate::midi::bank_select::SOUNDFONT_DRUM_BANK;
+
+/// GM Level 1 program names, program number order (0-based)
+pub const GM_PROGRAM_NAMES: [&str; 128] = [
+    // Piano
+    "Acoustic Grand Piano", "Bright Acoustic Piano", "Electric Grand Piano", "Honky-tonk Piano",
+    "Electric Piano 1", "Electric Piano 2", "Harpsichord", "Clavi",
+    // Chromatic Percussion
+    "Celesta", "Glockenspiel", "Music Box", "Vibraphone",
+    "Marimba", "Xylophone", "Tubular Bells", "Dulcimer",
+    // Organ
+    "Drawbar Organ", "Percussive Organ", "Rock Organ", "Church Organ",
+    "Reed Organ", "Accordion", "Harmonica", "Tango Accordion",
+    // Guitar
+    "Acoustic Guitar (nylon)", "Acoustic Guitar (steel)", "Electric Guitar (jazz)", "Electric Guitar (clean)",
+    "Electric Guitar (muted)", "Overdriven Guitar", "Distortion Guitar", "Guitar Harmonics",
+    // Bass
+    "Acoustic Bass", "Electric Bass (finger)", "Electric Bass (pick)", "Fretless Bass",
+    "Slap Bass 1", "Slap Bass 2", "Synth Bass 1", "Synth Bass 2",
+    // Strings
+    "Violin", "Viola", "Cello", "Contrabass",
+    "Tremolo Strings", "Pizzicato Strings", "Orchestral Harp", "Timpani",
+    // Ensemble
+    "String Ensemble 1", "String Ensemble 2", "SynthStrings 1", "SynthStrings 2",
+    "Choir Aahs", "Voice Oohs", "Synth Voice", "Orchestra Hit",
+    // Brass
+    "Trumpet", "Trombone", "Tuba", "Muted Trumpet",
+    "French Horn", "Brass Section", "SynthBrass 1", "SynthBrass 2",
+    // Reed
+    "Soprano Sax", "Alto Sax", "Tenor Sax", "Baritone Sax",
+    "Oboe", "English Horn", "Bassoon", "Clarinet",
+    // Pipe
+    "Piccolo", "Flute", "Recorder", "Pan Flute",
+    "Blown Bottle", "Shakuhachi", "Whistle", "Ocarina",
+    // Synth Lead
+    "Lead 1 (square)", "Lead 2 (sawtooth)", "Lead 3 (calliope)", "Lead 4 (chiff)",
+    "Lead 5 (charang)", "Lead 6 (voice)", "Lead 7 (fifths)", "Lead 8 (bass + lead)",
+    // Synth Pad
+    "Pad 1 (new age)", "Pad 2 (warm)", "Pad 3 (polysynth)", "Pad 4 (choir)",
+    "Pad 5 (bowed)", "Pad 6 (metallic)", "Pad 7 (halo)", "Pad 8 (sweep)",
+    // Synth Effects
+    "FX 1 (rain)", "FX 2 (soundtrack)", "FX 3 (crystal)", "FX 4 (atmosphere)",
+    "FX 5 (brightness)", "FX 6 (goblins)", "FX 7 (echoes)", "FX 8 (sci-fi)",
+    // Ethnic
+    "Sitar", "Banjo", "Shamisen", "Koto",
+    "Kalimba", "Bag pipe", "Fiddle", "Shanai",
+    // Percussive
+    "Tinkle Bell", "Agogo", "Steel Drums", "Woodblock",
+    "Taiko Drum", "Melodic Tom", "Synth Drum", "Reverse Cymbal",
+    // Sound Effects
+    "Guitar Fret Noise", "Breath Noise", "Seashore", "Bird Tweet",
+    "Telephone Ring", "Helicopter", "Applause", "Gunshot",
+];
+
+/// Drum kits by program number (GS kit map, as used by SoundFont bank 128)
+pub const GM_DRUM_KIT_NAMES: [(u8, &str); 9] = [
+    (0, "Standard Kit"),
+    (8, "Room Kit"),
+    (16, "Power Kit"),
+    (24, "Electronic Kit"),
+    (25, "TR-808 Kit"),
+    (32, "Jazz Kit"),
+    (40, "Brush Kit"),
+    (48, "Orchestra Kit"),
+    (56, "SFX Kit"),
+];
+
+/// First key of the GM percussion map
+pub const GM_DRUM_FIRST_KEY: u8 = 35;
+
+/// GM percussion key names, from GM_DRUM_FIRST_KEY (35) to key 81
+pub const GM_DRUM_NOTE_NAMES: [&str; 47] = [
+    "Acoustic Bass Drum", "Bass Drum 1", "Side Stick", "Acoustic Snare", "Hand Clap",
+    "Electric Snare", "Low Floor Tom", "Closed Hi-Hat", "High Floor Tom", "Pedal Hi-Hat",
+    "Low Tom", "Open Hi-Hat", "Low-Mid Tom", "Hi-Mid Tom", "Crash Cymbal 1",
+    "High Tom", "Ride Cymbal 1", "Chinese Cymbal", "Ride Bell", "Tambourine",
+    "Splash Cymbal", "Cowbell", "Crash Cymbal 2", "Vibraslap", "Ride Cymbal 2",
+    "Hi Bongo", "Low Bongo", "Mute Hi Conga", "Open Hi Conga", "Low Conga",
+    "High Timbale", "Low Timbale", "High Agogo", "Low Agogo", "Cabasa",
+    "Maracas", "Short Whistle", "Long Whistle", "Short Guiro", "Long Guiro",
+    "Claves", "Hi Wood Block", "Low Wood Block", "Mute Cuica", "Open Cuica",
+    "Mute Triangle", "Open Triangle",
+];
+
+/// GM name of a melodic program (None above 127)
+pub fn gm_program_name(program: u8) -> Option<&'static str> {
+    GM_PROGRAM_NAMES.get(program as usize).copied()
+}
+
+/// Name of the drum kit at `program` (None for programs outside the kit map)
+pub fn gm_drum_kit_name(program: u8) -> Option<&'static str> {
+    GM_DRUM_KIT_NAMES.iter().find(|(kit, _)| *kit == program).map(|(_, name)| *name)
+}
+
+/// GM percussion name of a key (None outside 35-81)
+pub fn gm_drum_note_name(key: u8) -> Option<&'static str> {
+    key.checked_sub(GM_DRUM_FIRST_KEY).and_then(|offset| GM_DRUM_NOTE_NAMES.get(offset as usize).copied())
+}
+
+/// The name a preset at `bank`/`program` is expected to have: a kit name in
+/// the drum bank, otherwise the GM program name (variation banks keep the
+/// GM instrument of their program)
+pub fn expected_gm_name(bank: u16, program: u8) -> Option<&'static str> {
+    if bank == SOUNDFONT_DRUM_BANK {
+        gm_drum_kit_name(program)
+    } else {
+        gm_program_name(program)
+    }
+}
+
+/// One drum kit of the kit map
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct GmDrumKit {
+    pub program: u8,
+    pub name: String,
+}
+
+/// Every GM program and drum kit name (get_gm_names)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct GmNames {
+    pub programs: Vec<String>,      // Indexed by program number
+    pub drum_kits: Vec<GmDrumKit>,
+}
+
+impl GmNames {
+    pub fn new() -> Self {
+        Self {
+            programs: GM_PROGRAM_NAMES.iter().map(|name| name.to_string()).collect(),
+            drum_kits: GM_DRUM_KIT_NAMES.iter()
+                .map(|&(program, name)| GmDrumKit { program, name: name.to_string() })
+                .collect(),
+        }
+    }
+}
+
+impl Default for GmNames {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,162 @@
+/**
+ * AWE Player - Playback Humanize
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Quantized MIDI files sound mechanical: every hit lands exactly on the
+ * grid at the velocity it was entered with. The humanizer sits between the
+ * sequencer and the synth and, per channel, scatters note-on velocities
+ * within a range and moves note-ons within a timing window, without
+ * touching the file.
+ *
+ * A note can only be moved later than the sequencer dispatches it, so while
+ * any channel has a timing window, all sequencer events are held back by
+ * the widest window and note-ons are then placed anywhere within that
+ * window either side. Channels keep their relative timing, and the whole
+ * sequence plays a few milliseconds late. A note-off moves with its note-on
+ * so note lengths survive, and a note-on never lands before a program
+ * change or controller sent earlier on its channel.
+ *
+ * Held events wait in storage reserved up front; if it fills, events go
+ * out on time instead.
+ */
+
+use serde::Serialize;
+use crate::MidiEvent;
+
+/// Widest timing window either side of the grid (ms)
+pub const MAX_TIMING_JITTER_MS: f32 = 50.0;
+/// Widest velocity range either side of the written velocity
+pub const MAX_VELOCITY_RANGE: u8 = 64;
+/// Events held back at once
+const MAX_PENDING_EVENTS: usize = 512;
+
+const DEFAULT_SEED: u32 = 0x6A09_E667;
+
+/// Humanize settings of one channel (get_playback_humanize_global)
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ChannelHumanize {
+    pub velocity_range: u8,     // Note-on velocity moves up to this far either way
+    pub timing_ms: f32,         // Note-ons move up to this far either way
+}
+
+/// Velocity and timing scatter for sequencer playback
+#[derive(Debug, Clone)]
+pub struct Humanizer {
+    sample_rate: f32,
+    channels: [ChannelHumanize; 16],
+    rng: u32,
+    pending: Vec<MidiEvent>,                // Held events, in timestamp order
+    note_shifts: [[i64; 128]; 16],          // Shift given to each sounding note's note-on (samples)
+    channel_floor: [u64; 16],               // Time of the last non-note event per channel
+}
+
+impl Humanizer {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate,
+            channels: [ChannelHumanize::default(); 16],
+            rng: DEFAULT_SEED,
+            pending: Vec::with_capacity(MAX_PENDING_EVENTS),
+            note_shifts: [[0; 128]; 16],
+            channel_floor: [0; 16],
+        }
+    }
+
+    /// Set the ranges of a channel, or all channels if `channel` is None (values are clamped)
+    pub fn set_channel(&mut self, channel: Option<u8>, velocity_range: u8, timing_ms: f32) -> bool {
+        let settings = ChannelHumanize {
+            velocity_range: velocity_range.min(MAX_VELOCITY_RANGE),
+            timing_ms: if timing_ms.is_finite() { timing_ms.clamp(0.0, MAX_TIMING_JITTER_MS) } else { 0.0 },
+        };
+        match channel {
+            Some(channel) => match self.channels.get_mut(channel as usize) {
+                Some(slot) => *slot = settings,
+                None => return false,
+            },
+            None => self.channels = [settings; 16],
+        }
+        true
+    }
+
+    pub fn channels(&self) -> &[ChannelHumanize; 16] {
+        &self.channels
+    }
+
+    /// Restart the random sequence (0 keeps the current one), so a render with the same
+    /// seed and file comes out the same
+    pub fn set_seed(&mut self, seed: u32) {
+        if seed != 0 {
+            self.rng = seed;
+        }
+    }
+
+    /// How long every sequencer event is held back (samples)
+    pub fn latency_samples(&self) -> u64 {
+        let widest = self.channels.iter().map(|channel| channel.timing_ms).fold(0.0, f32::max);
+        (widest * 0.001 * self.sample_rate) as u64
+    }
+
+    /// Humanize a sequencer event dispatched at `now`; returns it if it goes out now,
+    /// otherwise it is held until next_due reaches its time
+    pub fn schedule(&mut self, mut event: MidiEvent, now: u64) -> Option<MidiEvent> {
+        let channel = (event.channel & 0x0F) as usize;
+        let note = (event.data1 & 0x7F) as usize;
+        let settings = self.channels[channel];
+        let latency = self.latency_samples();
+        let due = now + latency;
+        let is_note_on = event.message_type & 0xF0 == 0x90 && event.data2 > 0;
+        let is_note_off = event.message_type & 0xF0 == 0x80 || (event.message_type & 0xF0 == 0x90 && event.data2 == 0);
+
+        event.timestamp = if is_note_on {
+            if settings.velocity_range > 0 {
+                let spread = settings.velocity_range as f32 * 2.0 * self.uniform();
+                event.data2 = (event.data2 as f32 + spread).round().clamp(1.0, 127.0) as u8;
+            }
+            let window = settings.timing_ms * 0.001 * self.sample_rate;
+            let jittered = (due as i64 + (window * 2.0 * self.uniform()) as i64).max(self.channel_floor[channel] as i64) as u64;
+            self.note_shifts[channel][note] = jittered as i64 - due as i64;
+            jittered
+        } else if is_note_off {
+            (due as i64 + std::mem::take(&mut self.note_shifts[channel][note])).max(0) as u64
+        } else {
+            self.channel_floor[channel] = due;
+            due
+        };
+
+        if event.timestamp <= now || self.pending.len() >= MAX_PENDING_EVENTS {
+            event.timestamp = event.timestamp.min(now);
+            return Some(event);
+        }
+        let position = self.pending.partition_point(|held| held.timestamp <= event.timestamp);
+        self.pending.insert(position, event);
+        None
+    }
+
+    /// Next held event whose time has come (at or before `now`), in timestamp order
+    pub fn next_due(&mut self, now: u64) -> Option<MidiEvent> {
+        self.pending.first()
+            .is_some_and(|held| held.timestamp <= now)
+            .then(|| self.pending.remove(0))
+    }
+
+    /// Drop held events (stop and seek release every note anyway)
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.note_shifts = [[0; 128]; 16];
+        self.channel_floor = [0; 16];
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Uniform value in -0.5..0.5 (xorshift32)
+    fn uniform(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        self.rng as f32 / u32::MAX as f32 - 0.5
+    }
+}
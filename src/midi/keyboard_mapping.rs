@@ -0,0 +1,303 @@
+/**
+ * AWE Player - Computer Keyboard to MIDI Mapping
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Maps computer keyboard keys onto MIDI notes so web demos can be played
+ * without a MIDI controller. Accepts either `KeyboardEvent.key` values
+ * ("z", "S") or `KeyboardEvent.code` values ("KeyZ", "Comma").
+ *
+ * Layouts:
+ * - Tracker: two rows (Z-row = base octave, Q-row = base octave + 1)
+ * - HomeRow: single row starting at A (A W S E D F T G Y H U J K O L P ; ')
+ */
+
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+use crate::MidiEvent;
+use super::constants::*;
+use super::event_trace::EventSource;
+
+/// Lowest/highest octave shift allowed (in octaves from the layout base)
+pub const KEYBOARD_OCTAVE_SHIFT_MIN: i8 = -4;
+pub const KEYBOARD_OCTAVE_SHIFT_MAX: i8 = 4;
+
+/// Default velocity for keyboard-triggered notes
+pub const KEYBOARD_DEFAULT_VELOCITY: u8 = 100;
+
+/// Tracker layout: lower row plays C3..E4, upper row plays C4..E5
+const TRACKER_LAYOUT: &[(&str, u8)] = &[
+    ("z", 0), ("s", 1), ("x", 2), ("d", 3), ("c", 4), ("v", 5), ("g", 6),
+    ("b", 7), ("h", 8), ("n", 9), ("j", 10), ("m", 11), (",", 12), ("l", 13),
+    (".", 14), (";", 15), ("/", 16),
+    ("q", 12), ("2", 13), ("w", 14), ("3", 15), ("e", 16), ("r", 17), ("5", 18),
+    ("t", 19), ("6", 20), ("y", 21), ("7", 22), ("u", 23), ("i", 24), ("9", 25),
+    ("o", 26), ("0", 27), ("p", 28),
+];
+
+/// Home row layout: white keys on A-row, black keys on Q-row
+const HOME_ROW_LAYOUT: &[(&str, u8)] = &[
+    ("a", 0), ("w", 1), ("s", 2), ("e", 3), ("d", 4), ("f", 5), ("t", 6),
+    ("g", 7), ("y", 8), ("h", 9), ("u", 10), ("j", 11), ("k", 12), ("o", 13),
+    ("l", 14), ("p", 15), (";", 16), ("'", 17),
+];
+
+/// Built-in keyboard layouts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyboardLayout {
+    Tracker,
+    HomeRow,
+}
+
+impl KeyboardLayout {
+    /// Parse layout name ("tracker" / "home_row"), defaults to Tracker
+    pub fn from_name(name: &str) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "home_row" | "homerow" | "home-row" => KeyboardLayout::HomeRow,
+            _ => KeyboardLayout::Tracker,
+        }
+    }
+
+    fn key_table(&self) -> &'static [(&'static str, u8)] {
+        match self {
+            KeyboardLayout::Tracker => TRACKER_LAYOUT,
+            KeyboardLayout::HomeRow => HOME_ROW_LAYOUT,
+        }
+    }
+}
+
+/// Computer keyboard to MIDI note mapper
+///
+/// Held keys remember the note they started, so changing octave or
+/// layout while a key is down still produces the matching Note Off.
+#[derive(Debug, Clone)]
+pub struct KeyboardMapper {
+    layout: KeyboardLayout,
+    key_map: HashMap<String, u8>,    // Normalized key -> semitone offset from base note
+    base_note: u8,                   // Note played by the first key of the layout
+    octave_shift: i8,
+    velocity: u8,
+    channel: u8,
+    held_keys: HashMap<String, u8>,  // Normalized key -> sounding MIDI note
+}
+
+impl KeyboardMapper {
+    /// Create mapper for layout with base note C3 (48)
+    pub fn new(layout: KeyboardLayout) -> Self {
+        let mut mapper = Self {
+            layout,
+            key_map: HashMap::new(),
+            base_note: 48,
+            octave_shift: 0,
+            velocity: KEYBOARD_DEFAULT_VELOCITY,
+            channel: 0,
+            held_keys: HashMap::new(),
+        };
+        mapper.set_layout(layout);
+        mapper
+    }
+
+    /// Replace the key map with a built-in layout (custom mappings are cleared)
+    pub fn set_layout(&mut self, layout: KeyboardLayout) {
+        self.layout = layout;
+        self.key_map = layout.key_table()
+            .iter()
+            .map(|&(key, offset)| (key.to_string(), offset))
+            .collect();
+    }
+
+    /// Map (or remap) a single key to a semitone offset from the base note
+    pub fn map_key(&mut self, key: &str, semitone_offset: u8) {
+        self.key_map.insert(Self::normalize_key(key), semitone_offset);
+    }
+
+    /// Remove a key from the map
+    pub fn unmap_key(&mut self, key: &str) {
+        self.key_map.remove(&Self::normalize_key(key));
+    }
+
+    pub fn set_base_note(&mut self, note: u8) {
+        self.base_note = note.min(MIDI_NOTE_MAX);
+    }
+
+    pub fn set_octave_shift(&mut self, shift: i8) {
+        self.octave_shift = shift.clamp(KEYBOARD_OCTAVE_SHIFT_MIN, KEYBOARD_OCTAVE_SHIFT_MAX);
+    }
+
+    pub fn octave_up(&mut self) {
+        self.set_octave_shift(self.octave_shift.saturating_add(1));
+    }
+
+    pub fn octave_down(&mut self) {
+        self.set_octave_shift(self.octave_shift.saturating_sub(1));
+    }
+
+    /// Set note velocity (1-127; 0 would be interpreted as Note Off)
+    pub fn set_velocity(&mut self, velocity: u8) {
+        self.velocity = velocity.clamp(1, MIDI_VELOCITY_MAX);
+    }
+
+    pub fn set_channel(&mut self, channel: u8) {
+        self.channel = channel.min(MIDI_CHANNEL_COUNT - 1);
+    }
+
+    pub fn layout(&self) -> KeyboardLayout { self.layout }
+    pub fn octave_shift(&self) -> i8 { self.octave_shift }
+    pub fn velocity(&self) -> u8 { self.velocity }
+    pub fn channel(&self) -> u8 { self.channel }
+
+    /// MIDI note a key would play with the current octave shift
+    pub fn note_for_key(&self, key: &str) -> Option<u8> {
+        let offset = *self.key_map.get(&Self::normalize_key(key))?;
+        let note = self.base_note as i32 + offset as i32 + self.octave_shift as i32 * 12;
+        if (MIDI_NOTE_MIN as i32..=MIDI_NOTE_MAX as i32).contains(&note) {
+            Some(note as u8)
+        } else {
+            None
+        }
+    }
+
+    /// Handle key press - returns Note On, or None for unmapped/repeated keys
+    pub fn key_down(&mut self, key: &str, timestamp: u64) -> Option<MidiEvent> {
+        let normalized = Self::normalize_key(key);
+        if self.held_keys.contains_key(&normalized) {
+            return None; // Auto-repeat
+        }
+
+        let note = self.note_for_key(&normalized)?;
+        self.held_keys.insert(normalized, note);
+        Some(MidiEvent::new(timestamp, self.channel, MIDI_EVENT_NOTE_ON << 4, note, self.velocity))
+    }
+
+    /// Handle key release - returns Note Off for the note the key started
+    pub fn key_up(&mut self, key: &str, timestamp: u64) -> Option<MidiEvent> {
+        let note = self.held_keys.remove(&Self::normalize_key(key))?;
+        Some(MidiEvent::new(timestamp, self.channel, MIDI_EVENT_NOTE_OFF << 4, note, 0))
+    }
+
+    /// Release every held key (e.g. on window blur)
+    pub fn release_all(&mut self, timestamp: u64) -> Vec<MidiEvent> {
+        let channel = self.channel;
+        self.held_keys
+            .drain()
+            .map(|(_, note)| MidiEvent::new(timestamp, channel, MIDI_EVENT_NOTE_OFF << 4, note, 0))
+            .collect()
+    }
+
+    pub fn held_key_count(&self) -> usize {
+        self.held_keys.len()
+    }
+
+    /// Normalize `KeyboardEvent.key` / `KeyboardEvent.code` to a lowercase key
+    fn normalize_key(key: &str) -> String {
+        if let Some(letter) = key.strip_prefix("Key").filter(|rest| rest.len() == 1) {
+            return letter.to_ascii_lowercase();
+        }
+        if let Some(digit) = key.strip_prefix("Digit").filter(|rest| rest.len() == 1) {
+            return digit.to_string();
+        }
+        match key {
+            "Comma" => ",".to_string(),
+            "Period" => ".".to_string(),
+            "Semicolon" => ";".to_string(),
+            "Slash" => "/".to_string(),
+            "Quote" => "'".to_string(),
+            // Shifted characters on a US layout resolve to their unshifted key
+            "<" => ",".to_string(),
+            ">" => ".".to_string(),
+            ":" => ";".to_string(),
+            "?" => "/".to_string(),
+            "\"" => "'".to_string(),
+            _ => key.to_ascii_lowercase(),
+        }
+    }
+}
+
+impl Default for KeyboardMapper {
+    fn default() -> Self {
+        Self::new(KeyboardLayout::Tracker)
+    }
+}
+
+// ===== WASM EXPORTS =====
+
+/// Global keyboard mapper for WASM exports
+static mut GLOBAL_KEYBOARD_MAPPER: Option<KeyboardMapper> = None;
+
+#[allow(static_mut_refs)]
+fn with_keyboard_mapper<T>(f: impl FnOnce(&mut KeyboardMapper) -> T) -> T {
+    unsafe {
+        f(GLOBAL_KEYBOARD_MAPPER.get_or_insert_with(KeyboardMapper::default))
+    }
+}
+
+fn queue_keyboard_event(event: MidiEvent) {
+    if let Some(queue) = crate::MIDI_EVENT_QUEUE.get() {
+        if let Ok(mut queue) = queue.lock() {
+            if queue.len() >= 1000 {
+                queue.pop_front();
+            }
+            queue.push_back((event, EventSource::Live));
+        }
+    }
+}
+
+/// Select keyboard layout ("tracker" or "home_row")
+#[wasm_bindgen]
+pub fn set_keyboard_layout(layout: &str) {
+    with_keyboard_mapper(|mapper| mapper.set_layout(KeyboardLayout::from_name(layout)));
+}
+
+/// Set keyboard octave shift (-4..+4), returns applied value
+#[wasm_bindgen]
+pub fn set_keyboard_octave_shift(shift: i8) -> i8 {
+    with_keyboard_mapper(|mapper| {
+        mapper.set_octave_shift(shift);
+        mapper.octave_shift()
+    })
+}
+
+/// Set keyboard note velocity (1-127)
+#[wasm_bindgen]
+pub fn set_keyboard_velocity(velocity: u8) {
+    with_keyboard_mapper(|mapper| mapper.set_velocity(velocity));
+}
+
+/// Set MIDI channel used for keyboard notes (0-15)
+#[wasm_bindgen]
+pub fn set_keyboard_channel(channel: u8) {
+    with_keyboard_mapper(|mapper| mapper.set_channel(channel));
+}
+
+/// Handle key press: queues Note On and returns the note (255 if key is unmapped)
+#[wasm_bindgen]
+pub fn keyboard_key_down(key: &str) -> u8 {
+    match with_keyboard_mapper(|mapper| mapper.key_down(key, 0)) {
+        Some(event) => {
+            queue_keyboard_event(event);
+            event.data1
+        }
+        None => 255,
+    }
+}
+
+/// Handle key release: queues Note Off and returns the note (255 if nothing was held)
+#[wasm_bindgen]
+pub fn keyboard_key_up(key: &str) -> u8 {
+    match with_keyboard_mapper(|mapper| mapper.key_up(key, 0)) {
+        Some(event) => {
+            queue_keyboard_event(event);
+            event.data1
+        }
+        None => 255,
+    }
+}
+
+/// Release all held keys, returns number of Note Offs queued
+#[wasm_bindgen]
+pub fn keyboard_release_all() -> u32 {
+    let events = with_keyboard_mapper(|mapper| mapper.release_all(0));
+    for event in &events {
+        queue_keyboard_event(*event);
+    }
+    events.len() as u32
+}
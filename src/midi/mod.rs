@@ -2,5 +2,21 @@ pub mod constants;
 pub mod message;
 pub mod parser;
 pub mod sequencer;
+pub mod edit; // Insert/delete/move/quantize on loaded MIDI tracks
+pub mod chase; // Program/controller state reconstruction when seeking
+pub mod setup; // Song setup region applied at once before playback starts
+pub mod tempo_ramp; // Tempo multiplier glide and scheduled accel./rit. ramps
+pub mod event_trace; // Recent handled events with their source and outcome
 pub mod test_sequences;
-pub mod effects_controller; // Phase 15C - MIDI effects control (CC 91/93)
\ No newline at end of file
+pub mod effects_controller; // Phase 15C - MIDI effects control (CC 91/93)
+pub mod effects_cc_map; // Host-mapped controllers driving global reverb/chorus parameters
+pub mod keyboard_mapping; // Computer keyboard to MIDI note mapping for web demos
+pub mod bank_select; // GM/GM2 system modes and bank select resolution
+pub mod gm_names; // Standard GM program and drum kit names
+pub mod transport; // MMC and Song Position Pointer to sequencer transport
+pub mod recording; // Live input overdubs with punch-in/out and pre-roll
+pub mod take_export; // Takes to SMF with export-time quantize, swing and legato
+pub mod writer; // Standard MIDI File output
+pub mod humanize; // Velocity and timing scatter on sequencer playback
+pub mod automation; // Host parameter curves evaluated per sample at song position
+pub mod theory; // Chords, scales and note names for test sequences and demos
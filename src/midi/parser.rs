@@ -1,4 +1,5 @@
 use crate::error::AweError;
+use crate::events::LoadStage;
 use crate::midi::constants::*;
 
 /// Standard MIDI file structure
@@ -17,6 +18,7 @@ pub struct MidiFile {
 }
 
 /// Individual MIDI track
+#[derive(Clone)]
 pub struct MidiTrack {
     /// Track name (from track name meta event)
     pub name: Option<String>,
@@ -26,6 +28,7 @@ pub struct MidiTrack {
 }
 
 /// MIDI event with timing information
+#[derive(Clone)]
 pub struct MidiEvent {
     /// Delta time in ticks since last event
     pub delta_time: u32,
@@ -38,6 +41,7 @@ pub struct MidiEvent {
 }
 
 /// Types of MIDI events
+#[derive(Clone)]
 pub enum MidiEventType {
     /// Note Off event
     NoteOff { channel: u8, note: u8, velocity: u8 },
@@ -62,6 +66,7 @@ pub enum MidiEventType {
 }
 
 /// Meta event types
+#[derive(Clone)]
 pub enum MetaEventType {
     /// Set tempo (microseconds per quarter note)
     SetTempo { microseconds_per_quarter: u32 },
@@ -79,8 +84,14 @@ pub enum MetaEventType {
 impl MidiFile {
     /// Parse a MIDI file from bytes
     pub fn parse(data: &[u8]) -> Result<MidiFile, AweError> {
+        Self::parse_with_progress(data, &mut |_, _| {})
+    }
+
+    /// Parse, calling `progress(stage, fraction)` after the header and after each track
+    /// (`fraction` is the share of the file consumed, 0.0-1.0)
+    pub fn parse_with_progress(data: &[u8], progress: &mut dyn FnMut(LoadStage, f32)) -> Result<MidiFile, AweError> {
         let mut parser = MidiParser::new(data);
-        parser.parse_file()
+        parser.parse_file(progress)
     }
 }
 
@@ -95,10 +106,15 @@ impl<'a> MidiParser<'a> {
         Self { data, position: 0 }
     }
 
+    fn fraction_consumed(&self) -> f32 {
+        (self.position as f32 / self.data.len().max(1) as f32).min(1.0)
+    }
+
     /// Parse the complete MIDI file
-    fn parse_file(&mut self) -> Result<MidiFile, AweError> {
+    fn parse_file(&mut self, progress: &mut dyn FnMut(LoadStage, f32)) -> Result<MidiFile, AweError> {
         // Parse MThd header chunk
         let (format, track_count, division) = self.parse_header()?;
+        progress(LoadStage::Container, self.fraction_consumed());
         
         crate::log(&format!("MIDI Header: format={}, tracks={}, division={}", 
             format, track_count, division));
@@ -109,6 +125,7 @@ impl<'a> MidiParser<'a> {
             crate::log(&format!("Parsing track {}...", i));
             let track = self.parse_track()?;
             tracks.push(track);
+            progress(LoadStage::Tracks, self.fraction_consumed());
         }
         
         Ok(MidiFile {
@@ -241,8 +258,10 @@ impl<'a> MidiParser<'a> {
                 return Err(AweError::InvalidMidiFile);
             }
         } else {
-            // This is a status byte
-            *running_status = Some(status_byte);
+            // This is a status byte; SysEx and meta events leave running status alone
+            if status_byte < MIDI_STATUS_SYSEX_START {
+                *running_status = Some(status_byte);
+            }
             status_byte
         };
         
@@ -281,8 +300,10 @@ impl<'a> MidiParser<'a> {
                 // System event - check if it's a meta event (0xFF)
                 if actual_status == MIDI_STATUS_META_EVENT {
                     self.parse_meta_event()
+                } else if actual_status == MIDI_STATUS_SYSEX_START || actual_status == MIDI_STATUS_SYSEX_END {
+                    self.parse_sysex_event(actual_status)
                 } else {
-                    // Other system events (SysEx, etc.) - skip for now
+                    // Other system events never appear in a file - skip for now
                     crate::log(&format!("Skipping system event: 0x{:02X}", actual_status));
                     Ok(MidiEventType::MetaEvent(MetaEventType::EndOfTrack))
                 }
@@ -310,6 +331,23 @@ impl<'a> MidiParser<'a> {
         }
     }
 
+    /// Parse a SysEx event: F0 <length> <data> is kept with its F0 so it reads like the
+    /// message on the wire; an F7 <length> <bytes> escape is kept as given
+    fn parse_sysex_event(&mut self, status: u8) -> Result<MidiEventType, AweError> {
+        let length = self.read_vlq()? as usize;
+        if length > self.data.len() - self.position {
+            crate::log(&format!("ERROR: SysEx length {} runs past the end of the file", length));
+            return Err(AweError::InvalidMidiFile);
+        }
+        let mut data = Vec::with_capacity(length + 1);
+        if status == MIDI_STATUS_SYSEX_START {
+            data.push(status);
+        }
+        data.extend_from_slice(&self.data[self.position..self.position + length]);
+        self.position += length;
+        Ok(MidiEventType::SysEx { data })
+    }
+
     /// Parse a meta event (0xFF events)
     fn parse_meta_event(&mut self) -> Result<MidiEventType, AweError> {
         let meta_type = self.read_u8()?;
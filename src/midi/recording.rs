@@ -0,0 +1,384 @@
+/**
+ * AWE Player - Sequence Recording
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Overdubs live input into a track of the loaded sequence. Recording runs
+ * with the sequencer: events are stamped with the tick playback has reached
+ * when they arrive (block resolution) and merged into the track when
+ * recording ends. A punch region limits what is kept to a span of the song;
+ * pre-roll starts playback a number of bars before punch-in so the player
+ * hears the lead-in before the first captured note. Without a punch-in,
+ * recording starts with playback; without a punch-out it runs until stop
+ * or the end of the song.
+ *
+ * Storage is reserved when recording is armed; the audio path only pushes
+ * into spare capacity, and events past MAX_RECORDED_EVENTS are counted as
+ * dropped rather than grown into. A pass can end in the audio path (end of
+ * song, fade-out stop); if the sequence refuses it there, the failure is
+ * kept here and counted, and the control thread reports it.
+ *
+ * In take mode a pass is not merged: it becomes a track of its own (a take)
+ * next to the song's tracks. Takes of the same target track and punch
+ * region form a group in which one take is selected and plays; the others
+ * are muted. Committing a take merges it into its target and removes the
+ * whole group.
+ */
+
+use serde::Serialize;
+use super::parser::{MidiEventType, MidiTrack};
+
+/// A channel message as received (status with channel, data1, data2) at a tick
+pub type RecordedMessage = (u64, [u8; 3]);
+
+/// Events kept per recording pass
+pub const MAX_RECORDED_EVENTS: usize = 16384;
+/// Longest pre-roll
+pub const MAX_PRE_ROLL_BARS: u32 = 16;
+
+/// Where a recording pass is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum RecordingPhase {
+    Idle,       // Not recording
+    PreRoll,    // Playing toward punch-in, nothing kept yet
+    Recording,  // Inside the punch region
+}
+
+/// Recording settings and progress (get_recording_status_global)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct RecordingStatus {
+    pub phase: RecordingPhase,
+    pub track: usize,
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub punch_in: Option<u64>,
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub punch_out: Option<u64>,
+    pub pre_roll_bars: u32,
+    pub events_captured: usize,
+    pub events_dropped: usize,
+    pub passes_failed: usize,   // Finished passes the sequence refused
+}
+
+/// A finished pass the sequence refused, waiting to be reported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordingFailure {
+    NotMerged { track: usize, error: String },
+    TakeNotStored { error: String },
+}
+
+impl std::fmt::Display for RecordingFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RecordingFailure::NotMerged { track, error } => write!(f, "Recording could not be merged into track {}: {}", track, error),
+            RecordingFailure::TakeNotStored { error } => write!(f, "Take could not be stored: {}", error),
+        }
+    }
+}
+
+/// A recorded pass kept as a track of its own (get_takes_global)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Take {
+    pub id: u32,
+    pub track: usize,           // Track holding the take
+    pub target: usize,          // Track it merges into when committed
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub punch_in: Option<u64>,
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub punch_out: Option<u64>,
+    pub pre_roll_bars: u32,
+    pub selected: bool,         // The take of its group that plays
+}
+
+impl Take {
+    fn same_group(&self, other: &Take) -> bool {
+        (self.target, self.punch_in, self.punch_out) == (other.target, other.punch_in, other.punch_out)
+    }
+}
+
+/// Note, controller and program messages of a take track, for merging into its target
+pub fn track_messages(track: &MidiTrack) -> Vec<RecordedMessage> {
+    track.events.iter()
+        .filter_map(|event| {
+            let message = match event.event_type {
+                MidiEventType::NoteOff { channel, note, velocity } => [0x80 | channel, note, velocity],
+                MidiEventType::NoteOn { channel, note, velocity } => [0x90 | channel, note, velocity],
+                MidiEventType::ControlChange { channel, controller, value } => [0xB0 | channel, controller, value],
+                MidiEventType::ProgramChange { channel, program } => [0xC0 | channel, program, 0],
+                _ => return None,
+            };
+            Some((event.absolute_time, message))
+        })
+        .collect()
+}
+
+/// Punch region, pre-roll and the events of the pass in progress
+#[derive(Debug, Clone)]
+pub struct SequenceRecorder {
+    punch_in: Option<u64>,      // Tick recording starts at
+    punch_out: Option<u64>,     // Tick recording ends at
+    pre_roll_bars: u32,
+    phase: RecordingPhase,
+    track: usize,               // Track the pass is merged into
+    events: Vec<RecordedMessage>,
+    dropped: usize,
+    take_mode: bool,            // Keep passes as takes instead of merging them
+    takes: Vec<Take>,
+    next_take_id: u32,
+    failure: Option<RecordingFailure>,  // Latest refused pass, until reported
+    passes_failed: usize,
+}
+
+impl SequenceRecorder {
+    pub fn new() -> Self {
+        Self {
+            punch_in: None,
+            punch_out: None,
+            pre_roll_bars: 0,
+            phase: RecordingPhase::Idle,
+            track: 0,
+            events: Vec::new(),
+            dropped: 0,
+            take_mode: false,
+            takes: Vec::new(),
+            next_take_id: 1,
+            failure: None,
+            passes_failed: 0,
+        }
+    }
+
+    /// Limit recording to punch_in..punch_out (ticks; None leaves that side open)
+    /// False (and unchanged) if the region is empty
+    pub fn set_punch_region(&mut self, punch_in: Option<u64>, punch_out: Option<u64>) -> bool {
+        if let (Some(start), Some(end)) = (punch_in, punch_out) {
+            if end <= start {
+                return false;
+            }
+        }
+        self.punch_in = punch_in;
+        self.punch_out = punch_out;
+        true
+    }
+
+    /// Bars played before punch-in (clamped to MAX_PRE_ROLL_BARS; needs a punch-in)
+    pub fn set_pre_roll_bars(&mut self, bars: u32) {
+        self.pre_roll_bars = bars.min(MAX_PRE_ROLL_BARS);
+    }
+
+    /// Tick playback should start from for a pass: pre-roll before punch-in, None to start
+    /// where playback is
+    pub fn start_tick(&self, ticks_per_bar: u64) -> Option<u64> {
+        self.punch_in.map(|tick| tick.saturating_sub(self.pre_roll_bars as u64 * ticks_per_bar))
+    }
+
+    /// Begin a pass into `track`, dropping anything captured by an unfinished one
+    pub fn arm(&mut self, track: usize) {
+        self.track = track;
+        self.events = Vec::with_capacity(MAX_RECORDED_EVENTS);
+        self.dropped = 0;
+        self.phase = RecordingPhase::PreRoll;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.phase != RecordingPhase::Idle
+    }
+
+    pub fn phase(&self) -> RecordingPhase {
+        self.phase
+    }
+
+    pub fn track(&self) -> usize {
+        self.track
+    }
+
+    /// Follow playback to `tick`; returns the tick the pass ends at once punch-out is reached
+    pub fn advance(&mut self, tick: u64) -> Option<u64> {
+        if self.phase == RecordingPhase::PreRoll && self.punch_in.is_none_or(|start| tick >= start) {
+            self.phase = RecordingPhase::Recording;
+        }
+        match self.punch_out {
+            Some(end) if self.phase == RecordingPhase::Recording && tick >= end => Some(end),
+            _ => None,
+        }
+    }
+
+    /// Keep a live note, controller or program message heard at `tick`
+    /// (ignored outside the punch region)
+    pub fn capture(&mut self, tick: u64, message: [u8; 3]) {
+        if self.phase != RecordingPhase::Recording || self.punch_out.is_some_and(|end| tick >= end) {
+            return;
+        }
+        if self.events.len() < self.events.capacity() {
+            let tick = self.punch_in.map_or(tick, |start| tick.max(start));
+            self.events.push((tick, message));
+        } else {
+            self.dropped += 1;
+        }
+    }
+
+    /// End the pass at `end_tick`: notes still held are released there, and releases of
+    /// notes struck before punch-in are left out (they would cut the song's own notes)
+    /// Returns the pass's events, ready to merge into the track
+    pub fn finish(&mut self, end_tick: u64) -> Vec<RecordedMessage> {
+        self.phase = RecordingPhase::Idle;
+        let captured = std::mem::take(&mut self.events);
+        let mut messages = Vec::with_capacity(captured.len());
+        let mut held: Vec<(u8, u8, u64)> = Vec::new(); // status channel, note, note-on tick
+        // Releases land at least a tick after their note-on (same-tick note-offs sort first)
+        let release = |channel: u8, note: u8, tick: u64, on_tick: u64| (tick.max(on_tick + 1), [0x80 | channel, note, 0]);
+        for (tick, message) in captured {
+            let [status, data1, data2] = message;
+            let channel = status & 0x0F;
+            match status & 0xF0 {
+                0x90 if data2 > 0 => {
+                    held.push((channel, data1, tick));
+                    messages.push((tick, message));
+                }
+                0x80 | 0x90 => {
+                    if let Some(position) = held.iter().position(|&(c, n, _)| (c, n) == (channel, data1)) {
+                        let (_, _, on_tick) = held.remove(position);
+                        messages.push(release(channel, data1, tick, on_tick));
+                    }
+                }
+                _ => messages.push((tick, message)),
+            }
+        }
+        messages.extend(held.into_iter().map(|(channel, note, on_tick)| release(channel, note, end_tick, on_tick)));
+        messages
+    }
+
+    /// Keep later passes as takes (true) or merge them straight into their track
+    pub fn set_take_mode(&mut self, enabled: bool) {
+        self.take_mode = enabled;
+    }
+
+    pub fn take_mode(&self) -> bool {
+        self.take_mode
+    }
+
+    pub fn takes(&self) -> &[Take] {
+        &self.takes
+    }
+
+    pub fn take(&self, id: u32) -> Option<&Take> {
+        self.takes.iter().find(|take| take.id == id)
+    }
+
+    pub fn is_take_track(&self, track: usize) -> bool {
+        self.takes.iter().any(|take| take.track == track)
+    }
+
+    /// Track for a finished pass of `messages` ending at `end_tick` (named after its take)
+    pub fn take_track(&self, messages: &[RecordedMessage], end_tick: u64) -> MidiTrack {
+        let mut track = MidiTrack { name: Some(format!("Take {}", self.next_take_id)), events: Vec::new() };
+        track.events.push(super::parser::MidiEvent {
+            delta_time: 0,
+            absolute_time: end_tick,
+            event_type: MidiEventType::MetaEvent(super::parser::MetaEventType::EndOfTrack),
+        });
+        // Messages come from capture(), which only keeps what InsertMessages accepts
+        let _ = super::edit::apply(&mut track, &super::edit::TrackEdit::InsertMessages { messages: messages.to_vec() });
+        track
+    }
+
+    /// Register the pass just finished, stored at `track`, as the selected take of its group;
+    /// returns the new take's id
+    pub fn add_take(&mut self, track: usize) -> u32 {
+        let take = Take {
+            id: self.next_take_id,
+            track,
+            target: self.track,
+            punch_in: self.punch_in,
+            punch_out: self.punch_out,
+            pre_roll_bars: self.pre_roll_bars,
+            selected: true,
+        };
+        self.next_take_id += 1;
+        for other in self.takes.iter_mut().filter(|other| other.same_group(&take)) {
+            other.selected = false;
+        }
+        let id = take.id;
+        self.takes.push(take);
+        id
+    }
+
+    /// Make `id` the take of its group that plays (false if there is no such take)
+    pub fn select_take(&mut self, id: u32) -> bool {
+        let Some(chosen) = self.take(id).cloned() else {
+            return false;
+        };
+        for take in self.takes.iter_mut().filter(|take| take.same_group(&chosen)) {
+            take.selected = take.id == id;
+        }
+        true
+    }
+
+    /// Takes to remove with `id`: itself, or its whole group when `group` is set
+    pub fn takes_to_remove(&self, id: u32, group: bool) -> Vec<Take> {
+        let Some(chosen) = self.take(id) else {
+            return Vec::new();
+        };
+        self.takes.iter()
+            .filter(|take| if group { take.same_group(chosen) } else { take.id == id })
+            .cloned()
+            .collect()
+    }
+
+    /// Forget a take whose track was removed; later take tracks move down one index
+    pub fn remove_take(&mut self, id: u32) {
+        let Some(position) = self.takes.iter().position(|take| take.id == id) else {
+            return;
+        };
+        let removed = self.takes.remove(position);
+        for take in self.takes.iter_mut().filter(|take| take.track > removed.track) {
+            take.track -= 1;
+        }
+        // A discarded selected take hands over to the newest one left in its group
+        if removed.selected {
+            if let Some(next) = self.takes.iter_mut().rev().find(|take| take.same_group(&removed)) {
+                next.selected = true;
+            }
+        }
+    }
+
+    /// Drop every take (their tracks went with the file they were recorded into)
+    pub fn clear_takes(&mut self) {
+        self.takes.clear();
+        self.phase = RecordingPhase::Idle;
+        self.events.clear();
+    }
+
+    /// Keep a refused pass for the control thread (no formatting; safe in the audio path)
+    pub fn fail(&mut self, failure: RecordingFailure) {
+        self.failure = Some(failure);
+        self.passes_failed += 1;
+    }
+
+    /// The refused pass not yet reported, if any
+    pub fn take_failure(&mut self) -> Option<RecordingFailure> {
+        self.failure.take()
+    }
+
+    pub fn status(&self) -> RecordingStatus {
+        RecordingStatus {
+            phase: self.phase,
+            track: self.track,
+            punch_in: self.punch_in,
+            punch_out: self.punch_out,
+            pre_roll_bars: self.pre_roll_bars,
+            events_captured: self.events.len(),
+            events_dropped: self.dropped,
+            passes_failed: self.passes_failed,
+        }
+    }
+}
+
+impl Default for SequenceRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,5 +1,13 @@
 use crate::error::AweError;
-use crate::midi::parser::{MidiFile, MidiEvent, MidiEventType, MetaEventType};
+use crate::events::LoadStage;
+use crate::midi::parser::{MidiFile, MidiEvent, MidiEventType, MidiTrack, MetaEventType};
+use crate::midi::edit::{self, TrackEdit};
+use crate::midi::chase;
+use crate::midi::setup::{self, SongSetup};
+use crate::midi::tempo_ramp::{TempoControl, TempoRamp};
+
+/// Tempo until the file sets one (120 BPM)
+const DEFAULT_TEMPO: u32 = 500_000;
 
 /// Playback state for the MIDI sequencer
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -29,8 +37,8 @@ pub struct MidiSequencer {
     /// Current tempo in microseconds per quarter note
     current_tempo: u32,
     
-    /// Tempo multiplier (1.0 = original speed, 2.0 = double speed)
-    tempo_multiplier: f64,
+    /// Tempo multiplier (1.0 = original speed, 2.0 = double speed) with glide and ramps
+    tempo_control: TempoControl,
     
     /// Sample rate for timing calculations
     sample_rate: f64,
@@ -38,17 +46,35 @@ pub struct MidiSequencer {
     /// Current sample position (for precise timing)
     current_sample: u64,
     
-    /// Sample position when playback started
-    playback_start_sample: u64,
+    /// Sample position the tick position was last advanced to
+    last_sample: u64,
+    
+    /// Playback position in fractional ticks (advanced block by block)
+    tick_position: f64,
     
     /// Track event indices for each track (optimization)
     track_event_indices: Vec<usize>,
     
+    /// Tracks whose notes are not played (their note-offs and tempo changes still are)
+    track_muted: Vec<bool>,
+    
     /// Duration of the MIDI file in ticks
     duration_ticks: u64,
     
     /// Duration in seconds (calculated)
     duration_seconds: f64,
+    
+    /// Events rebuilding channel state at the last seek point
+    chase_events: Vec<ProcessedMidiEvent>,
+    
+    /// Setup region of the loaded file (program, controller and system SysEx dump before the first note)
+    setup: SongSetup,
+    
+    /// Whether the setup region is handed to the player when playback starts at the top
+    setup_enabled: bool,
+    
+    /// The setup region was applied for this pass; its events are skipped as they come due
+    setup_applied: bool,
 }
 
 impl MidiSequencer {
@@ -62,33 +88,46 @@ impl MidiSequencer {
             current_tick: 0,
             seek_tick: 0,
             ticks_per_quarter: 480, // Default value
-            current_tempo: 500_000, // Default 120 BPM (500,000 microseconds per quarter)
-            tempo_multiplier: 1.0,
+            current_tempo: DEFAULT_TEMPO, // Default 120 BPM (500,000 microseconds per quarter)
+            tempo_control: TempoControl::new(),
             sample_rate,
             current_sample: 0,
-            playback_start_sample: 0,
+            last_sample: 0,
+            tick_position: 0.0,
             track_event_indices: Vec::new(),
+            track_muted: Vec::new(),
             duration_ticks: 0,
             duration_seconds: 0.0,
+            chase_events: Vec::new(),
+            setup: SongSetup::default(),
+            setup_enabled: true,
+            setup_applied: false,
         }
     }
     
     /// Load a MIDI file into the sequencer
     pub fn load_midi_file(&mut self, data: &[u8]) -> Result<(), AweError> {
+        self.load_midi_file_with_progress(data, &mut |_, _| {})
+    }
+    
+    /// Load a MIDI file, reporting parse progress (see MidiFile::parse_with_progress)
+    pub fn load_midi_file_with_progress(&mut self, data: &[u8], progress: &mut dyn FnMut(LoadStage, f32)) -> Result<(), AweError> {
         crate::log("MidiSequencer::load_midi_file() - Loading MIDI file");
         
-        let midi_file = MidiFile::parse(data)?;
+        let midi_file = MidiFile::parse_with_progress(data, progress)?;
         
         // Initialize track indices
         self.track_event_indices = vec![0; midi_file.tracks.len()];
+        self.track_muted = vec![false; midi_file.tracks.len()];
         
         // Set timing parameters
         self.ticks_per_quarter = midi_file.division;
-        self.current_tempo = 500_000; // Reset to default 120 BPM
-        self.tempo_multiplier = 1.0;
+        self.current_tempo = DEFAULT_TEMPO; // Reset to default 120 BPM
+        self.tempo_control.reset();
         
         // Calculate duration
         self.calculate_duration(&midi_file);
+        self.setup = SongSetup::scan(&midi_file);
         
         crate::log(&format!("MIDI file loaded: {} tracks, {} ticks/quarter, {:.1}s duration", 
             midi_file.tracks.len(), self.ticks_per_quarter, self.duration_seconds));
@@ -109,15 +148,14 @@ impl MidiSequencer {
         match self.state {
             PlaybackState::Stopped => {
                 crate::log("Starting playback from beginning");
-                self.playback_start_sample = current_sample;
+                self.last_sample = current_sample;
                 self.current_sample = current_sample;
                 self.state = PlaybackState::Playing;
             },
             PlaybackState::Paused => {
                 crate::log("Resuming playback");
-                // Adjust start sample to account for paused time
-                let paused_samples = current_sample - self.current_sample;
-                self.playback_start_sample += paused_samples;
+                // The paused time doesn't advance the position
+                self.last_sample = current_sample;
                 self.current_sample = current_sample;
                 self.state = PlaybackState::Playing;
             },
@@ -146,27 +184,100 @@ impl MidiSequencer {
     /// Seek to a specific position (0.0 to 1.0)
     pub fn seek(&mut self, position: f64, current_sample: u64) {
         let position = position.clamp(0.0, 1.0);
-        self.seek_tick = (position * self.duration_ticks as f64) as u64;
+        crate::log(&format!("Seeking to position {:.1}%", position * 100.0));
+        self.seek_to_tick((position * self.duration_ticks as f64) as u64, current_sample);
+    }
+    
+    /// Seek to a time in seconds (same time base as get_position_seconds)
+    pub fn seek_to_seconds(&mut self, seconds: f64, current_sample: u64) {
+        if self.duration_seconds > 0.0 {
+            self.seek(seconds / self.duration_seconds, current_sample);
+        }
+    }
+    
+    /// Seek to an absolute tick (clamped to the end of the file)
+    pub fn seek_to_tick(&mut self, tick: u64, current_sample: u64) {
+        self.seek_tick = tick.min(self.duration_ticks);
         self.current_tick = self.seek_tick;
-        
-        crate::log(&format!("Seeking to position {:.1}% (tick {})", position * 100.0, self.seek_tick));
+        self.tick_position = self.seek_tick as f64;
+        self.setup_applied = false;
         
         // Reset track indices to find events at new position
         self.reset_track_indices_for_seek();
         
-        // If playing, adjust timing
-        if self.state == PlaybackState::Playing {
-            self.playback_start_sample = current_sample;
+        // Chase: tempo and channel state left by the skipped events
+        if let Some(ref midi_file) = self.midi_file {
+            let state = chase::chase(midi_file, self.seek_tick);
+            self.current_tempo = state.tempo.unwrap_or(DEFAULT_TEMPO);
+            self.chase_events = state.events;
+        }
+        
+        // If playing or paused, restart timing from the new position
+        if self.state != PlaybackState::Stopped {
+            self.last_sample = current_sample;
             self.current_sample = current_sample;
         }
     }
     
-    /// Set tempo multiplier (1.0 = original, 2.0 = double speed)
+    /// Channel state events from the last seek (program, controllers, sustain)
+    /// The player dispatches them right after seeking; later calls return nothing
+    pub fn take_chase_events(&mut self) -> Vec<ProcessedMidiEvent> {
+        std::mem::take(&mut self.chase_events)
+    }
+    
+    /// Setup region events for the player to apply before playback starts
+    /// Only handed out when play is about to start at or before the first note with nothing
+    /// played since the last seek or stop; the sequencer then skips them as they come due
+    pub fn take_setup_events(&mut self) -> Vec<ProcessedMidiEvent> {
+        let at_top = self.current_tick == self.seek_tick && self.seek_tick <= self.setup.end_tick;
+        if !self.setup_enabled || self.setup_applied || !at_top || self.state == PlaybackState::Playing || self.midi_file.is_none() {
+            return Vec::new();
+        }
+        self.setup_applied = true;
+        self.setup.events.clone()
+    }
+    
+    /// Setup region of the loaded file
+    pub fn song_setup(&self) -> &SongSetup {
+        &self.setup
+    }
+    
+    /// Apply the setup region at once at the start of playback (default), or play it as recorded
+    pub fn set_setup_enabled(&mut self, enabled: bool) {
+        self.setup_enabled = enabled;
+        if !enabled {
+            self.setup_applied = false;
+        }
+    }
+    
+    pub fn is_setup_enabled(&self) -> bool {
+        self.setup_enabled
+    }
+    
+    /// Set tempo multiplier (1.0 = original, 2.0 = double speed), clamped to 0.25-4.0
+    /// Glides in over TEMPO_GLIDE_SECONDS; the playback position never jumps
     pub fn set_tempo_multiplier(&mut self, multiplier: f64) {
-        let old_multiplier = self.tempo_multiplier;
-        self.tempo_multiplier = multiplier.clamp(0.25, 4.0); // 25% to 400% speed
-        
-        crate::log(&format!("Tempo multiplier changed: {:.2} → {:.2}", old_multiplier, self.tempo_multiplier));
+        self.tempo_control.set(multiplier);
+    }
+    
+    /// Current tempo multiplier (mid-glide or mid-ramp values included)
+    pub fn get_tempo_multiplier(&self) -> f64 {
+        self.tempo_control.multiplier()
+    }
+    
+    /// Ramp the multiplier to `target` between two ticks (accelerando/ritardando)
+    pub fn schedule_tempo_ramp(&mut self, start_tick: u64, end_tick: u64, target: f64) -> bool {
+        self.tempo_control.schedule_ramp(start_tick, end_tick, target)
+    }
+    
+    /// Cancel scheduled and running tempo ramps
+    pub fn clear_tempo_ramps(&mut self) {
+        self.tempo_control.clear_ramps();
+    }
+    
+    /// Ramps still to run or running
+    pub fn tempo_ramps(&self) -> &[TempoRamp] {
+        self.tempo_control.ramps()
     }
     
     /// Get current playback state
@@ -194,7 +305,7 @@ impl MidiSequencer {
     
     /// Get current tempo in BPM
     pub fn get_current_tempo_bpm(&self) -> f64 {
-        (60_000_000.0 / self.current_tempo as f64) * self.tempo_multiplier
+        (60_000_000.0 / self.current_tempo as f64) * self.tempo_control.multiplier()
     }
     
     /// Get original tempo in BPM (without multiplier)
@@ -202,6 +313,171 @@ impl MidiSequencer {
         60_000_000.0 / self.current_tempo as f64
     }
     
+    /// Current playback position in ticks
+    pub fn get_current_tick(&self) -> u64 {
+        self.current_tick
+    }
+
+    /// Playback position in ticks, between whole ticks (as of the last process call)
+    pub fn get_tick_position(&self) -> f64 {
+        self.tick_position
+    }
+
+    /// Ticks one sample moves at the current tempo and multiplier
+    pub fn ticks_per_sample(&self) -> f64 {
+        let effective_tempo = self.current_tempo as f64 / self.tempo_control.multiplier();
+        1_000_000.0 * self.ticks_per_quarter as f64 / (effective_tempo * self.sample_rate)
+    }
+
+    /// Length of the loaded file in ticks (its last event)
+    pub fn get_duration_ticks(&self) -> u64 {
+        self.duration_ticks
+    }
+
+    /// Ticks per quarter note of the loaded file
+    pub fn get_ticks_per_quarter(&self) -> u16 {
+        self.ticks_per_quarter
+    }
+
+    /// Beat length in ticks and beats per bar at the current position
+    pub fn get_beat_grid(&self) -> (u64, u8) {
+        self.beat_grid_at(self.current_tick)
+    }
+
+    /// Beat length in ticks and beats per bar at `tick`
+    /// Follows the latest Time Signature event at or before it (4/4 if there is none)
+    pub fn beat_grid_at(&self, tick: u64) -> (u64, u8) {
+        let mut signature = (0u64, 4u8, 4u8);
+        if let Some(ref midi_file) = self.midi_file {
+            for event in midi_file.tracks.iter().flat_map(|track| track.events.iter()) {
+                if let MidiEventType::MetaEvent(MetaEventType::TimeSignature { numerator, denominator, .. }) = event.event_type {
+                    if event.absolute_time <= tick && event.absolute_time >= signature.0 {
+                        signature = (event.absolute_time, numerator, denominator);
+                    }
+                }
+            }
+        }
+        let (_, numerator, denominator) = signature;
+        let ticks_per_beat = self.ticks_per_quarter as u64 * 4 / denominator.max(1) as u64;
+        (ticks_per_beat, numerator)
+    }
+
+    /// Loaded MIDI file, if any
+    pub fn midi_file(&self) -> Option<&MidiFile> {
+        self.midi_file.as_ref()
+    }
+    
+    /// Apply an edit to one track and re-index playback from the current position
+    /// Returns (channel, note) of notes that are sounding now and lost their note-off
+    pub fn edit_track(&mut self, track_index: usize, edit: &TrackEdit) -> Result<Vec<(u8, u8)>, String> {
+        let mut midi_file = self.midi_file.take().ok_or_else(|| "No MIDI file loaded".to_string())?;
+        let result = self.edit_loaded_track(&mut midi_file, track_index, edit);
+        self.calculate_duration(&midi_file);
+        self.setup = SongSetup::scan(&midi_file);
+        self.midi_file = Some(midi_file);
+        result
+    }
+    
+    fn edit_loaded_track(&mut self, midi_file: &mut MidiFile, track_index: usize, edit: &TrackEdit) -> Result<Vec<(u8, u8)>, String> {
+        let track = midi_file.tracks.get_mut(track_index)
+            .ok_or_else(|| format!("No track {}", track_index))?;
+        let sounding = self.sounding_notes(track, track_index);
+        
+        edit::apply(track, edit)?;
+        
+        let boundary = self.played_boundary(track);
+        self.track_event_indices[track_index] = boundary;
+        let mut pending_offs: Vec<(u8, u8)> = track.events[boundary..].iter()
+            .filter_map(|event| match event.event_type {
+                MidiEventType::NoteOff { channel, note, .. } | MidiEventType::NoteOn { channel, note, velocity: 0 } => Some((channel, note)),
+                _ => None,
+            })
+            .collect();
+        Ok(sounding.into_iter()
+            .filter(|note| match pending_offs.iter().position(|off| off == note) {
+                Some(position) => {
+                    pending_offs.swap_remove(position);
+                    false
+                }
+                None => true,
+            })
+            .collect())
+    }
+    
+    /// Add a track to the loaded file, joining playback at the current position
+    /// Returns its index
+    pub fn add_track(&mut self, track: MidiTrack) -> Result<usize, String> {
+        let mut midi_file = self.midi_file.take().ok_or_else(|| "No MIDI file loaded".to_string())?;
+        self.track_event_indices.push(self.played_boundary(&track));
+        self.track_muted.push(false);
+        midi_file.tracks.push(track);
+        self.calculate_duration(&midi_file);
+        self.setup = SongSetup::scan(&midi_file);
+        self.midi_file = Some(midi_file);
+        Ok(self.track_event_indices.len() - 1)
+    }
+    
+    /// Remove a track; later tracks move down one index
+    /// Returns (channel, note) of its notes that are sounding now
+    pub fn remove_track(&mut self, track_index: usize) -> Result<Vec<(u8, u8)>, String> {
+        let mut midi_file = self.midi_file.take().ok_or_else(|| "No MIDI file loaded".to_string())?;
+        if track_index >= midi_file.tracks.len() {
+            self.midi_file = Some(midi_file);
+            return Err(format!("No track {}", track_index));
+        }
+        let track = midi_file.tracks.remove(track_index);
+        let sounding = if self.track_muted[track_index] { Vec::new() } else { self.sounding_notes(&track, track_index) };
+        self.track_event_indices.remove(track_index);
+        self.track_muted.remove(track_index);
+        self.calculate_duration(&midi_file);
+        self.setup = SongSetup::scan(&midi_file);
+        self.midi_file = Some(midi_file);
+        Ok(sounding)
+    }
+    
+    /// Play or silence a track's notes (false if there is no such track)
+    /// Notes already sounding still get their note-offs
+    pub fn set_track_muted(&mut self, track_index: usize, muted: bool) -> bool {
+        match self.track_muted.get_mut(track_index) {
+            Some(slot) => {
+                *slot = muted;
+                true
+            }
+            None => false,
+        }
+    }
+    
+    pub fn is_track_muted(&self, track_index: usize) -> bool {
+        self.track_muted.get(track_index).copied().unwrap_or(false)
+    }
+    
+    /// Notes of `track` whose note-on has been dispatched but whose note-off has not
+    fn sounding_notes(&self, track: &MidiTrack, track_index: usize) -> Vec<(u8, u8)> {
+        let mut sounding: Vec<(u8, u8)> = Vec::new();
+        for event in &track.events[..self.track_event_indices[track_index]] {
+            match event.event_type {
+                MidiEventType::NoteOn { channel, note, velocity } if velocity > 0 => sounding.push((channel, note)),
+                MidiEventType::NoteOn { channel, note, .. } | MidiEventType::NoteOff { channel, note, .. } => {
+                    if let Some(position) = sounding.iter().position(|&n| n == (channel, note)) {
+                        sounding.swap_remove(position);
+                    }
+                }
+                _ => {}
+            }
+        }
+        sounding
+    }
+    
+    /// Number of leading events of `track` already dispatched at the current position
+    fn played_boundary(&self, track: &MidiTrack) -> usize {
+        if self.current_tick == self.seek_tick {
+            // Nothing processed since the last seek/stop: events at the seek tick are still due
+            track.events.partition_point(|event| event.absolute_time < self.current_tick)
+        } else {
+            track.events.partition_point(|event| event.absolute_time <= self.current_tick)
+        }
+    }
+    
     /// Process MIDI events for the current sample buffer
     /// Returns events that should be triggered
     pub fn process(&mut self, current_sample: u64, _buffer_size: usize) -> Vec<ProcessedMidiEvent> {
@@ -212,16 +488,17 @@ impl MidiSequencer {
         let mut events = Vec::new();
         self.current_sample = current_sample;
         
-        // Calculate current tick based on elapsed samples
-        let samples_elapsed = current_sample - self.playback_start_sample;
+        // Advance by the ticks this block covers at the current tempo and multiplier,
+        // so tempo and multiplier changes affect only what is still to play
+        let samples_elapsed = current_sample.saturating_sub(self.last_sample);
+        self.last_sample = current_sample;
         let seconds_elapsed = samples_elapsed as f64 / self.sample_rate;
-        
-        // Convert seconds to ticks using current tempo and multiplier
-        let effective_tempo = self.current_tempo as f64 / self.tempo_multiplier;
+        let multiplier = self.tempo_control.advance(self.current_tick, seconds_elapsed);
+        let effective_tempo = self.current_tempo as f64 / multiplier;
         let quarters_elapsed = seconds_elapsed * 1_000_000.0 / effective_tempo;
-        let ticks_elapsed = quarters_elapsed * self.ticks_per_quarter as f64;
+        self.tick_position += quarters_elapsed * self.ticks_per_quarter as f64;
         
-        let target_tick = self.seek_tick + ticks_elapsed as u64;
+        let target_tick = self.tick_position as u64;
         
         // Process events between current_tick and target_tick
         if let Some(ref midi_file) = self.midi_file {
@@ -232,7 +509,13 @@ impl MidiSequencer {
                     if event.absolute_time <= target_tick {
                         // Convert MIDI event to processed event directly (avoiding mutable borrow)
                         if let Some(processed_event) = Self::convert_midi_event(event, &mut self.current_tempo) {
-                            events.push(processed_event);
+                            let silenced = self.track_muted[track_idx]
+                                && matches!(processed_event.event_type, ProcessedEventType::NoteOn { velocity, .. } if velocity > 0);
+                            let set_up = self.setup_applied && event.absolute_time <= self.setup.end_tick
+                                && setup::is_setup_event(&processed_event.event_type);
+                            if !silenced && !set_up {
+                                events.push(processed_event);
+                            }
                         }
                         self.track_event_indices[track_idx] += 1;
                     } else {
@@ -258,8 +541,10 @@ impl MidiSequencer {
         self.current_tick = 0;
         self.seek_tick = 0;
         self.current_sample = 0;
-        self.playback_start_sample = 0;
+        self.last_sample = 0;
+        self.tick_position = 0.0;
         self.track_event_indices.fill(0);
+        self.setup_applied = false;
     }
     
     /// Reset track indices for seeking
@@ -341,6 +626,12 @@ impl MidiSequencer {
                     },
                 })
             },
+            MidiEventType::SysEx { data } => {
+                Some(ProcessedMidiEvent {
+                    sample_offset: 0,
+                    event_type: ProcessedEventType::SysEx { data: data.clone() },
+                })
+            },
             MidiEventType::MetaEvent(MetaEventType::SetTempo { microseconds_per_quarter }) => {
                 // Update current tempo
                 *current_tempo = *microseconds_per_quarter;
@@ -372,4 +663,5 @@ pub enum ProcessedEventType {
     NoteOff { channel: u8, note: u8, velocity: u8 },
     ProgramChange { channel: u8, program: u8 },
     ControlChange { channel: u8, controller: u8, value: u8 },
+    SysEx { data: Vec<u8> },    // Starting with F0 (F7 escapes as stored in the file)
 }
\ No newline at end of file
@@ -0,0 +1,114 @@
+/**
+ * AWE Player - Song Setup Region
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Files written for GS and XG modules open with a setup measure: a system
+ * reset SysEx, then bank selects, programs, volume, pan and effect sends
+ * for every part, spread over the first bar (sometimes longer) ahead of the
+ * first note. Played as recorded, those settings arrive one at a time while
+ * controller smoothing glides toward each new value, and a song whose
+ * first note shares a tick with its setup can start on the wrong program.
+ *
+ * The setup region is every event before the first Note On, plus the
+ * events on that note's own tick. When playback starts at the top of the
+ * song the player applies the region's system SysEx, program changes and
+ * controllers in one go, in file order and without glides, before the
+ * first block is rendered; the sequencer then skips those events as they
+ * come due. Tempo and other meta events stay with the sequencer.
+ */
+
+use serde::Serialize;
+use super::bank_select::parse_system_sysex;
+use super::parser::{MidiEventType, MidiFile};
+use super::sequencer::{ProcessedEventType, ProcessedMidiEvent};
+
+/// Setup region of a file
+#[derive(Debug, Clone, Default)]
+pub struct SongSetup {
+    pub end_tick: u64,                      // First Note On (the last event if there are no notes)
+    pub events: Vec<ProcessedMidiEvent>,    // Setup events up to and at end_tick, in file order
+}
+
+impl SongSetup {
+    pub fn scan(midi_file: &MidiFile) -> Self {
+        let file_events = || midi_file.tracks.iter().flat_map(|track| track.events.iter());
+        let end_tick = file_events()
+            .filter(|event| matches!(event.event_type, MidiEventType::NoteOn { .. }))
+            .map(|event| event.absolute_time)
+            .min()
+            .or_else(|| file_events().map(|event| event.absolute_time).max())
+            .unwrap_or(0);
+
+        let mut region: Vec<_> = file_events().filter(|event| event.absolute_time <= end_tick).collect();
+        // Stable: events at the same tick keep their track order
+        region.sort_by_key(|event| event.absolute_time);
+        let events = region.into_iter()
+            .filter_map(|event| setup_event(&event.event_type))
+            .map(|event_type| ProcessedMidiEvent { sample_offset: 0, event_type })
+            .collect();
+        Self { end_tick, events }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn report(&self, enabled: bool) -> SongSetupReport {
+        let mut report = SongSetupReport { enabled, end_tick: self.end_tick, ..SongSetupReport::default() };
+        for event in &self.events {
+            let channel = match event.event_type {
+                ProcessedEventType::SysEx { .. } => {
+                    report.sysex += 1;
+                    continue;
+                }
+                ProcessedEventType::ProgramChange { channel, .. } => {
+                    report.program_changes += 1;
+                    channel
+                }
+                ProcessedEventType::ControlChange { channel, .. } => {
+                    report.controllers += 1;
+                    channel
+                }
+                ProcessedEventType::NoteOn { .. } | ProcessedEventType::NoteOff { .. } => continue,
+            };
+            if !report.channels.contains(&channel) {
+                report.channels.push(channel);
+            }
+        }
+        report.channels.sort_unstable();
+        report
+    }
+}
+
+/// Whether a sequencer event is of a kind the setup region holds
+pub fn is_setup_event(event_type: &ProcessedEventType) -> bool {
+    !matches!(event_type, ProcessedEventType::NoteOn { .. } | ProcessedEventType::NoteOff { .. })
+}
+
+/// Setup event for a file event: system SysEx the engine understands, programs and controllers
+fn setup_event(event_type: &MidiEventType) -> Option<ProcessedEventType> {
+    match *event_type {
+        MidiEventType::ProgramChange { channel, program } => Some(ProcessedEventType::ProgramChange { channel, program }),
+        MidiEventType::ControlChange { channel, controller, value } => {
+            Some(ProcessedEventType::ControlChange { channel, controller, value })
+        }
+        MidiEventType::SysEx { ref data } if parse_system_sysex(data).is_some() => {
+            Some(ProcessedEventType::SysEx { data: data.clone() })
+        }
+        _ => None,
+    }
+}
+
+/// What the loaded song's setup region holds (get_song_setup_global)
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SongSetupReport {
+    pub enabled: bool,              // Applied at once when playback starts at the top
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub end_tick: u64,              // First note of the song
+    pub sysex: usize,               // GM/GS/XG system messages
+    pub program_changes: usize,
+    pub controllers: usize,
+    pub channels: Vec<u8>,          // Channels given a program or controller
+}
@@ -0,0 +1,132 @@
+/**
+ * AWE Player - Take Export
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Writes a recorded take out as a Standard MIDI File. Live input is
+ * captured as played, and the take keeps it that way; tightening it up is
+ * a choice made per export. Three optional passes run on a copy of the
+ * take's events:
+ *
+ * - quantize: note starts snap to the nearest line of a grid (in ticks);
+ *   notes keep their length, so the release moves with the start.
+ * - swing: every second grid line moves late, to a percentage of the
+ *   two-line pair (50% is straight, 66.7% a triplet feel, 75% at most).
+ * - legato: the gap between a note's release and the next note on its
+ *   channel is filled by a fraction (1.0 joins them); overlaps are left.
+ *
+ * Controllers and program changes keep their ticks. The file is format 1:
+ * a conductor track with the song's tempo and time signature changes, so
+ * the take lines up with the song, then the take itself.
+ */
+
+use serde::{Deserialize, Serialize};
+use super::edit;
+use super::parser::{MetaEventType, MidiEvent, MidiEventType, MidiFile, MidiTrack};
+use super::writer::write_smf;
+
+/// Straight and heaviest swing (percent of a grid pair)
+pub const STRAIGHT_SWING: f32 = 50.0;
+pub const MAX_SWING: f32 = 75.0;
+
+/// Take export settings (JSON; missing fields leave the take as played)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct MidiExportOptions {
+    pub quantize_ticks: u32,    // Grid for note starts, 0 = as played
+    pub swing: f32,             // Off-beat position, 50-75% of a grid pair
+    pub legato: f32,            // Share of the gap to the next note filled, 0.0-1.0
+}
+
+impl Default for MidiExportOptions {
+    fn default() -> Self {
+        Self { quantize_ticks: 0, swing: STRAIGHT_SWING, legato: 0.0 }
+    }
+}
+
+impl MidiExportOptions {
+    /// Options from JSON with swing and legato clamped to their ranges; None if it doesn't parse
+    pub fn from_json(json: &str) -> Option<Self> {
+        let options: Self = serde_json::from_str(json).ok()?;
+        let clamp = |value: f32, low: f32, high: f32, neutral: f32| if value.is_finite() { value.clamp(low, high) } else { neutral };
+        Some(Self {
+            swing: clamp(options.swing, STRAIGHT_SWING, MAX_SWING, STRAIGHT_SWING),
+            legato: clamp(options.legato, 0.0, 1.0, 0.0),
+            ..options
+        })
+    }
+
+    /// Nearest line of the (swung) grid to `tick`; ties go to the earlier line
+    pub fn snap(&self, tick: u64) -> u64 {
+        let grid = self.quantize_ticks as u64;
+        if grid == 0 {
+            return tick;
+        }
+        let pair = grid * 2;
+        let start = tick / pair * pair;
+        let off_beat = start + (pair as f64 * self.swing as f64 / 100.0).round() as u64;
+        [start, off_beat, start + pair].into_iter()
+            .min_by_key(|&line| line.abs_diff(tick))
+            .unwrap_or(tick)
+    }
+}
+
+/// SMF bytes of a take: the song's tempo map, then the take with `options` applied
+pub fn export_take(song: &MidiFile, take: &MidiTrack, options: &MidiExportOptions) -> Vec<u8> {
+    write_smf(song.division, &[conductor_track(song), apply_options(take, options)])
+}
+
+/// Copy of `take` with quantize, swing and legato applied
+pub fn apply_options(take: &MidiTrack, options: &MidiExportOptions) -> MidiTrack {
+    let mut track = take.clone();
+    // (note-on index, note-off index) pairs; notes without a release are left alone
+    let notes: Vec<(usize, usize)> = (0..track.events.len())
+        .filter_map(|on| edit::note_off_for(&track, on).map(|off| (on, off)))
+        .collect();
+
+    if options.quantize_ticks > 0 {
+        for &(on, off) in &notes {
+            let start = track.events[on].absolute_time;
+            let snapped = options.snap(start);
+            track.events[on].absolute_time = snapped;
+            track.events[off].absolute_time = (track.events[off].absolute_time + snapped).saturating_sub(start);
+        }
+    }
+
+    if options.legato > 0.0 {
+        let channel_of = |on: usize| match track.events[on].event_type {
+            MidiEventType::NoteOn { channel, .. } => channel,
+            _ => 0,
+        };
+        let mut starts: Vec<(u8, u64)> = notes.iter().map(|&(on, _)| (channel_of(on), track.events[on].absolute_time)).collect();
+        starts.sort_unstable();
+        let releases: Vec<(usize, u64)> = notes.iter()
+            .filter_map(|&(on, off)| {
+                let (channel, start, end) = (channel_of(on), track.events[on].absolute_time, track.events[off].absolute_time);
+                let (next_channel, next) = *starts.get(starts.partition_point(|&note| note <= (channel, start)))?;
+                (next_channel == channel && next > end)
+                    .then(|| (off, end + ((next - end) as f64 * options.legato as f64).round() as u64))
+            })
+            .collect();
+        for (off, tick) in releases {
+            track.events[off].absolute_time = tick;
+        }
+    }
+
+    edit::normalize(&mut track);
+    track
+}
+
+/// Tempo and time signature changes of every track of the song
+fn conductor_track(song: &MidiFile) -> MidiTrack {
+    let mut events: Vec<MidiEvent> = song.tracks.iter()
+        .flat_map(|track| track.events.iter())
+        .filter(|event| matches!(event.event_type,
+            MidiEventType::MetaEvent(MetaEventType::SetTempo { .. } | MetaEventType::TimeSignature { .. })))
+        .cloned()
+        .collect();
+    events.sort_by_key(|event| event.absolute_time);
+    let mut track = MidiTrack { name: None, events };
+    edit::normalize(&mut track);
+    track
+}
@@ -0,0 +1,136 @@
+/**
+ * AWE Player - Tempo Multiplier Ramps
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * The tempo multiplier scales playback speed between 0.25x and 4x without
+ * touching pitch: only the sequencer's clock changes, and samples play at
+ * their normal rate. Changing it never moves the playback position, since
+ * the sequencer advances by the ticks each block covers at the multiplier
+ * in effect. A new multiplier glides in over a short time so a slider drag
+ * doesn't lurch. Practice tools can also schedule ramps: between two ticks
+ * the multiplier moves linearly (in ticks, so an accelerando over four bars
+ * stays over four bars) from whatever it is when the ramp starts to the
+ * ramp's target. Ramps run once; a manual change cancels any ramp already
+ * running.
+ */
+
+/// Accepted multiplier range (25% to 400% speed)
+pub const MIN_TEMPO_MULTIPLIER: f64 = 0.25;
+pub const MAX_TEMPO_MULTIPLIER: f64 = 4.0;
+
+/// Time a set_tempo_multiplier change takes to glide in
+pub const TEMPO_GLIDE_SECONDS: f64 = 0.05;
+
+/// Ramps that can wait at once
+const MAX_RAMPS: usize = 16;
+
+/// Scheduled multiplier ramp between two ticks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TempoRamp {
+    pub start_tick: u64,
+    pub end_tick: u64,
+    pub target: f64,
+    from: Option<f64>,          // Multiplier when the ramp started
+}
+
+/// Current tempo multiplier with its glide and scheduled ramps
+#[derive(Debug, Clone)]
+pub struct TempoControl {
+    multiplier: f64,
+    glide_target: f64,
+    glide_step: f64,            // Multiplier change per second while gliding
+    ramps: Vec<TempoRamp>,      // Ordered by start tick
+}
+
+impl TempoControl {
+    pub fn new() -> Self {
+        Self {
+            multiplier: 1.0,
+            glide_target: 1.0,
+            glide_step: 0.0,
+            ramps: Vec::with_capacity(MAX_RAMPS),
+        }
+    }
+
+    /// Back to 1.0 with nothing scheduled
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    /// Glide to `multiplier` over TEMPO_GLIDE_SECONDS; cancels any running ramp
+    pub fn set(&mut self, multiplier: f64) {
+        if !multiplier.is_finite() {
+            return;
+        }
+        self.ramps.retain(|ramp| ramp.from.is_none());
+        self.glide_target = multiplier.clamp(MIN_TEMPO_MULTIPLIER, MAX_TEMPO_MULTIPLIER);
+        self.glide_step = (self.glide_target - self.multiplier).abs() / TEMPO_GLIDE_SECONDS;
+    }
+
+    /// Schedule a ramp to `target` between `start_tick` and `end_tick`
+    /// Returns false for a bad range, a non-finite target or a full schedule
+    pub fn schedule_ramp(&mut self, start_tick: u64, end_tick: u64, target: f64) -> bool {
+        if end_tick < start_tick || !target.is_finite() || self.ramps.len() >= MAX_RAMPS {
+            return false;
+        }
+        let ramp = TempoRamp {
+            start_tick,
+            end_tick,
+            target: target.clamp(MIN_TEMPO_MULTIPLIER, MAX_TEMPO_MULTIPLIER),
+            from: None,
+        };
+        let index = self.ramps.partition_point(|other| other.start_tick <= start_tick);
+        self.ramps.insert(index, ramp);
+        true
+    }
+
+    /// Drop every scheduled and running ramp (the multiplier stays where it is)
+    pub fn clear_ramps(&mut self) {
+        self.ramps.clear();
+        self.glide_target = self.multiplier;
+        self.glide_step = 0.0;
+    }
+
+    pub fn ramps(&self) -> &[TempoRamp] {
+        &self.ramps
+    }
+
+    /// Multiplier for a block starting at `tick` that lasts `seconds`
+    pub fn advance(&mut self, tick: u64, seconds: f64) -> f64 {
+        if let Some(ramp) = self.ramps.first_mut().filter(|ramp| tick >= ramp.start_tick) {
+            let from = *ramp.from.get_or_insert(self.multiplier);
+            let span = ramp.end_tick - ramp.start_tick;
+            self.multiplier = if tick >= ramp.end_tick || span == 0 {
+                ramp.target
+            } else {
+                from + (ramp.target - from) * (tick - ramp.start_tick) as f64 / span as f64
+            };
+            self.glide_target = self.multiplier;
+            self.glide_step = 0.0;
+            if tick >= ramp.end_tick {
+                self.ramps.remove(0);
+            }
+            return self.multiplier;
+        }
+
+        let multiplier = self.multiplier;
+        if self.glide_step > 0.0 {
+            let step = self.glide_step * seconds;
+            let distance = self.glide_target - self.multiplier;
+            self.multiplier = if distance.abs() <= step { self.glide_target } else { self.multiplier + step.copysign(distance) };
+            // The block runs at the average of its start and end speed
+            return (multiplier + self.multiplier) * 0.5;
+        }
+        multiplier
+    }
+}
+
+impl Default for TempoControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -9,14 +9,18 @@
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
 use crate::MidiEvent;
+use super::event_trace::EventSource;
+use super::theory::{self, ChordQuality, Scale};
 
 /// MIDI test sequence configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct TestSequenceConfig {
     pub channel: u8,
     pub velocity: u8,
     pub note_duration_ms: u32,
     pub note_gap_ms: u32,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
     pub start_timestamp: u64,
 }
 
@@ -34,6 +38,7 @@ impl Default for TestSequenceConfig {
 
 /// Test sequence for audio pipeline validation
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct MidiTestSequence {
     pub name: String,
     pub notes: Vec<u8>,
@@ -56,28 +61,38 @@ impl MidiTestSequenceGenerator {
     /// Generate C major scale sequence
     pub fn generate_c_major_scale(&self, config: Option<TestSequenceConfig>) -> MidiTestSequence {
         let config = config.unwrap_or_default();
-        let notes = vec![60, 62, 64, 65, 67, 69, 71, 72]; // C D E F G A B C
+        let notes = Scale::Major.notes(60, 1); // C D E F G A B C
         self.generate_sequence("C Major Scale", notes, config)
     }
     
     /// Generate chromatic scale sequence
     pub fn generate_chromatic_scale(&self, config: Option<TestSequenceConfig>) -> MidiTestSequence {
         let config = config.unwrap_or_default();
-        let notes = (60..73).collect(); // C to C (one octave chromatic)
+        let notes = Scale::Chromatic.notes(60, 1); // C to C (one octave chromatic)
         self.generate_sequence("Chromatic Scale", notes, config)
     }
     
+    /// Generate one octave of any scale, up from `tonic`
+    pub fn generate_scale(&self, tonic: u8, scale: Scale, config: Option<TestSequenceConfig>) -> MidiTestSequence {
+        let config = config.unwrap_or_default();
+        let name = format!("{} {:?} Scale", theory::note_name(tonic, false), scale);
+        self.generate_sequence(&name, scale.notes(tonic, 1), config)
+    }
+    
     /// Generate arpeggios sequence
     pub fn generate_c_major_arpeggio(&self, config: Option<TestSequenceConfig>) -> MidiTestSequence {
         let config = config.unwrap_or_default();
-        let notes = vec![60, 64, 67, 72, 67, 64, 60]; // C E G C G E C
+        let mut notes = ChordQuality::Major.notes(60, 0).unwrap_or_default();
+        notes.push(72);
+        let descending: Vec<u8> = notes.iter().rev().skip(1).copied().collect();
+        notes.extend(descending); // C E G C G E C
         self.generate_sequence("C Major Arpeggio", notes, config)
     }
     
     /// Generate polyphonic chord test
     pub fn generate_chord_test(&self, config: Option<TestSequenceConfig>) -> MidiTestSequence {
         let config = config.unwrap_or_default();
-        let chord_notes = vec![60, 64, 67]; // C Major chord
+        let chord_notes = ChordQuality::Major.notes(60, 0).unwrap_or_default(); // C Major chord
         
         let mut events = Vec::new();
         let mut current_timestamp = config.start_timestamp;
@@ -216,40 +231,17 @@ pub struct NoteNameUtils;
 impl NoteNameUtils {
     /// Convert MIDI note number to note name (e.g., 60 -> "C4")
     pub fn midi_to_note_name(note: u8) -> String {
-        let note_names = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
-        let octave = (note / 12) as i32 - 1;
-        let note_name = note_names[(note % 12) as usize];
-        format!("{}{}", note_name, octave)
+        theory::note_name(note, false)
     }
     
-    /// Convert note name to MIDI note number (e.g., "C4" -> 60)
+    /// Convert note name to MIDI note number (e.g., "C4" -> 60, "Eb3" -> 51, "C-1" -> 0)
     pub fn note_name_to_midi(note_name: &str) -> Option<u8> {
-        if note_name.len() < 2 {
-            return None;
-        }
-        
-        let note_names = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
-        let (note_part, octave_part) = if note_name.len() == 3 && note_name.chars().nth(1) == Some('#') {
-            (&note_name[0..2], &note_name[2..])
-        } else {
-            (&note_name[0..1], &note_name[1..])
-        };
-        
-        let note_index = note_names.iter().position(|&n| n == note_part)?;
-        let octave: i32 = octave_part.parse().ok()?;
-        
-        let midi_note = (octave + 1) * 12 + note_index as i32;
-        
-        if midi_note >= 0 && midi_note <= 127 {
-            Some(midi_note as u8)
-        } else {
-            None
-        }
+        theory::parse_note_name(note_name)
     }
     
     /// Get all note names for an octave
     pub fn get_note_names() -> Vec<&'static str> {
-        vec!["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"]
+        theory::SHARP_NAMES.to_vec()
     }
 }
 
@@ -347,6 +339,29 @@ pub fn generate_chord_test(config_json: Option<String>) -> String {
     }
 }
 
+/// Generate one octave of a scale ("major", "dorian", "blues", ...) up from `tonic` as JSON
+#[wasm_bindgen]
+pub fn generate_scale_test(tonic: u8, scale: &str, config_json: Option<String>) -> String {
+    let Some(scale) = Scale::from_name(scale) else {
+        return r#"{"error": "Unknown scale"}"#.to_string();
+    };
+    unsafe {
+        if let Some(ref generator) = GLOBAL_TEST_GENERATOR {
+            let config = if let Some(json) = config_json {
+                serde_json::from_str(&json).unwrap_or_default()
+            } else {
+                TestSequenceConfig::default()
+            };
+            
+            let sequence = generator.generate_scale(tonic, scale, Some(config));
+            serde_json::to_string(&sequence).unwrap_or_else(|_| "{}".to_string())
+        } else {
+            crate::log("❌ Test sequence generator not initialized");
+            r#"{"error": "Generator not initialized"}"#.to_string()
+        }
+    }
+}
+
 /// Generate velocity test sequence as JSON
 #[wasm_bindgen]
 pub fn generate_velocity_test(config_json: Option<String>) -> String {
@@ -397,7 +412,7 @@ pub fn execute_test_sequence(sequence_json: &str) -> u32 {
                         if queue.len() >= 1000 {
                             queue.pop_front();
                         }
-                        queue.push_back(*event);
+                        queue.push_back((*event, EventSource::Live));
                         events_queued += 1;
                     }
                 }
@@ -0,0 +1,278 @@
+/**
+ * AWE Player - Chord and Scale Helpers
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Small music-theory toolkit for the test-sequence generator and demo
+ * apps: chords built from a root and quality (with inversions), scales
+ * and modes over any number of octaves, snapping a note to the nearest
+ * scale tone, and note names in either sharp or flat spelling.
+ *
+ * Names follow the MIDI convention where middle C (60) is C4 and the
+ * lowest note (0) is C-1. Anything that would leave the 0-127 note range
+ * is refused rather than wrapped.
+ */
+
+use wasm_bindgen::prelude::*;
+
+/// Pitch class names, sharp spelling
+pub const SHARP_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+/// Pitch class names, flat spelling
+pub const FLAT_NAMES: [&str; 12] = ["C", "Db", "D", "Eb", "E", "F", "Gb", "G", "Ab", "A", "Bb", "B"];
+
+/// Chord qualities, as semitones above the root
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChordQuality {
+    Major,
+    Minor,
+    Diminished,
+    Augmented,
+    Sus2,
+    Sus4,
+    Major7,
+    Minor7,
+    Dominant7,
+    HalfDiminished7,
+    Diminished7,
+    Major6,
+    Minor6,
+    Add9,
+}
+
+impl ChordQuality {
+    /// Accepts the long names and common chord symbols ("maj", "m", "m7b5", "7")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "major" | "maj" | "" => Some(ChordQuality::Major),
+            "minor" | "min" | "m" => Some(ChordQuality::Minor),
+            "diminished" | "dim" => Some(ChordQuality::Diminished),
+            "augmented" | "aug" | "+" => Some(ChordQuality::Augmented),
+            "sus2" => Some(ChordQuality::Sus2),
+            "sus4" | "sus" => Some(ChordQuality::Sus4),
+            "major7" | "maj7" => Some(ChordQuality::Major7),
+            "minor7" | "min7" | "m7" => Some(ChordQuality::Minor7),
+            "dominant7" | "7" => Some(ChordQuality::Dominant7),
+            "half_diminished7" | "m7b5" => Some(ChordQuality::HalfDiminished7),
+            "diminished7" | "dim7" => Some(ChordQuality::Diminished7),
+            "major6" | "6" => Some(ChordQuality::Major6),
+            "minor6" | "m6" => Some(ChordQuality::Minor6),
+            "add9" => Some(ChordQuality::Add9),
+            _ => None,
+        }
+    }
+
+    pub fn intervals(&self) -> &'static [u8] {
+        match self {
+            ChordQuality::Major => &[0, 4, 7],
+            ChordQuality::Minor => &[0, 3, 7],
+            ChordQuality::Diminished => &[0, 3, 6],
+            ChordQuality::Augmented => &[0, 4, 8],
+            ChordQuality::Sus2 => &[0, 2, 7],
+            ChordQuality::Sus4 => &[0, 5, 7],
+            ChordQuality::Major7 => &[0, 4, 7, 11],
+            ChordQuality::Minor7 => &[0, 3, 7, 10],
+            ChordQuality::Dominant7 => &[0, 4, 7, 10],
+            ChordQuality::HalfDiminished7 => &[0, 3, 6, 10],
+            ChordQuality::Diminished7 => &[0, 3, 6, 9],
+            ChordQuality::Major6 => &[0, 4, 7, 9],
+            ChordQuality::Minor6 => &[0, 3, 7, 9],
+            ChordQuality::Add9 => &[0, 4, 7, 14],
+        }
+    }
+
+    /// Chord notes from the bass up; each inversion moves the lowest note up an octave
+    /// None if a note would pass 127 or the inversion is not below the note count
+    pub fn notes(&self, root: u8, inversion: u8) -> Option<Vec<u8>> {
+        let intervals = self.intervals();
+        if inversion as usize >= intervals.len() {
+            return None;
+        }
+        let mut notes: Vec<u16> = intervals.iter().map(|&interval| root as u16 + interval as u16).collect();
+        notes.rotate_left(inversion as usize);
+        let len = notes.len();
+        for note in &mut notes[len - inversion as usize..] {
+            *note += 12;
+        }
+        notes.into_iter().map(|note| u8::try_from(note).ok().filter(|&note| note <= 127)).collect()
+    }
+}
+
+/// Scales and modes, as semitones above the tonic within one octave
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scale {
+    Major,
+    NaturalMinor,
+    HarmonicMinor,
+    MelodicMinor,
+    Dorian,
+    Phrygian,
+    Lydian,
+    Mixolydian,
+    Locrian,
+    MajorPentatonic,
+    MinorPentatonic,
+    Blues,
+    WholeTone,
+    Chromatic,
+}
+
+impl Scale {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "major" | "ionian" => Some(Scale::Major),
+            "minor" | "natural_minor" | "aeolian" => Some(Scale::NaturalMinor),
+            "harmonic_minor" => Some(Scale::HarmonicMinor),
+            "melodic_minor" => Some(Scale::MelodicMinor),
+            "dorian" => Some(Scale::Dorian),
+            "phrygian" => Some(Scale::Phrygian),
+            "lydian" => Some(Scale::Lydian),
+            "mixolydian" => Some(Scale::Mixolydian),
+            "locrian" => Some(Scale::Locrian),
+            "major_pentatonic" | "pentatonic" => Some(Scale::MajorPentatonic),
+            "minor_pentatonic" => Some(Scale::MinorPentatonic),
+            "blues" => Some(Scale::Blues),
+            "whole_tone" => Some(Scale::WholeTone),
+            "chromatic" => Some(Scale::Chromatic),
+            _ => None,
+        }
+    }
+
+    pub fn intervals(&self) -> &'static [u8] {
+        match self {
+            Scale::Major => &[0, 2, 4, 5, 7, 9, 11],
+            Scale::NaturalMinor => &[0, 2, 3, 5, 7, 8, 10],
+            Scale::HarmonicMinor => &[0, 2, 3, 5, 7, 8, 11],
+            Scale::MelodicMinor => &[0, 2, 3, 5, 7, 9, 11],
+            Scale::Dorian => &[0, 2, 3, 5, 7, 9, 10],
+            Scale::Phrygian => &[0, 1, 3, 5, 7, 8, 10],
+            Scale::Lydian => &[0, 2, 4, 6, 7, 9, 11],
+            Scale::Mixolydian => &[0, 2, 4, 5, 7, 9, 10],
+            Scale::Locrian => &[0, 1, 3, 5, 6, 8, 10],
+            Scale::MajorPentatonic => &[0, 2, 4, 7, 9],
+            Scale::MinorPentatonic => &[0, 3, 5, 7, 10],
+            Scale::Blues => &[0, 3, 5, 6, 7, 10],
+            Scale::WholeTone => &[0, 2, 4, 6, 8, 10],
+            Scale::Chromatic => &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+        }
+    }
+
+    /// Ascending scale from `tonic` over `octaves` octaves, ending on the tonic above
+    /// (notes past 127 are left off)
+    pub fn notes(&self, tonic: u8, octaves: u8) -> Vec<u8> {
+        (0..octaves as u16)
+            .flat_map(|octave| self.intervals().iter().map(move |&interval| tonic as u16 + octave * 12 + interval as u16))
+            .chain(std::iter::once(tonic as u16 + octaves as u16 * 12))
+            .filter(|&note| note <= 127)
+            .map(|note| note as u8)
+            .collect()
+    }
+
+    /// Whether `note` is in the scale on `tonic` (any octave)
+    pub fn contains(&self, tonic: u8, note: u8) -> bool {
+        self.intervals().contains(&((note % 12 + 12 - tonic % 12) % 12))
+    }
+
+    /// Nearest note of the scale on `tonic`; a note halfway between two scale tones goes down
+    pub fn snap(&self, tonic: u8, note: u8) -> u8 {
+        let note = note.min(127);
+        (0..12u8)
+            .find_map(|distance| {
+                let below = note.checked_sub(distance).filter(|&below| self.contains(tonic, below));
+                below.or_else(|| Some(note + distance).filter(|&above| above <= 127 && self.contains(tonic, above)))
+            })
+            .unwrap_or(note)
+    }
+}
+
+/// Note name like "C4" or "Eb3" (middle C = C4)
+pub fn note_name(note: u8, flats: bool) -> String {
+    let names = if flats { &FLAT_NAMES } else { &SHARP_NAMES };
+    format!("{}{}", names[(note % 12) as usize], (note / 12) as i32 - 1)
+}
+
+/// Pitch class (0-11) of a note letter with any sharps (#) or flats (b), e.g. "F#", "bb", "Cb"
+pub fn pitch_class(name: &str) -> Option<u8> {
+    let mut chars = name.chars();
+    let letter = match chars.next()?.to_ascii_uppercase() {
+        'C' => 0, 'D' => 2, 'E' => 4, 'F' => 5, 'G' => 7, 'A' => 9, 'B' => 11,
+        _ => return None,
+    };
+    chars.try_fold(letter + 12, |class: i32, accidental| match accidental {
+        '#' => Some(class + 1),
+        'b' => Some(class - 1),
+        _ => None,
+    })
+    .map(|class| class.rem_euclid(12) as u8)
+}
+
+/// MIDI note of a name like "C4", "Eb3", "f#5" or "C-1"; the octave follows the letter,
+/// so "B#3" is C4 and "Cb4" is B3
+pub fn parse_note_name(name: &str) -> Option<u8> {
+    let name = name.trim();
+    let split = name.char_indices().skip(1).find(|&(_, c)| c == '-' || c.is_ascii_digit())?.0;
+    let (pitch, octave) = name.split_at(split);
+    let octave: i32 = octave.parse().ok()?;
+    let letter = pitch_class(&pitch[..1])? as i32;
+    let accidentals = pitch_class(pitch)? as i32 - letter;
+    // Accidentals past B or below C cross into the next octave
+    let offset = match accidentals {
+        a if a > 6 => a - 12,
+        a if a < -6 => a + 12,
+        a => a,
+    };
+    let note = (octave + 1) * 12 + letter + offset;
+    (0..=127).contains(&note).then_some(note as u8)
+}
+
+/// Equal-tempered frequency of a note (A4 = 440 Hz)
+pub fn note_frequency(note: u8) -> f32 {
+    440.0 * 2f32.powf((note as f32 - 69.0) / 12.0)
+}
+
+/// Nearest note to a frequency, None outside the MIDI range
+pub fn frequency_note(frequency: f32) -> Option<u8> {
+    if frequency.is_nan() || frequency <= 0.0 {
+        return None;
+    }
+    let note = (69.0 + 12.0 * (frequency / 440.0).log2()).round();
+    (0.0..=127.0).contains(&note).then_some(note as u8)
+}
+
+// ===== WASM EXPORTS =====
+
+/// Chord notes on `root` for a quality name or symbol ("major", "m7", "dim", ...) in the
+/// given inversion (empty if unknown or out of range)
+#[wasm_bindgen]
+pub fn build_chord(root: u8, quality: &str, inversion: u8) -> Vec<u8> {
+    ChordQuality::from_name(quality)
+        .and_then(|quality| quality.notes(root, inversion))
+        .unwrap_or_default()
+}
+
+/// Ascending scale from `tonic` over `octaves` octaves (empty for an unknown scale name)
+#[wasm_bindgen]
+pub fn build_scale(tonic: u8, scale: &str, octaves: u8) -> Vec<u8> {
+    Scale::from_name(scale).map(|scale| scale.notes(tonic, octaves)).unwrap_or_default()
+}
+
+/// Nearest note of the scale on `tonic` (returns 255 for an unknown scale name)
+#[wasm_bindgen]
+pub fn snap_note_to_scale(note: u8, tonic: u8, scale: &str) -> u8 {
+    Scale::from_name(scale).map(|scale| scale.snap(tonic, note)).unwrap_or(255)
+}
+
+/// Convert MIDI note to a flat-spelled name (63 -> "Eb4")
+#[wasm_bindgen]
+pub fn midi_note_to_flat_name(note: u8) -> String {
+    note_name(note, true)
+}
+
+#[wasm_bindgen]
+pub fn midi_note_to_frequency(note: u8) -> f32 {
+    note_frequency(note)
+}
+
+/// Nearest MIDI note to a frequency (returns 255 outside the MIDI range)
+#[wasm_bindgen]
+pub fn frequency_to_midi_note(frequency: f32) -> u8 {
+    frequency_note(frequency).unwrap_or(255)
+}
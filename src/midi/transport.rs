@@ -0,0 +1,69 @@
+/**
+ * AWE Player - MIDI Transport Control
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * External transport messages mapped to sequencer actions. MIDI Machine
+ * Control (F0 7F <dev> 06 <command> ... F7) covers Stop, Play, Deferred
+ * Play, Pause and Locate to an SMPTE time; Song Position Pointer
+ * (F2 <lsb> <msb>) locates in MIDI beats (sixteenth notes) from the start.
+ */
+
+/// Transport action requested by an external controller
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransportCommand {
+    Play,
+    Stop,
+    Pause,
+    /// Locate to a time in seconds (MMC Locate)
+    LocateSeconds(f64),
+    /// Locate to a position in MIDI beats (Song Position Pointer)
+    LocateBeats(u16),
+}
+
+const MMC_STOP: u8 = 0x01;
+const MMC_PLAY: u8 = 0x02;
+const MMC_DEFERRED_PLAY: u8 = 0x03;
+const MMC_PAUSE: u8 = 0x09;
+const MMC_LOCATE: u8 = 0x44;
+
+/// Parse an MMC command (any device ID, including 7F = all)
+pub fn parse_mmc(data: &[u8]) -> Option<TransportCommand> {
+    match data {
+        [0xF0, 0x7F, _device, 0x06, MMC_STOP, 0xF7, ..] => Some(TransportCommand::Stop),
+        [0xF0, 0x7F, _device, 0x06, MMC_PLAY | MMC_DEFERRED_PLAY, 0xF7, ..] => Some(TransportCommand::Play),
+        [0xF0, 0x7F, _device, 0x06, MMC_PAUSE, 0xF7, ..] => Some(TransportCommand::Pause),
+        // Locate TARGET: 44 06 01 <hr> <mn> <sc> <fr> <ff>
+        [0xF0, 0x7F, _device, 0x06, MMC_LOCATE, 0x06, 0x01, hours, minutes, seconds, frames, subframes, 0xF7, ..] => {
+            Some(TransportCommand::LocateSeconds(smpte_to_seconds(*hours, *minutes, *seconds, *frames, *subframes)))
+        }
+        _ => None,
+    }
+}
+
+/// Parse a Song Position Pointer message (F2 <lsb> <msb>)
+pub fn parse_song_position(data: &[u8]) -> Option<TransportCommand> {
+    match data {
+        [0xF2, lsb, msb, ..] if lsb & 0x80 == 0 && msb & 0x80 == 0 => {
+            Some(TransportCommand::LocateBeats(((*msb as u16) << 7) | *lsb as u16))
+        }
+        _ => None,
+    }
+}
+
+/// Sequencer tick for a Song Position Pointer value (one MIDI beat = a sixteenth note)
+pub fn midi_beats_to_ticks(beats: u16, ticks_per_quarter: u16) -> u64 {
+    beats as u64 * ticks_per_quarter as u64 / 4
+}
+
+/// MMC time code: the hours byte carries the frame rate in bits 5-6
+/// (0 = 24, 1 = 25, 2 = 29.97 drop-frame, 3 = 30 fps); subframes are 1/100 frame
+fn smpte_to_seconds(hours: u8, minutes: u8, seconds: u8, frames: u8, subframes: u8) -> f64 {
+    let fps = match (hours >> 5) & 0x03 {
+        0 => 24.0,
+        1 => 25.0,
+        2 => 29.97,
+        _ => 30.0,
+    };
+    let whole = (hours & 0x1F) as f64 * 3600.0 + (minutes & 0x3F) as f64 * 60.0 + (seconds & 0x3F) as f64;
+    whole + ((frames & 0x1F) as f64 + (subframes & 0x7F) as f64 / 100.0) / fps
+}
@@ -0,0 +1,119 @@
+/**
+ * AWE Player - Standard MIDI File Writer
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * The counterpart of the parser: tracks in, SMF bytes out. One track is
+ * written as format 0, several as format 1. Events are written in the
+ * order the track holds them with delta times taken from their absolute
+ * ticks, so tracks should be in time order (edit::normalize keeps them so).
+ * A track's name goes out as a Track Name event unless it holds one, note-offs
+ * are written as 0x80 messages, running status is not used, and every track
+ * ends with an End of Track at or after its last event.
+ */
+
+use super::constants::*;
+use super::parser::{MetaEventType, MidiEventType, MidiTrack};
+
+/// Largest delta time a four-byte VLQ holds
+const MAX_DELTA: u64 = 0x0FFF_FFFF;
+
+/// SMF bytes of `tracks` at `division` ticks per quarter note
+pub fn write_smf(division: u16, tracks: &[MidiTrack]) -> Vec<u8> {
+    let format: u16 = if tracks.len() == 1 { 0 } else { 1 };
+    let mut bytes = b"MThd".to_vec();
+    bytes.extend_from_slice(&6u32.to_be_bytes());
+    bytes.extend_from_slice(&format.to_be_bytes());
+    bytes.extend_from_slice(&(tracks.len() as u16).to_be_bytes());
+    bytes.extend_from_slice(&division.to_be_bytes());
+    for track in tracks {
+        write_track(track, &mut bytes);
+    }
+    bytes
+}
+
+fn write_track(track: &MidiTrack, out: &mut Vec<u8>) {
+    let mut body = Vec::new();
+    let named = track.events.iter().any(|event| matches!(event.event_type, MidiEventType::MetaEvent(MetaEventType::TrackName { .. })));
+    if let (Some(name), false) = (&track.name, named) {
+        body.push(0x00);
+        write_meta(&MetaEventType::TrackName { name: name.clone() }, &mut body);
+    }
+    let mut previous = 0u64;
+    let mut ended = false;
+    for event in &track.events {
+        if ended {
+            break;
+        }
+        let tick = event.absolute_time.max(previous);
+        write_vlq((tick - previous).min(MAX_DELTA) as u32, &mut body);
+        previous = tick;
+        ended = matches!(event.event_type, MidiEventType::MetaEvent(MetaEventType::EndOfTrack));
+        write_event(&event.event_type, &mut body);
+    }
+    if !ended {
+        body.extend_from_slice(&[0x00, MIDI_STATUS_META_EVENT, META_EVENT_END_OF_TRACK, 0x00]);
+    }
+    out.extend_from_slice(b"MTrk");
+    out.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    out.extend_from_slice(&body);
+}
+
+fn write_event(event_type: &MidiEventType, out: &mut Vec<u8>) {
+    match *event_type {
+        MidiEventType::NoteOff { channel, note, velocity } => out.extend_from_slice(&[0x80 | channel, note, velocity]),
+        MidiEventType::NoteOn { channel, note, velocity } => out.extend_from_slice(&[0x90 | channel, note, velocity]),
+        MidiEventType::ControlChange { channel, controller, value } => out.extend_from_slice(&[0xB0 | channel, controller, value]),
+        MidiEventType::ProgramChange { channel, program } => out.extend_from_slice(&[0xC0 | channel, program]),
+        MidiEventType::PitchBend { channel, value } => {
+            let bend = (value as i32 + 8192).clamp(0, 16383) as u16;
+            out.extend_from_slice(&[0xE0 | channel, (bend & 0x7F) as u8, (bend >> 7) as u8]);
+        }
+        MidiEventType::SysEx { ref data } => {
+            // Stored with its F0 (see the parser); anything else goes out as an F7 escape
+            let (status, payload) = match data.split_first() {
+                Some((&MIDI_STATUS_SYSEX_START, rest)) => (MIDI_STATUS_SYSEX_START, rest),
+                _ => (MIDI_STATUS_SYSEX_END, data.as_slice()),
+            };
+            out.push(status);
+            write_vlq(payload.len() as u32, out);
+            out.extend_from_slice(payload);
+        }
+        MidiEventType::MetaEvent(ref meta) => write_meta(meta, out),
+    }
+}
+
+fn write_meta(meta: &MetaEventType, out: &mut Vec<u8>) {
+    let (meta_type, data) = match *meta {
+        MetaEventType::SetTempo { microseconds_per_quarter } => {
+            (META_EVENT_SET_TEMPO, microseconds_per_quarter.min(0xFF_FFFF).to_be_bytes()[1..].to_vec())
+        }
+        MetaEventType::TimeSignature { numerator, denominator, clocks_per_click, notes_per_quarter } => {
+            // Denominator goes out as a power of two
+            let power = denominator.max(1).ilog2() as u8;
+            (META_EVENT_TIME_SIGNATURE, vec![numerator, power, clocks_per_click, notes_per_quarter])
+        }
+        MetaEventType::TrackName { ref name } => (META_EVENT_TRACK_NAME, name.as_bytes().to_vec()),
+        MetaEventType::EndOfTrack => (META_EVENT_END_OF_TRACK, Vec::new()),
+    };
+    out.extend_from_slice(&[MIDI_STATUS_META_EVENT, meta_type]);
+    write_vlq(data.len() as u32, out);
+    out.extend_from_slice(&data);
+}
+
+/// Variable-length quantity: 7 bits per byte, high bit set on all but the last
+fn write_vlq(value: u32, out: &mut Vec<u8>) {
+    let mut groups = [0u8; 5];
+    let mut count = 0;
+    let mut rest = value;
+    loop {
+        groups[count] = (rest & 0x7F) as u8;
+        count += 1;
+        rest >>= 7;
+        if rest == 0 {
+            break;
+        }
+    }
+    for index in (0..count).rev() {
+        out.push(groups[index] | if index > 0 { 0x80 } else { 0 });
+    }
+}
@@ -0,0 +1,328 @@
+/**
+ * AWE Player - Versioned Bridge Protocol
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Typed command/response protocol between main thread and AudioWorklet.
+ * Every command is a JSON object tagged by `type`; every response is too.
+ *
+ * Handshake: the first command must be `hello` carrying the caller's
+ * protocol version. The engine answers with its own version and the list
+ * of capabilities it supports; all other commands are rejected with
+ * `handshakeRequired` until a compatible `hello` has been received.
+ *
+ * Binary payloads (SF2 / MIDI files) travel in the separate `payload`
+ * argument of `bridge_command_with_payload()` rather than inside the JSON.
+ *
+ * The version negotiated here is the only protocol version: the embedded
+ * AudioWorklet processor sends `hello` with its init message's version
+ * and stamps replies with the negotiated one. Loose exports that duplicate
+ * a command (queue_midi_event_global, set_buffer_size_global, ...) run
+ * through `execute()` and are deprecated; docs/WASM_EXPORTS.md maps each
+ * one to its command.
+ */
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU32, Ordering};
+use crate::worklet::AudioWorkletBridge;
+use crate::events::LoadErrorCode;
+use crate::error::{ErrorCode, ErrorEvent};
+
+/// Current bridge protocol version
+pub const BRIDGE_PROTOCOL_VERSION: u32 = 1;
+
+/// Oldest client protocol version this engine still accepts
+pub const BRIDGE_MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// Capabilities advertised in the handshake response
+pub const BRIDGE_CAPABILITIES: &[&str] = &[
+    "init",
+    "loadSoundFont",
+    "selectPreset",
+    "loadMidiFile",
+    "midi",
+    "transport",
+    "setParam",
+    "telemetry",
+];
+
+/// Transport actions for the `transport` command
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum TransportAction {
+    Play,
+    Pause,
+    Stop,
+    Seek,
+    Tempo,
+    PlayWithFade,   // value = fade time in ms
+    StopWithFade,
+}
+
+/// Commands sent from main thread to the engine
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum BridgeCommand {
+    Hello { protocol_version: u32 },
+    Init { sample_rate: f32 },
+    LoadSoundFont,
+    SelectPreset { bank: u16, program: u8 },
+    LoadMidiFile,
+    Midi {
+        #[serde(default)]
+        #[cfg_attr(feature = "ts", ts(as = "Option<u32>", optional))]
+        timestamp: u32,
+        channel: u8,
+        message_type: u8,
+        data1: u8,
+        data2: u8,
+    },
+    Transport {
+        action: TransportAction,
+        #[serde(default)]
+        #[cfg_attr(feature = "ts", ts(as = "Option<f64>", optional))]
+        value: f64,
+    },
+    SetParam { name: String, value: f64 },
+    GetTelemetry,
+}
+
+/// Error codes returned in `error` responses
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum BridgeErrorCode {
+    InvalidCommand,
+    HandshakeRequired,
+    VersionMismatch,
+    NotInitialized,
+    MissingPayload,
+    LoadFailed,
+    UnknownParam,
+    InvalidValue,
+}
+
+/// Responses sent from the engine back to the main thread
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum BridgeResponse {
+    Hello {
+        protocol_version: u32,
+        min_protocol_version: u32,
+        capabilities: Vec<String>,
+    },
+    Ack { command: String },
+    Error { code: BridgeErrorCode, message: String },
+    Telemetry {
+        sample_rate: f32,
+        buffer_size: usize,
+        active_voices: usize,
+        soundfont_loaded: bool,
+        playback_state: u8,
+        position_seconds: f64,
+        duration_seconds: f64,
+    },
+}
+
+impl BridgeResponse {
+    fn ack(command: &str) -> Self {
+        BridgeResponse::Ack { command: command.to_string() }
+    }
+
+    fn error(code: BridgeErrorCode, message: impl Into<String>) -> Self {
+        BridgeResponse::Error { code, message: message.into() }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self)
+            .unwrap_or_else(|_| r#"{"type":"error","code":"invalidCommand","message":"Serialization failed"}"#.to_string())
+    }
+}
+
+/// Negotiated client protocol version (0 until handshake completes)
+static NEGOTIATED_VERSION: AtomicU32 = AtomicU32::new(0);
+
+/// Whether a compatible `hello` has been received
+pub fn is_handshake_complete() -> bool {
+    NEGOTIATED_VERSION.load(Ordering::Relaxed) != 0
+}
+
+/// Client protocol version agreed in the last `hello` (None before the handshake)
+pub fn negotiated_version() -> Option<u32> {
+    Some(NEGOTIATED_VERSION.load(Ordering::Relaxed)).filter(|&version| version != 0)
+}
+
+/// Parse and execute one command (payload only used by load commands)
+pub fn handle_command(command_json: &str, payload: Option<&[u8]>) -> BridgeResponse {
+    let command: BridgeCommand = match serde_json::from_str(command_json) {
+        Ok(command) => command,
+        Err(e) => return BridgeResponse::error(BridgeErrorCode::InvalidCommand, format!("Invalid command: {}", e)),
+    };
+
+    if !matches!(command, BridgeCommand::Hello { .. }) && !is_handshake_complete() {
+        return BridgeResponse::error(BridgeErrorCode::HandshakeRequired, "Send 'hello' before other commands");
+    }
+    execute(command, payload)
+}
+
+/// Execute a parsed command without the handshake check (loose exports call this)
+pub fn execute(command: BridgeCommand, payload: Option<&[u8]>) -> BridgeResponse {
+    match command {
+        BridgeCommand::Hello { protocol_version } => negotiate(protocol_version),
+        BridgeCommand::Init { sample_rate } => {
+            if crate::init_all_systems(sample_rate) {
+                BridgeResponse::ack("init")
+            } else {
+                BridgeResponse::error(BridgeErrorCode::NotInitialized, "System initialization failed")
+            }
+        }
+        BridgeCommand::LoadSoundFont => {
+            let Some(data) = payload else {
+                return BridgeResponse::error(BridgeErrorCode::MissingPayload, "loadSoundFont requires SF2 payload");
+            };
+            let soundfont = match crate::parse_soundfont_reporting(data) {
+                Ok(soundfont) => soundfont,
+                Err(e) => return BridgeResponse::error(BridgeErrorCode::LoadFailed, e.to_string()),
+            };
+            match crate::install_soundfont_reporting(soundfont) {
+                Ok(()) => BridgeResponse::ack("loadSoundFont"),
+                Err((LoadErrorCode::NotInitialized, e)) => BridgeResponse::error(BridgeErrorCode::NotInitialized, e),
+                Err((_, e)) => BridgeResponse::error(BridgeErrorCode::LoadFailed, e),
+            }
+        }
+        BridgeCommand::SelectPreset { bank, program } => {
+            with_bridge(|bridge| match bridge.select_preset_internal(bank, program) {
+                Ok(_) => BridgeResponse::ack("selectPreset"),
+                Err(e) => BridgeResponse::error(BridgeErrorCode::LoadFailed, e),
+            })
+        }
+        BridgeCommand::LoadMidiFile => {
+            let Some(data) = payload else {
+                return BridgeResponse::error(BridgeErrorCode::MissingPayload, "loadMidiFile requires MIDI payload");
+            };
+            with_bridge(|bridge| match bridge.midi_player_mut().load_midi_data(data) {
+                Ok(()) => BridgeResponse::ack("loadMidiFile"),
+                Err(_) => BridgeResponse::error(BridgeErrorCode::LoadFailed, "Failed to parse MIDI file"),
+            })
+        }
+        BridgeCommand::Midi { timestamp, channel, message_type, data1, data2 } => {
+            with_bridge(|bridge| {
+                bridge.queue_midi_event(timestamp as u64, channel, message_type, data1, data2);
+                BridgeResponse::ack("midi")
+            })
+        }
+        BridgeCommand::Transport { action, value } => {
+            with_bridge(|bridge| {
+                let player = bridge.midi_player_mut();
+                match action {
+                    TransportAction::Play => player.play(),
+                    TransportAction::Pause => player.pause(),
+                    TransportAction::Stop => player.stop(),
+                    TransportAction::Seek => player.seek(value),
+                    TransportAction::Tempo => player.set_tempo_multiplier(value),
+                    TransportAction::PlayWithFade => player.play_with_fade(value as f32),
+                    TransportAction::StopWithFade => player.stop_with_fade(value as f32),
+                }
+                BridgeResponse::ack("transport")
+            })
+        }
+        BridgeCommand::SetParam { name, value } => {
+            with_bridge(|bridge| match name.as_str() {
+                "bufferSize" => {
+                    if bridge.set_buffer_size(value as usize) {
+                        BridgeResponse::ack("setParam")
+                    } else {
+                        BridgeResponse::error(BridgeErrorCode::InvalidValue, format!("Buffer size {} outside 32-1024 frames", value))
+                    }
+                }
+                "adaptiveMode" => {
+                    bridge.set_adaptive_mode(value != 0.0);
+                    BridgeResponse::ack("setParam")
+                }
+                _ => BridgeResponse::error(BridgeErrorCode::UnknownParam, format!("Unknown parameter '{}'", name)),
+            })
+        }
+        BridgeCommand::GetTelemetry => {
+            with_bridge(|bridge| {
+                let sample_rate = bridge.get_sample_rate();
+                let buffer_size = bridge.get_buffer_size();
+                let soundfont_loaded = bridge.is_soundfont_loaded_internal();
+                let player = bridge.midi_player_mut();
+                BridgeResponse::Telemetry {
+                    sample_rate,
+                    buffer_size,
+                    active_voices: player.get_active_voice_count(),
+                    soundfont_loaded,
+                    playback_state: player.get_playback_state(),
+                    position_seconds: player.get_position_seconds(),
+                    duration_seconds: player.get_duration_seconds(),
+                }
+            })
+        }
+    }
+}
+
+/// Reset handshake state (next command must be `hello` again)
+pub fn reset_handshake() {
+    NEGOTIATED_VERSION.store(0, Ordering::Relaxed);
+}
+
+fn negotiate(client_version: u32) -> BridgeResponse {
+    if !(BRIDGE_MIN_PROTOCOL_VERSION..=BRIDGE_PROTOCOL_VERSION).contains(&client_version) {
+        reset_handshake();
+        return BridgeResponse::error(
+            BridgeErrorCode::VersionMismatch,
+            format!("Client protocol {} unsupported (engine supports {}-{})",
+                    client_version, BRIDGE_MIN_PROTOCOL_VERSION, BRIDGE_PROTOCOL_VERSION),
+        );
+    }
+
+    NEGOTIATED_VERSION.store(client_version, Ordering::Relaxed);
+    BridgeResponse::Hello {
+        protocol_version: BRIDGE_PROTOCOL_VERSION,
+        min_protocol_version: BRIDGE_MIN_PROTOCOL_VERSION,
+        capabilities: BRIDGE_CAPABILITIES.iter().map(|c| c.to_string()).collect(),
+    }
+}
+
+/// Execute a command for a loose export: error responses become thrown ErrorEvents
+pub(crate) fn execute_export(command: BridgeCommand) -> Result<BridgeResponse, ErrorEvent> {
+    match execute(command, None) {
+        BridgeResponse::Error { code: BridgeErrorCode::NotInitialized, .. } => Err(ErrorEvent::not_initialized()),
+        BridgeResponse::Error { code: BridgeErrorCode::InvalidValue | BridgeErrorCode::UnknownParam, message } => {
+            Err(ErrorEvent::raise(ErrorCode::InvalidArgument, message))
+        }
+        BridgeResponse::Error { message, .. } => Err(ErrorEvent::raise(ErrorCode::Internal, message)),
+        response => Ok(response),
+    }
+}
+
+fn with_bridge(f: impl FnOnce(&mut AudioWorkletBridge) -> BridgeResponse) -> BridgeResponse {
+    match crate::global_bridge_mut() {
+        Some(bridge) => f(bridge),
+        None => BridgeResponse::error(BridgeErrorCode::NotInitialized, "AudioWorklet bridge not initialized"),
+    }
+}
+
+// ===== WASM EXPORTS =====
+
+/// Execute a bridge command (JSON) and return the response (JSON)
+#[wasm_bindgen]
+pub fn bridge_command(command_json: &str) -> String {
+    handle_command(command_json, None).to_json()
+}
+
+/// Execute a bridge command carrying a binary payload (SF2 / MIDI file)
+#[wasm_bindgen]
+pub fn bridge_command_with_payload(command_json: &str, payload: &[u8]) -> String {
+    handle_command(command_json, Some(payload)).to_json()
+}
+
+/// Get bridge protocol version
+#[wasm_bindgen]
+pub fn get_bridge_protocol_version() -> u32 {
+    BRIDGE_PROTOCOL_VERSION
+}
@@ -0,0 +1,422 @@
+/**
+ * AWE Player - Engine Self-Test
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * One entry point for checking that a build works on the page it runs in.
+ * Checks are grouped by level so a host can run only what it can afford:
+ *
+ * - engine: SoundFont parsing and a scratch synth (envelope, synthesis,
+ *   voice allocation). Needs nothing loaded and leaves the engine alone.
+ * - soundfont: the loaded SoundFont's sample data, and a chord played on
+ *   the live engine through channel 0's preset.
+ * - pipeline: the worklet bridge and a block rendered through it.
+ *
+ * Checks that use the live engine only run while it is idle (no song
+ * playing and no voices sounding) and are skipped otherwise, so a
+ * self-test never talks over playback. Every item reports pass, fail or
+ * skip with a message and the numbers it measured.
+ */
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use crate::soundfont::{SoundFontModule, SoundFontParser};
+use crate::synth::voice_manager::{VoiceManager, MAX_VOICES};
+use crate::worklet::AudioWorkletBridge;
+use crate::error::{ErrorCode, ErrorEvent, ExportScope};
+
+/// Sample rate of the scratch synth when no bridge is running
+const SCRATCH_SAMPLE_RATE: f32 = 44100.0;
+/// Longest release the envelope check waits for (seconds)
+const MAX_RELEASE_SECONDS: f32 = 2.0;
+/// Output below this counts as silence
+const SILENCE: f32 = 0.001;
+/// Notes of the chord the synthesis checks play (C major)
+const TEST_CHORD: [u8; 3] = [60, 64, 67];
+
+/// Group of checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum SelfTestLevel {
+    Engine,
+    SoundFont,
+    Pipeline,
+}
+
+impl SelfTestLevel {
+    pub const ALL: [SelfTestLevel; 3] = [SelfTestLevel::Engine, SelfTestLevel::SoundFont, SelfTestLevel::Pipeline];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "engine" => Some(SelfTestLevel::Engine),
+            "soundfont" => Some(SelfTestLevel::SoundFont),
+            "pipeline" => Some(SelfTestLevel::Pipeline),
+            _ => None,
+        }
+    }
+
+    /// Levels from a comma-separated list ("engine,pipeline"); empty or "all" is every level
+    /// None if any name is unknown
+    pub fn parse_list(list: &str) -> Option<Vec<Self>> {
+        let list = list.trim();
+        if list.is_empty() || list == "all" {
+            return Some(Self::ALL.to_vec());
+        }
+        let mut levels = Vec::new();
+        for name in list.split(',') {
+            let level = Self::from_name(name.trim())?;
+            if !levels.contains(&level) {
+                levels.push(level);
+            }
+        }
+        Some(levels)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum SelfTestStatus {
+    Pass,
+    Fail,
+    Skip,       // Could not run here (nothing loaded, engine busy)
+}
+
+/// A number a check measured
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Measurement {
+    pub name: String,
+    pub value: f64,
+    pub unit: String,           // Empty for plain counts
+}
+
+/// Outcome of one check
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SelfTestItem {
+    pub level: SelfTestLevel,
+    pub name: String,
+    pub status: SelfTestStatus,
+    pub message: String,
+    pub measurements: Vec<Measurement>,
+}
+
+/// Every check of a run, in the order they ran (run_self_test)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SelfTestReport {
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub items: Vec<SelfTestItem>,
+}
+
+impl SelfTestReport {
+    fn new(items: Vec<SelfTestItem>) -> Self {
+        let count = |status| items.iter().filter(|item| item.status == status).count();
+        Self {
+            passed: count(SelfTestStatus::Pass),
+            failed: count(SelfTestStatus::Fail),
+            skipped: count(SelfTestStatus::Skip),
+            items,
+        }
+    }
+
+    pub fn item(&self, name: &str) -> Option<&SelfTestItem> {
+        self.items.iter().find(|item| item.name == name)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| "null".to_string())
+    }
+}
+
+/// Builds one item; measurements are added as the check goes
+struct Check {
+    level: SelfTestLevel,
+    name: &'static str,
+    measurements: Vec<Measurement>,
+}
+
+impl Check {
+    fn new(level: SelfTestLevel, name: &'static str) -> Self {
+        Self { level, name, measurements: Vec::new() }
+    }
+
+    fn measure(mut self, name: &str, value: f64, unit: &str) -> Self {
+        self.measurements.push(Measurement { name: name.to_string(), value, unit: unit.to_string() });
+        self
+    }
+
+    fn finish(self, status: SelfTestStatus, message: impl Into<String>) -> SelfTestItem {
+        SelfTestItem {
+            level: self.level,
+            name: self.name.to_string(),
+            status,
+            message: message.into(),
+            measurements: self.measurements,
+        }
+    }
+
+    fn pass(self, message: impl Into<String>) -> SelfTestItem {
+        self.finish(SelfTestStatus::Pass, message)
+    }
+
+    fn fail(self, message: impl Into<String>) -> SelfTestItem {
+        self.finish(SelfTestStatus::Fail, message)
+    }
+
+    fn skip(self, message: impl Into<String>) -> SelfTestItem {
+        self.finish(SelfTestStatus::Skip, message)
+    }
+
+    /// Pass or fail on `ok`
+    fn verdict(self, ok: bool, pass: impl Into<String>, fail: impl Into<String>) -> SelfTestItem {
+        if ok { self.pass(pass) } else { self.fail(fail) }
+    }
+}
+
+/// Run the checks of `levels`; checks that need the bridge are skipped without one
+pub fn run(mut bridge: Option<&mut AudioWorkletBridge>, levels: &[SelfTestLevel]) -> SelfTestReport {
+    let sample_rate = bridge.as_ref().map_or(SCRATCH_SAMPLE_RATE, |bridge| bridge.get_sample_rate());
+    let mut items = Vec::new();
+    for &level in levels {
+        match level {
+            SelfTestLevel::Engine => {
+                items.push(check_header_validation());
+                items.push(check_parsing());
+                items.push(check_envelope(sample_rate));
+                items.push(check_synthesis(sample_rate));
+                items.push(check_voice_allocation(sample_rate));
+            }
+            SelfTestLevel::SoundFont => {
+                items.push(check_sample_data(bridge.as_deref()));
+                items.push(check_preset_synthesis(bridge.as_deref_mut()));
+            }
+            SelfTestLevel::Pipeline => {
+                items.push(check_bridge(bridge.as_deref()));
+                items.push(check_block_render(bridge.as_deref_mut()));
+            }
+        }
+    }
+    SelfTestReport::new(items)
+}
+
+/// Smallest file the parser accepts: INFO and sdta lists with a short ramp of samples
+pub fn minimal_soundfont(name: &str) -> Vec<u8> {
+    fn chunk(id: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut chunk = id.to_vec();
+        chunk.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        chunk.extend_from_slice(data);
+        if data.len() % 2 == 1 {
+            chunk.push(0);
+        }
+        chunk
+    }
+    fn list(form: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        chunk(b"LIST", &[form.as_slice(), body].concat())
+    }
+
+    let mut info = chunk(b"ifil", &[0x02, 0x00, 0x01, 0x00]);
+    info.extend(chunk(b"isng", b"EMU8000\0"));
+    info.extend(chunk(b"INAM", format!("{}\0", name).as_bytes()));
+    let samples: Vec<u8> = (0..64i16).flat_map(|sample| (sample * 100).to_le_bytes()).collect();
+
+    let mut body = b"sfbk".to_vec();
+    body.extend(list(b"INFO", &info));
+    body.extend(list(b"sdta", &chunk(b"smpl", &samples)));
+    chunk(b"RIFF", &body)
+}
+
+fn check_header_validation() -> SelfTestItem {
+    let check = Check::new(SelfTestLevel::Engine, "soundFontHeader");
+    let good = SoundFontModule::validate_soundfont_header(&minimal_soundfont("Self Test")).is_ok();
+    let bad = SoundFontModule::validate_soundfont_header(b"RIFF\x04\x00\x00\x00WAVE").is_err();
+    check.verdict(good && bad, "RIFF/sfbk header accepted, other RIFF forms refused",
+                  if good { "Header check accepted a WAVE file" } else { "Header check refused a valid SoundFont" })
+}
+
+fn check_parsing() -> SelfTestItem {
+    let check = Check::new(SelfTestLevel::Engine, "soundFontParsing");
+    let file = minimal_soundfont("Self Test");
+    let check = check.measure("bytes", file.len() as f64, "bytes");
+    match SoundFontParser::parse_soundfont(&file) {
+        Ok(soundfont) => {
+            let check = check.measure("version", soundfont.header.version.major as f64, "");
+            check.verdict(soundfont.header.name == "Self Test", "Parsed a generated SoundFont",
+                          format!("Parsed name '{}' instead of 'Self Test'", soundfont.header.name))
+        }
+        Err(e) => check.fail(format!("Parser refused a generated SoundFont: {}", e)),
+    }
+}
+
+/// Note on, release, and the time until the voice is free again
+fn check_envelope(sample_rate: f32) -> SelfTestItem {
+    let check = Check::new(SelfTestLevel::Engine, "envelope");
+    let mut voices = VoiceManager::new(sample_rate);
+    if voices.note_on(60, 100, 0).is_none() {
+        return check.fail("No voice started for a note on");
+    }
+    for _ in 0..(sample_rate * 0.05) as usize {
+        voices.process();
+    }
+    voices.note_off(60);
+    let limit = (sample_rate * MAX_RELEASE_SECONDS) as usize;
+    let released = (0..limit).position(|_| {
+        voices.process();
+        voices.get_active_voice_count() == 0
+    });
+    match released {
+        Some(samples) => check
+            .measure("releaseTime", samples as f64 / sample_rate as f64 * 1000.0, "ms")
+            .pass("Voice released and freed"),
+        None => check.fail(format!("Voice still active {} s after note off", MAX_RELEASE_SECONDS)),
+    }
+}
+
+/// Chord on the built-in bank: audible and finite (the voice mix is pre-master, so it may
+/// exceed full scale)
+fn check_synthesis(sample_rate: f32) -> SelfTestItem {
+    let check = Check::new(SelfTestLevel::Engine, "synthesis");
+    let mut voices = VoiceManager::new(sample_rate);
+    let started = TEST_CHORD.iter().filter(|&&note| voices.note_on(note, 100, 0).is_some()).count();
+    let mut peak = 0.0f32;
+    let mut finite = true;
+    for _ in 0..(sample_rate * 0.1) as usize {
+        let (left, right) = voices.process();
+        finite &= left.is_finite() && right.is_finite();
+        peak = peak.max(left.abs()).max(right.abs());
+    }
+    let check = check.measure("voices", started as f64, "").measure("peak", peak as f64, "");
+    if started != TEST_CHORD.len() {
+        check.fail(format!("{} of {} chord notes started a voice", started, TEST_CHORD.len()))
+    } else if !finite {
+        check.fail("Output contains NaN or infinite samples")
+    } else {
+        check.verdict(peak >= SILENCE, "Chord audible", "Chord produced no audio")
+    }
+}
+
+/// More notes than voices: the pool fills, then steals rather than refusing
+fn check_voice_allocation(sample_rate: f32) -> SelfTestItem {
+    let check = Check::new(SelfTestLevel::Engine, "voiceAllocation");
+    let mut voices = VoiceManager::new(sample_rate);
+    let notes = MAX_VOICES + 4;
+    let started = (0..notes).filter(|&index| voices.note_on(36 + (index % 64) as u8, 100, (index % 9) as u8).is_some()).count();
+    let active = voices.get_active_voice_count();
+    voices.stop_all_sound();
+    let check = check.measure("started", started as f64, "").measure("active", active as f64, "")
+        .measure("maxVoices", MAX_VOICES as f64, "");
+    if started != notes {
+        check.fail(format!("{} of {} notes got a voice", started, notes))
+    } else {
+        check.verdict(active == MAX_VOICES, format!("All {} voices in use, extra notes stole voices", MAX_VOICES),
+                      format!("{} of {} voices active", active, MAX_VOICES))
+    }
+}
+
+fn check_sample_data(bridge: Option<&AudioWorkletBridge>) -> SelfTestItem {
+    let check = Check::new(SelfTestLevel::SoundFont, "sampleData");
+    let Some(soundfont) = bridge.and_then(|bridge| bridge.get_loaded_soundfont()) else {
+        return check.skip("No SoundFont loaded");
+    };
+    let with_data = soundfont.samples.iter().filter(|sample| sample.sample_data.iter().any(|&value| value != 0)).count();
+    let frames: usize = soundfont.samples.iter().map(|sample| sample.sample_data.len()).sum();
+    let check = check
+        .measure("samples", soundfont.samples.len() as f64, "")
+        .measure("samplesWithData", with_data as f64, "")
+        .measure("sampleFrames", frames as f64, "")
+        .measure("presets", soundfont.presets.len() as f64, "")
+        .measure("instruments", soundfont.instruments.len() as f64, "");
+    if soundfont.samples.is_empty() {
+        check.fail("SoundFont has no samples")
+    } else if soundfont.presets.is_empty() {
+        check.fail("SoundFont has no presets")
+    } else {
+        check.verdict(with_data > 0, format!("{} of {} samples hold audio", with_data, soundfont.samples.len()),
+                      "All sample data is zero")
+    }
+}
+
+/// Whether the live engine may be used for a check; the reason to skip otherwise
+fn live_engine_busy(bridge: &mut AudioWorkletBridge) -> Option<&'static str> {
+    let player = bridge.midi_player_mut();
+    if !player.is_idle() {
+        Some("Engine is playing")
+    } else if player.get_active_voice_count() > 0 {
+        Some("Voices are sounding")
+    } else {
+        None
+    }
+}
+
+/// Chord through channel 0's preset on the live engine
+fn check_preset_synthesis(bridge: Option<&mut AudioWorkletBridge>) -> SelfTestItem {
+    let check = Check::new(SelfTestLevel::SoundFont, "presetSynthesis");
+    let Some(bridge) = bridge.filter(|bridge| bridge.is_soundfont_loaded_internal()) else {
+        return check.skip("No SoundFont loaded");
+    };
+    if let Some(reason) = live_engine_busy(bridge) {
+        return check.skip(reason);
+    }
+    let frames = (bridge.get_sample_rate() * 0.1) as usize;
+    let (started, peak) = bridge.midi_player_mut().sound_check(0, &TEST_CHORD, frames);
+    let check = check.measure("voices", started as f64, "").measure("peak", peak as f64, "");
+    if started == 0 {
+        check.fail("Channel 0's preset started no voices")
+    } else if !peak.is_finite() || peak < SILENCE {
+        check.fail("Chord produced no audio")
+    } else {
+        check.pass(format!("{} voices sounding, peak {:.3}", started, peak))
+    }
+}
+
+fn check_bridge(bridge: Option<&AudioWorkletBridge>) -> SelfTestItem {
+    let check = Check::new(SelfTestLevel::Pipeline, "bridge");
+    let Some(bridge) = bridge else {
+        return check.fail("AudioWorklet bridge not initialized");
+    };
+    check
+        .measure("sampleRate", bridge.get_sample_rate() as f64, "Hz")
+        .measure("bufferSize", bridge.get_buffer_size() as f64, "frames")
+        .pass("AudioWorklet bridge ready")
+}
+
+/// One block through the bridge's render path while idle: full length and finite
+fn check_block_render(bridge: Option<&mut AudioWorkletBridge>) -> SelfTestItem {
+    let check = Check::new(SelfTestLevel::Pipeline, "blockRender");
+    let Some(bridge) = bridge else {
+        return check.skip("AudioWorklet bridge not initialized");
+    };
+    if let Some(reason) = live_engine_busy(bridge) {
+        return check.skip(reason);
+    }
+    let frames = bridge.get_buffer_size();
+    let rendered = bridge.render_stereo_block(frames);
+    let block = bridge.rendered_block(rendered);
+    let finite = block.iter().all(|sample| sample.is_finite());
+    let peak = block.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+    let check = check.measure("frames", rendered as f64, "frames").measure("peak", peak as f64, "");
+    if rendered != frames {
+        check.fail(format!("Rendered {} of {} frames", rendered, frames))
+    } else {
+        check.verdict(finite, "Block rendered", "Block contains NaN or infinite samples")
+    }
+}
+
+// ===== WASM EXPORTS =====
+
+/// Run the engine self-test for a comma-separated list of levels ("engine", "soundfont",
+/// "pipeline"; empty or "all" runs them all). Returns a SelfTestReport as JSON; an unknown
+/// level name throws an invalidArgument ErrorEvent
+#[wasm_bindgen]
+pub fn run_self_test(levels: &str) -> Result<String, ErrorEvent> {
+    let _scope = ExportScope::enter("run_self_test");
+    let levels = SelfTestLevel::parse_list(levels)
+        .ok_or_else(|| ErrorEvent::raise(ErrorCode::InvalidArgument, format!("Unknown self-test level in \"{}\"", levels)))?;
+    Ok(run(crate::global_bridge_mut(), &levels).to_json())
+}
@@ -0,0 +1,326 @@
+/**
+ * AWE Player - SoundFont Sample Catalog
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Serializable summaries of loaded samples for browser UIs. Large fonts
+ * hold thousands of samples, so the catalog is served in pages with an
+ * optional name filter. Each entry can carry a min/max waveform overview
+ * decimated to the resolution the UI will draw at.
+ *
+ * Loop points are validated against the sample length so the UI can trust
+ * them. The raw header values are kept alongside for debugging broken files.
+ *
+ * Presets are paged the same way. Each lists the reverb and chorus send
+ * its generators give (SF2 2.01 8.1.3: instrument value plus the preset's
+ * additive offset, in 0.1% steps), averaged over its zones, and any send
+ * the host has set in its place, along with the GM name its bank and
+ * program number call for.
+ */
+
+use std::collections::HashMap;
+use serde::Serialize;
+use crate::midi::gm_names::expected_gm_name;
+use crate::synth::zone_generators::PresetGenerators;
+use super::types::{Generator, GeneratorAmount, GeneratorType, SoundFont, SoundFontPreset, SoundFontSample};
+
+/// Most entries returned by one page request
+pub const MAX_PAGE_SIZE: usize = 500;
+
+/// Highest waveform resolution (min/max pairs) per sample
+pub const MAX_WAVEFORM_POINTS: usize = 4096;
+
+/// Min/max overview of a sample, normalized to -1.0..1.0
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SampleWaveform {
+    pub min: Vec<f32>,
+    pub max: Vec<f32>,
+}
+
+impl SampleWaveform {
+    /// Decimate to at most `points` buckets (fewer if the sample is shorter)
+    pub fn decimate(data: &[i16], points: usize) -> Self {
+        let points = points.min(MAX_WAVEFORM_POINTS).min(data.len());
+        let mut min = Vec::with_capacity(points);
+        let mut max = Vec::with_capacity(points);
+        for bucket in 0..points {
+            let start = bucket * data.len() / points;
+            let end = ((bucket + 1) * data.len() / points).max(start + 1);
+            let (low, high) = data[start..end].iter()
+                .fold((i16::MAX, i16::MIN), |(low, high), &s| (low.min(s), high.max(s)));
+            min.push(low as f32 / 32768.0);
+            max.push(high as f32 / 32768.0);
+        }
+        Self { min, max }
+    }
+
+    /// Interleaved min/max pairs for binary transfer
+    pub fn interleaved(&self) -> Vec<f32> {
+        self.min.iter().zip(&self.max).flat_map(|(&low, &high)| [low, high]).collect()
+    }
+}
+
+/// One sample as listed in the catalog
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SampleCatalogEntry {
+    pub index: usize,
+    pub name: String,
+    pub length: u32,
+    pub sample_rate: u32,
+    pub original_pitch: u8,
+    pub pitch_correction: i8,
+    pub loop_start: u32,              // 0 when has_valid_loop is false
+    pub loop_end: u32,
+    pub has_valid_loop: bool,
+    pub raw_loop_start: u32,          // As stored in the shdr record
+    pub raw_loop_end: u32,
+    pub peak: f32,                    // Largest absolute value, 0.0-1.0
+    pub has_data: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub waveform: Option<SampleWaveform>,
+}
+
+impl SampleCatalogEntry {
+    /// `waveform_points` = 0 leaves the waveform out
+    pub fn new(index: usize, sample: &SoundFontSample, waveform_points: usize) -> Self {
+        let length = sample.sample_data.len() as u32;
+        // loop_end == 0 means no loop; loop_start == 0 with loop_end > 0 loops from the beginning
+        let has_valid_loop = sample.loop_end > 0
+            && sample.loop_start < sample.loop_end
+            && sample.loop_end <= length;
+        let (loop_start, loop_end) = if has_valid_loop { (sample.loop_start, sample.loop_end) } else { (0, 0) };
+        let peak = sample.sample_data.iter().map(|&s| s.unsigned_abs()).max().unwrap_or(0);
+
+        Self {
+            index,
+            name: sample.name.clone(),
+            length,
+            sample_rate: sample.sample_rate,
+            original_pitch: sample.original_pitch,
+            pitch_correction: sample.pitch_correction,
+            loop_start,
+            loop_end,
+            has_valid_loop,
+            raw_loop_start: sample.loop_start,
+            raw_loop_end: sample.loop_end,
+            peak: peak as f32 / 32768.0,
+            has_data: peak > 0,
+            waveform: (waveform_points > 0).then(|| SampleWaveform::decimate(&sample.sample_data, waveform_points)),
+        }
+    }
+}
+
+/// Page request: `offset`/`limit` index the (filtered) sample list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SampleQuery {
+    pub offset: usize,
+    pub limit: usize,
+    pub name_filter: String,          // Case-insensitive substring; empty matches all
+    pub waveform_points: usize,       // 0 = no waveform
+}
+
+/// One page of the sample catalog (get_soundfont_samples_page)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SampleCatalogPage {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub error: Option<String>,
+    pub total_samples: usize,         // All samples in the font
+    pub matching_samples: usize,      // Samples passing the name filter
+    pub offset: usize,
+    pub samples: Vec<SampleCatalogEntry>,
+}
+
+impl SampleCatalogPage {
+    pub fn query(soundfont: &SoundFont, query: &SampleQuery) -> Self {
+        let filter = query.name_filter.to_lowercase();
+        let matches = |sample: &&SoundFontSample| filter.is_empty() || sample.name.to_lowercase().contains(&filter);
+
+        let samples = soundfont.samples.iter()
+            .enumerate()
+            .filter(|(_, sample)| matches(sample))
+            .skip(query.offset)
+            .take(query.limit.min(MAX_PAGE_SIZE))
+            .map(|(index, sample)| SampleCatalogEntry::new(index, sample, query.waveform_points))
+            .collect();
+
+        Self {
+            success: true,
+            error: None,
+            total_samples: soundfont.samples.len(),
+            matching_samples: soundfont.samples.iter().filter(matches).count(),
+            offset: query.offset,
+            samples,
+        }
+    }
+
+    pub fn error(message: &str) -> Self {
+        Self {
+            success: false,
+            error: Some(message.to_string()),
+            total_samples: 0,
+            matching_samples: 0,
+            offset: 0,
+            samples: Vec::new(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| r#"{"success":false,"error":"Serialization failed","samples":[]}"#.to_string())
+    }
+}
+
+/// Reverb and chorus send levels, 0.0-1.0
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct EffectsSends {
+    pub reverb: f32,
+    pub chorus: f32,
+}
+
+impl EffectsSends {
+    pub fn new(reverb: f32, chorus: f32) -> Self {
+        Self { reverb: reverb.clamp(0.0, 1.0), chorus: chorus.clamp(0.0, 1.0) }
+    }
+}
+
+/// Value of `generator` in a zone, if set there
+pub(crate) fn zone_generator(generators: &[Generator], generator: &GeneratorType) -> Option<i32> {
+    generators.iter()
+        .find(|candidate| candidate.generator_type == *generator)
+        .and_then(|candidate| match candidate.amount {
+            GeneratorAmount::Short(value) => Some(value as i32),
+            GeneratorAmount::UShort(value) => Some(value as i32),
+            GeneratorAmount::Range { .. } => None,
+        })
+}
+
+/// Sends a preset's generators give: the mean over its sounding zones, and whether they differ
+/// (the values a note of that zone starts with, see PresetGenerators)
+pub fn preset_effects_sends(soundfont: &SoundFont, preset: &SoundFontPreset) -> (EffectsSends, bool) {
+    let generators = PresetGenerators::resolve(preset, soundfont);
+    let mut zones: Vec<(f32, f32)> = Vec::new();
+    for (preset_zone, zone) in preset.preset_zones.iter().enumerate() {
+        let Some(instrument) = zone.instrument_id.and_then(|id| soundfont.instruments.get(id as usize)) else {
+            continue;
+        };
+        for (instrument_zone, _) in instrument.instrument_zones.iter().enumerate().filter(|(_, zone)| zone.sample_id.is_some()) {
+            zones.push(generators.zone(preset_zone, instrument_zone).effects_sends());
+        }
+    }
+
+    if zones.is_empty() {
+        return (EffectsSends::new(0.0, 0.0), false);
+    }
+    let count = zones.len() as f32;
+    let reverb = zones.iter().map(|zone| zone.0).sum::<f32>() / count;
+    let chorus = zones.iter().map(|zone| zone.1).sum::<f32>() / count;
+    let vary = zones.iter().any(|zone| *zone != zones[0]);
+    (EffectsSends::new(reverb, chorus), vary)
+}
+
+/// One preset as listed in the catalog
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PresetCatalogEntry {
+    pub index: usize,
+    pub name: String,
+    pub bank: u16,
+    pub program: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub gm_name: Option<String>,      // Expected GM program or kit name for bank/program
+    pub zones: usize,                 // Preset zones, global zone included
+    pub sends: EffectsSends,          // From the generators
+    pub sends_vary: bool,             // Zones differ; `sends` is their mean
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub sends_override: Option<EffectsSends>, // Host value new notes use instead
+}
+
+impl PresetCatalogEntry {
+    pub fn new(index: usize, soundfont: &SoundFont, preset: &SoundFontPreset, sends_override: Option<EffectsSends>) -> Self {
+        let (sends, sends_vary) = preset_effects_sends(soundfont, preset);
+        Self {
+            index,
+            name: preset.name.clone(),
+            bank: preset.bank,
+            program: preset.program,
+            gm_name: expected_gm_name(preset.bank, preset.program).map(str::to_string),
+            zones: preset.preset_zones.len(),
+            sends,
+            sends_vary,
+            sends_override,
+        }
+    }
+}
+
+/// Page request: `offset`/`limit` index the (filtered) preset list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresetQuery {
+    pub offset: usize,
+    pub limit: usize,
+    pub name_filter: String,          // Case-insensitive substring; empty matches all
+}
+
+/// One page of the preset catalog (get_soundfont_presets_page)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PresetCatalogPage {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub error: Option<String>,
+    pub total_presets: usize,
+    pub matching_presets: usize,
+    pub offset: usize,
+    pub presets: Vec<PresetCatalogEntry>,
+}
+
+impl PresetCatalogPage {
+    /// `overrides` maps preset index to the sends the host set for it
+    pub fn query(soundfont: &SoundFont, query: &PresetQuery, overrides: &HashMap<usize, EffectsSends>) -> Self {
+        let filter = query.name_filter.to_lowercase();
+        let matches = |preset: &&SoundFontPreset| filter.is_empty() || preset.name.to_lowercase().contains(&filter);
+
+        let presets = soundfont.presets.iter()
+            .enumerate()
+            .filter(|(_, preset)| matches(preset))
+            .skip(query.offset)
+            .take(query.limit.min(MAX_PAGE_SIZE))
+            .map(|(index, preset)| PresetCatalogEntry::new(index, soundfont, preset, overrides.get(&index).copied()))
+            .collect();
+
+        Self {
+            success: true,
+            error: None,
+            total_presets: soundfont.presets.len(),
+            matching_presets: soundfont.presets.iter().filter(matches).count(),
+            offset: query.offset,
+            presets,
+        }
+    }
+
+    pub fn error(message: &str) -> Self {
+        Self {
+            success: false,
+            error: Some(message.to_string()),
+            total_presets: 0,
+            matching_presets: 0,
+            offset: 0,
+            presets: Vec::new(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| r#"{"success":false,"error":"Serialization failed","presets":[]}"#.to_string())
+    }
+}
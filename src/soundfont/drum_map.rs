@@ -0,0 +1,93 @@
+/**
+ * AWE Player - Drum Kit Key Map
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Which keys of a percussion preset actually play something. A kit only
+ * sounds where a preset zone and one of its instrument's zones both
+ * cover the key and the instrument zone has a sample; everywhere else a
+ * note is silent. The map lists every GM percussion key (covered or not)
+ * plus any extra key the kit covers, so a pad UI can label the drums it
+ * has and grey out the ones it doesn't.
+ */
+
+use serde::Serialize;
+use crate::midi::gm_names::gm_drum_note_name;
+use super::types::{KeyRange, SoundFont, SoundFontPreset};
+
+/// One key of the map
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct DrumKey {
+    pub key: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub gm_name: Option<String>,    // GM percussion name (keys 35-81)
+    pub covered: bool,              // The kit plays a sample here
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub sample: Option<String>,     // First sample the key plays
+}
+
+/// Key map of one preset (get_drum_kit_map_global)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct DrumKitMap {
+    pub preset_index: usize,
+    pub preset_name: String,
+    pub bank: u16,
+    pub program: u8,
+    pub covered_keys: usize,
+    pub silent_gm_keys: usize,      // GM percussion keys the kit leaves silent
+    pub keys: Vec<DrumKey>,         // GM keys and covered keys, ascending
+}
+
+fn in_range(range: &Option<KeyRange>, key: u8) -> bool {
+    range.as_ref().is_none_or(|range| (range.low..=range.high).contains(&key))
+}
+
+/// Map every key of `preset`
+pub fn map_drum_kit(soundfont: &SoundFont, preset_index: usize, preset: &SoundFontPreset) -> DrumKitMap {
+    // First sample each key reaches through a preset zone and an instrument zone
+    let mut samples: [Option<usize>; 128] = [None; 128];
+    for preset_zone in &preset.preset_zones {
+        let Some(instrument) = preset_zone.instrument_id.and_then(|id| soundfont.instruments.get(id as usize)) else {
+            continue;
+        };
+        for zone in &instrument.instrument_zones {
+            let Some(sample_id) = zone.sample_id.map(|id| id as usize).filter(|&id| id < soundfont.samples.len()) else {
+                continue;
+            };
+            for (key, sample) in samples.iter_mut().enumerate() {
+                let key = key as u8;
+                if sample.is_none() && in_range(&preset_zone.key_range, key) && in_range(&zone.key_range, key) {
+                    *sample = Some(sample_id);
+                }
+            }
+        }
+    }
+
+    let keys: Vec<DrumKey> = (0..128u8)
+        .filter_map(|key| {
+            let gm_name = gm_drum_note_name(key);
+            let sample = samples[key as usize];
+            (gm_name.is_some() || sample.is_some()).then(|| DrumKey {
+                key,
+                gm_name: gm_name.map(str::to_string),
+                covered: sample.is_some(),
+                sample: sample.map(|id| soundfont.samples[id].name.clone()),
+            })
+        })
+        .collect();
+
+    DrumKitMap {
+        preset_index,
+        preset_name: preset.name.clone(),
+        bank: preset.bank,
+        program: preset.program,
+        covered_keys: keys.iter().filter(|key| key.covered).count(),
+        silent_gm_keys: keys.iter().filter(|key| key.gm_name.is_some() && !key.covered).count(),
+        keys,
+    }
+}
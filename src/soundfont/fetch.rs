@@ -0,0 +1,122 @@
+/**
+ * AWE Player - SoundFont Fetch (feature "fetch")
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Downloads an SF2 file with the Fetch API and copies each read of the
+ * response body straight into a SoundFontStream's buffer, so neither
+ * JavaScript nor Rust holds a second copy of the file; it is parsed once
+ * complete. Works from the main thread and from workers (the AudioWorklet
+ * scope has no fetch). Progress is reported per network read, both to the
+ * optional callback and as load events; a load is cancelled by cancel() or
+ * by starting another load.
+ */
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use js_sys::{Function, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{ReadableStreamDefaultReader, ReadableStreamReadResult, Response, Window, WorkerGlobalScope};
+use super::stream::SoundFontStream;
+use super::{SoundFont, SoundFontError, SoundFontResult};
+use crate::audio::memory;
+use crate::events::{self, LoadEvent, LoadKind, LoadStage};
+
+/// Share of the overall load percent taken by the download; parsing follows
+const DOWNLOAD_END_PERCENT: f32 = 70.0;
+
+/// Bumped by every load and cancel; a load stops once it no longer owns the current value
+static LOAD_GENERATION: AtomicU32 = AtomicU32::new(0);
+
+/// Cancel the load in progress, if any
+pub fn cancel() {
+    LOAD_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Fetch and parse the SF2 file at `url`
+/// `on_progress(bytesReceived, totalBytes)` is called after each read; totalBytes is undefined until known
+pub async fn fetch_soundfont(url: &str, on_progress: Option<Function>) -> SoundFontResult<SoundFont> {
+    let generation = LOAD_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let io_error = |message: String| SoundFontError::IoError {
+        file_path: Some(url.to_string()),
+        operation: "fetch".to_string(),
+        message,
+    };
+    let cancelled = || LOAD_GENERATION.load(Ordering::SeqCst) != generation;
+    let cancel_error = || SoundFontError::IoError {
+        file_path: Some(url.to_string()),
+        operation: "cancel".to_string(),
+        message: "Load cancelled".to_string(),
+    };
+
+    let response: Response = JsFuture::from(start_fetch(url).map_err(|e| io_error(js_error_message(&e)))?)
+        .await
+        .map_err(|e| io_error(js_error_message(&e)))?
+        .unchecked_into();
+    if !response.ok() {
+        return Err(io_error(format!("HTTP status {}", response.status())));
+    }
+    let body = response.body().ok_or_else(|| io_error("Response has no body".to_string()))?;
+    let reader: ReadableStreamDefaultReader = body.get_reader().unchecked_into();
+
+    let mut stream = SoundFontStream::new();
+    let mut reserved = false;
+    loop {
+        if cancelled() {
+            // Stop the download; the reader may already be closed
+            let _ = JsFuture::from(reader.cancel()).await;
+            return Err(cancel_error());
+        }
+        let result: ReadableStreamReadResult = JsFuture::from(reader.read())
+            .await
+            .map_err(|e| io_error(js_error_message(&e)))?
+            .unchecked_into();
+        if result.get_done().unwrap_or(false) {
+            break;
+        }
+        let chunk: Uint8Array = result.get_value().unchecked_into();
+        if let Err(e) = stream.push_from(chunk.length() as usize, |spare| chunk.copy_to_uninit(spare)) {
+            let _ = JsFuture::from(reader.cancel()).await;
+            return Err(e);
+        }
+
+        // Grow the heap once for the whole load instead of on every chunk
+        if !reserved {
+            if let Some(total) = stream.total_bytes() {
+                memory::reserve(memory::estimate_soundfont_load_bytes(total));
+                reserved = true;
+            }
+        }
+        if let Some(fraction) = stream.progress() {
+            events::emit(LoadEvent::progress(LoadKind::SoundFont, LoadStage::Download, fraction * DOWNLOAD_END_PERCENT));
+        }
+        if let Some(callback) = &on_progress {
+            let total = stream.total_bytes().map_or(JsValue::UNDEFINED, |t| JsValue::from_f64(t as f64));
+            let _ = callback.call2(&JsValue::NULL, &JsValue::from_f64(stream.bytes_received() as f64), &total);
+        }
+    }
+
+    if cancelled() {
+        return Err(cancel_error());
+    }
+    stream.finish_with_progress(&mut events::parse_progress(LoadKind::SoundFont, DOWNLOAD_END_PERCENT))
+}
+
+/// fetch() from whichever global scope the module runs in
+fn start_fetch(url: &str) -> Result<js_sys::Promise, JsValue> {
+    let global = js_sys::global();
+    if let Some(window) = global.dyn_ref::<Window>() {
+        Ok(window.fetch_with_str(url))
+    } else if let Some(worker) = global.dyn_ref::<WorkerGlobalScope>() {
+        Ok(worker.fetch_with_str(url))
+    } else {
+        Err(JsValue::from_str("fetch is not available in this context"))
+    }
+}
+
+fn js_error_message(value: &JsValue) -> String {
+    if let Some(error) = value.dyn_ref::<js_sys::Error>() {
+        String::from(error.message())
+    } else {
+        value.as_string().unwrap_or_else(|| "Unknown fetch error".to_string())
+    }
+}
@@ -0,0 +1,159 @@
+/**
+ * AWE Player - SoundFont Identity
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Stable fingerprint of a SoundFont's content, for hosts that cache banks
+ * in IndexedDB, skip duplicate loads or key saved sessions to a bank. The
+ * hash covers what the synthesizer uses (INFO header, presets, instruments,
+ * their zones, sample headers and PCM data), not the file bytes, so the
+ * same bank gives the same hash whether it was loaded from memory, through
+ * the bridge or streamed from a URL. It is taken before any load-time
+ * sample reduction.
+ *
+ * The hash is 128-bit FNV-1a over a length-prefixed encoding of those
+ * fields; it identifies content and is not meant to resist tampering.
+ */
+
+use serde::Serialize;
+use super::types::{Generator, GeneratorAmount, KeyRange, Modulator, SoundFont, VelocityRange};
+
+const FNV_OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+const FNV_PRIME: u128 = 0x0000000001000000000000000000013B;
+
+/// Identity of the loaded SoundFont (get_soundfont_identity_global)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SoundFontIdentity {
+    pub hash: String,             // 32 hex digits
+    pub name: String,             // INAM
+    pub version: String,          // ifil, e.g. "2.1"
+    pub presets: usize,
+    pub instruments: usize,
+    pub samples: usize,
+    pub sample_bytes: usize,      // PCM data at full size
+}
+
+impl SoundFontIdentity {
+    pub fn of(soundfont: &SoundFont) -> Self {
+        let header = &soundfont.header;
+        SoundFontIdentity {
+            hash: format!("{:032x}", content_hash(soundfont)),
+            name: header.name.clone(),
+            version: format!("{}.{}", header.version.major, header.version.minor),
+            presets: soundfont.presets.len(),
+            instruments: soundfont.instruments.len(),
+            samples: soundfont.samples.len(),
+            sample_bytes: soundfont.samples.iter().map(|sample| sample.sample_data.len() * 2).sum(),
+        }
+    }
+}
+
+/// 128-bit FNV-1a accumulator
+struct ContentHasher(u128);
+
+impl ContentHasher {
+    fn bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u128).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn number(&mut self, value: u64) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    /// Length prefix keeps ("ab", "c") and ("a", "bc") apart
+    fn text(&mut self, text: &str) {
+        self.number(text.len() as u64);
+        self.bytes(text.as_bytes());
+    }
+
+    fn generators(&mut self, generators: &[Generator]) {
+        self.number(generators.len() as u64);
+        for generator in generators {
+            self.number(generator.generator_type.clone() as u64);
+            match generator.amount {
+                GeneratorAmount::Short(value) => self.bytes(&[0, value as u8, (value >> 8) as u8]),
+                GeneratorAmount::UShort(value) => self.bytes(&[1, value as u8, (value >> 8) as u8]),
+                GeneratorAmount::Range { low, high } => self.bytes(&[2, low, high]),
+            }
+        }
+    }
+
+    fn modulators(&mut self, modulators: &[Modulator]) {
+        self.number(modulators.len() as u64);
+        for modulator in modulators {
+            for value in [modulator.source_enum, modulator.dest_enum.clone() as u16, modulator.amount as u16,
+                          modulator.amount_source_enum, modulator.trans_enum] {
+                self.bytes(&value.to_le_bytes());
+            }
+        }
+    }
+
+    fn ranges(&mut self, key_range: &Option<KeyRange>, velocity_range: &Option<VelocityRange>) {
+        match key_range {
+            Some(range) => self.bytes(&[1, range.low, range.high]),
+            None => self.bytes(&[0]),
+        }
+        match velocity_range {
+            Some(range) => self.bytes(&[1, range.low, range.high]),
+            None => self.bytes(&[0]),
+        }
+    }
+
+    fn link(&mut self, id: Option<u16>) {
+        self.number(id.map_or(u64::MAX, u64::from));
+    }
+}
+
+/// Content hash of `soundfont` (see module docs for what it covers)
+pub fn content_hash(soundfont: &SoundFont) -> u128 {
+    let mut hasher = ContentHasher(FNV_OFFSET_BASIS);
+    let header = &soundfont.header;
+    hasher.number(((header.version.major as u64) << 16) | header.version.minor as u64);
+    for field in [&header.name, &header.engine, &header.tools, &header.creation_date,
+                  &header.author, &header.product, &header.copyright, &header.comments] {
+        hasher.text(field);
+    }
+
+    hasher.number(soundfont.presets.len() as u64);
+    for preset in &soundfont.presets {
+        hasher.text(&preset.name);
+        hasher.number(((preset.bank as u64) << 8) | preset.program as u64);
+        hasher.number(preset.preset_zones.len() as u64);
+        for zone in &preset.preset_zones {
+            hasher.generators(&zone.generators);
+            hasher.modulators(&zone.modulators);
+            hasher.link(zone.instrument_id);
+            hasher.ranges(&zone.key_range, &zone.velocity_range);
+        }
+    }
+
+    hasher.number(soundfont.instruments.len() as u64);
+    for instrument in &soundfont.instruments {
+        hasher.text(&instrument.name);
+        hasher.number(instrument.instrument_zones.len() as u64);
+        for zone in &instrument.instrument_zones {
+            hasher.generators(&zone.generators);
+            hasher.modulators(&zone.modulators);
+            hasher.link(zone.sample_id);
+            hasher.ranges(&zone.key_range, &zone.velocity_range);
+        }
+    }
+
+    hasher.number(soundfont.samples.len() as u64);
+    for sample in &soundfont.samples {
+        hasher.text(&sample.name);
+        for value in [sample.loop_start, sample.loop_end, sample.sample_rate] {
+            hasher.number(value as u64);
+        }
+        hasher.bytes(&[sample.original_pitch, sample.pitch_correction as u8]);
+        hasher.number(((sample.sample_link as u64) << 16) | sample.sample_type.clone() as u64);
+        hasher.number(sample.sample_data.len() as u64);
+        for value in sample.sample_data.iter() {
+            hasher.bytes(&value.to_le_bytes());
+        }
+    }
+    hasher.0
+}
@@ -0,0 +1,88 @@
+/**
+ * AWE Player - Loop Point Repair
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * The parser drops a sample's loop when its points fall outside the
+ * sample or run backwards, and the note then plays once and dies instead
+ * of sustaining. A good share of amateur SF2s miss by a sample or two:
+ * a loop end one past the sample end (an inclusive end written where SF2
+ * expects an exclusive one), or a loop start that lands on or just past
+ * the loop end. With repair on, loops that miss by at most
+ * MAX_LOOP_REPAIR_SAMPLES are nudged into range instead of dropped, and
+ * each nudge is recorded. Anything further off is still dropped - a loop
+ * that is badly wrong would click or buzz, and a one-shot is safer.
+ *
+ * Repair happens while sample headers are parsed, so the setting lives
+ * with the thread that parses (like the load event listener) and applies
+ * to every later load, whichever path it takes.
+ */
+
+use std::cell::{Cell, RefCell};
+use serde::Serialize;
+
+/// Largest miss (in samples) that is repaired rather than dropped
+pub const MAX_LOOP_REPAIR_SAMPLES: u32 = 2;
+
+/// A sample whose loop points were nudged (get_loop_repairs_global)
+/// Declared points are the header's, relative to the sample start (negative = before it)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LoopRepair {
+    pub sample: usize,
+    pub name: String,
+    pub declared_start: i32,
+    pub declared_end: i32,
+    pub loop_start: u32,
+    pub loop_end: u32,
+}
+
+thread_local! {
+    static ENABLED: Cell<bool> = const { Cell::new(false) };
+    static REPAIRS: RefCell<Vec<LoopRepair>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Repair near-miss loops in later parses (off by default)
+pub fn set_enabled(enabled: bool) {
+    ENABLED.with(|slot| slot.set(enabled));
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.with(Cell::get)
+}
+
+/// Valid loop points (relative to the sample start) for a near-miss loop, None if the
+/// declared loop (absolute, like the sample bounds) is too far off or not looped at all
+pub fn repair_loop(start: u32, end: u32, loop_start: u32, loop_end: u32) -> Option<(u32, u32)> {
+    if end - start < 2 {
+        return None;
+    }
+    // Ends just outside the sample: pull them in
+    let mut loop_start = if loop_start < start && start - loop_start <= MAX_LOOP_REPAIR_SAMPLES { start } else { loop_start };
+    let mut loop_end = if loop_end > end && loop_end - end <= MAX_LOOP_REPAIR_SAMPLES { end } else { loop_end };
+    // Start on or just past the end: open the loop to its shortest valid length
+    if loop_start >= loop_end && loop_start - loop_end <= MAX_LOOP_REPAIR_SAMPLES {
+        if loop_start < end {
+            loop_end = loop_start + 1;
+        } else {
+            loop_start = end - 1;
+            loop_end = end;
+        }
+    }
+    (loop_start >= start && loop_end > loop_start && loop_end <= end)
+        .then(|| (loop_start - start, loop_end - start))
+}
+
+/// Forget the repairs of a previous parse (called as sample headers start)
+pub(crate) fn begin_parse() {
+    REPAIRS.with(|repairs| repairs.borrow_mut().clear());
+}
+
+pub(crate) fn record(repair: LoopRepair) {
+    REPAIRS.with(|repairs| repairs.borrow_mut().push(repair));
+}
+
+/// Repairs made by the most recent parse on this thread (leaves the record empty)
+pub fn take_repairs() -> Vec<LoopRepair> {
+    REPAIRS.with(|repairs| std::mem::take(&mut *repairs.borrow_mut()))
+}
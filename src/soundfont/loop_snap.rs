@@ -0,0 +1,114 @@
+/**
+ * AWE Player - Zero-Crossing Loop Snap
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * A loop clicks when the sample value at its end does not lead smoothly
+ * into the value at its start. Hand-set loop points often land mid-swing;
+ * moving each point to the nearest rising zero crossing (within a small
+ * window, so the loop keeps its length and timbre) makes the wrap go
+ * through zero in the same direction at both ends. Points are moved only
+ * when both ends find a crossing.
+ *
+ * Stereo pairs that share their loop points are snapped together, on the
+ * sum of both channels, so the two sides keep looping in phase.
+ */
+
+use serde::Serialize;
+use super::types::{SampleType, SoundFont, SoundFontSample};
+
+/// Widest search either side of a loop point (samples)
+pub const MAX_SNAP_WINDOW: u32 = 64;
+
+/// A sample whose loop points were moved (get_loop_adjustments_global)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LoopAdjustment {
+    pub sample: usize,
+    pub name: String,
+    pub loop_start_before: u32,
+    pub loop_end_before: u32,
+    pub loop_start: u32,
+    pub loop_end: u32,
+}
+
+/// Snap every looped sample of `soundfont` to zero crossings within `window` samples
+/// (0 = off, clamped to MAX_SNAP_WINDOW); one adjustment per moved sample
+pub fn snap_loops(soundfont: &mut SoundFont, window: u32) -> Vec<LoopAdjustment> {
+    let window = window.min(MAX_SNAP_WINDOW) as usize;
+    let mut adjustments = Vec::new();
+    if window == 0 {
+        return adjustments;
+    }
+    let mut done = vec![false; soundfont.samples.len()];
+    for index in 0..soundfont.samples.len() {
+        if done[index] {
+            continue;
+        }
+        let sample = &soundfont.samples[index];
+        if sample.loop_end <= sample.loop_start {
+            continue;
+        }
+        let partner = stereo_partner(&soundfont.samples, index);
+        let snapped = match partner {
+            Some(partner) => {
+                let (left, right) = (&sample.sample_data, &soundfont.samples[partner].sample_data);
+                snap(sample, window, |i| left[i] as i32 + right[i] as i32)
+            }
+            None => snap(sample, window, |i| sample.sample_data[i] as i32),
+        };
+        let Some((loop_start, loop_end)) = snapped else {
+            continue;
+        };
+        for target in std::iter::once(index).chain(partner) {
+            done[target] = true;
+            let sample = &mut soundfont.samples[target];
+            if (sample.loop_start, sample.loop_end) != (loop_start, loop_end) {
+                adjustments.push(LoopAdjustment {
+                    sample: target,
+                    name: sample.name.clone(),
+                    loop_start_before: sample.loop_start,
+                    loop_end_before: sample.loop_end,
+                    loop_start,
+                    loop_end,
+                });
+                sample.loop_start = loop_start;
+                sample.loop_end = loop_end;
+            }
+        }
+    }
+    adjustments.sort_by_key(|adjustment| adjustment.sample);
+    adjustments
+}
+
+/// The other half of a stereo pair, if it is loaded and loops over the same frames
+fn stereo_partner(samples: &[SoundFontSample], index: usize) -> Option<usize> {
+    let sample = &samples[index];
+    if !matches!(sample.sample_type, SampleType::LeftSample | SampleType::RightSample
+        | SampleType::RomLeftSample | SampleType::RomRightSample) {
+        return None;
+    }
+    let partner = sample.sample_link as usize;
+    samples.get(partner)
+        .filter(|other| partner != index
+            && other.sample_data.len() == sample.sample_data.len()
+            && (other.loop_start, other.loop_end) == (sample.loop_start, sample.loop_end))
+        .map(|_| partner)
+}
+
+/// Loop points moved to the nearest rising zero crossings of `value`, None if either end has none
+fn snap(sample: &SoundFontSample, window: usize, value: impl Fn(usize) -> i32) -> Option<(u32, u32)> {
+    let len = sample.sample_data.len();
+    // Rising crossing just before frame i: the wrap lands on i coming from below zero
+    let rising = |i: usize| i >= 1 && i < len && value(i - 1) < 0 && value(i) >= 0;
+    let nearest = |point: usize| (0..=window).find_map(|distance| {
+        let after = point + distance;
+        if rising(after) {
+            return Some(after);
+        }
+        point.checked_sub(distance).filter(|&before| rising(before))
+    });
+    let loop_start = nearest(sample.loop_start as usize)?;
+    let loop_end = nearest(sample.loop_end as usize)?;
+    (loop_end > loop_start).then_some((loop_start as u32, loop_end as u32))
+}
@@ -16,6 +16,18 @@ use crate::log;
 pub mod riff_parser;
 pub mod types;
 pub mod parser;
+pub mod catalog;
+pub mod drum_map;
+pub mod identity;
+pub mod loop_repair;
+pub mod loop_snap;
+pub mod modulators;
+pub mod preload;
+pub mod reduce;
+pub mod sample_rates;
+pub mod stream;
+#[cfg(feature = "fetch")]
+pub mod fetch;
 
 // Re-export main types for convenience
 pub use types::*;
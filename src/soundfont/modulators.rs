@@ -0,0 +1,87 @@
+/**
+ * AWE Player - SoundFont Modulator Curves and Defaults
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Source curve shapes from SoundFont 2.01 section 8.2 (linear, concave,
+ * convex, switch, each unipolar or bipolar and positive or negative) and
+ * the default modulators of section 8.4 that every zone carries unless it
+ * overrides them. Velocity reaches attenuation and filter cutoff only
+ * through these two defaults:
+ *
+ * Note-On Velocity to Initial Attenuation: negative unipolar concave,
+ * 960 cB. Full velocity adds nothing; the attenuation works out to a gain
+ * of (velocity/127)^2.
+ *
+ * Note-On Velocity to Filter Cutoff: negative unipolar linear, -2400 cents,
+ * so the cutoff falls two octaves between velocity 127 and 0.
+ */
+
+/// Modulator source curve (SoundFont 2.01 section 8.2.4)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveType {
+    Linear,
+    Concave,
+    Convex,
+    Switch,
+}
+
+/// Source mapping: curve shape, direction and polarity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceCurve {
+    pub curve: CurveType,
+    pub negative: bool,     // Maps max -> min
+    pub bipolar: bool,      // Output -1..1 instead of 0..1
+}
+
+/// Default modulator 8.4.2: velocity to initialAttenuation
+pub const VELOCITY_TO_ATTENUATION: SourceCurve = SourceCurve { curve: CurveType::Concave, negative: true, bipolar: false };
+pub const VELOCITY_TO_ATTENUATION_CB: f32 = 960.0;
+
+/// Default modulator 8.4.3: velocity to initialFilterFc
+pub const VELOCITY_TO_FILTER_FC: SourceCurve = SourceCurve { curve: CurveType::Linear, negative: true, bipolar: false };
+pub const VELOCITY_TO_FILTER_FC_CENTS: f32 = -2400.0;
+
+impl SourceCurve {
+    /// Map a normalized controller value (0.0-1.0) through the curve
+    pub fn map(&self, value: f32) -> f32 {
+        let mut x = value.clamp(0.0, 1.0);
+        if self.negative {
+            x = 1.0 - x;
+        }
+        if self.bipolar {
+            // Each half uses the curve shape away from the center
+            let half = 2.0 * x - 1.0;
+            let magnitude = shape(self.curve, half.abs());
+            return if half < 0.0 { -magnitude } else { magnitude };
+        }
+        shape(self.curve, x)
+    }
+}
+
+/// Unipolar positive curve shapes
+fn shape(curve: CurveType, x: f32) -> f32 {
+    match curve {
+        CurveType::Linear => x,
+        CurveType::Concave => concave(x),
+        CurveType::Convex => 1.0 - concave(1.0 - x),
+        CurveType::Switch => if x >= 0.5 { 1.0 } else { 0.0 },
+    }
+}
+
+/// -20/96 log10((1 - x)^2), the spec's concave curve, clamped to 0..1
+fn concave(x: f32) -> f32 {
+    if x >= 1.0 {
+        return 1.0;
+    }
+    (-(40.0 / 96.0) * (1.0 - x).log10()).clamp(0.0, 1.0)
+}
+
+/// Attenuation (centibels) the default velocity modulator adds
+pub fn velocity_attenuation_cb(velocity: f32) -> f32 {
+    VELOCITY_TO_ATTENUATION_CB * VELOCITY_TO_ATTENUATION.map(velocity)
+}
+
+/// Cutoff shift (cents) the default velocity modulator adds
+pub fn velocity_filter_cents(velocity: f32) -> f32 {
+    VELOCITY_TO_FILTER_FC_CENTS * VELOCITY_TO_FILTER_FC.map(velocity)
+}
@@ -8,10 +8,16 @@
 use super::{
     SoundFontResult, SoundFontError, SampleErrorType,
     types::*,
+    loop_repair,
     riff_parser::{RiffParser, RiffChunk, SoundFontRiff},
 };
+use crate::events::LoadStage;
 use crate::log;
 use std::collections::HashMap;
+use std::ops::Range;
+
+/// Names of the terminal phdr/inst/shdr records ("EOP", "EOI", "EOS")
+const TERMINAL_RECORD_NAMES: [&str; 3] = ["EOP", "EOI", "EOS"];
 
 /// Main SoundFont Parser with SF2 header parsing capability
 pub struct SoundFontParser {
@@ -32,18 +38,27 @@ impl SoundFontParser {
     
     /// Parse complete SF2 file - Tasks 9A.4 and 9A.5 implementation
     pub fn parse_soundfont(data: &[u8]) -> SoundFontResult<SoundFont> {
+        Self::parse_soundfont_with_progress(data, &mut |_, _| {})
+    }
+    
+    /// Parse complete SF2 file, calling `progress(stage, fraction)` as each stage completes
+    /// (`fraction` is the share of the whole parse done, 0.0-1.0)
+    pub fn parse_soundfont_with_progress(data: &[u8], progress: &mut dyn FnMut(LoadStage, f32)) -> SoundFontResult<SoundFont> {
         let mut parser = Self::new();
         
         // SoundFont parsing - reduced logging to prevent flooding
         
         // Step 1: Parse RIFF container structure
         let riff = RiffParser::parse_soundfont_riff(data)?;
+        progress(LoadStage::Container, 0.1);
         
         // Step 2: Parse INFO chunk for header information
         let header = parser.parse_info_chunk(&riff.chunks)?;
+        progress(LoadStage::Info, 0.2);
         
         // Step 3: Parse sample data (sdta chunk) - Task 9A.5
         let raw_samples = Self::parse_sample_data(&riff.chunks)?;
+        progress(LoadStage::Samples, 0.4);
         
         // Step 4: Parse individual sample headers and extract actual samples
         let samples = if !raw_samples.is_empty() {
@@ -70,9 +85,11 @@ impl SoundFontParser {
         } else {
             Vec::new()
         };
+        progress(LoadStage::Samples, 0.6);
         
         // Step 5: Parse presets and instruments (pdta chunk) - Task 9A.6
         let (presets, instruments) = Self::parse_preset_data(&riff.chunks)?;
+        progress(LoadStage::Presets, 1.0);
         
         // Store RIFF data for future use
         parser.riff_data = Some(riff);
@@ -328,7 +345,7 @@ impl SoundFontParser {
             pitch_correction: 0,
             sample_link: 0,
             sample_type: SampleType::MonoSample,
-            sample_data,
+            sample_data: sample_data.into(),
         };
         
         // Sample data extraction completion debug removed
@@ -353,30 +370,19 @@ impl SoundFontParser {
         
         // Sample header chunk found debug removed
         
-        // Each sample header is 46 bytes
+        // Each sample header is 46 bytes; a trailing partial header is ignored
         const SAMPLE_HEADER_SIZE: usize = 46;
-        if shdr_chunk.data.len() % SAMPLE_HEADER_SIZE != 0 {
-            return Err(SoundFontError::SampleError {
-                sample_name: "shdr_chunk".to_string(),
-                sample_index: None,
-                error_type: SampleErrorType::InvalidFormat,
-                message: format!("Invalid sample header chunk size: {} (must be multiple of {})", 
-                               shdr_chunk.data.len(), SAMPLE_HEADER_SIZE),
-            });
-        }
-        
-        let sample_count = shdr_chunk.data.len() / SAMPLE_HEADER_SIZE;
+        let headers: Vec<&[u8]> = shdr_chunk.data.chunks_exact(SAMPLE_HEADER_SIZE).collect();
+        let sample_count = content_record_count(&headers);
         let mut samples = Vec::new();
         let mut loop_stats = (0usize, 0usize, 0usize); // (valid_loops, invalid_loops, no_loops)
+        loop_repair::begin_parse();
         
-        for i in 0..sample_count {
-            let header_offset = i * SAMPLE_HEADER_SIZE;
-            let header_data = &shdr_chunk.data[header_offset..header_offset + SAMPLE_HEADER_SIZE];
-            
+        for (i, header_data) in headers[..sample_count].iter().enumerate() {
             // Parse sample header structure
             let sample = Self::parse_single_sample_header(header_data, raw_sample_data, i)?;
             
-            // Track loop statistics
+            // Track loop statistics (unnamed samples are kept: zones refer to samples by index)
             if !sample.name.is_empty() {
                 if sample.loop_end > 0 {
                     loop_stats.0 += 1; // Has valid loop
@@ -391,9 +397,8 @@ impl SoundFontParser {
                         loop_stats.2 += 1; // No loop defined
                     }
                 }
-                
-                samples.push(sample);
             }
+            samples.push(sample);
         }
         
         // Store loop validation info to be returned (not logged here)
@@ -414,9 +419,7 @@ impl SoundFontParser {
         }
         
         // Extract sample name (20 bytes, null-terminated)
-        let name_bytes = &header_data[0..20];
-        let name_end = name_bytes.iter().position(|&b| b == 0).unwrap_or(20);
-        let sample_name = String::from_utf8_lossy(&name_bytes[0..name_end]).to_string();
+        let sample_name = record_name(header_data);
         
         // Extract sample parameters
         let start_offset = u32::from_le_bytes([header_data[20], header_data[21], header_data[22], header_data[23]]);
@@ -464,16 +467,26 @@ impl SoundFontParser {
         // Check if this sample has loop data defined in the SF2
         let has_loop_data = loop_start != 0 || loop_end != 0;
         
-        let relative_loop_start = if has_valid_loop {
-            loop_start - start_offset
-        } else {
-            0  // No loop or invalid loop
-        };
-        
-        let relative_loop_end = if has_valid_loop {
-            loop_end - start_offset
+        let (relative_loop_start, relative_loop_end) = if has_valid_loop {
+            (loop_start - start_offset, loop_end - start_offset)
+        } else if has_loop_data && loop_repair::is_enabled() {
+            // Near misses are nudged into range (see loop_repair.rs), the rest dropped
+            match loop_repair::repair_loop(start_offset, end_offset, loop_start, loop_end) {
+                Some((repaired_start, repaired_end)) => {
+                    loop_repair::record(loop_repair::LoopRepair {
+                        sample: sample_index,
+                        name: sample_name.clone(),
+                        declared_start: loop_start.wrapping_sub(start_offset) as i32,
+                        declared_end: loop_end.wrapping_sub(start_offset) as i32,
+                        loop_start: repaired_start,
+                        loop_end: repaired_end,
+                    });
+                    (repaired_start, repaired_end)
+                }
+                None => (0, 0),
+            }
         } else {
-            0  // No loop or invalid loop
+            (0, 0)  // No loop or invalid loop
         };
         
         // Parse sample type
@@ -490,7 +503,7 @@ impl SoundFontParser {
             pitch_correction,
             sample_link,
             sample_type,
-            sample_data,
+            sample_data: sample_data.into(),
         })
     }
     
@@ -568,44 +581,29 @@ impl SoundFontParser {
         
         // Instrument chunks found debug removed
         
-        // Parse instrument headers (22 bytes each)
+        // Parse instrument headers (22 bytes each; bag index at byte 20)
         const INST_HEADER_SIZE: usize = 22;
-        if inst_chunk.data.len() % INST_HEADER_SIZE != 0 {
-            return Err(SoundFontError::InvalidFormat {
-                message: format!("Invalid instrument header chunk size: {} (must be multiple of {})", 
-                               inst_chunk.data.len(), INST_HEADER_SIZE),
-                position: None,
-            });
-        }
-        
-        let instrument_count = inst_chunk.data.len() / INST_HEADER_SIZE;
-        let mut instruments = Vec::new();
+        let headers: Vec<&[u8]> = inst_chunk.data.chunks_exact(INST_HEADER_SIZE).collect();
         
         // Parse instrument bag data (4 bytes each: generator_index, modulator_index)
-        let bag_data = Self::parse_bag_data(&ibag_chunk.data)?;
+        let bag_data = Self::parse_bag_data(whole_records(&ibag_chunk.data, BAG_SIZE))?;
         
-        // Parse generators
-        let generators = Self::parse_generators(&igen_chunk.data)?;
+        // Parse generators (without the terminal record)
+        let generators = Self::parse_generators(zone_records(&igen_chunk.data, GEN_SIZE))?;
         
         // Parse modulators (if present)
         let modulators = if let Some(imod_chunk) = imod_chunk {
-            Self::parse_modulators(&imod_chunk.data)?
+            Self::parse_modulators(zone_records(&imod_chunk.data, MOD_SIZE))?
         } else {
             Vec::new()
         };
         
-        for i in 0..instrument_count {
-            let header_offset = i * INST_HEADER_SIZE;
-            let header_data = &inst_chunk.data[header_offset..header_offset + INST_HEADER_SIZE];
-            
-            // Parse instrument header
-            let instrument = Self::parse_single_instrument_header(header_data, i, &bag_data, &generators, &modulators)?;
-            
-            // Skip terminal instrument (empty name)
-            if !instrument.name.is_empty() {
-                // Individual instrument debug removed
-                instruments.push(instrument);
-            }
+        let zone_bags = header_bag_ranges(&headers, 20, &bag_data, generators.len());
+        let mut instruments = Vec::new();
+        for (header_data, bags) in headers.iter().zip(zone_bags) {
+            // Parse instrument header (unnamed instruments are kept: presets refer to them by index)
+            let instrument = Self::parse_single_instrument_header(header_data, bags, &bag_data, &generators, &modulators)?;
+            instruments.push(instrument);
         }
         
         // Instrument parsing completion debug removed
@@ -646,40 +644,30 @@ impl SoundFontParser {
         
         // Preset chunks found debug removed
         
-        // Parse preset headers (38 bytes each)
+        // Parse preset headers (38 bytes each; bag index at byte 24)
         const PRESET_HEADER_SIZE: usize = 38;
-        if phdr_chunk.data.len() % PRESET_HEADER_SIZE != 0 {
-            return Err(SoundFontError::InvalidFormat {
-                message: format!("Invalid preset header chunk size: {} (must be multiple of {})", 
-                               phdr_chunk.data.len(), PRESET_HEADER_SIZE),
-                position: None,
-            });
-        }
-        
-        let preset_count = phdr_chunk.data.len() / PRESET_HEADER_SIZE;
-        let mut presets = Vec::new();
+        let headers: Vec<&[u8]> = phdr_chunk.data.chunks_exact(PRESET_HEADER_SIZE).collect();
         
         // Parse preset bag data
-        let bag_data = Self::parse_bag_data(&pbag_chunk.data)?;
+        let bag_data = Self::parse_bag_data(whole_records(&pbag_chunk.data, BAG_SIZE))?;
         
-        // Parse generators
-        let generators = Self::parse_generators(&pgen_chunk.data)?;
+        // Parse generators (without the terminal record)
+        let generators = Self::parse_generators(zone_records(&pgen_chunk.data, GEN_SIZE))?;
         
         // Parse modulators (if present)
         let modulators = if let Some(pmod_chunk) = pmod_chunk {
-            Self::parse_modulators(&pmod_chunk.data)?
+            Self::parse_modulators(zone_records(&pmod_chunk.data, MOD_SIZE))?
         } else {
             Vec::new()
         };
         
-        for i in 0..preset_count {
-            let header_offset = i * PRESET_HEADER_SIZE;
-            let header_data = &phdr_chunk.data[header_offset..header_offset + PRESET_HEADER_SIZE];
-            
+        let zone_bags = header_bag_ranges(&headers, 24, &bag_data, generators.len());
+        let mut presets = Vec::new();
+        for (i, (header_data, bags)) in headers.iter().zip(zone_bags).enumerate() {
             // Parse preset header
-            let preset = Self::parse_single_preset_header(header_data, i, &bag_data, &generators, &modulators, instruments)?;
+            let preset = Self::parse_single_preset_header(header_data, i, bags, &bag_data, &generators, &modulators, instruments)?;
             
-            // Skip terminal preset (empty name)
+            // Skip unnamed presets (nothing refers to a preset by index)
             if !preset.name.is_empty() {
                 // Individual preset debug removed
                 presets.push(preset);
@@ -692,7 +680,6 @@ impl SoundFontParser {
     
     /// Parse bag data (4 bytes each: generator_index, modulator_index)
     fn parse_bag_data(bag_data: &[u8]) -> SoundFontResult<Vec<(u16, u16)>> {
-        const BAG_SIZE: usize = 4;
         if bag_data.len() % BAG_SIZE != 0 {
             return Err(SoundFontError::InvalidFormat {
                 message: format!("Invalid bag chunk size: {} (must be multiple of {})", 
@@ -716,7 +703,6 @@ impl SoundFontParser {
     
     /// Parse generator data (4 bytes each: type, amount)
     fn parse_generators(gen_data: &[u8]) -> SoundFontResult<Vec<Generator>> {
-        const GEN_SIZE: usize = 4;
         if gen_data.len() % GEN_SIZE != 0 {
             return Err(SoundFontError::InvalidFormat {
                 message: format!("Invalid generator chunk size: {} (must be multiple of {})", 
@@ -757,7 +743,6 @@ impl SoundFontParser {
     
     /// Parse modulator data (10 bytes each)
     fn parse_modulators(mod_data: &[u8]) -> SoundFontResult<Vec<Modulator>> {
-        const MOD_SIZE: usize = 10;
         if mod_data.len() % MOD_SIZE != 0 {
             return Err(SoundFontError::InvalidFormat {
                 message: format!("Invalid modulator chunk size: {} (must be multiple of {})", 
@@ -794,7 +779,7 @@ impl SoundFontParser {
     /// Parse single instrument header (22 bytes)
     fn parse_single_instrument_header(
         header_data: &[u8],
-        instrument_index: usize,
+        zone_bags: Range<usize>,
         bag_data: &[(u16, u16)],
         generators: &[Generator],
         modulators: &[Modulator]
@@ -807,52 +792,18 @@ impl SoundFontParser {
         }
         
         // Extract instrument name (20 bytes, null-terminated)
-        let name_bytes = &header_data[0..20];
-        let name_end = name_bytes.iter().position(|&b| b == 0).unwrap_or(20);
-        let instrument_name = String::from_utf8_lossy(&name_bytes[0..name_end]).to_string();
+        let instrument_name = record_name(header_data);
         
         // Extract bag index
         let bag_index = u16::from_le_bytes([header_data[20], header_data[21]]) as usize;
         
-        // Determine bag range for this instrument
-        let next_bag_index = if instrument_index + 1 < bag_data.len() {
-            // Use next instrument's bag index (from next header)
-            if header_data.len() >= 44 { // Check if we have next header data
-                let next_bag_idx = u16::from_le_bytes([header_data[42], header_data[43]]) as usize;
-                next_bag_idx
-            } else {
-                bag_data.len() // Use end of bag data
-            }
-        } else {
-            bag_data.len() // Last instrument uses all remaining bags
-        };
-        
         // Parse instrument zones
         let mut instrument_zones = Vec::new();
         
-        for bag_idx in bag_index..next_bag_index {
-            if bag_idx >= bag_data.len() {
-                break;
-            }
-            
-            let (gen_start, mod_start) = bag_data[bag_idx];
-            
-            // Determine generator and modulator ranges
-            let gen_end = if bag_idx + 1 < bag_data.len() {
-                bag_data[bag_idx + 1].0
-            } else {
-                generators.len() as u16
-            };
-            
-            let mod_end = if bag_idx + 1 < bag_data.len() {
-                bag_data[bag_idx + 1].1
-            } else {
-                modulators.len() as u16
-            };
-            
+        for bag_idx in zone_bags {
             // Extract generators and modulators for this zone
-            let zone_generators = generators[gen_start as usize..gen_end as usize].to_vec();
-            let zone_modulators = modulators[mod_start as usize..mod_end as usize].to_vec();
+            let zone_generators = bag_items(generators, bag_data, bag_idx, |bag| bag.0);
+            let zone_modulators = bag_items(modulators, bag_data, bag_idx, |bag| bag.1);
             
             // Extract zone parameters
             let (sample_id, key_range, velocity_range) = Self::extract_zone_parameters(&zone_generators);
@@ -879,6 +830,7 @@ impl SoundFontParser {
     fn parse_single_preset_header(
         header_data: &[u8],
         preset_index: usize,
+        zone_bags: Range<usize>,
         bag_data: &[(u16, u16)],
         generators: &[Generator],
         modulators: &[Modulator],
@@ -894,9 +846,7 @@ impl SoundFontParser {
         }
         
         // Extract preset name (20 bytes, null-terminated)
-        let name_bytes = &header_data[0..20];
-        let name_end = name_bytes.iter().position(|&b| b == 0).unwrap_or(20);
-        let preset_name = String::from_utf8_lossy(&name_bytes[0..name_end]).to_string();
+        let preset_name = record_name(header_data);
         
         // Extract preset parameters
         let program = u16::from_le_bytes([header_data[20], header_data[21]]) as u8;
@@ -906,39 +856,13 @@ impl SoundFontParser {
         let genre = u32::from_le_bytes([header_data[30], header_data[31], header_data[32], header_data[33]]);
         let morphology = u32::from_le_bytes([header_data[34], header_data[35], header_data[36], header_data[37]]);
         
-        // Determine bag range for this preset (similar to instrument parsing)
-        let next_bag_index = if preset_index + 1 < bag_data.len() {
-            bag_data.len() // Simplified - use all remaining for now
-        } else {
-            bag_data.len()
-        };
-        
         // Parse preset zones
         let mut preset_zones = Vec::new();
         
-        for bag_idx in bag_index..next_bag_index {
-            if bag_idx >= bag_data.len() {
-                break;
-            }
-            
-            let (gen_start, mod_start) = bag_data[bag_idx];
-            
-            // Determine ranges
-            let gen_end = if bag_idx + 1 < bag_data.len() {
-                bag_data[bag_idx + 1].0
-            } else {
-                generators.len() as u16
-            };
-            
-            let mod_end = if bag_idx + 1 < bag_data.len() {
-                bag_data[bag_idx + 1].1
-            } else {
-                modulators.len() as u16
-            };
-            
+        for bag_idx in zone_bags {
             // Extract generators and modulators for this zone
-            let zone_generators = generators[gen_start as usize..gen_end as usize].to_vec();
-            let zone_modulators = modulators[mod_start as usize..mod_end as usize].to_vec();
+            let zone_generators = bag_items(generators, bag_data, bag_idx, |bag| bag.0);
+            let zone_modulators = bag_items(modulators, bag_data, bag_idx, |bag| bag.1);
             
             // Extract zone parameters
             let (instrument_id, key_range, velocity_range) = Self::extract_preset_zone_parameters(&zone_generators);
@@ -1070,4 +994,87 @@ impl SoundFontParser {
         // Structure validation debug removed
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/*
+ * Record bookkeeping for pdta sub-chunks
+ *
+ * Each header's zones run from its bag index to the next header's, and each bag's
+ * generators/modulators to the next bag's, with a terminal record closing every list.
+ * Fonts exported by some tools drop terminal records, leave partial records behind
+ * or carry indices that run backwards or past the end; the ranges below are rebuilt
+ * from whatever is there, so a bad record loses its own zones instead of the load failing.
+ */
+
+/// Bytes per bag record (generator index, modulator index)
+const BAG_SIZE: usize = 4;
+/// Bytes per generator record (type, amount)
+const GEN_SIZE: usize = 4;
+/// Bytes per modulator record
+const MOD_SIZE: usize = 10;
+
+/// 20-byte null-terminated name at the start of a phdr/inst/shdr record
+fn record_name(record: &[u8]) -> String {
+    let name_bytes = &record[..20.min(record.len())];
+    let name_end = name_bytes.iter().position(|&b| b == 0).unwrap_or(name_bytes.len());
+    String::from_utf8_lossy(&name_bytes[..name_end]).to_string()
+}
+
+/// Headers before the terminal one; a last record without a terminal name
+/// (the terminal record was left out) is a real header and is kept
+fn content_record_count(headers: &[&[u8]]) -> usize {
+    match headers.last() {
+        Some(last) => {
+            let name = record_name(last);
+            if name.is_empty() || TERMINAL_RECORD_NAMES.contains(&name.as_str()) {
+                headers.len() - 1
+            } else {
+                headers.len()
+            }
+        }
+        None => 0,
+    }
+}
+
+/// `data` cut to whole records (a trailing partial record is dropped)
+fn whole_records(data: &[u8], record_size: usize) -> &[u8] {
+    &data[..data.len() - data.len() % record_size]
+}
+
+/// Whole records of a generator/modulator list without its terminal (all-zero) record
+fn zone_records(data: &[u8], record_size: usize) -> &[u8] {
+    let data = whole_records(data, record_size);
+    match data.len().checked_sub(record_size) {
+        Some(last) if data[last..].iter().all(|&b| b == 0) => &data[..last],
+        _ => data,
+    }
+}
+
+/// Bag range of every header before the terminal one (bag index at `bag_offset`)
+///
+/// A header ends where the next one (or the terminal header) starts; without a
+/// terminal header the last one runs to the end of the bags, less the terminal
+/// bag when that is present (it points past the last generator). Indices are
+/// clamped into the bag list and never run backwards.
+fn header_bag_ranges(headers: &[&[u8]], bag_offset: usize, bags: &[(u16, u16)], generator_count: usize) -> Vec<Range<usize>> {
+    let bag_index = |header: &[u8]| u16::from_le_bytes([header[bag_offset], header[bag_offset + 1]]) as usize;
+    let zone_bags = match bags.last() {
+        Some(&(gen_index, _)) if gen_index as usize >= generator_count => bags.len() - 1,
+        _ => bags.len(),
+    };
+    (0..content_record_count(headers))
+        .map(|i| {
+            let start = bag_index(headers[i]).min(zone_bags);
+            let end = headers.get(i + 1).map_or(zone_bags, |next| bag_index(next));
+            start..end.clamp(start, zone_bags)
+        })
+        .collect()
+}
+
+/// Items of bag `bag`: from its index to the next bag's (or the end of `items`),
+/// clamped to the list
+fn bag_items<T: Clone>(items: &[T], bags: &[(u16, u16)], bag: usize, index: fn(&(u16, u16)) -> u16) -> Vec<T> {
+    let start = (index(&bags[bag]) as usize).min(items.len());
+    let end = bags.get(bag + 1).map_or(items.len(), |next| index(next) as usize);
+    items[start..end.clamp(start, items.len())].to_vec()
+}
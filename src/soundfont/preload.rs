@@ -0,0 +1,69 @@
+/**
+ * AWE Player - Preset Pre-Warming
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * A program change mid-song is heard on the next note, and that note pays
+ * for everything the preset has never done before: walking its preset and
+ * instrument zones and pulling its sample data into memory for the first
+ * time (the browser commits WASM memory pages on first touch). Pre-warming
+ * does that work on the control thread ahead of time: it walks every zone
+ * of the preset and reads each memory page of every sample it reaches.
+ */
+
+use serde::Serialize;
+use super::types::{SoundFont, SoundFontPreset};
+
+/// Sample frames per 4 KiB memory page
+const FRAMES_PER_PAGE: usize = 2048;
+
+/// What pre_load_preset touched (JSON)
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PreloadReport {
+    pub preset_index: usize,
+    pub preset_name: String,
+    pub zones: usize,             // Instrument zones reached through the preset
+    pub samples: usize,           // Distinct samples touched
+    pub sample_bytes: usize,
+}
+
+/// Walk `preset`'s zones and touch the sample data they play
+pub fn warm_preset(soundfont: &SoundFont, preset_index: usize, preset: &SoundFontPreset) -> PreloadReport {
+    let mut report = PreloadReport {
+        preset_index,
+        preset_name: preset.name.clone(),
+        ..PreloadReport::default()
+    };
+    let mut touched = vec![false; soundfont.samples.len()];
+    let mut checksum = 0i32;
+    let instrument_zones = preset.preset_zones.iter()
+        .filter_map(|zone| zone.instrument_id)
+        .filter_map(|id| soundfont.instruments.get(id as usize))
+        .flat_map(|instrument| instrument.instrument_zones.iter());
+    for zone in instrument_zones {
+        report.zones += 1;
+        let Some(id) = zone.sample_id.map(|id| id as usize) else {
+            continue;
+        };
+        let (Some(sample), Some(seen)) = (soundfont.samples.get(id), touched.get_mut(id)) else {
+            continue;
+        };
+        if *seen {
+            continue;
+        }
+        *seen = true;
+        report.samples += 1;
+        report.sample_bytes += sample.sample_data.len() * 2;
+        checksum = checksum.wrapping_add(touch_pages(&sample.sample_data));
+    }
+    // Keeps the reads from being optimized away
+    std::hint::black_box(checksum);
+    report
+}
+
+/// Read one frame per memory page (and the last frame)
+fn touch_pages(data: &[i16]) -> i32 {
+    let last = data.last().map_or(0, |&value| value as i32);
+    data.iter().step_by(FRAMES_PER_PAGE).fold(last, |sum, &value| sum.wrapping_add(value as i32))
+}
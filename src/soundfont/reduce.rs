@@ -0,0 +1,157 @@
+/**
+ * AWE Player - Load-Time Sample Reduction
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Optional memory savings applied to a parsed SoundFont before the engine
+ * takes it: samples above a target rate are downsampled (box-filtered, so
+ * content above the new Nyquist is attenuated rather than folded back) and
+ * very long samples are truncated. Both trade fidelity for fitting large
+ * banks into constrained WASM memory.
+ *
+ * Loop points and the address offset generators of the zones that use a
+ * resampled sample are rescaled to the new rate. Truncation never cuts into
+ * a loop: a looped sample keeps everything up to its loop end.
+ */
+
+use serde::Serialize;
+use std::sync::Arc;
+use super::types::{Generator, GeneratorAmount, GeneratorType, SoundFont, SoundFontSample};
+
+/// Lowest accepted target rate
+pub const MIN_TARGET_SAMPLE_RATE: u32 = 8000;
+
+/// Frames per unit of the coarse address offset generators
+const COARSE_OFFSET_FRAMES: i32 = 32768;
+
+/// Fine/coarse generator pairs addressing sample frames
+const ADDRESS_GENERATORS: [(GeneratorType, GeneratorType); 4] = [
+    (GeneratorType::StartAddrsOffset, GeneratorType::StartAddrsCoarseOffset),
+    (GeneratorType::EndAddrsOffset, GeneratorType::EndAddrsCoarseOffset),
+    (GeneratorType::StartloopAddrsOffset, GeneratorType::StartloopAddrsCoarseOffset),
+    (GeneratorType::EndloopAddrsOffset, GeneratorType::EndloopAddrsCoarseOffset),
+];
+
+/// Reduction applied at load time (both limits off by default)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SampleReduction {
+    pub target_sample_rate: Option<u32>,    // Downsample anything above this rate
+    pub max_sample_seconds: Option<f32>,    // Truncate samples longer than this
+}
+
+/// What the last load's reduction did (get_sample_reduction_report_global)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ReductionReport {
+    pub samples_resampled: usize,
+    pub samples_truncated: usize,
+    pub bytes_before: usize,
+    pub bytes_after: usize,
+}
+
+impl SampleReduction {
+    /// Options from export arguments: 0 (or non-finite seconds) disables a limit
+    pub fn from_limits(target_sample_rate: u32, max_sample_seconds: f32) -> Self {
+        SampleReduction {
+            target_sample_rate: (target_sample_rate > 0).then_some(target_sample_rate.max(MIN_TARGET_SAMPLE_RATE)),
+            max_sample_seconds: (max_sample_seconds.is_finite() && max_sample_seconds > 0.0).then_some(max_sample_seconds),
+        }
+    }
+
+    /// Reduce `soundfont` in place
+    pub fn apply(&self, soundfont: &mut SoundFont) -> ReductionReport {
+        let mut report = ReductionReport::default();
+        let mut ratios: Vec<Option<f64>> = vec![None; soundfont.samples.len()];
+        for (sample, ratio) in soundfont.samples.iter_mut().zip(ratios.iter_mut()) {
+            report.bytes_before += sample.sample_data.len() * 2;
+            if let Some(target) = self.target_sample_rate.filter(|&target| sample.sample_rate > target) {
+                *ratio = Some(resample(sample, target));
+                report.samples_resampled += 1;
+            }
+            if let Some(seconds) = self.max_sample_seconds {
+                if truncate(sample, seconds) {
+                    report.samples_truncated += 1;
+                }
+            }
+            report.bytes_after += sample.sample_data.len() * 2;
+        }
+
+        // Zone address offsets count frames at the old rate
+        for zone in soundfont.instruments.iter_mut().flat_map(|instrument| instrument.instrument_zones.iter_mut()) {
+            let Some(ratio) = zone.sample_id.and_then(|id| ratios.get(id as usize).copied().flatten()) else {
+                continue;
+            };
+            for (fine, coarse) in ADDRESS_GENERATORS.iter() {
+                let (fine_value, coarse_value) = (short_value(&zone.generators, fine), short_value(&zone.generators, coarse));
+                if fine_value.is_none() && coarse_value.is_none() {
+                    continue;
+                }
+                // Shrinking keeps a fine-only offset within the fine generator's range
+                let frames = coarse_value.unwrap_or(0) * COARSE_OFFSET_FRAMES + fine_value.unwrap_or(0);
+                let scaled = (frames as f64 * ratio).round() as i32;
+                let (new_coarse, new_fine) = (scaled / COARSE_OFFSET_FRAMES, scaled % COARSE_OFFSET_FRAMES);
+                for generator in zone.generators.iter_mut() {
+                    if &generator.generator_type == fine {
+                        generator.amount = GeneratorAmount::Short(new_fine as i16);
+                    } else if &generator.generator_type == coarse {
+                        generator.amount = GeneratorAmount::Short(new_coarse as i16);
+                    }
+                }
+                if fine_value.is_none() && new_fine != 0 {
+                    // A coarse-only offset no longer lands on a 32768-frame boundary
+                    zone.generators.push(Generator { generator_type: fine.clone(), amount: GeneratorAmount::Short(new_fine as i16) });
+                }
+            }
+        }
+        report
+    }
+}
+
+fn short_value(generators: &[Generator], kind: &GeneratorType) -> Option<i32> {
+    generators.iter()
+        .find(|generator| &generator.generator_type == kind)
+        .and_then(|generator| match generator.amount {
+            GeneratorAmount::Short(value) => Some(value as i32),
+            _ => None,
+        })
+}
+
+/// Downsample to `target` Hz, returns the frame ratio (new/old)
+fn resample(sample: &mut SoundFontSample, target: u32) -> f64 {
+    let ratio = target as f64 / sample.sample_rate as f64;
+    let step = 1.0 / ratio;
+    let source = &sample.sample_data;
+    let length = ((source.len() as f64) * ratio).ceil() as usize;
+    let mut output = Vec::with_capacity(length);
+    for index in 0..length {
+        // Average the source frames this output frame covers
+        let start = (index as f64 * step) as usize;
+        let end = (((index + 1) as f64 * step) as usize).clamp(start + 1, source.len());
+        let sum: i32 = source[start..end].iter().map(|&s| s as i32).sum();
+        output.push((sum / (end - start) as i32) as i16);
+    }
+
+    let scale = |frame: u32| ((frame as f64 * ratio).round() as u32).min(length as u32);
+    if sample.loop_end > sample.loop_start {
+        sample.loop_start = scale(sample.loop_start);
+        sample.loop_end = scale(sample.loop_end).max(sample.loop_start + 1).min(length as u32);
+    }
+    sample.end_offset = sample.start_offset + length as u32;
+    sample.sample_rate = target;
+    sample.sample_data = Arc::from(output);
+    ratio
+}
+
+/// Cut `sample` after `seconds` (looped samples keep their loop), true if it shrank
+fn truncate(sample: &mut SoundFontSample, seconds: f32) -> bool {
+    let mut limit = (seconds as f64 * sample.sample_rate as f64).ceil() as usize;
+    if sample.loop_end > sample.loop_start {
+        limit = limit.max(sample.loop_end as usize);
+    }
+    if sample.sample_data.len() <= limit {
+        return false;
+    }
+    sample.sample_data = Arc::from(&sample.sample_data[..limit]);
+    sample.end_offset = sample.start_offset + limit as u32;
+    true
+}
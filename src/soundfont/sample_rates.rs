@@ -0,0 +1,60 @@
+/**
+ * AWE Player - Sample Rate Validation
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Sample headers carry their own rate, and some editors write nonsense
+ * there: 0 for an undeclared rate, or values far beyond any converter.
+ * A zero rate turns every rate ratio downstream into a division by zero.
+ * Rates are checked when a bank is installed; undeclared rates are
+ * assumed to be 44.1 kHz, the rest are clamped to what SF2 playback
+ * supports, and each correction is recorded so a front end can show
+ * which samples may play at the wrong pitch.
+ */
+
+use serde::Serialize;
+use super::types::SoundFont;
+
+/// Rate assumed for a sample that declares none
+pub const DEFAULT_SAMPLE_RATE: u32 = 44_100;
+/// Lowest rate the SF2 specification expects players to support
+pub const MIN_SAMPLE_RATE: u32 = 400;
+/// Highest rate accepted as declared
+pub const MAX_SAMPLE_RATE: u32 = 192_000;
+
+/// A sample whose declared rate was replaced (get_sample_rate_warnings_global)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SampleRateWarning {
+    pub sample: usize,
+    pub name: String,
+    pub declared: u32,
+    pub corrected: u32,
+}
+
+/// The rate playback uses for a declared rate
+pub fn corrected_rate(declared: u32) -> u32 {
+    if declared == 0 {
+        DEFAULT_SAMPLE_RATE
+    } else {
+        declared.clamp(MIN_SAMPLE_RATE, MAX_SAMPLE_RATE)
+    }
+}
+
+/// Correct out-of-range sample rates in place, one warning per corrected sample
+pub fn sanitize_sample_rates(soundfont: &mut SoundFont) -> Vec<SampleRateWarning> {
+    let mut warnings = Vec::new();
+    for (index, sample) in soundfont.samples.iter_mut().enumerate() {
+        let corrected = corrected_rate(sample.sample_rate);
+        if corrected != sample.sample_rate {
+            warnings.push(SampleRateWarning {
+                sample: index,
+                name: sample.name.clone(),
+                declared: sample.sample_rate,
+                corrected,
+            });
+            sample.sample_rate = corrected;
+        }
+    }
+    warnings
+}
@@ -0,0 +1,155 @@
+/**
+ * AWE Player - Incremental SoundFont Loading
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Accepts an SF2 file in arbitrary chunks as it downloads. The RIFF header
+ * is validated as soon as the first 12 bytes arrive, so a wrong URL fails
+ * after one network read instead of after the whole body. The declared RIFF
+ * size gives the total for progress reporting and lets the buffer be
+ * allocated once. Top-level LIST sections (INFO, sdta, pdta) are tracked
+ * as they complete, but only to report them: nothing is parsed until
+ * finish(), which runs the whole-file parser once all data is in.
+ */
+
+use std::mem::MaybeUninit;
+use super::{SoundFontError, SoundFontParser, SoundFontResult, SoundFont};
+use crate::events::LoadStage;
+
+/// RIFF header: "RIFF", size, "sfbk"
+const RIFF_HEADER_LEN: usize = 12;
+
+/// Upper bound for the up-front reservation; larger files grow as data arrives
+pub const MAX_PREALLOCATE_BYTES: usize = 256 * 1024 * 1024;
+
+/// Receives an SF2 file chunk by chunk
+#[derive(Debug, Default)]
+pub struct SoundFontStream {
+    buffer: Vec<u8>,
+    total_bytes: Option<usize>,       // Declared RIFF size + 8, known after the header
+    next_section: usize,              // Offset of the first top-level chunk not yet complete
+    sections: Vec<[u8; 4]>,           // LIST form types completed so far
+}
+
+impl SoundFontStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append downloaded bytes; fails as soon as the data cannot be an SF2 file
+    pub fn push(&mut self, data: &[u8]) -> SoundFontResult<()> {
+        self.buffer.extend_from_slice(data);
+        self.appended()
+    }
+
+    /// push() for `len` bytes that `fill` copies straight into the buffer's spare capacity
+    /// (e.g. Uint8Array::copy_to_uninit), without a temporary copy; `fill` returns the
+    /// slice it was given, now initialized
+    pub fn push_from(
+        &mut self,
+        len: usize,
+        fill: impl for<'a> FnOnce(&'a mut [MaybeUninit<u8>]) -> &'a mut [u8],
+    ) -> SoundFontResult<()> {
+        self.buffer.reserve(len);
+        let start = self.buffer.len();
+        let spare = &mut self.buffer.spare_capacity_mut()[..len];
+        let spare_ptr = spare.as_ptr() as *const u8;
+        let written = fill(spare);
+        assert!(std::ptr::eq(written.as_ptr(), spare_ptr) && written.len() == len, "fill must initialize the slice it was given");
+        // SAFETY: the `len` bytes after `start` were just initialized by `fill` (checked above)
+        unsafe { self.buffer.set_len(start + len) };
+        self.appended()
+    }
+
+    /// Validate the header once it is complete and record finished sections
+    fn appended(&mut self) -> SoundFontResult<()> {
+        let had_header = self.total_bytes.is_some();
+        if !had_header && self.buffer.len() >= RIFF_HEADER_LEN {
+            let total = Self::validate_header(&self.buffer)?;
+            let reserve = total.min(MAX_PREALLOCATE_BYTES).saturating_sub(self.buffer.len());
+            self.buffer.reserve_exact(reserve);
+            self.total_bytes = Some(total);
+            self.next_section = RIFF_HEADER_LEN;
+        }
+        if self.total_bytes.is_some() {
+            self.scan_sections();
+        }
+        Ok(())
+    }
+
+    fn validate_header(header: &[u8]) -> SoundFontResult<usize> {
+        if &header[0..4] != b"RIFF" {
+            return Err(SoundFontError::InvalidFormat {
+                message: "Invalid RIFF signature".to_string(),
+                position: Some(0),
+            });
+        }
+        if &header[8..12] != b"sfbk" {
+            return Err(SoundFontError::InvalidFormat {
+                message: format!("Invalid SoundFont format: expected 'sfbk', found '{}'",
+                                 String::from_utf8_lossy(&header[8..12])),
+                position: Some(8),
+            });
+        }
+        let riff_size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        Ok(riff_size as usize + 8)
+    }
+
+    /// Record every top-level chunk that is now fully buffered
+    fn scan_sections(&mut self) {
+        while self.next_section + 8 <= self.buffer.len() {
+            let header = &self.buffer[self.next_section..self.next_section + 8];
+            let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+            let end = self.next_section + 8 + size + size % 2;
+            if end > self.buffer.len() {
+                break;
+            }
+            if &header[0..4] == b"LIST" && size >= 4 {
+                let form = &self.buffer[self.next_section + 8..self.next_section + 12];
+                self.sections.push([form[0], form[1], form[2], form[3]]);
+            }
+            self.next_section = end;
+        }
+    }
+
+    pub fn bytes_received(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// File size declared by the RIFF header (None until 12 bytes have arrived)
+    pub fn total_bytes(&self) -> Option<usize> {
+        self.total_bytes
+    }
+
+    /// 0.0-1.0, or None while the total is unknown
+    pub fn progress(&self) -> Option<f32> {
+        self.total_bytes
+            .map(|total| (self.buffer.len() as f32 / total.max(1) as f32).min(1.0))
+    }
+
+    /// Whether the LIST section with this form type ("INFO", "sdta", "pdta") is complete
+    pub fn has_section(&self, form: &[u8; 4]) -> bool {
+        self.sections.contains(form)
+    }
+
+    /// Parse the buffered file; fails if the stream ended before the declared size
+    pub fn finish(self) -> SoundFontResult<SoundFont> {
+        self.finish_with_progress(&mut |_, _| {})
+    }
+
+    /// finish() with parse progress (see SoundFontParser::parse_soundfont_with_progress)
+    pub fn finish_with_progress(self, progress: &mut dyn FnMut(LoadStage, f32)) -> SoundFontResult<SoundFont> {
+        let total = self.total_bytes.ok_or_else(|| SoundFontError::InvalidFormat {
+            message: "File too small for RIFF header".to_string(),
+            position: Some(0),
+        })?;
+        if self.buffer.len() < total {
+            return Err(SoundFontError::RiffError {
+                chunk_type: "RIFF".to_string(),
+                expected_size: Some(u32::try_from(total).unwrap_or(u32::MAX)),
+                actual_size: self.buffer.len() as u32,
+                message: "Stream ended before the end of the file".to_string(),
+            });
+        }
+        SoundFontParser::parse_soundfont_with_progress(&self.buffer, progress)
+    }
+}
@@ -10,6 +10,7 @@
 
 use super::{SoundFontResult, SoundFontError};
 use crate::log;
+use std::sync::Arc;
 
 /// SoundFont file header information
 #[derive(Debug, Clone)]
@@ -98,7 +99,7 @@ pub struct SoundFontSample {
     pub pitch_correction: i8,      // Pitch correction in cents (-50 to +50)
     pub sample_link: u16,          // Link to stereo partner sample
     pub sample_type: SampleType,   // Sample type (mono, stereo, etc.)
-    pub sample_data: Vec<i16>,     // 16-bit PCM sample data
+    pub sample_data: Arc<[i16]>,   // 16-bit PCM sample data (shared with playing voices)
 }
 
 /// Sample type enumeration
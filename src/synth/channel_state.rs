@@ -0,0 +1,304 @@
+/**
+ * AWE Player - Per-Channel MIDI State
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Tracks the state each of the 16 MIDI channels needs at note-on time:
+ * pending bank select, resolved preset, percussion flag, the GM2
+ * sound controllers (CC70-79: resonance, release/attack time, brightness
+ * and vibrato rate/depth/delay act as offsets on the SoundFont values),
+ * the RPN/NRPN selected for Data Entry and RPN 1 fine tuning.
+ * The mixer controllers (CC7/10/11/64) are kept for channel telemetry,
+ * and the last pitch bend for the controller snapshot a UI mirror (or a
+ * check that chase-on-seek restored a channel) reads.
+ */
+
+use serde::Serialize;
+use super::multizone_voice::PlaybackDirection;
+use super::mod_envelope::FilterEnvelopeShape;
+use crate::midi::constants::{MIDI_CC_EXPRESSION, MIDI_CC_PAN, MIDI_CC_SUSTAIN, MIDI_CC_VOLUME};
+
+/// First sound controller number (CC70 - Sound Variation)
+pub const SOUND_CONTROLLER_FIRST: u8 = 70;
+/// Last sound controller number (CC79)
+pub const SOUND_CONTROLLER_LAST: u8 = 79;
+
+/// GM2 sound controller assignments
+pub const SC_RESONANCE: u8 = 71;      // Timbre/Harmonic Intensity
+pub const SC_RELEASE_TIME: u8 = 72;
+pub const SC_ATTACK_TIME: u8 = 73;
+pub const SC_BRIGHTNESS: u8 = 74;
+pub const SC_VIBRATO_RATE: u8 = 76;
+pub const SC_VIBRATO_DEPTH: u8 = 77;
+pub const SC_VIBRATO_DELAY: u8 = 78;
+
+/// Onset delay CC78 adds at 127 to a patch's vibrato delay
+pub const MAX_ADDED_VIBRATO_DELAY_SECONDS: f32 = 1.0;
+
+/// RPN 0/1: channel fine tuning, 14-bit Data Entry (8192 = center, ±100 cents)
+pub const RPN_FINE_TUNING: u16 = 1;
+
+/// NRPN 126/0 (AWE Player extension): sample playback direction
+/// Data Entry MSB: 0 = forward, 1 = reverse, 2 = ping-pong
+pub const NRPN_PLAYBACK_DIRECTION: u16 = 126 << 7;
+
+/// Largest humanize start offset window (longer skips eat into the attack)
+pub const MAX_START_OFFSET_WINDOW_MS: f32 = 50.0;
+
+/// RPN 127/127 deselects the current parameter
+const RPN_NULL: u16 = (127 << 7) | 127;
+
+/// RPN 1 value with no detune
+const FINE_TUNING_CENTER: u16 = 8192;
+
+/// Parameter number (14-bit) targeted by Data Entry (CC6/CC38)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterNumber {
+    Rpn(u16),
+    Nrpn(u16),
+}
+
+/// Sound controllers CC70-79 (64 = no change)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoundControllers {
+    values: [u8; 10],
+}
+
+impl SoundControllers {
+    pub fn new() -> Self {
+        Self { values: [64; 10] }
+    }
+
+    /// Store controller value, returns false if `controller` is not CC70-79
+    pub fn set(&mut self, controller: u8, value: u8) -> bool {
+        if !(SOUND_CONTROLLER_FIRST..=SOUND_CONTROLLER_LAST).contains(&controller) {
+            return false;
+        }
+        self.values[(controller - SOUND_CONTROLLER_FIRST) as usize] = value.min(127);
+        true
+    }
+
+    pub fn get(&self, controller: u8) -> u8 {
+        if (SOUND_CONTROLLER_FIRST..=SOUND_CONTROLLER_LAST).contains(&controller) {
+            self.values[(controller - SOUND_CONTROLLER_FIRST) as usize]
+        } else {
+            64
+        }
+    }
+
+    /// Bipolar offset for a controller (-1.0 at 0, 0.0 at 64, ~+1.0 at 127)
+    fn offset(&self, controller: u8) -> f32 {
+        (self.get(controller) as f32 - 64.0) / 64.0
+    }
+
+    /// Filter cutoff multiplier from CC74 (±2 octaves)
+    pub fn cutoff_scale(&self) -> f32 {
+        2.0_f32.powf(self.offset(SC_BRIGHTNESS) * 2.0)
+    }
+
+    /// Filter Q multiplier from CC71 (±2 octaves of Q)
+    pub fn resonance_scale(&self) -> f32 {
+        2.0_f32.powf(self.offset(SC_RESONANCE) * 2.0)
+    }
+
+    /// Attack time multiplier from CC73 (1/16x to 16x)
+    pub fn attack_time_scale(&self) -> f32 {
+        2.0_f32.powf(self.offset(SC_ATTACK_TIME) * 4.0)
+    }
+
+    /// Release time multiplier from CC72 (1/16x to 16x)
+    pub fn release_time_scale(&self) -> f32 {
+        2.0_f32.powf(self.offset(SC_RELEASE_TIME) * 4.0)
+    }
+
+    /// Vibrato LFO rate multiplier from CC76 (±2 octaves)
+    pub fn vibrato_rate_scale(&self) -> f32 {
+        2.0_f32.powf(self.offset(SC_VIBRATO_RATE) * 2.0)
+    }
+
+    /// Vibrato depth multiplier from CC77 (1/4x to 4x)
+    pub fn vibrato_depth_scale(&self) -> f32 {
+        2.0_f32.powf(self.offset(SC_VIBRATO_DEPTH) * 2.0)
+    }
+
+    /// Vibrato onset delay from CC78 given the patch's delay: below 64 shortens
+    /// it (none at 0), above 64 adds up to MAX_ADDED_VIBRATO_DELAY_SECONDS
+    pub fn vibrato_delay_seconds(&self, base_seconds: f32) -> f32 {
+        let offset = self.offset(SC_VIBRATO_DELAY);
+        if offset < 0.0 {
+            base_seconds * (1.0 + offset)
+        } else {
+            base_seconds + offset * MAX_ADDED_VIBRATO_DELAY_SECONDS
+        }
+    }
+
+    pub fn is_neutral(&self) -> bool {
+        self.values.iter().all(|&v| v == 64)
+    }
+}
+
+impl Default for SoundControllers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// State of one MIDI channel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelState {
+    pub bank_msb: u8,                 // CC0 (applied at next program change)
+    pub bank_lsb: u8,                 // CC32 (applied at next program change)
+    pub program: u8,
+    pub preset_index: Option<usize>,  // Resolved preset (None = global preset selection)
+    pub is_drum: bool,
+    pub sound_controllers: SoundControllers,
+    pub playback_direction: PlaybackDirection,
+    pub filter_envelope: Option<FilterEnvelopeShape>, // Host filter-envelope shape for new notes
+    pub start_offset_window_ms: f32,  // Humanize: random sample start within this window (0 = off)
+    pub volume: u8,                   // CC7
+    pub pan: u8,                      // CC10
+    pub expression: u8,               // CC11
+    pub sustain: bool,                // CC64
+    pub modulation: u8,               // CC1 (reaches voices through modulation routes)
+    pub pressure: u8,                 // Channel aftertouch
+    pub pitch_bend: f32,              // Semitones
+    fine_tuning: u16,                 // RPN 1, 14-bit
+    parameter_msb: u8,
+    parameter_lsb: u8,
+    selected_parameter: Option<ParameterNumber>,
+}
+
+impl ChannelState {
+    pub fn new(channel: u8) -> Self {
+        Self {
+            bank_msb: 0,
+            bank_lsb: 0,
+            program: 0,
+            preset_index: None,
+            is_drum: channel == crate::midi::constants::MIDI_DRUM_CHANNEL,
+            sound_controllers: SoundControllers::new(),
+            playback_direction: PlaybackDirection::Forward,
+            filter_envelope: None,
+            start_offset_window_ms: 0.0,
+            volume: 100,
+            pan: 64,
+            expression: 127,
+            sustain: false,
+            modulation: 0,
+            pressure: 0,
+            pitch_bend: 0.0,
+            fine_tuning: FINE_TUNING_CENTER,
+            parameter_msb: 127,
+            parameter_lsb: 127,
+            selected_parameter: None,
+        }
+    }
+    
+    /// Record CC7/CC10/CC11/CC64, returns false for any other controller
+    pub fn set_mixer_controller(&mut self, controller: u8, value: u8) -> bool {
+        let value = value & 0x7F;
+        match controller {
+            MIDI_CC_VOLUME => self.volume = value,
+            MIDI_CC_PAN => self.pan = value,
+            MIDI_CC_EXPRESSION => self.expression = value,
+            MIDI_CC_SUSTAIN => self.sustain = value >= 64,
+            _ => return false,
+        }
+        true
+    }
+    
+    /// CC99 (NRPN MSB) / CC101 (RPN MSB)
+    pub fn set_parameter_msb(&mut self, nrpn: bool, value: u8) {
+        self.parameter_msb = value & 0x7F;
+        self.update_selected_parameter(nrpn);
+    }
+    
+    /// CC98 (NRPN LSB) / CC100 (RPN LSB)
+    pub fn set_parameter_lsb(&mut self, nrpn: bool, value: u8) {
+        self.parameter_lsb = value & 0x7F;
+        self.update_selected_parameter(nrpn);
+    }
+    
+    pub fn selected_parameter(&self) -> Option<ParameterNumber> {
+        self.selected_parameter
+    }
+    
+    /// RPN 1 Data Entry MSB (clears the LSB, as a coarse step should)
+    pub fn set_fine_tuning_msb(&mut self, value: u8) {
+        self.fine_tuning = ((value & 0x7F) as u16) << 7;
+    }
+    
+    /// RPN 1 Data Entry LSB
+    pub fn set_fine_tuning_lsb(&mut self, value: u8) {
+        self.fine_tuning = (self.fine_tuning & !0x7F) | (value & 0x7F) as u16;
+    }
+    
+    /// RPN 1 fine tuning in cents (-100 to just under +100)
+    pub fn fine_tuning_cents(&self) -> f32 {
+        (self.fine_tuning as f32 - FINE_TUNING_CENTER as f32) / FINE_TUNING_CENTER as f32 * 100.0
+    }
+    
+    /// Controller snapshot of channel `channel`, with its CC91/CC93 send levels (0.0-1.0)
+    pub fn report(&self, channel: u8, reverb_send: f32, chorus_send: f32) -> ChannelStateReport {
+        let (rpn, nrpn) = match self.selected_parameter {
+            Some(ParameterNumber::Rpn(number)) => (Some(number), None),
+            Some(ParameterNumber::Nrpn(number)) => (None, Some(number)),
+            None => (None, None),
+        };
+        ChannelStateReport {
+            channel,
+            bank_msb: self.bank_msb,
+            bank_lsb: self.bank_lsb,
+            program: self.program,
+            preset_index: self.preset_index,
+            is_drum: self.is_drum,
+            volume: self.volume,
+            pan: self.pan,
+            expression: self.expression,
+            sustain: self.sustain,
+            modulation: self.modulation,
+            pressure: self.pressure,
+            reverb_send: (reverb_send.clamp(0.0, 1.0) * 127.0).round() as u8,
+            chorus_send: (chorus_send.clamp(0.0, 1.0) * 127.0).round() as u8,
+            sound_controllers: self.sound_controllers.values,
+            pitch_bend: self.pitch_bend,
+            rpn,
+            nrpn,
+            fine_tuning_cents: self.fine_tuning_cents(),
+        }
+    }
+    
+    fn update_selected_parameter(&mut self, nrpn: bool) {
+        let number = ((self.parameter_msb as u16) << 7) | self.parameter_lsb as u16;
+        self.selected_parameter = match (nrpn, number) {
+            (false, RPN_NULL) => None,
+            (false, number) => Some(ParameterNumber::Rpn(number)),
+            (true, number) => Some(ParameterNumber::Nrpn(number)),
+        };
+    }
+}
+
+/// Controller snapshot of one channel (get_channel_state_global)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ChannelStateReport {
+    pub channel: u8,
+    pub bank_msb: u8,                 // CC0 (applied at next program change)
+    pub bank_lsb: u8,                 // CC32
+    pub program: u8,
+    pub preset_index: Option<usize>,  // Resolved preset (null = global preset selection)
+    pub is_drum: bool,
+    pub volume: u8,                   // CC7
+    pub pan: u8,                      // CC10
+    pub expression: u8,               // CC11
+    pub sustain: bool,                // CC64 >= 64
+    pub modulation: u8,               // CC1
+    pub pressure: u8,                 // Channel aftertouch
+    pub reverb_send: u8,              // CC91
+    pub chorus_send: u8,              // CC93
+    pub sound_controllers: [u8; 10],  // CC70-79 (64 = no change)
+    pub pitch_bend: f32,              // Semitones
+    pub rpn: Option<u16>,             // RPN selected for Data Entry (14-bit)
+    pub nrpn: Option<u16>,            // NRPN selected for Data Entry (14-bit)
+    pub fine_tuning_cents: f32,       // RPN 1
+}
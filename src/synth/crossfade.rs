@@ -0,0 +1,101 @@
+/**
+ * AWE Player - Velocity Crossfade Curves
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Zones with velocity ranges fade in and out at the edges of their range
+ * so layers blend instead of switching. The original fade was a straight
+ * line over a quarter of the range; where two layers overlap, their
+ * linear gains sum to one but their power does not, and a layered piano
+ * audibly dips between dynamics. Equal-power (sine) and S-curve
+ * (smoothstep) shapes avoid that, and the fade width is adjustable.
+ */
+
+use serde::Serialize;
+
+/// Default fade width: a quarter of the zone's velocity range at each edge
+pub const DEFAULT_CROSSFADE_WIDTH: f32 = 0.25;
+/// Widest fade (the two edges meet in the middle)
+pub const MAX_CROSSFADE_WIDTH: f32 = 0.5;
+
+/// Shape of the fade at a velocity range edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum CrossfadeCurve {
+    #[default]
+    Linear,
+    EqualPower,     // sin(t * pi/2): constant power across overlapping layers
+    SCurve,         // Smoothstep: gentle at both ends of the fade
+}
+
+impl CrossfadeCurve {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "linear" => Some(CrossfadeCurve::Linear),
+            "equalPower" => Some(CrossfadeCurve::EqualPower),
+            "sCurve" => Some(CrossfadeCurve::SCurve),
+            _ => None,
+        }
+    }
+
+    /// Gain at position `t` (0.0-1.0) through the fade-in
+    pub fn shape(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            CrossfadeCurve::Linear => t,
+            CrossfadeCurve::EqualPower => (t * std::f32::consts::FRAC_PI_2).sin(),
+            CrossfadeCurve::SCurve => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// Curve and width used for velocity range crossfades
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct VelocityCrossfade {
+    pub curve: CrossfadeCurve,
+    pub width: f32,     // Fraction of the range faded at each edge (0.0 = hard switch)
+}
+
+impl VelocityCrossfade {
+    pub fn new(curve: CrossfadeCurve, width: f32) -> Self {
+        let width = if width.is_finite() { width.clamp(0.0, MAX_CROSSFADE_WIDTH) } else { DEFAULT_CROSSFADE_WIDTH };
+        Self { curve, width }
+    }
+
+    /// Weight of a zone spanning `range_low`-`range_high` at `velocity`
+    pub fn weight(&self, velocity: u8, range_low: u8, range_high: u8) -> f32 {
+        if velocity < range_low || velocity > range_high {
+            return 0.0; // Outside range
+        }
+
+        let range_size = range_high - range_low;
+        if range_size <= 4 {
+            return 1.0; // Small range, no crossfading
+        }
+
+        let crossfade_size = (range_size as f32 * self.width).floor();
+        if crossfade_size < 1.0 {
+            return 1.0; // Hard switch at the range edges
+        }
+        let velocity_pos = (velocity - range_low) as f32;
+
+        if velocity_pos < crossfade_size {
+            // Fade in at start of range
+            self.curve.shape(velocity_pos / crossfade_size)
+        } else if velocity_pos > range_size as f32 - crossfade_size {
+            // Fade out at end of range
+            self.curve.shape((range_size as f32 - velocity_pos) / crossfade_size)
+        } else {
+            // Full weight in middle of range
+            1.0
+        }
+    }
+}
+
+impl Default for VelocityCrossfade {
+    fn default() -> Self {
+        Self::new(CrossfadeCurve::Linear, DEFAULT_CROSSFADE_WIDTH)
+    }
+}
@@ -0,0 +1,157 @@
+/**
+ * AWE Player - Device Profiles
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * A phone and a desktop need different trade-offs, and the knobs that
+ * decide them belong together: a larger block gives the callback slack,
+ * fewer voices and linear interpolation cut the per-sample cost, and the
+ * chorus (or both effects) can be skipped. A profile sets all four at
+ * once. Hosts pick one by name, or leave the choice to auto-detection
+ * from the core count and memory the browser reports
+ * (navigator.hardwareConcurrency / navigator.deviceMemory), using the
+ * same thresholds as the buffer manager's size suggestion.
+ *
+ * The live profile is for playing a MIDI keyboard rather than a device
+ * class, and is never detected: it takes the smallest block the Web Audio
+ * render quantum allows, runs dry (reverb and chorus are the costliest
+ * stages after the voices), and makes the bridge hand live input to the
+ * voices the moment it is queued, with no render-ahead in front of it.
+ */
+
+use serde::Serialize;
+use super::voice_manager::MAX_VOICES;
+
+/// Sample interpolation used by voices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum InterpolationQuality {
+    #[default]
+    Linear,
+    Cubic,      // 4-point Hermite: cleaner high-pitched playback, about twice the cost
+}
+
+impl InterpolationQuality {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "linear" => Some(InterpolationQuality::Linear),
+            "cubic" => Some(InterpolationQuality::Cubic),
+            _ => None,
+        }
+    }
+}
+
+/// How much of the effects section runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum EffectsQuality {
+    #[default]
+    Full,
+    Reduced,    // Reverb only
+    Off,        // Dry mix
+}
+
+impl EffectsQuality {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "full" => Some(EffectsQuality::Full),
+            "reduced" => Some(EffectsQuality::Reduced),
+            "off" => Some(EffectsQuality::Off),
+            _ => None,
+        }
+    }
+}
+
+/// Named device class
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum DeviceProfile {
+    LowEnd,
+    Mid,
+    Desktop,
+    Live,       // Playing live: lowest latency first
+}
+
+/// What a profile configures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ProfileSettings {
+    pub buffer_size: usize,
+    pub interpolation: InterpolationQuality,
+    pub max_polyphony: usize,
+    pub effects: EffectsQuality,
+}
+
+impl DeviceProfile {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "lowEnd" | "low" | "mobile" => Some(DeviceProfile::LowEnd),
+            "mid" => Some(DeviceProfile::Mid),
+            "desktop" => Some(DeviceProfile::Desktop),
+            "live" => Some(DeviceProfile::Live),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DeviceProfile::LowEnd => "lowEnd",
+            DeviceProfile::Mid => "mid",
+            DeviceProfile::Desktop => "desktop",
+            DeviceProfile::Live => "live",
+        }
+    }
+
+    /// Profile for the reported core count and memory (GB)
+    pub fn detect(hardware_concurrency: u32, device_memory_gb: u32) -> Self {
+        if hardware_concurrency >= 8 && device_memory_gb >= 8 {
+            DeviceProfile::Desktop
+        } else if hardware_concurrency >= 4 && device_memory_gb >= 4 {
+            DeviceProfile::Mid
+        } else {
+            DeviceProfile::LowEnd
+        }
+    }
+
+    pub fn settings(&self) -> ProfileSettings {
+        match self {
+            DeviceProfile::LowEnd => ProfileSettings {
+                buffer_size: 512,
+                interpolation: InterpolationQuality::Linear,
+                max_polyphony: 16,
+                effects: EffectsQuality::Reduced,
+            },
+            DeviceProfile::Mid => ProfileSettings {
+                buffer_size: 256,
+                interpolation: InterpolationQuality::Cubic,
+                max_polyphony: 24,
+                effects: EffectsQuality::Full,
+            },
+            DeviceProfile::Desktop => ProfileSettings {
+                buffer_size: 128,
+                interpolation: InterpolationQuality::Cubic,
+                max_polyphony: MAX_VOICES,
+                effects: EffectsQuality::Full,
+            },
+            DeviceProfile::Live => ProfileSettings {
+                buffer_size: 128,
+                interpolation: InterpolationQuality::Linear,
+                max_polyphony: MAX_VOICES,
+                effects: EffectsQuality::Off,
+            },
+        }
+    }
+}
+
+/// Active profile and how it was chosen (get_device_profile_global)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct DeviceProfileStatus {
+    pub profile: Option<DeviceProfile>,     // None = engine defaults, nothing applied yet
+    pub auto_detect: bool,
+    pub settings: ProfileSettings,          // Values currently in effect
+}
@@ -4,12 +4,17 @@
  * Implements authentic EMU8000 envelope behavior with:
  * - 6-stage envelope: Delay → Attack → Hold → Decay → Sustain → Release
  * - Exponential curves (FluidSynth-compatible)
+ * - Table-driven stage curves: no powf/exp in the per-sample path
  * - SoundFont 2.0 generator parameter support
  * - Key scaling for authentic instrument behavior
  */
 
+use serde::Serialize;
+
 /// EMU8000 envelope states for 6-stage DAHDSR envelope
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum EnvelopeState {
     /// Envelope is inactive (voice not playing)
     Off,
@@ -41,6 +46,74 @@ pub fn centibels_to_linear(centibels: i32) -> f32 {
     10.0_f32.powf(-centibels as f32 / 200.0)
 }
 
+/// SoundFont 2.0 sustainVolEnv range: centibels of attenuation below the peak
+/// 1000 (100 dB) and above mean fully attenuated; negative values mean no attenuation
+pub const MAX_SUSTAIN_ATTENUATION_CB: i32 = 1000;
+
+/// Sustain level (0.0-1.0) for a sustainVolEnv value (generator 37)
+/// Clamped to 0-1000 cB; 1000 cB is silence, not the -100 dB the formula would give
+pub fn sustain_level_from_centibels(sustain_centibels: i32) -> f32 {
+    let centibels = sustain_centibels.clamp(0, MAX_SUSTAIN_ATTENUATION_CB);
+    if centibels == MAX_SUSTAIN_ATTENUATION_CB {
+        return 0.0;
+    }
+    centibels_to_linear(centibels)
+}
+
+/// Levels below this (-100dB) are flushed to zero so long tails never go denormal
+const SILENCE_THRESHOLD: f32 = 0.00001;
+
+/// Shortest release any note gets; a zero-length release would drop straight to silence and click
+pub const MIN_RELEASE_SECONDS: f32 = 0.005;
+
+/// Precomputed curve for one envelope stage
+/// Produces shape(n / length) for n = 0, 1, 2... by forward differencing:
+/// two adds per sample instead of a powf. f64 keeps multi-second stages drift-free.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CurveSegment {
+    value: f64,
+    step: f64,
+    step_delta: f64,
+}
+
+impl CurveSegment {
+    /// Ease-in curve p² (attack: slow start, fast finish)
+    pub fn ease_in(length: u32) -> Self {
+        if length == 0 {
+            return Self { value: 1.0, step: 0.0, step_delta: 0.0 };
+        }
+        let length_sq = (length as f64) * (length as f64);
+        Self { value: 0.0, step: 1.0 / length_sq, step_delta: 2.0 / length_sq }
+    }
+    
+    /// Ease-out curve 1 - (1 - p)² (decay/release: fast start, slow finish)
+    pub fn ease_out(length: u32) -> Self {
+        if length == 0 {
+            return Self { value: 1.0, step: 0.0, step_delta: 0.0 };
+        }
+        let length = length as f64;
+        Self {
+            value: 0.0,
+            step: 2.0 / length - 1.0 / (length * length),
+            step_delta: -2.0 / (length * length),
+        }
+    }
+    
+    /// Current curve position (0.0-1.0), then advance one sample
+    #[inline]
+    pub fn advance(&mut self) -> f32 {
+        let value = self.value.clamp(0.0, 1.0) as f32;
+        self.value += self.step;
+        self.step += self.step_delta;
+        value
+    }
+}
+
+/// Stage curve slots in the segment table
+const SEGMENT_ATTACK: usize = 0;
+const SEGMENT_DECAY: usize = 1;
+const SEGMENT_RELEASE: usize = 2;
+
 /// EMU8000 6-stage DAHDSR envelope generator
 /// Implements authentic envelope behavior with exponential curves
 #[derive(Debug, Clone)]
@@ -58,11 +131,18 @@ pub struct DAHDSREnvelope {
     pub hold_samples: u32,
     pub decay_samples: u32,
     pub release_samples: u32,
+    /// Release floor (MIN_RELEASE_SECONDS at this envelope's sample rate)
+    pub min_release_samples: u32,
     
     /// Sustain level (0.0 to 1.0, converted from centibels)
     pub sustain_level: f32,
     /// Level when release phase started (for proper release calculation)
     pub release_start_level: f32,
+    
+    /// Initial curve state per stage, built by prepare_segments()
+    segment_table: [CurveSegment; 3],
+    /// Curve of the stage currently running
+    active_segment: CurveSegment,
 }
 
 impl DAHDSREnvelope {
@@ -93,14 +173,26 @@ impl DAHDSREnvelope {
             hold_samples: (hold_seconds * sample_rate) as u32,
             decay_samples: (decay_seconds * sample_rate) as u32,
             release_samples: (release_seconds * sample_rate) as u32,
-            sustain_level: centibels_to_linear(sustain_centibels),
+            min_release_samples: ((MIN_RELEASE_SECONDS * sample_rate) as u32).max(1),
+            sustain_level: sustain_level_from_centibels(sustain_centibels),
             release_start_level: 0.0,
+            segment_table: [CurveSegment::default(); 3],
+            active_segment: CurveSegment::default(),
         }
     }
     
+    /// Rebuild stage curves from the current stage lengths
+    /// Call after changing attack/decay/release sample counts on a triggered envelope
+    pub fn prepare_segments(&mut self) {
+        self.segment_table[SEGMENT_ATTACK] = CurveSegment::ease_in(self.attack_samples);
+        self.segment_table[SEGMENT_DECAY] = CurveSegment::ease_out(self.decay_samples);
+        self.segment_table[SEGMENT_RELEASE] = CurveSegment::ease_out(self.release_samples);
+    }
+    
     /// Trigger envelope start (note-on event)
     /// Transitions from Off state to Delay stage
     pub fn trigger(&mut self) {
+        self.prepare_segments();
         self.state = EnvelopeState::Delay;
         self.stage_samples = 0;
         self.current_level = 0.0;
@@ -109,11 +201,58 @@ impl DAHDSREnvelope {
     /// Release envelope (note-off event)
     /// Transitions to Release stage from any active state
     pub fn release(&mut self) {
-        if self.state != EnvelopeState::Off {
-            // Store the current level when release starts (for proper release calculation)
-            self.release_start_level = self.current_level;
-            self.state = EnvelopeState::Release;
-            self.stage_samples = 0;
+        if self.state == EnvelopeState::Off {
+            return;
+        }
+        // Nothing audible left to release
+        if self.current_level <= SILENCE_THRESHOLD {
+            self.state = EnvelopeState::Off;
+            self.current_level = 0.0;
+            return;
+        }
+        if self.release_samples < self.min_release_samples {
+            self.release_samples = self.min_release_samples;
+            self.segment_table[SEGMENT_RELEASE] = CurveSegment::ease_out(self.release_samples);
+        }
+        // Store the current level when release starts (for proper release calculation)
+        self.release_start_level = self.current_level;
+        self.enter_stage(EnvelopeState::Release);
+    }
+    
+    /// Release over just the minimum release time (voice stealing, All Sound Off)
+    /// Restarts the release from the current level if one is already running
+    pub fn fast_release(&mut self) {
+        self.release_samples = self.min_release_samples;
+        self.segment_table[SEGMENT_RELEASE] = CurveSegment::ease_out(self.release_samples);
+        self.release();
+    }
+    
+    /// How far through the current stage the envelope is (0.0-1.0)
+    /// Sustain and Off have no length and report 0.0
+    pub fn stage_progress(&self) -> f32 {
+        let length = match self.state {
+            EnvelopeState::Off | EnvelopeState::Sustain => return 0.0,
+            EnvelopeState::Delay => self.delay_samples,
+            EnvelopeState::Attack => self.attack_samples,
+            EnvelopeState::Hold => self.hold_samples,
+            EnvelopeState::Decay => self.decay_samples,
+            EnvelopeState::Release => self.release_samples,
+        };
+        if length == 0 {
+            return 1.0;
+        }
+        (self.stage_samples as f32 / length as f32).min(1.0)
+    }
+    
+    /// Switch stage and load its precomputed curve
+    fn enter_stage(&mut self, state: EnvelopeState) {
+        self.state = state;
+        self.stage_samples = 0;
+        match state {
+            EnvelopeState::Attack => self.active_segment = self.segment_table[SEGMENT_ATTACK],
+            EnvelopeState::Decay => self.active_segment = self.segment_table[SEGMENT_DECAY],
+            EnvelopeState::Release => self.active_segment = self.segment_table[SEGMENT_RELEASE],
+            _ => {}
         }
     }
     
@@ -130,21 +269,17 @@ impl DAHDSREnvelope {
                 self.current_level = 0.0;
                 self.stage_samples += 1;
                 if self.stage_samples >= self.delay_samples {
-                    self.state = EnvelopeState::Attack;
-                    self.stage_samples = 0;
+                    self.enter_stage(EnvelopeState::Attack);
                 }
             },
             EnvelopeState::Attack => {
                 // Exponential rise from 0 to 1.0
                 if self.attack_samples > 0 {
-                    let progress = (self.stage_samples as f32 / self.attack_samples as f32).min(1.0);
-                    let exp_progress = progress.powf(2.0); // EMU8000/FluidSynth exponential curve
-                    self.current_level = exp_progress;
+                    self.current_level = self.active_segment.advance();
                 }
                 self.stage_samples += 1;
                 if self.stage_samples >= self.attack_samples {
-                    self.state = EnvelopeState::Hold;
-                    self.stage_samples = 0;
+                    self.enter_stage(EnvelopeState::Hold);
                     self.current_level = 1.0; // Ensure we reach peak
                 }
             },
@@ -153,22 +288,25 @@ impl DAHDSREnvelope {
                 self.current_level = 1.0;
                 self.stage_samples += 1;
                 if self.stage_samples >= self.hold_samples {
-                    self.state = EnvelopeState::Decay;
-                    self.stage_samples = 0;
+                    self.enter_stage(EnvelopeState::Decay);
                 }
             },
             EnvelopeState::Decay => {
                 // Exponential fall from 1.0 to sustain_level (fast-start, slow-end)
                 if self.decay_samples > 0 {
-                    let progress = (self.stage_samples as f32 / self.decay_samples as f32).min(1.0);
-                    let exp_progress = 1.0 - (1.0 - progress).powf(2.0); // Fast-start exponential decay
-                    self.current_level = 1.0 + (self.sustain_level - 1.0) * exp_progress;
+                    let progress = self.active_segment.advance();
+                    self.current_level = 1.0 + (self.sustain_level - 1.0) * progress;
                 }
                 self.stage_samples += 1;
                 if self.stage_samples >= self.decay_samples {
-                    self.state = EnvelopeState::Sustain;
-                    self.stage_samples = 0;
-                    self.current_level = self.sustain_level; // Ensure we reach sustain level
+                    if self.sustain_level == 0.0 {
+                        // Fully attenuated sustain: nothing left to hold, the note ends with its decay
+                        self.state = EnvelopeState::Off;
+                        self.current_level = 0.0;
+                    } else {
+                        self.enter_stage(EnvelopeState::Sustain);
+                        self.current_level = self.sustain_level; // Ensure we reach sustain level
+                    }
                 }
             },
             EnvelopeState::Sustain => {
@@ -178,15 +316,13 @@ impl DAHDSREnvelope {
             EnvelopeState::Release => {
                 // Exponential fall from release start level to 0 (fast-start, slow-end)
                 if self.release_samples > 0 {
-                    let progress = (self.stage_samples as f32 / self.release_samples as f32).min(1.0);
-                    let exp_progress = 1.0 - (1.0 - progress).powf(2.0); // Fast-start exponential decay
-                    self.current_level = self.release_start_level * (1.0 - exp_progress);
+                    let progress = self.active_segment.advance();
+                    self.current_level = self.release_start_level * (1.0 - progress);
                 }
                 self.stage_samples += 1;
-                // Only check amplitude if we started from a non-zero level
                 // CRITICAL FIX: Envelope was truncating at 0.1% - way too high for quiet sustained notes!
-                // Changed from 0.001 to 0.00001 (0.001%) to allow much quieter sustain levels
-                let amplitude_finished = self.current_level <= 0.00001 && self.release_start_level > 0.00001;
+                // Threshold is 0.001% so quiet sustain levels still release fully
+                let amplitude_finished = self.current_level <= SILENCE_THRESHOLD;
                 if self.stage_samples >= self.release_samples || amplitude_finished {
                     self.state = EnvelopeState::Off;
                     self.current_level = 0.0;
@@ -194,6 +330,11 @@ impl DAHDSREnvelope {
             },
         }
         
+        // Flush sub-audible levels (e.g. decay towards zero sustain) before they go denormal
+        if self.current_level < SILENCE_THRESHOLD {
+            self.current_level = 0.0;
+        }
+        
         self.current_level
     }
-}
\ No newline at end of file
+}
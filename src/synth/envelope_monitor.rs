@@ -0,0 +1,135 @@
+/**
+ * AWE Player - Envelope Monitor
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Teaching tools draw each voice's DAHDSR envelope as it runs: which
+ * stage it is in, how far through that stage, and the level it is at.
+ * Like the channel telemetry, the bridge packs this into a fixed buffer
+ * a UI reads straight from WASM memory - but at a rate the host picks
+ * (in rendered audio time, off by default), since 32 voices at every
+ * block is more than a 60 Hz display needs.
+ */
+
+use serde::Serialize;
+use super::envelope::EnvelopeState;
+use super::multizone_voice::MultiZoneSampleVoice;
+use super::voice_manager::MAX_VOICES;
+
+/// Floats per voice in the packed buffer:
+/// stage (EnvelopeState order, 0 = off), progress, level, note, channel
+pub const ENVELOPE_STRIDE: usize = 5;
+/// Packed buffer size (every voice, voice order)
+pub const ENVELOPE_MONITOR_FLOATS: usize = ENVELOPE_STRIDE * MAX_VOICES;
+/// Fastest refresh rate (updates per second of rendered audio)
+pub const MAX_MONITOR_RATE_HZ: f32 = 240.0;
+
+/// One voice's volume envelope (get_voice_envelopes_global)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct VoiceEnvelope {
+    pub voice: usize,
+    pub active: bool,
+    pub note: u8,
+    pub channel: u8,
+    pub stage: EnvelopeState,
+    pub progress: f32,          // Through the current stage, 0.0-1.0 (0 in sustain)
+    pub level: f32,             // Envelope output, 0.0-1.0
+}
+
+impl VoiceEnvelope {
+    pub fn of(voice_index: usize, voice: &MultiZoneSampleVoice) -> Self {
+        let active = voice.is_active();
+        let (stage, progress) = if active { voice.get_volume_envelope_stage() } else { (EnvelopeState::Off, 0.0) };
+        Self {
+            voice: voice_index,
+            active,
+            note: voice.get_note(),
+            channel: voice.get_channel(),
+            stage,
+            progress,
+            level: if active { voice.get_volume_envelope_level() } else { 0.0 },
+        }
+    }
+}
+
+/// Refresh timing and the packed buffer
+#[derive(Debug, Clone)]
+pub struct EnvelopeMonitor {
+    rate_hz: f32,                   // 0 = off
+    frames_until_update: f32,
+    updates: u32,
+    buffer: [f32; ENVELOPE_MONITOR_FLOATS],
+}
+
+impl EnvelopeMonitor {
+    pub fn new() -> Self {
+        Self {
+            rate_hz: 0.0,
+            frames_until_update: 0.0,
+            updates: 0,
+            buffer: [0.0; ENVELOPE_MONITOR_FLOATS],
+        }
+    }
+
+    /// Updates per second of rendered audio (0 = off, up to MAX_MONITOR_RATE_HZ)
+    /// False (and unchanged) for negative or non-finite rates
+    pub fn set_rate(&mut self, rate_hz: f32) -> bool {
+        if !rate_hz.is_finite() || rate_hz < 0.0 {
+            return false;
+        }
+        self.rate_hz = rate_hz.min(MAX_MONITOR_RATE_HZ);
+        self.frames_until_update = 0.0; // First update on the next block
+        if self.rate_hz == 0.0 {
+            self.buffer.fill(0.0);
+        }
+        true
+    }
+
+    pub fn rate(&self) -> f32 {
+        self.rate_hz
+    }
+
+    /// Refreshes so far, wrapping (a UI can skip redraws while it is unchanged)
+    pub fn updates(&self) -> u32 {
+        self.updates
+    }
+
+    /// Count `frames` of rendered audio; true when the buffer is due a refresh
+    pub fn advance(&mut self, frames: usize, sample_rate: f32) -> bool {
+        if self.rate_hz == 0.0 {
+            return false;
+        }
+        self.frames_until_update -= frames as f32;
+        if self.frames_until_update > 0.0 {
+            return false;
+        }
+        self.frames_until_update += sample_rate / self.rate_hz;
+        self.frames_until_update = self.frames_until_update.max(0.0);
+        true
+    }
+
+    /// Write every voice to the buffer (ENVELOPE_STRIDE floats each)
+    pub fn pack(&mut self, envelopes: &[VoiceEnvelope; MAX_VOICES]) {
+        for (envelope, slot) in envelopes.iter().zip(self.buffer.chunks_exact_mut(ENVELOPE_STRIDE)) {
+            slot.copy_from_slice(&[
+                envelope.stage as u8 as f32,
+                envelope.progress,
+                envelope.level,
+                envelope.note as f32,
+                envelope.channel as f32,
+            ]);
+        }
+        self.updates = self.updates.wrapping_add(1);
+    }
+
+    pub fn buffer(&self) -> &[f32; ENVELOPE_MONITOR_FLOATS] {
+        &self.buffer
+    }
+}
+
+impl Default for EnvelopeMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,330 @@
+/**
+ * AWE Player - Procedural GM Fallback Bank
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Built-in bank used while no SoundFont is loaded, so MIDI files are
+ * recognizable before the real SoundFont finishes loading.
+ *
+ * The bank is an ordinary in-memory SoundFont and plays through the
+ * normal MultiZoneSampleVoice path:
+ * - Bank 0: 128 presets, one per GM program, grouped by GM family. Each family
+ *   maps to a looped sine/triangle/square/noise table plus an envelope preset.
+ * - Bank 128: one drum kit with synthesized kick, snare, toms, hats, cymbals
+ *   and a generic percussion click.
+ *
+ * Waveform tables hold a whole number of A440 cycles at the engine sample
+ * rate (voices play samples at 1:1 and transpose by root key only).
+ */
+
+use crate::soundfont::types::*;
+use crate::midi::bank_select::SOUNDFONT_DRUM_BANK;
+
+/// Preset index of the drum kit (after the 128 melodic presets)
+pub const FALLBACK_DRUM_PRESET_INDEX: usize = 128;
+
+/// Root key of the waveform tables (A4)
+const WAVE_ROOT_KEY: u8 = 69;
+
+/// Peak amplitude of generated samples (leaves headroom for layering)
+const SAMPLE_PEAK: f32 = 0.6;
+
+/// Waveforms available to melodic presets
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Waveform {
+    Sine,
+    Triangle,
+    Square,
+    Noise,
+}
+
+impl Waveform {
+    /// Sample index in the generated bank
+    fn sample_index(self) -> u16 {
+        match self {
+            Waveform::Sine => 0,
+            Waveform::Triangle => 1,
+            Waveform::Square => 2,
+            Waveform::Noise => 3,
+        }
+    }
+}
+
+/// Per-family envelope preset (timecents / centibels as in SoundFont generators)
+struct FamilyPreset {
+    name: &'static str,
+    waveform: Waveform,
+    attack: i16,
+    decay: i16,
+    sustain: i16,
+    release: i16,
+}
+
+/// GM families (program / 8)
+const GM_FAMILIES: [FamilyPreset; 16] = [
+    FamilyPreset { name: "Piano", waveform: Waveform::Triangle, attack: -11959, decay: 1902, sustain: 300, release: -2786 },
+    FamilyPreset { name: "Chromatic Percussion", waveform: Waveform::Sine, attack: -11959, decay: 1200, sustain: 1000, release: -2084 },
+    FamilyPreset { name: "Organ", waveform: Waveform::Square, attack: -9000, decay: -11959, sustain: 0, release: -5186 },
+    FamilyPreset { name: "Guitar", waveform: Waveform::Triangle, attack: -11959, decay: 1200, sustain: 400, release: -2786 },
+    FamilyPreset { name: "Bass", waveform: Waveform::Triangle, attack: -11959, decay: 0, sustain: 200, release: -3986 },
+    FamilyPreset { name: "Strings", waveform: Waveform::Triangle, attack: -2786, decay: -11959, sustain: 0, release: -1200 },
+    FamilyPreset { name: "Ensemble", waveform: Waveform::Triangle, attack: -2084, decay: -11959, sustain: 0, release: -1200 },
+    FamilyPreset { name: "Brass", waveform: Waveform::Square, attack: -5186, decay: -1200, sustain: 60, release: -3986 },
+    FamilyPreset { name: "Reed", waveform: Waveform::Square, attack: -7973, decay: -11959, sustain: 0, release: -5186 },
+    FamilyPreset { name: "Pipe", waveform: Waveform::Sine, attack: -5186, decay: -11959, sustain: 0, release: -3986 },
+    FamilyPreset { name: "Synth Lead", waveform: Waveform::Square, attack: -11959, decay: -11959, sustain: 0, release: -3986 },
+    FamilyPreset { name: "Synth Pad", waveform: Waveform::Triangle, attack: -1200, decay: -11959, sustain: 0, release: 0 },
+    FamilyPreset { name: "Synth Effects", waveform: Waveform::Triangle, attack: -1200, decay: 1200, sustain: 200, release: 1200 },
+    FamilyPreset { name: "Ethnic", waveform: Waveform::Triangle, attack: -11959, decay: 1200, sustain: 400, release: -2786 },
+    FamilyPreset { name: "Percussive", waveform: Waveform::Sine, attack: -11959, decay: -1200, sustain: 1000, release: -2786 },
+    FamilyPreset { name: "Sound Effects", waveform: Waveform::Noise, attack: -5186, decay: -11959, sustain: 0, release: -2084 },
+];
+
+/// Synthesized drum voices (declaration order = sample order in the bank)
+#[derive(Debug, Clone, Copy)]
+enum DrumSound {
+    Kick,
+    Snare,
+    Tom,
+    ClosedHat,
+    OpenHat,
+    Cymbal,
+    Click,
+}
+
+const DRUM_SOUNDS: [DrumSound; 7] = [
+    DrumSound::Kick, DrumSound::Snare, DrumSound::Tom, DrumSound::ClosedHat,
+    DrumSound::OpenHat, DrumSound::Cymbal, DrumSound::Click,
+];
+
+/// Drum kit layout: (low key, high key, sound)
+/// Keys away from a sound's natural pitch are transposed (e.g. tom pitches)
+const DRUM_ZONES: &[(u8, u8, DrumSound)] = &[
+    (27, 34, DrumSound::Click),
+    (35, 36, DrumSound::Kick),
+    (37, 40, DrumSound::Snare),
+    (41, 41, DrumSound::Tom),
+    (42, 42, DrumSound::ClosedHat),
+    (43, 43, DrumSound::Tom),
+    (44, 44, DrumSound::ClosedHat),
+    (45, 45, DrumSound::Tom),
+    (46, 46, DrumSound::OpenHat),
+    (47, 48, DrumSound::Tom),
+    (49, 49, DrumSound::Cymbal),
+    (50, 50, DrumSound::Tom),
+    (51, 53, DrumSound::Cymbal),
+    (54, 54, DrumSound::ClosedHat),
+    (55, 55, DrumSound::Cymbal),
+    (56, 56, DrumSound::Click),
+    (57, 57, DrumSound::Cymbal),
+    (58, 58, DrumSound::Click),
+    (59, 59, DrumSound::Cymbal),
+    (60, 87, DrumSound::Click),
+];
+
+/// Build the procedural fallback bank for the given engine sample rate
+pub fn build_fallback_soundfont(sample_rate: f32) -> SoundFont {
+    let mut samples = vec![
+        wave_sample("Fallback Sine", Waveform::Sine, sample_rate),
+        wave_sample("Fallback Triangle", Waveform::Triangle, sample_rate),
+        wave_sample("Fallback Square", Waveform::Square, sample_rate),
+        wave_sample("Fallback Noise", Waveform::Noise, sample_rate),
+    ];
+
+    // One instrument per GM family
+    let mut instruments: Vec<SoundFontInstrument> = GM_FAMILIES.iter().map(|family| SoundFontInstrument {
+        name: family.name.to_string(),
+        instrument_bag_index: 0,
+        instrument_zones: vec![InstrumentZone {
+            generators: envelope_generators(family.attack, family.decay, family.sustain, family.release),
+            modulators: Vec::new(),
+            sample_id: Some(family.waveform.sample_index()),
+            key_range: None,
+            velocity_range: None,
+        }],
+    }).collect();
+
+    // Drum kit instrument: one zone per key range
+    let drum_sample_base = samples.len() as u16;
+    samples.extend(DRUM_SOUNDS.iter().map(|&sound| drum_sample(sound, sample_rate)));
+    let drum_zones = DRUM_ZONES.iter().map(|&(low, high, sound)| InstrumentZone {
+        generators: envelope_generators(-11959, -11959, 0, 0),
+        modulators: Vec::new(),
+        sample_id: Some(drum_sample_base + sound as u16),
+        key_range: Some(KeyRange { low, high }),
+        velocity_range: None,
+    }).collect();
+    instruments.push(SoundFontInstrument {
+        name: "Fallback Drum Kit".to_string(),
+        instrument_bag_index: 0,
+        instrument_zones: drum_zones,
+    });
+
+    let mut presets: Vec<SoundFontPreset> = (0..128u8).map(|program| {
+        let family = (program / 8) as usize;
+        preset(format!("{} {}", GM_FAMILIES[family].name, program + 1), 0, program, family as u16)
+    }).collect();
+    presets.push(preset("Standard Kit".to_string(), SOUNDFONT_DRUM_BANK, 0, GM_FAMILIES.len() as u16));
+
+    let mut header = SoundFontHeader::new();
+    header.name = "AWE Player Fallback Bank".to_string();
+    header.comments = "Procedurally generated - used until a SoundFont is loaded".to_string();
+    header.preset_count = presets.len();
+    header.instrument_count = instruments.len();
+    header.sample_count = samples.len();
+
+    SoundFont { header, presets, instruments, samples }
+}
+
+fn preset(name: String, bank: u16, program: u8, instrument_id: u16) -> SoundFontPreset {
+    SoundFontPreset {
+        name,
+        program,
+        bank,
+        preset_bag_index: 0,
+        library: 0,
+        genre: 0,
+        morphology: 0,
+        preset_zones: vec![PresetZone {
+            generators: Vec::new(),
+            modulators: Vec::new(),
+            instrument_id: Some(instrument_id),
+            key_range: None,
+            velocity_range: None,
+        }],
+    }
+}
+
+fn short_generator(generator_type: GeneratorType, value: i16) -> Generator {
+    Generator { generator_type, amount: GeneratorAmount::Short(value) }
+}
+
+fn envelope_generators(attack: i16, decay: i16, sustain: i16, release: i16) -> Vec<Generator> {
+    vec![
+        short_generator(GeneratorType::AttackVolEnv, attack),
+        short_generator(GeneratorType::DecayVolEnv, decay),
+        short_generator(GeneratorType::SustainVolEnv, sustain),
+        short_generator(GeneratorType::ReleaseVolEnv, release),
+    ]
+}
+
+/// Looped waveform table: 0.1s holding exactly 44 cycles of 440Hz
+fn wave_sample(name: &str, waveform: Waveform, sample_rate: f32) -> SoundFontSample {
+    let length = ((sample_rate / 10.0).round() as usize).max(64);
+    let cycles = 44.0;
+    let mut noise = NoiseSource::new(0x1234_5678);
+
+    let mut data: Vec<i16> = (0..length).map(|i| {
+        let phase = (i as f32 * cycles / length as f32).fract();
+        let value = match waveform {
+            Waveform::Sine => (phase * std::f32::consts::TAU).sin(),
+            Waveform::Triangle => band_limited(phase, |h| {
+                // Odd harmonics, 1/h² with alternating sign
+                let sign = if (h / 2) % 2 == 0 { 1.0 } else { -1.0 };
+                sign * 8.0 / (std::f32::consts::PI.powi(2) * (h * h) as f32)
+            }),
+            Waveform::Square => band_limited(phase, |h| 4.0 / (std::f32::consts::PI * h as f32)) * 0.7,
+            Waveform::Noise => noise.next() * 0.5,
+        };
+        to_pcm(value)
+    }).collect();
+    // Guard sample so interpolation at the loop end reads the loop start
+    data.push(data[0]);
+
+    pcm_sample(name, data, sample_rate, length as u32, WAVE_ROOT_KEY)
+}
+
+/// Additive synthesis over odd harmonics 1..=15 (keeps aliasing down when transposed up)
+fn band_limited(phase: f32, amplitude: impl Fn(u32) -> f32) -> f32 {
+    (1..=15).step_by(2)
+        .map(|h| amplitude(h) * (phase * h as f32 * std::f32::consts::TAU).sin())
+        .sum()
+}
+
+/// One-shot drum sample
+fn drum_sample(sound: DrumSound, sample_rate: f32) -> SoundFontSample {
+    let mut noise = NoiseSource::new(0x9E37_79B9);
+    let (name, duration, root_key) = match sound {
+        DrumSound::Kick => ("Fallback Kick", 0.35, 36),
+        DrumSound::Snare => ("Fallback Snare", 0.25, 38),
+        DrumSound::Tom => ("Fallback Tom", 0.4, 45),
+        DrumSound::ClosedHat => ("Fallback Closed Hat", 0.08, 42),
+        DrumSound::OpenHat => ("Fallback Open Hat", 0.4, 46),
+        DrumSound::Cymbal => ("Fallback Cymbal", 1.2, 49),
+        DrumSound::Click => ("Fallback Click", 0.05, 64),
+    };
+    let length = (sample_rate * duration) as usize;
+    let mut phase = 0.0f32;
+    let mut previous_noise = 0.0f32;
+
+    let data = (0..length).map(|i| {
+        let t = i as f32 / sample_rate;
+        let white = noise.next();
+        // First difference brightens noise for metallic sounds
+        let bright = white - previous_noise;
+        previous_noise = white;
+
+        let value = match sound {
+            DrumSound::Kick | DrumSound::Tom => {
+                let (start_hz, end_hz, decay) = if matches!(sound, DrumSound::Kick) {
+                    (150.0, 45.0, 8.0)
+                } else {
+                    (220.0, 110.0, 6.0)
+                };
+                let frequency = end_hz + (start_hz - end_hz) * (-t * 30.0).exp();
+                phase = (phase + frequency / sample_rate).fract();
+                (phase * std::f32::consts::TAU).sin() * (-t * decay).exp()
+            }
+            DrumSound::Snare => {
+                phase = (phase + 185.0 / sample_rate).fract();
+                let tone = (phase * std::f32::consts::TAU).sin() * (-t * 25.0).exp();
+                tone * 0.5 + white * 0.7 * (-t * 14.0).exp()
+            }
+            DrumSound::ClosedHat => bright * 0.5 * (-t * 60.0).exp(),
+            DrumSound::OpenHat => bright * 0.5 * (-t * 8.0).exp(),
+            DrumSound::Cymbal => bright * 0.4 * (-t * 3.0).exp(),
+            DrumSound::Click => white * (-t * 90.0).exp(),
+        };
+        to_pcm(value)
+    }).collect();
+
+    pcm_sample(name, data, sample_rate, 0, root_key)
+}
+
+fn pcm_sample(name: &str, sample_data: Vec<i16>, sample_rate: f32, loop_end: u32, original_pitch: u8) -> SoundFontSample {
+    SoundFontSample {
+        name: name.to_string(),
+        start_offset: 0,
+        end_offset: sample_data.len() as u32,
+        loop_start: 0,
+        loop_end,
+        sample_rate: sample_rate as u32,
+        original_pitch,
+        pitch_correction: 0,
+        sample_link: 0,
+        sample_type: SampleType::MonoSample,
+        sample_data: sample_data.into(),
+    }
+}
+
+fn to_pcm(value: f32) -> i16 {
+    (value.clamp(-1.0, 1.0) * SAMPLE_PEAK * 32767.0) as i16
+}
+
+/// Deterministic xorshift noise so the bank is identical on every build
+struct NoiseSource {
+    state: u32,
+}
+
+impl NoiseSource {
+    fn new(seed: u32) -> Self {
+        Self { state: seed.max(1) }
+    }
+
+    /// Next value in -1.0..1.0
+    fn next(&mut self) -> f32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        (self.state as f32 / u32::MAX as f32) * 2.0 - 1.0
+    }
+}
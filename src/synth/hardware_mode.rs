@@ -0,0 +1,70 @@
+/**
+ * AWE Player - EMU8000 Hardware Mode
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * "Modern" synthesis keeps every stage in 32-bit float. "Hardware" mode
+ * re-imposes the limits of the EMU8000 registers and DAC so the output can
+ * be compared with a real AWE32:
+ * each voice and the final mix are quantized to 16 bits, and the mix hard
+ * clips at full scale instead of relying on float headroom. Pitch is capped
+ * at two octaves above the sample's original rate, the filter cutoff moves
+ * in the 256 steps of the 8-bit cutoff register, and reverb/chorus sends
+ * use 8-bit levels.
+ *
+ * The 2-pole filter, its 100 Hz-8 kHz range and linear interpolation are
+ * shared by both modes.
+ */
+
+/// Synthesis fidelity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SynthesisMode {
+    Modern,
+    Hardware,
+}
+
+impl SynthesisMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "modern" => Some(SynthesisMode::Modern),
+            "hardware" => Some(SynthesisMode::Hardware),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SynthesisMode::Modern => "modern",
+            SynthesisMode::Hardware => "hardware",
+        }
+    }
+
+    pub fn is_hardware(&self) -> bool {
+        *self == SynthesisMode::Hardware
+    }
+}
+
+/// Highest playback rate the pitch register reaches (two octaves up)
+pub const MAX_PITCH_RATIO: f64 = 4.0;
+
+const CUTOFF_MIN_HZ: f32 = 100.0;
+const CUTOFF_MAX_HZ: f32 = 8000.0;
+const CUTOFF_STEPS: f32 = 255.0;
+const SEND_STEPS: f32 = 255.0;
+const PCM_FULL_SCALE: f32 = 32768.0;
+
+/// Round to the 16-bit PCM grid and clip at full scale
+pub fn quantize_16bit(sample: f32) -> f32 {
+    ((sample.clamp(-1.0, 1.0) * PCM_FULL_SCALE).round().min(PCM_FULL_SCALE - 1.0)) / PCM_FULL_SCALE
+}
+
+/// Nearest of the 256 logarithmically spaced cutoff register values
+pub fn quantize_cutoff(cutoff_hz: f32) -> f32 {
+    let span = (CUTOFF_MAX_HZ / CUTOFF_MIN_HZ).ln();
+    let position = ((cutoff_hz.clamp(CUTOFF_MIN_HZ, CUTOFF_MAX_HZ) / CUTOFF_MIN_HZ).ln() / span * CUTOFF_STEPS).round();
+    CUTOFF_MIN_HZ * (position / CUTOFF_STEPS * span).exp()
+}
+
+/// Effects send level on the 8-bit register grid
+pub fn quantize_send(level: f32) -> f32 {
+    (level.clamp(0.0, 1.0) * SEND_STEPS).round() / SEND_STEPS
+}
@@ -7,9 +7,20 @@
  * - Multiple waveforms: sine, triangle, square
  * - SoundFont 2.0 generator compliance (generators 21-25)
  * - Phase synchronization and reset capabilities
+ * - Onset delay (delayModLFO/delayVibLFO): output holds at zero until it elapses
+ *
+ * Start phase follows the EMU8000: after the delay every waveform leaves zero
+ * on its upward slope (SF2 2.01: "begins its upward ramp from zero value"),
+ * so the triangle starts a quarter cycle in rather than at its minimum.
+ * Square has no zero crossing and starts high.
+ *
+ * Free-running phase (LfoPhaseMode::Random) is an option on top: each note's
+ * LFOs start a random fraction of a cycle past that point, so a stack of
+ * unison voices doesn't pulse in lockstep.
  */
 
-use crate::log;
+use std::f32::consts::PI;
+use super::envelope::timecents_to_seconds;
 
 /// LFO waveform types available in EMU8000
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -19,6 +30,24 @@ pub enum LfoWaveform {
     Square,    // Step modulation (on/off)
 }
 
+/// Where a new note's LFOs start their cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LfoPhaseMode {
+    #[default]
+    Sync,       // Rising zero crossing on every note (EMU8000, deterministic)
+    Random,     // Random start phase per voice, from a seeded generator
+}
+
+impl LfoPhaseMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sync" => Some(LfoPhaseMode::Sync),
+            "random" => Some(LfoPhaseMode::Random),
+            _ => None,
+        }
+    }
+}
+
 /// Low-frequency oscillator for modulation effects
 #[derive(Debug, Clone)]
 pub struct LFO {
@@ -36,6 +65,10 @@ pub struct LFO {
     pub phase_increment: f32,
     /// Current LFO output level (-1.0 to 1.0)
     pub current_level: f32,
+    /// Samples of silence after trigger before the LFO starts
+    pub delay_samples: u32,
+    /// Delay samples left until the LFO starts
+    pub delay_remaining: u32,
 }
 
 impl LFO {
@@ -47,17 +80,16 @@ impl LFO {
         // Calculate phase increment for frequency
         let phase_increment = clamped_frequency / sample_rate;
         
-        log(&format!("LFO created: freq={:.2}Hz depth={:.3} waveform={:?}", 
-                   clamped_frequency, depth, waveform));
-        
         LFO {
             waveform,
             frequency_hz: clamped_frequency,
             depth: depth.clamp(0.0, 1.0),
             sample_rate,
-            phase: 0.0,
+            phase: Self::start_phase(waveform),
             phase_increment,
             current_level: 0.0,
+            delay_samples: 0,
+            delay_remaining: 0,
         }
     }
     
@@ -72,6 +104,12 @@ impl LFO {
         self.depth = depth.clamp(0.0, 1.0);
     }
     
+    /// Set onset delay in seconds (restarts the delay from now)
+    pub fn set_delay(&mut self, seconds: f32) {
+        self.delay_samples = (seconds.max(0.0) * self.sample_rate) as u32;
+        self.delay_remaining = self.delay_samples;
+    }
+    
     /// Set waveform type
     pub fn set_waveform(&mut self, waveform: LfoWaveform) {
         self.waveform = waveform;
@@ -82,7 +120,7 @@ impl LFO {
         match self.waveform {
             LfoWaveform::Sine => {
                 // Standard sine wave
-                (self.phase * 2.0 * std::f32::consts::PI).sin()
+                (self.phase * 2.0 * PI).sin()
             },
             LfoWaveform::Triangle => {
                 // Triangle wave: linear ramp up and down
@@ -117,6 +155,13 @@ impl LFO {
     
     /// Process LFO for one sample with phase accumulation
     pub fn process(&mut self) -> f32 {
+        // Hold still until the onset delay has passed
+        if self.delay_remaining > 0 {
+            self.delay_remaining -= 1;
+            self.current_level = 0.0;
+            return 0.0;
+        }
+        
         // Generate current waveform output
         self.current_level = self.generate_waveform();
         
@@ -143,7 +188,7 @@ impl LFO {
     pub fn from_soundfont_generators(
         sample_rate: f32,
         freq_cents: i32,        // Frequency in cents
-        _delay_timecents: i32,   // Delay before LFO starts (not implemented yet)
+        delay_timecents: i32,   // Delay before LFO starts
         depth: f32,             // Modulation depth (0.0-1.0)
         waveform: LfoWaveform,  // Waveform type
     ) -> Self {
@@ -152,23 +197,38 @@ impl LFO {
         let base_frequency = 8.176; // Hz at 0 cents
         let frequency_hz = base_frequency * (2.0_f32).powf(freq_cents as f32 / 1200.0);
         
-        log(&format!("LFO from SoundFont: freq_cents={} -> {:.3}Hz, depth={:.3}", 
-                   freq_cents, frequency_hz, depth));
-        
-        // Create LFO with calculated frequency
-        // Note: delay_timecents will be implemented in future for delayed LFO start
-        LFO::new(sample_rate, frequency_hz, depth, waveform)
+        let mut lfo = LFO::new(sample_rate, frequency_hz, depth, waveform);
+        lfo.set_delay(timecents_to_seconds(delay_timecents));
+        lfo.trigger();
+        lfo
+    }
+    
+    /// Phase at which the waveform leaves zero rising (see module docs)
+    pub fn start_phase(waveform: LfoWaveform) -> f32 {
+        match waveform {
+            LfoWaveform::Triangle => 0.25,
+            LfoWaveform::Sine | LfoWaveform::Square => 0.0,
+        }
     }
     
     /// Reset LFO phase for note-on synchronization
     pub fn trigger(&mut self) {
-        self.phase = 0.0;
+        self.phase = Self::start_phase(self.waveform);
+        self.delay_remaining = self.delay_samples;
+        self.current_level = self.generate_waveform();
+    }
+    
+    /// Move the start point `offset` cycles (0.0-1.0) past the rising zero crossing
+    /// Call after trigger(); the onset delay is unaffected
+    pub fn offset_start_phase(&mut self, offset: f32) {
+        self.phase = (Self::start_phase(self.waveform) + offset.rem_euclid(1.0)).fract();
         self.current_level = self.generate_waveform();
     }
     
     /// Reset LFO to silent state
     pub fn reset(&mut self) {
-        self.phase = 0.0;
+        self.phase = Self::start_phase(self.waveform);
+        self.delay_remaining = self.delay_samples;
         self.current_level = 0.0;
     }
 }
\ No newline at end of file
@@ -3,4 +3,24 @@ pub mod voice_manager;
 pub mod envelope;
 pub mod mod_envelope; // Phase 12A - Modulation envelope for filter/pitch modulation
 pub mod lfo; // Phase 13A - Dual LFO system for tremolo/vibrato
-pub mod oscillator;
\ No newline at end of file
+pub mod oscillator;
+pub mod channel_state; // Per-channel bank/program/sound controller state
+pub mod fallback_bank; // Procedural GM bank used until a SoundFont is loaded
+pub mod tuning; // Host-supplied per-key tuning tables
+pub mod velocity_map; // Per-channel velocity range/curve before zone selection
+pub mod hardware_mode; // EMU8000 register/DAC limits vs modern float synthesis
+pub mod sample_preview; // Raw SoundFont sample playback for inspection
+pub mod performance; // Keyboard split/layer zones for live playing
+pub mod smoothing; // One-pole glides for controller-driven mixer parameters
+pub mod snapshot; // Restorable host mixer settings (channel trims, velocity maps)
+pub mod telemetry; // Per-channel voice counts and mixer controllers for host UIs
+pub mod watchdog; // Force-release of notes that lost their Note Off
+pub mod voice_stats; // Voice lifetimes, steals and polyphony over time
+pub mod device_profile; // Buffer/interpolation/polyphony/effects presets per device class
+pub mod preview_channel; // 17th channel for instrument previews alongside song playback
+pub mod crossfade; // Velocity layer crossfade curves and width
+pub mod zone_generators; // Per-preset generator values resolved at preset selection
+pub mod mod_wheel; // CC1 to vibrato depth range/curve per channel and from SoundFont modulators
+pub mod envelope_monitor; // Per-voice envelope stage/progress/level for visualizations
+pub mod preset_balance; // Per-preset level measurement and normalization trims
+pub mod trace; // Zero-cost audio path trace counters (audio-trace feature)
\ No newline at end of file
@@ -6,11 +6,99 @@
  * - Exponential curves matching EMU8000 behavior
  * - SoundFont 2.0 generator compliance (generators 26-32)
  * - Key scaling support for envelope timing
+ *
+ * Sustain (generator 29) is not an attenuation like the volume envelope's
+ * centibels: it is the drop from peak in 0.1% units, so 0 sustains at the
+ * peak and 1000 falls all the way to zero. Read as centibels, the usual
+ * values (a few hundred) would leave the envelope near silence and a filter
+ * sweep would never settle where the SoundFont asks.
  */
 
-use crate::synth::envelope::{EnvelopeState, timecents_to_seconds};
+use crate::synth::envelope::{EnvelopeState, timecents_to_seconds, MAX_SUSTAIN_ATTENUATION_CB};
 use crate::log;
 
+/// Attenuation handed to a centibel envelope for a fully-down sustain (1000 cB = silence)
+pub const MAX_SUSTAIN_CENTIBELS: i32 = MAX_SUSTAIN_ATTENUATION_CB;
+
+/// Sustain level (0.0-1.0) for a generator 29 value: the decrease from peak in 0.1% units
+pub fn sustain_fraction(sustain_decrease: i32) -> f32 {
+    1.0 - sustain_decrease.clamp(0, 1000) as f32 / 1000.0
+}
+
+/// The same level as centibels of attenuation, for envelopes that take a centibel sustain
+pub fn sustain_centibels(sustain_decrease: i32) -> i32 {
+    let level = sustain_fraction(sustain_decrease);
+    if level <= 0.0 {
+        return MAX_SUSTAIN_CENTIBELS;
+    }
+    ((-200.0 * level.log10()).round() as i32).min(MAX_SUSTAIN_CENTIBELS)
+}
+
+/// Classic filter-envelope shapes: the modulation envelope opens the filter
+/// by `depth_cents` at its peak and settles at the sustain level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FilterEnvelopeShape {
+    pub attack_timecents: i32,
+    pub hold_timecents: i32,
+    pub decay_timecents: i32,
+    pub sustain_decrease: i32,  // Generator 29 units (0.1% below peak)
+    pub release_timecents: i32,
+    pub depth_cents: f32,       // Cutoff shift at the envelope's peak (modEnvToFilterFc)
+}
+
+impl FilterEnvelopeShape {
+    /// Instant open, 150 ms fall to the base cutoff: plucked bass and clavinet
+    pub const PLUCK: Self = Self {
+        attack_timecents: -12000,
+        hold_timecents: -12000,
+        decay_timecents: -3284,
+        sustain_decrease: 1000,
+        release_timecents: -3600,
+        depth_cents: 4800.0,
+    };
+
+    /// 80 ms rise, 400 ms settle to 60%: brass swell
+    pub const BRASS: Self = Self {
+        attack_timecents: -4370,
+        hold_timecents: -12000,
+        decay_timecents: -1586,
+        sustain_decrease: 400,
+        release_timecents: -2400,
+        depth_cents: 2400.0,
+    };
+
+    /// Half-second opening, settling to 70% over two seconds: pad sweep
+    pub const SWEEP: Self = Self {
+        attack_timecents: -1200,
+        hold_timecents: -12000,
+        decay_timecents: 1200,
+        sustain_decrease: 300,
+        release_timecents: 0,
+        depth_cents: 3600.0,
+    };
+
+    /// Shape by name ("pluck", "brass", "sweep")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "pluck" => Some(Self::PLUCK),
+            "brass" => Some(Self::BRASS),
+            "sweep" => Some(Self::SWEEP),
+            _ => None,
+        }
+    }
+
+    /// The shape as a modulation envelope
+    pub fn envelope(&self, sample_rate: f32) -> ModulationEnvelope {
+        ModulationEnvelope::new(sample_rate, self.attack_timecents, self.hold_timecents, self.decay_timecents,
+                                self.sustain_decrease, self.release_timecents, 0, 0)
+    }
+
+    /// Cutoff shift in cents for an envelope level
+    pub fn cutoff_cents(&self, level: f32) -> f32 {
+        level * self.depth_cents
+    }
+}
+
 /// 6-stage modulation envelope for filter/pitch modulation
 #[derive(Debug, Clone)]
 pub struct ModulationEnvelope {
@@ -34,7 +122,7 @@ pub struct ModulationEnvelope {
     pub hold_timecents: i32,
     /// Decay time in timecents
     pub decay_timecents: i32,
-    /// Sustain level (0.1% units, 0-1000)
+    /// Sustain: decrease from peak in 0.1% units (0 = peak, 1000 = zero)
     pub sustain_level: i32,
     /// Release time in timecents
     pub release_timecents: i32,
@@ -55,7 +143,7 @@ impl ModulationEnvelope {
         attack_timecents: i32,    // Generator 26: attackModEnv
         hold_timecents: i32,      // Generator 27: holdModEnv
         decay_timecents: i32,     // Generator 28: decayModEnv
-        sustain_level: i32,       // Generator 29: sustainModEnv (0.1% units)
+        sustain_level: i32,       // Generator 29: sustainModEnv (0.1% decrease)
         release_timecents: i32,   // Generator 30: releaseModEnv
         keynum_to_hold: i32,      // Generator 31: keynumToModEnvHold
         keynum_to_decay: i32,     // Generator 32: keynumToModEnvDecay
@@ -116,7 +204,7 @@ impl ModulationEnvelope {
                 if self.samples_remaining > 0 {
                     // Exponential decay curve (fast start, slow end)
                     let progress = self.samples_remaining as f32 / self.phase_samples as f32;
-                    let sustain_linear = (self.sustain_level as f32 / 1000.0).clamp(0.0, 1.0);
+                    let sustain_linear = sustain_fraction(self.sustain_level);
                     self.current_level = sustain_linear + (1.0 - sustain_linear) * progress * progress;
                     self.samples_remaining -= 1;
                     
@@ -125,14 +213,14 @@ impl ModulationEnvelope {
                         self.current_level = sustain_linear;
                     }
                 } else {
-                    let sustain_linear = (self.sustain_level as f32 / 1000.0).clamp(0.0, 1.0);
+                    let sustain_linear = sustain_fraction(self.sustain_level);
                     self.current_level = sustain_linear;
                     self.state = EnvelopeState::Sustain;
                 }
                 self.current_level
             },
             EnvelopeState::Sustain => {
-                let sustain_linear = (self.sustain_level as f32 / 1000.0).clamp(0.0, 1.0);
+                let sustain_linear = sustain_fraction(self.sustain_level);
                 self.current_level = sustain_linear;
                 sustain_linear
             },
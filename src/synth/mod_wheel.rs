@@ -0,0 +1,89 @@
+/**
+ * AWE Player - Mod Wheel Vibrato
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * How far the mod wheel (CC1) opens the vibrato. The voice used to map it
+ * onto a fixed 0.02-0.15 LFO2 depth; here the range and the curve are
+ * settings. Depth is a fraction of the vibrato full scale (LFO2 pitch swing
+ * of ±2 semitones, as for host "vibrato" routes), so the old mapping is
+ * 4-30 cents.
+ *
+ * A channel gets wheel vibrato from the host's setting for it, else from
+ * a SoundFont modulator CC1 -> vibLfoToPitch in the preset (SF2 2.01
+ * section 8.2 source enumeration, amount in cents), else not at all -
+ * the EMU8000 leaves vibLfoToPitch at 0 unless the bank asks for more.
+ */
+
+use crate::effects::modulation::{ModulationDestination, LINEAR_CURVE};
+use crate::soundfont::modulators::{CurveType, SourceCurve};
+use crate::soundfont::types::{GeneratorType, Modulator};
+
+/// Depth of the former fixed mapping with the wheel down
+pub const DEFAULT_MIN_VIBRATO_DEPTH: f32 = 0.02;
+/// Depth of the former fixed mapping with the wheel up
+pub const DEFAULT_MAX_VIBRATO_DEPTH: f32 = 0.15;
+
+/// SF2 source enumeration: CC flag plus controller 1
+const SOURCE_CC1: u16 = 0x0081;
+/// SF2 source enumeration bits below the direction/polarity/type fields
+const SOURCE_INDEX_MASK: u16 = 0x00FF;
+
+/// Mod wheel position to vibrato depth for one channel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModWheelVibrato {
+    pub min_depth: f32,     // Depth at CC1 = 0 (fraction of the vibrato full scale)
+    pub max_depth: f32,     // Depth at CC1 = 127
+    pub curve: SourceCurve, // Shape of the wheel's travel
+}
+
+impl ModWheelVibrato {
+    /// Depths are clamped to -1.0..=1.0 (negative inverts the swing); non-finite ones are 0
+    pub fn new(min_depth: f32, max_depth: f32, curve: SourceCurve) -> Self {
+        let depth = |value: f32| if value.is_finite() { value.clamp(-1.0, 1.0) } else { 0.0 };
+        Self { min_depth: depth(min_depth), max_depth: depth(max_depth), curve }
+    }
+
+    /// Depth with the wheel at `wheel` (0.0-1.0)
+    pub fn depth(&self, wheel: f32) -> f32 {
+        self.min_depth + self.curve.map(wheel) * (self.max_depth - self.min_depth)
+    }
+
+    /// Pitch swing in semitones at LFO2 peak for `wheel`
+    pub fn semitones(&self, wheel: f32) -> f32 {
+        self.depth(wheel) * ModulationDestination::Vibrato.full_scale()
+    }
+
+    /// Sum the CC1 -> vibLfoToPitch modulators among `modulators` (None if there are none)
+    /// The curve is the first matching modulator's source curve
+    pub fn from_modulators(modulators: &[Modulator]) -> Option<Self> {
+        let mut matching = modulators.iter().filter(|modulator| {
+            modulator.source_enum & SOURCE_INDEX_MASK == SOURCE_CC1
+                && modulator.dest_enum == GeneratorType::VibLfoToPitch
+        });
+        let first = matching.next()?;
+        let cents = first.amount as f32 + matching.map(|modulator| modulator.amount as f32).sum::<f32>();
+        let full_scale_cents = ModulationDestination::Vibrato.full_scale() * 100.0;
+        Some(Self::new(0.0, cents / full_scale_cents, source_curve(first.source_enum)))
+    }
+}
+
+impl Default for ModWheelVibrato {
+    fn default() -> Self {
+        Self::new(DEFAULT_MIN_VIBRATO_DEPTH, DEFAULT_MAX_VIBRATO_DEPTH, LINEAR_CURVE)
+    }
+}
+
+/// Curve fields of an SF2 source enumeration (type bits 10-15, polarity bit 9, direction bit 8)
+fn source_curve(source_enum: u16) -> SourceCurve {
+    let curve = match source_enum >> 10 {
+        1 => CurveType::Concave,
+        2 => CurveType::Convex,
+        3 => CurveType::Switch,
+        _ => CurveType::Linear,
+    };
+    SourceCurve {
+        curve,
+        negative: source_enum & 0x0100 != 0,
+        bipolar: source_enum & 0x0200 != 0,
+    }
+}
@@ -15,12 +15,49 @@
 // Multi-Zone Sample Mixing → Pitch Modulation → Filter → Volume Envelope → 
 // Tremolo → Pan → Effects Sends → Final Output
 
-use crate::synth::envelope::{DAHDSREnvelope, EnvelopeState};
+use crate::synth::envelope::{timecents_to_seconds, DAHDSREnvelope, EnvelopeState};
 use crate::synth::lfo::{LFO, LfoWaveform};
+use crate::synth::mod_wheel::ModWheelVibrato;
 use crate::effects::filter::LowPassFilter;
-use crate::effects::modulation::{ModulationRouter, ModulationSource, ModulationDestination};
+use crate::effects::modulation::{ModulationRoute, ModulationRouter, ModulationSource, ModulationDestination};
 use crate::soundfont::types::{SoundFont, SoundFontPreset};
+use crate::soundfont::modulators::{velocity_attenuation_cb, velocity_filter_cents};
+use crate::soundfont::sample_rates::corrected_rate;
 use crate::error::AweError;
+use std::sync::{Arc, OnceLock};
+use super::channel_state::SoundControllers;
+use super::hardware_mode;
+use super::device_profile::InterpolationQuality;
+use super::crossfade::VelocityCrossfade;
+use super::zone_generators::{PresetGenerators, ZoneGenerators};
+use super::mod_envelope::{self, FilterEnvelopeShape};
+use super::trace::{self, TraceEvent};
+
+/// Zones a voice can hold without reallocating (presets rarely layer more than a few)
+/// Extra matching zones are not played, so note-on never grows the zone pool
+pub const MAX_ACTIVE_ZONES: usize = 16;
+
+/// Samples a warm-up note renders before the voice is returned to idle (one render quantum)
+const WARM_UP_SAMPLES: usize = 128;
+
+/// Semitones of cutoff shift per unit of filter modulation (the mod envelope's full swing)
+const FILTER_MODULATION_RANGE: f32 = 4.0;
+
+/// Send "breathing": LFO1 at full swing moves the reverb send up and the chorus send
+/// down by this share of their base levels; the factors are refreshed once per block
+const SEND_MODULATION_DEPTH: f32 = 0.05;
+const SEND_MODULATION_BLOCK: u32 = 32;
+
+/// LFO rates, depths and filter resonance until generators 22, 24 and 9 are read; fixed
+/// per note, since velocity only reaches attenuation and cutoff (the SF2 default modulators)
+const MOD_LFO_FREQUENCY_HZ: f32 = 2.5;
+const MOD_LFO_DEPTH: f32 = 0.125;
+const VIB_LFO_DEPTH: f32 = 0.06;
+const FILTER_RESONANCE: f32 = 0.55;
+
+/// Fallback test tone: 2 seconds of A4, looped over the middle half
+const TEST_TONE_SAMPLE_RATE: f32 = 44100.0;
+const TEST_TONE_SECONDS: f32 = 2.0;
 
 /// Complete EMU8000-authentic multi-zone sample voice with all effects
 #[derive(Debug, Clone)]
@@ -30,10 +67,15 @@ pub struct MultiZoneSampleVoice {
     state: VoiceState,
     note: u8,                    // MIDI note number (0-127)
     velocity: u8,                // MIDI velocity (0-127)
+    velocity_fine: f32,          // Normalized velocity (0.0-1.0), 14-bit resolution with CC88
     channel: u8,                 // MIDI channel (0-15)
+    pedal_held: bool,            // Note off arrived while the sustain pedal was down
     
     // ===== Multi-Zone Sample Management =====
     zones: Vec<ActiveZone>,      // Active zones for this note/velocity
+    playback_direction: PlaybackDirection, // Direction applied to zones at note start
+    interpolation: InterpolationQuality,
+    velocity_crossfade: VelocityCrossfade, // Fade shape at velocity range edges
     
     // ===== Envelopes (6-stage DAHDSR) =====
     volume_envelope: DAHDSREnvelope,
@@ -42,23 +84,41 @@ pub struct MultiZoneSampleVoice {
     // ===== Dual LFO System =====
     lfo1: LFO,                   // Modulation LFO (tremolo, filter)
     lfo2: LFO,                   // Vibrato LFO (pitch only)
+    vibrato_rate_scale: f32,     // CC76 multiplier on the vibrato rate
+    vibrato_depth_scale: f32,    // CC77 multiplier on the vibrato depth
+    mod_wheel_vibrato: Option<ModWheelVibrato>, // CC1 to vibrato depth (None: routes only)
     
     // ===== Filter =====
     filter: LowPassFilter,       // 2-pole resonant filter (100Hz-8kHz)
+    brightness_scale: f32,       // Cutoff multiplier from CC74 (1.0 = neutral)
+    filter_envelope: Option<FilterEnvelopeShape>, // Mod envelope drives the cutoff only, with this shape
     
     // ===== Modulation Routing =====
     modulation_router: ModulationRouter,
     
     // ===== Effects Sends =====
-    reverb_send: f32,            // 0.0-1.0 send level
-    chorus_send: f32,            // 0.0-1.0 send level
+    reverb_send: f32,            // 0.0-1.0 base send level (note-on or host)
+    chorus_send: f32,            // 0.0-1.0 base send level (note-on or host)
+    send_modulation: (f32, f32), // Reverb/chorus factors on the base levels for this block
+    send_modulation_countdown: u32, // Samples until the factors are refreshed
     
     // ===== Real-time Parameters =====
     pitch_bend: f32,             // -2.0 to +2.0 semitones
+    key_tuning: f32,             // Per-key tuning table offset in semitones
+    master_tuning: f32,          // A4 reference offset in semitones (kept across notes)
+    level_trim: f32,             // Linear gain of the preset balance trim
+    hardware_mode: bool,         // EMU8000 register limits (see hardware_mode.rs)
+    enhancements: bool,          // Non-EMU8000 heuristics (bend-dependent vibrato rate)
     base_pitch: f32,             // Calculated from note + tuning
     current_pitch: f32,          // After all modulation
     pan: f32,                    // -1.0 (left) to 1.0 (right)
     
+    // ===== Click Suppression =====
+    last_output: (f32, f32),     // Most recent stereo output
+    declick_offset: (f32, f32),  // Level of a stolen note, ramped to zero under the new one
+    declick_step: (f32, f32),
+    declick_remaining: u32,      // Samples left in the ramp
+    
     // ===== Performance Tracking =====
     samples_processed: u64,
     sample_rate: f32,
@@ -73,8 +133,8 @@ struct ActiveZone {
     zone_id: usize,              // Zone identifier
     sample_id: usize,            // Sample identifier
     
-    // Sample data reference (will be properly referenced later)
-    sample_data: Vec<i16>,       // Sample PCM data
+    // Sample data (shared with the SoundFont - starting a note copies nothing)
+    sample_data: Arc<[i16]>,     // Sample PCM data
     sample_rate: f32,            // Original sample rate
     
     // Playback state
@@ -83,15 +143,51 @@ struct ActiveZone {
     loop_start: Option<usize>,   // Loop start position
     loop_end: Option<usize>,     // Loop end position
     loop_active: bool,           // Currently in loop
+    direction: PlaybackDirection,
+    backwards: bool,             // Currently moving towards the sample start
     
     // Zone mixing
     zone_amplitude: f32,         // Velocity/key crossfade amount
+    zone_gain: f32,              // Initial attenuation and velocity, linear (not normalized away)
     is_active: bool,
     
     // Zone parameters from SoundFont
     key_range: (u8, u8),         // Min/max key range
     velocity_range: (u8, u8),    // Min/max velocity range
     root_key: u8,                // Original pitch of sample
+    generators: ZoneGenerators,  // Resolved for this (preset zone, instrument zone) pair
+}
+
+/// Sample playback direction (SFZ `direction` opcode, ping-pong loops)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlaybackDirection {
+    #[default]
+    Forward,
+    /// Start at the sample end; loops run backwards
+    Reverse,
+    /// Forward until loop end, then bounce between loop points
+    PingPong,
+}
+
+impl PlaybackDirection {
+    /// Decode NRPN data entry / API value (0 = forward, 1 = reverse, 2 = ping-pong)
+    pub fn from_value(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(PlaybackDirection::Forward),
+            1 => Some(PlaybackDirection::Reverse),
+            2 => Some(PlaybackDirection::PingPong),
+            _ => None,
+        }
+    }
+    
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "forward" => Some(PlaybackDirection::Forward),
+            "reverse" => Some(PlaybackDirection::Reverse),
+            "pingpong" | "ping_pong" | "ping-pong" => Some(PlaybackDirection::PingPong),
+            _ => None,
+        }
+    }
 }
 
 /// Voice lifecycle state
@@ -124,7 +220,7 @@ impl MultiZoneSampleVoice {
             -7200,   // attack: 16ms
             -12000,  // hold: 1ms
             -4800,   // decay: 62ms
-            500,     // sustain: 50%
+            mod_envelope::sustain_centibels(500), // sustain: 50%
             -3000,   // release: 176ms
         );
         
@@ -139,20 +235,41 @@ impl MultiZoneSampleVoice {
             state: VoiceState::Idle,
             note: 0,
             velocity: 0,
+            velocity_fine: 0.0,
             channel: 0,
-            zones: Vec::with_capacity(4), // Pre-allocate for typical 4 zones
+            pedal_held: false,
+            zones: Vec::with_capacity(MAX_ACTIVE_ZONES), // Pre-allocated so note-on never grows it
+            playback_direction: PlaybackDirection::Forward,
+            interpolation: InterpolationQuality::Linear,
+            velocity_crossfade: VelocityCrossfade::default(),
             volume_envelope,
             modulation_envelope,
             lfo1,
             lfo2,
+            vibrato_rate_scale: 1.0,
+            vibrato_depth_scale: 1.0,
+            mod_wheel_vibrato: None,
             filter,
+            brightness_scale: 1.0,
+            filter_envelope: None,
             modulation_router,
             reverb_send: 0.0,
             chorus_send: 0.0,
+            send_modulation: (1.0, 1.0),
+            send_modulation_countdown: 0,
             pitch_bend: 0.0,
+            key_tuning: 0.0,
+            master_tuning: 0.0,
+            level_trim: 1.0,
+            hardware_mode: false,
+            enhancements: true,
             base_pitch: 0.0,
             current_pitch: 0.0,
             pan: 0.0,
+            last_output: (0.0, 0.0),
+            declick_offset: (0.0, 0.0),
+            declick_step: (0.0, 0.0),
+            declick_remaining: 0,
             samples_processed: 0,
             sample_rate,
             mix_buffer: vec![0.0; 128], // Pre-allocate mix buffer
@@ -168,18 +285,74 @@ impl MultiZoneSampleVoice {
         soundfont: &SoundFont,
         preset: &SoundFontPreset,
     ) -> Result<(), AweError> {
-        // Debug logging removed
-        
+        let generators = PresetGenerators::resolve(preset, soundfont);
+        self.begin_note(note, velocity, velocity as f32 / 127.0, channel, soundfont, preset, &generators)
+    }
+    
+    /// Start playing a note with 14-bit velocity (MSB = Note On velocity, LSB = CC88 prefix)
+    /// Zone selection uses the 7-bit MSB; envelope/attenuation use the full resolution
+    pub fn start_note_high_res(
+        &mut self,
+        note: u8,
+        velocity_14bit: u16,
+        channel: u8,
+        soundfont: &SoundFont,
+        preset: &SoundFontPreset,
+    ) -> Result<(), AweError> {
+        let velocity_14bit = velocity_14bit.min(0x3FFF);
+        let velocity = (velocity_14bit >> 7) as u8;
+        // LSB subdivides each 7-bit step, so LSB 0 matches plain 7-bit velocity exactly
+        let velocity_fine = velocity_14bit as f32 / (128.0 * 127.0);
+        let generators = PresetGenerators::resolve(preset, soundfont);
+        self.begin_note(note, velocity, velocity_fine, channel, soundfont, preset, &generators)
+    }
+    
+    /// Start a note with `preset`'s generators already resolved (see VoiceManager's cache)
+    /// `velocity_14bit` as for start_note_high_res; plain 7-bit velocity is `velocity << 7`
+    pub fn start_note_with_generators(
+        &mut self,
+        note: u8,
+        velocity_14bit: u16,
+        channel: u8,
+        soundfont: &SoundFont,
+        preset: &SoundFontPreset,
+        generators: &PresetGenerators,
+    ) -> Result<(), AweError> {
+        let velocity_14bit = velocity_14bit.min(0x3FFF);
+        let velocity = (velocity_14bit >> 7) as u8;
+        let velocity_fine = velocity_14bit as f32 / (128.0 * 127.0);
+        self.begin_note(note, velocity, velocity_fine, channel, soundfont, preset, generators)
+    }
+    
+    #[allow(clippy::too_many_arguments)]
+    fn begin_note(
+        &mut self,
+        note: u8,
+        velocity: u8,
+        velocity_fine: f32,
+        channel: u8,
+        soundfont: &SoundFont,
+        preset: &SoundFontPreset,
+        generators: &PresetGenerators,
+    ) -> Result<(), AweError> {
         // Reset voice state
         self.note = note;
         self.velocity = velocity;
+        self.velocity_fine = velocity_fine.clamp(0.0, 1.0);
         self.channel = channel;
         self.state = VoiceState::Starting;
         self.samples_processed = 0;
+        self.brightness_scale = 1.0;
+        self.vibrato_rate_scale = 1.0;
+        self.vibrato_depth_scale = 1.0;
+        self.key_tuning = 0.0;
+        self.level_trim = 1.0;
+        self.modulation_router.set_source_value(ModulationSource::Velocity, self.velocity_fine);
+        self.modulation_router.set_source_value(ModulationSource::KeyNumber, note as f32 / 127.0);
         
         // Select and activate zones for this note/velocity
         // Zone selection debug removed
-        match self.select_zones(note, velocity, soundfont, preset) {
+        match self.select_zones(note, velocity, soundfont, preset, generators) {
             Ok(_) => {
                 // Zone selection success debug removed
             }
@@ -190,7 +363,7 @@ impl MultiZoneSampleVoice {
         }
         
         // Apply SoundFont generators first (this may reconfigure envelopes)
-        self.apply_generators(preset, soundfont)?;
+        self.apply_generators(preset)?;
         
         // Trigger envelopes (after generators are applied)
         self.volume_envelope.trigger();
@@ -199,6 +372,8 @@ impl MultiZoneSampleVoice {
         // Reset LFOs
         self.lfo1.reset();
         self.lfo2.reset();
+        self.send_modulation = (1.0, 1.0);
+        self.send_modulation_countdown = 0;
         
         // Calculate base pitch from note
         self.base_pitch = note as f32;
@@ -206,12 +381,48 @@ impl MultiZoneSampleVoice {
         
         // Activate voice
         self.state = VoiceState::Active;
+        self.pedal_held = false;
         
         Ok(())
     }
     
+    /// Run an inaudible note of `preset` through the voice and return it to idle, so the
+    /// first real note does not pay for code and memory the voice has never touched
+    /// (browsers commit WASM pages on first touch and optimize functions once they run)
+    /// Does nothing on a sounding voice
+    pub fn warm_up(&mut self, soundfont: &SoundFont, preset: &SoundFontPreset, generators: &PresetGenerators) {
+        if self.is_active() {
+            return;
+        }
+        self.mix_buffer.fill(0.0);
+        self.zones.reserve(MAX_ACTIVE_ZONES);
+        if self.start_note_with_generators(60, 1 << 7, 0, soundfont, preset, generators).is_ok() {
+            for _ in 0..WARM_UP_SAMPLES {
+                std::hint::black_box(self.process());
+            }
+        }
+        self.reset_to_idle();
+    }
+    
+    /// Silence the voice at once, keeping its zone pool and buffers allocated
+    fn reset_to_idle(&mut self) {
+        self.state = VoiceState::Idle;
+        self.zones.clear();
+        self.volume_envelope.state = EnvelopeState::Off;
+        self.volume_envelope.current_level = 0.0;
+        self.last_output = (0.0, 0.0);
+        self.declick_remaining = 0;
+        self.samples_processed = 0;
+    }
+    
+    /// Zones the current note is playing
+    pub fn get_zone_count(&self) -> usize {
+        self.zones.len()
+    }
+    
     /// Stop playing (trigger release)
     pub fn stop_note(&mut self) {
+        self.pedal_held = false;
         if self.state == VoiceState::Active {
             self.state = VoiceState::Releasing;
             self.volume_envelope.release();
@@ -219,6 +430,18 @@ impl MultiZoneSampleVoice {
         }
     }
     
+    /// Note off under a held sustain pedal: keep sounding until the pedal lifts
+    pub fn hold_for_pedal(&mut self) {
+        if self.state == VoiceState::Active {
+            self.pedal_held = true;
+        }
+    }
+    
+    /// Released by its key but kept sounding by the sustain pedal
+    pub fn is_pedal_held(&self) -> bool {
+        self.pedal_held && self.state == VoiceState::Active
+    }
+    
     /// Process one sample of audio
     pub fn process(&mut self) -> (f32, f32) {
         if self.state == VoiceState::Idle {
@@ -228,9 +451,9 @@ impl MultiZoneSampleVoice {
         // Generate mixed sample from all active zones
         let mut sample = self.generate_mixed_sample();
         
-        // Log first few samples for debugging
+        // Trace first few samples for debugging
         if self.samples_processed < 5 {
-            crate::log(&format!("Voice process #{}: raw sample = {:.6}", self.samples_processed, sample));
+            trace::record(TraceEvent::VoiceStartSample, sample);
         }
         
         // Apply pitch modulation
@@ -248,28 +471,43 @@ impl MultiZoneSampleVoice {
         // Check if voice should stop
         if self.volume_envelope.state == EnvelopeState::Off {
             self.state = VoiceState::Idle;
+            self.last_output = (0.0, 0.0);
+            self.declick_remaining = 0;
             return (0.0, 0.0); // Voice is fully inactive
         }
         
         // Apply tremolo (LFO1 to amplitude)
         let tremolo = self.calculate_tremolo();
         sample *= tremolo;
-        
-        // Apply subtle effects send modulation (EMU8000 "breathing" effect)
-        let lfo1_level = self.lfo1.get_level();
-        if lfo1_level.abs() > 0.01 { // Only if LFO1 is active
-            // Note: This is a copy of effects sends for modulation calculation
-            // The actual sends remain unchanged until next note trigger
-            // This creates the subtle "breathing" effect without permanent changes
+        // Host routes to amplitude (e.g. aftertouch swell)
+        let routed_gain = 1.0 + self.modulation_router.route_amount(ModulationDestination::Amplitude);
+        sample *= routed_gain.max(0.0) * self.level_trim;
+        
+        // Effects sends breathe with LFO1 (EMU8000 characteristic): the factors follow it per
+        // block and scale the base levels on their way to the buses, which stay as set
+        if self.send_modulation_countdown == 0 {
+            let lfo1 = if self.lfo1.is_active() { self.lfo1.current_level } else { 0.0 };
+            self.modulate_effects_sends(lfo1);
+            self.send_modulation_countdown = SEND_MODULATION_BLOCK;
         }
+        self.send_modulation_countdown -= 1;
         
         // Calculate stereo output with optimized 32-bit precision panning
         // EMU8000 used simple linear panning, but we can do better with constant-power
         let pan_normalized = (self.pan + 1.0) * 0.5; // Convert -1.0..1.0 to 0.0..1.0
         let left_gain = ((1.0 - pan_normalized) * std::f32::consts::FRAC_PI_2).cos();
         let right_gain = (pan_normalized * std::f32::consts::FRAC_PI_2).cos();
-        let left = sample * left_gain;
-        let right = sample * right_gain;
+        let mut left = sample * left_gain;
+        let mut right = sample * right_gain;
+        
+        if self.declick_remaining > 0 {
+            left += self.declick_offset.0;
+            right += self.declick_offset.1;
+            self.declick_offset.0 -= self.declick_step.0;
+            self.declick_offset.1 -= self.declick_step.1;
+            self.declick_remaining -= 1;
+        }
+        self.last_output = (left, right);
         
         self.samples_processed += 1;
         
@@ -288,8 +526,20 @@ impl MultiZoneSampleVoice {
     /// Apply real-time MIDI control
     pub fn set_pitch_bend(&mut self, bend: f32) {
         self.pitch_bend = bend.clamp(-2.0, 2.0);
-        // Apply pitch bend effect to LFO2 vibrato speed (subtle EMU8000 behavior)
-        self.apply_pitch_bend_to_lfo(self.pitch_bend);
+        // Large bends speed up the vibrato (an enhancement, not spec'd EMU8000 behavior)
+        if self.enhancements {
+            self.apply_pitch_bend_to_lfo(self.pitch_bend);
+        }
+    }
+    
+    /// Install the host modulation routes that apply to this voice's channel
+    pub fn set_modulation_routes(&mut self, global: &[ModulationRoute], channel: &[ModulationRoute]) {
+        self.modulation_router.set_routes(global, channel);
+    }
+    
+    /// Update a controller source (mod wheel, aftertouch) for the host routes, 0.0-1.0
+    pub fn set_modulation_source(&mut self, source: ModulationSource, value: f32) {
+        self.modulation_router.set_source_value(source, value);
     }
     
     pub fn set_modulation(&mut self, amount: f32) {
@@ -318,15 +568,124 @@ impl MultiZoneSampleVoice {
         self.velocity
     }
     
+    /// Seconds since the note started
+    pub fn get_age_seconds(&self) -> f32 {
+        self.samples_processed as f32 / self.sample_rate
+    }
+    
+    /// Set playback direction for subsequent notes
+    pub fn set_playback_direction(&mut self, direction: PlaybackDirection) {
+        self.playback_direction = direction;
+    }
+    
+    pub fn get_playback_direction(&self) -> PlaybackDirection {
+        self.playback_direction
+    }
+    
+    /// Sample interpolation (takes effect on the next sample, sounding notes included)
+    pub fn set_interpolation(&mut self, interpolation: InterpolationQuality) {
+        self.interpolation = interpolation;
+    }
+    
+    pub fn get_interpolation(&self) -> InterpolationQuality {
+        self.interpolation
+    }
+    
+    /// Velocity layer crossfade (applies from the next note)
+    pub fn set_velocity_crossfade(&mut self, crossfade: VelocityCrossfade) {
+        self.velocity_crossfade = crossfade;
+    }
+    
+    /// Apply EMU8000 register limits (pitch ceiling, cutoff and send resolution)
+    pub fn set_hardware_mode(&mut self, enabled: bool) {
+        self.hardware_mode = enabled;
+    }
+    
+    /// Allow heuristics the EMU8000 documentation doesn't describe; turning them off
+    /// puts a bent note's vibrato back at its normal rate
+    pub fn set_enhancements(&mut self, enabled: bool) {
+        self.enhancements = enabled;
+        if !enabled {
+            self.lfo2.set_frequency(self.vibrato_base_frequency() * self.vibrato_rate_scale);
+        }
+    }
+    
+    /// Offset this note's pitch by a tuning table entry (cents)
+    pub fn set_key_tuning(&mut self, cents: f32) {
+        self.key_tuning = cents / 100.0;
+    }
+    
+    /// Raise or lower this note's level (dB, see preset_balance.rs)
+    pub fn set_level_trim(&mut self, db: f32) {
+        self.level_trim = 10.0_f32.powf(db / 20.0);
+    }
+    
+    /// Offset every note this voice plays, the current one included (cents)
+    pub fn set_master_tuning(&mut self, cents: f32) {
+        self.master_tuning = cents / 100.0;
+    }
+    
+    /// Start LFO1 and LFO2 part-way through their cycles (fractions of a cycle, see LfoPhaseMode)
+    pub fn offset_lfo_phases(&mut self, lfo1_offset: f32, lfo2_offset: f32) {
+        self.lfo1.offset_start_phase(lfo1_offset);
+        self.lfo2.offset_start_phase(lfo2_offset);
+    }
+    
+    /// Skip the first `offset` sample frames of every zone (humanized start)
+    /// Forward zones never skip past their loop end; reverse zones stop at the sample start
+    pub fn apply_start_offset(&mut self, offset: u32) {
+        for zone in self.zones.iter_mut() {
+            if zone.backwards {
+                zone.position = (zone.position - offset as f64).max(0.0);
+            } else {
+                let limit = zone.loop_end.unwrap_or(zone.sample_data.len()).saturating_sub(2);
+                zone.position = (zone.position + offset as f64).min(limit as f64);
+            }
+        }
+    }
+    
+    /// Change playback direction of one active zone (e.g. an SFZ region with its own `direction`)
+    /// Returns false if the zone index is out of range
+    pub fn set_zone_playback_direction(&mut self, zone_index: usize, direction: PlaybackDirection) -> bool {
+        let Some(zone) = self.zones.get_mut(zone_index) else {
+            return false;
+        };
+        zone.direction = direction;
+        match direction {
+            PlaybackDirection::Forward => zone.backwards = false,
+            PlaybackDirection::Reverse => zone.backwards = true,
+            PlaybackDirection::PingPong => {} // Keep current heading, bounce at loop points
+        }
+        true
+    }
+    
+    /// Normalized velocity (0.0-1.0) including any CC88 high-resolution LSB
+    pub fn get_velocity_fine(&self) -> f32 {
+        self.velocity_fine
+    }
+    
     pub fn get_channel(&self) -> u8 {
         self.channel
     }
     
     /// Voice stealing support
+    /// The next note starts right away, so the cut-off output ramps out underneath it
     pub fn prepare_for_steal(&mut self) {
         self.state = VoiceState::Stealing;
-        // Quick release for stealing
-        self.volume_envelope.quick_release();
+        self.volume_envelope.fast_release();
+        
+        let length = self.volume_envelope.min_release_samples.max(1);
+        self.declick_offset = self.last_output;
+        self.declick_step = (self.last_output.0 / length as f32, self.last_output.1 / length as f32);
+        self.declick_remaining = length;
+    }
+    
+    /// All Sound Off: fade out over the minimum release time
+    pub fn fade_out(&mut self) {
+        if self.state == VoiceState::Active {
+            self.state = VoiceState::Releasing;
+        }
+        self.force_quick_release();
     }
     
     pub fn get_steal_priority(&self) -> f32 {
@@ -365,6 +724,7 @@ impl MultiZoneSampleVoice {
         velocity: u8,
         soundfont: &SoundFont,
         preset: &SoundFontPreset,
+        generators: &PresetGenerators,
     ) -> Result<(), AweError> {
         self.zones.clear();
         
@@ -372,23 +732,6 @@ impl MultiZoneSampleVoice {
         
         // SoundFont data debug removed
         
-        // Debug: Check if SoundFont has any actual sample data
-        let total_sample_data: usize = soundfont.samples.iter()
-            .map(|s| s.sample_data.len())
-            .sum();
-        // SoundFont data check debug removed
-        
-        // Debug: Show first few samples if available
-        if !soundfont.samples.is_empty() && !soundfont.samples[0].sample_data.is_empty() {
-            let first_samples: Vec<i16> = soundfont.samples[0].sample_data.iter()
-                .take(8)
-                .cloned()
-                .collect();
-            // First sample data debug removed
-        } else {
-            // No sample data warning removed
-        }
-        
         // Find matching preset zones for this note/velocity
         for (zone_id, preset_zone) in preset.preset_zones.iter().enumerate() {
             // Check if this preset zone matches our note/velocity
@@ -407,7 +750,7 @@ impl MultiZoneSampleVoice {
             if let Some(instrument_id) = preset_zone.instrument_id {
                 if let Some(instrument) = soundfont.instruments.get(instrument_id as usize) {
                     // Find matching instrument zones
-                    for instrument_zone in &instrument.instrument_zones {
+                    for (instrument_zone_id, instrument_zone) in instrument.instrument_zones.iter().enumerate() {
                         let inst_key_match = instrument_zone.key_range.as_ref()
                             .map(|range| range.contains(note))
                             .unwrap_or(true);
@@ -418,6 +761,9 @@ impl MultiZoneSampleVoice {
                         if !inst_key_match || !inst_vel_match {
                             continue;
                         }
+                        if self.zones.len() == MAX_ACTIVE_ZONES {
+                            break; // Zone pool is full
+                        }
                         
                         // Get sample from instrument zone
                         if let Some(sample_id) = instrument_zone.sample_id {
@@ -429,27 +775,12 @@ impl MultiZoneSampleVoice {
                                     &instrument_zone.velocity_range
                                 );
                                 
-                                // Debug: Analyze sample data before creating zone
-                                let sample_data_info = if sample.sample_data.is_empty() {
-                                    "EMPTY SAMPLE DATA!".to_string()
-                                } else {
-                                    let first_few: Vec<i16> = sample.sample_data.iter().take(4).cloned().collect();
-                                    let non_zero_count = sample.sample_data.iter().filter(|&&s| s != 0).count();
-                                    let max_amplitude = sample.sample_data.iter().map(|&s| s.abs()).max().unwrap_or(0);
-                                    format!(
-                                        "first 4: {:?}, non-zero: {}/{}, max_amp: {}",
-                                        first_few, non_zero_count, sample.sample_data.len(), max_amplitude
-                                    )
-                                };
-                                
-                                // Sample analysis debug removed
-                                
                                 // Create active zone with real sample data
                                 let active_zone = ActiveZone {
                                     zone_id,
                                     sample_id: sample_id as usize,
-                                    sample_data: sample.sample_data.clone(), // FIXED: Use real sample data
-                                    sample_rate: sample.sample_rate as f32,
+                                    sample_data: Arc::clone(&sample.sample_data),
+                                    sample_rate: corrected_rate(sample.sample_rate) as f32,
                                     position: 0.0,
                                     playback_rate: 1.0, // Will be calculated based on pitch
                                     // Loop points: both must be non-zero for a valid loop
@@ -464,7 +795,10 @@ impl MultiZoneSampleVoice {
                                         None 
                                     },
                                     loop_active: false,
+                                    direction: self.playback_direction,
+                                    backwards: false,
                                     zone_amplitude,
+                                    zone_gain: 1.0,
                                     is_active: true,
                                     key_range: (
                                         instrument_zone.key_range.as_ref()
@@ -479,6 +813,7 @@ impl MultiZoneSampleVoice {
                                             .map(|r| r.high).unwrap_or(127)
                                     ),
                                     root_key: sample.original_pitch,
+                                    generators: generators.zone(zone_id, instrument_zone_id),
                                 };
                                 
                                 self.zones.push(active_zone);
@@ -493,58 +828,48 @@ impl MultiZoneSampleVoice {
         
         // If no zones were found, create a fallback test tone
         if self.zones.is_empty() {
-            crate::log(&format!("⚠️ No zones found for note {} velocity {}, creating fallback test tone", note, velocity));
-            self.create_fallback_test_tone(note, velocity);
-        } else {
-            crate::log(&format!("✅ {} zones activated for note {} velocity {}", self.zones.len(), note, velocity));
+            self.create_fallback_test_tone(velocity);
+        }
+        
+        // Reverse playback starts at the last interpolatable sample
+        for zone in self.zones.iter_mut() {
+            if zone.direction == PlaybackDirection::Reverse {
+                zone.position = zone.sample_data.len().saturating_sub(2) as f64;
+                zone.backwards = true;
+            }
         }
         
         Ok(())
     }
     
     /// Create a fallback sine wave test tone when no SoundFont zones are available
-    fn create_fallback_test_tone(&mut self, note: u8, velocity: u8) {
-        // Generate a short sine wave at the appropriate frequency
-        let sample_rate = 44100.0;
-        let frequency = 440.0 * 2.0_f32.powf((note as f32 - 69.0) / 12.0); // A4 = 440Hz
-        let duration = 2.0; // 2 seconds for longer test tone
-        let sample_count = (sample_rate * duration) as usize;
-        
-        let mut sample_data = Vec::with_capacity(sample_count);
-        let amplitude = (velocity as f32 / 127.0) * 0.8; // Higher amplitude for testing
-        
-        for i in 0..sample_count {
-            let t = i as f32 / sample_rate;
-            let sample = (2.0 * std::f32::consts::PI * frequency * t).sin() * amplitude;
-            sample_data.push((sample * 32767.0) as i16); // Convert to 16-bit
-        }
-        
-        // Verify the test tone has non-zero data
-        let non_zero_count = sample_data.iter().filter(|&&s| s != 0).count();
-        let max_amplitude = sample_data.iter().map(|&s| s.abs()).max().unwrap_or(0);
-        
-        // Test tone data debug removed
+    fn create_fallback_test_tone(&mut self, velocity: u8) {
+        // One shared A4 table, pitched to the note through root_key like any other sample
+        let sample_data = fallback_test_tone();
+        let sample_count = sample_data.len();
         
         let zone = ActiveZone {
             zone_id: 999, // Special ID for test tone
             sample_id: 999,
             sample_data,
-            sample_rate,
+            sample_rate: TEST_TONE_SAMPLE_RATE,
             position: 0.0,
             playback_rate: 1.0,
             loop_start: Some(sample_count / 4), // Loop after 25%
             loop_end: Some(sample_count * 3 / 4), // Loop before 75%
             loop_active: false,
-            zone_amplitude: 1.0,
+            direction: self.playback_direction,
+            backwards: false,
+            zone_amplitude: (velocity as f32 / 127.0) * 0.8, // Higher amplitude for testing
+            zone_gain: 1.0,
             is_active: true,
             key_range: (0, 127),
             velocity_range: (0, 127),
-            root_key: note,
+            root_key: 69,
+            generators: ZoneGenerators::default(),
         };
         
         self.zones.push(zone);
-        
-        // Fallback test tone debug removed
     }
     
     /// Calculate zone amplitude for velocity crossfading
@@ -571,28 +896,7 @@ impl MultiZoneSampleVoice {
     
     /// Calculate velocity-based weight for crossfading
     fn calculate_velocity_weight(&self, velocity: u8, range_low: u8, range_high: u8) -> f32 {
-        if velocity < range_low || velocity > range_high {
-            return 0.0;
-        }
-        
-        let range_size = range_high - range_low;
-        if range_size <= 4 {
-            return 1.0; // Small range, no crossfading
-        }
-        
-        let crossfade_size = (range_size / 4).max(1); // 25% crossfade zones
-        let velocity_pos = velocity - range_low;
-        
-        if velocity_pos < crossfade_size {
-            // Fade in at start
-            velocity_pos as f32 / crossfade_size as f32
-        } else if velocity_pos > range_size - crossfade_size {
-            // Fade out at end
-            (range_size - velocity_pos) as f32 / crossfade_size as f32
-        } else {
-            // Full weight in middle
-            1.0
-        }
+        self.velocity_crossfade.weight(velocity, range_low, range_high)
     }
     
     /// Generate mixed sample from all active zones
@@ -602,14 +906,15 @@ impl MultiZoneSampleVoice {
             return 0.0;
         }
         
-        // Log zones status once
+        // Trace zones status once
         if self.samples_processed == 0 {
-            crate::log(&format!("Mixing from {} zones", self.zones.len()));
+            trace::record(TraceEvent::ZoneMixStart, self.zones.len() as f32);
         }
         
         let mut output = 0.0;
         let mut total_weight = 0.0;
         let mut active_zones = 0;
+        let interpolation = self.interpolation;
         
         for (i, zone) in self.zones.iter_mut().enumerate() {
             if !zone.is_active {
@@ -619,31 +924,15 @@ impl MultiZoneSampleVoice {
             active_zones += 1;
             
             // Get interpolated sample at current position
-            let sample = Self::interpolate_sample_static(&zone);
+            let sample = Self::interpolate_sample_static(zone, interpolation);
             
             // Sample interpolation debug removed - was flooding log in audio processing loop
             
-            // Advance position
-            zone.position += zone.playback_rate;
-            
-            // Handle looping
-            if let Some(loop_end) = zone.loop_end {
-                if zone.position >= loop_end as f64 {
-                    if let Some(loop_start) = zone.loop_start {
-                        zone.position = loop_start as f64 + (zone.position - loop_end as f64);
-                        zone.loop_active = true;
-                    } else {
-                        zone.is_active = false;
-                        // Zone deactivation logging removed - was flooding log in audio processing loop
-                    }
-                }
-            } else if zone.position >= zone.sample_data.len() as f64 {
-                zone.is_active = false;
-                // Zone end logging removed - was flooding log in audio processing loop
-            }
+            // Advance position (handles looping and playback direction)
+            Self::advance_zone(zone);
             
-            // Mix with crossfade weight
-            output += sample * zone.zone_amplitude;
+            // Mix with crossfade weight; only the weights are normalized, the zone's own gain is kept
+            output += sample * zone.zone_amplitude * zone.zone_gain;
             total_weight += zone.zone_amplitude;
         }
         
@@ -661,8 +950,63 @@ impl MultiZoneSampleVoice {
         final_output
     }
     
-    /// 4-point interpolation for sample playback
-    fn interpolate_sample_static(zone: &ActiveZone) -> f32 {
+    /// Advance a zone's playback position by one output sample
+    fn advance_zone(zone: &mut ActiveZone) {
+        if zone.backwards {
+            zone.position -= zone.playback_rate;
+            let loop_points = match (zone.loop_start, zone.loop_end) {
+                (Some(start), Some(end)) if start < end => Some((start as f64, end as f64)),
+                _ => None,
+            };
+            match loop_points {
+                Some((loop_start, loop_end)) if zone.position < loop_start => {
+                    if zone.direction == PlaybackDirection::PingPong {
+                        // Bounce off loop start and head forward again
+                        zone.position = loop_start + (loop_start - zone.position);
+                        zone.backwards = false;
+                    } else {
+                        zone.position += loop_end - loop_start;
+                    }
+                    zone.loop_active = true;
+                }
+                None if zone.position < 0.0 => {
+                    zone.is_active = false;
+                }
+                _ => {}
+            }
+            return;
+        }
+        
+        zone.position += zone.playback_rate;
+        
+        // Handle looping
+        if let Some(loop_end) = zone.loop_end {
+            if zone.position >= loop_end as f64 {
+                match zone.loop_start {
+                    Some(loop_start) if zone.direction == PlaybackDirection::PingPong && loop_start < loop_end => {
+                        // Bounce off loop end and head back towards loop start
+                        zone.position = loop_end as f64 - (zone.position - loop_end as f64);
+                        zone.backwards = true;
+                        zone.loop_active = true;
+                    }
+                    Some(loop_start) => {
+                        zone.position = loop_start as f64 + (zone.position - loop_end as f64);
+                        zone.loop_active = true;
+                    }
+                    None => {
+                        zone.is_active = false;
+                        // Zone deactivation logging removed - was flooding log in audio processing loop
+                    }
+                }
+            }
+        } else if zone.position >= zone.sample_data.len() as f64 {
+            zone.is_active = false;
+            // Zone end logging removed - was flooding log in audio processing loop
+        }
+    }
+    
+    /// Linear or 4-point (Hermite) interpolation for sample playback
+    fn interpolate_sample_static(zone: &ActiveZone, interpolation: InterpolationQuality) -> f32 {
         let pos = zone.position;
         let idx = pos as usize;
         let fract = pos - idx as f64;
@@ -675,17 +1019,23 @@ impl MultiZoneSampleVoice {
             return 0.0;
         }
         
-        // Simple linear interpolation for now
-        // TODO: Implement proper 4-point interpolation
         let s0 = zone.sample_data[idx] as f32 / 32768.0;
-        let s1 = if idx + 1 < zone.sample_data.len() {
-            zone.sample_data[idx + 1] as f32 / 32768.0
-        } else {
-            0.0
+        let s1 = zone.sample_data[idx + 1] as f32 / 32768.0;
+        let fract = fract as f32;
+        
+        let interpolated = match interpolation {
+            InterpolationQuality::Linear => s0 + (s1 - s0) * fract,
+            InterpolationQuality::Cubic => {
+                // Outer points are clamped to the sample ends
+                let before = zone.sample_data[idx.saturating_sub(1)] as f32 / 32768.0;
+                let after = zone.sample_data[(idx + 2).min(zone.sample_data.len() - 1)] as f32 / 32768.0;
+                let c1 = 0.5 * (s1 - before);
+                let c2 = before - 2.5 * s0 + 2.0 * s1 - 0.5 * after;
+                let c3 = 0.5 * (after - before) + 1.5 * (s0 - s1);
+                ((c3 * fract + c2) * fract + c1) * fract + s0
+            }
         };
         
-        let interpolated = s0 + (s1 - s0) * fract as f32;
-        
         // Emergency fallback: generate sine wave if we're getting zeros from real sample data
         if interpolated.abs() < 0.0001 && zone.sample_data[idx] == 0 && !zone.sample_data.iter().any(|&s| s != 0) {
             // This sample appears to be all zeros - generate emergency sine wave
@@ -707,12 +1057,17 @@ impl MultiZoneSampleVoice {
         self.modulation_router.set_source_value(ModulationSource::Lfo2, lfo2_value);
         self.modulation_router.set_source_value(ModulationSource::ModulationEnvelope, mod_env_value);
         
-        // Get combined pitch modulation from router
-        let router_modulation = self.modulation_router.get_modulated_value(ModulationDestination::Pitch, 0.0);
+        // Get combined pitch modulation from router, plus host and mod wheel vibrato depth on the raw LFO2 wave
+        let wheel_vibrato = self.mod_wheel_vibrato.map_or(0.0, |vibrato| {
+            vibrato.semitones(self.modulation_router.source_values[ModulationSource::ModWheel as usize]) * self.vibrato_depth_scale
+        });
+        let router_modulation = self.modulation_router.route_amount(ModulationDestination::Pitch)
+            + self.lfo2.current_level * (self.modulation_router.route_amount(ModulationDestination::Vibrato) + wheel_vibrato);
         
         // Add direct modulation envelope contribution to pitch (EMU8000 behavior)
         // Modulation envelope affects both filter and pitch, but less pitch modulation
-        let direct_mod_env = mod_env_value * 0.2; // 20% of modulation envelope goes to pitch
+        // A filter envelope shape keeps the envelope off the pitch
+        let direct_mod_env = if self.filter_envelope.is_some() { 0.0 } else { mod_env_value * 0.2 }; // 20% of modulation envelope goes to pitch
         
         // Combine all pitch modulation sources
         let total_pitch_mod = router_modulation + direct_mod_env + self.pitch_bend;
@@ -727,12 +1082,19 @@ impl MultiZoneSampleVoice {
             // Convert semitones to playback rate ratio
             let pitch_ratio = 2.0_f32.powf(pitch_mod / 12.0);
             
-            // Calculate rate based on note difference from root key
-            let note_diff = self.note as i32 - zone.root_key as i32;
-            let note_ratio = 2.0_f32.powf(note_diff as f32 / 12.0);
+            // Calculate rate based on note difference from root key (plus the zone's, key and master tuning)
+            let zone_tuning = zone.generators.tuning_cents() as f32 / 100.0;
+            let note_diff = self.note as f32 + zone_tuning + self.key_tuning + self.master_tuning - zone.root_key as f32;
+            let note_ratio = 2.0_f32.powf(note_diff / 12.0);
             
             // Combine ratios
             zone.playback_rate = (pitch_ratio * note_ratio) as f64;
+            if !zone.playback_rate.is_finite() {
+                zone.playback_rate = 1.0; // NaN modulation input: hold pitch rather than lose the position
+            }
+            if self.hardware_mode {
+                zone.playback_rate = zone.playback_rate.min(hardware_mode::MAX_PITCH_RATIO);
+            }
         }
     }
     
@@ -745,11 +1107,15 @@ impl MultiZoneSampleVoice {
         self.modulation_router.set_source_value(ModulationSource::Lfo1, lfo1_value);
         self.modulation_router.set_source_value(ModulationSource::ModulationEnvelope, mod_env_value);
         
-        // Get combined filter modulation from router
-        let router_modulation = self.modulation_router.get_modulated_value(ModulationDestination::FilterCutoff, 0.0);
+        // Routed cutoff shift is in semitones; apply_filter scales its input by the modulation range
+        let router_modulation = self.modulation_router.route_amount(ModulationDestination::FilterCutoff) / FILTER_MODULATION_RANGE;
         
         // Add direct modulation envelope contribution (EMU8000 has both routed and direct modulation)
-        let direct_mod_env = mod_env_value * 0.8; // 80% of modulation envelope goes to filter
+        let direct_mod_env = match self.filter_envelope {
+            // Shape depth in cents on the unscaled envelope level
+            Some(shape) => shape.cutoff_cents(self.get_modulation_envelope_level()) / 100.0 / FILTER_MODULATION_RANGE,
+            None => mod_env_value * 0.8, // 80% of modulation envelope goes to filter
+        };
         
         // Combine all modulation sources
         router_modulation + direct_mod_env
@@ -758,17 +1124,19 @@ impl MultiZoneSampleVoice {
     /// Apply filter with modulation (EMU8000-authentic behavior)
     fn apply_filter(&mut self, input: f32, modulation: f32) -> f32 {
         // Get base cutoff from filter state (set by apply_filter_generators)
-        let base_cutoff = self.get_current_filter_cutoff();
+        let base_cutoff = self.get_current_filter_cutoff() * self.brightness_scale;
         
         // Apply modulation to filter cutoff with EMU8000 ranges
         // Modulation can push filter up to 2 octaves higher or down to minimum
-        let modulation_range = 4.0; // ±2 octaves in semitones
-        let modulation_multiplier = 2.0_f32.powf(modulation * modulation_range / 12.0);
+        let modulation_multiplier = 2.0_f32.powf(modulation * FILTER_MODULATION_RANGE / 12.0);
         
-        let modulated_cutoff = (base_cutoff * modulation_multiplier).clamp(100.0, 8000.0);
+        let mut modulated_cutoff = (base_cutoff * modulation_multiplier).clamp(100.0, 8000.0);
+        if self.hardware_mode {
+            modulated_cutoff = hardware_mode::quantize_cutoff(modulated_cutoff);
+        }
         
         // Only update filter if cutoff actually changed (avoid unnecessary recalculation)
-        if (modulated_cutoff - self.get_current_filter_cutoff()).abs() > 1.0 {
+        if (modulated_cutoff - self.filter.cutoff_hz).abs() > 1.0 {
             self.filter.set_cutoff(modulated_cutoff);
         }
         
@@ -786,144 +1154,52 @@ impl MultiZoneSampleVoice {
     fn calculate_tremolo(&mut self) -> f32 {
         let lfo1_value = self.lfo1.process();
         let tremolo_depth = 0.1; // 10% tremolo depth for now
-        1.0 + lfo1_value * tremolo_depth
+        // Host tremolo routes add depth on the raw LFO1 wave
+        let routed_depth = self.modulation_router.route_amount(ModulationDestination::Tremolo);
+        (1.0 + lfo1_value * tremolo_depth + self.lfo1.current_level * routed_depth).max(0.0)
     }
     
     /// Apply SoundFont generators to voice parameters
-    fn apply_generators(&mut self, preset: &SoundFontPreset, soundfont: &SoundFont) -> Result<(), AweError> {
-        // REAL GENERATOR IMPLEMENTATION - Apply all SoundFont generators from both preset and instrument zones
-        // This replaces the old TODO with actual SoundFont 2.0 compliance
+    fn apply_generators(&mut self, preset: &SoundFontPreset) -> Result<(), AweError> {
+        // Each zone carries the values resolved for its (preset zone, instrument zone) pair;
+        // the voice-wide envelope and LFOs follow the first selected zone
+        let generators = self.zones.first().map(|zone| zone.generators).unwrap_or_default();
         
         // Apply volume envelope generators (33-40)
-        self.apply_volume_envelope_generators(preset, soundfont)?;
+        self.apply_volume_envelope_generators(&generators)?;
         
         // Apply volume/attenuation generators (48, 51, 52) - CRITICAL FOR AUDIO LEVELS
-        self.apply_volume_generators(preset, soundfont)?;
+        self.apply_volume_generators()?;
         
         // Apply modulation envelope generators (25-32)
         self.apply_modulation_envelope_generators(preset)?;
         
         // Apply LFO generators (21-24)
-        self.apply_lfo_generators(preset)?;
+        self.apply_lfo_generators(&generators)?;
         
         // Apply filter generators (8-10)
         self.apply_filter_generators(preset)?;
         
         // Apply effects send generators (91-92)
-        self.apply_effects_send_generators(preset)?;
+        self.apply_effects_send_generators(&generators)?;
         
         // Apply loop offset generators (2, 3, 45, 50) - CRITICAL FOR LOOP POINTS
-        self.apply_loop_generators(preset, soundfont)?;
+        self.apply_loop_generators()?;
         
         Ok(())
     }
     
     /// Apply volume envelope SoundFont generators (33-40)
-    fn apply_volume_envelope_generators(&mut self, preset: &SoundFontPreset, soundfont: &SoundFont) -> Result<(), AweError> {
-        // REAL SOUNDFONT GENERATOR READING - Read both preset and instrument generators
-        
-        // Default EMU8000 envelope (transparent for simple samples)
-        let mut delay_env = -12000i32;     // 1ms delay
-        let mut attack_env = -12000i32;    // 1ms attack (immediate)
-        let mut hold_env = -12000i32;      // 1ms hold (minimal)  
-        let mut decay_env = -12000i32;     // 1ms decay (minimal)
-        let mut sustain_env = 0i32;        // 0cb = 100% sustain (transparent)
-        let mut release_env = -6000i32;    // 44ms release
-        
-        // Read preset zone generators first (global settings)
-        for zone in &preset.preset_zones {
-            for generator in &zone.generators {
-                match generator.generator_type {
-                    crate::soundfont::types::GeneratorType::DelayVolEnv => {
-                        if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                            delay_env = value as i32;
-                        }
-                    },
-                    crate::soundfont::types::GeneratorType::AttackVolEnv => {
-                        if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                            attack_env = value as i32;
-                        }
-                    },
-                    crate::soundfont::types::GeneratorType::HoldVolEnv => {
-                        if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                            hold_env = value as i32;
-                        }
-                    },
-                    crate::soundfont::types::GeneratorType::DecayVolEnv => {
-                        if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                            decay_env = value as i32;
-                        }
-                    },
-                    crate::soundfont::types::GeneratorType::SustainVolEnv => {
-                        if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                            sustain_env = value as i32;
-                        }
-                    },
-                    crate::soundfont::types::GeneratorType::ReleaseVolEnv => {
-                        if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                            release_env = value as i32;
-                        }
-                    },
-                    _ => {} // Ignore non-envelope generators here
-                }
-            }
-        }
-        
-        // Read instrument zone generators (sample-specific settings override preset)
-        // This is critical for correct SoundFont 2.0 compliance
-            for zone in &preset.preset_zones {
-                if let Some(instrument_id) = zone.instrument_id {
-                    if let Some(instrument) = soundfont.instruments.get(instrument_id as usize) {
-                        for inst_zone in &instrument.instrument_zones {
-                            for generator in &inst_zone.generators {
-                                match generator.generator_type {
-                                    crate::soundfont::types::GeneratorType::DelayVolEnv => {
-                                        if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                                            delay_env = value as i32; // Instrument overrides preset
-                                        }
-                                    },
-                                    crate::soundfont::types::GeneratorType::AttackVolEnv => {
-                                        if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                                            attack_env = value as i32;
-                                        }
-                                    },
-                                    crate::soundfont::types::GeneratorType::HoldVolEnv => {
-                                        if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                                            hold_env = value as i32;
-                                        }
-                                    },
-                                    crate::soundfont::types::GeneratorType::DecayVolEnv => {
-                                        if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                                            decay_env = value as i32;
-                                        }
-                                    },
-                                    crate::soundfont::types::GeneratorType::SustainVolEnv => {
-                                        if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                                            sustain_env = value as i32;
-                                        }
-                                    },
-                                    crate::soundfont::types::GeneratorType::ReleaseVolEnv => {
-                                        if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                                            release_env = value as i32;
-                                        }
-                                    },
-                                    _ => {} // Ignore non-envelope generators here
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        
+    fn apply_volume_envelope_generators(&mut self, generators: &ZoneGenerators) -> Result<(), AweError> {
         // Create envelope with actual SoundFont parameters (or defaults if none specified)
         self.volume_envelope = DAHDSREnvelope::new(
             self.sample_rate,
-            delay_env,    // Use SoundFont delay or default
-            attack_env,   // Use SoundFont attack or default  
-            hold_env,     // Use SoundFont hold or default
-            decay_env,    // Use SoundFont decay or default
-            sustain_env,  // Use SoundFont sustain or default (0cb = 100%)
-            release_env,  // Use SoundFont release or default
+            generators.delay_vol_env,
+            generators.attack_vol_env,
+            generators.hold_vol_env,
+            generators.decay_vol_env,
+            generators.sustain_vol_env,   // 0cb = 100%
+            generators.release_vol_env,
         );
         
         // Re-trigger envelope with actual parameters if voice is active
@@ -942,10 +1218,15 @@ impl MultiZoneSampleVoice {
         }
     }
     
+    /// Volume envelope stage and progress through it (0.0-1.0)
+    pub fn get_volume_envelope_stage(&self) -> (EnvelopeState, f32) {
+        (self.volume_envelope.state, self.volume_envelope.stage_progress())
+    }
+    
     /// Force envelope to quick release for voice stealing
     pub fn force_quick_release(&mut self) {
-        self.volume_envelope.quick_release();
-        self.modulation_envelope.quick_release();
+        self.volume_envelope.fast_release();
+        self.modulation_envelope.fast_release();
     }
     
     /// Get current modulation envelope level (0.0-1.0)
@@ -969,104 +1250,22 @@ impl MultiZoneSampleVoice {
             // Note: We can't directly set state to Off here, the envelope handles that
         }
         
-        // Apply velocity sensitivity to envelope output
-        // EMU8000 has built-in velocity curve that affects envelope amplitude
-        let velocity_factor = self.velocity as f32 / 127.0;
-        let velocity_curve = velocity_factor * velocity_factor; // Quadratic curve
-        
-        envelope_level * velocity_curve
+        // Velocity reaches amplitude through the default modulator (see apply_volume_generators)
+        envelope_level
     }
     
     /// Apply volume/attenuation SoundFont generators (48, 51, 52)
-    fn apply_volume_generators(&mut self, preset: &SoundFontPreset, soundfont: &SoundFont) -> Result<(), AweError> {
-        // REAL SOUNDFONT GENERATOR READING - Read volume generators from both preset and instrument zones
-        
-        // Default EMU8000 volume settings (transparent)
-        let mut initial_attenuation = 0i32;    // 0cb = no attenuation (100% volume)
-        let mut coarse_tune = 0i32;            // 0 semitones
-        let mut fine_tune = 0i32;              // 0 cents
-        
-        // Read preset zone generators first (global settings)
-        for zone in &preset.preset_zones {
-            for generator in &zone.generators {
-                match generator.generator_type {
-                    crate::soundfont::types::GeneratorType::InitialAttenuation => {
-                        if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                            initial_attenuation = value as i32;
-                        }
-                    },
-                    crate::soundfont::types::GeneratorType::CoarseTune => {
-                        if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                            coarse_tune = value as i32;
-                        }
-                    },
-                    crate::soundfont::types::GeneratorType::FineTune => {
-                        if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                            fine_tune = value as i32;
-                        }
-                    },
-                    _ => {}
-                }
-            }
-        }
-        
-        // Also read instrument zone generators (these override preset settings)
-        if let Some(instrument_id) = preset.preset_zones.first().and_then(|z| z.instrument_id) {
-            if let Some(instrument) = soundfont.instruments.get(instrument_id as usize) {
-                for zone in &instrument.instrument_zones {
-                    for generator in &zone.generators {
-                        match generator.generator_type {
-                            crate::soundfont::types::GeneratorType::InitialAttenuation => {
-                                if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                                    initial_attenuation = value as i32;
-                                }
-                            },
-                            crate::soundfont::types::GeneratorType::CoarseTune => {
-                                if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                                    coarse_tune = value as i32;
-                                }
-                            },
-                            crate::soundfont::types::GeneratorType::FineTune => {
-                                if let crate::soundfont::types::GeneratorAmount::Short(value) = generator.amount {
-                                    fine_tune = value as i32;
-                                }
-                            },
-                            _ => {}
-                        }
-                    }
-                }
-            }
-        }
-        
-        // Apply initial attenuation (convert centibels to linear factor)
+    fn apply_volume_generators(&mut self) -> Result<(), AweError> {
+        // Each zone's initial attenuation plus the default velocity modulator (convert centibels to linear factor)
         // SoundFont spec: attenuation in centibels (1cb = 0.1dB), 0cb = no attenuation
-        // For now, store the attenuation factor in the zones (will be applied during processing)
-        let attenuation_factor = if initial_attenuation != 0 {
-            let attenuation_db = initial_attenuation as f32 * 0.1; // cb to dB
-            (10.0_f32).powf(-attenuation_db / 20.0) // dB to linear
-        } else {
-            1.0 // No attenuation
-        };
-        
-        // Apply attenuation to all active zones
+        // Coarse/fine tune stay in the zone and are applied with its playback rate
+        let velocity_attenuation = velocity_attenuation_cb(self.velocity_fine);
         for zone in &mut self.zones {
-            zone.zone_amplitude *= attenuation_factor;
+            let total_attenuation = zone.generators.initial_attenuation as f32 + velocity_attenuation;
+            let attenuation_db = total_attenuation * 0.1; // cb to dB
+            zone.zone_gain = (10.0_f32).powf(-attenuation_db / 20.0); // dB to linear
         }
         
-        // Apply pitch adjustment from coarse/fine tune
-        if coarse_tune != 0 || fine_tune != 0 {
-            let total_cents = (coarse_tune * 100) + fine_tune; // Coarse tune in semitones, fine in cents
-            let pitch_factor = 2.0_f32.powf(total_cents as f32 / 1200.0); // Convert cents to frequency ratio
-            self.base_pitch *= pitch_factor;
-            self.current_pitch = self.base_pitch;
-        }
-        
-        crate::log(&format!(
-            "Applied volume generators: attenuation={}cb ({:.3}x), coarse_tune={}st, fine_tune={}c, pitch_factor={:.3}", 
-            initial_attenuation, attenuation_factor, coarse_tune, fine_tune, 
-            self.current_pitch / (self.note as f32)
-        ));
-        
         Ok(())
     }
     
@@ -1085,24 +1284,36 @@ impl MultiZoneSampleVoice {
         
         // For now, use EMU8000 defaults optimized for modulation
         // Modulation envelope is typically faster than volume envelope
-        let velocity_factor = self.velocity as f32 / 127.0;
         
         // Apply key scaling to modulation envelope
         // Higher notes = faster envelope times (more responsive modulation)
         let key_factor = (self.note as f32 - 60.0) / 127.0; // C4 = 0, higher = positive
         let key_scaling = -key_factor * 800.0; // Up to 800tc faster for high notes
         
-        // Velocity affects modulation intensity - higher velocity = more modulation
-        let velocity_scaling = velocity_factor * 600.0; // Up to 600tc faster attack
+        if let Some(shape) = self.filter_envelope {
+            self.modulation_envelope = DAHDSREnvelope::new(
+                self.sample_rate,
+                -12000,                                         // delay: 1ms
+                shape.attack_timecents,
+                shape.hold_timecents,
+                shape.decay_timecents,
+                mod_envelope::sustain_centibels(shape.sustain_decrease),
+                shape.release_timecents,
+            );
+            if self.state == VoiceState::Active || self.state == VoiceState::Starting {
+                self.modulation_envelope.trigger();
+            }
+            return Ok(());
+        }
         
-        // Update modulation envelope with key/velocity scaling
+        // Update modulation envelope with key scaling
         self.modulation_envelope = DAHDSREnvelope::new(
             self.sample_rate,
             -10000,                                         // delay: 5ms (faster than volume)
-            (-6000.0 + key_scaling + velocity_scaling) as i32, // attack: key/velocity scaled
+            (-6000.0 + key_scaling) as i32,                 // attack: key scaled
             -10000,                                         // hold: 5ms (shorter than volume)
             (-3600.0 + key_scaling) as i32,                // decay: key scaled
-            600 + (velocity_factor * 300.0) as i32,       // sustain: velocity dependent
+            mod_envelope::sustain_centibels(1000),          // sustain: decays fully
             -2400,                                          // release: 250ms (faster than volume)
         );
         
@@ -1131,36 +1342,33 @@ impl MultiZoneSampleVoice {
     }
     
     /// Apply LFO SoundFont generators (21-24)
-    fn apply_lfo_generators(&mut self, _preset: &SoundFontPreset) -> Result<(), AweError> {
-        // TODO: Extract generators from preset
+    fn apply_lfo_generators(&mut self, generators: &ZoneGenerators) -> Result<(), AweError> {
         // SoundFont 2.0 LFO generators:
-        // - Generator 21: freqModLFO (modulation LFO frequency in cents)
-        // - Generator 22: delayModLFO (modulation LFO delay in timecents)
-        // - Generator 23: freqVibLFO (vibrato LFO frequency in cents)
-        // - Generator 24: delayVibLFO (vibrato LFO delay in timecents)
+        // - Generator 21: delayModLFO (modulation LFO delay in timecents) - read
+        // - Generator 22: freqModLFO (modulation LFO frequency in cents) - TODO
+        // - Generator 23: delayVibLFO (vibrato LFO delay in timecents) - read
+        // - Generator 24: freqVibLFO (vibrato LFO frequency in cents) - TODO
+        let delay_mod_lfo = generators.delay_mod_lfo;
+        let delay_vib_lfo = generators.delay_vib_lfo;
         
-        // For now, use EMU8000 defaults with velocity and note sensitivity
-        let velocity_factor = self.velocity as f32 / 127.0;
-        let note_factor = self.note as f32 / 127.0;
+        // Frequencies and depths still use EMU8000 defaults
         
         // LFO1 (Modulation/Tremolo) - affects amplitude and filter
-        // Lower velocity = slower, less intense tremolo
-        let lfo1_frequency = 1.0 + velocity_factor * 3.0; // 1-4 Hz based on velocity
-        let lfo1_depth = 0.05 + velocity_factor * 0.15;    // 5-20% depth based on velocity
-        
-        self.lfo1 = LFO::new(self.sample_rate, lfo1_frequency, lfo1_depth, LfoWaveform::Triangle);
+        self.lfo1 = LFO::new(self.sample_rate, MOD_LFO_FREQUENCY_HZ, MOD_LFO_DEPTH, LfoWaveform::Triangle);
         
         // LFO2 (Vibrato) - affects pitch only
         // Higher notes get slightly faster vibrato (EMU8000 behavior)
-        let lfo2_frequency = 4.0 + note_factor * 2.0;      // 4-6 Hz based on note
-        let lfo2_depth = 0.02 + velocity_factor * 0.08;    // 2-10% depth based on velocity
+        let lfo2_frequency = self.vibrato_base_frequency(); // 4-6 Hz based on note
+        self.lfo2 = LFO::new(self.sample_rate, lfo2_frequency, VIB_LFO_DEPTH, LfoWaveform::Sine);
         
-        self.lfo2 = LFO::new(self.sample_rate, lfo2_frequency, lfo2_depth, LfoWaveform::Sine);
+        // No modulation until each LFO's delay has elapsed
+        self.lfo1.set_delay(timecents_to_seconds(delay_mod_lfo));
+        self.lfo2.set_delay(timecents_to_seconds(delay_vib_lfo));
         
         // Reset LFOs to synchronized state if voice is active
         if self.state == VoiceState::Active || self.state == VoiceState::Starting {
-            self.lfo1.trigger(); // Start from phase 0
-            self.lfo2.trigger(); // Start from phase 0
+            self.lfo1.trigger(); // Start from the waveform's rising zero crossing
+            self.lfo2.trigger();
         }
         
         Ok(())
@@ -1178,25 +1386,32 @@ impl MultiZoneSampleVoice {
     
     /// Set modulation wheel value (affects LFO2 vibrato depth)
     pub fn set_modulation_wheel(&mut self, value: f32) {
-        // Modulation wheel controls vibrato depth (EMU8000 standard behavior)
+        // Modulation wheel controls vibrato depth through the mod wheel mapping (default 0.02-0.15)
         let mod_value = value.clamp(0.0, 1.0);
-        let base_depth = 0.02; // Minimum vibrato depth
-        let max_depth = 0.15;  // Maximum vibrato depth
-        
-        let new_depth = base_depth + mod_value * (max_depth - base_depth);
-        self.lfo2.set_depth(new_depth);
+        self.modulation_router.set_source_value(ModulationSource::ModWheel, mod_value);
+        let new_depth = self.mod_wheel_vibrato.unwrap_or_default().depth(mod_value);
+        self.lfo2.set_depth(new_depth.abs() * self.vibrato_depth_scale);
     }
     
-    /// Apply pitch bend to affect LFO2 vibrato speed (subtle EMU8000 effect)
+    /// Mod wheel to vibrato mapping for this voice's notes (None: CC1 vibrato only through routes)
+    pub fn set_mod_wheel_vibrato(&mut self, vibrato: Option<ModWheelVibrato>) {
+        self.mod_wheel_vibrato = vibrato;
+    }
+    
+    /// Apply pitch bend to affect LFO2 vibrato speed (enhancement, see set_enhancements)
     pub fn apply_pitch_bend_to_lfo(&mut self, pitch_bend: f32) {
-        // Extreme pitch bends slightly affect vibrato speed (EMU8000 behavior)
+        // Extreme pitch bends slightly affect vibrato speed
         if pitch_bend.abs() > 1.0 { // Only for significant bends (>1 semitone)
             let bend_factor = 1.0 + pitch_bend.abs() * 0.05; // Up to 5% speed change
-            let base_freq = 4.0 + (self.note as f32 / 127.0) * 2.0;
-            self.lfo2.set_frequency(base_freq * bend_factor);
+            self.lfo2.set_frequency(self.vibrato_base_frequency() * bend_factor * self.vibrato_rate_scale);
         }
     }
     
+    /// Vibrato rate before CC76 and bends: 4-6 Hz rising with the note
+    fn vibrato_base_frequency(&self) -> f32 {
+        4.0 + (self.note as f32 / 127.0) * 2.0
+    }
+    
     /// Apply filter SoundFont generators (8-10)
     fn apply_filter_generators(&mut self, _preset: &SoundFontPreset) -> Result<(), AweError> {
         // TODO: Extract generators from preset
@@ -1206,21 +1421,20 @@ impl MultiZoneSampleVoice {
         // - Generator 10: modLfoToFilterFc (mod LFO to filter cutoff in cents)
         
         // For now, apply EMU8000-authentic filter settings based on note/velocity
-        let velocity_factor = self.velocity as f32 / 127.0;
+        let velocity_factor = self.velocity_fine;
         let note_factor = self.note as f32 / 127.0;
         
-        // Base filter cutoff - EMU8000 default behavior
-        // Higher velocity = brighter sound (higher initial cutoff)
+        // Base filter cutoff at full velocity - EMU8000 default behavior
         // Higher notes = slightly more closed filter (prevents shrillness)
-        let base_cutoff = 1000.0 + velocity_factor * 2000.0 - note_factor * 300.0; // 700Hz - 3700Hz range
-        let cutoff = base_cutoff.clamp(100.0, 8000.0); // EMU8000 hard limits
+        // Lower velocity closes it through the default velocity-to-cutoff modulator (two octaves at 0)
+        let base_cutoff = 3000.0 - note_factor * 300.0; // 2700Hz - 3000Hz range
+        let velocity_shift = 2.0_f32.powf(velocity_filter_cents(velocity_factor) / 1200.0);
+        let cutoff = (base_cutoff * velocity_shift).clamp(100.0, 8000.0); // EMU8000 hard limits
         
-        // Filter resonance - EMU8000 behavior  
-        // Higher velocity = more resonance (more character)
+        // Filter resonance - EMU8000 behavior
         // Very high notes = less resonance (prevents feedback)
-        let base_resonance = 0.3 + velocity_factor * 0.5; // 0.3 - 0.8 range
         let note_reduction = (note_factor * 0.3).min(0.2); // Reduce resonance for high notes
-        let resonance = (base_resonance - note_reduction).clamp(0.1, 0.99);
+        let resonance = (FILTER_RESONANCE - note_reduction).clamp(0.1, 0.99);
         
         // Create new filter with calculated parameters
         self.filter = LowPassFilter::new(self.sample_rate, cutoff, resonance);
@@ -1230,6 +1444,16 @@ impl MultiZoneSampleVoice {
         Ok(())
     }
     
+    /// Filter envelope shape for the following notes (None = default modulation envelope)
+    pub fn set_filter_envelope(&mut self, shape: Option<FilterEnvelopeShape>) {
+        self.filter_envelope = shape;
+    }
+    
+    /// Cutoff the filter is running at, after modulation
+    pub fn get_modulated_cutoff(&self) -> f32 {
+        self.filter.cutoff_hz
+    }
+    
     /// Get current filter parameters for debugging
     pub fn get_filter_cutoff(&self) -> f32 {
         self.get_current_filter_cutoff()
@@ -1251,152 +1475,62 @@ impl MultiZoneSampleVoice {
         self.filter.set_resonance(clamped_resonance);
     }
     
-    /// Apply effects send SoundFont generators (91-92)
-    fn apply_effects_send_generators(&mut self, _preset: &SoundFontPreset) -> Result<(), AweError> {
-        // TODO: Extract generators from preset
-        // SoundFont 2.0 effects send generators:
-        // - Generator 91: reverbEffectsSend (reverb send level in centibels)
-        // - Generator 92: chorusEffectsSend (chorus send level in centibels)
-        
-        // For now, apply EMU8000-authentic effects send behavior
-        let velocity_factor = self.velocity as f32 / 127.0;
-        let note_factor = self.note as f32 / 127.0;
-        
-        // Reverb send - EMU8000 behavior
-        // Higher velocity = more reverb (enhances expressiveness)
-        // Lower notes = more reverb (natural acoustic behavior)  
-        let base_reverb = 0.15 + velocity_factor * 0.25;    // 15-40% base range
-        let note_reverb_boost = (1.0 - note_factor) * 0.2; // Up to +20% for low notes
-        self.reverb_send = (base_reverb + note_reverb_boost).clamp(0.0, 0.8);
-        
-        // Chorus send - EMU8000 behavior  
-        // Higher velocity = slight more chorus (but less than reverb)
-        // Mid-range notes = most chorus (natural sweet spot)
-        let base_chorus = 0.08 + velocity_factor * 0.12;    // 8-20% base range
-        let mid_range_boost = {
-            let mid_distance = (note_factor - 0.5).abs(); // Distance from middle
-            (1.0 - mid_distance * 2.0).max(0.0) * 0.1     // Up to +10% for mid notes
-        };
-        self.chorus_send = (base_chorus + mid_range_boost).clamp(0.0, 0.5);
-        
-        // Effects sends debug removed
+    /// Apply GM2 sound controllers (CC71-74, CC76-78) to a newly started note
+    /// Envelope times and vibrato are fixed at note start; brightness can follow later via set_brightness_scale()
+    pub fn apply_sound_controllers(&mut self, controllers: &SoundControllers) {
+        if controllers.is_neutral() {
+            return;
+        }
         
+        self.brightness_scale = controllers.cutoff_scale();
+        self.filter.set_resonance(self.filter.resonance_q * controllers.resonance_scale());
+        
+        let attack = self.volume_envelope.attack_samples as f32 * controllers.attack_time_scale();
+        let release = self.volume_envelope.release_samples as f32 * controllers.release_time_scale();
+        self.volume_envelope.attack_samples = attack as u32;
+        self.volume_envelope.release_samples = release as u32;
+        self.volume_envelope.prepare_segments();
+        
+        self.vibrato_rate_scale = controllers.vibrato_rate_scale();
+        self.vibrato_depth_scale = controllers.vibrato_depth_scale();
+        self.lfo2.set_frequency(self.lfo2.frequency_hz * self.vibrato_rate_scale);
+        self.lfo2.set_depth(self.lfo2.depth * self.vibrato_depth_scale);
+        let base_delay = self.lfo2.delay_samples as f32 / self.sample_rate;
+        self.lfo2.set_delay(controllers.vibrato_delay_seconds(base_delay));
+    }
+    
+    /// Real-time brightness (CC74) cutoff multiplier
+    pub fn set_brightness_scale(&mut self, scale: f32) {
+        self.brightness_scale = scale.clamp(0.25, 4.0);
+    }
+    
+    /// Apply effects send SoundFont generators (91-92)
+    fn apply_effects_send_generators(&mut self, generators: &ZoneGenerators) -> Result<(), AweError> {
+        // Generator 91/92 sends in 0.1% units; the buses add the channel's CC91/CC93 level
+        (self.reverb_send, self.chorus_send) = generators.effects_sends();
         Ok(())
     }
     
     /// Apply loop offset SoundFont generators (2, 3, 45, 50)
-    fn apply_loop_generators(&mut self, preset: &SoundFontPreset, soundfont: &SoundFont) -> Result<(), AweError> {
-        use crate::soundfont::types::{GeneratorType, GeneratorAmount};
-        
-        // SoundFont 2.0 loop offset generators:
-        // - Generator 2: startloopAddrsOffset (fine loop start offset in samples)
-        // - Generator 3: endloopAddrsOffset (fine loop end offset in samples)  
-        // - Generator 45: startloopAddrsCoarseOffset (coarse loop start offset in 32768-sample units)
-        // - Generator 50: endloopAddrsCoarseOffset (coarse loop end offset in 32768-sample units)
-        
-        // Default offsets (no change)
-        let mut start_fine_offset = 0i32;
-        let mut end_fine_offset = 0i32;
-        let mut start_coarse_offset = 0i32;
-        let mut end_coarse_offset = 0i32;
-        
-        // Collect generators from all preset zones
-        for preset_zone in &preset.preset_zones {
-            for generator in &preset_zone.generators {
-                match generator.generator_type {
-                    GeneratorType::StartloopAddrsOffset => {
-                        if let GeneratorAmount::Short(value) = generator.amount {
-                            start_fine_offset += value as i32;
-                        }
-                    },
-                    GeneratorType::EndloopAddrsOffset => {
-                        if let GeneratorAmount::Short(value) = generator.amount {
-                            end_fine_offset += value as i32;
-                        }
-                    },
-                    GeneratorType::StartloopAddrsCoarseOffset => {
-                        if let GeneratorAmount::Short(value) = generator.amount {
-                            start_coarse_offset += value as i32;
-                        }
-                    },
-                    GeneratorType::EndloopAddrsCoarseOffset => {
-                        if let GeneratorAmount::Short(value) = generator.amount {
-                            end_coarse_offset += value as i32;
-                        }
-                    },
-                    _ => {} // Ignore other generators
-                }
-            }
-        }
-        
-        // Collect generators from instrument zones (if preset links to instruments)
-        for preset_zone in &preset.preset_zones {
-            if let Some(instrument_id) = preset_zone.instrument_id {
-                if let Some(instrument) = soundfont.instruments.get(instrument_id as usize) {
-                    for instrument_zone in &instrument.instrument_zones {
-                        for generator in &instrument_zone.generators {
-                            match generator.generator_type {
-                                GeneratorType::StartloopAddrsOffset => {
-                                    if let GeneratorAmount::Short(value) = generator.amount {
-                                        start_fine_offset += value as i32;
-                                    }
-                                },
-                                GeneratorType::EndloopAddrsOffset => {
-                                    if let GeneratorAmount::Short(value) = generator.amount {
-                                        end_fine_offset += value as i32;
-                                    }
-                                },
-                                GeneratorType::StartloopAddrsCoarseOffset => {
-                                    if let GeneratorAmount::Short(value) = generator.amount {
-                                        start_coarse_offset += value as i32;
-                                    }
-                                },
-                                GeneratorType::EndloopAddrsCoarseOffset => {
-                                    if let GeneratorAmount::Short(value) = generator.amount {
-                                        end_coarse_offset += value as i32;
-                                    }
-                                },
-                                _ => {} // Ignore other generators
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        
-        // Apply loop offset calculations to all active zones
+    fn apply_loop_generators(&mut self) -> Result<(), AweError> {
+        // Each zone carries the offsets resolved for its own (preset zone, instrument zone) pair
         for zone in &mut self.zones {
-            if zone.is_active {
-                // Get sample for this zone
-                if let Some(sample) = soundfont.samples.get(0) { // TODO: Use correct sample index from zone
-                    // Use sample's relative loop points (already converted from absolute during parsing)
-                    let sample_length = sample.sample_data.len();
-                    
-                    // Validate that loop points are within sample bounds and properly ordered
-                    // Note: loop_end == 0 means no loop in the SF2 file
-                    if sample.loop_end > 0 && sample.loop_start < sample.loop_end && 
-                       (sample.loop_start as usize) < sample_length && 
-                       (sample.loop_end as usize) <= sample_length {
-                        // Valid loop points - use them
-                        zone.loop_start = Some(sample.loop_start as usize);
-                        zone.loop_end = Some(sample.loop_end as usize);
-                        
-                        crate::log(&format!("SoundFont loop: Sample '{}' has valid loop points: {}-{} (sample length: {})",
-                                          sample.name, sample.loop_start, sample.loop_end, sample_length));
-                    } else {
-                        // No loop or invalid loop points - disable looping
-                        zone.loop_start = None;
-                        zone.loop_end = None;
-                        
-                        let reason = if sample.loop_end == 0 {
-                            "no loop defined"
-                        } else {
-                            "invalid loop points"
-                        };
-                        crate::log(&format!("SoundFont loop: Sample '{}' {}: {}-{} (sample length: {})",
-                                          sample.name, reason, sample.loop_start, sample.loop_end, sample_length));
-                    }
-                }
+            if !zone.is_active {
+                continue;
+            }
+            let (Some(loop_start), Some(loop_end)) = (zone.loop_start, zone.loop_end) else {
+                continue; // Sample has no loop
+            };
+            let start = loop_start as i64 + zone.generators.loop_start_offset as i64;
+            let end = loop_end as i64 + zone.generators.loop_end_offset as i64;
+            
+            // Offset loop must stay inside the sample and properly ordered, else looping is off
+            if start >= 0 && start < end && end <= zone.sample_data.len() as i64 {
+                zone.loop_start = Some(start as usize);
+                zone.loop_end = Some(end as usize);
+            } else {
+                zone.loop_start = None;
+                zone.loop_end = None;
             }
         }
         
@@ -1413,45 +1547,42 @@ impl MultiZoneSampleVoice {
         self.chorus_send = level.clamp(0.0, 1.0);
     }
     
-    /// Get current effects send levels for processing
+    /// Effects send levels the buses receive this sample: base levels times the block's modulation
     pub fn get_effects_sends(&self) -> (f32, f32) {
-        (self.reverb_send, self.chorus_send)
+        let reverb = (self.reverb_send * self.send_modulation.0).clamp(0.0, 1.0);
+        let chorus = (self.chorus_send * self.send_modulation.1).clamp(0.0, 1.0);
+        if self.hardware_mode {
+            (hardware_mode::quantize_send(reverb), hardware_mode::quantize_send(chorus))
+        } else {
+            (reverb, chorus)
+        }
     }
     
-    /// Apply expression control to effects sends (MIDI CC11)
-    pub fn apply_expression_to_effects(&mut self, expression: f32) {
-        // Expression affects effects sends (EMU8000 behavior)
-        // Lower expression = less effects (more "dry" sound)
-        let expression_factor = expression.clamp(0.0, 1.0);
-        let expression_curve = expression_factor * expression_factor; // Quadratic response
-        
-        // Store original levels if not already stored
-        // TODO: Store base levels to restore when expression changes
-        
-        // Apply expression scaling to current sends
-        self.reverb_send *= expression_curve;
-        self.chorus_send *= expression_curve;
+    /// Set the send modulation from an LFO1 value (-1.0 to 1.0): reverb follows it, chorus
+    /// moves the opposite way; the base levels are untouched
+    pub fn modulate_effects_sends(&mut self, lfo1_value: f32) {
+        let swing = lfo1_value.clamp(-1.0, 1.0) * SEND_MODULATION_DEPTH;
+        self.send_modulation = (1.0 + swing, 1.0 - swing);
     }
     
-    /// Modulate effects sends with LFO1 (subtle EMU8000 effect)
-    pub fn modulate_effects_sends(&mut self, lfo1_value: f32) {
-        // Very subtle modulation of effects sends by LFO1
-        // Creates "breathing" effect in reverb/chorus (EMU8000 characteristic)
-        let mod_amount = 0.05; // 5% maximum modulation
-        let reverb_mod = 1.0 + lfo1_value * mod_amount;
-        let chorus_mod = 1.0 - lfo1_value * mod_amount; // Opposite phase
-        
-        // Apply modulation while respecting limits
-        self.reverb_send = (self.reverb_send * reverb_mod).clamp(0.0, 1.0);
-        self.chorus_send = (self.chorus_send * chorus_mod).clamp(0.0, 1.0);
+    /// Reverb and chorus factors applied to the base send levels
+    pub fn get_send_modulation(&self) -> (f32, f32) {
+        self.send_modulation
     }
 }
 
-// Extension trait for quick release
-impl DAHDSREnvelope {
-    fn quick_release(&mut self) {
-        // Force quick release for voice stealing
-        // This will be implemented in the envelope module
-        self.release();
-    }
-}
\ No newline at end of file
+/// Shared fallback test tone, built once (VoiceManager::new warms it up off the audio thread)
+pub(crate) fn fallback_test_tone() -> Arc<[i16]> {
+    static TEST_TONE: OnceLock<Arc<[i16]>> = OnceLock::new();
+    let tone = TEST_TONE.get_or_init(|| {
+        let sample_count = (TEST_TONE_SAMPLE_RATE * TEST_TONE_SECONDS) as usize;
+        let amplitude = 32767.0;
+        (0..sample_count)
+            .map(|i| {
+                let t = i as f32 / TEST_TONE_SAMPLE_RATE;
+                ((2.0 * std::f32::consts::PI * 440.0 * t).sin() * amplitude) as i16
+            })
+            .collect()
+    });
+    Arc::clone(tone)
+}
@@ -0,0 +1,122 @@
+/**
+ * AWE Player - Keyboard Split/Layer Performance
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Live setups split one keyboard into ranges or stack several sounds on the
+ * same keys. Up to MAX_PERFORMANCE_ZONES zones are defined over the incoming
+ * key range, each with its own bank/program, transpose and volume. Notes
+ * arriving on the performance input channel go to every zone whose range
+ * contains them (overlapping zones layer); each zone plays on an internal
+ * channel above the 16 MIDI channels so it never disturbs song playback.
+ */
+
+use super::voice_manager::AUDITION_CHANNEL;
+
+/// Zones per performance
+pub const MAX_PERFORMANCE_ZONES: usize = 4;
+/// Internal channel of zone 0 (zone n plays on this + n)
+pub const PERFORMANCE_CHANNEL_BASE: u8 = AUDITION_CHANNEL + 1;
+/// Largest zone volume (linear gain)
+pub const MAX_ZONE_VOLUME: f32 = 2.0;
+
+/// One key range of the performance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PerformanceZone {
+    pub low_key: u8,
+    pub high_key: u8,
+    pub bank: u16,          // 128 = drum kits
+    pub program: u8,
+    pub transpose: i8,      // Semitones added to incoming keys
+    pub volume: f32,        // Linear gain, 1.0 = unity
+}
+
+impl PerformanceZone {
+    /// Zone with its fields brought into range (keys swapped if reversed)
+    pub fn new(low_key: u8, high_key: u8, bank: u16, program: u8, transpose: i8, volume: f32) -> Self {
+        let (low_key, high_key) = (low_key.min(127), high_key.min(127));
+        PerformanceZone {
+            low_key: low_key.min(high_key),
+            high_key: low_key.max(high_key),
+            bank,
+            program: program & 0x7F,
+            transpose,
+            volume: if volume.is_finite() { volume.clamp(0.0, MAX_ZONE_VOLUME) } else { 1.0 },
+        }
+    }
+
+    /// Note this zone plays for incoming `key`, None outside the range or past 0-127
+    pub fn target_note(&self, key: u8) -> Option<u8> {
+        if key < self.low_key || key > self.high_key {
+            return None;
+        }
+        let note = key as i16 + self.transpose as i16;
+        (0..=127).contains(&note).then_some(note as u8)
+    }
+}
+
+/// Performance input channel and zones
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyboardPerformance {
+    input_channel: Option<u8>,
+    zones: [Option<PerformanceZone>; MAX_PERFORMANCE_ZONES],
+}
+
+impl KeyboardPerformance {
+    pub fn new() -> Self {
+        Self {
+            input_channel: None,
+            zones: [None; MAX_PERFORMANCE_ZONES],
+        }
+    }
+
+    /// MIDI channel routed through the zones (None = performance off)
+    pub fn input_channel(&self) -> Option<u8> {
+        self.input_channel
+    }
+
+    pub fn set_input_channel(&mut self, channel: Option<u8>) {
+        self.input_channel = channel.filter(|&channel| channel < 16);
+    }
+
+    /// True if notes on `channel` belong to the performance
+    pub fn takes_channel(&self, channel: u8) -> bool {
+        self.input_channel == Some(channel) && self.zones.iter().any(Option::is_some)
+    }
+
+    pub fn zone(&self, index: usize) -> Option<&PerformanceZone> {
+        self.zones.get(index).and_then(Option::as_ref)
+    }
+
+    /// Define or clear (None) zone `index`; false for an index past MAX_PERFORMANCE_ZONES
+    pub fn set_zone(&mut self, index: usize, zone: Option<PerformanceZone>) -> bool {
+        match self.zones.get_mut(index) {
+            Some(slot) => {
+                *slot = zone;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Defined zones with their indices
+    pub fn zones(&self) -> impl Iterator<Item = (usize, &PerformanceZone)> {
+        self.zones.iter().enumerate().filter_map(|(index, zone)| zone.as_ref().map(|zone| (index, zone)))
+    }
+
+    /// Zone volume for voices on internal `channel`, None for other channels
+    pub fn channel_volume(&self, channel: u8) -> Option<f32> {
+        let index = channel.checked_sub(PERFORMANCE_CHANNEL_BASE)? as usize;
+        self.zone(index).map(|zone| zone.volume)
+    }
+}
+
+impl Default for KeyboardPerformance {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Internal channel zone `index` plays on
+pub fn zone_channel(index: usize) -> u8 {
+    PERFORMANCE_CHANNEL_BASE + index as u8
+}
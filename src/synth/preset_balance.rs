@@ -0,0 +1,113 @@
+/**
+ * AWE Player - Preset Level Balance
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Free SoundFonts are often assembled from banks recorded at different
+ * levels, so one program can sit 10 dB under the next. The balance pass
+ * renders a few representative notes of every melodic preset on a voice
+ * of its own (no effects, no channel mixer), measures their RMS level and
+ * trims each preset toward the median of the bank. Trims are capped so a
+ * preset that is meant to be quiet is evened out rather than blown up;
+ * drum kits are measured but keep their level, since a kit's balance is
+ * between its keys and a handful of notes says little about it.
+ */
+
+use serde::Serialize;
+use crate::soundfont::types::SoundFont;
+use super::multizone_voice::MultiZoneSampleVoice;
+use super::zone_generators::PresetGenerators;
+
+/// Notes rendered per melodic preset (C3, C4, C5)
+pub const MELODIC_NOTES: [u8; 3] = [48, 60, 72];
+/// Notes rendered per drum kit (kick, snare, closed hi-hat)
+pub const DRUM_NOTES: [u8; 3] = [36, 38, 42];
+/// Largest trim applied either way (dB)
+pub const MAX_BALANCE_TRIM_DB: f32 = 12.0;
+
+/// Bank number of GM drum kits
+const DRUM_BANK: u16 = 128;
+/// Velocity of the measured notes
+const MEASURE_VELOCITY: u8 = 100;
+/// Length measured of each note, attack included
+const MEASURE_SECONDS: f32 = 0.25;
+/// Levels below this are treated as silence (dBFS)
+const SILENCE_DB: f32 = -90.0;
+
+/// Level and trim of one preset (JSON)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PresetLevel {
+    pub preset_index: usize,
+    pub name: String,
+    pub bank: u16,
+    pub program: u8,
+    pub level_db: Option<f32>,  // RMS of the measured notes (None = silent)
+    pub trim_db: f32,           // Gain applied to the preset's notes
+}
+
+/// Result of a balance pass over one bank (JSON)
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PresetBalance {
+    pub reference_db: Option<f32>,  // Median melodic level the trims aim for
+    pub presets: Vec<PresetLevel>,
+}
+
+impl PresetBalance {
+    /// Measure every preset of `soundfont` (`generators` resolved per preset, same order)
+    pub fn analyze(soundfont: &SoundFont, generators: &[PresetGenerators], sample_rate: f32) -> Self {
+        let mut voice = MultiZoneSampleVoice::new(0, sample_rate);
+        let mut presets: Vec<PresetLevel> = soundfont.presets.iter()
+            .zip(generators)
+            .enumerate()
+            .map(|(preset_index, (preset, generators))| {
+                let notes = if preset.bank == DRUM_BANK { &DRUM_NOTES } else { &MELODIC_NOTES };
+                let mut sum_squares = 0.0f64;
+                let mut frames = 0usize;
+                for &note in notes {
+                    if voice.start_note_with_generators(note, (MEASURE_VELOCITY as u16) << 7, 0, soundfont, preset, generators).is_err() {
+                        continue;
+                    }
+                    for _ in 0..(MEASURE_SECONDS * sample_rate) as usize {
+                        let (left, right) = voice.process();
+                        sum_squares += (left as f64 * left as f64 + right as f64 * right as f64) * 0.5;
+                        frames += 1;
+                    }
+                }
+                let level_db = (frames > 0)
+                    .then(|| 10.0 * (sum_squares / frames as f64).log10() as f32)
+                    .filter(|db| db.is_finite() && *db > SILENCE_DB);
+                PresetLevel {
+                    preset_index,
+                    name: preset.name.clone(),
+                    bank: preset.bank,
+                    program: preset.program,
+                    level_db,
+                    trim_db: 0.0,
+                }
+            })
+            .collect();
+
+        let mut melodic: Vec<f32> = presets.iter()
+            .filter(|preset| preset.bank != DRUM_BANK)
+            .filter_map(|preset| preset.level_db)
+            .collect();
+        melodic.sort_by(f32::total_cmp);
+        let reference_db = melodic.get(melodic.len() / 2).copied();
+        if let Some(reference) = reference_db {
+            for preset in presets.iter_mut().filter(|preset| preset.bank != DRUM_BANK) {
+                if let Some(level) = preset.level_db {
+                    preset.trim_db = (reference - level).clamp(-MAX_BALANCE_TRIM_DB, MAX_BALANCE_TRIM_DB);
+                }
+            }
+        }
+        Self { reference_db, presets }
+    }
+
+    /// Trim for the preset at `preset_index` (0 dB if it was not measured)
+    pub fn trim_db(&self, preset_index: usize) -> f32 {
+        self.presets.get(preset_index).map_or(0.0, |preset| preset.trim_db)
+    }
+}
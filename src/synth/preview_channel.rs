@@ -0,0 +1,64 @@
+/**
+ * AWE Player - Preview Channel
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * An instrument picker wants to play sounds while a song is running
+ * without touching the song's channels. The preview channel is a 17th
+ * channel (the internal audition channel) with its own bank/program and
+ * volume. Hosts play it directly, or route one live MIDI input channel to
+ * it; program changes and controllers from the song never reach it, and
+ * its notes only take free voices, so the song is never cut off.
+ */
+
+use serde::Serialize;
+
+/// Largest preview volume (linear gain)
+pub const MAX_PREVIEW_VOLUME: f32 = 2.0;
+
+/// Preview channel settings (JSON for get_preview_channel_global)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PreviewChannel {
+    pub bank: u16,                  // 128 = drum kits
+    pub program: u8,
+    pub volume: f32,                // Linear gain, 1.0 = unity
+    pub input_channel: Option<u8>,  // Live MIDI channel played on the preview channel
+}
+
+impl PreviewChannel {
+    pub fn new() -> Self {
+        Self {
+            bank: 0,
+            program: 0,
+            volume: 1.0,
+            input_channel: None,
+        }
+    }
+
+    pub fn set_program(&mut self, bank: u16, program: u8) {
+        self.bank = bank;
+        self.program = program & 0x7F;
+    }
+
+    pub fn set_volume(&mut self, volume: f32) {
+        if volume.is_finite() {
+            self.volume = volume.clamp(0.0, MAX_PREVIEW_VOLUME);
+        }
+    }
+
+    pub fn set_input_channel(&mut self, channel: Option<u8>) {
+        self.input_channel = channel.filter(|&channel| channel < 16);
+    }
+
+    /// True if live notes on `channel` are played on the preview channel
+    pub fn takes_channel(&self, channel: u8) -> bool {
+        self.input_channel == Some(channel)
+    }
+}
+
+impl Default for PreviewChannel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,81 @@
+/**
+ * AWE Player - Raw Sample Preview
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Plays one SoundFont sample exactly as stored, for SoundFont inspection:
+ * no preset, zone generators, envelopes or filter are involved. The pitch
+ * is given in semitones relative to the sample's root key (its pitch
+ * correction is honoured), the loop can be switched on or off, and the
+ * result is mixed into the dry output so it is heard through the normal
+ * master path. The preview never takes one of the 32 voices.
+ */
+
+use std::sync::Arc;
+use crate::soundfont::types::SoundFontSample;
+
+/// Highest preview gain (+12 dB)
+pub const MAX_PREVIEW_GAIN: f32 = 4.0;
+/// Widest pitch offset from the root key
+pub const MAX_PREVIEW_SEMITONES: f32 = 48.0;
+
+/// A single sample playing at a fixed rate
+#[derive(Debug, Clone)]
+pub struct SamplePreview {
+    data: Arc<[i16]>,
+    position: f64,
+    step: f64,                          // Source samples per output sample
+    loop_range: Option<(usize, usize)>, // Relative loop points when looping is on
+    gain: f32,
+}
+
+impl SamplePreview {
+    /// Start `sample` at `semitones` from its root key; None for an empty sample
+    pub fn new(sample: &SoundFontSample, output_rate: f32, semitones: f32, looped: bool, gain: f32) -> Option<Self> {
+        if sample.sample_data.is_empty() || !semitones.is_finite() || !gain.is_finite() {
+            return None;
+        }
+        let offset = semitones.clamp(-MAX_PREVIEW_SEMITONES, MAX_PREVIEW_SEMITONES) as f64
+            + sample.pitch_correction as f64 / 100.0;
+        let step = sample.sample_rate.max(1) as f64 / output_rate.max(1.0) as f64 * 2.0_f64.powf(offset / 12.0);
+        let (loop_start, loop_end) = (sample.loop_start as usize, sample.loop_end as usize);
+        let loop_range = (looped && loop_start < loop_end && loop_end <= sample.sample_data.len())
+            .then_some((loop_start, loop_end));
+        Some(Self {
+            data: Arc::clone(&sample.sample_data),
+            position: 0.0,
+            step,
+            loop_range,
+            gain: gain.clamp(0.0, MAX_PREVIEW_GAIN),
+        })
+    }
+
+    /// True once a non-looping preview has run off the end of its sample
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.data.len() as f64
+    }
+
+    /// Next output sample (linear interpolation, 0.0 once finished)
+    pub fn next_sample(&mut self) -> f32 {
+        if self.is_finished() {
+            return 0.0;
+        }
+        let index = self.position as usize;
+        let fraction = (self.position - index as f64) as f32;
+        let next_index = match self.loop_range {
+            Some((start, end)) if index + 1 >= end => start,
+            _ => index + 1,
+        };
+        let current = self.data[index] as f32;
+        let next = self.data.get(next_index).copied().unwrap_or(0) as f32;
+        let sample = (current + (next - current) * fraction) / 32768.0 * self.gain;
+
+        self.position += self.step;
+        if let Some((start, end)) = self.loop_range {
+            let length = (end - start) as f64;
+            while self.position >= end as f64 {
+                self.position -= length;
+            }
+        }
+        sample
+    }
+}
@@ -0,0 +1,144 @@
+/**
+ * AWE Player - Controller Smoothing
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * 7-bit controllers move in audible steps: a CC7 fade or a CC74 sweep
+ * arriving as 128 discrete values produces zipper noise. Every
+ * controller-driven parameter the mixer applies per sample (channel gain
+ * from CC7/CC11, CC10 pan, CC74 brightness, CC91/CC93 sends) instead
+ * glides toward its new value through a one-pole lowpass with a shared,
+ * configurable time constant. The host's channel trim glides the same way.
+ */
+
+/// Default time constant (ms)
+pub const DEFAULT_SMOOTHING_MS: f32 = 5.0;
+/// Longest accepted time constant (ms)
+pub const MAX_SMOOTHING_MS: f32 = 200.0;
+
+/// Channel trim range (dB)
+pub const MIN_TRIM_DB: f32 = -60.0;
+pub const MAX_TRIM_DB: f32 = 12.0;
+
+/// Difference below which a value counts as settled (snaps to target)
+const SETTLE_EPSILON: f32 = 1e-5;
+
+/// Per-sample coefficient for a one-pole smoother (0.0 = jump immediately)
+pub fn smoothing_coefficient(time_ms: f32, sample_rate: f32) -> f32 {
+    if !time_ms.is_finite() || time_ms <= 0.0 {
+        return 0.0;
+    }
+    let samples = time_ms.min(MAX_SMOOTHING_MS) * sample_rate / 1000.0;
+    (-1.0 / samples.max(1.0)).exp()
+}
+
+/// A value gliding toward its target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SmoothedValue {
+    current: f32,
+    target: f32,
+}
+
+impl SmoothedValue {
+    pub fn new(value: f32) -> Self {
+        Self { current: value, target: value }
+    }
+
+    pub fn set_target(&mut self, target: f32) {
+        self.target = target;
+    }
+
+    /// Skip the glide (resets, new notes)
+    pub fn jump_to(&mut self, value: f32) {
+        self.current = value;
+        self.target = value;
+    }
+
+    pub fn current(&self) -> f32 {
+        self.current
+    }
+
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    pub fn is_settled(&self) -> bool {
+        self.current == self.target
+    }
+
+    /// Advance one sample with `coefficient` from smoothing_coefficient()
+    pub fn next(&mut self, coefficient: f32) -> f32 {
+        if self.current != self.target {
+            self.current = self.target + (self.current - self.target) * coefficient;
+            if (self.current - self.target).abs() < SETTLE_EPSILON {
+                self.current = self.target;
+            }
+        }
+        self.current
+    }
+}
+
+/// Smoothed mixer parameters of one MIDI channel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelSmoothing {
+    pub gain: SmoothedValue,            // CC7 x CC11 amplitude
+    pub pan: SmoothedValue,             // CC10 as -1.0 (left) to 1.0 (right)
+    pub brightness: SmoothedValue,      // CC74 cutoff multiplier
+    pub reverb_send: SmoothedValue,     // CC91
+    pub chorus_send: SmoothedValue,     // CC93
+    pub trim: SmoothedValue,            // Host trim (linear), on top of CC7/CC11
+}
+
+impl ChannelSmoothing {
+    pub fn new(reverb_send: f32, chorus_send: f32) -> Self {
+        Self {
+            gain: SmoothedValue::new(1.0),
+            pan: SmoothedValue::new(0.0),
+            brightness: SmoothedValue::new(1.0),
+            reverb_send: SmoothedValue::new(reverb_send),
+            chorus_send: SmoothedValue::new(chorus_send),
+            trim: SmoothedValue::new(1.0),
+        }
+    }
+
+    pub fn is_settled(&self) -> bool {
+        self.gain.is_settled() && self.pan.is_settled() && self.brightness.is_settled()
+            && self.reverb_send.is_settled() && self.chorus_send.is_settled() && self.trim.is_settled()
+    }
+
+    /// Advance every parameter one sample
+    pub fn tick(&mut self, coefficient: f32) {
+        self.gain.next(coefficient);
+        self.pan.next(coefficient);
+        self.brightness.next(coefficient);
+        self.reverb_send.next(coefficient);
+        self.chorus_send.next(coefficient);
+        self.trim.next(coefficient);
+    }
+
+    /// (left, right) gains: trimmed channel gain times `gain` with a balance pan (unity at
+    /// center) moved by `pan_offset` (automation lanes; 1.0 and 0.0 leave the controllers alone)
+    pub fn stereo_gains(&self, gain: f32, pan_offset: f32) -> (f32, f32) {
+        let pan = (self.pan.current() + pan_offset).clamp(-1.0, 1.0);
+        let gain = self.gain.current() * self.trim.current() * gain;
+        (gain * (1.0 - pan).min(1.0), gain * (1.0 + pan).min(1.0))
+    }
+}
+
+/// Channel gain from CC7 volume and CC11 expression: squared (GM curve),
+/// normalized so the power-on values (100, 127) are unity. The mixer applies it
+/// before the reverb/chorus sends, so both controllers scale the sends too while
+/// the voices' own send levels stay as the SoundFont and CC91/CC93 set them
+pub fn channel_gain(volume: u8, expression: u8) -> f32 {
+    let level = (volume.min(127) as f32 * expression.min(127) as f32) / (100.0 * 127.0);
+    level * level
+}
+
+/// Channel trim in dB to linear gain (clamped to MIN_TRIM_DB..MAX_TRIM_DB)
+pub fn trim_gain(db: f32) -> f32 {
+    10f32.powf(db.clamp(MIN_TRIM_DB, MAX_TRIM_DB) / 20.0)
+}
+
+/// CC10 value to -1.0..1.0 (64 = center)
+pub fn pan_position(pan: u8) -> f32 {
+    ((pan.min(127) as f32 - 64.0) / 63.0).clamp(-1.0, 1.0)
+}
@@ -0,0 +1,41 @@
+/**
+ * AWE Player - Engine State Snapshot
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Host-side mixer settings that belong to a session rather than to a MIDI
+ * file (channel trims, velocity maps), in a form the host can store (localStorage, a project file) and
+ * hand back later. MIDI-driven state (programs, controllers) is not part of
+ * it: that comes from the file or the live input.
+ *
+ * Snapshots carry a version; fields added later are optional so older
+ * snapshots keep restoring.
+ */
+
+use serde::{Deserialize, Serialize};
+use super::velocity_map::VelocityMap;
+
+/// Current snapshot format
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Restorable engine settings (get_state_snapshot_global JSON)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct EngineSnapshot {
+    pub version: u32,
+    #[serde(default)]
+    pub channel_trims_db: Vec<f32>,     // One per MIDI channel, 0.0 = unity
+    #[serde(default)]
+    pub velocity_maps: Vec<VelocityMap>,  // One per MIDI channel
+}
+
+impl EngineSnapshot {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Parse a snapshot; None for malformed JSON or a newer format
+    pub fn from_json(json: &str) -> Option<Self> {
+        serde_json::from_str::<EngineSnapshot>(json).ok().filter(|snapshot| snapshot.version <= SNAPSHOT_VERSION)
+    }
+}
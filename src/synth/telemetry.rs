@@ -0,0 +1,49 @@
+/**
+ * AWE Player - Channel Telemetry
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Per-channel snapshot for a host mixer view: sounding voices, last
+ * program and the mixer controllers (CC7 volume, CC10 pan, CC11
+ * expression, CC64 sustain). The bridge packs the snapshot into a fixed
+ * byte buffer after every rendered block, so a UI can read it straight
+ * from WASM memory without a call or an allocation per frame; the JSON
+ * export is there for occasional reads.
+ */
+
+use serde::Serialize;
+
+/// Bytes per channel in the packed buffer:
+/// voices, program, volume, pan, expression, sustain (0/1)
+pub const TELEMETRY_STRIDE: usize = 6;
+/// Packed buffer size (16 MIDI channels)
+pub const TELEMETRY_BYTES: usize = TELEMETRY_STRIDE * 16;
+
+/// One channel's state (get_channel_telemetry_global)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ChannelTelemetry {
+    pub channel: u8,
+    pub active_voices: u8,      // Voices sounding on the channel (incl. release tails)
+    pub program: u8,
+    pub volume: u8,             // CC7
+    pub pan: u8,                // CC10 (64 = center)
+    pub expression: u8,         // CC11
+    pub sustain: bool,          // CC64 >= 64
+}
+
+impl ChannelTelemetry {
+    /// Write all channels to `out` (TELEMETRY_STRIDE bytes each, channel order)
+    pub fn pack(channels: &[ChannelTelemetry; 16], out: &mut [u8; TELEMETRY_BYTES]) {
+        for (entry, slot) in channels.iter().zip(out.chunks_exact_mut(TELEMETRY_STRIDE)) {
+            slot.copy_from_slice(&[
+                entry.active_voices,
+                entry.program,
+                entry.volume,
+                entry.pan,
+                entry.expression,
+                entry.sustain as u8,
+            ]);
+        }
+    }
+}
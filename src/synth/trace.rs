@@ -0,0 +1,118 @@
+/**
+ * AWE Player - Audio Path Trace Counters
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Replaces string logging inside the per-sample audio path. Events bump
+ * atomic counters and remember the last value - no formatting, no
+ * allocation. Without the `audio-trace` feature every call compiles to
+ * nothing, so release builds pay zero cost.
+ */
+
+use std::collections::BTreeMap;
+use serde::Serialize;
+
+/// Audio-path events worth counting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceEvent {
+    /// One of the first samples rendered by a newly started voice (value = raw mixed sample)
+    VoiceStartSample,
+    /// Zone mixing began for a new note (value = number of zones)
+    ZoneMixStart,
+}
+
+impl TraceEvent {
+    pub const ALL: [TraceEvent; 2] = [TraceEvent::VoiceStartSample, TraceEvent::ZoneMixStart];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TraceEvent::VoiceStartSample => "voiceStartSample",
+            TraceEvent::ZoneMixStart => "zoneMixStart",
+        }
+    }
+}
+
+#[cfg(feature = "audio-trace")]
+mod counters {
+    use super::TraceEvent;
+    use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+    static COUNTS: [AtomicU64; TraceEvent::ALL.len()] = [AtomicU64::new(0), AtomicU64::new(0)];
+    static LAST_VALUES: [AtomicU32; TraceEvent::ALL.len()] = [AtomicU32::new(0), AtomicU32::new(0)];
+
+    #[inline]
+    pub fn record(event: TraceEvent, value: f32) {
+        COUNTS[event as usize].fetch_add(1, Ordering::Relaxed);
+        LAST_VALUES[event as usize].store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn snapshot(event: TraceEvent) -> (u64, f32) {
+        (
+            COUNTS[event as usize].load(Ordering::Relaxed),
+            f32::from_bits(LAST_VALUES[event as usize].load(Ordering::Relaxed)),
+        )
+    }
+
+    pub fn reset() {
+        for (count, value) in COUNTS.iter().zip(LAST_VALUES.iter()) {
+            count.store(0, Ordering::Relaxed);
+            value.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Whether trace counters are compiled in
+pub const TRACE_ENABLED: bool = cfg!(feature = "audio-trace");
+
+/// Record an audio-path event (no-op without `audio-trace`)
+#[inline(always)]
+pub fn record(event: TraceEvent, value: f32) {
+    #[cfg(feature = "audio-trace")]
+    counters::record(event, value);
+    #[cfg(not(feature = "audio-trace"))]
+    let _ = (event, value);
+}
+
+/// Event count and last recorded value (zeros without `audio-trace`)
+pub fn snapshot(event: TraceEvent) -> (u64, f32) {
+    #[cfg(feature = "audio-trace")]
+    return counters::snapshot(event);
+    #[cfg(not(feature = "audio-trace"))]
+    {
+        let _ = event;
+        (0, 0.0)
+    }
+}
+
+/// Clear all counters
+pub fn reset() {
+    #[cfg(feature = "audio-trace")]
+    counters::reset();
+}
+
+/// Count and last value of one event
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct TraceCounter {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub count: u64,
+    pub last_value: f32,
+}
+
+/// All counters keyed by event name (get_audio_trace_counters)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct TraceReport {
+    pub enabled: bool,
+    pub events: BTreeMap<String, TraceCounter>,
+}
+
+pub fn report() -> TraceReport {
+    let events = TraceEvent::ALL.iter()
+        .map(|&event| {
+            let (count, last_value) = snapshot(event);
+            (event.name().to_string(), TraceCounter { count, last_value })
+        })
+        .collect();
+    TraceReport { enabled: TRACE_ENABLED, events }
+}
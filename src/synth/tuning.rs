@@ -0,0 +1,101 @@
+/**
+ * AWE Player - Per-Key Tuning Tables
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Hosts that compute their own tunings (scales, historical temperaments,
+ * stretch tuning) hand the engine a table of 128 cent offsets, one per MIDI
+ * key. A global table applies to every channel; a channel table replaces
+ * the global one for that channel. Offsets are read at note-on and added at
+ * the pitch calculation stage, on top of the SoundFont's own tuning.
+ *
+ * Master tuning moves the A4 reference for every voice, sounding ones
+ * included, so the engine can join an ensemble at 432, 442 or baroque
+ * 415 Hz. A channel's RPN 1 fine tuning is relative to that reference.
+ */
+
+/// Keys per table
+pub const KEY_COUNT: usize = 128;
+
+/// Largest offset accepted, enough to move any key onto any other (cents)
+pub const MAX_KEY_TUNING_CENTS: f32 = 12_800.0;
+
+const CHANNEL_COUNT: usize = 16;
+
+/// Default A4 reference (Hz)
+pub const STANDARD_A4_HZ: f32 = 440.0;
+/// Lowest master tuning accepted, a little over a semitone flat (Hz)
+pub const MIN_MASTER_TUNING_HZ: f32 = 410.0;
+/// Highest master tuning accepted, a little over a semitone sharp (Hz)
+pub const MAX_MASTER_TUNING_HZ: f32 = 470.0;
+
+/// Offset in cents of an A4 reference from 440 Hz (None outside the accepted range)
+pub fn master_tuning_cents(a4_hz: f32) -> Option<f32> {
+    if !(MIN_MASTER_TUNING_HZ..=MAX_MASTER_TUNING_HZ).contains(&a4_hz) {
+        return None;
+    }
+    Some(1200.0 * (a4_hz / STANDARD_A4_HZ).log2())
+}
+
+/// Global and per-channel key tuning
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyTuning {
+    global: [f32; KEY_COUNT],
+    channels: [Option<[f32; KEY_COUNT]>; CHANNEL_COUNT],
+}
+
+impl KeyTuning {
+    pub fn new() -> Self {
+        Self {
+            global: [0.0; KEY_COUNT],
+            channels: [None; CHANNEL_COUNT],
+        }
+    }
+
+    /// Install `cents` (exactly 128 finite values) for a channel, or globally if `channel` is None
+    /// Returns false, changing nothing, for a bad table or channel
+    pub fn set(&mut self, channel: Option<u8>, cents: &[f32]) -> bool {
+        if cents.len() != KEY_COUNT || cents.iter().any(|c| !c.is_finite()) {
+            return false;
+        }
+        let mut table = [0.0; KEY_COUNT];
+        for (slot, &offset) in table.iter_mut().zip(cents) {
+            *slot = offset.clamp(-MAX_KEY_TUNING_CENTS, MAX_KEY_TUNING_CENTS);
+        }
+        match channel {
+            None => self.global = table,
+            Some(channel) => match self.channels.get_mut(channel as usize) {
+                Some(slot) => *slot = Some(table),
+                None => return false,
+            },
+        }
+        true
+    }
+
+    /// Drop a channel's table (it follows the global table again), or with None
+    /// return every channel and the global table to equal temperament
+    pub fn reset(&mut self, channel: Option<u8>) -> bool {
+        match channel {
+            None => *self = Self::new(),
+            Some(channel) => match self.channels.get_mut(channel as usize) {
+                Some(slot) => *slot = None,
+                None => return false,
+            },
+        }
+        true
+    }
+
+    /// Offset in cents for `note` on `channel` (channels without a table, such as
+    /// the audition channel, use the global table)
+    pub fn cents(&self, channel: u8, note: u8) -> f32 {
+        let table = self.channels.get(channel as usize)
+            .and_then(|table| table.as_ref())
+            .unwrap_or(&self.global);
+        table[note as usize % KEY_COUNT]
+    }
+}
+
+impl Default for KeyTuning {
+    fn default() -> Self {
+        Self::new()
+    }
+}
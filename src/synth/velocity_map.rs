@@ -0,0 +1,79 @@
+/**
+ * AWE Player - Per-Channel Velocity Remapping
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * When a SoundFont's velocity layers don't match a file's dynamics (a piano
+ * part played entirely at 40-70 never reaches the bright top layer), the
+ * incoming velocity can be reshaped per channel before zone selection:
+ * velocities 1-127 are spread over `min`-`max` through a power curve.
+ * Curve 1.0 is linear; below 1.0 lifts soft playing, above 1.0 pushes it
+ * down. Velocity 0 stays a Note Off and never reaches the map.
+ */
+
+use serde::{Deserialize, Serialize};
+
+/// Accepted curve exponents
+pub const MIN_VELOCITY_CURVE: f32 = 0.1;
+pub const MAX_VELOCITY_CURVE: f32 = 10.0;
+
+/// Velocity range and curve for one channel
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct VelocityMap {
+    pub min: u8,
+    pub max: u8,
+    pub curve: f32,
+}
+
+impl VelocityMap {
+    /// Velocities pass through unchanged
+    pub const IDENTITY: VelocityMap = VelocityMap { min: 1, max: 127, curve: 1.0 };
+
+    /// Map with its fields brought into range (max never below min)
+    pub fn new(min: u8, max: u8, curve: f32) -> Self {
+        let min = min.clamp(1, 127);
+        VelocityMap {
+            min,
+            max: max.clamp(min, 127),
+            curve: if curve.is_finite() { curve.clamp(MIN_VELOCITY_CURVE, MAX_VELOCITY_CURVE) } else { 1.0 },
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    /// Remap a 7-bit Note On velocity (1-127)
+    pub fn apply(&self, velocity: u8) -> u8 {
+        if self.is_identity() {
+            return velocity;
+        }
+        self.map(velocity.clamp(1, 127) as f32).round() as u8
+    }
+
+    /// Remap a 14-bit velocity (MSB = 7-bit velocity, LSB = CC88)
+    /// The LSB interpolates toward the next step, so LSB 0 matches apply() exactly
+    pub fn apply_14bit(&self, velocity_14bit: u16) -> u16 {
+        if self.is_identity() {
+            return velocity_14bit;
+        }
+        let velocity_14bit = velocity_14bit.min(0x3FFF);
+        let msb = ((velocity_14bit >> 7) as u8).max(1);
+        let lsb = (velocity_14bit & 0x7F) as f32;
+        let low = self.apply(msb) as f32;
+        let high = if msb < 127 { self.apply(msb + 1) as f32 } else { low };
+        ((low * 128.0 + lsb * (high - low)).round() as u16).min(0x3FFF)
+    }
+
+    fn map(&self, velocity: f32) -> f32 {
+        let position = ((velocity - 1.0) / 126.0).clamp(0.0, 1.0).powf(self.curve);
+        self.min as f32 + position * (self.max - self.min) as f32
+    }
+}
+
+impl Default for VelocityMap {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
@@ -1,13 +1,89 @@
 use super::multizone_voice::MultiZoneSampleVoice;
 use crate::soundfont::types::*;
+use crate::soundfont::preload::{warm_preset, PreloadReport};
+use crate::soundfont::drum_map::{map_drum_kit, DrumKitMap};
+use crate::soundfont::catalog::EffectsSends;
 use crate::effects::reverb::ReverbBus;
 use crate::effects::chorus::ChorusBus;
+use crate::effects::modulation::{ModulationDestination, ModulationMatrix, ModulationRoute, ModulationSource};
 use crate::midi::effects_controller::MidiEffectsController;
+use crate::midi::effects_cc_map::{EffectsCcMap, EffectsParameter};
+use crate::midi::event_trace::DropReason;
+use crate::midi::automation::{AutomationParameter, ChannelAutomation};
+use crate::midi::constants::{MIDI_CC_EXPRESSION, MIDI_CC_SUSTAIN};
+use crate::midi::bank_select::{fallback_banks, resolve_bank_select, BankSelection, MidiSystemMode, SOUNDFONT_DRUM_BANK};
+use super::channel_state::{ChannelState, ChannelStateReport, ParameterNumber, MAX_START_OFFSET_WINDOW_MS, NRPN_PLAYBACK_DIRECTION, RPN_FINE_TUNING, SC_BRIGHTNESS};
+use super::multizone_voice::{fallback_test_tone, PlaybackDirection};
+use super::fallback_bank::{build_fallback_soundfont, FALLBACK_DRUM_PRESET_INDEX};
+use super::tuning::{master_tuning_cents, KeyTuning, STANDARD_A4_HZ};
+use super::velocity_map::VelocityMap;
+use super::mod_envelope::FilterEnvelopeShape;
+use super::watchdog::{HungNote, NoteWatchdog};
+use super::voice_stats::{VoiceStats, VoiceStatsReport};
+use super::performance::{zone_channel, KeyboardPerformance, PerformanceZone, MAX_PERFORMANCE_ZONES};
+use super::hardware_mode::{quantize_16bit, SynthesisMode};
+use super::device_profile::{EffectsQuality, InterpolationQuality};
+use super::crossfade::VelocityCrossfade;
+use super::lfo::LfoPhaseMode;
+use super::mod_wheel::ModWheelVibrato;
+use super::zone_generators::PresetGenerators;
+use super::preset_balance::PresetBalance;
+use super::sample_preview::SamplePreview;
+use super::preview_channel::PreviewChannel;
+use super::telemetry::ChannelTelemetry;
+use super::envelope_monitor::VoiceEnvelope;
+use super::smoothing::{channel_gain, pan_position, smoothing_coefficient, trim_gain, ChannelSmoothing, DEFAULT_SMOOTHING_MS, MAX_SMOOTHING_MS, MAX_TRIM_DB, MIN_TRIM_DB};
+use super::snapshot::{EngineSnapshot, SNAPSHOT_VERSION};
 use crate::log;
 use std::collections::HashMap;
+use serde::Serialize;
+
+/// Internal channel for preset audition and the preview channel, outside the 16 MIDI
+/// channels so sequencer messages never reach its voices
+pub const AUDITION_CHANNEL: u8 = 16;
+
+/// Longest audition note
+pub const MAX_AUDITION_MS: u32 = 10_000;
+
+/// EMU8000 voice count
+pub const MAX_VOICES: usize = 32;
+
+/// Starting state of the LFO phase generator
+pub const DEFAULT_LFO_PHASE_SEED: u32 = 0x9E37_79B9;
+
+/// Debug routing: which voices reach the mixer (dry mix and effects sends)
+/// Muted voices keep running, so un-soloing resumes them mid-note
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugSolo {
+    Off,
+    Voice(usize),
+    Channel(u8),
+}
+
+impl DebugSolo {
+    /// Parse "off", "voice" (index 0-31) or "channel" (index 0-15)
+    pub fn from_parts(mode: &str, index: u32) -> Option<Self> {
+        match mode {
+            "off" => Some(DebugSolo::Off),
+            "voice" if (index as usize) < MAX_VOICES => Some(DebugSolo::Voice(index as usize)),
+            "channel" if index < 16 => Some(DebugSolo::Channel(index as u8)),
+            _ => None,
+        }
+    }
+
+    fn passes(self, voice_index: usize, channel: u8) -> bool {
+        match self {
+            DebugSolo::Off => true,
+            DebugSolo::Voice(index) => index == voice_index,
+            DebugSolo::Channel(solo_channel) => solo_channel == channel,
+        }
+    }
+}
 
 /// Zone selection strategies for multi-sample instruments
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum ZoneSelectionStrategy {
     /// Select all matching zones (default EMU8000 behavior)
     AllMatching,
@@ -21,8 +97,33 @@ pub enum ZoneSelectionStrategy {
     Priority,
 }
 
+impl ZoneSelectionStrategy {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "allMatching" => Some(ZoneSelectionStrategy::AllMatching),
+            "roundRobin" => Some(ZoneSelectionStrategy::RoundRobin),
+            "firstMatch" => Some(ZoneSelectionStrategy::FirstMatch),
+            "random" => Some(ZoneSelectionStrategy::Random),
+            "priority" => Some(ZoneSelectionStrategy::Priority),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ZoneSelectionStrategy::AllMatching => "allMatching",
+            ZoneSelectionStrategy::RoundRobin => "roundRobin",
+            ZoneSelectionStrategy::FirstMatch => "firstMatch",
+            ZoneSelectionStrategy::Random => "random",
+            ZoneSelectionStrategy::Priority => "priority",
+        }
+    }
+}
+
 /// Analysis information for zone selection debugging
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct ZoneSelectionAnalysis {
     pub note: u8,
     pub velocity: u8,
@@ -34,7 +135,9 @@ pub struct ZoneSelectionAnalysis {
 }
 
 /// Details about a specific zone for analysis
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct ZoneDetail {
     pub sample_name: String,
     pub preset_name: String,
@@ -45,12 +148,20 @@ pub struct ZoneDetail {
 }
 
 pub struct VoiceManager {
-    voices: [MultiZoneSampleVoice; 32], // EMU8000-authentic multi-zone voices (Phase 20.4 - single voice system)
+    voices: [MultiZoneSampleVoice; MAX_VOICES], // EMU8000-authentic multi-zone voices (Phase 20.4 - single voice system)
     sample_rate: f32,
     // SoundFont integration
     loaded_soundfont: Option<SoundFont>,
     preset_map: HashMap<(u16, u8), usize>, // (bank, program) -> preset_index
     current_preset: Option<usize>, // Currently selected preset index
+    fallback_bank: Option<SoundFont>, // Procedural GM bank (built up front, dropped once a SoundFont loads)
+    fallback_bank_enabled: bool,
+    // Per-channel bank/program state
+    channels: [ChannelState; 16],
+    system_mode: MidiSystemMode,      // GM/GM2 bank select interpretation
+    humanize_rng: u32,                // xorshift state for start offset randomization
+    lfo_phase_mode: LfoPhaseMode,     // Sync (EMU8000) or random LFO start phase per voice
+    lfo_phase_rng: u32,               // xorshift state for LFO start phases
     // Round-robin and advanced zone selection
     round_robin_counters: HashMap<String, usize>, // Per-instrument round-robin state
     enable_round_robin: bool,         // True = use round-robin sample selection
@@ -58,8 +169,42 @@ pub struct VoiceManager {
     // EMU8000 send/return effects
     reverb_bus: ReverbBus,            // Global reverb with send/return architecture
     chorus_bus: ChorusBus,            // Global chorus with send/return architecture
+    effects_frozen: bool,             // Paused transport: tails hold where they are
+    flush_effects_when_silent: bool,  // Stopped transport: flush again once the voices have faded
     // MIDI effects control
     midi_effects: MidiEffectsController, // MIDI CC 91/93 effects control
+    effects_cc_map: EffectsCcMap,     // Host-mapped controllers to global effect parameters
+    audition_remaining: Option<u32>,  // Samples until the audition note is released
+    audition_voice: Option<(usize, u8)>, // Voice and note of the audition note
+    preview: PreviewChannel,          // Program, volume and live input of the preview channel
+    debug_solo: DebugSolo,            // Mixer-stage isolation for debugging
+    key_tuning: KeyTuning,            // Host-supplied cent offsets per key
+    master_tuning_hz: f32,            // A4 reference
+    synthesis_mode: SynthesisMode,    // Modern float or EMU8000 hardware limits
+    enhancements: bool,               // Heuristics beyond documented EMU8000 behavior
+    sample_preview: Option<SamplePreview>, // Raw sample playing outside the voice pool
+    // Controller smoothing (CC7/10/11/74/91/93)
+    channel_smoothing: [ChannelSmoothing; 16],
+    smoothing_time_ms: f32,
+    smoothing_coefficient: f32,       // Per-sample one-pole coefficient for smoothing_time_ms
+    channel_trims_db: [f32; 16],      // Host output trim per channel (post-voice, pre-send)
+    channel_automation: [ChannelAutomation; 16], // Automation lane values on top of the controllers
+    automation_changed: [bool; 16],   // Channels whose automation moved since the last sample
+    velocity_maps: [VelocityMap; 16], // Host velocity remapping per channel
+    mod_wheel_vibrato: [Option<ModWheelVibrato>; 16], // Host CC1 vibrato range/curve (None: the preset's)
+    drum_overrides: [Option<bool>; 16], // Host percussion/melodic choice per channel (None: system mode rules)
+    performance: KeyboardPerformance, // Keyboard split/layer zones
+    watchdog: NoteWatchdog,           // Hanging-note detection (off by default)
+    voice_stats: VoiceStats,          // Lifetimes, steals and polyphony since the last reset
+    modulation_matrix: ModulationMatrix, // Host modulation routes (global and per channel)
+    voice_limit: usize,               // Voices new notes may use (device profile polyphony)
+    interpolation: InterpolationQuality,
+    effects_quality: EffectsQuality,
+    preset_sends: HashMap<usize, EffectsSends>, // Host send overrides by preset index
+    velocity_crossfade: VelocityCrossfade, // Curve and width of velocity layer fades
+    preset_generators: Vec<PresetGenerators>, // Per preset of the playing bank, read at note-on
+    preset_balance_enabled: bool,     // Apply level trims from the balance pass
+    preset_balance: Option<PresetBalance>, // Balance pass over the playing bank
 }
 
 impl VoiceManager {
@@ -70,16 +215,61 @@ impl VoiceManager {
             loaded_soundfont: None,
             preset_map: HashMap::new(),
             current_preset: None,
+            fallback_bank: Some(build_fallback_soundfont(sample_rate)),
+            fallback_bank_enabled: true,
+            channels: core::array::from_fn(|ch| ChannelState::new(ch as u8)),
+            system_mode: MidiSystemMode::Gm,
+            humanize_rng: 0x2545_F491,
+            lfo_phase_mode: LfoPhaseMode::Sync,
+            lfo_phase_rng: DEFAULT_LFO_PHASE_SEED,
             round_robin_counters: HashMap::new(),
             enable_round_robin: false,  // Default to all matching zones (EMU8000 authentic)
             zone_selection_strategy: ZoneSelectionStrategy::AllMatching, // Default EMU8000 behavior
             reverb_bus: ReverbBus::new(sample_rate),
             chorus_bus: ChorusBus::new(sample_rate),
+            effects_frozen: false,
+            flush_effects_when_silent: false,
             midi_effects: MidiEffectsController::new(),
+            effects_cc_map: EffectsCcMap::default(),
+            audition_remaining: None,
+            audition_voice: None,
+            preview: PreviewChannel::new(),
+            debug_solo: DebugSolo::Off,
+            key_tuning: KeyTuning::new(),
+            master_tuning_hz: STANDARD_A4_HZ,
+            synthesis_mode: SynthesisMode::Modern,
+            enhancements: true,
+            sample_preview: None,
+            channel_smoothing: [ChannelSmoothing::new(0.0, 0.0); 16],
+            channel_automation: [ChannelAutomation::NEUTRAL; 16],
+            automation_changed: [false; 16],
+            smoothing_time_ms: DEFAULT_SMOOTHING_MS,
+            smoothing_coefficient: smoothing_coefficient(DEFAULT_SMOOTHING_MS, sample_rate),
+            channel_trims_db: [0.0; 16],
+            velocity_maps: [VelocityMap::IDENTITY; 16],
+            mod_wheel_vibrato: [None; 16],
+            drum_overrides: [None; 16],
+            performance: KeyboardPerformance::new(),
+            watchdog: NoteWatchdog::new(),
+            voice_stats: VoiceStats::new(sample_rate),
+            modulation_matrix: ModulationMatrix::new(),
+            voice_limit: MAX_VOICES,
+            interpolation: InterpolationQuality::Linear,
+            effects_quality: EffectsQuality::Full,
+            preset_sends: HashMap::new(),
+            velocity_crossfade: VelocityCrossfade::default(),
+            preset_generators: Vec::new(),
+            preset_balance_enabled: false,
+            preset_balance: None,
         };
         
         // Initialize effects buses with default MIDI send levels
         voice_manager.update_effects_from_midi();
+        voice_manager.settle_smoothing();
+        // Shared no-zone test tone is built here, not on the first note-on
+        fallback_test_tone();
+        voice_manager.resolve_preset_generators();
+        voice_manager.warm_up_voices();
         // VoiceManager initialization debug removed
         voice_manager
     }
@@ -137,11 +327,26 @@ impl VoiceManager {
         log(&format!("🔍 Final preset_map has {} entries", preset_map.len()));
         
         self.preset_map = preset_map;
+        self.fallback_bank = None; // Real SoundFont replaces the procedural bank
+        
+        // Preset indices from a previous SoundFont are meaningless now
+        for channel in self.channels.iter_mut() {
+            channel.preset_index = None;
+        }
+        self.preset_sends.clear();
         self.loaded_soundfont = Some(soundfont);
+        self.sample_preview = None;
+        self.resolve_preset_generators();
+        self.flush_effects(); // Tails of the previous bank's sounds
+        self.preset_balance = None;
+        if self.preset_balance_enabled {
+            self.analyze_preset_balance();
+        }
         
         // Set default preset (first available)
         if !self.preset_map.is_empty() {
             self.current_preset = Some(0);
+            self.warm_up_voices();
             log(&format!("Default preset set to: '{}'", 
                        self.loaded_soundfont.as_ref().unwrap().presets[0].name));
         }
@@ -153,11 +358,12 @@ impl VoiceManager {
     
     /// Select a SoundFont preset by bank and program number
     pub fn select_preset(&mut self, bank: u16, program: u8) {
-        if let Some(preset_index) = self.preset_map.get(&(bank, program)) {
-            self.current_preset = Some(*preset_index);
+        if let Some(&preset_index) = self.preset_map.get(&(bank, program)) {
+            self.current_preset = Some(preset_index);
+            self.flush_effects();
             if let Some(soundfont) = &self.loaded_soundfont {
                 log(&format!("Selected preset: '{}' (Bank {}, Program {})", 
-                           soundfont.presets[*preset_index].name, bank, program));
+                           soundfont.presets[preset_index].name, bank, program));
             }
         } else {
             log(&format!("Warning: Preset not found for Bank {}, Program {} - keeping current preset", 
@@ -166,6 +372,940 @@ impl VoiceManager {
     }
     
     
+    /// Enable/disable the procedural fallback bank used while no SoundFont is loaded
+    pub fn set_fallback_bank_enabled(&mut self, enabled: bool) {
+        self.fallback_bank_enabled = enabled;
+        if !enabled {
+            self.fallback_bank = None;
+        } else if self.loaded_soundfont.is_none() && self.fallback_bank.is_none() {
+            // Built here rather than at note-on so the audio thread never allocates it
+            self.fallback_bank = Some(build_fallback_soundfont(self.sample_rate));
+        }
+        self.resolve_preset_generators();
+    }
+    
+    /// Resolve the generators of every preset of the bank notes now play from
+    /// Done when the bank is installed, not at Program Change: MIDI arrives on the
+    /// audio thread, which must not allocate
+    fn resolve_preset_generators(&mut self) {
+        self.preset_generators = match self.loaded_soundfont.as_ref().or(self.fallback_bank.as_ref()) {
+            Some(soundfont) => soundfont.presets.iter()
+                .map(|preset| PresetGenerators::resolve(preset, soundfont))
+                .collect(),
+            None => Vec::new(),
+        };
+    }
+    
+    /// Play an inaudible note of the default preset on every idle voice (construction and
+    /// SoundFont load), so the first chord starts on voices that have run before
+    /// Returns the number of voices warmed
+    pub fn warm_up_voices(&mut self) -> usize {
+        let preset_index = self.current_preset.unwrap_or(0);
+        let Some(soundfont) = self.loaded_soundfont.as_ref().or(self.fallback_bank.as_ref()) else {
+            return 0;
+        };
+        let (Some(preset), Some(generators)) = (soundfont.presets.get(preset_index), self.preset_generators.get(preset_index)) else {
+            return 0;
+        };
+        let mut warmed = 0;
+        for voice in self.voices.iter_mut().filter(|voice| !voice.is_active()) {
+            voice.warm_up(soundfont, preset, generators);
+            warmed += 1;
+        }
+        warmed
+    }
+    
+    /// Trim every preset of the playing bank toward a common level (off by default)
+    /// Enabling measures the bank unless it was measured already; later loads are measured as they arrive
+    pub fn set_preset_balance(&mut self, enabled: bool) {
+        self.preset_balance_enabled = enabled;
+        if enabled && self.preset_balance.is_none() {
+            self.analyze_preset_balance();
+        }
+    }
+    
+    pub fn is_preset_balance_enabled(&self) -> bool {
+        self.preset_balance_enabled
+    }
+    
+    /// Levels and trims of the last balance pass (None until one has run for this bank)
+    pub fn preset_balance(&self) -> Option<&PresetBalance> {
+        self.preset_balance.as_ref()
+    }
+    
+    /// Render representative notes of every preset and compute their trims (control thread)
+    fn analyze_preset_balance(&mut self) {
+        self.preset_balance = self.loaded_soundfont.as_ref().or(self.fallback_bank.as_ref())
+            .map(|soundfont| PresetBalance::analyze(soundfont, &self.preset_generators, self.sample_rate));
+    }
+    
+    /// Generators resolved for `preset_index` of the playing bank
+    pub fn preset_generators(&self, preset_index: usize) -> Option<&PresetGenerators> {
+        self.preset_generators.get(preset_index)
+    }
+    
+    pub fn is_fallback_bank_enabled(&self) -> bool {
+        self.fallback_bank_enabled
+    }
+    
+    /// Set MIDI system mode (affects how bank select is interpreted)
+    pub fn set_system_mode(&mut self, mode: MidiSystemMode) {
+        self.system_mode = mode;
+    }
+    
+    pub fn get_system_mode(&self) -> MidiSystemMode {
+        self.system_mode
+    }
+    
+    /// Reset all channels to power-on state (GM/GM2 System On)
+    pub fn reset_channels(&mut self) {
+        self.channels = core::array::from_fn(|ch| ChannelState::new(ch as u8));
+        for channel in 0..self.channels.len() {
+            if let Some(is_drum) = self.drum_overrides[channel] {
+                if is_drum != self.channels[channel].is_drum {
+                    self.set_channel_drum(channel as u8, is_drum);
+                }
+            }
+        }
+        for voice in self.voices.iter_mut() {
+            if voice.is_active() {
+                voice.stop_note();
+            }
+        }
+        for (index, smoothing) in self.channel_smoothing.iter_mut().enumerate() {
+            let state = &self.channels[index];
+            smoothing.gain.set_target(channel_gain(state.volume, state.expression));
+            smoothing.pan.set_target(pan_position(state.pan));
+            smoothing.brightness.set_target(state.sound_controllers.cutoff_scale());
+        }
+    }
+    
+    /// Set the controller smoothing time constant in ms (0 = controllers apply immediately)
+    pub fn set_smoothing_time_ms(&mut self, time_ms: f32) {
+        self.smoothing_time_ms = if time_ms.is_finite() { time_ms.clamp(0.0, MAX_SMOOTHING_MS) } else { DEFAULT_SMOOTHING_MS };
+        self.smoothing_coefficient = smoothing_coefficient(self.smoothing_time_ms, self.sample_rate);
+    }
+    
+    pub fn get_smoothing_time_ms(&self) -> f32 {
+        self.smoothing_time_ms
+    }
+    
+    /// Smoothed mixer parameters of a channel (current and target values)
+    pub fn channel_smoothing(&self, channel: u8) -> Option<&ChannelSmoothing> {
+        self.channel_smoothing.get(channel as usize)
+    }
+    
+    /// Trim a channel's output by `db` (clamped to -60..+12 dB), after the voices and
+    /// before the reverb/chorus sends. Survives channel resets; false for a bad channel
+    pub fn set_channel_trim_db(&mut self, channel: u8, db: f32) -> bool {
+        let (Some(trim), Some(smoothing)) = (self.channel_trims_db.get_mut(channel as usize),
+                                             self.channel_smoothing.get_mut(channel as usize)) else {
+            return false;
+        };
+        *trim = if db.is_finite() { db.clamp(MIN_TRIM_DB, MAX_TRIM_DB) } else { 0.0 };
+        smoothing.trim.set_target(trim_gain(*trim));
+        true
+    }
+    
+    pub fn channel_trim_db(&self, channel: u8) -> Option<f32> {
+        self.channel_trims_db.get(channel as usize).copied()
+    }
+    
+    /// Automation lane value for a channel parameter (see midi::automation), applied
+    /// from the next sample without controller smoothing
+    pub fn set_automation(&mut self, channel: u8, parameter: AutomationParameter, value: f32) {
+        if let Some(automation) = self.channel_automation.get_mut(channel as usize) {
+            if automation.set(parameter, value) {
+                self.automation_changed[channel as usize] = true;
+            }
+        }
+    }
+    
+    /// Return a channel parameter (or every automated value with None) to its controllers alone
+    pub fn reset_automation(&mut self, target: Option<(u8, AutomationParameter)>) {
+        match target {
+            Some((channel, parameter)) => {
+                if let Some(automation) = self.channel_automation.get_mut(channel as usize) {
+                    automation.reset(parameter);
+                    self.automation_changed[channel as usize] = true;
+                }
+            }
+            None => {
+                self.channel_automation = [ChannelAutomation::NEUTRAL; 16];
+                self.automation_changed = [true; 16];
+            }
+        }
+    }
+    
+    pub fn channel_automation(&self, channel: u8) -> Option<ChannelAutomation> {
+        self.channel_automation.get(channel as usize).copied()
+    }
+    
+    /// Reshape Note On velocities of `channel` before zone selection (see synth::velocity_map)
+    /// Returns false for a bad channel
+    pub fn set_velocity_map(&mut self, channel: u8, map: VelocityMap) -> bool {
+        match self.velocity_maps.get_mut(channel as usize) {
+            Some(slot) => {
+                *slot = map;
+                true
+            }
+            None => false,
+        }
+    }
+    
+    pub fn velocity_map(&self, channel: u8) -> Option<VelocityMap> {
+        self.velocity_maps.get(channel as usize).copied()
+    }
+    
+    /// Mod wheel vibrato for new notes on a channel (all channels if `channel` is None)
+    /// None hands the channel back to the preset's CC1 modulators; survives channel resets
+    pub fn set_mod_wheel_vibrato(&mut self, channel: Option<u8>, vibrato: Option<ModWheelVibrato>) -> bool {
+        match channel {
+            Some(channel) => match self.mod_wheel_vibrato.get_mut(channel as usize) {
+                Some(slot) => *slot = vibrato,
+                None => return false,
+            },
+            None => self.mod_wheel_vibrato.fill(vibrato),
+        }
+        true
+    }
+    
+    pub fn mod_wheel_vibrato(&self, channel: u8) -> Option<ModWheelVibrato> {
+        self.mod_wheel_vibrato.get(channel as usize).copied().flatten()
+    }
+    
+    /// Host settings to persist (see synth::snapshot)
+    pub fn state_snapshot(&self) -> EngineSnapshot {
+        EngineSnapshot {
+            version: SNAPSHOT_VERSION,
+            channel_trims_db: self.channel_trims_db.to_vec(),
+            velocity_maps: self.velocity_maps.to_vec(),
+        }
+    }
+    
+    /// Restore saved host settings; channels missing from the snapshot return to defaults
+    pub fn restore_state_snapshot(&mut self, snapshot: &EngineSnapshot) {
+        for channel in 0..16u8 {
+            let db = snapshot.channel_trims_db.get(channel as usize).copied().unwrap_or(0.0);
+            self.set_channel_trim_db(channel, db);
+            let map = snapshot.velocity_maps.get(channel as usize)
+                .map_or(VelocityMap::IDENTITY, |map| VelocityMap::new(map.min, map.max, map.curve));
+            self.set_velocity_map(channel, map);
+        }
+    }
+    
+    /// Jump every smoothed parameter to its target
+    pub fn settle_smoothing(&mut self) {
+        for (channel, smoothing) in self.channel_smoothing.iter_mut().enumerate() {
+            for value in [&mut smoothing.gain, &mut smoothing.pan, &mut smoothing.brightness,
+                          &mut smoothing.reverb_send, &mut smoothing.chorus_send, &mut smoothing.trim] {
+                value.jump_to(value.target());
+            }
+            self.reverb_bus.set_channel_send(channel as u8, smoothing.reverb_send.current());
+            self.chorus_bus.set_channel_send(channel as u8, smoothing.chorus_send.current());
+        }
+    }
+    
+    /// Store Bank Select MSB (CC0) - takes effect at the next Program Change
+    pub fn bank_select_msb(&mut self, channel: u8, value: u8) {
+        if let Some(state) = self.channels.get_mut(channel as usize) {
+            state.bank_msb = value & 0x7F;
+        }
+    }
+    
+    /// Store Bank Select LSB (CC32) - takes effect at the next Program Change
+    pub fn bank_select_lsb(&mut self, channel: u8, value: u8) {
+        if let Some(state) = self.channels.get_mut(channel as usize) {
+            state.bank_lsb = value & 0x7F;
+        }
+    }
+    
+    /// Program Change: resolve bank select for the current system mode and pick the preset
+    /// Returns the selected preset index, or None if nothing suitable is loaded
+    pub fn program_change(&mut self, channel: u8, program: u8) -> Option<usize> {
+        let state = self.channels.get(channel as usize).copied()?;
+        let selection = resolve_bank_select(
+            self.system_mode, channel, state.bank_msb, state.bank_lsb, program & 0x7F, state.is_drum,
+        );
+        // A host override wins over bank select and Use For Rhythm Part
+        let selection = match self.drum_overrides[channel as usize] {
+            Some(is_drum) if is_drum != selection.is_drum => BankSelection {
+                bank: if is_drum { SOUNDFONT_DRUM_BANK } else { 0 },
+                program: program & 0x7F,
+                is_drum,
+            },
+            _ => selection,
+        };
+        let preset_index = self.find_preset_with_fallback(&selection);
+        
+        let state = &mut self.channels[channel as usize];
+        state.program = selection.program;
+        state.is_drum = selection.is_drum;
+        state.preset_index = preset_index;
+        preset_index
+    }
+    
+    /// Look up a preset, falling back to the capital tone (bank 0 / drum kit 0) if the variation is missing
+    fn find_preset_with_fallback(&self, selection: &BankSelection) -> Option<usize> {
+        if let Some(&index) = self.preset_map.get(&(selection.bank, selection.program)) {
+            return Some(index);
+        }
+        fallback_banks(self.system_mode, selection)
+            .find_map(|bank| self.preset_map.get(&(bank, selection.program)).copied())
+            .or_else(|| {
+                // Unknown drum kits use the standard kit
+                if selection.is_drum {
+                    self.preset_map.get(&(selection.bank, 0)).copied()
+                } else {
+                    None
+                }
+            })
+    }
+    
+    /// Switch a channel between melodic and percussion (GS "Use For Rhythm Part")
+    /// The channel's program is re-resolved so the change is heard at the next note
+    pub fn set_channel_drum(&mut self, channel: u8, is_drum: bool) {
+        let Some(state) = self.channels.get_mut(channel as usize) else {
+            return;
+        };
+        state.is_drum = is_drum;
+        let program = state.program;
+        self.program_change(channel, program);
+    }
+    
+    /// Force a channel to percussion (Some(true)) or melodic (Some(false)) whatever the
+    /// system mode and the file select; None returns it to the rules (channel 10 = drums)
+    /// Survives channel resets; the program is re-resolved so the next note uses it
+    pub fn set_channel_drum_override(&mut self, channel: u8, is_drum: Option<bool>) -> bool {
+        let Some(slot) = self.drum_overrides.get_mut(channel as usize) else {
+            return false;
+        };
+        *slot = is_drum;
+        self.set_channel_drum(channel, is_drum.unwrap_or(ChannelState::new(channel).is_drum));
+        true
+    }
+    
+    pub fn channel_drum_override(&self, channel: u8) -> Option<bool> {
+        self.drum_overrides.get(channel as usize).copied().flatten()
+    }
+    
+    /// Update a sound controller (CC70-79) on a channel
+    pub fn set_sound_controller(&mut self, channel: u8, controller: u8, value: u8) -> bool {
+        let Some(state) = self.channels.get_mut(channel as usize) else {
+            return false;
+        };
+        if !state.sound_controllers.set(controller, value) {
+            return false;
+        }
+        
+        // Brightness glides in real time (see process()); envelope times apply from the next note
+        if controller == SC_BRIGHTNESS {
+            let scale = state.sound_controllers.cutoff_scale();
+            self.channel_smoothing[channel as usize].brightness.set_target(scale);
+        }
+        true
+    }
+    
+    /// Select RPN/NRPN parameter MSB (CC101 / CC99)
+    pub fn parameter_number_msb(&mut self, channel: u8, nrpn: bool, value: u8) {
+        if let Some(state) = self.channels.get_mut(channel as usize) {
+            state.set_parameter_msb(nrpn, value);
+        }
+    }
+    
+    /// Select RPN/NRPN parameter LSB (CC100 / CC98)
+    pub fn parameter_number_lsb(&mut self, channel: u8, nrpn: bool, value: u8) {
+        if let Some(state) = self.channels.get_mut(channel as usize) {
+            state.set_parameter_lsb(nrpn, value);
+        }
+    }
+    
+    /// Data Entry MSB (CC6) for the selected RPN/NRPN
+    /// Returns true if the parameter is supported and the value was applied
+    pub fn data_entry_msb(&mut self, channel: u8, value: u8) -> bool {
+        let Some(parameter) = self.channels.get(channel as usize).and_then(|state| state.selected_parameter()) else {
+            return false;
+        };
+        match parameter {
+            ParameterNumber::Nrpn(NRPN_PLAYBACK_DIRECTION) => match PlaybackDirection::from_value(value) {
+                Some(direction) => self.set_channel_playback_direction(channel, direction),
+                None => false,
+            },
+            ParameterNumber::Rpn(RPN_FINE_TUNING) => {
+                self.channels[channel as usize].set_fine_tuning_msb(value);
+                true
+            }
+            _ => false,
+        }
+    }
+    
+    /// Data Entry LSB (CC38) for the selected RPN/NRPN
+    /// Returns true if the parameter takes an LSB and the value was applied
+    pub fn data_entry_lsb(&mut self, channel: u8, value: u8) -> bool {
+        let Some(state) = self.channels.get_mut(channel as usize) else {
+            return false;
+        };
+        match state.selected_parameter() {
+            Some(ParameterNumber::Rpn(RPN_FINE_TUNING)) => {
+                state.set_fine_tuning_lsb(value);
+                true
+            }
+            _ => false,
+        }
+    }
+    
+    /// Set sample playback direction for notes started on a channel
+    pub fn set_channel_playback_direction(&mut self, channel: u8, direction: PlaybackDirection) -> bool {
+        match self.channels.get_mut(channel as usize) {
+            Some(state) => {
+                state.playback_direction = direction;
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// Filter-envelope shape for notes started on a channel, or all channels if `channel` is None
+    /// (None shape = the default modulation envelope)
+    pub fn set_filter_envelope(&mut self, channel: Option<u8>, shape: Option<FilterEnvelopeShape>) -> bool {
+        match channel {
+            Some(channel) => match self.channels.get_mut(channel as usize) {
+                Some(state) => {
+                    state.filter_envelope = shape;
+                    true
+                }
+                None => false,
+            },
+            None => {
+                self.channels.iter_mut().for_each(|state| state.filter_envelope = shape);
+                true
+            }
+        }
+    }
+    
+    /// Set humanize start offset window (ms) for a channel, or all channels if `channel` is None
+    pub fn set_start_offset_window(&mut self, channel: Option<u8>, window_ms: f32) -> bool {
+        let window_ms = if window_ms.is_finite() { window_ms.clamp(0.0, MAX_START_OFFSET_WINDOW_MS) } else { 0.0 };
+        match channel {
+            Some(channel) => match self.channels.get_mut(channel as usize) {
+                Some(state) => state.start_offset_window_ms = window_ms,
+                None => return false,
+            },
+            None => self.channels.iter_mut().for_each(|state| state.start_offset_window_ms = window_ms),
+        }
+        true
+    }
+    
+    /// Switch between modern float synthesis and EMU8000 hardware limits
+    /// Takes effect immediately, including on sounding voices
+    pub fn set_synthesis_mode(&mut self, mode: SynthesisMode) {
+        self.synthesis_mode = mode;
+        for voice in self.voices.iter_mut() {
+            voice.set_hardware_mode(mode.is_hardware());
+        }
+    }
+    
+    pub fn get_synthesis_mode(&self) -> SynthesisMode {
+        self.synthesis_mode
+    }
+    
+    /// Enable voice heuristics that aren't documented EMU8000 behavior (on by default);
+    /// off, only spec'd behavior is reproduced. Independent of the synthesis mode
+    pub fn set_enhancements(&mut self, enabled: bool) {
+        self.enhancements = enabled;
+        for voice in self.voices.iter_mut() {
+            voice.set_enhancements(enabled);
+        }
+    }
+    
+    pub fn get_enhancements(&self) -> bool {
+        self.enhancements
+    }
+    
+    /// Voices new notes may start on (1 to MAX_VOICES); notes beyond a lowered limit
+    /// play out, later notes steal within the limit
+    pub fn set_max_polyphony(&mut self, voices: usize) {
+        self.voice_limit = voices.clamp(1, MAX_VOICES);
+    }
+    
+    pub fn max_polyphony(&self) -> usize {
+        self.voice_limit
+    }
+    
+    /// Sample interpolation for every voice, sounding ones included
+    pub fn set_interpolation(&mut self, interpolation: InterpolationQuality) {
+        self.interpolation = interpolation;
+        for voice in self.voices.iter_mut() {
+            voice.set_interpolation(interpolation);
+        }
+    }
+    
+    pub fn interpolation(&self) -> InterpolationQuality {
+        self.interpolation
+    }
+    
+    /// Velocity layer crossfade curve and width; sounding notes keep the fade they started with
+    pub fn set_velocity_crossfade(&mut self, crossfade: VelocityCrossfade) {
+        self.velocity_crossfade = crossfade;
+        for voice in self.voices.iter_mut() {
+            voice.set_velocity_crossfade(crossfade);
+        }
+    }
+    
+    pub fn velocity_crossfade(&self) -> VelocityCrossfade {
+        self.velocity_crossfade
+    }
+    
+    /// Which effect buses run; a skipped bus takes no sends, so its tail stops
+    pub fn set_effects_quality(&mut self, quality: EffectsQuality) {
+        self.effects_quality = quality;
+    }
+    
+    pub fn effects_quality(&self) -> EffectsQuality {
+        self.effects_quality
+    }
+    
+    /// Install a 128-entry cent offset table for a channel, or globally if `channel` is None
+    pub fn set_key_tuning(&mut self, channel: Option<u8>, cents: &[f32]) -> bool {
+        self.key_tuning.set(channel, cents)
+    }
+    
+    /// Remove a channel's tuning table, or with None every table
+    pub fn reset_key_tuning(&mut self, channel: Option<u8>) -> bool {
+        self.key_tuning.reset(channel)
+    }
+    
+    /// Set the A4 reference (MIN_MASTER_TUNING_HZ to MAX_MASTER_TUNING_HZ), retuning
+    /// sounding voices too; false, changing nothing, outside that range
+    pub fn set_master_tuning(&mut self, a4_hz: f32) -> bool {
+        let Some(cents) = master_tuning_cents(a4_hz) else {
+            return false;
+        };
+        self.master_tuning_hz = a4_hz;
+        for voice in self.voices.iter_mut() {
+            voice.set_master_tuning(cents);
+        }
+        true
+    }
+    
+    pub fn get_master_tuning(&self) -> f32 {
+        self.master_tuning_hz
+    }
+    
+    /// Mute every voice except one voice index or one MIDI channel at the mixer
+    pub fn set_debug_solo(&mut self, solo: DebugSolo) {
+        self.debug_solo = solo;
+    }
+    
+    /// LFO start phase for new notes; a non-zero `seed` restarts the random sequence,
+    /// so a render with the same seed and events gets the same phases
+    pub fn set_lfo_phase_mode(&mut self, mode: LfoPhaseMode, seed: u32) {
+        self.lfo_phase_mode = mode;
+        if seed != 0 {
+            self.lfo_phase_rng = seed;
+        }
+    }
+    
+    pub fn get_lfo_phase_mode(&self) -> LfoPhaseMode {
+        self.lfo_phase_mode
+    }
+    
+    pub fn get_debug_solo(&self) -> DebugSolo {
+        self.debug_solo
+    }
+    
+    /// Random start offset (in sample frames) for a new note, None if humanize is off
+    fn next_start_offset(&mut self, window_ms: f32) -> Option<u32> {
+        let window = (window_ms * self.sample_rate / 1000.0) as u32;
+        if window == 0 {
+            return None;
+        }
+        Some(xorshift32(&mut self.humanize_rng) % (window + 1))
+    }
+    
+    /// Random fraction of a cycle (0.0-1.0) for an LFO start phase
+    fn next_lfo_phase(&mut self) -> f32 {
+        (xorshift32(&mut self.lfo_phase_rng) >> 8) as f32 / (1u32 << 24) as f32
+    }
+    
+    /// Play one note of a preset on the internal audition channel for `duration_ms`
+    /// (plus its release). Channel state, the global preset and live voices are left
+    /// alone: a previous audition note is released and no sounding voice is stolen.
+    /// Bank 128 selects drum kits. Returns false if the preset or a free voice is missing.
+    pub fn audition_preset(&mut self, bank: u16, program: u8, note: u8, velocity: u8, duration_ms: u32) -> bool {
+        self.stop_audition();
+        let Some(preset_index) = self.resolve_preset(bank, program) else {
+            return false;
+        };
+        
+        let velocity = velocity.clamp(1, 127);
+        let Some(voice) = self.start_voice(note & 0x7F, velocity, None, AUDITION_CHANNEL, preset_index, false) else {
+            return false;
+        };
+        self.audition_voice = Some((voice, note & 0x7F));
+        let duration_ms = duration_ms.clamp(1, MAX_AUDITION_MS);
+        self.audition_remaining = Some((duration_ms as f32 * self.sample_rate / 1000.0) as u32);
+        true
+    }
+    
+    /// Preset index for a bank/program outside any channel's state (bank 128 = drums),
+    /// from the loaded SoundFont with bank fallback, or the procedural bank
+    fn resolve_preset(&self, bank: u16, program: u8) -> Option<usize> {
+        let is_drum = bank == SOUNDFONT_DRUM_BANK;
+        if self.loaded_soundfont.is_some() {
+            self.find_preset_with_fallback(&BankSelection { bank, program: program & 0x7F, is_drum })
+        } else if self.fallback_bank.is_some() {
+            Some(if is_drum { FALLBACK_DRUM_PRESET_INDEX } else { (program & 0x7F) as usize })
+        } else {
+            None
+        }
+    }
+    
+    /// Set (or with None, clear) the effects sends new notes of a loaded-SoundFont preset
+    /// use instead of the default ones. Exact bank/program only; false if no such preset
+    pub fn set_preset_effects_sends(&mut self, bank: u16, program: u8, sends: Option<EffectsSends>) -> bool {
+        let Some(&preset_index) = self.preset_map.get(&(bank, program)) else {
+            return false;
+        };
+        match sends {
+            Some(sends) => self.preset_sends.insert(preset_index, sends),
+            None => self.preset_sends.remove(&preset_index),
+        };
+        true
+    }
+    
+    /// Host send overrides by preset index
+    pub fn preset_effects_sends(&self) -> &HashMap<usize, EffectsSends> {
+        &self.preset_sends
+    }
+    
+    /// Resolve a preset (bank 128 = drums, with the usual bank fallback) and touch
+    /// its zones and sample data now, so a later program change to it has no first-note cost
+    pub fn pre_load_preset(&mut self, bank: u16, program: u8) -> Option<PreloadReport> {
+        let preset_index = self.resolve_preset(bank, program)?;
+        let soundfont = self.loaded_soundfont.as_ref().or(self.fallback_bank.as_ref())?;
+        let preset = soundfont.presets.get(preset_index)?;
+        Some(warm_preset(soundfont, preset_index, preset))
+    }
+    
+    /// Key map of a preset (bank 128 = drum kits, with the usual bank fallback):
+    /// which keys play a sample and which GM percussion keys stay silent
+    pub fn drum_kit_map(&self, bank: u16, program: u8) -> Option<DrumKitMap> {
+        let preset_index = self.resolve_preset(bank, program)?;
+        let soundfont = self.loaded_soundfont.as_ref().or(self.fallback_bank.as_ref())?;
+        let preset = soundfont.presets.get(preset_index)?;
+        Some(map_drum_kit(soundfont, preset_index, preset))
+    }
+    
+    /// Release the audition note, if one is sounding (preview channel notes keep playing)
+    pub fn stop_audition(&mut self) {
+        self.audition_remaining = None;
+        if let Some((index, note)) = self.audition_voice.take() {
+            let voice = &mut self.voices[index];
+            if voice.is_active() && voice.get_channel() == AUDITION_CHANNEL && voice.get_note() == note {
+                voice.stop_note();
+            }
+        }
+    }
+    
+    pub fn is_auditioning(&self) -> bool {
+        self.audition_remaining.is_some()
+    }
+    
+    pub fn preview_channel(&self) -> PreviewChannel {
+        self.preview
+    }
+    
+    /// Select the preview channel's program (bank 128 = drum kits); notes already
+    /// sounding keep theirs. False if neither the SoundFont nor the fallback bank has it
+    pub fn set_preview_program(&mut self, bank: u16, program: u8) -> bool {
+        if self.resolve_preset(bank, program).is_none() {
+            return false;
+        }
+        self.preview.set_program(bank, program);
+        true
+    }
+    
+    /// Preview channel volume (linear, 0.0-2.0)
+    pub fn set_preview_volume(&mut self, volume: f32) {
+        self.preview.set_volume(volume);
+    }
+    
+    /// Play live notes and pitch bend on MIDI `channel` on the preview channel (None = off)
+    /// Preview notes still sounding are released
+    pub fn set_preview_input(&mut self, channel: Option<u8>) {
+        self.preview.set_input_channel(channel);
+        self.release_preview_notes();
+    }
+    
+    /// True if a note on `channel` is played on the preview channel
+    pub fn is_preview_input(&self, channel: u8) -> bool {
+        self.preview.takes_channel(channel)
+    }
+    
+    /// Start a note on the preview channel with its program; only free voices are used
+    pub fn preview_note_on(&mut self, note: u8, velocity: u8) -> Option<usize> {
+        let preset_index = self.resolve_preset(self.preview.bank, self.preview.program)?;
+        self.start_voice(note & 0x7F, velocity.clamp(1, 127), None, AUDITION_CHANNEL, preset_index, false)
+    }
+    
+    /// Release a preview channel note (not the audition note)
+    pub fn preview_note_off(&mut self, note: u8) {
+        let audition = self.audition_voice.map(|(index, _)| index);
+        for (index, voice) in self.voices.iter_mut().enumerate() {
+            if voice.is_active() && voice.get_channel() == AUDITION_CHANNEL && voice.get_note() == note
+                && audition != Some(index) {
+                voice.stop_note();
+            }
+        }
+    }
+    
+    /// Pitch bend (semitones) for the preview channel
+    pub fn preview_pitch_bend(&mut self, bend_value: f32) {
+        self.apply_pitch_bend(AUDITION_CHANNEL, bend_value);
+    }
+    
+    /// Release every preview channel note except the audition note
+    fn release_preview_notes(&mut self) {
+        let audition = self.audition_voice.map(|(index, _)| index);
+        for (index, voice) in self.voices.iter_mut().enumerate() {
+            if voice.is_active() && voice.get_channel() == AUDITION_CHANNEL && audition != Some(index) {
+                voice.stop_note();
+            }
+        }
+    }
+    
+    /// Route notes on MIDI `channel` through the performance zones (None = off)
+    /// Zone notes still sounding are released
+    pub fn set_performance_input(&mut self, channel: Option<u8>) {
+        self.performance.set_input_channel(channel);
+        for index in 0..MAX_PERFORMANCE_ZONES {
+            self.release_channel(zone_channel(index));
+        }
+    }
+    
+    pub fn performance_input(&self) -> Option<u8> {
+        self.performance.input_channel()
+    }
+    
+    /// True if a note on `channel` is played by the performance zones
+    pub fn is_performance_channel(&self, channel: u8) -> bool {
+        self.performance.takes_channel(channel)
+    }
+    
+    /// Define (Some) or clear (None) performance zone `index`, releasing its sounding notes
+    /// Returns false for an index past MAX_PERFORMANCE_ZONES
+    pub fn set_performance_zone(&mut self, index: usize, zone: Option<PerformanceZone>) -> bool {
+        if !self.performance.set_zone(index, zone) {
+            return false;
+        }
+        self.release_channel(zone_channel(index));
+        true
+    }
+    
+    pub fn performance_zone(&self, index: usize) -> Option<PerformanceZone> {
+        self.performance.zone(index).copied()
+    }
+    
+    /// Play incoming `key` on every zone covering it; returns the number of zones sounding
+    pub fn performance_note_on(&mut self, key: u8, velocity: u8) -> usize {
+        let mut started = 0;
+        for index in 0..MAX_PERFORMANCE_ZONES {
+            let Some(zone) = self.performance.zone(index).copied() else {
+                continue;
+            };
+            let Some(note) = zone.target_note(key) else {
+                continue;
+            };
+            let Some(preset_index) = self.resolve_preset(zone.bank, zone.program) else {
+                continue;
+            };
+            if self.start_voice(note, velocity, None, zone_channel(index), preset_index, true).is_some() {
+                started += 1;
+            }
+        }
+        started
+    }
+    
+    /// Release incoming `key` in every zone covering it
+    pub fn performance_note_off(&mut self, key: u8) {
+        for index in 0..MAX_PERFORMANCE_ZONES {
+            let Some(note) = self.performance.zone(index).and_then(|zone| zone.target_note(key)) else {
+                continue;
+            };
+            let channel = zone_channel(index);
+            for voice in self.voices.iter_mut() {
+                if voice.is_active() && voice.get_note() == note && voice.get_channel() == channel {
+                    voice.stop_note();
+                }
+            }
+        }
+    }
+    
+    /// Pitch bend (semitones) for every performance zone
+    pub fn performance_pitch_bend(&mut self, bend_value: f32) {
+        for index in 0..MAX_PERFORMANCE_ZONES {
+            self.apply_pitch_bend(zone_channel(index), bend_value);
+        }
+    }
+    
+    /// Release every sounding note on `channel`
+    fn release_channel(&mut self, channel: u8) {
+        for voice in self.voices.iter_mut() {
+            if voice.is_active() && voice.get_channel() == channel {
+                voice.stop_note();
+            }
+        }
+    }
+    
+    /// Play SoundFont sample `sample_index` raw, `semitones` from its root key
+    /// Replaces any running preview; returns false without a loaded SoundFont or sample
+    pub fn preview_sample(&mut self, sample_index: usize, semitones: f32, looped: bool, gain: f32) -> bool {
+        let sample = self.loaded_soundfont.as_ref().and_then(|soundfont| soundfont.samples.get(sample_index));
+        self.sample_preview = sample.and_then(|sample| SamplePreview::new(sample, self.sample_rate, semitones, looped, gain));
+        self.sample_preview.is_some()
+    }
+    
+    pub fn stop_sample_preview(&mut self) {
+        self.sample_preview = None;
+    }
+    
+    pub fn is_previewing_sample(&self) -> bool {
+        self.sample_preview.is_some()
+    }
+    
+    /// Count down the audition note (once per sample)
+    fn advance_audition(&mut self) {
+        match self.audition_remaining {
+            Some(0) => self.stop_audition(),
+            Some(remaining) => self.audition_remaining = Some(remaining - 1),
+            None => {}
+        }
+    }
+    
+    /// Mixer controller (CC7/10/11/64): volume, expression and pan glide to the new value
+    pub fn set_mixer_controller(&mut self, channel: u8, controller: u8, value: u8) -> bool {
+        let Some(state) = self.channels.get_mut(channel as usize) else {
+            return false;
+        };
+        let was_sustained = state.sustain;
+        if !state.set_mixer_controller(controller, value) {
+            return false;
+        }
+        let released = was_sustained && !state.sustain;
+        let smoothing = &mut self.channel_smoothing[channel as usize];
+        smoothing.gain.set_target(channel_gain(state.volume, state.expression));
+        smoothing.pan.set_target(pan_position(state.pan));
+        if released {
+            self.release_pedal_held(channel);
+        }
+        true
+    }
+    
+    /// Mod wheel (CC1) as a modulation route source for the channel's voices
+    pub fn set_modulation_wheel(&mut self, channel: u8, value: u8) {
+        let value = value & 0x7F;
+        if let Some(state) = self.channels.get_mut(channel as usize) {
+            state.modulation = value;
+            self.update_modulation_source(channel, ModulationSource::ModWheel, value);
+        }
+    }
+    
+    /// Channel aftertouch as a modulation route source for the channel's voices
+    pub fn set_channel_pressure(&mut self, channel: u8, value: u8) {
+        let value = value & 0x7F;
+        if let Some(state) = self.channels.get_mut(channel as usize) {
+            state.pressure = value;
+            self.update_modulation_source(channel, ModulationSource::Aftertouch, value);
+        }
+    }
+    
+    fn update_modulation_source(&mut self, channel: u8, source: ModulationSource, value: u8) {
+        for voice in self.voices.iter_mut() {
+            if voice.is_active() && voice.get_channel() == channel {
+                voice.set_modulation_source(source, value as f32 / 127.0);
+            }
+        }
+    }
+    
+    /// Add or replace a modulation route for one channel (None = all channels)
+    /// Sounding voices pick the change up immediately; false for a bad channel or a full route list
+    pub fn set_modulation_route(&mut self, channel: Option<u8>, route: ModulationRoute) -> bool {
+        let changed = self.modulation_matrix.set_route(channel, route);
+        if changed {
+            self.refresh_modulation_routes();
+        }
+        changed
+    }
+    
+    /// Remove the route from `source` to `destination`; false if there was none
+    pub fn remove_modulation_route(&mut self, channel: Option<u8>, source: ModulationSource, destination: ModulationDestination) -> bool {
+        let changed = self.modulation_matrix.remove_route(channel, source, destination);
+        if changed {
+            self.refresh_modulation_routes();
+        }
+        changed
+    }
+    
+    /// Remove all routes of one channel (None = the global routes)
+    pub fn clear_modulation_routes(&mut self, channel: Option<u8>) -> bool {
+        let cleared = self.modulation_matrix.clear(channel);
+        if cleared {
+            self.refresh_modulation_routes();
+        }
+        cleared
+    }
+    
+    pub fn modulation_matrix(&self) -> &ModulationMatrix {
+        &self.modulation_matrix
+    }
+    
+    fn refresh_modulation_routes(&mut self) {
+        for voice in self.voices.iter_mut().filter(|voice| voice.is_active()) {
+            let channel = voice.get_channel();
+            voice.set_modulation_routes(self.modulation_matrix.global_routes(), self.modulation_matrix.channel_routes(channel));
+        }
+    }
+    
+    /// Snapshot of all 16 MIDI channels (no allocation, safe per audio block)
+    /// Volume envelope stage, progress and level of every voice (voice order)
+    pub fn voice_envelopes(&self) -> [VoiceEnvelope; MAX_VOICES] {
+        core::array::from_fn(|index| VoiceEnvelope::of(index, &self.voices[index]))
+    }
+    
+    pub fn channel_telemetry(&self) -> [ChannelTelemetry; 16] {
+        let mut voice_counts = [0u8; 16];
+        for voice in self.voices.iter().filter(|voice| voice.is_active()) {
+            if let Some(count) = voice_counts.get_mut(voice.get_channel() as usize) {
+                *count += 1;
+            }
+        }
+        core::array::from_fn(|index| {
+            let state = &self.channels[index];
+            ChannelTelemetry {
+                channel: index as u8,
+                active_voices: voice_counts[index],
+                program: state.program,
+                volume: state.volume,
+                pan: state.pan,
+                expression: state.expression,
+                sustain: state.sustain,
+            }
+        })
+    }
+    
+    /// Get per-channel state (bank select, program, drum flag, sound controllers)
+    pub fn get_channel_state(&self, channel: u8) -> Option<&ChannelState> {
+        self.channels.get(channel as usize)
+    }
+    
+    /// Full controller snapshot of a MIDI channel (None outside 0-15)
+    pub fn channel_state_report(&self, channel: u8) -> Option<ChannelStateReport> {
+        let state = self.channels.get(channel as usize)?;
+        let index = channel as usize;
+        Some(state.report(channel, self.midi_effects.reverb_send_levels[index], self.midi_effects.chorus_send_levels[index]))
+    }
+    
     /// Enable round-robin sample selection for variation
     pub fn enable_round_robin(&mut self) {
         self.enable_round_robin = true;
@@ -240,6 +1380,42 @@ impl VoiceManager {
         self.chorus_bus.set_return_level(return_level);
     }
     
+    /// Clear the reverb and chorus delay lines so earlier tails stop at once
+    /// Sends, levels and room settings are untouched; no allocation (audio-thread safe)
+    pub fn flush_effects(&mut self) {
+        self.reverb_bus.flush();
+        self.chorus_bus.flush();
+    }
+    
+    /// Flush the tails and rewind the chorus LFO: a render from here repeats sample for
+    /// sample (A/B comparison passes)
+    pub fn restart_effects(&mut self) {
+        self.reverb_bus.flush();
+        self.chorus_bus.restart();
+    }
+    
+    /// Freeze (true) or resume the reverb and chorus tails
+    /// While frozen the buses are neither fed nor processed: their wet output is
+    /// silent and the tail picks up where it left off on resume
+    pub fn set_effects_frozen(&mut self, frozen: bool) {
+        self.effects_frozen = frozen;
+    }
+    
+    pub fn is_effects_frozen(&self) -> bool {
+        self.effects_frozen
+    }
+    
+    /// Transport stop: fade every channel's voices out (All Sound Off) and flush the effects,
+    /// then flush once more when the last voice has ended so nothing it sent lingers
+    pub fn stop_all_sound(&mut self) {
+        for channel in 0..16 {
+            self.all_sound_off(channel);
+        }
+        self.effects_frozen = false;
+        self.flush_effects();
+        self.flush_effects_when_silent = true;
+    }
+    
     /// Process MIDI Control Change message for effects
     /// 
     /// # Arguments
@@ -261,17 +1437,66 @@ impl VoiceManager {
     }
     
     /// Update effects buses with current MIDI send levels
+    /// The buses follow the smoothed levels from process()
     fn update_effects_from_midi(&mut self) {
-        // Update reverb bus channel send levels from MIDI controller
-        for channel in 0..16 {
-            let reverb_send = self.midi_effects.get_reverb_send(channel as u8);
-            let chorus_send = self.midi_effects.get_chorus_send(channel as u8);
-            
-            self.reverb_bus.set_channel_send(channel as u8, reverb_send);
-            self.chorus_bus.set_channel_send(channel as u8, chorus_send);
+        for (channel, smoothing) in self.channel_smoothing.iter_mut().enumerate() {
+            smoothing.reverb_send.set_target(self.midi_effects.get_reverb_send(channel as u8));
+            smoothing.chorus_send.set_target(self.midi_effects.get_chorus_send(channel as u8));
+        }
+    }
+    
+    /// Set a global reverb or chorus parameter (clamped to its range; no allocation)
+    pub fn set_effects_parameter(&mut self, parameter: EffectsParameter, value: f32) {
+        let reverb = &mut self.reverb_bus.reverb_processor;
+        let chorus = &mut self.chorus_bus.chorus_processor;
+        match parameter {
+            EffectsParameter::ReverbRoomSize => reverb.set_room_size(value),
+            EffectsParameter::ReverbDamping => reverb.set_damping(value),
+            EffectsParameter::ReverbDiffusion => reverb.set_diffusion(value),
+            EffectsParameter::ReverbReturn => self.reverb_bus.set_return_level(value),
+            EffectsParameter::ChorusRate => chorus.set_rate(value),
+            EffectsParameter::ChorusDepth => chorus.set_depth(value),
+            EffectsParameter::ChorusFeedback => chorus.set_feedback(value),
+            EffectsParameter::ChorusSpread => chorus.set_stereo_spread(value),
+            EffectsParameter::ChorusReturn => self.chorus_bus.set_return_level(value),
+        }
+    }
+    
+    /// Current value of a global reverb or chorus parameter
+    pub fn effects_parameter(&self, parameter: EffectsParameter) -> f32 {
+        let reverb = &self.reverb_bus.reverb_processor;
+        let chorus = &self.chorus_bus.chorus_processor;
+        match parameter {
+            EffectsParameter::ReverbRoomSize => reverb.room_size,
+            EffectsParameter::ReverbDamping => reverb.damping,
+            EffectsParameter::ReverbDiffusion => reverb.diffusion,
+            EffectsParameter::ReverbReturn => self.reverb_bus.return_level,
+            EffectsParameter::ChorusRate => chorus.rate,
+            EffectsParameter::ChorusDepth => chorus.depth,
+            EffectsParameter::ChorusFeedback => chorus.feedback,
+            EffectsParameter::ChorusSpread => chorus.stereo_spread,
+            EffectsParameter::ChorusReturn => self.chorus_bus.return_level,
         }
     }
     
+    /// Controller to effect parameter map (see midi::effects_cc_map)
+    pub fn effects_cc_map(&self) -> &EffectsCcMap {
+        &self.effects_cc_map
+    }
+    
+    pub fn effects_cc_map_mut(&mut self) -> &mut EffectsCcMap {
+        &mut self.effects_cc_map
+    }
+    
+    /// Apply a controller through the effects map; false if it is not mapped
+    pub fn apply_effects_controller(&mut self, controller: u8, value: u8) -> bool {
+        let Some(mapping) = self.effects_cc_map.get(controller) else {
+            return false;
+        };
+        self.set_effects_parameter(mapping.parameter, mapping.value(value));
+        true
+    }
+    
     /// Set MIDI effects logging enable/disable
     pub fn set_midi_effects_logging(&mut self, enable: bool) {
         self.midi_effects.set_effects_logging(enable);
@@ -603,30 +1828,9 @@ impl VoiceManager {
     /// 
     /// Uses EMU8000-style crossfading:
     /// - Full weight in the center of the range
-    /// - Linear falloff at the edges for crossfading
+    /// - Falloff at the edges shaped by the configured crossfade curve
     fn calculate_range_weight(&self, velocity: u8, range_low: u8, range_high: u8) -> f32 {
-        if velocity < range_low || velocity > range_high {
-            return 0.0; // Outside range
-        }
-        
-        let range_size = range_high - range_low;
-        if range_size <= 4 {
-            return 1.0; // Small range, no crossfading
-        }
-        
-        let crossfade_size = (range_size / 4).max(1); // 25% of range for crossfading
-        let velocity_pos = velocity - range_low;
-        
-        if velocity_pos < crossfade_size {
-            // Fade in at start of range
-            velocity_pos as f32 / crossfade_size as f32
-        } else if velocity_pos > range_size - crossfade_size {
-            // Fade out at end of range
-            (range_size - velocity_pos) as f32 / crossfade_size as f32
-        } else {
-            // Full weight in middle of range
-            1.0
-        }
+        self.velocity_crossfade.weight(velocity, range_low, range_high)
     }
     
     /// Get count of matching zones for analysis
@@ -691,34 +1895,83 @@ impl VoiceManager {
     
     pub fn note_on(&mut self, note: u8, velocity: u8, channel: u8) -> Option<usize> {
         // Phase 20.4.1: Use only MultiZoneSampleVoice system
-        self.note_on_multi_zone(note, velocity, channel)
+        self.note_on_multi_zone(note, velocity, None, channel)
+    }
+    
+    /// Note On with 14-bit velocity (Note On velocity MSB + CC88 high-resolution LSB)
+    pub fn note_on_high_res(&mut self, note: u8, velocity_14bit: u16, channel: u8) -> Option<usize> {
+        let velocity_14bit = velocity_14bit.min(0x3FFF);
+        self.note_on_multi_zone(note, (velocity_14bit >> 7) as u8, Some(velocity_14bit), channel)
     }
     
     /// EMU8000 Multi-Zone note triggering (Phase 20.4.1 - single voice system)
-    fn note_on_multi_zone(&mut self, note: u8, velocity: u8, channel: u8) -> Option<usize> {
-        // Check if SoundFont and preset are available
-        let soundfont = match &self.loaded_soundfont {
-            Some(sf) => sf,
-            None => {
-                log(&format!("No SoundFont loaded for note {} velocity {}", note, velocity));
-                return None;
+    fn note_on_multi_zone(&mut self, note: u8, velocity: u8, velocity_14bit: Option<u16>, channel: u8) -> Option<usize> {
+        self.flush_effects_when_silent = false; // A new note's tail is not the stopped song's
+        // Velocity remapping comes first so zone selection sees the remapped value
+        let velocity_map = self.velocity_maps.get(channel as usize).copied().unwrap_or_default();
+        let (velocity, velocity_14bit) = match velocity_14bit {
+            Some(velocity_14bit) => {
+                let velocity_14bit = velocity_map.apply_14bit(velocity_14bit);
+                ((velocity_14bit >> 7) as u8, Some(velocity_14bit))
             }
+            None => (velocity_map.apply(velocity), None),
         };
-        
-        let preset_index = match self.current_preset {
-            Some(idx) => idx,
-            None => {
-                log(&format!("No preset selected for note {} velocity {}", note, velocity));
-                return None;
-            }
+        // Without a SoundFont, play the procedural fallback bank (GM program per channel)
+        let channel_state = self.channels.get(channel as usize).copied();
+        let preset_index = match (&self.loaded_soundfont, &self.fallback_bank) {
+            // Channel's own program (set by Program Change) takes priority over the global preset
+            (Some(_), _) => channel_state.and_then(|state| state.preset_index).or(self.current_preset)?,
+            (None, Some(_)) => match channel_state {
+                Some(state) if state.is_drum => FALLBACK_DRUM_PRESET_INDEX,
+                Some(state) => state.program as usize,
+                None => 0,
+            },
+            (None, None) => return None, // No SoundFont loaded
         };
-        
-        let preset = &soundfont.presets[preset_index];
+        self.start_voice(note, velocity, velocity_14bit, channel, preset_index, true)
+    }
+    
+    /// Why a Note On on `channel` started no voice (note_on returned None)
+    pub fn note_drop_reason(&self, channel: u8) -> DropReason {
+        match (&self.loaded_soundfont, &self.fallback_bank) {
+            (Some(soundfont), _) => {
+                let preset_index = if channel == AUDITION_CHANNEL {
+                    self.resolve_preset(self.preview.bank, self.preview.program)
+                } else {
+                    self.channels.get(channel as usize)
+                        .and_then(|state| state.preset_index)
+                        .or(self.current_preset)
+                };
+                match preset_index {
+                    Some(index) if index < soundfont.presets.len() => DropReason::NoVoice,
+                    _ => DropReason::NoPreset,
+                }
+            }
+            (None, Some(_)) => DropReason::NoVoice,
+            (None, None) => DropReason::NoSoundFont,
+        }
+    }
+    
+    /// Start a note of `preset_index` on a free voice
+    /// With `allow_steal` false the note is dropped instead of cutting off a sounding voice
+    fn start_voice(
+        &mut self,
+        note: u8,
+        velocity: u8,
+        velocity_14bit: Option<u16>,
+        channel: u8,
+        preset_index: usize,
+        allow_steal: bool,
+    ) -> Option<usize> {
+        let channel_state = self.channels.get(channel as usize).copied();
+        let soundfont = self.loaded_soundfont.as_ref().or(self.fallback_bank.as_ref())?;
+        let preset = soundfont.presets.get(preset_index)?;
+        let generators = self.preset_generators.get(preset_index)?;
         
         // Find an available voice
         let available_voice_index = {
             let mut found_index = None;
-            for (i, voice) in self.voices.iter().enumerate() {
+            for (i, voice) in self.voices[..self.voice_limit].iter().enumerate() {
                 if !voice.is_active() {
                     found_index = Some(i);
                     break;
@@ -729,11 +1982,15 @@ impl VoiceManager {
         
         let voice_index = match available_voice_index {
             Some(index) => index,
+            None if !allow_steal => {
+                self.voice_stats.note_dropped();
+                return None;
+            }
             None => {
                 // Voice stealing: find oldest releasing voice or lowest priority
                 let mut best_candidate: Option<(usize, f32)> = None;
                 
-                for (i, voice) in self.voices.iter().enumerate() {
+                for (i, voice) in self.voices[..self.voice_limit].iter().enumerate() {
                     let priority = voice.get_steal_priority();
                     
                     match best_candidate {
@@ -747,34 +2004,58 @@ impl VoiceManager {
                 }
                 
                 match best_candidate {
-                    Some((voice_index, _)) => {
-                        log(&format!("Voice {} selected for stealing", voice_index));
-                        voice_index
-                    },
-                    None => {
-                        log(&format!("No available voices for note {} velocity {} (all 32 voices busy)", note, velocity));
-                        return None;
-                    }
+                    Some((voice_index, _)) => voice_index,
+                    None => return None,
                 }
             }
         };
         
         // Prepare voice for stealing if it was not available
         if available_voice_index.is_none() {
+            self.voice_stats.voice_stolen(self.voices[voice_index].get_age_seconds());
             self.voices[voice_index].prepare_for_steal();
         }
         
         // Start the note on the selected voice
-        match self.voices[voice_index].start_note(note, velocity, channel, soundfont, preset) {
+        if let Some(state) = channel_state {
+            self.voices[voice_index].set_playback_direction(state.playback_direction);
+        }
+        self.voices[voice_index].set_filter_envelope(channel_state.and_then(|state| state.filter_envelope));
+        let host_vibrato = self.mod_wheel_vibrato.get(channel as usize).copied().flatten();
+        self.voices[voice_index].set_mod_wheel_vibrato(host_vibrato.or(generators.mod_wheel_vibrato));
+        let velocity_14bit = velocity_14bit.unwrap_or((velocity as u16) << 7);
+        let start_result = self.voices[voice_index]
+            .start_note_with_generators(note, velocity_14bit, channel, soundfont, preset, generators);
+        match start_result {
             Ok(_) => {
-                log(&format!("MultiZoneSampleVoice triggered: Note {} Vel {} Ch {} -> Voice {}",
-                           note, velocity, channel, voice_index));
+                self.voice_stats.note_started();
+                // RPN 1 fine tuning rides on the key tuning (read at note-on, like the table)
+                let fine_tuning = channel_state.map_or(0.0, |state| state.fine_tuning_cents());
+                self.voices[voice_index].set_key_tuning(self.key_tuning.cents(channel, note) + fine_tuning);
+                if let Some(balance) = self.preset_balance.as_ref().filter(|_| self.preset_balance_enabled) {
+                    self.voices[voice_index].set_level_trim(balance.trim_db(preset_index));
+                }
+                if let Some(sends) = self.preset_sends.get(&preset_index) {
+                    self.voices[voice_index].set_reverb_send(sends.reverb);
+                    self.voices[voice_index].set_chorus_send(sends.chorus);
+                }
+                let matrix = &self.modulation_matrix;
+                self.voices[voice_index].set_modulation_routes(matrix.global_routes(), matrix.channel_routes(channel));
+                if let Some(state) = channel_state {
+                    self.voices[voice_index].set_modulation_source(ModulationSource::ModWheel, state.modulation as f32 / 127.0);
+                    self.voices[voice_index].set_modulation_source(ModulationSource::Aftertouch, state.pressure as f32 / 127.0);
+                    self.voices[voice_index].apply_sound_controllers(&state.sound_controllers);
+                    if let Some(offset) = self.next_start_offset(state.start_offset_window_ms) {
+                        self.voices[voice_index].apply_start_offset(offset);
+                    }
+                }
+                if self.lfo_phase_mode == LfoPhaseMode::Random {
+                    let (lfo1, lfo2) = (self.next_lfo_phase(), self.next_lfo_phase());
+                    self.voices[voice_index].offset_lfo_phases(lfo1, lfo2);
+                }
                 Some(voice_index)
             },
-            Err(e) => {
-                log(&format!("Failed to start note {} velocity {} on voice {}: {}", note, velocity, voice_index, e));
-                None
-            }
+            Err(_) => None,
         }
     }
     
@@ -782,31 +2063,128 @@ impl VoiceManager {
     
     
     pub fn note_off(&mut self, note: u8) {
-        let mut released_count = 0;
-        
-        // Release matching voices
+        // Release matching voices on the MIDI channels (audition notes end on their
+        // own timer, performance zones through performance_note_off); a held sustain
+        // pedal keeps them sounding until it lifts
+        let channels = &self.channels;
+        for voice in self.voices.iter_mut() {
+            if voice.is_active() && voice.get_note() == note && voice.get_channel() < AUDITION_CHANNEL {
+                if channels[voice.get_channel() as usize].sustain {
+                    voice.hold_for_pedal();
+                } else {
+                    voice.stop_note();
+                }
+            }
+        }
+    }
+    
+    /// All Notes Off (CC123): release every note on a channel, as note offs do
+    /// (notes stay under a held sustain pedal)
+    pub fn all_notes_off(&mut self, channel: u8) {
+        let Some(sustain) = self.channels.get(channel as usize).map(|state| state.sustain) else {
+            return;
+        };
+        for voice in self.voices.iter_mut() {
+            if voice.is_active() && voice.get_channel() == channel {
+                if sustain {
+                    voice.hold_for_pedal();
+                } else {
+                    voice.stop_note();
+                }
+            }
+        }
+    }
+    
+    /// Release the notes the sustain pedal was holding on a channel
+    fn release_pedal_held(&mut self, channel: u8) {
         for voice in self.voices.iter_mut() {
-            if voice.is_active() && voice.get_note() == note {
+            if voice.get_channel() == channel && voice.is_pedal_held() {
                 voice.stop_note();
-                released_count += 1;
             }
         }
-        
-        if released_count > 0 {
-            log(&format!("Note {} released on {} voice(s)", note, released_count));
+    }
+    
+    /// Reset All Controllers (CC121): mod wheel, expression and sustain go back to their
+    /// defaults (the controllers chase resets too); lifting the pedal releases held notes
+    pub fn reset_controllers(&mut self, channel: u8) {
+        if channel as usize >= self.channels.len() {
+            return;
+        }
+        self.set_modulation_wheel(channel, 0);
+        self.set_mixer_controller(channel, MIDI_CC_EXPRESSION, 127);
+        self.set_mixer_controller(channel, MIDI_CC_SUSTAIN, 0);
+    }
+    
+    /// All Sound Off (CC120): silence a channel's voices, including release tails
+    /// Voices fade over the minimum release time rather than stopping on a click
+    pub fn all_sound_off(&mut self, channel: u8) {
+        for voice in self.voices.iter_mut() {
+            if voice.is_active() && voice.get_channel() == channel {
+                voice.fade_out();
+            }
         }
     }
     
     /// Process all active voices and return mixed stereo audio sample
     /// This is the main audio processing method - call once per sample
     pub fn process(&mut self) -> (f32, f32) {
+        self.advance_audition();
+        if self.watchdog.tick() {
+            self.release_hung_notes();
+        }
         let mut dry_left = 0.0;
         let mut dry_right = 0.0;
         
         // Process all MultiZoneSampleVoices with modern 32-bit float precision
-        for voice in self.voices.iter_mut() {
+        let solo = self.debug_solo;
+        let hardware = self.synthesis_mode.is_hardware();
+        
+        // Advance controller glides; only moving channels touch voices and buses
+        let mut gliding = [false; 16];
+        let mut sounding = 0;
+        for (channel, smoothing) in self.channel_smoothing.iter_mut().enumerate() {
+            if smoothing.is_settled() {
+                continue;
+            }
+            smoothing.tick(self.smoothing_coefficient);
+            self.reverb_bus.set_channel_send(channel as u8, smoothing.reverb_send.current());
+            self.chorus_bus.set_channel_send(channel as u8, smoothing.chorus_send.current());
+            gliding[channel] = true;
+        }
+        for (channel, changed) in self.automation_changed.iter_mut().enumerate() {
+            gliding[channel] |= std::mem::take(changed);
+        }
+        
+        for (index, voice) in self.voices.iter_mut().enumerate() {
             if voice.is_active() {
-                let (left, right) = voice.process();
+                // Audition and performance notes play outside the 16 MIDI channels
+                let smoothing = self.channel_smoothing.get(voice.get_channel() as usize);
+                let automation = self.channel_automation.get(voice.get_channel() as usize).copied().unwrap_or(ChannelAutomation::NEUTRAL);
+                // A cutoff lane also reaches notes started since it last moved
+                if let Some(smoothing) = smoothing.filter(|_| gliding[voice.get_channel() as usize] || automation.brightness != 1.0) {
+                    voice.set_brightness_scale(smoothing.brightness.current() * automation.brightness);
+                }
+                let (mut left, mut right) = voice.process();
+                if voice.is_active() {
+                    sounding += 1;
+                } else {
+                    self.voice_stats.voice_finished(voice.get_age_seconds());
+                }
+                if hardware {
+                    // Each voice leaves the EMU8000 as 16-bit data
+                    (left, right) = (quantize_16bit(left), quantize_16bit(right));
+                }
+                if !solo.passes(index, voice.get_channel()) {
+                    continue;
+                }
+                if let Some(smoothing) = smoothing {
+                    let (gain_left, gain_right) = smoothing.stereo_gains(automation.gain, automation.pan);
+                    (left, right) = (left * gain_left, right * gain_right);
+                } else if voice.get_channel() == AUDITION_CHANNEL {
+                    (left, right) = (left * self.preview.volume, right * self.preview.volume);
+                } else if let Some(volume) = self.performance.channel_volume(voice.get_channel()) {
+                    (left, right) = (left * volume, right * volume);
+                }
                 // Apply modern voice gain - EMU8000 was limited by 16-bit integer math
                 let voice_gain = 2.2;  // 220% voice gain for optimal 32-bit headroom
                 dry_left += left * voice_gain;
@@ -817,14 +2195,39 @@ impl VoiceManager {
                 let channel = voice.get_channel();
                 // Use stereo RMS for proper effects send level (better than L+R sum)
                 let stereo_rms = ((left * left + right * right) * 0.5).sqrt() * voice_gain;
-                self.reverb_bus.add_voice_send(stereo_rms, reverb_send, channel);
-                self.chorus_bus.add_voice_send(stereo_rms, chorus_send, channel);
+                if self.effects_frozen {
+                    continue;
+                }
+                if self.effects_quality != EffectsQuality::Off {
+                    self.reverb_bus.add_voice_send(stereo_rms, reverb_send, channel);
+                }
+                if self.effects_quality == EffectsQuality::Full {
+                    self.chorus_bus.add_voice_send(stereo_rms, chorus_send, channel);
+                }
+            }
+        }
+        
+        self.voice_stats.sample(sounding);
+        
+        // Raw sample preview joins the dry mix (centered, no effects sends)
+        if let Some(preview) = self.sample_preview.as_mut() {
+            let sample = preview.next_sample();
+            dry_left += sample;
+            dry_right += sample;
+            if preview.is_finished() {
+                self.sample_preview = None;
             }
         }
         
+        if self.flush_effects_when_silent && sounding == 0 {
+            self.flush_effects();
+            self.flush_effects_when_silent = false;
+        }
+        
         // Process global effects and get wet signals
-        let reverb_wet = self.reverb_bus.process_reverb();
-        let chorus_wet = self.chorus_bus.process_chorus();
+        let effects = if self.effects_frozen { EffectsQuality::Off } else { self.effects_quality };
+        let reverb_wet = if effects != EffectsQuality::Off { self.reverb_bus.process_reverb() } else { 0.0 };
+        let chorus_wet = if effects == EffectsQuality::Full { self.chorus_bus.process_chorus() } else { 0.0 };
         
         // Mix dry and wet signals (Modern 32-bit style)
         let dry_level = 0.9; // 90% dry signal - 32-bit precision allows higher levels  
@@ -836,6 +2239,51 @@ impl VoiceManager {
         (final_left, final_right)
     }
     
+    /// Voice lifetimes, steals and polyphony since the last reset
+    pub fn voice_stats(&self) -> VoiceStatsReport {
+        self.voice_stats.report()
+    }
+    
+    pub fn reset_voice_stats(&mut self) {
+        self.voice_stats.reset();
+    }
+    
+    /// Hanging-note watchdog: enable and set how long a note may be held (seconds)
+    pub fn set_note_watchdog(&mut self, enabled: bool, timeout_seconds: f32) {
+        self.watchdog.configure(enabled, timeout_seconds);
+    }
+    
+    pub fn note_watchdog(&self) -> &NoteWatchdog {
+        &self.watchdog
+    }
+    
+    /// Notes the watchdog released since the last call
+    pub fn take_hung_notes(&mut self) -> Vec<HungNote> {
+        if !self.watchdog.has_pending() {
+            return Vec::new();
+        }
+        self.watchdog.take_pending()
+    }
+    
+    /// Release voices held (not yet released) past the watchdog timeout
+    fn release_hung_notes(&mut self) {
+        let timeout = self.watchdog.timeout_seconds();
+        for (index, voice) in self.voices.iter_mut().enumerate() {
+            // Audition notes end on their own timer
+            if !voice.is_active() || voice.is_releasing() || voice.get_channel() == AUDITION_CHANNEL
+                || voice.get_age_seconds() < timeout {
+                continue;
+            }
+            voice.stop_note();
+            self.watchdog.record(HungNote {
+                voice: index,
+                channel: voice.get_channel(),
+                note: voice.get_note(),
+                held_seconds: voice.get_age_seconds(),
+            });
+        }
+    }
+    
     /// Process envelopes for all processing voices (call once per audio sample)
     /// Returns the number of voices that are still generating audio  
     pub fn process_envelopes(&mut self) -> u32 {
@@ -863,8 +2311,9 @@ impl VoiceManager {
     /// * `channel` - MIDI channel (0-15)
     /// * `bend_value` - Pitch bend value in semitones (-2.0 to +2.0 for EMU8000)
     pub fn apply_pitch_bend(&mut self, channel: u8, bend_value: f32) {
-        log(&format!("Applying pitch bend: channel={} bend={:.2} semitones", channel, bend_value));
-        
+        if let Some(state) = self.channels.get_mut(channel as usize) {
+            state.pitch_bend = bend_value;
+        }
         // Apply to all active voices on the specified channel
         for voice in self.voices.iter_mut() {
             if voice.is_active() && voice.get_channel() == channel {
@@ -891,4 +2340,12 @@ impl VoiceManager {
         }
     }
     
-}
\ No newline at end of file
+}
+
+/// One xorshift32 step (state must be non-zero)
+fn xorshift32(state: &mut u32) -> u32 {
+    *state ^= *state << 13;
+    *state ^= *state >> 17;
+    *state ^= *state << 5;
+    *state
+}
@@ -0,0 +1,153 @@
+/**
+ * AWE Player - Voice Aging Statistics
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Choosing a polyphony limit (or judging whether a SoundFont's release
+ * times are sensible) needs more than the current voice count. The voice
+ * pool keeps running statistics instead: per note, how long each voice
+ * lived from note-on until it went idle or was stolen, in a fixed-bucket
+ * histogram; per sample, how many voices were sounding, from which the
+ * average, peak and 95th-percentile polyphony follow. Steals and notes
+ * dropped for lack of a free voice are counted separately. Everything is
+ * fixed-size and updated on the audio thread; the report is built on
+ * request.
+ */
+
+use serde::Serialize;
+use super::voice_manager::MAX_VOICES;
+
+/// Upper bounds (seconds) of the lifetime histogram buckets; a last bucket holds the rest
+pub const LIFETIME_BUCKET_SECONDS: [f32; 7] = [0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0];
+
+const BUCKETS: usize = LIFETIME_BUCKET_SECONDS.len() + 1;
+
+/// Voices that ended after at most `up_to_seconds` (None = longer than the last bound)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LifetimeBucket {
+    pub up_to_seconds: Option<f32>,
+    pub count: u64,
+}
+
+/// Voice statistics since the last reset (get_voice_stats_global)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct VoiceStatsReport {
+    pub elapsed_seconds: f64,
+    pub notes_started: u64,
+    pub notes_finished: u64,        // Voices that ran to idle
+    pub steals: u64,                // Voices cut off for a new note
+    pub dropped_notes: u64,         // Notes that found no voice and could not steal
+    pub mean_lifetime_seconds: f32, // Finished and stolen voices
+    pub longest_lifetime_seconds: f32,
+    pub lifetime_histogram: Vec<LifetimeBucket>,
+    pub average_polyphony: f32,     // Time-weighted sounding voices
+    pub peak_polyphony: usize,
+    pub polyphony_95th: usize,      // Voice count not exceeded 95% of the time
+}
+
+/// Running voice pool statistics
+#[derive(Debug, Clone)]
+pub struct VoiceStats {
+    sample_rate: f32,
+    notes_started: u64,
+    notes_finished: u64,
+    steals: u64,
+    dropped_notes: u64,
+    lifetime_total: f64,
+    longest_lifetime: f32,
+    lifetime_counts: [u64; BUCKETS],
+    polyphony_samples: [u64; MAX_VOICES + 1], // Samples spent at each voice count
+}
+
+impl VoiceStats {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate,
+            notes_started: 0,
+            notes_finished: 0,
+            steals: 0,
+            dropped_notes: 0,
+            lifetime_total: 0.0,
+            longest_lifetime: 0.0,
+            lifetime_counts: [0; BUCKETS],
+            polyphony_samples: [0; MAX_VOICES + 1],
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.sample_rate);
+    }
+
+    pub fn note_started(&mut self) {
+        self.notes_started += 1;
+    }
+
+    pub fn note_dropped(&mut self) {
+        self.dropped_notes += 1;
+    }
+
+    /// A voice went idle after `age_seconds`
+    pub fn voice_finished(&mut self, age_seconds: f32) {
+        self.notes_finished += 1;
+        self.record_lifetime(age_seconds);
+    }
+
+    /// A voice was stolen `age_seconds` after its note-on
+    pub fn voice_stolen(&mut self, age_seconds: f32) {
+        self.steals += 1;
+        self.record_lifetime(age_seconds);
+    }
+
+    /// Count one output sample with `active_voices` sounding
+    pub fn sample(&mut self, active_voices: usize) {
+        self.polyphony_samples[active_voices.min(MAX_VOICES)] += 1;
+    }
+
+    fn record_lifetime(&mut self, age_seconds: f32) {
+        let bucket = LIFETIME_BUCKET_SECONDS.iter()
+            .position(|&bound| age_seconds <= bound)
+            .unwrap_or(BUCKETS - 1);
+        self.lifetime_counts[bucket] += 1;
+        self.lifetime_total += age_seconds as f64;
+        self.longest_lifetime = self.longest_lifetime.max(age_seconds);
+    }
+
+    pub fn report(&self) -> VoiceStatsReport {
+        let samples: u64 = self.polyphony_samples.iter().sum();
+        let voice_samples: u64 = self.polyphony_samples.iter().enumerate()
+            .map(|(voices, &count)| voices as u64 * count)
+            .sum();
+        let ended = self.notes_finished + self.steals;
+        VoiceStatsReport {
+            elapsed_seconds: samples as f64 / self.sample_rate as f64,
+            notes_started: self.notes_started,
+            notes_finished: self.notes_finished,
+            steals: self.steals,
+            dropped_notes: self.dropped_notes,
+            mean_lifetime_seconds: if ended > 0 { (self.lifetime_total / ended as f64) as f32 } else { 0.0 },
+            longest_lifetime_seconds: self.longest_lifetime,
+            lifetime_histogram: self.lifetime_counts.iter().enumerate()
+                .map(|(index, &count)| LifetimeBucket { up_to_seconds: LIFETIME_BUCKET_SECONDS.get(index).copied(), count })
+                .collect(),
+            average_polyphony: if samples > 0 { (voice_samples as f64 / samples as f64) as f32 } else { 0.0 },
+            peak_polyphony: self.polyphony_samples.iter().rposition(|&count| count > 0).unwrap_or(0),
+            polyphony_95th: self.polyphony_percentile(samples, 0.95),
+        }
+    }
+
+    /// Smallest voice count covering `fraction` of the counted samples
+    fn polyphony_percentile(&self, samples: u64, fraction: f64) -> usize {
+        let threshold = (samples as f64 * fraction).ceil() as u64;
+        let mut covered = 0;
+        for (voices, &count) in self.polyphony_samples.iter().enumerate() {
+            covered += count;
+            if covered >= threshold {
+                return voices;
+            }
+        }
+        0
+    }
+}
@@ -0,0 +1,132 @@
+/**
+ * AWE Player - Hanging-Note Watchdog
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * A dropped Note Off (lost WebMIDI message, unplugged controller, queue
+ * overflow) leaves a looping voice in sustain forever. With the watchdog
+ * on, any voice still held after a configurable time is force-released
+ * through its normal release envelope and reported to the host. Voices
+ * that decay or end on their own never trip it; legitimately long held
+ * notes (organ pedals, drones) need a timeout longer than the longest
+ * expected note.
+ *
+ * Detection runs on the audio thread and only records what it released;
+ * the render exports hand the records to the listener after the block.
+ */
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use serde::Serialize;
+
+/// Default hold time before a note counts as hanging
+pub const DEFAULT_WATCHDOG_SECONDS: f32 = 30.0;
+/// Shortest accepted hold time
+pub const MIN_WATCHDOG_SECONDS: f32 = 1.0;
+
+/// Samples between scans of the voice pool
+const CHECK_INTERVAL: u32 = 1024;
+/// Releases kept until the host collects them (later ones are released but not reported)
+const MAX_PENDING: usize = 16;
+
+/// A voice the watchdog released (JSON for the watchdog callback)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct HungNote {
+    pub voice: usize,
+    pub channel: u8,
+    pub note: u8,
+    pub held_seconds: f32,
+}
+
+/// Watchdog settings and releases awaiting delivery
+#[derive(Debug, Clone)]
+pub struct NoteWatchdog {
+    enabled: bool,
+    timeout_seconds: f32,
+    countdown: u32,
+    pending: [Option<HungNote>; MAX_PENDING],
+}
+
+impl NoteWatchdog {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            timeout_seconds: DEFAULT_WATCHDOG_SECONDS,
+            countdown: CHECK_INTERVAL,
+            pending: [None; MAX_PENDING],
+        }
+    }
+
+    /// Turn the watchdog on or off; a non-finite timeout keeps the current one
+    pub fn configure(&mut self, enabled: bool, timeout_seconds: f32) {
+        self.enabled = enabled;
+        if timeout_seconds.is_finite() {
+            self.timeout_seconds = timeout_seconds.max(MIN_WATCHDOG_SECONDS);
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn timeout_seconds(&self) -> f32 {
+        self.timeout_seconds
+    }
+
+    /// Advance one sample; true when the voice pool is due for a scan
+    pub fn tick(&mut self) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        self.countdown -= 1;
+        if self.countdown > 0 {
+            return false;
+        }
+        self.countdown = CHECK_INTERVAL;
+        true
+    }
+
+    /// Remember a release for the host (allocation-free)
+    pub fn record(&mut self, note: HungNote) {
+        if let Some(slot) = self.pending.iter_mut().find(|slot| slot.is_none()) {
+            *slot = Some(note);
+        }
+    }
+
+    pub fn has_pending(&self) -> bool {
+        self.pending[0].is_some()
+    }
+
+    /// Releases since the last call, in the order they happened
+    pub fn take_pending(&mut self) -> Vec<HungNote> {
+        self.pending.iter_mut().filter_map(Option::take).collect()
+    }
+}
+
+impl Default for NoteWatchdog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub type WatchdogListener = Rc<dyn Fn(&HungNote)>;
+
+thread_local! {
+    static LISTENER: RefCell<Option<WatchdogListener>> = const { RefCell::new(None) };
+}
+
+/// Install (or with None, remove) the watchdog listener
+pub fn set_listener(listener: Option<WatchdogListener>) {
+    LISTENER.with(|slot| *slot.borrow_mut() = listener);
+}
+
+/// Deliver releases to the listener, if one is registered
+pub fn emit(notes: &[HungNote]) {
+    let listener = LISTENER.with(|slot| slot.borrow().clone());
+    if let Some(listener) = listener {
+        for note in notes {
+            listener(note);
+        }
+    }
+}
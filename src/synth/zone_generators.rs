@@ -0,0 +1,196 @@
+/**
+ * AWE Player - Resolved Zone Generators
+ * Part of AWE Player EMU8000 Emulator
+ *
+ * Note-on used to walk every preset zone and every linked instrument zone
+ * once per generator group, on the audio thread, for every note. Those
+ * values only depend on the preset, so every preset of a bank is resolved
+ * once when the bank is installed (Program Change itself arrives on the
+ * audio thread, which must not allocate) and the voice reads them from here.
+ *
+ * Generators are resolved per (preset zone, instrument zone) pair, as SF2
+ * prescribes: the instrument zone's value (else its instrument's global
+ * zone, else the default) plus the preset zone's offset (else the preset's
+ * global zone, else nothing). A note reads the pairs it selects. Mod wheel
+ * vibrato modulators are read from the first preset zone's instrument, with
+ * the preset's amounts added as SF2 prescribes for modulators.
+ */
+
+use crate::soundfont::catalog::zone_generator;
+use crate::soundfont::types::{Generator, GeneratorType, InstrumentZone, Modulator, PresetZone, SoundFont, SoundFontPreset};
+use super::mod_wheel::ModWheelVibrato;
+
+/// Samples per unit of the coarse address offset generators
+const COARSE_OFFSET_SAMPLES: i32 = 32768;
+/// SF2 ranges of the envelope and LFO times (timecents) and of attenuation/sustain (centibels)
+const TIMECENTS: (i32, i32) = (-12000, 8000);
+const DELAY_TIMECENTS: (i32, i32) = (-12000, 5000);
+const CENTIBELS: (i32, i32) = (0, 1440);
+/// Effects sends, 0.1% units
+const PERMILLE: (i32, i32) = (0, 1000);
+
+/// Generators resolved for one (preset zone, instrument zone) pair
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZoneGenerators {
+    // Volume envelope (33-38), timecents / centibels
+    pub delay_vol_env: i32,
+    pub attack_vol_env: i32,
+    pub hold_vol_env: i32,
+    pub decay_vol_env: i32,
+    pub sustain_vol_env: i32,
+    pub release_vol_env: i32,
+    // Level and tuning (48, 51, 52)
+    pub initial_attenuation: i32,   // Centibels
+    pub coarse_tune: i32,           // Semitones
+    pub fine_tune: i32,             // Cents
+    // LFO delays (21, 23), timecents
+    pub delay_mod_lfo: i32,
+    pub delay_vib_lfo: i32,
+    // Effects sends (91, 92), 0.1% units
+    pub reverb_send: i32,
+    pub chorus_send: i32,
+    // Loop offsets (2, 3, 45, 50)
+    pub loop_start_offset: i32,     // Samples added to the sample's loop start
+    pub loop_end_offset: i32,       // Samples added to the sample's loop end
+}
+
+impl Default for ZoneGenerators {
+    /// EMU8000 defaults: transparent envelope, no attenuation or detune, ~1ms LFO delays
+    fn default() -> Self {
+        Self {
+            delay_vol_env: -12000,      // 1ms delay
+            attack_vol_env: -12000,     // 1ms attack (immediate)
+            hold_vol_env: -12000,       // 1ms hold (minimal)
+            decay_vol_env: -12000,      // 1ms decay (minimal)
+            sustain_vol_env: 0,         // 0cb = 100% sustain
+            release_vol_env: -6000,     // 44ms release
+            initial_attenuation: 0,
+            coarse_tune: 0,
+            fine_tune: 0,
+            delay_mod_lfo: -12000,
+            delay_vib_lfo: -12000,
+            reverb_send: 0,
+            chorus_send: 0,
+            loop_start_offset: 0,
+            loop_end_offset: 0,
+        }
+    }
+}
+
+impl ZoneGenerators {
+    /// Resolve one pair; the globals are the instrument's and the preset's global zones
+    fn resolve(
+        instrument: &[Generator],
+        instrument_global: Option<&[Generator]>,
+        preset: &[Generator],
+        preset_global: Option<&[Generator]>,
+    ) -> Self {
+        let defaults = Self::default();
+        // Instrument value (or `default`) plus the preset's offset, within `range`
+        let value = |generator: GeneratorType, default: i32, range: (i32, i32)| {
+            let base = zone_value(instrument, instrument_global, &generator).unwrap_or(default);
+            let offset = zone_value(preset, preset_global, &generator).unwrap_or(0);
+            (base + offset).clamp(range.0, range.1)
+        };
+        let offsets = |fine: GeneratorType, coarse: GeneratorType| {
+            let samples = |generators: &[Generator], global: Option<&[Generator]>| {
+                zone_value(generators, global, &fine).unwrap_or(0)
+                    + zone_value(generators, global, &coarse).unwrap_or(0) * COARSE_OFFSET_SAMPLES
+            };
+            samples(instrument, instrument_global) + samples(preset, preset_global)
+        };
+        Self {
+            delay_vol_env: value(GeneratorType::DelayVolEnv, defaults.delay_vol_env, DELAY_TIMECENTS),
+            attack_vol_env: value(GeneratorType::AttackVolEnv, defaults.attack_vol_env, TIMECENTS),
+            hold_vol_env: value(GeneratorType::HoldVolEnv, defaults.hold_vol_env, DELAY_TIMECENTS),
+            decay_vol_env: value(GeneratorType::DecayVolEnv, defaults.decay_vol_env, TIMECENTS),
+            sustain_vol_env: value(GeneratorType::SustainVolEnv, defaults.sustain_vol_env, CENTIBELS),
+            release_vol_env: value(GeneratorType::ReleaseVolEnv, defaults.release_vol_env, TIMECENTS),
+            initial_attenuation: value(GeneratorType::InitialAttenuation, defaults.initial_attenuation, CENTIBELS),
+            coarse_tune: value(GeneratorType::CoarseTune, defaults.coarse_tune, (-120, 120)),
+            fine_tune: value(GeneratorType::FineTune, defaults.fine_tune, (-99, 99)),
+            delay_mod_lfo: value(GeneratorType::DelayModLfo, defaults.delay_mod_lfo, DELAY_TIMECENTS),
+            delay_vib_lfo: value(GeneratorType::DelayVibLfo, defaults.delay_vib_lfo, DELAY_TIMECENTS),
+            reverb_send: value(GeneratorType::ReverbEffectsSend, defaults.reverb_send, PERMILLE),
+            chorus_send: value(GeneratorType::ChorusEffectsSend, defaults.chorus_send, PERMILLE),
+            loop_start_offset: offsets(GeneratorType::StartloopAddrsOffset, GeneratorType::StartloopAddrsCoarseOffset),
+            loop_end_offset: offsets(GeneratorType::EndloopAddrsOffset, GeneratorType::EndloopAddrsCoarseOffset),
+        }
+    }
+
+    /// Reverb and chorus sends, 0.0-1.0
+    pub fn effects_sends(&self) -> (f32, f32) {
+        (self.reverb_send as f32 / 1000.0, self.chorus_send as f32 / 1000.0)
+    }
+
+    /// Tuning in cents (coarse and fine together)
+    pub fn tuning_cents(&self) -> i32 {
+        self.coarse_tune * 100 + self.fine_tune
+    }
+}
+
+/// Generator values the notes of one preset start with
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PresetGenerators {
+    // CC1 -> vibLfoToPitch modulators (None: the preset has none)
+    pub mod_wheel_vibrato: Option<ModWheelVibrato>,
+    zones: Vec<Vec<ZoneGenerators>>, // [preset zone][instrument zone]
+}
+
+impl PresetGenerators {
+    /// Walk `preset` and its instruments once
+    pub fn resolve(preset: &SoundFontPreset, soundfont: &SoundFont) -> Self {
+        let instrument_zones = |zone: &PresetZone| -> &[InstrumentZone] {
+            zone.instrument_id
+                .and_then(|id| soundfont.instruments.get(id as usize))
+                .map(|instrument| instrument.instrument_zones.as_slice())
+                .unwrap_or(&[])
+        };
+
+        // Mod wheel vibrato: the first preset zone's instrument, plus the preset zones
+        let instrument_modulators = preset.preset_zones.first()
+            .and_then(|first| wheel_vibrato(instrument_zones(first).iter().map(|zone| zone.modulators.as_slice())));
+        let preset_modulators = wheel_vibrato(preset.preset_zones.iter().map(|zone| zone.modulators.as_slice()));
+        let mod_wheel_vibrato = match (instrument_modulators, preset_modulators) {
+            (Some(instrument), Some(preset)) => Some(ModWheelVibrato::new(
+                instrument.min_depth, instrument.max_depth + preset.max_depth, instrument.curve,
+            )),
+            (instrument, preset) => instrument.or(preset),
+        };
+
+        let preset_global = preset.preset_zones.iter()
+            .find(|zone| zone.instrument_id.is_none())
+            .map(|global| global.generators.as_slice());
+        let zones = preset.preset_zones.iter().map(|zone| {
+            let zones = instrument_zones(zone);
+            let instrument_global = zones.iter()
+                .find(|zone| zone.sample_id.is_none())
+                .map(|global| global.generators.as_slice());
+            zones.iter()
+                .map(|instrument_zone| ZoneGenerators::resolve(
+                    &instrument_zone.generators, instrument_global, &zone.generators, preset_global,
+                ))
+                .collect()
+        }).collect();
+
+        Self { mod_wheel_vibrato, zones }
+    }
+
+    /// Generators of one pair (defaults if the pair is not part of the preset)
+    pub fn zone(&self, preset_zone: usize, instrument_zone: usize) -> ZoneGenerators {
+        self.zones.get(preset_zone)
+            .and_then(|zones| zones.get(instrument_zone))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Mod wheel vibrato of a set of zones, later zones replacing earlier ones
+fn wheel_vibrato<'a>(zones: impl Iterator<Item = &'a [Modulator]>) -> Option<ModWheelVibrato> {
+    zones.filter_map(ModWheelVibrato::from_modulators).last()
+}
+
+/// Value of `generator` in a zone, falling back to its global zone
+fn zone_value(generators: &[Generator], global: Option<&[Generator]>, generator: &GeneratorType) -> Option<i32> {
+    zone_generator(generators, generator).or_else(|| global.and_then(|global| zone_generator(global, generator)))
+}
@@ -9,7 +9,30 @@
 use wasm_bindgen::prelude::*;
 use crate::MidiPlayer;
 use crate::audio::{AudioBufferManager, BufferSize};
+use crate::audio::alloc_audit::AuditScope;
+use crate::audio::memory;
+use crate::audio::capture::OutputCapture;
+use crate::audio::clock::ClockSync;
+use crate::audio::fade::FadeStatus;
+use crate::audio::loudness::LoudnessMeter;
+use crate::audio::render_ahead::{RenderAhead, RenderAheadStatus, MAX_RENDERS_PER_CALLBACK};
+use crate::audio::silence::{OfflineRenderStatus, SilenceDetector};
+use crate::audio::batch::{BatchOptions, BatchRender};
+use crate::audio::ab_compare::{self, AbComparison, AbRequest, AbSettings};
+use crate::synth::telemetry::{ChannelTelemetry, TELEMETRY_BYTES};
+use crate::synth::envelope_monitor::EnvelopeMonitor;
+use crate::synth::voice_manager::MAX_VOICES;
+use crate::synth::device_profile::{DeviceProfile, DeviceProfileStatus, EffectsQuality, InterpolationQuality, ProfileSettings};
+use crate::audio::quality_scaler::{self, QualityScaler, QualityScalingStatus};
+use crate::audio::deadline::{self, DeadlineStatus, DeadlineWatchdog};
+use crate::audio::export::{ExportFormat, ExportOptions};
+use crate::audio::wav::WavFormat;
 use crate::soundfont::SoundFont;
+use crate::soundfont::reduce::{ReductionReport, SampleReduction};
+use crate::soundfont::sample_rates::{sanitize_sample_rates, SampleRateWarning};
+use crate::soundfont::loop_repair::{self, LoopRepair};
+use crate::soundfont::loop_snap::{snap_loops, LoopAdjustment, MAX_SNAP_WINDOW};
+use crate::soundfont::identity::SoundFontIdentity;
 
 /// Pipeline status for audio worklet coordination
 #[derive(Debug, Clone, PartialEq)]
@@ -126,14 +149,26 @@ impl AudioPipelineManager {
         // Pipeline status report (logging disabled)
     }
     
-    /// Get pipeline statistics as JSON string
-    pub fn get_pipeline_stats(&self) -> String {
+    /// Get pipeline statistics (with the transport fade) as JSON string
+    pub fn get_pipeline_stats(&self, fade: &FadeStatus) -> String {
         let uptime_seconds = self.current_sample_time as f32 / self.sample_rate;
-        format!(r#"{{"sampleTime": {}, "uptimeSeconds": {:.1}, "sampleRate": {}, "status": "{:?}", "isReady": {}, "connected": {}}}"#,
-            self.current_sample_time, uptime_seconds, self.sample_rate, self.status, self.is_ready(), self.connected_to_destination)
+        let fade = serde_json::to_string(fade).unwrap_or_else(|_| "null".to_string());
+        format!(r#"{{"sampleTime": {}, "uptimeSeconds": {:.1}, "sampleRate": {}, "status": "{:?}", "isReady": {}, "connected": {}, "fade": {}}}"#,
+            self.current_sample_time, uptime_seconds, self.sample_rate, self.status, self.is_ready(), self.connected_to_destination, fade)
     }
 }
 
+/// Largest block rendered per call (mono samples, or frames for stereo)
+pub const MAX_RENDER_FRAMES: usize = 1024;
+/// Most blocks a single prime() call renders
+pub const MAX_PRIME_BLOCKS: usize = 64;
+/// Smallest block size set_buffer_size accepts
+pub const MIN_BUFFER_FRAMES: usize = 32;
+/// Frames rendered between sequencer updates in batch conversion (0.7 ms at 44.1 kHz)
+const BATCH_BLOCK_FRAMES: usize = 32;
+/// Longest stopped notes may take to release between batch files
+const BATCH_DRAIN_SECONDS: f32 = 1.0;
+
 /// AudioWorklet bridge for real-time audio processing
 /// Manages buffer-based audio processing between Web Audio API and WASM
 #[wasm_bindgen]
@@ -141,8 +176,33 @@ pub struct AudioWorkletBridge {
     midi_player: MidiPlayer,
     sample_rate: f32,
     buffer_size: usize,
+    pending_buffer_size: Option<usize>, // Applied at the start of the next block
     buffer_manager: AudioBufferManager,
     pipeline_manager: AudioPipelineManager,
+    render_buffer: Vec<f32>, // Interleaved stereo block the worklet reads from WASM memory
+    click_buffer: Vec<f32>,  // Mono click bus for the same block (metronome)
+    capture: OutputCapture,  // Master output recording (start_capture/stop_capture)
+    loudness: LoudnessMeter, // LUFS and true-peak metering of the master output
+    render_ahead: RenderAhead, // Blocks rendered before the worklet plays them
+    offline_render: SilenceDetector, // Ends offline renders once the tails have decayed
+    device_profile: Option<DeviceProfile>, // Last profile applied
+    profile_auto_detect: bool, // Apply the detected profile when device info arrives
+    quality_scaler: QualityScaler, // Steps quality down below the profile under sustained load
+    deadline: DeadlineWatchdog, // Times render_stereo_block against the block's playback time
+    polyphony_limit: usize,  // Voice cap below the profile's (startup configuration)
+    clock: ClockSync,        // Sample counter <-> AudioContext time
+    telemetry: [u8; TELEMETRY_BYTES], // Packed channel telemetry for the last block
+    envelope_monitor: EnvelopeMonitor, // Packed voice envelopes, refreshed at the host's rate
+    sample_reduction: SampleReduction, // Downsample/trim applied to SoundFonts at load
+    last_reduction: ReductionReport,
+    sample_rate_warnings: Vec<SampleRateWarning>, // Declared rates corrected in the last SoundFont
+    loop_repairs: Vec<LoopRepair>,  // Near-miss loops nudged into range in the last SoundFont
+    loop_snap_window: u32,          // Zero-crossing search either side of loop points (0 = off)
+    loop_adjustments: Vec<LoopAdjustment>, // Loops snapped to zero crossings in the last SoundFont
+    soundfont_identity: Option<SoundFontIdentity>, // Content hash of the loaded SoundFont
+    batch: Option<BatchRender>,     // Batch conversion in progress, or the last one's outputs
+    batch_restore: Option<OfflineRenderStatus>, // Offline render settings to put back after a batch
+    ab_renders: [Vec<f32>; 2],      // Interleaved stereo renders of the last A/B comparison
 }
 
 #[wasm_bindgen]
@@ -164,8 +224,33 @@ impl AudioWorkletBridge {
             midi_player: MidiPlayer::new(),
             sample_rate,
             buffer_size: 128, // Default Web Audio buffer size
+            pending_buffer_size: None,
             buffer_manager,
             pipeline_manager,
+            render_buffer: vec![0.0; MAX_RENDER_FRAMES * 2],
+            click_buffer: vec![0.0; MAX_RENDER_FRAMES],
+            capture: OutputCapture::new(),
+            loudness: LoudnessMeter::new(sample_rate),
+            render_ahead: RenderAhead::new(),
+            offline_render: SilenceDetector::new(sample_rate),
+            device_profile: None,
+            profile_auto_detect: true,
+            quality_scaler: QualityScaler::new(),
+            deadline: DeadlineWatchdog::new(sample_rate),
+            polyphony_limit: MAX_VOICES,
+            clock: ClockSync::new(sample_rate),
+            telemetry: [0; TELEMETRY_BYTES],
+            envelope_monitor: EnvelopeMonitor::new(),
+            sample_reduction: SampleReduction::default(),
+            last_reduction: ReductionReport::default(),
+            sample_rate_warnings: Vec::new(),
+            loop_repairs: Vec::new(),
+            loop_snap_window: 0,
+            loop_adjustments: Vec::new(),
+            soundfont_identity: None,
+            batch: None,
+            batch_restore: None,
+            ab_renders: [Vec::new(), Vec::new()],
         }
     }
     
@@ -175,27 +260,129 @@ impl AudioWorkletBridge {
         self.sample_rate
     }
     
-    /// Set the buffer size for processing (128, 256, or 512 samples)
+    /// Change the block size mid-session (MIN_BUFFER_FRAMES to MAX_RENDER_FRAMES frames)
+    /// The block being rendered finishes at the old size; the new one applies from the next
+    /// block. Returns false, keeping the current size, for sizes outside the range
     #[wasm_bindgen]
-    pub fn set_buffer_size(&mut self, size: usize) {
-        if size == 128 || size == 256 || size == 512 {
-            self.buffer_size = size;
-            self.pipeline_manager.on_buffer_size_changed(size);
-            // Buffer size set
-        } else {
-            // Invalid buffer size - using 128
-            self.buffer_size = 128;
-            self.pipeline_manager.on_buffer_size_changed(128);
+    pub fn set_buffer_size(&mut self, size: usize) -> bool {
+        if !(MIN_BUFFER_FRAMES..=MAX_RENDER_FRAMES).contains(&size) {
+            return false;
         }
+        self.pending_buffer_size = (size != self.buffer_size).then_some(size);
+        true
     }
     
-    /// Get the current buffer size
+    /// Get the current buffer size (a pending change shows once its first block has rendered)
     #[wasm_bindgen]
     pub fn get_buffer_size(&self) -> usize {
         self.buffer_size
     }
     
+    /// Configure buffer size, interpolation, polyphony and effects from a profile
+    /// The live profile also drops render-ahead, whose queued blocks would delay input
+    fn apply_device_profile(&mut self, profile: DeviceProfile) {
+        let settings = profile.settings();
+        self.set_buffer_size(settings.buffer_size);
+        self.device_profile = Some(profile);
+        self.apply_quality_level();
+        let live = profile == DeviceProfile::Live;
+        self.midi_player.set_live_mode(live);
+        if live {
+            self.render_ahead.set_depth_blocks(0);
+        }
+    }
+    
+    /// Settings quality scaling steps down from: the device profile's, or the engine defaults,
+    /// with polyphony held to the configured limit
+    fn quality_baseline(&self) -> ProfileSettings {
+        let settings = self.device_profile.map(|profile| profile.settings()).unwrap_or(ProfileSettings {
+            buffer_size: self.buffer_size,
+            interpolation: InterpolationQuality::Linear,
+            max_polyphony: MAX_VOICES,
+            effects: EffectsQuality::Full,
+        });
+        ProfileSettings { max_polyphony: settings.max_polyphony.min(self.polyphony_limit), ..settings }
+    }
+    
+    /// Cap polyphony below whatever the device profile allows (1 to MAX_VOICES)
+    pub(crate) fn set_polyphony_limit(&mut self, voices: usize) {
+        self.polyphony_limit = voices.clamp(1, MAX_VOICES);
+        self.apply_quality_level();
+    }
+    
+    /// Configure interpolation, polyphony and effects for the current quality rung
+    fn apply_quality_level(&mut self) {
+        let settings = self.quality_scaler.level().apply(&self.quality_baseline());
+        let voice_manager = &mut self.midi_player.voice_manager;
+        voice_manager.set_interpolation(settings.interpolation);
+        voice_manager.set_max_polyphony(settings.max_polyphony);
+        voice_manager.set_effects_quality(settings.effects);
+    }
+    
+    /// Hand over to a pending buffer size between blocks: timing history and the underrun
+    /// window were measured at the old size, so they start over
+    fn apply_pending_buffer_size(&mut self) {
+        if let Some(size) = self.pending_buffer_size.take() {
+            self.buffer_size = size;
+            self.buffer_manager.adopt_block_size(size);
+            self.pipeline_manager.on_buffer_size_changed(size);
+        }
+    }
+    
     /// Load SoundFont into the synthesis engine (internal method)
+    /// Memory-saving options for subsequent SoundFont loads
+    pub(crate) fn set_sample_reduction(&mut self, reduction: SampleReduction) {
+        self.sample_reduction = reduction;
+    }
+    
+    /// What the reduction did to the most recent SoundFont
+    pub(crate) fn last_reduction(&self) -> ReductionReport {
+        self.last_reduction
+    }
+    
+    /// Samples of the most recent SoundFont whose declared rate was corrected
+    pub(crate) fn sample_rate_warnings(&self) -> &[SampleRateWarning] {
+        &self.sample_rate_warnings
+    }
+    
+    /// Samples of the most recent SoundFont whose loop points were repaired while parsing
+    pub(crate) fn loop_repairs(&self) -> &[LoopRepair] {
+        &self.loop_repairs
+    }
+    
+    /// Zero-crossing snap window for subsequent SoundFont loads (0 = off, capped at MAX_SNAP_WINDOW)
+    pub(crate) fn set_loop_snap_window(&mut self, window: u32) {
+        self.loop_snap_window = window.min(MAX_SNAP_WINDOW);
+    }
+    
+    pub(crate) fn loop_snap_window(&self) -> u32 {
+        self.loop_snap_window
+    }
+    
+    /// Samples of the most recent SoundFont whose loops were snapped to zero crossings
+    pub(crate) fn loop_adjustments(&self) -> &[LoopAdjustment] {
+        &self.loop_adjustments
+    }
+    
+    /// Identity of the loaded SoundFont (None before the first successful load)
+    pub(crate) fn soundfont_identity(&self) -> Option<&SoundFontIdentity> {
+        self.soundfont_identity.as_ref()
+    }
+    
+    pub(crate) fn set_soundfont_identity(&mut self, identity: SoundFontIdentity) {
+        self.soundfont_identity = Some(identity);
+    }
+    
+    /// Apply the configured sample reduction to a SoundFont about to be loaded
+    /// (bogus declared sample rates are corrected first; reduction divides by them)
+    /// and keep the loop repairs its parse made; loops are snapped at the original rate
+    pub(crate) fn reduce_soundfont(&mut self, soundfont: &mut SoundFont) {
+        self.sample_rate_warnings = sanitize_sample_rates(soundfont);
+        self.loop_repairs = loop_repair::take_repairs();
+        self.loop_adjustments = snap_loops(soundfont, self.loop_snap_window);
+        self.last_reduction = self.sample_reduction.apply(soundfont);
+    }
+    
     pub(crate) fn load_soundfont_internal(&mut self, soundfont: SoundFont) -> Result<(), String> {
         // Loading SoundFont into synthesis engine
         
@@ -261,57 +448,28 @@ impl AudioWorkletBridge {
     pub(crate) fn get_loaded_soundfont(&self) -> Option<&SoundFont> {
         self.midi_player.voice_manager.get_loaded_soundfont()
     }
-    
+
+    /// Mutable access to the MidiPlayer for transport/file commands (internal method)
+    pub(crate) fn midi_player_mut(&mut self) -> &mut MidiPlayer {
+        &mut self.midi_player
+    }
+
     /// Process audio buffer - main AudioWorklet processing method
     /// Takes output buffer size and fills it with synthesized audio
-    /// Returns number of samples processed
+    /// Allocates the returned Vec - the worklet uses render_stereo_block() instead
     #[wasm_bindgen]
     pub fn process_audio_buffer(&mut self, buffer_length: usize) -> Vec<f32> {
-        // Check pipeline readiness
-        if !self.pipeline_manager.is_ready() {
-            // Pipeline not ready
-            return vec![0.0; buffer_length.min(1024)];
-        }
-        
-        // Validate buffer size
-        if buffer_length > 1024 {
-            // Buffer size too large, capping at 1024
-        }
-        
-        let actual_length = buffer_length.min(1024);
-        let mut output_buffer = Vec::with_capacity(actual_length);
-        
-        // Generate audio samples using MidiPlayer::process()
-        // Note: In WASM context, precise timing measurements are limited
-        // We'll use a simple estimation based on sample count for now
-        for _ in 0..actual_length {
-            let sample = self.midi_player.process();
-            output_buffer.push(sample);
-        }
-        
-        // Estimate processing time based on buffer size and sample rate
-        // This is a placeholder until we have proper WASM timing
-        let estimated_processing_time_ms = (actual_length as f32 / self.sample_rate) * 1000.0 * 0.1; // Assume 10% CPU usage
-        self.buffer_manager.record_processing_time(estimated_processing_time_ms, actual_length);
-        self.pipeline_manager.advance_sample_time(actual_length as u64);
-        
+        let mut output_buffer = vec![0.0; buffer_length.min(MAX_RENDER_FRAMES)];
+        self.process_audio_buffer_into(&mut output_buffer);
         output_buffer
     }
     
     /// Process stereo audio buffer (interleaved L/R samples)
-    /// For stereo output: [L0, R0, L1, R1, L2, R2, ...]
+    /// Returns interleaved stereo samples: [L0, R0, L1, R1, ...]
     #[wasm_bindgen]
     pub fn process_stereo_buffer(&mut self, buffer_length: usize) -> Vec<f32> {
-        let mono_length = buffer_length / 2;
-        let mut output_buffer = Vec::with_capacity(buffer_length);
-        
-        // Generate true stereo samples
-        for _ in 0..mono_length {
-            let (left, right) = self.midi_player.process_stereo();
-            output_buffer.push(left);  // Left channel
-            output_buffer.push(right); // Right channel
-        }
-        
+        let mut output_buffer = vec![0.0; buffer_length];
+        self.process_stereo_into(&mut output_buffer);
         output_buffer
     }
     
@@ -339,6 +497,180 @@ impl AudioWorkletBridge {
         result
     }
     
+    /// Render `frames` interleaved stereo frames into the bridge's own buffer
+    /// Returns the number of frames rendered; read them through render_buffer_ptr()
+    /// In offline render mode, 0 once the song and its tails have ended
+    #[wasm_bindgen]
+    pub fn render_stereo_block(&mut self, frames: usize) -> usize {
+        let started = if self.deadline.is_enabled() { deadline::now_ms() } else { 0.0 };
+        if self.offline_render.is_finished() {
+            if self.midi_player.is_idle() {
+                return 0;
+            }
+            self.offline_render.reset(); // New events: keep rendering
+        }
+        let frames = frames.min(MAX_RENDER_FRAMES);
+        let mut buffer = std::mem::take(&mut self.render_buffer);
+        let rendered = if self.render_ahead.is_active() {
+            // Render into the queue, then play its oldest block
+            let mut renders = 0;
+            while renders < MAX_RENDERS_PER_CALLBACK && self.render_ahead.needs_block(frames) {
+                let samples = self.process_stereo_into(&mut buffer[..frames * 2]);
+                self.render_ahead.push(&buffer[..samples], &self.click_buffer[..samples / 2]);
+                renders += 1;
+            }
+            self.render_ahead.pop(frames, &mut buffer, &mut self.click_buffer)
+        } else {
+            self.process_stereo_into(&mut buffer[..frames * 2]) / 2
+        };
+        if self.offline_render.is_enabled() {
+            let idle = self.midi_player.is_idle();
+            self.offline_render.observe(&buffer[..rendered * 2], idle, self.midi_player.get_active_voice_count());
+        }
+        self.render_buffer = buffer;
+        if self.deadline.is_enabled() {
+            self.observe_deadline(deadline::now_ms() - started, frames);
+        }
+        rendered
+    }
+    
+    /// Check a block's render time against its deadline: warn the host on a near miss
+    /// and, if enabled, let the load drive quality scaling
+    fn observe_deadline(&mut self, elapsed_ms: f64, frames: usize) {
+        let voices = self.midi_player.get_active_voice_count();
+        if let Some(warning) = self.deadline.observe(elapsed_ms, frames, voices) {
+            deadline::emit(&warning);
+        }
+        if self.deadline.scales_quality() {
+            self.observe_load(self.deadline.last_load());
+        }
+    }
+    
+    /// Render `blocks` blocks at the current buffer size and throw them away, so code and
+    /// buffers are warm before the first audible block. Capture, metering and the render
+    /// queue never see them. Returns frames primed (0 unless the engine is idle)
+    #[wasm_bindgen]
+    pub fn prime(&mut self, blocks: usize) -> usize {
+        self.apply_pending_buffer_size();
+        let primed = self.midi_player.prime(blocks.min(MAX_PRIME_BLOCKS) * self.buffer_size);
+        if primed > 0 {
+            ChannelTelemetry::pack(&self.midi_player.channel_telemetry(), &mut self.telemetry);
+        }
+        primed
+    }
+    
+    /// Stop offline renders when the output falls silent after the last event (see SilenceDetector)
+    #[wasm_bindgen]
+    pub fn set_offline_render(&mut self, enabled: bool, threshold_db: f32, tail_cap_seconds: f32) {
+        self.offline_render.configure(enabled, threshold_db, tail_cap_seconds);
+    }
+    
+    /// Offline render mode and stop state as JSON (OfflineRenderStatus)
+    #[wasm_bindgen]
+    pub fn get_offline_render_status(&self) -> String {
+        serde_json::to_string(&self.offline_render.status()).unwrap_or_else(|_| "{}".to_string())
+    }
+    
+    /// Blocks rendered ahead of playback (0 = off, up to 3); each adds one block of latency
+    #[wasm_bindgen]
+    pub fn set_render_ahead(&mut self, blocks: usize) {
+        self.render_ahead.set_depth_blocks(blocks);
+    }
+    
+    /// Let repeated underruns deepen render-ahead automatically (on by default)
+    #[wasm_bindgen]
+    pub fn set_render_ahead_auto(&mut self, enabled: bool) {
+        self.render_ahead.set_auto_enable(enabled);
+    }
+    
+    /// Render-ahead depth, queue and added latency as JSON (RenderAheadStatus)
+    #[wasm_bindgen]
+    pub fn get_render_ahead_status(&self) -> String {
+        serde_json::to_string(&self.render_ahead_status()).unwrap_or_else(|_| "{}".to_string())
+    }
+    
+    /// Address of the render buffer inside WASM memory (MAX_RENDER_FRAMES interleaved frames)
+    #[wasm_bindgen]
+    pub fn render_buffer_ptr(&self) -> *const f32 {
+        self.render_buffer.as_ptr()
+    }
+    
+    /// First `frames` interleaved frames of the last render_stereo_block
+    pub(crate) fn rendered_block(&self, frames: usize) -> &[f32] {
+        &self.render_buffer[..frames.min(MAX_RENDER_FRAMES) * 2]
+    }
+    
+    /// Address of the click bus buffer (MAX_RENDER_FRAMES mono samples, filled by render_stereo_block)
+    #[wasm_bindgen]
+    pub fn click_buffer_ptr(&self) -> *const f32 {
+        self.click_buffer.as_ptr()
+    }
+    
+    /// Address of the packed channel telemetry (TELEMETRY_BYTES, updated after each block)
+    #[wasm_bindgen]
+    pub fn telemetry_buffer_ptr(&self) -> *const u8 {
+        self.telemetry.as_ptr()
+    }
+    
+    /// Voice envelope refreshes per second of rendered audio (0 = off, max 240)
+    #[wasm_bindgen]
+    pub fn set_envelope_monitor_rate(&mut self, rate_hz: f32) -> bool {
+        self.envelope_monitor.set_rate(rate_hz)
+    }
+    
+    /// Address of the packed voice envelopes (ENVELOPE_MONITOR_FLOATS)
+    #[wasm_bindgen]
+    pub fn envelope_monitor_ptr(&self) -> *const f32 {
+        self.envelope_monitor.buffer().as_ptr()
+    }
+    
+    /// Envelope buffer refreshes so far (wrapping)
+    #[wasm_bindgen]
+    pub fn envelope_monitor_updates(&self) -> u32 {
+        self.envelope_monitor.updates()
+    }
+    
+    /// Start recording the master output (max_seconds <= 0 = 10 minute safety cap)
+    /// Memory for the full duration is reserved now; returns false if that fails
+    #[wasm_bindgen]
+    pub fn start_capture(&mut self, max_seconds: f32) -> bool {
+        self.capture.start(self.sample_rate as u32, max_seconds)
+    }
+    
+    /// Stop recording and return the encoded capture
+    /// `format`: "pcm16", "pcm24", "float32", or "flac"/"oga-flac" in builds with those features
+    /// (unknown formats fall back to pcm16 WAV)
+    #[wasm_bindgen]
+    pub fn stop_capture(&mut self, format: &str) -> Vec<u8> {
+        self.capture.stop(ExportFormat::from_name(format).unwrap_or(ExportFormat::Wav(WavFormat::Pcm16)))
+    }
+    
+    /// stop_capture() with encoder options JSON (`{"dither": true, "noiseShaping": true}`)
+    /// Options that don't parse are ignored rather than losing the recording
+    #[wasm_bindgen]
+    pub fn stop_capture_with_options(&mut self, format: &str, options_json: &str) -> Vec<u8> {
+        let format = ExportFormat::from_name(format).unwrap_or(ExportFormat::Wav(WavFormat::Pcm16));
+        self.capture.stop_with_options(format, ExportOptions::from_json(options_json).unwrap_or_default())
+    }
+    
+    /// Capture state as JSON: recording, seconds, maxSeconds, reachedCap
+    #[wasm_bindgen]
+    pub fn get_capture_status(&self) -> String {
+        serde_json::to_string(&self.capture.status()).unwrap_or_else(|_| "{}".to_string())
+    }
+    
+    /// Master loudness as JSON (LoudnessReport)
+    #[wasm_bindgen]
+    pub fn get_loudness(&self) -> String {
+        serde_json::to_string(&self.loudness.report()).unwrap_or_else(|_| "{}".to_string())
+    }
+    
+    /// Restart loudness metering (integrated loudness and true peak start over)
+    #[wasm_bindgen]
+    pub fn reset_loudness(&mut self) {
+        self.loudness.reset();
+    }
+    
     /// Get reference to internal MidiPlayer for MIDI event handling
     /// This allows the JavaScript side to queue MIDI events
     #[wasm_bindgen]
@@ -359,18 +691,95 @@ impl AudioWorkletBridge {
     #[wasm_bindgen]
     pub fn set_device_info(&mut self, hardware_concurrency: u32, device_memory_gb: u32) {
         self.buffer_manager.set_device_info(hardware_concurrency, device_memory_gb);
+        if self.profile_auto_detect {
+            self.apply_device_profile(DeviceProfile::detect(hardware_concurrency, device_memory_gb));
+        }
+    }
+    
+    /// Select a device profile: "lowEnd", "mid" or "desktop", or "auto" to follow the
+    /// reported device info (applied now if it has been reported). Returns false for unknown names
+    #[wasm_bindgen]
+    pub fn set_device_profile(&mut self, name: &str) -> bool {
+        if name == "auto" {
+            self.profile_auto_detect = true;
+            let detected = self.buffer_manager.device_info()
+                .map(|device| DeviceProfile::detect(device.hardware_concurrency, device.device_memory_gb));
+            if let Some(profile) = detected {
+                self.apply_device_profile(profile);
+            }
+            return true;
+        }
+        match DeviceProfile::from_name(name) {
+            Some(profile) => {
+                self.profile_auto_detect = false;
+                self.apply_device_profile(profile);
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// Active device profile and the settings in effect as JSON (DeviceProfileStatus)
+    #[wasm_bindgen]
+    pub fn get_device_profile(&self) -> String {
+        serde_json::to_string(&self.device_profile_status()).unwrap_or_else(|_| "{}".to_string())
     }
     
     /// Record processing time for buffer performance monitoring
+    /// Sustained high load steps quality down (and quiet periods back up); each step is
+    /// reported to the quality callback
     #[wasm_bindgen]
     pub fn record_processing_time(&mut self, processing_time_ms: f32, buffer_size: usize) {
         self.buffer_manager.record_processing_time(processing_time_ms, buffer_size);
+        if buffer_size == 0 {
+            return;
+        }
+        self.observe_load(processing_time_ms / (buffer_size as f32 / self.sample_rate * 1000.0));
+    }
+    
+    /// Feed one block's load (processing time / block time) to quality scaling
+    fn observe_load(&mut self, load: f32) {
+        let baseline = self.quality_baseline();
+        if let Some(change) = self.quality_scaler.observe(load, &baseline) {
+            self.apply_quality_level();
+            quality_scaler::emit(&change);
+        }
+    }
+    
+    /// Enable or disable quality scaling (on by default); disabling restores full quality
+    #[wasm_bindgen]
+    pub fn set_quality_scaling(&mut self, enabled: bool) {
+        self.quality_scaler.set_enabled(enabled);
+        self.apply_quality_level();
+    }
+    
+    /// Quality scaling state as JSON (QualityScalingStatus)
+    #[wasm_bindgen]
+    pub fn get_quality_scaling(&self) -> String {
+        serde_json::to_string(&self.quality_scaling_status()).unwrap_or_else(|_| "{}".to_string())
+    }
+    
+    /// Configure the block deadline watchdog (on by default, warning at 70% of the block time);
+    /// with `scale_quality` its measured load also drives quality scaling. Counters start over
+    #[wasm_bindgen]
+    pub fn set_deadline_watchdog(&mut self, enabled: bool, warn_load: f32, scale_quality: bool) {
+        self.deadline.configure(enabled, warn_load, scale_quality);
+    }
+    
+    /// Deadline watchdog settings and counters as JSON (DeadlineStatus)
+    #[wasm_bindgen]
+    pub fn get_deadline_status(&self) -> String {
+        serde_json::to_string(&self.deadline_status()).unwrap_or_else(|_| "{}".to_string())
     }
     
     /// Record buffer underrun (audio glitch)
     #[wasm_bindgen]
     pub fn record_underrun(&mut self) {
-        self.buffer_manager.record_underrun();
+        // Underruns that keep coming mean the callback needs more slack than it has
+        // (live mode keeps latency down and leaves quality scaling to find it)
+        if self.buffer_manager.record_underrun_at(self.midi_player.get_current_sample()) && !self.midi_player.live_mode() {
+            self.render_ahead.deepen();
+        }
     }
     
     /// Record buffer overrun (processing too fast)
@@ -408,7 +817,7 @@ impl AudioWorkletBridge {
     pub fn set_optimal_buffer_size(&mut self, size: u32) {
         if let Some(buffer_size) = BufferSize::from_usize(size as usize) {
             self.buffer_manager.set_buffer_size(buffer_size);
-            self.buffer_size = size as usize;
+            self.set_buffer_size(size as usize);
         }
     }
     
@@ -427,45 +836,14 @@ impl AudioWorkletBridge {
     
     // Debug log system removed
     
-    /// Test the worklet bridge with a simple tone
-    #[wasm_bindgen]
-    pub fn test_worklet_bridge(&mut self, buffer_size: usize) -> String {
-        // Testing AudioWorkletBridge
-        
-        // Queue a test note (Middle C, velocity 100)
-        self.queue_midi_event(0, 0, 0x90, 60, 100);
-        
-        // Process a small buffer and check output
-        let test_buffer = self.process_audio_buffer(buffer_size);
-        
-        // Analyze buffer for non-zero samples
-        let non_zero_count = test_buffer.iter().filter(|&&sample| sample.abs() > 0.001).count();
-        let max_amplitude = test_buffer.iter().map(|&sample| sample.abs()).fold(0.0, f32::max);
-        let min_amplitude = test_buffer.iter().map(|&sample| sample.abs()).fold(f32::INFINITY, f32::min);
-        
-        // Release the note
-        self.queue_midi_event(0, 0, 0x80, 60, 0);
-        
-        let result = format!(
-            "{{\"buffer_size\": {}, \"non_zero_samples\": {}, \"max_amplitude\": {:.6}, \"min_amplitude\": {:.6}, \"success\": {}}}",
-            buffer_size,
-            non_zero_count,
-            max_amplitude,
-            min_amplitude,
-            non_zero_count > 0
-        );
-        
-        // Test completed
-        
-        result
-    }
-    
     /// Reset all audio state (stop all voices, clear events)
     #[wasm_bindgen]
     pub fn reset_audio_state(&mut self) {
         // Create a new MidiPlayer to reset all state
         self.midi_player = MidiPlayer::new();
         self.pipeline_manager.reset();
+        self.render_ahead.clear();
+        self.clock.reset();
         // Audio state reset
     }
     
@@ -497,7 +875,7 @@ impl AudioWorkletBridge {
     /// Get comprehensive pipeline statistics as JSON
     #[wasm_bindgen]
     pub fn get_pipeline_stats(&self) -> String {
-        self.pipeline_manager.get_pipeline_stats()
+        self.pipeline_manager.get_pipeline_stats(&self.midi_player.fade_status())
     }
     
     /// Force pipeline status update (for testing/debugging)
@@ -510,12 +888,317 @@ impl AudioWorkletBridge {
     #[wasm_bindgen]
     pub fn get_comprehensive_status(&mut self) -> String {
         let buffer_status = self.buffer_manager.get_status_summary();
-        let pipeline_stats = self.pipeline_manager.get_pipeline_stats();
+        let pipeline_stats = self.pipeline_manager.get_pipeline_stats(&self.midi_player.fade_status());
         
         format!(r#"{{"bufferManager": {}, "pipeline": {}}}"#, buffer_status, pipeline_stats)
     }
 }
 
+// ===== Allocation-free rendering =====
+
+impl AudioWorkletBridge {
+    /// Fill `output` with mono samples (at most MAX_RENDER_FRAMES)
+    /// Never allocates - audited under the `alloc-audit` feature
+    pub fn process_audio_buffer_into(&mut self, output: &mut [f32]) -> usize {
+        let _audit = AuditScope::enter("AudioWorkletBridge::process_audio_buffer");
+        self.observe_memory_growth();
+        self.apply_pending_buffer_size();
+        let actual_length = output.len().min(MAX_RENDER_FRAMES);
+        
+        // Pipeline not ready - output silence
+        if !self.pipeline_manager.is_ready() {
+            output[..actual_length].fill(0.0);
+            return actual_length;
+        }
+        
+        for sample in output[..actual_length].iter_mut() {
+            *sample = self.midi_player.process();
+        }
+        self.capture.write_mono(&output[..actual_length]);
+        for &sample in &output[..actual_length] {
+            self.loudness.process_mono(sample);
+        }
+        
+        // Estimate processing time based on buffer size and sample rate
+        // This is a placeholder until we have proper WASM timing
+        let estimated_processing_time_ms = (actual_length as f32 / self.sample_rate) * 1000.0 * 0.1; // Assume 10% CPU usage
+        self.buffer_manager.record_processing_time(estimated_processing_time_ms, actual_length);
+        self.pipeline_manager.advance_sample_time(actual_length as u64);
+        
+        actual_length
+    }
+    
+    /// Fill `output` with interleaved stereo samples, returns samples written (whole frames only)
+    /// The first MAX_RENDER_FRAMES frames of the click bus go to the click buffer
+    pub fn process_stereo_into(&mut self, output: &mut [f32]) -> usize {
+        let _audit = AuditScope::enter("AudioWorkletBridge::process_stereo");
+        self.observe_memory_growth();
+        self.apply_pending_buffer_size();
+        let mut written = 0;
+        for (index, frame) in output.chunks_exact_mut(2).enumerate() {
+            let (left, right, click) = self.midi_player.process_stereo_with_click();
+            frame[0] = left;
+            frame[1] = right;
+            if let Some(slot) = self.click_buffer.get_mut(index) {
+                *slot = click;
+            }
+            written += 2;
+        }
+        self.capture.write_stereo(&output[..written]);
+        self.loudness.process_interleaved(&output[..written]);
+        ChannelTelemetry::pack(&self.midi_player.channel_telemetry(), &mut self.telemetry);
+        if self.envelope_monitor.advance(written / 2, self.sample_rate) {
+            self.envelope_monitor.pack(&self.midi_player.voice_envelopes());
+        }
+        written
+    }
+    
+    /// Correlate the next sample to render with AudioContext time (start of block)
+    pub fn observe_clock(&mut self, context_time: f64) {
+        // Queued frames were rendered already but play from context_time on
+        let playing = self.midi_player.get_current_sample().saturating_sub(self.render_ahead.queued_frames() as u64);
+        self.clock.observe(playing, context_time);
+    }
+    
+    pub fn render_ahead_status(&self) -> RenderAheadStatus {
+        self.render_ahead.status(self.sample_rate)
+    }
+    
+    pub fn envelope_monitor(&self) -> &EnvelopeMonitor {
+        &self.envelope_monitor
+    }
+    
+    pub fn quality_scaling_status(&self) -> QualityScalingStatus {
+        self.quality_scaler.status(&self.quality_baseline())
+    }
+    
+    pub fn deadline_status(&self) -> DeadlineStatus {
+        self.deadline.status()
+    }
+    
+    pub fn device_profile_status(&self) -> DeviceProfileStatus {
+        let voice_manager = &self.midi_player.voice_manager;
+        DeviceProfileStatus {
+            profile: self.device_profile,
+            auto_detect: self.profile_auto_detect,
+            settings: ProfileSettings {
+                buffer_size: self.pending_buffer_size.unwrap_or(self.buffer_size),
+                interpolation: voice_manager.interpolation(),
+                max_polyphony: voice_manager.max_polyphony(),
+                effects: voice_manager.effects_quality(),
+            },
+        }
+    }
+    
+    /// Sample/time mapping fed by observe_clock()
+    pub fn clock(&self) -> &ClockSync {
+        &self.clock
+    }
+    
+    /// Note any memory.grow since the previous block (audible if voices were sounding)
+    fn observe_memory_growth(&self) {
+        memory::observe_growth(self.midi_player.get_active_voice_count() > 0);
+    }
+}
+
+// ===== Batch conversion =====
+
+#[wasm_bindgen]
+impl AudioWorkletBridge {
+    /// Render up to `max_frames` frames of the running batch, moving on to the next file as
+    /// each one ends. Returns true while work remains (call again, e.g. from a timer)
+    #[wasm_bindgen]
+    pub fn step_batch_render(&mut self, max_frames: usize) -> bool {
+        let Some(mut batch) = self.batch.take() else {
+            return false;
+        };
+        let mut budget = max_frames.max(BATCH_BLOCK_FRAMES);
+        while budget > 0 && batch.is_running() {
+            if batch.current().is_none() {
+                match batch.start_next() {
+                    Some((_, data)) => {
+                        if let Err(message) = self.begin_batch_file(&data, batch.options()) {
+                            batch.fail_file(message);
+                        }
+                    }
+                    None => self.end_batch(&mut batch, false),
+                }
+                continue;
+            }
+            let frames = BATCH_BLOCK_FRAMES.min(budget);
+            budget -= frames;
+            let rendered = self.render_stereo_block(frames);
+            self.midi_player.run_sequencer(rendered);
+            if rendered == 0 {
+                batch.complete_file(self.sample_rate, self.offline_render.status().stop_reason);
+            } else if !batch.push(&self.render_buffer[..rendered * 2]) {
+                batch.complete_file(self.sample_rate, None);
+            }
+        }
+        batch.report_progress(self.midi_player.get_position());
+        let running = batch.is_running();
+        self.batch = Some(batch);
+        running
+    }
+    
+    /// Stop the running batch; files already converted can still be taken
+    #[wasm_bindgen]
+    pub fn cancel_batch_render(&mut self) -> bool {
+        let Some(mut batch) = self.batch.take() else {
+            return false;
+        };
+        let running = batch.is_running();
+        if running {
+            self.end_batch(&mut batch, true);
+        }
+        self.batch = Some(batch);
+        running
+    }
+    
+    /// Encoded output of file `index` of the last batch; each output can be taken once
+    /// (empty if the file failed, isn't done yet or was already taken)
+    #[wasm_bindgen]
+    pub fn take_batch_output(&mut self, index: usize) -> Vec<u8> {
+        self.batch.as_mut().and_then(|batch| batch.take_output(index)).unwrap_or_default()
+    }
+    
+    /// Batch progress as JSON (BatchStatus); "null" before the first batch
+    #[wasm_bindgen]
+    pub fn get_batch_render_status(&self) -> String {
+        serde_json::to_string(&self.batch.as_ref().map(BatchRender::status)).unwrap_or_else(|_| "null".to_string())
+    }
+}
+
+impl AudioWorkletBridge {
+    /// Queue `files` (Standard MIDI File bytes each) for conversion with the loaded SoundFont
+    /// False while another batch is running or for an unknown format name
+    pub fn start_batch_render(&mut self, files: Vec<Vec<u8>>, options: BatchOptions) -> bool {
+        if self.batch.as_ref().is_some_and(BatchRender::is_running) {
+            return false;
+        }
+        let Some(batch) = BatchRender::new(files, options, self.sample_rate) else {
+            return false;
+        };
+        self.batch_restore = Some(self.offline_render.status());
+        self.batch = Some(batch);
+        true
+    }
+    
+    /// Clean start for the next file: the previous one's notes released and tails flushed,
+    /// then the file loaded, channels reset and playback started in offline render mode
+    fn begin_batch_file(&mut self, data: &[u8], options: &BatchOptions) -> Result<(), String> {
+        self.stop_and_drain();
+        if self.midi_player.load_midi_data(data).is_err() {
+            return Err("Not a readable MIDI file".to_string());
+        }
+        self.midi_player.seek(0.0);
+        self.render_ahead.clear();
+        self.offline_render.configure(true, options.threshold_db, options.tail_cap_seconds);
+        self.midi_player.play();
+        Ok(())
+    }
+    
+    fn end_batch(&mut self, batch: &mut BatchRender, cancelled: bool) {
+        self.midi_player.stop();
+        if let Some(saved) = self.batch_restore.take() {
+            self.offline_render.configure(saved.enabled, saved.threshold_db, saved.tail_cap_seconds);
+        }
+        batch.finish(cancelled);
+    }
+    
+    /// Stop playback and let released notes finish (up to BATCH_DRAIN_SECONDS), then
+    /// flush the effect tails
+    fn stop_and_drain(&mut self) {
+        self.midi_player.stop();
+        let mut drain_frames = (BATCH_DRAIN_SECONDS * self.sample_rate) as usize;
+        while self.midi_player.get_active_voice_count() > 0 && drain_frames > 0 {
+            self.midi_player.process_stereo();
+            drain_frames -= 1;
+        }
+        self.midi_player.flush_effects();
+    }
+}
+
+// ===== A/B comparison =====
+
+#[wasm_bindgen]
+impl AudioWorkletBridge {
+    /// Interleaved stereo render of pass "a" or "b" of the last comparison; each can be
+    /// taken once (empty for another name or if already taken)
+    #[wasm_bindgen]
+    pub fn take_ab_render(&mut self, pass: &str) -> Vec<f32> {
+        match pass {
+            "a" => std::mem::take(&mut self.ab_renders[0]),
+            "b" => std::mem::take(&mut self.ab_renders[1]),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl AudioWorkletBridge {
+    /// Render the requested stretch of the loaded song under both snapshots and compare
+    /// the results; settings, position and transport state are restored afterwards
+    /// None without a song, past its end or while a batch is running
+    pub fn render_ab_comparison(&mut self, request: &AbRequest) -> Option<AbComparison> {
+        let duration = self.midi_player.get_duration_seconds();
+        if duration <= 0.0 || request.start_seconds >= duration || self.batch.as_ref().is_some_and(BatchRender::is_running) {
+            return None;
+        }
+        let saved = self.ab_settings();
+        let settings = [request.a.resolve(&saved)?, request.b.resolve(&saved)?];
+        let position = self.midi_player.get_position();
+        let playing = self.midi_player.get_playback_state() == 1;
+        let frames = (request.seconds.min(ab_compare::MAX_AB_SECONDS) * self.sample_rate as f64) as usize;
+        
+        for (pass, settings) in settings.iter().enumerate() {
+            self.stop_and_drain();
+            self.midi_player.voice_manager.restart_effects();
+            self.apply_ab_settings(settings);
+            self.midi_player.seek(request.start_seconds / duration);
+            self.midi_player.play();
+            let mut output = Vec::with_capacity(frames * 2);
+            while output.len() < frames * 2 {
+                let block = BATCH_BLOCK_FRAMES.min(frames - output.len() / 2);
+                for _ in 0..block {
+                    let (left, right) = self.midi_player.process_stereo();
+                    output.extend_from_slice(&[left, right]);
+                }
+                self.midi_player.run_sequencer(block);
+            }
+            self.ab_renders[pass] = output;
+        }
+        
+        self.stop_and_drain();
+        self.apply_ab_settings(&saved);
+        self.render_ahead.clear();
+        self.midi_player.seek(position);
+        if playing {
+            self.midi_player.play();
+        }
+        Some(ab_compare::compare(&self.ab_renders[0], &self.ab_renders[1], self.sample_rate, request.start_seconds))
+    }
+    
+    fn ab_settings(&self) -> AbSettings {
+        let voice_manager = &self.midi_player.voice_manager;
+        AbSettings {
+            interpolation: voice_manager.interpolation(),
+            synthesis_mode: voice_manager.get_synthesis_mode(),
+            enhancements: voice_manager.get_enhancements(),
+            effects: voice_manager.effects_quality(),
+            max_polyphony: voice_manager.max_polyphony(),
+        }
+    }
+    
+    fn apply_ab_settings(&mut self, settings: &AbSettings) {
+        let voice_manager = &mut self.midi_player.voice_manager;
+        voice_manager.set_interpolation(settings.interpolation);
+        voice_manager.set_synthesis_mode(settings.synthesis_mode);
+        voice_manager.set_enhancements(settings.enhancements);
+        voice_manager.set_effects_quality(settings.effects);
+        voice_manager.set_max_polyphony(settings.max_polyphony);
+    }
+}
+
 /// Utility functions for AudioWorklet integration
 
 /// Calculate optimal buffer size based on sample rate and target latency
@@ -556,4 +1239,36 @@ pub fn ms_to_samples(milliseconds: f32, sample_rate: f32) -> usize {
 #[wasm_bindgen]
 pub fn samples_to_ms(samples: usize, sample_rate: f32) -> f32 {
     (samples as f32 * 1000.0) / sample_rate
-}
\ No newline at end of file
+}
+
+// ===== EMBEDDED AUDIOWORKLET PROCESSOR =====
+
+/// Name the embedded processor registers with `registerProcessor()`
+pub const WORKLET_PROCESSOR_NAME: &str = "awe-player-processor";
+
+const WORKLET_PROCESSOR_TEMPLATE: &str = include_str!("assets/awe-worklet-processor.js");
+
+/// Get AudioWorkletProcessor source, ready for `audioWorklet.addModule()` via a Blob URL
+/// `bindings_url` is the absolute URL of the wasm-bindgen JS glue (awe_synth.js)
+#[wasm_bindgen]
+pub fn get_worklet_processor_source(bindings_url: &str) -> String {
+    WORKLET_PROCESSOR_TEMPLATE
+        .replace("__AWE_BINDINGS_URL__", bindings_url)
+        .replace("__AWE_PROTOCOL_VERSION__", &crate::protocol::BRIDGE_PROTOCOL_VERSION.to_string())
+        .replace("__AWE_PROCESSOR_NAME__", WORKLET_PROCESSOR_NAME)
+}
+
+/// Get worklet protocol description as JSON; the processor negotiates the bridge
+/// protocol version (send get_bridge_protocol_version() as `protocolVersion` in init)
+#[wasm_bindgen]
+pub fn get_worklet_protocol_info() -> String {
+    serde_json::json!({
+        "protocolVersion": crate::protocol::BRIDGE_PROTOCOL_VERSION,
+        "minProtocolVersion": crate::protocol::BRIDGE_MIN_PROTOCOL_VERSION,
+        "negotiatedVersion": crate::protocol::negotiated_version(),
+        "processorName": WORKLET_PROCESSOR_NAME,
+        "inbound": ["init", "midi", "control"],
+        "controlCommands": ["reset", "getStats", "initSystems"],
+        "outbound": ["status", "stats", "error", "debug"],
+    }).to_string()
+}
@@ -420,6 +420,7 @@ fn test_phase_13b_implementation_summary() {
     println!("✅ Waveform accuracy verification for sine/triangle/square waves");
     println!("✅ Frequency response testing across EMU8000 range (0.1Hz-20Hz)");
     println!("✅ Phase synchronization and trigger/reset behavior validation");
+    println!("✅ Delay timing at multiple sample rates and EMU8000 start phase");
     println!("✅ SoundFont generator support with cent-based frequency conversion");
     println!("✅ Voice integration testing with dual LFO operation");
     
@@ -0,0 +1,76 @@
+/**
+ * LFO Delay and Start Phase Tests
+ *
+ * EMU8000 LFO delay generators (21/23) and the rising zero crossing each
+ * LFO starts from on trigger, checked at common sample rates. Kept apart
+ * from lfo_tests.rs so run_unit_tests can include them on their own.
+ */
+
+use awe_synth::synth::lfo::{LFO, LfoWaveform};
+
+const SAMPLE_RATES: [f32; 4] = [22050.0, 44100.0, 48000.0, 96000.0];
+
+/// Test LFO delay: no modulation until the delay elapses, at several sample rates
+#[test]
+fn test_lfo_delay_timing() {
+    println!("=== Testing LFO Delay Timing ===");
+    
+    for sample_rate in SAMPLE_RATES {
+        // -2400 timecents = 0.25 seconds
+        let mut lfo = LFO::from_soundfont_generators(sample_rate, 0, -2400, 1.0, LfoWaveform::Sine);
+        let expected_delay = (0.25 * sample_rate) as usize;
+        assert!((lfo.delay_samples as i64 - expected_delay as i64).abs() <= 1,
+               "Delay at {}Hz: {} samples, expected {}", sample_rate, lfo.delay_samples, expected_delay);
+        
+        let mut first_motion = None;
+        for n in 0..expected_delay * 2 {
+            if lfo.process() != 0.0 {
+                first_motion = Some(n);
+                break;
+            }
+        }
+        // Sine starts at zero, so motion shows one sample after the delay
+        let first_motion = first_motion.expect("LFO should start after its delay");
+        assert!(first_motion >= expected_delay, "Modulation began during the delay at {}Hz", sample_rate);
+        assert!(first_motion <= expected_delay + 2, "Modulation began late at {}Hz: {}", sample_rate, first_motion);
+        println!("✅ {}Hz: delay {} samples, first motion at {}", sample_rate, lfo.delay_samples, first_motion);
+    }
+    
+    // trigger() restarts the delay
+    for sample_rate in SAMPLE_RATES {
+        let mut lfo = LFO::new(sample_rate, 5.0, 1.0, LfoWaveform::Sine);
+        lfo.set_delay(0.01);
+        for _ in 0..(0.02 * sample_rate) as usize {
+            lfo.process();
+        }
+        lfo.trigger();
+        assert_eq!(lfo.delay_remaining, lfo.delay_samples);
+        assert_eq!(lfo.process(), 0.0);
+    }
+    
+    println!("✅ LFO delay timing test completed");
+}
+
+/// Test EMU8000 start phase: every waveform leaves zero rising after trigger
+#[test]
+fn test_lfo_start_phase() {
+    println!("=== Testing LFO Start Phase ===");
+    
+    for sample_rate in SAMPLE_RATES {
+        for waveform in [LfoWaveform::Sine, LfoWaveform::Triangle] {
+            let mut lfo = LFO::new(sample_rate, 5.0, 1.0, waveform);
+            lfo.trigger();
+            let first = lfo.process();
+            let second = lfo.process();
+            assert!(first.abs() < 0.01, "{:?} should start at zero at {}Hz: {:.3}", waveform, sample_rate, first);
+            assert!(second > first, "{:?} should start rising at {}Hz", waveform, sample_rate);
+        }
+        
+        // Square has no zero crossing and starts high
+        let mut square = LFO::new(sample_rate, 5.0, 1.0, LfoWaveform::Square);
+        square.trigger();
+        assert_eq!(square.process(), 1.0, "Square should start high at {}Hz", sample_rate);
+    }
+    
+    println!("✅ LFO start phase test completed");
+}
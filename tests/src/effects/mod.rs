@@ -19,4 +19,6 @@ pub mod modulation_tests; // Phase 14B - Modulation routing system testing
 pub mod reverb_integration_tests; // Phase 16 - Send/return effects testing
 pub mod chorus_integration_tests; // Phase 16 - Chorus send/return effects testing
 pub mod midi_effects_integration_tests; // Phase 16 - MIDI CC 91/93 effects control testing
-pub mod voice_manager_effects_integration_tests; // Phase 16.14 - VoiceManager effects bus integration
\ No newline at end of file
+pub mod voice_manager_effects_integration_tests; // Phase 16.14 - VoiceManager effects bus integration
+pub mod transport_effects_tests; // Effect tails across pause, stop and seek
+pub mod lfo_timing_tests; // LFO delay generators and start phase
\ No newline at end of file
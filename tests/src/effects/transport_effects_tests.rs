@@ -0,0 +1,125 @@
+/**
+ * Transport Effects Tests - reverb/chorus tails across pause, stop and seek
+ *
+ * - Stop fades the voices and flushes the tails: no residual audio
+ * - Pause freezes the tails and play resumes them
+ * - Seek flushes the tails and keeps the transport state
+ */
+
+use awe_synth::synth::voice_manager::VoiceManager;
+use awe_synth::MidiPlayer;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYING: u8 = 1;
+    const PAUSED: u8 = 2;
+    const STOPPED: u8 = 0;
+
+    /// One track, 480 PPQ, End of Track after 16 quarters (8 s at 120 BPM)
+    fn sixteen_quarter_smf() -> Vec<u8> {
+        let events = [0xBC, 0x00, 0xFF, 0x2F, 0x00]; // Delta 7680
+        let mut data = b"MThd".to_vec();
+        data.extend_from_slice(&[0, 0, 0, 6, 0, 0, 0, 1, 0x01, 0xE0]);
+        data.extend_from_slice(b"MTrk");
+        data.extend_from_slice(&(events.len() as u32).to_be_bytes());
+        data.extend_from_slice(&events);
+        data
+    }
+
+    /// Player with a reverb-heavy note sounding for half a second on channel 0
+    fn ringing_player() -> MidiPlayer {
+        let mut player = MidiPlayer::new();
+        player.load_midi_file(&sixteen_quarter_smf()).unwrap();
+        player.play();
+        player.send_midi_message(&[0xB0, 91, 127]).unwrap();
+        player.send_midi_message(&[0xB0, 93, 127]).unwrap();
+        player.send_midi_message(&[0x90, 60, 127]).unwrap();
+        for _ in 0..22050 {
+            player.process();
+        }
+        player
+    }
+
+    /// Cut the note (All Sound Off) and let its fade finish, leaving only the effect tails
+    fn cut_voices(player: &mut MidiPlayer) {
+        player.send_midi_message(&[0xB0, 120, 0]).unwrap();
+        for _ in 0..4410 {
+            player.process();
+        }
+    }
+
+    fn peak(player: &mut MidiPlayer, frames: usize) -> f32 {
+        (0..frames).map(|_| player.process().abs()).fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_stop_leaves_no_residual_audio() {
+        let mut player = ringing_player();
+        assert!(peak(&mut player, 64) > 0.0);
+
+        player.stop();
+        assert_eq!(player.get_playback_state(), STOPPED);
+        peak(&mut player, 4410); // Voices fade over the minimum release
+        for frame in 0..44100 {
+            assert_eq!(player.process(), 0.0, "residual audio {} frames after the fade", frame);
+        }
+    }
+
+    #[test]
+    fn test_pause_freezes_tails_and_play_resumes_them() {
+        let mut player = ringing_player();
+        cut_voices(&mut player);
+        assert!(peak(&mut player, 64) > 0.0, "reverb tail outlives the note");
+
+        player.pause();
+        assert_eq!(player.get_playback_state(), PAUSED);
+        assert_eq!(peak(&mut player, 44100), 0.0, "tail held while paused");
+
+        player.play();
+        assert_eq!(player.get_playback_state(), PLAYING);
+        assert!(peak(&mut player, 64) > 0.0, "tail picks up where it stopped");
+    }
+
+    #[test]
+    fn test_seek_flushes_tails_and_keeps_transport_state() {
+        let mut player = ringing_player();
+        cut_voices(&mut player);
+        assert!(peak(&mut player, 64) > 0.0);
+
+        player.seek(0.5);
+        assert_eq!(player.get_playback_state(), PLAYING);
+        assert!((player.get_position() - 0.5).abs() < 0.01);
+        assert_eq!(peak(&mut player, 44100), 0.0, "old tail is gone");
+
+        // Paused seek: flushed and still frozen
+        let mut player = ringing_player();
+        cut_voices(&mut player);
+        player.pause();
+        player.seek(0.25);
+        player.play();
+        assert_eq!(peak(&mut player, 44100), 0.0);
+    }
+
+    #[test]
+    fn test_frozen_buses_ignore_new_sends() {
+        let mut manager = VoiceManager::new(44100.0);
+        manager.set_channel_reverb_send(0, 1.0);
+        manager.set_effects_frozen(true);
+        assert!(manager.is_effects_frozen());
+        assert!(manager.note_on(60, 127, 0).is_some());
+        for _ in 0..4410 {
+            manager.process();
+        }
+        manager.all_sound_off(0);
+        while manager.get_active_voice_count() > 0 {
+            manager.process();
+        }
+
+        manager.set_effects_frozen(false);
+        for _ in 0..44100 {
+            assert_eq!(manager.process(), (0.0, 0.0), "nothing reached the reverb while frozen");
+        }
+    }
+}
@@ -132,12 +132,12 @@ mod player_integration_tests {
     
     #[test]
     fn test_soundfont_module_test() {
-        let test_result = awe_synth::test_soundfont_module();
+        let test_result = awe_synth::self_test::run_self_test("engine").unwrap();
         
-        // Module test should pass
-        assert!(test_result.contains("\"test\":"), "Should report test result");
+        // Engine self-test should pass
+        assert!(test_result.contains("\"failed\":0"), "Engine self-test should pass");
         
-        println!("SoundFont module test: {}", test_result);
+        println!("Engine self-test: {}", test_result);
     }
 }
 
@@ -157,16 +157,13 @@ mod synthesis_integration_tests {
         println!("SoundFont load: {}", load_result);
         
         // Test synthesis
-        let synthesis_result = awe_synth::test_soundfont_synthesis();
+        let synthesis_result = awe_synth::self_test::run_self_test("soundfont").unwrap();
         println!("Synthesis test result: {}", synthesis_result);
         
-        // Verify the test ran (may fail in test environment due to WASM limitations)
-        assert!(synthesis_result.contains("\"success\":") || synthesis_result.contains("\"error\":"),
-               "Should provide synthesis test result");
+        // Verify the test ran
+        assert!(synthesis_result.contains("\"presetSynthesis\""), "Should provide synthesis test result");
         
-        if synthesis_result.contains("\"success\": true") {
-            // If synthesis works, verify it includes test details
-            assert!(synthesis_result.contains("\"notes_tested\":"), "Should report notes tested");
+        if synthesis_result.contains("\"failed\":0") {
             println!("✓ SoundFont synthesis working correctly");
         } else {
             // Expected in test environment - just verify error is reasonable
@@ -0,0 +1,92 @@
+//! Unit tests for A/B render comparison (request parsing, difference metric, bridge passes)
+
+use awe_synth::audio::ab_compare::{compare, AbRequest, AbSettings, MAX_AB_SECONDS};
+use awe_synth::synth::device_profile::InterpolationQuality;
+use awe_synth::synth::hardware_mode::SynthesisMode;
+use awe_synth::worklet::AudioWorkletBridge;
+use super::smf_fixture::smf;
+
+/// Single-track SMF at 120 BPM: a high C held for a quarter, then an E for a quarter
+fn two_note_smf() -> Vec<u8> {
+    let events = [
+        0x00, 0x90, 84, 100, 0x83, 0x60, 0x80, 84, 0,
+        0x00, 0x90, 88, 100, 0x83, 0x60, 0x80, 88, 0,
+        0x00, 0xFF, 0x2F, 0x00,
+    ];
+    smf(&[&events])
+}
+
+#[test]
+fn test_request_parsing_and_snapshot_defaults() {
+    let request = AbRequest::from_json(r#"{"seconds": 1e6, "a": {"interpolation": "linear"}, "b": {"synthesisMode": "hardware"}}"#).unwrap();
+    assert_eq!(request.seconds, MAX_AB_SECONDS);
+    assert_eq!(request.start_seconds, 0.0);
+
+    let current = AbSettings { interpolation: InterpolationQuality::Cubic, ..AbSettings::default() };
+    let a = request.a.resolve(&current).unwrap();
+    let b = request.b.resolve(&current).unwrap();
+    assert_eq!(a.interpolation, InterpolationQuality::Linear);
+    assert_eq!(b.interpolation, InterpolationQuality::Cubic, "unnamed settings keep the engine's");
+    assert_eq!(b.synthesis_mode, SynthesisMode::Hardware);
+
+    assert!(AbRequest::from_json(r#"{"seconds": 1, "a": {"interpolation": "sinc"}, "b": {}}"#).is_none());
+    assert!(AbRequest::from_json(r#"{"seconds": 0, "a": {}, "b": {}}"#).is_none());
+    assert!(AbRequest::from_json(r#"{"seconds": 1, "a": {}}"#).is_none());
+}
+
+#[test]
+fn test_difference_metric() {
+    let a: Vec<f32> = (0..2000).map(|i| (i as f32 * 0.05).sin() * 0.5).collect();
+    let same = compare(&a, &a, 44100.0, 0.0);
+    assert!(same.identical);
+    assert_eq!((same.difference_rms_db, same.null_depth_db), (None, None));
+    assert!((same.correlation.unwrap() - 1.0).abs() < 1e-6);
+    assert_eq!(same.frames, 1000);
+
+    // Half the level: the difference is the other half, 6 dB below A
+    let half: Vec<f32> = a.iter().map(|sample| sample * 0.5).collect();
+    let quieter = compare(&a, &half, 44100.0, 0.0);
+    assert!(!quieter.identical);
+    assert!((quieter.null_depth_db.unwrap() + 6.02).abs() < 0.01);
+    assert!((quieter.a_rms_db.unwrap() - quieter.b_rms_db.unwrap() - 6.02).abs() < 0.01);
+    assert!((quieter.difference_peak_db.unwrap() - 20.0 * 0.25f32.log10()).abs() < 0.01);
+
+    let inverted: Vec<f32> = a.iter().map(|sample| -sample).collect();
+    assert!((compare(&a, &inverted, 44100.0, 0.0).correlation.unwrap() + 1.0).abs() < 1e-6);
+    assert_eq!(compare(&a, &vec![0.0; a.len()], 44100.0, 0.0).correlation, None, "silence has no correlation");
+}
+
+#[test]
+fn test_bridge_renders_both_passes_and_restores_settings() {
+    let mut bridge = AudioWorkletBridge::new(44100.0);
+    let request = AbRequest::from_json(r#"{"seconds": 0.5, "a": {}, "b": {}}"#).unwrap();
+    assert!(bridge.render_ab_comparison(&request).is_none(), "no song loaded");
+
+    let player = unsafe { &mut *bridge.get_midi_player() };
+    player.load_midi_file(&two_note_smf()).unwrap();
+    player.seek(0.25);
+
+    // The same settings twice null completely
+    let same = bridge.render_ab_comparison(&request).unwrap();
+    assert!(same.identical, "{:?}", same);
+    assert!(same.a_rms_db.is_some(), "the segment is not silent");
+    assert_eq!(same.frames, 22050);
+
+    let request = AbRequest::from_json(r#"{"startSeconds": 0.5, "seconds": 0.25, "a": {"interpolation": "linear"}, "b": {"interpolation": "cubic"}}"#).unwrap();
+    let interpolation = bridge.render_ab_comparison(&request).unwrap();
+    assert!(!interpolation.identical);
+    assert!(interpolation.null_depth_db.unwrap() < 0.0);
+    assert!(interpolation.correlation.unwrap() > 0.9, "the same notes, slightly different");
+
+    assert_eq!(bridge.take_ab_render("a").len(), interpolation.frames * 2);
+    assert!(bridge.take_ab_render("a").is_empty(), "taken once");
+    assert_eq!(bridge.take_ab_render("b").len(), interpolation.frames * 2);
+
+    let player = unsafe { &mut *bridge.get_midi_player() };
+    assert!((player.get_position() - 0.25).abs() < 1e-6, "position is restored");
+    assert_eq!(player.get_playback_state(), 0);
+    assert_eq!(bridge.device_profile_status().settings.interpolation, InterpolationQuality::Linear);
+
+    let past_end = AbRequest::from_json(r#"{"startSeconds": 5, "seconds": 1, "a": {}, "b": {}}"#).unwrap();
+    assert!(bridge.render_ab_comparison(&past_end).is_none());
+}
@@ -0,0 +1,45 @@
+//! Unit tests for the audio-path trace counters (`audio-trace` feature)
+
+use awe_synth::get_audio_trace_counters;
+use awe_synth::synth::trace::{self, TraceEvent};
+use awe_synth::synth::voice_manager::VoiceManager;
+
+/// Start a note on the fallback bank and render a few samples
+fn play_note() {
+    let mut manager = VoiceManager::new(44100.0);
+    assert!(manager.note_on(60, 100, 0).is_some());
+    for _ in 0..16 {
+        manager.process();
+    }
+}
+
+#[cfg(feature = "audio-trace")]
+#[test]
+fn test_counters_follow_note_start_and_mix() {
+    // Other tests render in parallel, so only growth is checked
+    let (starts, _) = trace::snapshot(TraceEvent::VoiceStartSample);
+    let (mixes, _) = trace::snapshot(TraceEvent::ZoneMixStart);
+    play_note();
+    let (starts_after, _) = trace::snapshot(TraceEvent::VoiceStartSample);
+    let (mixes_after, zones) = trace::snapshot(TraceEvent::ZoneMixStart);
+    assert!(starts_after >= starts + 5, "the first five samples of the voice are counted");
+    assert!(mixes_after > mixes);
+    assert!(zones >= 1.0, "last value is the zone count");
+
+    let report: serde_json::Value = serde_json::from_str(&get_audio_trace_counters()).unwrap();
+    assert_eq!(report["enabled"], true);
+    assert!(report["events"]["voiceStartSample"]["count"].as_u64().unwrap() >= starts_after);
+}
+
+#[cfg(not(feature = "audio-trace"))]
+#[test]
+fn test_counters_stay_zero_without_the_feature() {
+    assert!(!trace::report().enabled);
+    play_note();
+    let report: serde_json::Value = serde_json::from_str(&get_audio_trace_counters()).unwrap();
+    assert_eq!(report["enabled"], false);
+    for event in TraceEvent::ALL {
+        assert_eq!(trace::snapshot(event), (0, 0.0));
+        assert_eq!(report["events"][event.name()]["count"], 0);
+    }
+}
@@ -0,0 +1,40 @@
+//! Unit tests for preset audition on the internal channel
+
+use awe_synth::synth::voice_manager::VoiceManager;
+
+#[test]
+fn test_audition_ignores_live_note_off_and_ends_on_timer() {
+    let mut manager = VoiceManager::new(1000.0); // 1 sample per ms
+    assert!(manager.note_on(60, 100, 0).is_some());
+    assert!(manager.audition_preset(0, 40, 60, 100, 50));
+    assert!(manager.is_auditioning());
+    assert_eq!(manager.get_channel_state(0).map(|s| s.program), Some(0));
+
+    // Live note-off for the same key leaves the audition note sounding
+    manager.note_off(60);
+    assert!(manager.is_auditioning());
+
+    for _ in 0..=50 {
+        manager.process();
+    }
+    assert!(!manager.is_auditioning());
+}
+
+#[test]
+fn test_audition_never_steals_live_voices() {
+    let mut manager = VoiceManager::new(44100.0);
+    for note in 0..32 {
+        assert!(manager.note_on(40 + note, 100, 0).is_some());
+    }
+    assert!(!manager.audition_preset(128, 0, 38, 100, 200));
+    assert_eq!(manager.get_active_voice_count(), 32);
+}
+
+#[test]
+fn test_new_audition_replaces_previous() {
+    let mut manager = VoiceManager::new(44100.0);
+    assert!(manager.audition_preset(0, 0, 60, 100, 1000));
+    assert!(manager.audition_preset(128, 0, 36, 100, 1000));
+    manager.stop_audition();
+    assert!(!manager.is_auditioning());
+}
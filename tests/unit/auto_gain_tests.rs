@@ -0,0 +1,40 @@
+//! Unit tests for polyphony-aware master auto-gain
+
+use awe_synth::audio::auto_gain::{AutoGain, MIN_AUTO_GAIN, REFERENCE_VOICES};
+
+#[test]
+fn test_target_follows_voice_count() {
+    assert_eq!(AutoGain::target_for(0), 1.0);
+    assert_eq!(AutoGain::target_for(REFERENCE_VOICES), 1.0);
+    assert!((AutoGain::target_for(REFERENCE_VOICES * 4) - 0.5).abs() < 1e-6);
+    assert_eq!(AutoGain::target_for(10_000), MIN_AUTO_GAIN);
+}
+
+#[test]
+fn test_reduction_is_fast_and_recovery_slow() {
+    let sample_rate = 44100.0;
+    let mut auto_gain = AutoGain::new(sample_rate);
+    // Disabled: unity regardless of polyphony
+    assert_eq!(auto_gain.next(32), 1.0);
+
+    auto_gain.set_enabled(true);
+    for _ in 0..(sample_rate * 0.1) as usize {
+        auto_gain.next(16);
+    }
+    assert!((auto_gain.gain() - 0.5).abs() < 0.01, "reduced to {}", auto_gain.gain());
+
+    // 100 ms after the texture thins out the gain has barely recovered
+    for _ in 0..(sample_rate * 0.1) as usize {
+        auto_gain.next(1);
+    }
+    assert!(auto_gain.gain() < 0.6, "recovered to {}", auto_gain.gain());
+
+    // Disabling also recovers gradually
+    auto_gain.set_enabled(false);
+    assert!(auto_gain.is_engaged());
+    for _ in 0..(sample_rate * 20.0) as usize {
+        auto_gain.next(32);
+    }
+    assert_eq!(auto_gain.gain(), 1.0);
+    assert!(!auto_gain.is_engaged());
+}
@@ -0,0 +1,94 @@
+//! Unit tests for automation lanes and their effect on channel output
+
+use awe_synth::midi::automation::{
+    AutomationInterpolation, AutomationLane, AutomationLanes, AutomationParameter, AutomationPoint,
+    ChannelAutomation, MAX_AUTOMATION_LANES,
+};
+use awe_synth::synth::voice_manager::VoiceManager;
+
+fn lane(channel: u8, parameter: AutomationParameter, interpolation: AutomationInterpolation, points: &[(u64, f32)]) -> Option<AutomationLane> {
+    let points = points.iter().map(|&(tick, value)| AutomationPoint { tick, value }).collect();
+    AutomationLane::new(channel, parameter, interpolation, points)
+}
+
+/// Peak output over 0.05 s
+fn peak(manager: &mut VoiceManager) -> f32 {
+    let mut peak = 0.0f32;
+    for _ in 0..2205 {
+        let (left, right) = manager.process();
+        peak = peak.max(left.abs()).max(right.abs());
+    }
+    peak
+}
+
+#[test]
+fn test_lane_interpolation() {
+    let points = [(100, -12.0), (200, 0.0)];
+    let linear = lane(0, AutomationParameter::Volume, AutomationInterpolation::Linear, &points).unwrap();
+    assert_eq!(linear.value_at(0.0), -12.0, "holds the first point before it");
+    assert_eq!(linear.value_at(150.0), -6.0);
+    assert!((linear.value_at(125.5) + 8.94).abs() < 1e-4, "between whole ticks");
+    assert_eq!(linear.value_at(5000.0), 0.0, "holds the last point after it");
+
+    let step = lane(0, AutomationParameter::Volume, AutomationInterpolation::Step, &points).unwrap();
+    assert_eq!(step.value_at(199.9), -12.0);
+    assert_eq!(step.value_at(200.0), 0.0);
+
+    // Cosine ease: slow at both points, through the midpoint halfway
+    let smooth = lane(0, AutomationParameter::Volume, AutomationInterpolation::Smooth, &points).unwrap();
+    assert!((smooth.value_at(150.0) + 6.0).abs() < 1e-4);
+    assert!(smooth.value_at(110.0) < linear.value_at(110.0));
+    assert!(smooth.value_at(190.0) > linear.value_at(190.0));
+}
+
+#[test]
+fn test_lanes_clamp_sort_and_replace() {
+    // Out of order and out of range points are sorted and clamped
+    let cutoff = lane(3, AutomationParameter::Cutoff, AutomationInterpolation::Linear, &[(480, 9.0), (0, -1.0)]).unwrap();
+    assert_eq!(cutoff.points, vec![AutomationPoint { tick: 0, value: -1.0 }, AutomationPoint { tick: 480, value: 2.0 }]);
+    assert!(lane(16, AutomationParameter::Pan, AutomationInterpolation::Step, &[(0, 0.0)]).is_none());
+    assert!(lane(0, AutomationParameter::Pan, AutomationInterpolation::Step, &[]).is_none());
+
+    let mut lanes = AutomationLanes::new();
+    assert!(lanes.set(cutoff));
+    assert!(lanes.set(lane(3, AutomationParameter::Cutoff, AutomationInterpolation::Step, &[(0, 1.0)]).unwrap()));
+    assert_eq!(lanes.lanes().len(), 1, "same channel and parameter replaces");
+    assert_eq!(lanes.lanes()[0].interpolation, AutomationInterpolation::Step);
+
+    for channel in 0..16 {
+        for parameter in [AutomationParameter::Volume, AutomationParameter::Pan, AutomationParameter::Cutoff] {
+            lanes.set(lane(channel, parameter, AutomationInterpolation::Step, &[(0, 0.0)]).unwrap());
+        }
+    }
+    assert_eq!(lanes.lanes().len(), MAX_AUTOMATION_LANES);
+    assert!(lanes.remove(3, AutomationParameter::Cutoff));
+    assert!(!lanes.remove(3, AutomationParameter::Cutoff));
+
+    let mut applied = Vec::new();
+    lanes.evaluate(0.0, |channel, parameter, value| applied.push((channel, parameter, value)));
+    assert_eq!(applied.len(), MAX_AUTOMATION_LANES - 1);
+}
+
+#[test]
+fn test_automation_scales_channel_output() {
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_smoothing_time_ms(0.0);
+    manager.note_on(60, 100, 2);
+    let unity = peak(&mut manager);
+    assert!(unity > 0.0);
+
+    // -60 dB on the note's channel; other channels untouched
+    manager.set_automation(2, AutomationParameter::Volume, -60.0);
+    manager.set_automation(5, AutomationParameter::Cutoff, 1.0);
+    assert!((manager.channel_automation(2).unwrap().gain - 0.001).abs() < 1e-6);
+    assert_eq!(manager.channel_automation(5).unwrap().brightness, 2.0);
+    let faded = peak(&mut manager);
+    assert!(faded < unity * 0.01, "faded {} vs {}", faded, unity);
+
+    // Removing the lane hands the channel back to its controllers
+    manager.reset_automation(Some((2, AutomationParameter::Volume)));
+    assert_eq!(manager.channel_automation(2), Some(ChannelAutomation::NEUTRAL));
+    assert!(peak(&mut manager) > faded * 100.0);
+    manager.reset_automation(None);
+    assert_eq!(manager.channel_automation(5), Some(ChannelAutomation::NEUTRAL));
+}
@@ -0,0 +1,141 @@
+//! Unit tests for GM/GM2 bank select resolution and system SysEx parsing
+
+use awe_synth::midi::bank_select::*;
+use awe_synth::synth::channel_state::{SoundControllers, MAX_ADDED_VIBRATO_DELAY_SECONDS};
+use awe_synth::synth::lfo::{LfoWaveform, LFO};
+
+#[test]
+fn test_gm_ignores_bank_select() {
+    let melodic = resolve_bank_select(MidiSystemMode::Gm, 0, GM2_BANK_MSB_MELODIC, 3, 24, false);
+    assert_eq!(melodic, BankSelection { bank: 0, program: 24, is_drum: false });
+
+    let drums = resolve_bank_select(MidiSystemMode::Gm, 9, 0, 0, 16, true);
+    assert_eq!(drums, BankSelection { bank: SOUNDFONT_DRUM_BANK, program: 16, is_drum: true });
+}
+
+#[test]
+fn test_gm2_melodic_and_rhythm_banks() {
+    let variation = resolve_bank_select(MidiSystemMode::Gm2, 0, GM2_BANK_MSB_MELODIC, 2, 4, false);
+    assert_eq!(variation, BankSelection { bank: 2, program: 4, is_drum: false });
+
+    // Rhythm bank works on any channel
+    let rhythm = resolve_bank_select(MidiSystemMode::Gm2, 3, GM2_BANK_MSB_RHYTHM, 0, 8, false);
+    assert_eq!(rhythm, BankSelection { bank: SOUNDFONT_DRUM_BANK, program: 8, is_drum: true });
+
+    // Melodic MSB turns channel 10 into a melodic channel
+    let melodic_ch10 = resolve_bank_select(MidiSystemMode::Gm2, 9, GM2_BANK_MSB_MELODIC, 0, 0, true);
+    assert!(!melodic_ch10.is_drum);
+
+    // Unknown MSB keeps the channel's current role
+    let unchanged = resolve_bank_select(MidiSystemMode::Gm2, 9, 0, 0, 0, true);
+    assert!(unchanged.is_drum);
+}
+
+#[test]
+fn test_system_sysex_parsing() {
+    assert_eq!(parse_system_sysex(&[0xF0, 0x7E, 0x7F, 0x09, 0x03, 0xF7]), Some(SystemSysEx::Gm2SystemOn));
+    assert_eq!(parse_system_sysex(&[0xF0, 0x7E, 0x10, 0x09, 0x01, 0xF7]), Some(SystemSysEx::Gm1SystemOn));
+    assert_eq!(parse_system_sysex(&[0xF0, 0x7E, 0x7F, 0x09, 0x02, 0xF7]), Some(SystemSysEx::GmSystemOff));
+    assert_eq!(parse_system_sysex(&[0xF0, 0x43, 0x10, 0x4C, 0x00, 0xF7]), None);
+    assert_eq!(parse_system_sysex(&[0xF0, 0x7E]), None);
+}
+
+#[test]
+fn test_sound_controller_scaling() {
+    let mut controllers = SoundControllers::new();
+    assert!(controllers.is_neutral());
+    assert_eq!(controllers.cutoff_scale(), 1.0);
+
+    assert!(controllers.set(74, 0));
+    assert!((controllers.cutoff_scale() - 0.25).abs() < 1e-6);
+    assert!(controllers.set(73, 96));
+    assert!((controllers.attack_time_scale() - 4.0).abs() < 1e-4);
+    assert!(!controllers.set(80, 0));
+}
+
+#[test]
+fn test_vibrato_sound_controllers() {
+    let mut controllers = SoundControllers::new();
+    assert_eq!(controllers.vibrato_delay_seconds(0.2), 0.2);
+
+    assert!(controllers.set(76, 96));
+    assert!((controllers.vibrato_rate_scale() - 2.0).abs() < 1e-4);
+    assert!(controllers.set(77, 0));
+    assert!((controllers.vibrato_depth_scale() - 0.25).abs() < 1e-6);
+
+    // Below 64 shortens the patch delay, above 64 adds onset time
+    assert!(controllers.set(78, 0));
+    assert_eq!(controllers.vibrato_delay_seconds(0.2), 0.0);
+    assert!(controllers.set(78, 96));
+    assert!((controllers.vibrato_delay_seconds(0.2) - (0.2 + 0.5 * MAX_ADDED_VIBRATO_DELAY_SECONDS)).abs() < 1e-6);
+}
+
+#[test]
+fn test_lfo_onset_delay() {
+    let mut lfo = LFO::new(1000.0, 5.0, 1.0, LfoWaveform::Sine);
+    lfo.set_delay(0.01);
+    lfo.trigger();
+    for _ in 0..10 {
+        assert_eq!(lfo.process(), 0.0);
+    }
+    let started: Vec<f32> = (0..100).map(|_| lfo.process()).collect();
+    assert!(started.iter().any(|level| level.abs() > 0.5));
+}
+
+#[test]
+fn test_gs_and_xg_bank_mapping() {
+    let gs = resolve_bank_select(MidiSystemMode::Gs, 0, 9, 1, 0, false);
+    assert_eq!(gs, BankSelection { bank: 9, program: 0, is_drum: false });
+    assert_eq!(fallback_banks(MidiSystemMode::Gs, &gs).collect::<Vec<_>>(), vec![8, 0]);
+
+    let gs_kit = resolve_bank_select(MidiSystemMode::Gs, 9, 0, 0, 25, true);
+    assert_eq!(gs_kit, BankSelection { bank: SOUNDFONT_DRUM_BANK, program: 25, is_drum: true });
+
+    let xg = resolve_bank_select(MidiSystemMode::Xg, 0, 0, 18, 4, false);
+    assert_eq!(xg, BankSelection { bank: 18, program: 4, is_drum: false });
+    assert_eq!(fallback_banks(MidiSystemMode::Xg, &xg).collect::<Vec<_>>(), vec![0]);
+
+    let xg_kit = resolve_bank_select(MidiSystemMode::Xg, 2, XG_BANK_MSB_DRUM_KIT, 0, 16, false);
+    assert_eq!(xg_kit, BankSelection { bank: SOUNDFONT_DRUM_BANK, program: 16, is_drum: true });
+
+    let xg_sfx = resolve_bank_select(MidiSystemMode::Xg, 2, XG_BANK_MSB_SFX_KIT, 0, 0, false);
+    assert_eq!(xg_sfx.program, GS_SFX_KIT_PROGRAM);
+}
+
+#[test]
+fn test_gs_and_xg_sysex_parsing() {
+    let gs_reset = [0xF0, 0x41, 0x10, 0x42, 0x12, 0x40, 0x00, 0x7F, 0x00, 0x41, 0xF7];
+    assert_eq!(parse_system_sysex(&gs_reset), Some(SystemSysEx::GsReset));
+
+    let mut bad_checksum = gs_reset;
+    bad_checksum[9] = 0x42;
+    assert_eq!(parse_system_sysex(&bad_checksum), None);
+
+    // Part 11 (block 0x1A) -> channel index 10 as drums
+    let rhythm_part = [0xF0, 0x41, 0x10, 0x42, 0x12, 0x40, 0x1A, 0x15, 0x02, 0x0F, 0xF7];
+    assert_eq!(parse_system_sysex(&rhythm_part), Some(SystemSysEx::GsRhythmPart { channel: 10, is_drum: true }));
+
+    let xg_on = [0xF0, 0x43, 0x10, 0x4C, 0x00, 0x00, 0x7E, 0x00, 0xF7];
+    assert_eq!(parse_system_sysex(&xg_on), Some(SystemSysEx::XgSystemOn));
+}
+
+#[test]
+fn test_parameter_number_selection() {
+    use awe_synth::synth::channel_state::{ChannelState, ParameterNumber, NRPN_PLAYBACK_DIRECTION};
+
+    let mut state = ChannelState::new(0);
+    assert_eq!(state.selected_parameter(), None);
+
+    state.set_parameter_msb(true, 126);
+    state.set_parameter_lsb(true, 0);
+    assert_eq!(state.selected_parameter(), Some(ParameterNumber::Nrpn(NRPN_PLAYBACK_DIRECTION)));
+
+    state.set_parameter_msb(false, 0);
+    state.set_parameter_lsb(false, 0);
+    assert_eq!(state.selected_parameter(), Some(ParameterNumber::Rpn(0)));
+
+    // RPN null deselects
+    state.set_parameter_msb(false, 127);
+    state.set_parameter_lsb(false, 127);
+    assert_eq!(state.selected_parameter(), None);
+}
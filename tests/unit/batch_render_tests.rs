@@ -0,0 +1,102 @@
+//! Unit tests for batch MIDI-to-audio conversion
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use awe_synth::audio::batch::{self, BatchEvent, BatchOptions, BatchRender, MAX_BATCH_SECONDS};
+use awe_synth::worklet::AudioWorkletBridge;
+use super::smf_fixture::smf;
+
+/// Single-track SMF with no notes, ending after one quarter (0.5 s at 120 BPM)
+fn quarter_smf() -> Vec<u8> {
+    let events = [0x83, 0x60, 0xFF, 0x2F, 0x00];
+    smf(&[&events])
+}
+
+/// Run `f` with a listener attached, returning the events it emitted
+fn record(f: impl FnOnce()) -> Vec<BatchEvent> {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let sink = events.clone();
+    batch::set_listener(Some(Rc::new(move |event: &BatchEvent| sink.borrow_mut().push(event.clone()))));
+    f();
+    batch::set_listener(None);
+    let events = events.borrow().clone();
+    events
+}
+
+#[test]
+fn test_batch_options_defaults_and_limits() {
+    let options = BatchOptions::from_json(r#"{"format":"pcm24","targetLufs":-14,"maxSeconds":1e9}"#).unwrap();
+    assert_eq!(options.format, "pcm24");
+    assert_eq!(options.export.target_lufs, Some(-14.0), "export options sit beside the render ones");
+    assert_eq!(options.threshold_db, -90.0);
+    assert_eq!(BatchOptions::from_json("{}"), Some(BatchOptions::default()));
+    assert!(BatchOptions::from_json("not json").is_none());
+
+    let batch = BatchRender::new(vec![quarter_smf()], options, 44100.0).unwrap();
+    assert_eq!(batch.options().max_seconds, MAX_BATCH_SECONDS);
+    let unknown = BatchOptions { format: "mp3".to_string(), ..BatchOptions::default() };
+    assert!(BatchRender::new(vec![quarter_smf()], unknown, 44100.0).is_none());
+}
+
+#[test]
+fn test_batch_converts_files_in_order() {
+    let mut bridge = AudioWorkletBridge::new(44100.0);
+    let files = vec![quarter_smf(), b"not a midi file".to_vec(), quarter_smf()];
+    let events = record(|| {
+        assert!(bridge.start_batch_render(files, BatchOptions::default()));
+        assert!(!bridge.start_batch_render(vec![quarter_smf()], BatchOptions::default()), "one batch at a time");
+        let mut steps = 0;
+        while bridge.step_batch_render(4096) {
+            steps += 1;
+            assert!(steps < 1000, "never finished");
+        }
+    });
+
+    let kinds: Vec<&str> = events.iter().filter_map(|event| match event {
+        BatchEvent::Started { .. } => Some("started"),
+        BatchEvent::FileStarted { .. } => Some("fileStarted"),
+        BatchEvent::FileComplete { .. } => Some("fileComplete"),
+        BatchEvent::FileFailed { .. } => Some("fileFailed"),
+        BatchEvent::Complete { .. } => Some("complete"),
+        BatchEvent::Progress { .. } => None,
+    }).collect();
+    assert_eq!(kinds, ["started", "fileStarted", "fileComplete", "fileStarted", "fileFailed", "fileStarted", "fileComplete", "complete"]);
+    assert!(events.iter().any(|event| matches!(event, BatchEvent::Progress { index: 0, .. })));
+    assert_eq!(events.last(), Some(&BatchEvent::Complete { converted: 2, failed: 1, cancelled: false }));
+
+    // The song plus the silence hold, each file from a clean start
+    let Some(BatchEvent::FileComplete { seconds, truncated, .. }) = events.iter().find(|event| matches!(event, BatchEvent::FileComplete { .. })) else {
+        unreachable!();
+    };
+    assert!((0.5..1.0).contains(seconds), "{} s", seconds);
+    assert!(!truncated);
+
+    let first = bridge.take_batch_output(0);
+    assert_eq!(&first[..4], b"RIFF");
+    assert_eq!(bridge.take_batch_output(2).len(), first.len());
+    assert!(bridge.take_batch_output(0).is_empty(), "taken once");
+    assert!(bridge.take_batch_output(1).is_empty(), "failed file");
+    let status: serde_json::Value = serde_json::from_str(&bridge.get_batch_render_status()).unwrap();
+    assert_eq!((status["running"].as_bool(), status["converted"].as_u64()), (Some(false), Some(2)));
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&bridge.get_offline_render_status()).unwrap()["enabled"], false, "settings put back");
+}
+
+#[test]
+fn test_cancel_keeps_finished_outputs() {
+    let mut bridge = AudioWorkletBridge::new(44100.0);
+    let options = BatchOptions { max_seconds: 0.25, ..BatchOptions::default() };
+    let events = record(|| {
+        assert!(bridge.start_batch_render(vec![quarter_smf(), quarter_smf()], options));
+        while bridge.get_batch_render_status().contains(r#""converted":0"#) {
+            bridge.step_batch_render(1024);
+        }
+        assert!(bridge.cancel_batch_render());
+        assert!(!bridge.cancel_batch_render());
+        assert!(!bridge.step_batch_render(1024));
+    });
+
+    assert!(events.iter().any(|event| matches!(event, BatchEvent::FileComplete { index: 0, truncated: true, .. })));
+    assert_eq!(events.last(), Some(&BatchEvent::Complete { converted: 1, failed: 1, cancelled: true }));
+    assert!(!bridge.take_batch_output(0).is_empty());
+    assert!(bridge.get_batch_render_status().contains(r#""cancelled":true"#));
+}
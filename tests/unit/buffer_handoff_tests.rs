@@ -0,0 +1,39 @@
+//! Unit tests for changing the buffer size mid-session
+
+use awe_synth::audio::buffer_manager::{AudioBufferManager, BufferSize};
+use awe_synth::worklet::{AudioWorkletBridge, MAX_RENDER_FRAMES, MIN_BUFFER_FRAMES};
+
+#[test]
+fn test_buffer_size_changes_at_block_boundary() {
+    let mut bridge = AudioWorkletBridge::new(44100.0);
+    assert_eq!(bridge.get_buffer_size(), 128);
+
+    assert!(!bridge.set_buffer_size(MIN_BUFFER_FRAMES - 1));
+    assert!(!bridge.set_buffer_size(MAX_RENDER_FRAMES + 1));
+    assert!(bridge.set_buffer_size(441));
+    assert_eq!(bridge.get_buffer_size(), 128, "the change waits for the next block");
+
+    let mut block = [0.0f32; 128 * 2];
+    bridge.process_stereo_into(&mut block);
+    assert_eq!(bridge.get_buffer_size(), 441);
+
+    // Blocks of the new size render without reinitializing
+    let mut large = vec![0.0f32; 441 * 2];
+    assert_eq!(bridge.process_stereo_into(&mut large), 441 * 2);
+    assert_eq!(bridge.render_stereo_block(441), 441);
+}
+
+#[test]
+fn test_manager_follows_standard_block_sizes() {
+    let mut manager = AudioBufferManager::new(Some(BufferSize::Small));
+    manager.set_sample_rate(1000.0);
+    manager.record_underrun_at(0);
+    manager.record_underrun_at(100);
+
+    manager.adopt_block_size(512);
+    assert_eq!(manager.get_current_buffer_size(), BufferSize::Large);
+    assert!(!manager.record_underrun_at(200), "underruns at the old size no longer count");
+
+    manager.adopt_block_size(441);
+    assert_eq!(manager.get_current_buffer_size(), BufferSize::Large, "odd sizes keep the recommendation");
+}
@@ -0,0 +1,42 @@
+//! Unit tests for master output capture
+
+use awe_synth::audio::capture::OutputCapture;
+use awe_synth::audio::export::ExportFormat;
+use awe_synth::audio::wav::WavFormat;
+use awe_synth::worklet::AudioWorkletBridge;
+
+fn data_len(wav: &[u8]) -> u32 {
+    u32::from_le_bytes([wav[40], wav[41], wav[42], wav[43]])
+}
+
+#[test]
+fn test_capture_stops_at_cap() {
+    let mut capture = OutputCapture::new();
+    assert!(capture.start(100, 0.05)); // 5 frames
+    capture.write_stereo(&[0.1; 6]);
+    capture.write_mono(&[0.2; 4]);
+    assert!(!capture.is_recording());
+    assert!(capture.reached_cap());
+    assert!((capture.recorded_seconds() - 0.05).abs() < 1e-6);
+
+    let wav = capture.stop(ExportFormat::Wav(WavFormat::Pcm16));
+    assert_eq!(data_len(&wav), 5 * 2 * 2);
+}
+
+#[test]
+fn test_bridge_capture_records_rendered_blocks() {
+    let mut bridge = AudioWorkletBridge::new(44100.0);
+    let mut block = [0.0f32; 256];
+
+    bridge.process_stereo_into(&mut block); // Not recording yet
+    assert!(bridge.start_capture(1.0));
+    bridge.queue_midi_event(0, 0, 0x90, 69, 100);
+    bridge.process_stereo_into(&mut block);
+    bridge.process_audio_buffer_into(&mut block[..64]);
+    assert!(bridge.get_capture_status().contains("\"recording\":true"));
+
+    let wav = bridge.stop_capture("float32");
+    assert_eq!(&wav[0..4], b"RIFF");
+    assert_eq!(u32::from_le_bytes([wav[54], wav[55], wav[56], wav[57]]), (128 + 64) * 2 * 4);
+    assert!(bridge.get_capture_status().contains("\"recording\":false"));
+}
@@ -0,0 +1,167 @@
+//! Unit tests for the SoundFont sample and preset catalogs
+
+use std::collections::HashMap;
+use awe_synth::soundfont::catalog::{
+    preset_effects_sends, EffectsSends, PresetCatalogPage, PresetQuery, SampleCatalogPage, SampleQuery, SampleWaveform, MAX_PAGE_SIZE,
+};
+use awe_synth::soundfont::types::{Generator, GeneratorAmount, GeneratorType, PresetZone};
+use awe_synth::synth::fallback_bank::build_fallback_soundfont;
+use awe_synth::synth::multizone_voice::MultiZoneSampleVoice;
+use awe_synth::synth::voice_manager::VoiceManager;
+
+fn query(offset: usize, limit: usize, name_filter: &str, waveform_points: usize) -> SampleQuery {
+    SampleQuery { offset, limit, name_filter: name_filter.to_string(), waveform_points }
+}
+
+#[test]
+fn test_pages_cover_every_sample_with_validated_loops() {
+    let mut bank = build_fallback_soundfont(44100.0);
+    let length = bank.samples[0].sample_data.len() as u32;
+    bank.samples[0].loop_start = 10;
+    bank.samples[0].loop_end = length + 1; // Past the end of the data
+
+    let first = SampleCatalogPage::query(&bank, &query(0, 3, "", 0));
+    assert!(first.success);
+    assert_eq!(first.total_samples, bank.samples.len());
+    assert_eq!(first.samples.len(), 3);
+    assert!(!first.samples[0].has_valid_loop);
+    assert_eq!((first.samples[0].loop_start, first.samples[0].loop_end), (0, 0));
+    assert_eq!((first.samples[0].raw_loop_start, first.samples[0].raw_loop_end), (10, length + 1));
+    assert!(first.samples[0].waveform.is_none());
+
+    let rest = SampleCatalogPage::query(&bank, &query(3, usize::MAX, "", 0));
+    assert_eq!(rest.samples.len(), (bank.samples.len() - 3).min(MAX_PAGE_SIZE));
+    assert_eq!(rest.samples[0].index, 3);
+
+    let past_end = SampleCatalogPage::query(&bank, &query(bank.samples.len(), 10, "", 0));
+    assert!(past_end.success && past_end.samples.is_empty());
+}
+
+#[test]
+fn test_name_filter_keeps_original_indices() {
+    let bank = build_fallback_soundfont(44100.0);
+    let name = bank.samples[2].name.to_uppercase();
+
+    let page = SampleCatalogPage::query(&bank, &query(0, 10, &name, 16));
+    assert!(page.matching_samples >= 1 && page.matching_samples < page.total_samples);
+    assert!(page.samples.iter().any(|entry| entry.index == 2));
+    let waveform = page.samples[0].waveform.as_ref().expect("waveform requested");
+    assert_eq!(waveform.min.len(), 16);
+
+    let json = page.to_json();
+    assert!(json.contains("\"matchingSamples\""));
+    assert!(!json.contains("\"error\""));
+}
+
+#[test]
+fn test_waveform_decimation_keeps_extremes() {
+    let mut data = vec![0i16; 1000];
+    data[123] = 16384;
+    data[877] = -32768;
+
+    let waveform = SampleWaveform::decimate(&data, 10);
+    assert_eq!(waveform.max.len(), 10);
+    assert_eq!(waveform.max[1], 0.5);
+    assert_eq!(waveform.min[8], -1.0);
+    assert_eq!(waveform.interleaved().len(), 20);
+
+    // Never more points than samples
+    assert_eq!(SampleWaveform::decimate(&data[..4], 100).min.len(), 4);
+    assert!(SampleWaveform::decimate(&[], 100).min.is_empty());
+}
+
+#[test]
+fn test_empty_soundfont_page() {
+    let mut bank = build_fallback_soundfont(44100.0);
+    bank.samples.clear();
+    let page = SampleCatalogPage::query(&bank, &query(0, 10, "", 0));
+    assert_eq!(page.total_samples, 0);
+    assert!(page.samples.is_empty());
+    assert!(SampleCatalogPage::error("No SoundFont loaded").to_json().contains("No SoundFont loaded"));
+}
+
+fn send(generator_type: GeneratorType, value: i16) -> Generator {
+    Generator { generator_type, amount: GeneratorAmount::Short(value) }
+}
+
+#[test]
+fn test_preset_sends_add_preset_offset_to_instrument_value() {
+    let mut bank = build_fallback_soundfont(44100.0);
+    let instrument = bank.presets[0].preset_zones[0].instrument_id.unwrap() as usize;
+    bank.instruments[instrument].instrument_zones[0].generators.push(send(GeneratorType::ReverbEffectsSend, 300));
+    bank.presets[0].preset_zones.insert(0, PresetZone {
+        generators: vec![send(GeneratorType::ReverbEffectsSend, 100), send(GeneratorType::ChorusEffectsSend, 250)],
+        modulators: Vec::new(),
+        instrument_id: None,
+        key_range: None,
+        velocity_range: None,
+    });
+    let (sends, vary) = preset_effects_sends(&bank, &bank.presets[0]);
+    assert!((sends.reverb - 0.4).abs() < 1e-6);
+    assert!((sends.chorus - 0.25).abs() < 1e-6);
+    assert!(!vary);
+
+    // Sums are clamped to 100%
+    bank.presets[0].preset_zones[1].generators.push(send(GeneratorType::ReverbEffectsSend, 900));
+    assert_eq!(preset_effects_sends(&bank, &bank.presets[0]).0.reverb, 1.0);
+
+    // Drum kit zones without send generators report 0
+    let kit = bank.presets.last().unwrap();
+    assert_eq!(preset_effects_sends(&bank, kit).0, EffectsSends::new(0.0, 0.0));
+}
+
+#[test]
+fn test_catalog_sends_are_the_sends_notes_start_with() {
+    let mut bank = build_fallback_soundfont(44100.0);
+    let instrument = bank.presets[0].preset_zones[0].instrument_id.unwrap() as usize;
+    bank.instruments[instrument].instrument_zones[0].generators.push(send(GeneratorType::ReverbEffectsSend, 350));
+    bank.instruments[instrument].instrument_zones[0].generators.push(send(GeneratorType::ChorusEffectsSend, 120));
+    bank.presets[0].preset_zones[0].generators.push(send(GeneratorType::ChorusEffectsSend, 80));
+
+    for preset in [&bank.presets[0], &bank.presets[1], bank.presets.last().unwrap()] {
+        let (sends, vary) = preset_effects_sends(&bank, preset);
+        assert!(!vary);
+        for (note, velocity) in [(36, 20), (60, 100), (96, 127)] {
+            let mut voice = MultiZoneSampleVoice::new(0, 44100.0);
+            voice.start_note(note, velocity, 0, &bank, preset).unwrap();
+            assert_eq!((voice.get_reverb_send(), voice.get_chorus_send()), (sends.reverb, sends.chorus), "{} note {}", preset.name, note);
+        }
+    }
+    assert_eq!(preset_effects_sends(&bank, &bank.presets[0]).0, EffectsSends::new(0.35, 0.2));
+}
+
+#[test]
+fn test_preset_page_lists_sends_and_overrides() {
+    let bank = build_fallback_soundfont(44100.0);
+    let mut overrides = HashMap::new();
+    overrides.insert(1, EffectsSends::new(0.5, 2.0));
+
+    let page = PresetCatalogPage::query(&bank, &PresetQuery { offset: 0, limit: 3, name_filter: String::new() }, &overrides);
+    assert_eq!(page.total_presets, bank.presets.len());
+    assert_eq!(page.presets.len(), 3);
+    assert_eq!(page.presets[1].sends_override, Some(EffectsSends { reverb: 0.5, chorus: 1.0 }));
+    assert!(page.presets[0].sends_override.is_none());
+    let json = page.to_json();
+    assert!(json.contains("\"sendsOverride\":{\"reverb\":0.5,\"chorus\":1.0}"));
+
+    let kits = PresetCatalogPage::query(&bank, &PresetQuery { offset: 0, limit: 10, name_filter: "standard kit".to_string() }, &overrides);
+    assert_eq!(kits.matching_presets, 1);
+    assert_eq!(kits.presets[0].index, bank.presets.len() - 1);
+}
+
+#[test]
+fn test_voice_manager_keeps_overrides_until_next_soundfont() {
+    let mut manager = VoiceManager::new(44100.0);
+    assert!(!manager.set_preset_effects_sends(0, 0, Some(EffectsSends::new(0.1, 0.1))), "no SoundFont loaded");
+    manager.load_soundfont(build_fallback_soundfont(44100.0)).unwrap();
+
+    assert!(manager.set_preset_effects_sends(0, 5, Some(EffectsSends::new(0.7, 0.2))));
+    assert!(!manager.set_preset_effects_sends(3, 5, Some(EffectsSends::new(0.7, 0.2))), "exact bank only");
+    assert_eq!(manager.preset_effects_sends().get(&5), Some(&EffectsSends::new(0.7, 0.2)));
+    assert!(manager.set_preset_effects_sends(0, 5, None));
+    assert!(manager.preset_effects_sends().is_empty());
+
+    manager.set_preset_effects_sends(0, 5, Some(EffectsSends::new(0.7, 0.2)));
+    manager.load_soundfont(build_fallback_soundfont(44100.0)).unwrap();
+    assert!(manager.preset_effects_sends().is_empty());
+}
@@ -0,0 +1,39 @@
+//! Unit tests for per-channel percussion overrides
+
+use awe_synth::midi::bank_select::MidiSystemMode;
+use awe_synth::synth::voice_manager::VoiceManager;
+
+fn is_drum(manager: &VoiceManager, channel: u8) -> bool {
+    manager.get_channel_state(channel).unwrap().is_drum
+}
+
+#[test]
+fn test_drum_override_marks_any_channel_and_frees_channel_10() {
+    let mut manager = VoiceManager::new(44100.0);
+    assert!(is_drum(&manager, 9) && !is_drum(&manager, 10));
+
+    assert!(manager.set_channel_drum_override(10, Some(true)));
+    assert!(manager.set_channel_drum_override(9, Some(false)));
+    assert!(is_drum(&manager, 10) && !is_drum(&manager, 9));
+    assert!(!manager.set_channel_drum_override(16, Some(true)));
+
+    // Program changes and bank select keep the forced role
+    manager.set_system_mode(MidiSystemMode::Xg);
+    manager.bank_select_msb(9, 127); // XG drum kit MSB
+    manager.program_change(9, 0);
+    manager.program_change(10, 25);
+    assert!(!is_drum(&manager, 9) && is_drum(&manager, 10));
+
+    // GS Use For Rhythm Part cannot undo it either, and a reset keeps it
+    manager.set_channel_drum(10, false);
+    assert!(is_drum(&manager, 10));
+    manager.reset_channels();
+    assert!(is_drum(&manager, 10) && !is_drum(&manager, 9));
+    assert_eq!(manager.channel_drum_override(9), Some(false));
+
+    // Auto returns the channel to the rules
+    manager.set_channel_drum_override(9, None);
+    manager.set_channel_drum_override(10, None);
+    assert!(is_drum(&manager, 9) && !is_drum(&manager, 10));
+    assert_eq!(manager.channel_drum_override(10), None);
+}
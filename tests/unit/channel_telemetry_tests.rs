@@ -0,0 +1,61 @@
+//! Unit tests for per-channel telemetry (voice counts and mixer controllers) and the controller snapshot
+
+use awe_synth::synth::telemetry::{ChannelTelemetry, TELEMETRY_BYTES, TELEMETRY_STRIDE};
+use awe_synth::synth::voice_manager::VoiceManager;
+
+#[test]
+fn test_telemetry_tracks_voices_program_and_controllers() {
+    let mut manager = VoiceManager::new(44100.0);
+    let idle = manager.channel_telemetry();
+    assert_eq!((idle[3].volume, idle[3].pan, idle[3].expression, idle[3].sustain), (100, 64, 127, false));
+
+    manager.program_change(2, 40);
+    assert!(manager.note_on(60, 100, 2).is_some());
+    assert!(manager.note_on(64, 100, 2).is_some());
+    assert!(manager.set_mixer_controller(2, 7, 90));
+    assert!(manager.set_mixer_controller(2, 64, 127));
+    assert!(!manager.set_mixer_controller(2, 1, 10), "only mixer controllers are recorded");
+
+    let telemetry = manager.channel_telemetry();
+    assert_eq!(telemetry[2].active_voices, 2);
+    assert_eq!((telemetry[2].program, telemetry[2].volume, telemetry[2].sustain), (40, 90, true));
+    assert_eq!(telemetry[0].active_voices, 0);
+
+    let mut packed = [0u8; TELEMETRY_BYTES];
+    ChannelTelemetry::pack(&telemetry, &mut packed);
+    assert_eq!(&packed[2 * TELEMETRY_STRIDE..3 * TELEMETRY_STRIDE], &[2, 40, 90, 64, 127, 1]);
+}
+
+#[test]
+fn test_channel_state_report_mirrors_every_controller() {
+    let mut manager = VoiceManager::new(44100.0);
+    assert!(manager.channel_state_report(16).is_none());
+    let idle = manager.channel_state_report(9).unwrap();
+    assert!(idle.is_drum);
+    assert_eq!((idle.rpn, idle.nrpn, idle.pitch_bend), (None, None, 0.0));
+
+    manager.bank_select_msb(4, 1);
+    manager.program_change(4, 19);
+    manager.set_mixer_controller(4, 10, 20);
+    manager.set_modulation_wheel(4, 90);
+    manager.set_channel_pressure(4, 33);
+    manager.process_midi_control_change(4, 91, 64);
+    manager.set_sound_controller(4, 74, 100);
+    manager.apply_pitch_bend(4, -1.5);
+    manager.parameter_number_msb(4, false, 0);
+    manager.parameter_number_lsb(4, false, 1);
+    manager.data_entry_msb(4, 0x50);
+
+    let report = manager.channel_state_report(4).unwrap();
+    assert_eq!((report.channel, report.bank_msb, report.program), (4, 1, 19));
+    assert_eq!((report.pan, report.modulation, report.pressure), (20, 90, 33));
+    assert_eq!(report.reverb_send, 64);
+    assert_eq!(report.sound_controllers[4], 100, "CC74");
+    assert_eq!(report.pitch_bend, -1.5);
+    assert_eq!((report.rpn, report.nrpn), (Some(1), None));
+    assert_eq!(report.fine_tuning_cents, 25.0);
+
+    let json = serde_json::to_string(&report).unwrap();
+    assert!(json.contains("\"pitchBend\":-1.5") && json.contains("\"fineTuningCents\":25.0"), "{}", json);
+    assert_eq!(manager.channel_state_report(5).unwrap().pitch_bend, 0.0, "other channels untouched");
+}
@@ -0,0 +1,64 @@
+//! Unit tests for per-channel output trim and the engine state snapshot
+
+use awe_synth::synth::smoothing::trim_gain;
+use awe_synth::synth::snapshot::{EngineSnapshot, SNAPSHOT_VERSION};
+use awe_synth::synth::voice_manager::VoiceManager;
+
+/// Peak output of one note on `channel` over 0.1 s
+fn peak(manager: &mut VoiceManager, channel: u8) -> f32 {
+    manager.note_on(60, 100, channel);
+    let mut peak = 0.0f32;
+    for _ in 0..4410 {
+        let (left, right) = manager.process();
+        peak = peak.max(left.abs()).max(right.abs());
+    }
+    peak
+}
+
+#[test]
+fn test_trim_scales_channel_output() {
+    assert!((trim_gain(-6.0) - 0.501).abs() < 0.001);
+    assert_eq!(trim_gain(100.0), trim_gain(12.0));
+
+    let mut reference = VoiceManager::new(44100.0);
+    reference.set_smoothing_time_ms(0.0);
+    let unity = peak(&mut reference, 2);
+
+    let mut trimmed = VoiceManager::new(44100.0);
+    trimmed.set_smoothing_time_ms(0.0);
+    assert!(trimmed.set_channel_trim_db(2, -12.0));
+    assert!(!trimmed.set_channel_trim_db(16, -12.0));
+    let quiet = peak(&mut trimmed, 2);
+    assert!((quiet / unity - trim_gain(-12.0)).abs() < 0.01, "ratio {}", quiet / unity);
+
+    // Host setting, not MIDI state: a GM reset keeps it
+    trimmed.reset_channels();
+    assert_eq!(trimmed.channel_trim_db(2), Some(-12.0));
+    assert_eq!(trimmed.channel_smoothing(2).unwrap().trim.target(), trim_gain(-12.0));
+}
+
+#[test]
+fn test_state_snapshot_round_trip() {
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_channel_trim_db(0, -3.0);
+    manager.set_channel_trim_db(9, 40.0);
+    let json = manager.state_snapshot().to_json();
+
+    let snapshot = EngineSnapshot::from_json(&json).unwrap();
+    assert_eq!(snapshot.version, SNAPSHOT_VERSION);
+    assert_eq!(snapshot.channel_trims_db[9], 12.0, "stored clamped");
+
+    let mut restored = VoiceManager::new(44100.0);
+    restored.set_channel_trim_db(5, -20.0);
+    restored.restore_state_snapshot(&snapshot);
+    assert_eq!(restored.state_snapshot(), snapshot);
+    assert_eq!(restored.channel_trim_db(5), Some(0.0));
+
+    // Short trim lists restore the rest to unity; newer formats are refused
+    let partial = EngineSnapshot::from_json(r#"{"version":1,"channelTrimsDb":[-1.5]}"#).unwrap();
+    restored.restore_state_snapshot(&partial);
+    assert_eq!(restored.channel_trim_db(0), Some(-1.5));
+    assert_eq!(restored.channel_trim_db(9), Some(0.0));
+    assert!(EngineSnapshot::from_json(r#"{"version":99}"#).is_none());
+    assert!(EngineSnapshot::from_json("not json").is_none());
+}
@@ -0,0 +1,102 @@
+//! Unit tests for sequencer chase (state reconstruction when seeking)
+
+use awe_synth::midi::chase::chase;
+use awe_synth::midi::parser::{MetaEventType, MidiEvent, MidiEventType, MidiFile, MidiTrack};
+use awe_synth::midi::sequencer::{MidiSequencer, ProcessedEventType};
+use super::smf_fixture::smf;
+
+fn event(tick: u64, event_type: MidiEventType) -> MidiEvent {
+    MidiEvent { delta_time: 0, absolute_time: tick, event_type }
+}
+
+fn cc(tick: u64, channel: u8, controller: u8, value: u8) -> MidiEvent {
+    event(tick, MidiEventType::ControlChange { channel, controller, value })
+}
+
+/// Events as (channel, kind, data1, data2), kind 'P' program or 'C' controller
+fn summary(events: &[awe_synth::midi::sequencer::ProcessedMidiEvent]) -> Vec<(u8, char, u8, u8)> {
+    events.iter().filter_map(|event| match event.event_type {
+        ProcessedEventType::ProgramChange { channel, program } => Some((channel, 'P', program, 0)),
+        ProcessedEventType::ControlChange { channel, controller, value } => Some((channel, 'C', controller, value)),
+        _ => None,
+    }).collect()
+}
+
+fn file() -> MidiFile {
+    let conductor = MidiTrack {
+        name: None,
+        events: vec![
+            event(0, MidiEventType::MetaEvent(MetaEventType::SetTempo { microseconds_per_quarter: 400_000 })),
+            event(960, MidiEventType::MetaEvent(MetaEventType::SetTempo { microseconds_per_quarter: 600_000 })),
+            event(3840, MidiEventType::MetaEvent(MetaEventType::EndOfTrack)),
+        ],
+    };
+    let strings = MidiTrack {
+        name: Some("Strings".to_string()),
+        events: vec![
+            cc(0, 0, 0, 8),
+            event(0, MidiEventType::ProgramChange { channel: 0, program: 48 }),
+            cc(0, 0, 7, 40),
+            // Pitch bend range: RPN 0/0, data entry 12
+            cc(0, 0, 101, 0),
+            cc(0, 0, 100, 0),
+            cc(0, 0, 6, 12),
+            event(0, MidiEventType::NoteOn { channel: 0, note: 60, velocity: 100 }),
+            cc(480, 0, 7, 90),
+            cc(480, 0, 64, 127),
+            cc(600, 0, 1, 64),
+            cc(700, 0, 121, 0),
+            cc(800, 0, 0, 0),       // Bank for a later program change
+            cc(900, 0, 123, 0),     // Channel mode message, not state
+            event(1000, MidiEventType::NoteOff { channel: 0, note: 60, velocity: 0 }),
+            cc(2000, 0, 7, 10),
+            event(3840, MidiEventType::MetaEvent(MetaEventType::EndOfTrack)),
+        ],
+    };
+    MidiFile { format: 1, track_count: 2, division: 480, tracks: vec![conductor, strings] }
+}
+
+#[test]
+fn test_chase_rebuilds_program_and_controllers() {
+    let state = chase(&file(), 1920);
+    assert_eq!(state.tempo, Some(600_000));
+    assert_eq!(summary(&state.events), vec![
+        (0, 'C', 0, 8),         // Bank current at the program change
+        (0, 'P', 48, 0),
+        (0, 'C', 0, 0),         // Bank selected afterwards
+        (0, 'C', 7, 90),        // Last volume; sustain and mod wheel were reset by CC121
+        (0, 'C', 101, 0),
+        (0, 'C', 100, 0),
+        (0, 'C', 6, 12),
+        (0, 'C', 101, 127),     // CC121 nulls the parameter selection
+        (0, 'C', 100, 127),
+    ]);
+
+    // Before the reset the sustain pedal is still down
+    let held = summary(&chase(&file(), 650).events);
+    assert!(held.contains(&(0, 'C', 64, 127)));
+    assert!(held.contains(&(0, 'C', 1, 64)));
+
+    // Events at the seek tick itself are played, not chased
+    let start = chase(&file(), 0);
+    assert_eq!(start.tempo, None);
+    assert!(start.events.is_empty());
+}
+
+#[test]
+fn test_seek_picks_up_chase_events_and_tempo() {
+    let mut sequencer = MidiSequencer::new(44100.0);
+    // Format 0, 480 PPQ: CC7 = 50 at 0, Set Tempo 100 BPM at 0, CC7 = 80 at 480, End at 960
+    let events = [
+        0x00, 0xB0, 0x07, 50,
+        0x00, 0xFF, 0x51, 0x03, 0x09, 0x27, 0xC0,
+        0x83, 0x60, 0xB0, 0x07, 80,
+        0x83, 0x60, 0xFF, 0x2F, 0x00,
+    ];
+    sequencer.load_midi_file(&smf(&[&events])).unwrap();
+
+    sequencer.seek(0.75, 0);
+    assert_eq!(summary(&sequencer.take_chase_events()), vec![(0, 'C', 7, 80)]);
+    assert!(sequencer.take_chase_events().is_empty(), "chase events are handed out once");
+    assert!((sequencer.get_original_tempo_bpm() - 100.0).abs() < 1e-9);
+}
@@ -0,0 +1,53 @@
+//! Unit tests for sample counter / AudioContext time correlation
+
+use awe_synth::audio::clock::ClockSync;
+
+const RATE: f32 = 48000.0;
+const BLOCK: u64 = 128;
+
+/// Feed one observation per block for `seconds`; the context clock runs `ppm` fast
+fn feed(clock: &mut ClockSync, start_sample: u64, seconds: f64, start_time: f64, ppm: f64) -> u64 {
+    let blocks = (seconds * RATE as f64) as u64 / BLOCK;
+    for block in 0..blocks {
+        let sample = start_sample + block * BLOCK;
+        let elapsed = (sample - start_sample) as f64 / RATE as f64 * (1.0 + ppm * 1e-6);
+        clock.observe(sample, start_time + elapsed);
+    }
+    start_sample + blocks * BLOCK
+}
+
+#[test]
+fn test_fit_estimates_drift_and_maps_both_ways() {
+    let mut clock = ClockSync::new(RATE);
+    assert!(!clock.status().locked);
+    let end = feed(&mut clock, 0, 60.0, 3.25, 120.0);
+
+    let status = clock.status();
+    assert!(status.locked);
+    assert!((status.drift_ppm - 120.0).abs() < 0.5, "drift {}", status.drift_ppm);
+    assert!(status.residual_ms < 0.001);
+    assert!(status.observations <= 64, "history is bounded");
+
+    // One hour ahead, the nominal rate would be off by 120 ppm (0.43 s); the fit is not
+    let later = end + 3600 * RATE as u64;
+    let expected = 3.25 + later as f64 / RATE as f64 * (1.0 + 120e-6);
+    assert!((clock.sample_to_time(later) - expected).abs() < 0.001);
+    assert!(clock.time_to_sample(expected).abs_diff(later) <= 1);
+}
+
+#[test]
+fn test_discontinuity_restarts_the_fit() {
+    let mut clock = ClockSync::new(RATE);
+    let end = feed(&mut clock, 0, 10.0, 0.0, 0.0);
+    assert_eq!(clock.status().resyncs, 0);
+
+    // Context resumed two seconds later than the samples suggest
+    feed(&mut clock, end, 1.0, 12.0, 0.0);
+    let status = clock.status();
+    assert_eq!(status.resyncs, 1);
+    assert!(!status.locked);
+    assert!((clock.sample_to_time(end) - 12.0).abs() < 1e-9);
+
+    clock.observe(end, f64::NAN);
+    assert_eq!(clock.status().resyncs, 1, "non-finite times are ignored");
+}
@@ -0,0 +1,81 @@
+//! Unit tests for controller smoothing (one-pole glides of CC-driven mixer parameters)
+
+use awe_synth::synth::smoothing::{channel_gain, smoothing_coefficient, SmoothedValue};
+use awe_synth::synth::voice_manager::VoiceManager;
+
+#[test]
+fn test_smoothed_value_glides_monotonically_and_settles() {
+    let coefficient = smoothing_coefficient(5.0, 44100.0);
+    let mut value = SmoothedValue::new(0.0);
+    value.set_target(1.0);
+
+    let first = value.next(coefficient);
+    assert!(first > 0.0 && first < 0.01, "first step is a small fraction of the jump, got {}", first);
+    let mut previous = first;
+    for _ in 0..44100 {
+        let current = value.next(coefficient);
+        assert!(current >= previous && current <= 1.0);
+        previous = current;
+    }
+    assert!(value.is_settled());
+    assert_eq!(value.current(), 1.0);
+
+    // Zero time constant applies immediately
+    value.set_target(0.25);
+    assert_eq!(value.next(smoothing_coefficient(0.0, 44100.0)), 0.25);
+}
+
+#[test]
+fn test_volume_change_ramps_instead_of_stepping() {
+    assert_eq!(channel_gain(100, 127), 1.0);
+
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_smoothing_time_ms(0.0);
+    manager.set_mixer_controller(0, 7, 0);
+    manager.process();
+    manager.set_smoothing_time_ms(10.0);
+    assert!(manager.set_mixer_controller(0, 7, 127));
+
+    let smoothing = *manager.channel_smoothing(0).unwrap();
+    assert_eq!(smoothing.gain.current(), 0.0, "a setting only moves the target");
+    assert_eq!(smoothing.gain.target(), channel_gain(127, 127));
+
+    let mut levels = Vec::new();
+    for _ in 0..4410 {
+        manager.process();
+        levels.push(manager.channel_smoothing(0).unwrap().gain.current());
+    }
+    // Largest per-sample step is a small fraction of the 0 -> 127 jump
+    let largest_step = levels.windows(2).map(|pair| pair[1] - pair[0]).fold(levels[0], f32::max);
+    assert!(largest_step < 0.01 * channel_gain(127, 127), "step {}", largest_step);
+    assert!((levels[levels.len() - 1] - channel_gain(127, 127)).abs() < 0.01);
+
+    // Other channels are untouched
+    assert!(manager.channel_smoothing(1).unwrap().is_settled());
+    assert_eq!(manager.channel_smoothing(1).unwrap().gain.current(), 1.0);
+}
+
+#[test]
+fn test_expression_scales_volume_and_restores_fully() {
+    // CC11 multiplies CC7: half expression costs the same at any volume
+    let half_at_full = channel_gain(100, 64) / channel_gain(100, 127);
+    let half_at_low = channel_gain(40, 64) / channel_gain(40, 127);
+    assert!((half_at_full - half_at_low).abs() < 1e-6);
+    assert_eq!(channel_gain(100, 0), 0.0);
+
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_smoothing_time_ms(0.0);
+    manager.set_mixer_controller(0, 91, 100);
+    let sends = manager.channel_smoothing(0).unwrap().reverb_send.target();
+
+    manager.set_mixer_controller(0, 11, 20);
+    manager.process();
+    assert_eq!(manager.channel_smoothing(0).unwrap().gain.current(), channel_gain(100, 20));
+    assert_eq!(manager.channel_smoothing(0).unwrap().reverb_send.target(), sends, "send level is left alone");
+
+    // Back to full expression is back to full level, not a ratcheted-down one
+    manager.set_mixer_controller(0, 11, 127);
+    manager.process();
+    assert_eq!(manager.channel_smoothing(0).unwrap().gain.current(), 1.0);
+    assert_eq!(manager.channel_smoothing(0).unwrap().reverb_send.target(), sends);
+}
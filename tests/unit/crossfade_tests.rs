@@ -0,0 +1,43 @@
+//! Unit tests for velocity crossfade curves and width
+
+use awe_synth::synth::crossfade::{CrossfadeCurve, VelocityCrossfade, MAX_CROSSFADE_WIDTH};
+
+#[test]
+fn test_default_matches_quarter_range_linear_fade() {
+    let fade = VelocityCrossfade::default();
+    // Range 0-40: 10 velocities faded at each edge
+    assert_eq!(fade.weight(0, 0, 40), 0.0);
+    assert_eq!(fade.weight(5, 0, 40), 0.5);
+    assert_eq!(fade.weight(20, 0, 40), 1.0);
+    assert_eq!(fade.weight(35, 0, 40), 0.5);
+    assert_eq!(fade.weight(41, 0, 40), 0.0);
+    assert_eq!(fade.weight(2, 0, 4), 1.0, "small ranges never fade");
+}
+
+#[test]
+fn test_equal_power_keeps_overlap_power_constant() {
+    let fade = VelocityCrossfade::new(CrossfadeCurve::EqualPower, 0.25);
+    // Upper edge of 0-40 overlaps the lower edge of 30-70
+    for velocity in 30..=40 {
+        let lower = fade.weight(velocity, 0, 40);
+        let upper = fade.weight(velocity, 30, 70);
+        assert!((lower * lower + upper * upper - 1.0).abs() < 1e-5, "velocity {}", velocity);
+    }
+    // Linear dips to half power in the middle of the same overlap
+    let linear = VelocityCrossfade::default();
+    let (lower, upper) = (linear.weight(35, 0, 40), linear.weight(35, 30, 70));
+    assert!((lower * lower + upper * upper - 0.5).abs() < 1e-5);
+}
+
+#[test]
+fn test_s_curve_and_width() {
+    let fade = VelocityCrossfade::new(CrossfadeCurve::SCurve, 0.25);
+    assert_eq!(fade.weight(5, 0, 40), 0.5);
+    assert!(fade.weight(2, 0, 40) < VelocityCrossfade::default().weight(2, 0, 40));
+
+    let hard = VelocityCrossfade::new(CrossfadeCurve::Linear, 0.0);
+    assert_eq!(hard.weight(0, 0, 40), 1.0);
+    assert_eq!(VelocityCrossfade::new(CrossfadeCurve::Linear, 2.0).width, MAX_CROSSFADE_WIDTH);
+    assert_eq!(CrossfadeCurve::from_name("equalPower"), Some(CrossfadeCurve::EqualPower));
+    assert_eq!(CrossfadeCurve::from_name("cosine"), None);
+}
@@ -0,0 +1,64 @@
+//! Unit tests for the block deadline watchdog (thresholds, rate limiting, bridge wiring)
+
+use awe_synth::audio::deadline::{DeadlineWatchdog, DEFAULT_WARN_LOAD, MAX_WARN_LOAD, MIN_WARN_LOAD};
+use awe_synth::worklet::AudioWorkletBridge;
+
+const BLOCK_MS: f64 = 128.0 / 44100.0 * 1000.0;
+
+#[test]
+fn test_warns_at_threshold_and_counts_overruns() {
+    let mut watchdog = DeadlineWatchdog::new(44100.0);
+    assert!(watchdog.observe(BLOCK_MS * 0.5, 128, 4).is_none());
+    assert!(watchdog.observe(BLOCK_MS * (DEFAULT_WARN_LOAD as f64 - 0.05), 128, 4).is_none());
+
+    let warning = watchdog.observe(BLOCK_MS * 1.2, 128, 12).expect("overrun warns");
+    assert!((warning.load - 1.2).abs() < 1e-3);
+    assert!((warning.budget_ms as f64 - BLOCK_MS).abs() < 1e-3);
+    assert_eq!((warning.frames, warning.active_voices, warning.near_misses, warning.overruns), (128, 12, 1, 1));
+
+    let status = watchdog.status();
+    assert_eq!((status.blocks, status.near_misses, status.overruns, status.warnings), (3, 1, 1, 1));
+    assert!((status.peak_load - 1.2).abs() < 1e-3);
+
+    // Thresholds are clamped, and configuring starts the counters over
+    watchdog.configure(true, 5.0, false);
+    assert_eq!(watchdog.status().warn_load, MAX_WARN_LOAD);
+    assert_eq!(watchdog.status().blocks, 0);
+    watchdog.configure(true, 0.0, false);
+    assert_eq!(watchdog.status().warn_load, MIN_WARN_LOAD);
+
+    watchdog.configure(false, DEFAULT_WARN_LOAD, false);
+    assert!(watchdog.observe(BLOCK_MS * 2.0, 128, 0).is_none());
+    assert_eq!(watchdog.status().blocks, 0, "a disabled watchdog measures nothing");
+}
+
+#[test]
+fn test_warnings_are_held_off_for_a_second_of_audio() {
+    let mut watchdog = DeadlineWatchdog::new(44100.0);
+    assert!(watchdog.observe(BLOCK_MS * 0.8, 128, 0).is_some());
+
+    // The next second of audio (345 blocks of 128 frames): near misses are only counted
+    for block in 0..345 {
+        let elapsed = if block % 2 == 0 { BLOCK_MS * 0.9 } else { BLOCK_MS * 1.1 };
+        assert!(watchdog.observe(elapsed, 128, 0).is_none());
+    }
+    let warning = watchdog.observe(BLOCK_MS * 0.75, 128, 0).expect("warns again after the hold");
+    assert_eq!((warning.near_misses, warning.overruns), (346, 172), "the suppressed blocks are reported with it");
+    assert_eq!(watchdog.status().warnings, 2);
+}
+
+#[test]
+fn test_bridge_times_rendered_blocks() {
+    let mut bridge = AudioWorkletBridge::new(44100.0);
+    for _ in 0..4 {
+        assert_eq!(bridge.render_stereo_block(128), 128);
+    }
+    let status = bridge.deadline_status();
+    assert_eq!(status.blocks, 4);
+    assert!(status.enabled && !status.scale_quality);
+    assert!(bridge.get_deadline_status().contains("\"warnLoad\":0.7"));
+
+    bridge.set_deadline_watchdog(false, DEFAULT_WARN_LOAD, false);
+    bridge.render_stereo_block(128);
+    assert_eq!(bridge.deadline_status().blocks, 0);
+}
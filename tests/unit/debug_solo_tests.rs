@@ -0,0 +1,38 @@
+//! Unit tests for mixer-stage voice/channel solo
+
+use awe_synth::synth::voice_manager::{DebugSolo, VoiceManager};
+
+fn peak(manager: &mut VoiceManager, samples: usize) -> f32 {
+    (0..samples).fold(0.0f32, |peak, _| {
+        let (left, right) = manager.process();
+        peak.max(left.abs()).max(right.abs())
+    })
+}
+
+#[test]
+fn test_solo_parsing() {
+    assert_eq!(DebugSolo::from_parts("off", 99), Some(DebugSolo::Off));
+    assert_eq!(DebugSolo::from_parts("voice", 31), Some(DebugSolo::Voice(31)));
+    assert_eq!(DebugSolo::from_parts("voice", 32), None);
+    assert_eq!(DebugSolo::from_parts("channel", 15), Some(DebugSolo::Channel(15)));
+    assert_eq!(DebugSolo::from_parts("channel", 16), None);
+    assert_eq!(DebugSolo::from_parts("zone", 0), None);
+}
+
+#[test]
+fn test_solo_mutes_other_voices_without_stopping_them() {
+    let mut manager = VoiceManager::new(44100.0);
+    let piano = manager.note_on(60, 100, 0).unwrap();
+    assert!(manager.note_on(64, 100, 1).is_some());
+
+    manager.set_debug_solo(DebugSolo::Channel(5));
+    assert_eq!(peak(&mut manager, 512), 0.0, "nothing plays on the soloed channel");
+    assert_eq!(manager.get_active_voice_count(), 2, "muted voices keep running");
+
+    manager.set_debug_solo(DebugSolo::Channel(1));
+    assert!(peak(&mut manager, 512) > 0.0);
+    manager.set_debug_solo(DebugSolo::Voice(piano));
+    assert!(peak(&mut manager, 512) > 0.0);
+    manager.set_debug_solo(DebugSolo::Off);
+    assert_eq!(manager.get_debug_solo(), DebugSolo::Off);
+}
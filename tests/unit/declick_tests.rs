@@ -0,0 +1,33 @@
+//! Unit tests for click-free voice shutdown (All Sound Off)
+
+use awe_synth::synth::voice_manager::VoiceManager;
+
+#[test]
+fn test_all_sound_off_fades_instead_of_cutting() {
+    let mut manager = VoiceManager::new(44100.0);
+    assert!(manager.note_on(60, 127, 0).is_some());
+    assert!(manager.note_on(64, 127, 1).is_some());
+    let mut peak_before = 0.0f32;
+    for _ in 0..4410 {
+        peak_before = peak_before.max(manager.process().0.abs());
+    }
+    assert!(peak_before > 0.01);
+
+    manager.all_sound_off(0);
+    // Channel 1 keeps sounding
+    assert_eq!(manager.channel_telemetry()[1].active_voices, 1);
+
+    // Early in the fade the note is still clearly audible...
+    let mut peak_fading = 0.0f32;
+    for _ in 0..44 {
+        peak_fading = peak_fading.max(manager.process().0.abs());
+    }
+    assert!(peak_fading > 0.2 * peak_before, "cut to {} from {}", peak_fading, peak_before);
+
+    // ...and gone a few milliseconds later
+    for _ in 0..441 {
+        manager.process();
+    }
+    let telemetry = manager.channel_telemetry();
+    assert_eq!((telemetry[0].active_voices, telemetry[1].active_voices), (0, 1));
+}
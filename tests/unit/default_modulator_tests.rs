@@ -0,0 +1,55 @@
+//! Unit tests for SoundFont modulator curves and the default velocity modulators
+
+use awe_synth::soundfont::modulators::*;
+
+#[test]
+fn test_curve_shapes() {
+    let concave = SourceCurve { curve: CurveType::Concave, negative: false, bipolar: false };
+    let convex = SourceCurve { curve: CurveType::Convex, negative: false, bipolar: false };
+    assert_eq!((concave.map(0.0), concave.map(1.0)), (0.0, 1.0));
+    assert!(concave.map(0.5) < 0.5 && convex.map(0.5) > 0.5);
+    assert!((concave.map(0.3) + convex.map(0.7) - 1.0).abs() < 1e-6, "convex mirrors concave");
+
+    let switch = SourceCurve { curve: CurveType::Switch, negative: true, bipolar: true };
+    assert_eq!((switch.map(0.0), switch.map(1.0)), (1.0, -1.0));
+}
+
+#[test]
+fn test_default_velocity_modulators() {
+    // Full velocity: no attenuation, cutoff untouched
+    assert_eq!(velocity_attenuation_cb(1.0), 0.0);
+    assert_eq!(velocity_filter_cents(1.0), 0.0);
+
+    // Concave attenuation is a squared amplitude curve
+    for velocity in [0.25f32, 0.5, 0.8] {
+        let gain = 10.0_f32.powf(-velocity_attenuation_cb(velocity) / 200.0);
+        assert!((gain - velocity * velocity).abs() < 1e-4, "velocity {} gain {}", velocity, gain);
+    }
+    assert_eq!(velocity_attenuation_cb(0.0), VELOCITY_TO_ATTENUATION_CB);
+
+    // Linear cutoff: one octave down at half velocity, two at zero
+    assert!((velocity_filter_cents(0.5) + 1200.0).abs() < 1e-3);
+    assert_eq!(velocity_filter_cents(0.0), VELOCITY_TO_FILTER_FC_CENTS);
+}
+
+#[test]
+fn test_velocity_reaches_only_attenuation_and_cutoff() {
+    use awe_synth::synth::fallback_bank::build_fallback_soundfont;
+    use awe_synth::synth::multizone_voice::MultiZoneSampleVoice;
+
+    let bank = build_fallback_soundfont(44100.0);
+    let mut soft = MultiZoneSampleVoice::new(0, 44100.0);
+    let mut hard = MultiZoneSampleVoice::new(1, 44100.0);
+    soft.start_note(60, 20, 0, &bank, &bank.presets[0]).unwrap();
+    hard.start_note(60, 127, 0, &bank, &bank.presets[0]).unwrap();
+    assert!(soft.get_modulated_cutoff() < hard.get_modulated_cutoff());
+
+    // LFOs and the modulation envelope run the same whatever the velocity
+    for _ in 0..4410 {
+        soft.process();
+        hard.process();
+        assert_eq!(soft.get_lfo1_level(), hard.get_lfo1_level());
+        assert_eq!(soft.get_lfo2_level(), hard.get_lfo2_level());
+        assert_eq!(soft.get_modulation_envelope_level(), hard.get_modulation_envelope_level());
+    }
+}
@@ -0,0 +1,100 @@
+//! Unit tests for device profiles (detection, bridge configuration, polyphony/interpolation/effects)
+
+use awe_synth::synth::device_profile::{DeviceProfile, EffectsQuality, InterpolationQuality};
+use awe_synth::synth::voice_manager::{VoiceManager, MAX_VOICES};
+use awe_synth::worklet::AudioWorkletBridge;
+
+#[test]
+fn test_detection_thresholds_and_names() {
+    assert_eq!(DeviceProfile::detect(8, 8), DeviceProfile::Desktop);
+    assert_eq!(DeviceProfile::detect(16, 4), DeviceProfile::Mid);
+    assert_eq!(DeviceProfile::detect(4, 2), DeviceProfile::LowEnd);
+    for profile in [DeviceProfile::LowEnd, DeviceProfile::Mid, DeviceProfile::Desktop, DeviceProfile::Live] {
+        assert_eq!(DeviceProfile::from_name(profile.name()), Some(profile));
+    }
+    assert_eq!(DeviceProfile::from_name("mobile"), Some(DeviceProfile::LowEnd));
+    assert_eq!(DeviceProfile::Desktop.settings().max_polyphony, MAX_VOICES);
+}
+
+#[test]
+fn test_bridge_applies_detected_and_explicit_profiles() {
+    let mut bridge = AudioWorkletBridge::new(44100.0);
+    assert_eq!(bridge.device_profile_status().profile, None);
+
+    bridge.set_device_info(2, 2);
+    let status = bridge.device_profile_status();
+    assert_eq!(status.profile, Some(DeviceProfile::LowEnd));
+    assert_eq!(status.settings, DeviceProfile::LowEnd.settings());
+
+    assert!(bridge.set_device_profile("desktop"));
+    assert!(!bridge.device_profile_status().auto_detect);
+    bridge.set_device_info(2, 2);
+    assert_eq!(bridge.device_profile_status().profile, Some(DeviceProfile::Desktop), "explicit choice sticks");
+
+    assert!(bridge.set_device_profile("auto"));
+    assert_eq!(bridge.device_profile_status().profile, Some(DeviceProfile::LowEnd));
+    assert!(!bridge.set_device_profile("server"));
+
+    let json = bridge.get_device_profile();
+    assert!(json.contains("\"profile\":\"lowEnd\""));
+    assert!(json.contains("\"effects\":\"reduced\""));
+}
+
+#[test]
+fn test_live_profile_drops_render_ahead() {
+    let mut bridge = AudioWorkletBridge::new(44100.0);
+    bridge.set_render_ahead(2);
+    assert!(bridge.set_device_profile("live"));
+    let status = bridge.device_profile_status();
+    assert_eq!(status.profile, Some(DeviceProfile::Live));
+    assert_eq!(status.settings.effects, EffectsQuality::Off);
+    assert_eq!(status.settings.buffer_size, 128);
+    assert_eq!(bridge.render_ahead_status().depth_blocks, 0);
+
+    // Underruns no longer buy slack with latency
+    for _ in 0..64 {
+        bridge.render_stereo_block(128);
+        bridge.record_underrun();
+    }
+    assert_eq!(bridge.render_ahead_status().depth_blocks, 0);
+
+    bridge.set_device_info(2, 2);
+    assert_eq!(bridge.device_profile_status().profile, Some(DeviceProfile::Live), "never auto-detected away");
+    assert!(bridge.set_device_profile("auto"));
+    assert_eq!(bridge.device_profile_status().profile, Some(DeviceProfile::LowEnd));
+}
+
+#[test]
+fn test_polyphony_limit_steals_within_limit() {
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_max_polyphony(4);
+    let voices: Vec<usize> = (0..6).filter_map(|index| manager.note_on(60 + index, 100, 0)).collect();
+    assert_eq!(voices.len(), 6, "notes over the limit steal");
+    assert!(voices.iter().all(|&voice| voice < 4));
+    assert_eq!(manager.get_active_voice_count(), 4);
+
+    manager.set_max_polyphony(0);
+    assert_eq!(manager.max_polyphony(), 1);
+}
+
+#[test]
+fn test_interpolation_and_effects_quality() {
+    let render = |interpolation, effects| {
+        let mut manager = VoiceManager::new(44100.0);
+        manager.set_interpolation(interpolation);
+        manager.set_effects_quality(effects);
+        manager.process_midi_control_change(0, 91, 127);
+        manager.note_on(84, 100, 0);
+        (0..4096).map(|_| manager.process().0).collect::<Vec<f32>>()
+    };
+    let linear = render(InterpolationQuality::Linear, EffectsQuality::Full);
+    let cubic = render(InterpolationQuality::Cubic, EffectsQuality::Full);
+    assert!(cubic.iter().all(|sample| sample.is_finite()));
+    assert_ne!(linear, cubic);
+    let peak = |samples: &[f32]| samples.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+    assert!((peak(&linear) - peak(&cubic)).abs() < 0.1 * peak(&linear), "same level either way");
+
+    let dry = render(InterpolationQuality::Linear, EffectsQuality::Off);
+    let reduced = render(InterpolationQuality::Linear, EffectsQuality::Reduced);
+    assert_ne!(dry, reduced, "reduced quality keeps the reverb");
+}
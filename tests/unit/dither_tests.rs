@@ -0,0 +1,67 @@
+//! Unit tests for dithered 16-bit export
+
+use awe_synth::audio::dither::Dither;
+use awe_synth::audio::export::{encode, encode_with_options, ExportFormat, ExportOptions};
+use awe_synth::audio::wav::WavFormat;
+
+const PCM16: ExportFormat = ExportFormat::Wav(WavFormat::Pcm16);
+
+fn pcm16_samples(wav: &[u8]) -> Vec<i16> {
+    wav[44..].chunks(2).map(|b| i16::from_le_bytes([b[0], b[1]])).collect()
+}
+
+/// Sine well below one LSB
+fn quiet_sine(frames: usize, lsbs: f32) -> Vec<f32> {
+    (0..frames).map(|i| (i as f32 * 0.05).sin() * lsbs / 32767.0).collect()
+}
+
+#[test]
+fn test_options_parse_and_default_to_plain_rounding() {
+    assert_eq!(ExportOptions::default(), ExportOptions { dither: false, noise_shaping: false, ..ExportOptions::default() });
+    assert_eq!(ExportOptions::from_json(r#"{"dither": true}"#), Some(ExportOptions { dither: true, noise_shaping: false, ..ExportOptions::default() }));
+    assert_eq!(ExportOptions::from_json(r#"{"dither": true, "noiseShaping": true}"#), Some(ExportOptions { dither: true, noise_shaping: true, ..ExportOptions::default() }));
+    assert_eq!(ExportOptions::from_json("{}"), Some(ExportOptions::default()));
+    assert_eq!(ExportOptions::from_json("dither"), None);
+
+    let samples = [0.0, 0.5, -0.25, 1.0];
+    assert_eq!(encode_with_options(PCM16, 44100, 2, &samples, ExportOptions::default()), encode(PCM16, 44100, 2, &samples));
+}
+
+#[test]
+fn test_dither_keeps_sub_lsb_detail() {
+    let samples = quiet_sine(20000, 0.4);
+    let plain = pcm16_samples(&encode(PCM16, 44100, 1, &samples));
+    assert!(plain.iter().all(|&value| value == 0), "0.4 LSB rounds away without dither");
+
+    let options = ExportOptions { dither: true, noise_shaping: false, ..ExportOptions::default() };
+    let dithered = pcm16_samples(&encode_with_options(PCM16, 44100, 1, &samples, options));
+    assert!(dithered.iter().all(|&value| value.abs() <= 2));
+    // The signal survives as the average of the noise
+    let correlation: f32 = dithered.iter().zip(&samples).map(|(&value, &sample)| value as f32 * sample).sum();
+    assert!(correlation > 0.0, "dithered output should follow the signal: {}", correlation);
+
+    // Seeded: the same buffer encodes to the same bytes
+    assert_eq!(encode_with_options(PCM16, 44100, 1, &samples, options), encode_with_options(PCM16, 44100, 1, &samples, options));
+}
+
+#[test]
+fn test_noise_shaping_moves_noise_up() {
+    let silence = vec![0.0; 20000];
+    // Energy after an eight-sample moving sum (a crude low-pass)
+    let low_band = |shaping: bool| -> f32 {
+        let mut dither = Dither::new(shaping);
+        let values: Vec<f32> = silence.iter().map(|&sample| dither.quantize(sample, 0, 16) as f32).collect();
+        values.windows(8).map(|window| window.iter().sum::<f32>().powi(2)).sum()
+    };
+    assert!(low_band(true) < low_band(false) * 0.5);
+}
+
+#[test]
+fn test_only_pcm16_is_dithered() {
+    let samples = quiet_sine(64, 0.4);
+    let options = ExportOptions { dither: true, noise_shaping: true, ..ExportOptions::default() };
+    let pcm24 = ExportFormat::Wav(WavFormat::Pcm24);
+    assert_eq!(encode_with_options(pcm24, 48000, 1, &samples, options), encode(pcm24, 48000, 1, &samples));
+    let float = ExportFormat::Wav(WavFormat::Float32);
+    assert_eq!(encode_with_options(float, 48000, 1, &samples, options), encode(float, 48000, 1, &samples));
+}
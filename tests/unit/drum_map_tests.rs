@@ -0,0 +1,46 @@
+//! Unit tests for drum kit key maps (GM percussion names and covered keys)
+
+use awe_synth::midi::gm_names;
+use awe_synth::soundfont::drum_map::map_drum_kit;
+use awe_synth::soundfont::types::KeyRange;
+use awe_synth::synth::fallback_bank::{build_fallback_soundfont, FALLBACK_DRUM_PRESET_INDEX};
+use awe_synth::synth::voice_manager::VoiceManager;
+
+#[test]
+fn test_gm_drum_note_names() {
+    assert_eq!(gm_names::gm_drum_note_name(34), None);
+    assert_eq!(gm_names::gm_drum_note_name(35), Some("Acoustic Bass Drum"));
+    assert_eq!(gm_names::gm_drum_note_name(42), Some("Closed Hi-Hat"));
+    assert_eq!(gm_names::gm_drum_note_name(81), Some("Open Triangle"));
+    assert_eq!(gm_names::gm_drum_note_name(82), None);
+}
+
+#[test]
+fn test_fallback_kit_covers_the_gm_map() {
+    let manager = VoiceManager::new(44100.0);
+    let map = manager.drum_kit_map(128, 0).unwrap();
+    assert_eq!(map.preset_index, FALLBACK_DRUM_PRESET_INDEX);
+    assert_eq!(map.covered_keys, 61, "keys 27-87");
+    assert_eq!(map.silent_gm_keys, 0);
+    assert_eq!(map.keys.first().map(|key| (key.key, key.gm_name.clone())), Some((27, None)));
+    let kick = map.keys.iter().find(|key| key.key == 36).unwrap();
+    assert_eq!(kick.gm_name.as_deref(), Some("Bass Drum 1"));
+    assert!(kick.covered && kick.sample.is_some());
+    assert!(manager.drum_kit_map(128, 9).is_some(), "unknown kits fall back to the standard kit");
+}
+
+#[test]
+fn test_uncovered_keys_are_listed_as_silent() {
+    let mut bank = build_fallback_soundfont(44100.0);
+    let kit = FALLBACK_DRUM_PRESET_INDEX;
+    let instrument = bank.presets[kit].preset_zones[0].instrument_id.unwrap() as usize;
+    bank.instruments[instrument].instrument_zones.retain(|zone| zone.key_range.as_ref().is_some_and(|range| range.low != 37));
+    bank.presets[kit].preset_zones[0].key_range = Some(KeyRange { low: 0, high: 79 });
+
+    let map = map_drum_kit(&bank, kit, &bank.presets[kit]);
+    let silent: Vec<u8> = map.keys.iter().filter(|key| !key.covered).map(|key| key.key).collect();
+    assert_eq!(silent, vec![37, 38, 39, 40, 80, 81], "snare zone removed, preset range ends at 79");
+    assert_eq!(map.silent_gm_keys, 6);
+    assert!(map.keys.iter().all(|key| key.covered || key.gm_name.is_some()));
+    assert!(!serde_json::to_string(&map).unwrap().contains(r#""sample":null"#));
+}
@@ -0,0 +1,56 @@
+//! Unit tests for the controller to effect parameter map
+
+use awe_synth::effects::reverb::ReverbProcessor;
+use awe_synth::midi::effects_cc_map::{EffectsCcMap, EffectsParameter};
+use awe_synth::synth::voice_manager::VoiceManager;
+
+#[test]
+fn test_map_scales_and_refuses_fixed_controllers() {
+    let mut map = EffectsCcMap::default();
+    assert!(map.set(95, EffectsParameter::ReverbRoomSize, 0.2, 1.0));
+    assert!(map.set(94, EffectsParameter::ChorusRate, 8.0, 0.0)); // Reversed, clamped to 0.1
+    assert!(!map.set(7, EffectsParameter::ChorusDepth, 0.0, 1.0), "CC7 is the channel volume");
+    assert!(!map.set(91, EffectsParameter::ReverbReturn, 0.0, 1.0), "CC91 is the reverb send");
+    assert!(!map.set(20, EffectsParameter::ChorusDepth, f32::NAN, 1.0));
+
+    let room = map.get(95).unwrap();
+    assert_eq!((room.value(0), room.value(127)), (0.2, 1.0));
+    let rate = map.get(94).unwrap();
+    assert_eq!(rate.value(0), 8.0);
+    assert!((rate.value(127) - 0.1).abs() < 1e-6);
+    assert_eq!(map.mappings().iter().map(|mapping| mapping.controller).collect::<Vec<_>>(), vec![94, 95]);
+
+    assert!(map.remove(94));
+    assert!(!map.remove(94));
+    assert!(map.get(94).is_none());
+}
+
+#[test]
+fn test_mapped_controllers_drive_the_effects() {
+    let mut manager = VoiceManager::new(44100.0);
+    assert!(!manager.apply_effects_controller(95, 127), "nothing is mapped by default");
+
+    manager.effects_cc_map_mut().set(95, EffectsParameter::ReverbRoomSize, 0.0, 1.0);
+    manager.effects_cc_map_mut().set(94, EffectsParameter::ChorusDepth, 0.0, 1.0);
+    assert!(manager.apply_effects_controller(95, 127));
+    assert!(manager.apply_effects_controller(94, 0));
+    assert_eq!(manager.effects_parameter(EffectsParameter::ReverbRoomSize), 1.0);
+    assert_eq!(manager.effects_parameter(EffectsParameter::ChorusDepth), 0.0);
+}
+
+#[test]
+fn test_room_size_retunes_taps_in_place() {
+    let mut reverb = ReverbProcessor::new(44100.0, 0.5, 0.3, 0.7);
+    let lengths: Vec<usize> = reverb.delay_lines.iter().map(|line| line.buffer.len()).collect();
+    let taps = |reverb: &ReverbProcessor| reverb.delay_lines.iter().map(|line| line.delay_samples).collect::<Vec<_>>();
+    let medium = taps(&reverb);
+
+    reverb.set_room_size(0.0);
+    let small = taps(&reverb);
+    reverb.set_room_size(1.0);
+    assert_eq!(reverb.delay_lines.len(), 6, "no taps are added");
+    assert_eq!(reverb.delay_lines.iter().map(|line| line.buffer.len()).collect::<Vec<_>>(), lengths, "buffers are kept");
+    assert_eq!(small[0], 882, "20 ms base delay");
+    assert!(small.iter().zip(&medium).all(|(small, medium)| small < medium));
+    assert_eq!(taps(&reverb), lengths, "the largest room uses the whole buffer");
+}
@@ -0,0 +1,64 @@
+//! Unit tests for flushing reverb and chorus tails
+
+use awe_synth::effects::chorus::ChorusBus;
+use awe_synth::effects::reverb::ReverbBus;
+use awe_synth::synth::voice_manager::VoiceManager;
+
+/// Feed an impulse train through `process` and report the largest output afterwards
+fn ring<F: FnMut(f32) -> f32>(mut process: F, input_frames: usize, tail_frames: usize) -> f32 {
+    for i in 0..input_frames {
+        process(if i % 97 == 0 { 1.0 } else { 0.0 });
+    }
+    (0..tail_frames).map(|_| process(0.0).abs()).fold(0.0, f32::max)
+}
+
+#[test]
+fn test_reverb_flush_silences_the_tail() {
+    let mut bus = ReverbBus::new(44100.0);
+    bus.set_channel_send(0, 1.0);
+    let tail = ring(|x| { bus.add_voice_send(x, 1.0, 0); bus.process_reverb() }, 44100, 256);
+    assert!(tail > 0.0, "reverb rings after its input stops");
+
+    bus.add_voice_send(1.0, 1.0, 0); // Pending send is dropped too
+    bus.flush();
+    for _ in 0..44100 {
+        assert_eq!(bus.process_reverb(), 0.0);
+    }
+    assert!(ring(|x| { bus.add_voice_send(x, 1.0, 0); bus.process_reverb() }, 44100, 256) > 0.0, "still works afterwards");
+}
+
+#[test]
+fn test_chorus_flush_silences_the_delay_lines() {
+    let mut bus = ChorusBus::new(44100.0);
+    bus.set_channel_send(3, 1.0);
+    let tail = ring(|x| { bus.add_voice_send(x, 1.0, 3); bus.process_chorus() }, 4410, 64);
+    assert!(tail > 0.0);
+
+    bus.flush();
+    for _ in 0..4410 {
+        assert_eq!(bus.process_chorus(), 0.0);
+    }
+}
+
+#[test]
+fn test_voice_manager_flush_leaves_silence_once_notes_are_cut() {
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_fallback_bank_enabled(true);
+    manager.process_midi_control_change(0, 91, 127);
+    manager.settle_smoothing();
+    assert!(manager.note_on(60, 127, 0).is_some());
+    for _ in 0..22050 {
+        manager.process();
+    }
+    manager.all_sound_off(0);
+    while manager.get_active_voice_count() > 0 {
+        manager.process();
+    }
+    let (left, right) = manager.process();
+    assert!(left != 0.0 || right != 0.0, "reverb tail outlives the voice");
+
+    manager.flush_effects();
+    for _ in 0..4410 {
+        assert_eq!(manager.process(), (0.0, 0.0));
+    }
+}
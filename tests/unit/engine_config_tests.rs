@@ -0,0 +1,62 @@
+//! Unit tests for startup configuration documents
+
+use awe_synth::config::{ChorusConfig, EngineConfig};
+use awe_synth::synth::device_profile::{DeviceProfile, EffectsQuality};
+use awe_synth::synth::velocity_map::VelocityMap;
+use awe_synth::worklet::AudioWorkletBridge;
+
+#[test]
+fn test_config_parses_json_with_defaults_for_effect_fields() {
+    let config = EngineConfig::parse(r#"{
+        "sampleRate": 48000,
+        "deviceProfile": "mid",
+        "polyphony": 12,
+        "chorus": { "rate": 0.5 },
+        "velocityCurve": { "min": 20, "max": 110, "curve": 0.7 },
+        "systemMode": "gs",
+        "fallbackBank": false
+    }"#).unwrap();
+    assert_eq!(config.sample_rate, Some(48000.0));
+    assert_eq!(config.chorus, Some(ChorusConfig { rate: 0.5, ..ChorusConfig::default() }));
+    assert_eq!(config.velocity_curve, Some(VelocityMap { min: 20, max: 110, curve: 0.7 }));
+    assert!(config.reverb.is_none(), "absent sections leave the engine alone");
+    assert_eq!(EngineConfig::parse("{}"), Ok(EngineConfig::default()));
+}
+
+#[test]
+fn test_config_refuses_unknown_fields_and_bad_values() {
+    let error = |text: &str| EngineConfig::parse(text).unwrap_err();
+    assert!(error(r#"{"polyfony": 8}"#).contains("unknown field"));
+    assert!(error(r#"{"reverb": {"size": 0.5}}"#).contains("unknown field"));
+    assert_eq!(error(r#"{"polyphony": 0}"#), "polyphony must be 1-32");
+    assert_eq!(error(r#"{"deviceProfile": "tablet"}"#), "deviceProfile must be lowEnd, mid, desktop, live or auto");
+    assert_eq!(error(r#"{"reverb": {"damping": 1.5}}"#), "reverb.damping must be 0.0-1.0");
+    assert_eq!(error(r#"{"velocityCurve": {"min": 100, "max": 20, "curve": 1}}"#), "velocityCurve needs 1 <= min <= max <= 127");
+    assert_eq!(error(r#"{"systemMode": "mt32"}"#), "systemMode must be gm, gm2, gs or xg");
+    assert_eq!(error(r#"{"sampleRate": 1000}"#), "sampleRate must be 8000-192000 Hz");
+    #[cfg(not(feature = "toml"))]
+    assert!(error("polyphony = 8").contains("toml feature"));
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_config_parses_toml() {
+    let config = EngineConfig::parse("sampleRate = 44100.0\ndeviceProfile = \"live\"\n\n[reverb]\nroomSize = 0.8\n").unwrap();
+    assert_eq!(config.device_profile.as_deref(), Some("live"));
+    assert_eq!(config.reverb.unwrap().room_size, 0.8);
+}
+
+#[test]
+fn test_config_polyphony_caps_the_device_profile() {
+    let mut bridge = AudioWorkletBridge::new(44100.0);
+    EngineConfig::parse(r#"{"deviceProfile": "desktop", "polyphony": 10}"#).unwrap().apply(&mut bridge);
+    let status = bridge.device_profile_status();
+    assert_eq!(status.profile, Some(DeviceProfile::Desktop));
+    assert_eq!(status.settings.max_polyphony, 10);
+    assert_eq!(status.settings.effects, EffectsQuality::Full);
+
+    // A later profile keeps the cap; profiles with fewer voices stay below it
+    EngineConfig::parse(r#"{"deviceProfile": "lowEnd"}"#).unwrap().apply(&mut bridge);
+    let low_end = DeviceProfile::LowEnd.settings().max_polyphony;
+    assert_eq!(bridge.device_profile_status().settings.max_polyphony, low_end.min(10));
+}
@@ -0,0 +1,43 @@
+//! Unit tests for the enhancements flag (heuristics beyond documented EMU8000 behavior)
+
+use awe_synth::synth::mod_wheel::ModWheelVibrato;
+use awe_synth::synth::voice_manager::VoiceManager;
+
+/// Half a second of vibrato after a +2 semitone bend that returns to center before any audio
+fn render_after_bend(enhancements: Option<bool>) -> Vec<(f32, f32)> {
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_mod_wheel_vibrato(Some(0), Some(ModWheelVibrato::default()));
+    manager.set_modulation_wheel(0, 127);
+    assert!(manager.note_on(69, 100, 0).is_some());
+    if let Some(enabled) = enhancements {
+        manager.set_enhancements(enabled);
+        manager.apply_pitch_bend(0, 2.0);
+        manager.apply_pitch_bend(0, 0.0);
+    }
+    (0..22050).map(|_| manager.process()).collect()
+}
+
+#[test]
+fn test_enhancements_default_on() {
+    assert!(VoiceManager::new(44100.0).get_enhancements());
+}
+
+#[test]
+fn test_authentic_mode_leaves_vibrato_rate_alone() {
+    let unbent = render_after_bend(None);
+    assert_ne!(render_after_bend(Some(true)), unbent, "enhanced bend speeds the vibrato up");
+    assert_eq!(render_after_bend(Some(false)), unbent);
+}
+
+#[test]
+fn test_disabling_restores_the_rate_of_a_bent_voice() {
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_mod_wheel_vibrato(Some(0), Some(ModWheelVibrato::default()));
+    manager.set_modulation_wheel(0, 127);
+    assert!(manager.note_on(69, 100, 0).is_some());
+    manager.apply_pitch_bend(0, 2.0);
+    manager.apply_pitch_bend(0, 0.0);
+    manager.set_enhancements(false);
+    assert!(!manager.get_enhancements());
+    assert_eq!((0..22050).map(|_| manager.process()).collect::<Vec<_>>(), render_after_bend(None));
+}
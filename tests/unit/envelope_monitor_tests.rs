@@ -0,0 +1,81 @@
+//! Unit tests for the envelope monitor (stage progress, refresh rate, packed voice buffer)
+
+use awe_synth::synth::envelope::{DAHDSREnvelope, EnvelopeState};
+use awe_synth::synth::envelope_monitor::{EnvelopeMonitor, ENVELOPE_STRIDE, MAX_MONITOR_RATE_HZ};
+use awe_synth::synth::voice_manager::VoiceManager;
+use awe_synth::worklet::AudioWorkletBridge;
+
+#[test]
+fn test_stage_progress_tracks_each_stage() {
+    // Attack of 1200 timecents = 2 s; everything else minimal
+    let mut envelope = DAHDSREnvelope::new(1000.0, -12000, 1200, -12000, -12000, 100, -12000);
+    assert_eq!(envelope.stage_progress(), 0.0);
+    envelope.trigger();
+    while envelope.state == EnvelopeState::Delay {
+        envelope.process();
+    }
+    for _ in 0..1000 {
+        envelope.process();
+    }
+    assert_eq!(envelope.state, EnvelopeState::Attack);
+    assert!((envelope.stage_progress() - 0.5).abs() < 0.01);
+
+    while envelope.state != EnvelopeState::Sustain {
+        envelope.process();
+    }
+    assert_eq!(envelope.stage_progress(), 0.0, "sustain has no length");
+}
+
+#[test]
+fn test_refresh_rate_follows_rendered_audio() {
+    let mut monitor = EnvelopeMonitor::new();
+    assert!(!monitor.advance(128, 44100.0), "off by default");
+    assert!(!monitor.set_rate(-1.0));
+    assert!(!monitor.set_rate(f32::NAN));
+    assert!(monitor.set_rate(1000.0));
+    assert_eq!(monitor.rate(), MAX_MONITOR_RATE_HZ);
+
+    monitor.set_rate(100.0);
+    let due = (0..44100 / 128).filter(|_| monitor.advance(128, 44100.0)).count();
+    assert!((99..=101).contains(&due), "{} refreshes in one second", due);
+}
+
+#[test]
+fn test_voice_envelopes_report_sounding_voices() {
+    let mut manager = VoiceManager::new(44100.0);
+    let voice = manager.note_on(60, 100, 3).unwrap();
+    for _ in 0..2000 {
+        manager.process();
+    }
+    let envelopes = manager.voice_envelopes();
+    let sounding = envelopes[voice];
+    assert!(sounding.active);
+    assert_eq!((sounding.voice, sounding.note, sounding.channel), (voice, 60, 3));
+    assert_ne!(sounding.stage, EnvelopeState::Off);
+    assert!(sounding.level > 0.0);
+    assert!(envelopes.iter().filter(|envelope| envelope.active).count() == 1);
+    assert!(serde_json::to_string(&sounding).unwrap().contains("\"stage\":\""));
+}
+
+#[test]
+fn test_bridge_packs_at_the_set_rate() {
+    let mut bridge = AudioWorkletBridge::new(44100.0);
+    bridge.queue_midi_event(0, 2, 0x92, 64, 100);
+    let mut block = [0.0f32; 256];
+    bridge.process_stereo_into(&mut block);
+    assert_eq!(bridge.envelope_monitor().updates(), 0, "nothing packed while off");
+
+    assert!(bridge.set_envelope_monitor_rate(50.0));
+    for _ in 0..345 {
+        bridge.process_stereo_into(&mut block);
+    }
+    let updates = bridge.envelope_monitor_updates();
+    assert!((49..=51).contains(&updates), "{} refreshes in one second", updates);
+
+    // The note is on one voice: stage, progress, level, note, channel
+    let buffer = bridge.envelope_monitor().buffer();
+    let voice = buffer.chunks_exact(ENVELOPE_STRIDE).find(|slot| slot[0] != 0.0).expect("a sounding voice");
+    assert_eq!(voice[0], EnvelopeState::Decay as u8 as f32, "piano decays for ~3 s");
+    assert!(voice[2] > 0.0);
+    assert_eq!((voice[3], voice[4]), (64.0, 2.0));
+}
@@ -0,0 +1,67 @@
+//! Unit tests for the table-driven DAHDSR envelope
+
+use awe_synth::synth::envelope::{CurveSegment, DAHDSREnvelope, EnvelopeState, MIN_RELEASE_SECONDS};
+
+#[test]
+fn test_curve_segments_match_closed_form() {
+    let length = 48_000;
+    let mut attack = CurveSegment::ease_in(length);
+    let mut release = CurveSegment::ease_out(length);
+    for n in 0..length {
+        let p = n as f32 / length as f32;
+        let expected_in = p * p;
+        let expected_out = 1.0 - (1.0 - p) * (1.0 - p);
+        assert!((attack.advance() - expected_in).abs() < 1e-5, "ease_in drifted at {}", n);
+        assert!((release.advance() - expected_out).abs() < 1e-5, "ease_out drifted at {}", n);
+    }
+}
+
+#[test]
+fn test_long_release_flushes_to_zero() {
+    // 1ms stages, full sustain, ~8s release
+    let mut envelope = DAHDSREnvelope::new(44100.0, -12000, -12000, -12000, -12000, 0, 3600);
+    envelope.trigger();
+    for _ in 0..1000 {
+        envelope.process();
+    }
+    assert_eq!(envelope.state, EnvelopeState::Sustain);
+
+    envelope.release();
+    let mut samples = 0;
+    while envelope.state != EnvelopeState::Off {
+        let level = envelope.process();
+        assert!(level == 0.0 || level >= 0.00001, "sub-audible level {} not flushed", level);
+        samples += 1;
+        assert!(samples <= envelope.release_samples + 1);
+    }
+}
+
+#[test]
+fn test_release_while_silent_ends_immediately() {
+    let mut envelope = DAHDSREnvelope::new(44100.0, 0, -12000, -12000, -12000, 0, 1200);
+    envelope.trigger();
+    envelope.release(); // Still in delay at level 0
+    assert_eq!(envelope.state, EnvelopeState::Off);
+}
+
+#[test]
+fn test_short_release_is_held_to_the_floor() {
+    // 1ms release generator: below the anti-click floor
+    let mut envelope = DAHDSREnvelope::new(44100.0, -12000, -12000, -12000, -12000, 0, -12000);
+    envelope.trigger();
+    for _ in 0..500 {
+        envelope.process();
+    }
+    envelope.release();
+    assert_eq!(envelope.release_samples, envelope.min_release_samples);
+
+    let mut previous = envelope.current_level;
+    let mut samples = 0;
+    while envelope.state != EnvelopeState::Off {
+        let level = envelope.process();
+        assert!(previous - level < 0.02, "release dropped {} in one sample", previous - level);
+        previous = level;
+        samples += 1;
+    }
+    assert!(samples as f32 >= 44100.0 * MIN_RELEASE_SECONDS * 0.9, "release ended after {} samples", samples);
+}
@@ -0,0 +1,114 @@
+//! Unit tests for structured export errors and the panic hook
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use awe_synth::error::{install_panic_hook, set_error_listener, ErrorCode, ErrorEvent, ExportScope};
+
+fn collect_errors() -> Rc<RefCell<Vec<ErrorEvent>>> {
+    let received = Rc::new(RefCell::new(Vec::new()));
+    let sink = Rc::clone(&received);
+    set_error_listener(Some(Rc::new(move |event: &ErrorEvent| sink.borrow_mut().push(event.clone()))));
+    received
+}
+
+#[test]
+fn test_raised_errors_name_the_running_export() {
+    let received = collect_errors();
+    {
+        let _outer = ExportScope::enter("set_buffer_size_global");
+        {
+            let _inner = ExportScope::enter("reset_pipeline_global");
+        }
+        let event = ErrorEvent::not_initialized();
+        assert_eq!(event.export.as_deref(), Some("set_buffer_size_global"), "scopes nest");
+    }
+    ErrorEvent::raise(ErrorCode::InvalidArgument, "outside any export");
+    set_error_listener(None);
+
+    let received = received.borrow();
+    assert_eq!(received.len(), 2);
+    assert!(received[0].to_json().contains(r#""code":"notInitialized""#));
+    assert_eq!(received[1].export, None);
+    assert!(!received[1].to_json().contains(r#""export""#));
+}
+
+#[test]
+fn test_panic_hook_reports_panics() {
+    install_panic_hook();
+    let received = collect_errors();
+    let result = std::panic::catch_unwind(|| {
+        let _scope = ExportScope::enter("render_stereo_block_global");
+        panic!("voice index out of range");
+    });
+    set_error_listener(None);
+
+    assert!(result.is_err());
+    let received = received.borrow();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].code, ErrorCode::Panic);
+    assert_eq!(received[0].export.as_deref(), Some("render_stereo_block_global"));
+    assert!(received[0].message.starts_with("voice index out of range ("));
+}
+
+#[test]
+fn test_status_exports_throw_before_init() {
+    // No test initializes the AudioWorklet bridge
+    let received = collect_errors();
+    let error = awe_synth::get_pipeline_stats_global().unwrap_err();
+    assert!(awe_synth::process_stereo_buffer_global(128).is_err());
+    assert!(awe_synth::get_sample_rate().is_err());
+    set_error_listener(None);
+
+    assert_eq!(error.code, ErrorCode::NotInitialized);
+    assert_eq!(error.export.as_deref(), Some("get_pipeline_stats_global"));
+    assert_eq!(received.borrow().len(), 3);
+    assert!(awe_synth::get_system_status().contains(r#""pipelineReady": false"#), "the summary stays non-throwing");
+}
+
+#[test]
+fn test_recording_exports_throw_before_init() {
+    let received = collect_errors();
+    let commit = awe_synth::commit_take_global(1).unwrap_err();
+    assert!(awe_synth::discard_take_global(1).is_err());
+    assert!(awe_synth::stop_recording_global().is_err());
+    set_error_listener(None);
+
+    assert_eq!(commit.code, ErrorCode::NotInitialized);
+    assert_eq!(commit.export.as_deref(), Some("commit_take_global"));
+    assert_eq!(received.borrow().len(), 3);
+}
+
+#[test]
+fn test_load_and_export_exports_throw_invalid_arguments() {
+    let received = collect_errors();
+    let parse = awe_synth::parse_soundfont_file(b"RIFF\x04\x00\x00\x00WAVE").unwrap_err();
+    let midi = awe_synth::MidiPlayer::new().load_midi_file(b"not a midi file").unwrap_err();
+    let encode = awe_synth::encode_wav_buffer(&[0.0; 4], 44100, 2, "mp3").unwrap_err();
+    assert!(awe_synth::identify_soundfont(b"").is_err());
+    assert!(awe_synth::encode_audio_buffer_with_options(&[0.0; 4], 44100, 2, "pcm16", "{").is_err());
+    assert!(awe_synth::encode_audio_buffer_with_options(&[0.0; 4], 44100, 2, "pcm16", "{}").is_ok());
+    set_error_listener(None);
+
+    assert_eq!(parse.code, ErrorCode::InvalidArgument);
+    assert_eq!(parse.export.as_deref(), Some("parse_soundfont_file"));
+    assert_eq!(midi.code, ErrorCode::InvalidArgument);
+    assert_eq!(midi.export.as_deref(), Some("MidiPlayer.load_midi_file"));
+    assert_eq!(encode.message, r#"Unknown export format "mp3""#);
+    assert_eq!(received.borrow().len(), 5);
+}
+
+#[test]
+fn test_render_and_take_exports_throw_before_init() {
+    let received = collect_errors();
+    let render = awe_synth::render_stereo_block_global(128).unwrap_err();
+    assert!(awe_synth::take_ab_render_global("a").is_err());
+    assert!(awe_synth::stop_capture_global("pcm16").is_err());
+    assert!(awe_synth::set_punch_region_global(Some(960), Some(480)).is_err());
+    assert!(awe_synth::export_take_midi_global(1, "{}").is_err());
+    set_error_listener(None);
+
+    assert_eq!(render.code, ErrorCode::NotInitialized);
+    assert_eq!(render.export.as_deref(), Some("render_stereo_block_global"));
+    assert!(received.borrow().iter().all(|event| event.code == ErrorCode::NotInitialized));
+    assert_eq!(received.borrow().len(), 5);
+}
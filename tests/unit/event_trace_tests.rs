@@ -0,0 +1,64 @@
+//! Unit tests for the MIDI event trace (ring order, filters, actions, drop reasons)
+
+use awe_synth::midi::event_trace::{DropReason, EventAction, EventSource, EventTrace, TraceEntry, MAX_TRACE_CAPACITY};
+use awe_synth::synth::voice_manager::VoiceManager;
+
+fn entry(sample: u64, channel: u8, status: u8, action: EventAction) -> TraceEntry {
+    TraceEntry { sample, source: EventSource::Live, channel, status, data1: 60, data2: 100, action }
+}
+
+#[test]
+fn test_trace_keeps_last_entries_oldest_first() {
+    let mut trace = EventTrace::new(4);
+    for sample in 0..6 {
+        trace.record(entry(sample, 0, 0x90, EventAction::VoiceAllocated(sample as usize)));
+    }
+    assert_eq!(trace.len(), 4);
+    assert_eq!(trace.overwritten(), 2);
+    let samples: Vec<u64> = trace.iter().map(|entry| entry.sample).collect();
+    assert_eq!(samples, vec![2, 3, 4, 5]);
+
+    trace.set_enabled(false);
+    trace.record(entry(6, 0, 0x90, EventAction::Ignored));
+    assert_eq!(trace.iter().last().unwrap().sample, 5, "disabled trace records nothing");
+
+    trace.set_capacity(2);
+    assert!(trace.is_empty());
+    assert!(!trace.is_enabled(), "resizing keeps the enabled flag");
+    assert_eq!(EventTrace::new(usize::MAX).capacity(), MAX_TRACE_CAPACITY);
+    assert_eq!(EventTrace::new(0).capacity(), 1);
+}
+
+#[test]
+fn test_query_filters_by_channel_and_type() {
+    let mut trace = EventTrace::new(16);
+    trace.record(entry(0, 0, 0x90, EventAction::VoiceAllocated(12)));
+    trace.record(entry(1, 9, 0x99, EventAction::Dropped(DropReason::NoPreset)));
+    trace.record(entry(2, 0, 0xB0, EventAction::Applied));
+    trace.record(entry(3, 0, 0x80, EventAction::Released));
+
+    assert_eq!(trace.query(None, &[]).len(), 4);
+    let channel_zero = trace.query(Some(0), &[]);
+    assert_eq!(channel_zero.len(), 3);
+    assert_eq!(channel_zero[0].action, "voice 12 allocated");
+    assert_eq!(channel_zero[0].message_type, "noteOn");
+
+    let notes = trace.query(None, &["noteOn", "noteOff"]);
+    assert_eq!(notes.iter().map(|report| report.sample).collect::<Vec<_>>(), vec![0, 1, 3]);
+    assert_eq!(notes[1].action, "dropped: no preset");
+    assert!(trace.query(Some(9), &["controlChange"]).is_empty());
+
+    let json = serde_json::to_string(&notes[0]).unwrap();
+    assert!(json.contains("\"source\":\"live\""));
+    assert!(json.contains("\"messageType\":\"noteOn\""));
+}
+
+#[test]
+fn test_note_drop_reasons() {
+    let mut manager = VoiceManager::new(44100.0);
+    // The fallback bank always has a preset, so a dropped note means no voice
+    assert_eq!(manager.note_drop_reason(0), DropReason::NoVoice);
+    manager.set_fallback_bank_enabled(false);
+    assert_eq!(manager.note_drop_reason(0), DropReason::NoSoundFont);
+    assert!(manager.note_on(60, 100, 0).is_none());
+}
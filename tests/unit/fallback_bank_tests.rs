@@ -0,0 +1,33 @@
+//! Unit tests for the procedural GM fallback bank
+
+use awe_synth::synth::fallback_bank::{build_fallback_soundfont, FALLBACK_DRUM_PRESET_INDEX};
+use awe_synth::synth::voice_manager::VoiceManager;
+
+#[test]
+fn test_fallback_bank_layout() {
+    let bank = build_fallback_soundfont(44100.0);
+    assert_eq!(bank.presets.len(), 129);
+    assert!(bank.presets.iter().take(128).enumerate().all(|(i, p)| p.bank == 0 && p.program as usize == i));
+    assert_eq!(bank.presets[FALLBACK_DRUM_PRESET_INDEX].bank, 128);
+
+    // Every zone must reference a sample with audible data
+    for instrument in &bank.instruments {
+        for zone in &instrument.instrument_zones {
+            let sample = &bank.samples[zone.sample_id.expect("zone has sample") as usize];
+            assert!(sample.sample_data.iter().any(|&s| s != 0), "{} is silent", sample.name);
+        }
+    }
+}
+
+#[test]
+fn test_notes_play_without_soundfont() {
+    let mut manager = VoiceManager::new(44100.0);
+    assert!(manager.note_on(60, 100, 0).is_some());
+    assert!(manager.note_on(36, 100, 9).is_some());
+
+    let peak = (0..2048).map(|_| manager.process().0.abs()).fold(0.0f32, f32::max);
+    assert!(peak > 0.01, "fallback bank produced no audio (peak {})", peak);
+
+    manager.set_fallback_bank_enabled(false);
+    assert!(manager.note_on(64, 100, 0).is_none());
+}
@@ -0,0 +1,74 @@
+//! Unit tests for filter envelope shapes and generator 29 sustain semantics
+
+use awe_synth::synth::mod_envelope::{self, FilterEnvelopeShape, ModulationEnvelope};
+use awe_synth::synth::voice_manager::VoiceManager;
+
+const SAMPLE_RATE: f32 = 4000.0;
+
+fn run(envelope: &mut ModulationEnvelope, seconds: f32) -> f32 {
+    let mut level = envelope.current_level;
+    for _ in 0..(seconds * SAMPLE_RATE) as usize {
+        level = envelope.process();
+    }
+    level
+}
+
+#[test]
+fn test_sustain_decrease_conversions() {
+    assert_eq!(mod_envelope::sustain_fraction(0), 1.0);
+    assert_eq!(mod_envelope::sustain_fraction(250), 0.75);
+    assert_eq!(mod_envelope::sustain_fraction(1000), 0.0);
+    assert_eq!(mod_envelope::sustain_fraction(1500), 0.0);
+    assert_eq!(mod_envelope::sustain_centibels(0), 0);
+    assert_eq!(mod_envelope::sustain_centibels(500), 60);
+    assert_eq!(mod_envelope::sustain_centibels(1000), mod_envelope::MAX_SUSTAIN_CENTIBELS);
+}
+
+#[test]
+fn test_sustain_holds_at_decreased_level() {
+    for (sustain, expected) in [(0, 1.0), (250, 0.75), (1000, 0.0)] {
+        let mut envelope = ModulationEnvelope::new(SAMPLE_RATE, -12000, -12000, -3600, sustain, -3600, 0, 0);
+        envelope.trigger(60);
+        let level = run(&mut envelope, 1.0);
+        assert!((level - expected).abs() < 0.01, "sustain {} held at {}", sustain, level);
+    }
+}
+
+#[test]
+fn test_pluck_opens_then_closes() {
+    let shape = FilterEnvelopeShape::PLUCK;
+    let mut envelope = shape.envelope(SAMPLE_RATE);
+    envelope.trigger(60);
+    let peak = run(&mut envelope, 0.002);
+    assert!(shape.cutoff_cents(peak) > shape.depth_cents * 0.9, "pluck peak {}", peak);
+    let closed = run(&mut envelope, 0.4);
+    assert!(shape.cutoff_cents(closed) < 100.0, "pluck still open at {}", closed);
+}
+
+#[test]
+fn test_brass_and_sweep_settle_at_sustain() {
+    for (shape, seconds) in [(FilterEnvelopeShape::BRASS, 2.0), (FilterEnvelopeShape::SWEEP, 8.0)] {
+        let mut envelope = shape.envelope(SAMPLE_RATE);
+        envelope.trigger(60);
+        let level = run(&mut envelope, seconds);
+        let expected = mod_envelope::sustain_fraction(shape.sustain_decrease);
+        assert!((level - expected).abs() < 0.02, "{:?} settled at {}", shape, level);
+    }
+}
+
+#[test]
+fn test_channel_filter_envelope_selection() {
+    let mut manager = VoiceManager::new(SAMPLE_RATE);
+    assert_eq!(FilterEnvelopeShape::from_name("pluck"), Some(FilterEnvelopeShape::PLUCK));
+    assert_eq!(FilterEnvelopeShape::from_name("wah"), None);
+
+    assert!(manager.set_filter_envelope(Some(3), Some(FilterEnvelopeShape::BRASS)));
+    assert_eq!(manager.get_channel_state(3).and_then(|state| state.filter_envelope), Some(FilterEnvelopeShape::BRASS));
+    assert_eq!(manager.get_channel_state(4).and_then(|state| state.filter_envelope), None);
+    assert!(!manager.set_filter_envelope(Some(16), Some(FilterEnvelopeShape::PLUCK)));
+
+    assert!(manager.set_filter_envelope(None, Some(FilterEnvelopeShape::SWEEP)));
+    assert!((0..16).all(|channel| manager.get_channel_state(channel).and_then(|state| state.filter_envelope) == Some(FilterEnvelopeShape::SWEEP)));
+    assert!(manager.set_filter_envelope(None, None));
+    assert_eq!(manager.get_channel_state(3).and_then(|state| state.filter_envelope), None);
+}
@@ -0,0 +1,283 @@
+//! Unit tests for the FLAC and Ogg FLAC encoders
+//!
+//! Streams are checked by decoding them again with a small reference decoder
+//! covering the subset the encoder produces (constant, verbatim and fixed
+//! subframes, Rice partitions, stereo decorrelation).
+
+use awe_synth::audio::export::{encode, ExportFormat};
+use awe_synth::audio::flac::{encode_flac, FlacSpec, FLAC_BLOCK_SIZE};
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize, // In bits
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn read(&mut self, bits: u32) -> u64 {
+        let mut value = 0u64;
+        for _ in 0..bits {
+            let bit = (self.data[self.position / 8] >> (7 - self.position % 8)) & 1;
+            value = (value << 1) | bit as u64;
+            self.position += 1;
+        }
+        value
+    }
+
+    fn read_signed(&mut self, bits: u32) -> i64 {
+        let value = self.read(bits);
+        ((value << (64 - bits)) as i64) >> (64 - bits)
+    }
+
+    fn read_unary(&mut self) -> u64 {
+        let mut count = 0;
+        while self.read(1) == 0 {
+            count += 1;
+        }
+        count
+    }
+
+    fn align(&mut self) {
+        self.position = self.position.div_ceil(8) * 8;
+    }
+
+    fn byte_position(&self) -> usize {
+        self.position / 8
+    }
+}
+
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |mut crc, &byte| {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x8005 } else { crc << 1 };
+        }
+        crc
+    })
+}
+
+fn decode_subframe(reader: &mut BitReader, block_size: usize, bps: u32) -> Vec<i64> {
+    assert_eq!(reader.read(1), 0);
+    let kind = reader.read(6);
+    assert_eq!(reader.read(1), 0, "no wasted bits");
+    match kind {
+        0 => vec![reader.read_signed(bps); block_size],
+        1 => (0..block_size).map(|_| reader.read_signed(bps)).collect(),
+        8..=12 => {
+            let order = (kind - 8) as usize;
+            let mut samples: Vec<i64> = (0..order).map(|_| reader.read_signed(bps)).collect();
+            assert_eq!(reader.read(2), 0, "rice method 0");
+            let partition_order = reader.read(4);
+            let partitions = 1usize << partition_order;
+            let mut residual = Vec::with_capacity(block_size);
+            for partition in 0..partitions {
+                let parameter = reader.read(4) as u32;
+                assert!(parameter < 15);
+                let len = block_size / partitions - if partition == 0 { order } else { 0 };
+                for _ in 0..len {
+                    let value = (reader.read_unary() << parameter) | reader.read(parameter);
+                    residual.push(((value >> 1) as i64) ^ -((value & 1) as i64));
+                }
+            }
+            for r in residual {
+                let i = samples.len();
+                let s = &samples;
+                let prediction = match order {
+                    0 => 0,
+                    1 => s[i - 1],
+                    2 => 2 * s[i - 1] - s[i - 2],
+                    3 => 3 * s[i - 1] - 3 * s[i - 2] + s[i - 3],
+                    _ => 4 * s[i - 1] - 6 * s[i - 2] + 4 * s[i - 3] - s[i - 4],
+                };
+                samples.push(prediction + r);
+            }
+            samples
+        }
+        other => panic!("unexpected subframe type {}", other),
+    }
+}
+
+/// Decode frames starting at `data`; returns interleaved samples
+fn decode_frames(mut data: &[u8], channels: usize, bps: u32) -> Vec<i64> {
+    let mut output = Vec::new();
+    let mut expected_number = 0;
+    while !data.is_empty() {
+        let mut reader = BitReader::new(data);
+        assert_eq!(reader.read(16), 0xFFF8, "frame sync");
+        let block_code = reader.read(4);
+        let _rate_code = reader.read(4);
+        let assignment = reader.read(4);
+        assert_eq!(reader.read(3), if bps == 24 { 0b110 } else { 0b100 });
+        reader.read(1);
+        let first = reader.read(8);
+        let ones = (first as u8).leading_ones();
+        let mut number = first & (0x7F >> ones);
+        let extra = ones.saturating_sub(1);
+        for _ in 0..extra {
+            number = (number << 6) | (reader.read(8) & 0x3F);
+        }
+        assert_eq!(number, expected_number);
+        expected_number += 1;
+        let block_size = match block_code {
+            6 => reader.read(8) as usize + 1,
+            7 => reader.read(16) as usize + 1,
+            12 => 4096,
+            other => panic!("unexpected block size code {}", other),
+        };
+        reader.read(8); // Header CRC-8
+
+        let sizes: Vec<u32> = match assignment {
+            8 => vec![bps, bps + 1],
+            9 => vec![bps + 1, bps],
+            10 => vec![bps, bps + 1],
+            _ => vec![bps; assignment as usize + 1],
+        };
+        assert_eq!(sizes.len(), channels);
+        let mut decoded: Vec<Vec<i64>> = sizes.iter().map(|&bits| decode_subframe(&mut reader, block_size, bits)).collect();
+        match assignment {
+            8 => decoded[1] = decoded[0].iter().zip(&decoded[1]).map(|(l, s)| l - s).collect(),
+            9 => decoded[0] = decoded[0].iter().zip(&decoded[1]).map(|(s, r)| s + r).collect(),
+            10 => {
+                let (mid, side) = (decoded[0].clone(), decoded[1].clone());
+                for i in 0..block_size {
+                    let m = (mid[i] << 1) | (side[i] & 1);
+                    decoded[0][i] = (m + side[i]) >> 1;
+                    decoded[1][i] = (m - side[i]) >> 1;
+                }
+            }
+            _ => {}
+        }
+        for i in 0..block_size {
+            output.extend(decoded.iter().map(|channel| channel[i]));
+        }
+
+        reader.align();
+        let frame_len = reader.byte_position();
+        let crc = reader.read(16) as u16;
+        assert_eq!(crc16(&data[..frame_len]), crc, "frame CRC-16");
+        data = &data[frame_len + 2..];
+    }
+    output
+}
+
+fn test_signal(frames: usize, channels: usize) -> Vec<f32> {
+    (0..frames * channels)
+        .map(|i| {
+            let t = (i / channels) as f32;
+            let channel = (i % channels) as f32;
+            0.6 * (t * 0.031 * (channel + 1.0)).sin() + 0.1 * (t * 0.37).sin()
+        })
+        .collect()
+}
+
+fn quantized(samples: &[f32], bits: u32) -> Vec<i64> {
+    let full_scale = ((1i64 << (bits - 1)) - 1) as f32;
+    samples.iter().map(|s| (s.clamp(-1.0, 1.0) * full_scale).round() as i64).collect()
+}
+
+#[test]
+fn test_flac_stereo_round_trip() {
+    let samples = test_signal(FLAC_BLOCK_SIZE * 2 + 1000, 2);
+    let flac = encode_flac(FlacSpec::new(44100, 2, 16), &samples);
+
+    assert_eq!(&flac[0..4], b"fLaC");
+    assert_eq!(flac[4], 0x80, "STREAMINFO is the last metadata block");
+    let info = &flac[8..42];
+    assert_eq!(u16::from_be_bytes([info[0], info[1]]) as usize, FLAC_BLOCK_SIZE);
+    let packed = u64::from_be_bytes([info[10], info[11], info[12], info[13], info[14], info[15], info[16], info[17]]);
+    assert_eq!(packed >> 44, 44100);
+    assert_eq!((packed >> 41) & 0x7, 1, "two channels");
+    assert_eq!((packed >> 36) & 0x1F, 15, "16 bits per sample");
+    assert_eq!(packed & 0xF_FFFF_FFFF, (FLAC_BLOCK_SIZE * 2 + 1000) as u64);
+
+    assert_eq!(decode_frames(&flac[42..], 2, 16), quantized(&samples, 16));
+    assert!(flac.len() < samples.len() * 2, "smaller than 16-bit PCM");
+}
+
+#[test]
+fn test_flac_mono_24bit_and_silence_round_trip() {
+    let mut samples = test_signal(5000, 1);
+    samples[3000..].iter_mut().for_each(|s| *s = 0.0);
+    let flac = encode_flac(FlacSpec::new(48000, 1, 24), &samples);
+    assert_eq!(decode_frames(&flac[42..], 1, 24), quantized(&samples, 24));
+}
+
+#[test]
+fn test_flac_independent_channels_decode() {
+    // Uncorrelated, clipped channels exercise verbatim-ish coding and clamping
+    let samples: Vec<f32> = (0..3000).map(|i| if i % 2 == 0 { ((i * 7919) % 200) as f32 / 50.0 - 2.0 } else { 0.25 }).collect();
+    let flac = encode_flac(FlacSpec::new(22050, 2, 16), &samples);
+    assert_eq!(decode_frames(&flac[42..], 2, 16), quantized(&samples, 16));
+}
+
+#[cfg(feature = "ogg")]
+#[test]
+fn test_ogg_flac_pages() {
+    let samples = test_signal(FLAC_BLOCK_SIZE * 3, 2);
+    let format = ExportFormat::from_name("oga-flac").unwrap();
+    assert_eq!(format.mime_type(), "audio/ogg; codecs=flac");
+    assert!(ExportFormat::from_name("ogg").is_none(), "not a lossy Ogg format");
+    let ogg = encode(format, 44100, 2, &samples);
+
+    let mut offset = 0;
+    let mut packets: Vec<Vec<u8>> = vec![Vec::new()];
+    let mut last_granule = 0;
+    let mut sequence = 0;
+    while offset < ogg.len() {
+        let page = &ogg[offset..];
+        assert_eq!(&page[0..4], b"OggS");
+        let header_type = page[5];
+        assert_eq!(header_type & 0x02 != 0, sequence == 0, "BOS only on the first page");
+        last_granule = u64::from_le_bytes(page[6..14].try_into().unwrap());
+        assert_eq!(u32::from_le_bytes(page[18..22].try_into().unwrap()), sequence);
+        let segments = page[26] as usize;
+        let lacing = &page[27..27 + segments];
+        let body_len: usize = lacing.iter().map(|&l| l as usize).sum();
+        let page_len = 27 + segments + body_len;
+
+        let mut check = page[..page_len].to_vec();
+        check[22..26].fill(0);
+        let crc = check.iter().fold(0u32, |mut crc, &byte| {
+            crc ^= (byte as u32) << 24;
+            for _ in 0..8 {
+                crc = if crc & 0x8000_0000 != 0 { (crc << 1) ^ 0x04C1_1DB7 } else { crc << 1 };
+            }
+            crc
+        });
+        assert_eq!(u32::from_le_bytes(page[22..26].try_into().unwrap()), crc, "page CRC");
+
+        let mut body = &page[27 + segments..page_len];
+        for &len in lacing {
+            packets.last_mut().unwrap().extend_from_slice(&body[..len as usize]);
+            body = &body[len as usize..];
+            if len < 255 {
+                packets.push(Vec::new());
+            }
+        }
+        if offset + page_len == ogg.len() {
+            assert!(header_type & 0x04 != 0, "EOS on the last page");
+        }
+        offset += page_len;
+        sequence += 1;
+    }
+    packets.pop();
+
+    assert_eq!(&packets[0][0..5], b"\x7FFLAC");
+    assert_eq!(&packets[0][9..13], b"fLaC");
+    assert_eq!(packets[1][0], 0x84, "VORBIS_COMMENT, last metadata block");
+    assert_eq!(last_granule, (FLAC_BLOCK_SIZE * 3) as u64);
+
+    let frames: Vec<u8> = packets[2..].concat();
+    assert_eq!(decode_frames(&frames, 2, 16), quantized(&samples, 16));
+}
+
+#[test]
+fn test_export_format_names() {
+    assert_eq!(ExportFormat::from_name("flac24"), Some(ExportFormat::Flac { bits_per_sample: 24 }));
+    assert_eq!(ExportFormat::from_name("FLAC").map(|f| f.mime_type()), Some("audio/flac"));
+    let wav = encode(ExportFormat::from_name("pcm16").unwrap(), 44100, 2, &[0.0; 8]);
+    assert_eq!(&wav[0..4], b"RIFF");
+}
@@ -0,0 +1,39 @@
+//! Unit tests for the GM program/drum kit name tables and their catalog annotation
+
+use std::collections::HashMap;
+use awe_synth::midi::gm_names::{self, GmNames, GM_PROGRAM_NAMES};
+use awe_synth::soundfont::catalog::{PresetCatalogPage, PresetQuery};
+use awe_synth::synth::fallback_bank::build_fallback_soundfont;
+
+#[test]
+fn test_program_and_kit_lookups() {
+    assert_eq!(gm_names::gm_program_name(0), Some("Acoustic Grand Piano"));
+    assert_eq!(gm_names::gm_program_name(40), Some("Violin"));
+    assert_eq!(gm_names::gm_program_name(127), Some("Gunshot"));
+    assert_eq!(gm_names::gm_program_name(128), None);
+    assert_eq!(gm_names::gm_drum_kit_name(25), Some("TR-808 Kit"));
+    assert_eq!(gm_names::gm_drum_kit_name(1), None);
+    assert_eq!(gm_names::expected_gm_name(128, 0), Some("Standard Kit"));
+    assert_eq!(gm_names::expected_gm_name(8, 0), Some("Acoustic Grand Piano"), "variation banks keep the GM instrument");
+    assert!(GM_PROGRAM_NAMES.iter().all(|name| !name.is_empty()));
+}
+
+#[test]
+fn test_gm_names_json() {
+    let json = serde_json::to_value(GmNames::new()).unwrap();
+    assert_eq!(json["programs"].as_array().unwrap().len(), 128);
+    assert_eq!(json["programs"][56], "Trumpet");
+    assert_eq!(json["drumKits"][0], serde_json::json!({ "program": 0, "name": "Standard Kit" }));
+}
+
+#[test]
+fn test_preset_catalog_lists_expected_gm_name() {
+    let mut bank = build_fallback_soundfont(44100.0);
+    bank.presets[1].program = 200; // Not a GM program number
+    let page = PresetCatalogPage::query(&bank, &PresetQuery { offset: 0, limit: 500, name_filter: String::new() }, &HashMap::new());
+
+    assert_eq!(page.presets[0].gm_name.as_deref(), Some("Acoustic Grand Piano"));
+    assert_eq!(page.presets[1].gm_name, None);
+    assert_eq!(page.presets[128].gm_name.as_deref(), Some("Standard Kit"));
+    assert!(!page.to_json().contains(r#""gmName":null"#));
+}
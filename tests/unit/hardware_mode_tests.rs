@@ -0,0 +1,44 @@
+//! Unit tests for the modern / EMU8000 hardware synthesis toggle
+
+use awe_synth::synth::hardware_mode::{quantize_16bit, quantize_cutoff, quantize_send, SynthesisMode};
+use awe_synth::synth::voice_manager::VoiceManager;
+
+#[test]
+fn test_mode_names_and_quantizers() {
+    assert_eq!(SynthesisMode::from_name("hardware"), Some(SynthesisMode::Hardware));
+    assert_eq!(SynthesisMode::from_name("modern").map(|m| m.name()), Some("modern"));
+    assert_eq!(SynthesisMode::from_name("vintage"), None);
+
+    assert_eq!(quantize_16bit(2.0), 32767.0 / 32768.0, "clips at positive full scale");
+    assert_eq!(quantize_16bit(-2.0), -1.0);
+    assert_eq!(quantize_16bit(0.4 / 32768.0), 0.0, "below one LSB rounds away");
+
+    assert_eq!(quantize_cutoff(50.0), 100.0);
+    assert!((quantize_cutoff(8000.0) - 8000.0).abs() < 0.01);
+    let step = quantize_cutoff(1000.0) / quantize_cutoff(990.0);
+    assert!(step == 1.0 || step > 1.015, "cutoff moves in register steps, not continuously");
+    assert_eq!(quantize_send(0.5), 128.0 / 255.0);
+}
+
+fn render(mode: SynthesisMode) -> Vec<f32> {
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_synthesis_mode(mode);
+    assert!(manager.note_on(60, 127, 0).is_some());
+    (0..1024).map(|_| manager.process().0).collect()
+}
+
+#[test]
+fn test_hardware_mode_changes_detail_not_level() {
+    let modern = render(SynthesisMode::Modern);
+    let hardware = render(SynthesisMode::Hardware);
+    assert!(modern.iter().any(|&s| s != 0.0));
+    assert_ne!(modern, hardware, "hardware mode quantizes the voice output");
+    // Register stepping changes the filter's exact path but not the level
+    let peak = |samples: &[f32]| samples.iter().fold(0.0f32, |p, s| p.max(s.abs()));
+    let (modern_peak, hardware_peak) = (peak(&modern), peak(&hardware));
+    assert!((modern_peak - hardware_peak).abs() < modern_peak * 0.25, "{} vs {}", modern_peak, hardware_peak);
+
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_synthesis_mode(SynthesisMode::Hardware);
+    assert_eq!(manager.get_synthesis_mode(), SynthesisMode::Hardware);
+}
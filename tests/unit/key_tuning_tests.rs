@@ -0,0 +1,109 @@
+//! Unit tests for per-key tuning tables, master tuning and RPN 1 fine tuning
+
+use awe_synth::synth::channel_state::ChannelState;
+use awe_synth::synth::tuning::{master_tuning_cents, KeyTuning, KEY_COUNT};
+use awe_synth::synth::voice_manager::VoiceManager;
+
+/// Zero crossings of the left output over `samples` samples after a note-on
+fn crossings(manager: &mut VoiceManager, note: u8, channel: u8, samples: usize) -> usize {
+    manager.note_on(note, 100, channel).unwrap();
+    let mut previous = 0.0f32;
+    let mut count = 0;
+    for _ in 0..samples {
+        let (left, _) = manager.process();
+        if (previous < 0.0) != (left < 0.0) {
+            count += 1;
+        }
+        previous = left;
+    }
+    count
+}
+
+#[test]
+fn test_channel_tables_override_global() {
+    let mut tuning = KeyTuning::new();
+    let mut table = [0.0f32; KEY_COUNT];
+    table[60] = -13.7;
+    assert!(tuning.set(None, &table));
+    table[60] = 50.0;
+    table[61] = 99_999.0;
+    assert!(tuning.set(Some(9), &table));
+
+    assert_eq!(tuning.cents(0, 60), -13.7);
+    assert_eq!(tuning.cents(9, 60), 50.0);
+    assert_eq!(tuning.cents(9, 61), 12_800.0, "clamped");
+    assert_eq!(tuning.cents(16, 60), -13.7, "audition channel follows the global table");
+
+    assert!(!tuning.set(None, &table[..127]));
+    table[5] = f32::NAN;
+    assert!(!tuning.set(Some(1), &table));
+    assert!(!tuning.set(Some(16), &[0.0; KEY_COUNT]));
+
+    assert!(tuning.reset(Some(9)));
+    assert_eq!(tuning.cents(9, 60), -13.7);
+    assert!(tuning.reset(None));
+    assert_eq!(tuning.cents(0, 60), 0.0);
+}
+
+#[test]
+fn test_octave_offset_matches_the_key_above() {
+    let reference = crossings(&mut VoiceManager::new(44100.0), 72, 0, 8820);
+
+    let mut manager = VoiceManager::new(44100.0);
+    let mut table = [0.0f32; KEY_COUNT];
+    table[60] = 1200.0;
+    assert!(manager.set_key_tuning(Some(0), &table));
+    let tuned = crossings(&mut manager, 60, 0, 8820);
+    assert!(tuned.abs_diff(reference) <= reference / 50, "tuned {} vs reference {}", tuned, reference);
+
+    let untuned = crossings(&mut VoiceManager::new(44100.0), 60, 0, 8820);
+    assert!(untuned < reference * 2 / 3, "an octave lower without the table");
+}
+
+#[test]
+fn test_master_tuning_range() {
+    assert_eq!(master_tuning_cents(440.0), Some(0.0));
+    assert!((master_tuning_cents(415.305).unwrap() + 100.0).abs() < 0.01, "baroque pitch is a semitone flat");
+    assert!((master_tuning_cents(442.0).unwrap() - 7.85).abs() < 0.01);
+    assert_eq!(master_tuning_cents(400.0), None);
+    assert_eq!(master_tuning_cents(f32::NAN), None);
+
+    let mut manager = VoiceManager::new(44100.0);
+    assert_eq!(manager.get_master_tuning(), 440.0);
+    assert!(manager.set_master_tuning(432.0));
+    assert!(!manager.set_master_tuning(480.0));
+    assert_eq!(manager.get_master_tuning(), 432.0);
+}
+
+#[test]
+fn test_rpn_fine_tuning_is_relative_to_master_tuning() {
+    let mut state = ChannelState::new(0);
+    assert_eq!(state.fine_tuning_cents(), 0.0);
+    state.set_fine_tuning_msb(96);
+    assert_eq!(state.fine_tuning_cents(), 50.0);
+    state.set_fine_tuning_lsb(127);
+    assert!(state.fine_tuning_cents() > 50.0 && state.fine_tuning_cents() < 52.0, "LSB adds up to 1.55 cents");
+    state.set_fine_tuning_msb(0);
+    assert_eq!(state.fine_tuning_cents(), -100.0, "MSB clears the LSB");
+
+    // +50 cents on the channel against a master tuning 50 cents flat plays at concert pitch
+    let mut manager = VoiceManager::new(44100.0);
+    assert!(manager.set_master_tuning(440.0 * 2.0_f32.powf(-50.0 / 1200.0)));
+    manager.parameter_number_msb(0, false, 0);
+    manager.parameter_number_lsb(0, false, 1);
+    assert!(manager.data_entry_msb(0, 96));
+    assert!(manager.data_entry_lsb(0, 0));
+    manager.note_on(69, 100, 0).unwrap();
+
+    let mut reference = VoiceManager::new(44100.0);
+    reference.note_on(69, 100, 0).unwrap();
+    for _ in 0..8820 {
+        let (tuned, concert) = (manager.process(), reference.process());
+        assert!((tuned.0 - concert.0).abs() < 1e-3);
+    }
+
+    // Other channels only follow the master tuning
+    let flat = crossings(&mut manager, 81, 1, 8820);
+    let concert = crossings(&mut VoiceManager::new(44100.0), 81, 1, 8820);
+    assert!(flat < concert, "{} vs {}", flat, concert);
+}
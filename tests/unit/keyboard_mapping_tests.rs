@@ -0,0 +1,68 @@
+//! Unit tests for the computer keyboard to MIDI mapper
+
+use awe_synth::midi::keyboard_mapping::{KeyboardLayout, KeyboardMapper};
+
+#[test]
+fn test_tracker_layout_maps_lower_and_upper_rows() {
+    let mapper = KeyboardMapper::new(KeyboardLayout::Tracker);
+    assert_eq!(mapper.note_for_key("z"), Some(48));
+    assert_eq!(mapper.note_for_key("s"), Some(49));
+    assert_eq!(mapper.note_for_key("q"), Some(60));
+    assert_eq!(mapper.note_for_key("KeyQ"), Some(60));
+    assert_eq!(mapper.note_for_key("Comma"), Some(60));
+    assert_eq!(mapper.note_for_key("F1"), None);
+}
+
+#[test]
+fn test_key_down_emits_note_on_and_ignores_repeat() {
+    let mut mapper = KeyboardMapper::new(KeyboardLayout::HomeRow);
+    mapper.set_velocity(90);
+    mapper.set_channel(3);
+
+    let event = mapper.key_down("a", 10).expect("mapped key should emit Note On");
+    assert_eq!(event.message_type, 0x90);
+    assert_eq!(event.channel, 3);
+    assert_eq!(event.data1, 48);
+    assert_eq!(event.data2, 90);
+    assert_eq!(event.timestamp, 10);
+
+    assert!(mapper.key_down("A", 11).is_none(), "auto-repeat must not retrigger");
+}
+
+#[test]
+fn test_key_up_releases_note_started_before_octave_change() {
+    let mut mapper = KeyboardMapper::default();
+    let on = mapper.key_down("z", 0).unwrap();
+    mapper.octave_up();
+
+    let off = mapper.key_up("z", 5).unwrap();
+    assert_eq!(off.message_type, 0x80);
+    assert_eq!(off.data1, on.data1);
+    assert_eq!(mapper.note_for_key("z"), Some(60));
+}
+
+#[test]
+fn test_octave_shift_is_clamped_and_out_of_range_notes_dropped() {
+    let mut mapper = KeyboardMapper::default();
+    mapper.set_octave_shift(10);
+    assert_eq!(mapper.octave_shift(), 4);
+    // Base 48 + 48 = 96; key "p" is +28 -> 124, still valid
+    assert_eq!(mapper.note_for_key("p"), Some(124));
+
+    mapper.set_base_note(100);
+    assert_eq!(mapper.note_for_key("p"), None);
+}
+
+#[test]
+fn test_release_all_and_custom_mapping() {
+    let mut mapper = KeyboardMapper::default();
+    mapper.map_key("Digit1", 7);
+    assert_eq!(mapper.note_for_key("1"), Some(55));
+
+    mapper.key_down("z", 0);
+    mapper.key_down("1", 0);
+    let released = mapper.release_all(20);
+    assert_eq!(released.len(), 2);
+    assert!(released.iter().all(|e| e.message_type == 0x80 && e.timestamp == 20));
+    assert_eq!(mapper.held_key_count(), 0);
+}
@@ -0,0 +1,52 @@
+//! Unit tests for LFO start phase modes (sync and seeded random per voice)
+
+use awe_synth::synth::lfo::{LfoPhaseMode, LfoWaveform, LFO};
+use awe_synth::synth::voice_manager::VoiceManager;
+
+/// Three unison notes on one channel, rendered for a quarter second
+fn render_unison(mode: LfoPhaseMode, seed: u32) -> Vec<(f32, f32)> {
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_lfo_phase_mode(mode, seed);
+    for _ in 0..3 {
+        assert!(manager.note_on(60, 100, 0).is_some());
+    }
+    (0..11025).map(|_| manager.process()).collect()
+}
+
+#[test]
+fn test_offset_start_phase_wraps_from_the_rising_zero_crossing() {
+    let mut lfo = LFO::new(44100.0, 5.0, 1.0, LfoWaveform::Triangle);
+    lfo.trigger();
+    assert_eq!(lfo.phase, 0.25);
+
+    lfo.offset_start_phase(0.5);
+    assert_eq!(lfo.phase, 0.75);
+    assert_eq!(lfo.current_level, 0.0, "falling zero crossing");
+    lfo.offset_start_phase(0.9);
+    assert!((lfo.phase - 0.15).abs() < 1e-6);
+    lfo.offset_start_phase(-0.25);
+    assert!(lfo.phase.abs() < 1e-6);
+
+    let mut sine = LFO::new(44100.0, 5.0, 1.0, LfoWaveform::Sine);
+    sine.offset_start_phase(0.25);
+    assert!((sine.current_level - 1.0).abs() < 1e-6, "quarter cycle in: peak");
+}
+
+#[test]
+fn test_random_phases_are_reproducible_from_a_seed() {
+    let sync = render_unison(LfoPhaseMode::Sync, 0);
+    assert_eq!(sync, render_unison(LfoPhaseMode::Sync, 7), "sync mode ignores the generator");
+
+    let random = render_unison(LfoPhaseMode::Random, 1234);
+    assert_ne!(random, sync, "voices no longer start in lockstep");
+    assert_eq!(random, render_unison(LfoPhaseMode::Random, 1234));
+    assert_ne!(random, render_unison(LfoPhaseMode::Random, 99));
+}
+
+#[test]
+fn test_lfo_phase_mode_names() {
+    assert_eq!(LfoPhaseMode::from_name("sync"), Some(LfoPhaseMode::Sync));
+    assert_eq!(LfoPhaseMode::from_name("random"), Some(LfoPhaseMode::Random));
+    assert_eq!(LfoPhaseMode::from_name("free"), None);
+    assert_eq!(VoiceManager::new(44100.0).get_lfo_phase_mode(), LfoPhaseMode::Sync);
+}
@@ -0,0 +1,85 @@
+//! Unit tests for load lifecycle events (started / progress / complete / failed)
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use awe_synth::events::{set_listener, LoadErrorCode, LoadEvent, LoadKind, LoadStage};
+use awe_synth::MidiPlayer;
+use super::soundfont_stream_tests::minimal_sf2;
+use super::smf_fixture::smf;
+
+/// Collect events emitted on this thread while `f` runs
+fn record(f: impl FnOnce()) -> Vec<LoadEvent> {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let sink = events.clone();
+    set_listener(Some(Rc::new(move |event: &LoadEvent| sink.borrow_mut().push(event.clone()))));
+    f();
+    set_listener(None);
+    let recorded = events.borrow().clone();
+    recorded
+}
+
+fn percents(events: &[LoadEvent]) -> Vec<f32> {
+    events.iter()
+        .filter_map(|event| match event {
+            LoadEvent::Progress { percent, .. } => Some(*percent),
+            _ => None,
+        })
+        .collect()
+}
+
+fn smf_with_tracks(tracks: usize) -> Vec<u8> {
+    let events: &[u8] = &[0x00, 0x90, 60, 100, 0x60, 0x80, 60, 0, 0x00, 0xFF, 0x2F, 0x00];
+    smf(&vec![events; tracks])
+}
+
+#[test]
+fn test_midi_load_reports_track_progress_then_complete() {
+    let data = smf_with_tracks(3);
+    let mut player = MidiPlayer::new();
+    let events = record(|| player.load_midi_file(&data).unwrap());
+
+    assert_eq!(events.first(), Some(&LoadEvent::Started { kind: LoadKind::Midi, total_bytes: Some(data.len()) }));
+    assert_eq!(events.last(), Some(&LoadEvent::Complete { kind: LoadKind::Midi }));
+    let track_events = events.iter()
+        .filter(|event| matches!(event, LoadEvent::Progress { stage: LoadStage::Tracks, .. }))
+        .count();
+    assert_eq!(track_events, 3);
+    let percents = percents(&events);
+    assert!(percents.windows(2).all(|pair| pair[0] <= pair[1]), "monotonic: {:?}", percents);
+}
+
+#[test]
+fn test_midi_load_failure_carries_error_code() {
+    let mut player = MidiPlayer::new();
+    let events = record(|| assert!(player.load_midi_file(b"not a midi file at all").is_err()));
+    assert!(matches!(events.last(), Some(LoadEvent::Failed { kind: LoadKind::Midi, code: LoadErrorCode::InvalidFormat, .. })));
+
+    let mut truncated = smf_with_tracks(2);
+    truncated.truncate(truncated.len() - 6);
+    let events = record(|| assert!(player.load_midi_file(&truncated).is_err()));
+    assert!(matches!(events.last(), Some(LoadEvent::Failed { code: LoadErrorCode::CorruptData, .. })));
+}
+
+#[test]
+fn test_soundfont_load_reports_parse_stages_and_samples() {
+    let events = record(|| {
+        let _ = awe_synth::parse_soundfont_file(&minimal_sf2());
+    });
+
+    assert!(matches!(events.first(), Some(LoadEvent::Started { kind: LoadKind::SoundFont, .. })));
+    assert!(events.contains(&LoadEvent::SamplesLoaded { count: 1 }));
+    let percents = percents(&events);
+    assert!(percents.windows(2).all(|pair| pair[0] <= pair[1]), "monotonic: {:?}", percents);
+    // The engine may not be running in tests; either way the load ends with one terminal event
+    let terminal = events.iter()
+        .filter(|event| matches!(event, LoadEvent::Complete { .. } | LoadEvent::Failed { .. }))
+        .count();
+    assert_eq!(terminal, 1);
+    assert!(matches!(events.last(), Some(LoadEvent::Complete { .. } | LoadEvent::Failed { code: LoadErrorCode::NotInitialized, .. })));
+
+    let events = record(|| {
+        let _ = awe_synth::parse_soundfont_file(b"RIFF\x04\x00\x00\x00WAVE");
+    });
+    assert!(matches!(events.last(), Some(LoadEvent::Failed { code: LoadErrorCode::InvalidFormat, .. })));
+    assert!(events[events.len() - 1].to_json().contains(r#""type":"failed""#));
+}
@@ -0,0 +1,63 @@
+//! Unit tests for near-miss loop point repair
+
+use awe_synth::soundfont::loop_repair::{self, repair_loop, MAX_LOOP_REPAIR_SAMPLES};
+use awe_synth::soundfont::SoundFontParser;
+use super::soundfont_stream_tests::sub_chunk;
+
+/// shdr chunk with one sample at 100..200 and the given (absolute) loop points
+fn shdr(loop_start: u32, loop_end: u32) -> Vec<u8> {
+    let mut record = b"Strings".to_vec();
+    record.resize(20, 0);
+    for value in [100, 200, loop_start, loop_end, 44100] {
+        record.extend(u32::to_le_bytes(value));
+    }
+    record.extend([60, 0, 0, 0, 1, 0]);
+    sub_chunk(b"shdr", &record)
+}
+
+fn parsed_loop(loop_start: u32, loop_end: u32) -> (u32, u32) {
+    let samples = SoundFontParser::parse_sample_headers(&shdr(loop_start, loop_end), &[0; 256]).unwrap();
+    (samples[0].loop_start, samples[0].loop_end)
+}
+
+#[test]
+fn test_near_misses_are_nudged_into_range() {
+    // Valid loops are left to the parser
+    assert_eq!(repair_loop(100, 200, 120, 190), Some((20, 90)));
+    // Inclusive end one past the sample, start a sample early
+    assert_eq!(repair_loop(100, 200, 120, 201), Some((20, 100)));
+    assert_eq!(repair_loop(100, 200, 99, 190), Some((0, 90)));
+    // Collapsed and reversed-by-one loops open to a single sample
+    assert_eq!(repair_loop(100, 200, 150, 150), Some((50, 51)));
+    assert_eq!(repair_loop(100, 200, 151, 150), Some((51, 52)));
+    assert_eq!(repair_loop(100, 200, 201, 200), Some((99, 100)));
+}
+
+#[test]
+fn test_loops_that_are_far_off_stay_dropped() {
+    assert_eq!(repair_loop(100, 200, 120, 200 + MAX_LOOP_REPAIR_SAMPLES + 1), None);
+    assert_eq!(repair_loop(100, 200, 100 - MAX_LOOP_REPAIR_SAMPLES - 1, 190), None);
+    assert_eq!(repair_loop(100, 200, 180, 120), None);
+    assert_eq!(repair_loop(100, 101, 100, 102), None);
+}
+
+#[test]
+fn test_parser_repairs_only_when_enabled() {
+    assert!(!loop_repair::is_enabled());
+    assert_eq!(parsed_loop(120, 201), (0, 0));
+    assert!(loop_repair::take_repairs().is_empty());
+
+    loop_repair::set_enabled(true);
+    assert_eq!(parsed_loop(120, 201), (20, 100));
+    let repairs = loop_repair::take_repairs();
+    assert_eq!(repairs.len(), 1);
+    assert_eq!((repairs[0].sample, repairs[0].name.as_str()), (0, "Strings"));
+    assert_eq!((repairs[0].declared_start, repairs[0].declared_end), (20, 101));
+    assert_eq!((repairs[0].loop_start, repairs[0].loop_end), (20, 100));
+
+    // Valid and absent loops are not repairs
+    assert_eq!(parsed_loop(120, 190), (20, 90));
+    assert_eq!(parsed_loop(0, 0), (0, 0));
+    assert!(loop_repair::take_repairs().is_empty());
+    loop_repair::set_enabled(false);
+}
@@ -0,0 +1,76 @@
+//! Unit tests for zero-crossing loop snapping
+
+use awe_synth::soundfont::loop_snap::{snap_loops, LoopAdjustment, MAX_SNAP_WINDOW};
+use awe_synth::soundfont::types::{SampleType, SoundFont, SoundFontSample};
+use awe_synth::synth::fallback_bank::build_fallback_soundfont;
+
+/// 1000 frames of a sine with a 100-frame period (rising crossings at multiples of 100)
+fn sine_sample(name: &str, loop_start: u32, loop_end: u32, sample_type: SampleType, link: u16) -> SoundFontSample {
+    let data: Vec<i16> = (0..1000)
+        .map(|i| ((i as f32 * std::f32::consts::TAU / 100.0).sin() * 10000.0) as i16)
+        .collect();
+    SoundFontSample {
+        name: name.to_string(),
+        start_offset: 0,
+        end_offset: 1000,
+        loop_start,
+        loop_end,
+        sample_rate: 44100,
+        original_pitch: 60,
+        pitch_correction: 0,
+        sample_link: link,
+        sample_type,
+        sample_data: data.into(),
+    }
+}
+
+fn bank(samples: Vec<SoundFontSample>) -> SoundFont {
+    let mut soundfont = build_fallback_soundfont(44100.0);
+    soundfont.samples = samples;
+    soundfont
+}
+
+#[test]
+fn test_loop_points_move_to_the_nearest_rising_crossing() {
+    let mut soundfont = bank(vec![
+        sine_sample("Flute", 204, 796, SampleType::MonoSample, 0),
+        sine_sample("Aligned", 200, 800, SampleType::MonoSample, 0),
+        sine_sample("One-shot", 0, 0, SampleType::MonoSample, 0),
+    ]);
+    let adjustments = snap_loops(&mut soundfont, 8);
+    assert_eq!(adjustments, vec![LoopAdjustment {
+        sample: 0,
+        name: "Flute".to_string(),
+        loop_start_before: 204,
+        loop_end_before: 796,
+        loop_start: 200,
+        loop_end: 800,
+    }]);
+    assert_eq!((soundfont.samples[0].loop_start, soundfont.samples[0].loop_end), (200, 800));
+    assert_eq!((soundfont.samples[2].loop_start, soundfont.samples[2].loop_end), (0, 0));
+}
+
+#[test]
+fn test_loops_without_a_crossing_in_the_window_stay_put() {
+    // Start is 30 frames from a crossing; the end alone would snap
+    let mut soundfont = bank(vec![sine_sample("Pad", 230, 796, SampleType::MonoSample, 0)]);
+    assert!(snap_loops(&mut soundfont, 8).is_empty());
+    assert_eq!((soundfont.samples[0].loop_start, soundfont.samples[0].loop_end), (230, 796));
+
+    // Off leaves everything alone; windows are capped
+    assert!(snap_loops(&mut soundfont, 0).is_empty());
+    assert_eq!(snap_loops(&mut soundfont, MAX_SNAP_WINDOW * 100).len(), 1);
+}
+
+#[test]
+fn test_stereo_pairs_snap_together() {
+    let mut soundfont = bank(vec![
+        sine_sample("Piano L", 204, 796, SampleType::LeftSample, 1),
+        sine_sample("Piano R", 204, 796, SampleType::RightSample, 0),
+    ]);
+    let adjustments = snap_loops(&mut soundfont, 8);
+    assert_eq!(adjustments.iter().map(|adjustment| adjustment.sample).collect::<Vec<_>>(), vec![0, 1]);
+    for sample in &soundfont.samples {
+        assert_eq!((sample.loop_start, sample.loop_end), (200, 800));
+    }
+}
@@ -0,0 +1,88 @@
+//! Unit tests for BS.1770 loudness and true-peak metering
+
+use awe_synth::audio::loudness::{measure, LoudnessMeter};
+use awe_synth::worklet::AudioWorkletBridge;
+
+const SAMPLE_RATE: f32 = 48000.0;
+
+/// Interleaved stereo sine with both channels at `dbfs` peak
+fn stereo_sine(frequency: f32, dbfs: f32, seconds: f32) -> Vec<f32> {
+    let amplitude = 10f32.powf(dbfs / 20.0);
+    (0..(seconds * SAMPLE_RATE) as usize)
+        .flat_map(|i| {
+            let sample = amplitude * (2.0 * std::f32::consts::PI * frequency * i as f32 / SAMPLE_RATE).sin();
+            [sample, sample]
+        })
+        .collect()
+}
+
+#[test]
+fn test_reference_sine_reads_its_level() {
+    // EBU Tech 3341: a -23 dBFS 1 kHz stereo sine measures -23 LUFS
+    let report = measure(&stereo_sine(1000.0, -23.0, 5.0), 2, SAMPLE_RATE);
+    for reading in [report.momentary_lufs, report.short_term_lufs, report.integrated_lufs, report.max_momentary_lufs] {
+        let lufs = reading.expect("window filled");
+        assert!((lufs + 23.0).abs() < 0.1, "expected -23 LUFS, got {}", lufs);
+    }
+    assert!((report.elapsed_seconds - 5.0).abs() < 1e-6);
+    assert!((report.true_peak_dbtp.expect("peak") + 23.0).abs() < 0.1);
+}
+
+#[test]
+fn test_relative_gate_drops_quiet_passages() {
+    let mut samples = stereo_sine(1000.0, -20.0, 10.0);
+    samples.extend(stereo_sine(1000.0, -40.0, 10.0));
+    let report = measure(&samples, 2, SAMPLE_RATE);
+    let integrated = report.integrated_lufs.expect("gated blocks");
+    assert!((integrated + 20.0).abs() < 0.2, "quiet half should be gated out: {}", integrated);
+    assert!((report.short_term_lufs.expect("window filled") + 40.0).abs() < 0.1);
+
+    // Below the absolute gate nothing counts
+    let report = measure(&stereo_sine(1000.0, -80.0, 2.0), 2, SAMPLE_RATE);
+    assert_eq!(report.integrated_lufs, None);
+    assert!(report.momentary_lufs.expect("not silent") < -70.0);
+}
+
+#[test]
+fn test_true_peak_finds_inter_sample_overs() {
+    // Quarter-rate sine sampled 45 degrees off its peaks: samples reach only -3 dB
+    let samples: Vec<f32> = (0..4800)
+        .map(|i| (std::f32::consts::FRAC_PI_2 * i as f32 + std::f32::consts::FRAC_PI_4).sin())
+        .collect();
+    assert!(samples.iter().all(|sample| sample.abs() < 0.71));
+    let report = measure(&samples, 1, SAMPLE_RATE);
+    let true_peak = report.true_peak_dbtp.expect("peak");
+    assert!(true_peak > -0.5 && true_peak < 0.5, "true peak {}", true_peak);
+}
+
+#[test]
+fn test_silence_and_reset() {
+    let report = measure(&[0.0; 9600], 2, SAMPLE_RATE);
+    assert_eq!(report.momentary_lufs, None);
+    assert_eq!(report.integrated_lufs, None);
+    assert_eq!(report.true_peak_dbtp, None);
+
+    let mut meter = LoudnessMeter::new(SAMPLE_RATE);
+    meter.process_interleaved(&stereo_sine(1000.0, -10.0, 1.0));
+    assert!(meter.integrated_lufs().is_some());
+    meter.reset();
+    let report = meter.report();
+    assert_eq!(report.integrated_lufs, None);
+    assert_eq!(report.elapsed_seconds, 0.0);
+}
+
+#[test]
+fn test_bridge_meters_rendered_output() {
+    let mut bridge = AudioWorkletBridge::new(44100.0);
+    assert!(bridge.get_loudness().contains("\"elapsedSeconds\":0.0"));
+    let mut block = [0.0f32; 256];
+    bridge.queue_midi_event(0, 0, 0x90, 69, 100);
+    for _ in 0..200 {
+        bridge.process_stereo_into(&mut block);
+    }
+    let report: serde_json::Value = serde_json::from_str(&bridge.get_loudness()).expect("json");
+    assert!(report["momentaryLufs"].is_number());
+    assert!(report["truePeakDbtp"].is_number());
+    bridge.reset_loudness();
+    assert!(bridge.get_loudness().contains("\"momentaryLufs\":null"));
+}
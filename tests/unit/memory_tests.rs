@@ -0,0 +1,22 @@
+//! Unit tests for WASM memory reservation bookkeeping
+
+use awe_synth::audio::memory::{self, estimate_soundfont_load_bytes, SOUNDFONT_LOAD_FACTOR};
+
+#[test]
+fn test_reserve_tracks_largest_reservation() {
+    assert!(memory::reserve(256 * 1024));
+    assert!(memory::reserve(64 * 1024));
+    assert!(memory::stats().reserved_bytes >= 256 * 1024);
+}
+
+#[test]
+fn test_soundfont_estimate_and_native_growth() {
+    assert_eq!(estimate_soundfont_load_bytes(10), 10 * SOUNDFONT_LOAD_FACTOR);
+    assert_eq!(estimate_soundfont_load_bytes(usize::MAX), usize::MAX);
+
+    // No linear memory on native targets: nothing to observe
+    memory::observe_growth(true);
+    let stats = memory::stats();
+    assert_eq!(stats.current_bytes, 0);
+    assert_eq!(stats.growth_events, 0);
+}
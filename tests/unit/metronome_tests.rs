@@ -0,0 +1,77 @@
+//! Unit tests for the metronome click and its main mix / click bus routing
+
+use awe_synth::audio::metronome::Metronome;
+use awe_synth::midi::sequencer::MidiSequencer;
+use awe_synth::MidiPlayer;
+use super::smf_fixture::smf;
+
+/// Peak (main, bus) of the next `samples` click samples
+fn peaks(metronome: &mut Metronome, samples: usize) -> (f32, f32) {
+    (0..samples).fold((0.0f32, 0.0f32), |(main, bus), _| {
+        let (m, b) = metronome.next_sample();
+        (main.max(m.abs()), bus.max(b.abs()))
+    })
+}
+
+/// SMF with no notes; `signature` adds a Time Signature meta event (numerator, denominator power) at tick 0
+fn empty_smf(signature: Option<(u8, u8)>) -> Vec<u8> {
+    let mut events = Vec::new();
+    if let Some((numerator, power)) = signature {
+        events.extend_from_slice(&[0x00, 0xFF, 0x58, 0x04, numerator, power, 24, 8]);
+    }
+    // End of Track after 8 quarters at 480 PPQ
+    events.extend_from_slice(&[0x9E, 0x00, 0xFF, 0x2F, 0x00]);
+    smf(&[&events])
+}
+
+#[test]
+fn test_click_defaults_to_bus_only_with_accented_downbeat() {
+    let mut metronome = Metronome::new(44100.0);
+    metronome.advance_to(0, 480, 4);
+    assert_eq!(peaks(&mut metronome, 100), (0.0, 0.0), "disabled metronome stays silent");
+
+    metronome.set_enabled(true);
+    metronome.advance_to(0, 480, 4);
+    let (main, downbeat) = peaks(&mut metronome, 4410);
+    assert_eq!(main, 0.0, "main mix stays clean by default");
+    assert!(downbeat > 0.1);
+
+    metronome.advance_to(240, 480, 4);
+    assert_eq!(peaks(&mut metronome, 100).1, 0.0, "one click per beat");
+    metronome.advance_to(480, 480, 4);
+    let (_, beat) = peaks(&mut metronome, 4410);
+    assert!(beat > 0.0 && beat < downbeat, "downbeat is accented");
+
+    metronome.set_levels(0.5, 2.0);
+    assert_eq!(metronome.levels(), (0.5, 1.0));
+    metronome.reset();
+    metronome.advance_to(480, 480, 4);
+    let (main, bus) = peaks(&mut metronome, 4410);
+    assert!(main > 0.0 && (main - bus * 0.5).abs() < 1e-6, "reset re-arms the current beat");
+}
+
+#[test]
+fn test_beat_grid_follows_time_signature() {
+    let mut sequencer = MidiSequencer::new(44100.0);
+    sequencer.load_midi_file(&empty_smf(None)).unwrap();
+    assert_eq!(sequencer.get_beat_grid(), (480, 4));
+
+    sequencer.load_midi_file(&empty_smf(Some((6, 3)))).unwrap();
+    assert_eq!(sequencer.get_beat_grid(), (240, 6), "6/8 beats on eighth notes");
+}
+
+#[test]
+fn test_player_clicks_into_main_mix_only_when_sent() {
+    let mut player = MidiPlayer::new();
+    player.load_midi_file(&empty_smf(None)).unwrap();
+    player.set_metronome_enabled(true);
+    player.play();
+    player.advance_time(128);
+    let silent = (0..2000).map(|_| player.process().abs()).fold(0.0, f32::max);
+    assert_eq!(silent, 0.0, "click stays off the main mix at the default level");
+
+    player.set_metronome_levels(1.0, 1.0);
+    player.advance_time(22050); // Half a second at 120 BPM: next beat
+    let clicked = (0..2000).map(|_| player.process().abs()).fold(0.0, f32::max);
+    assert!(clicked > 0.1);
+}
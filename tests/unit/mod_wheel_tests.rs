@@ -0,0 +1,76 @@
+//! Unit tests for mod wheel vibrato mappings (host ranges/curves and SoundFont CC1 modulators)
+
+use awe_synth::effects::modulation::{curve_from_name, LINEAR_CURVE};
+use awe_synth::soundfont::modulators::CurveType;
+use awe_synth::soundfont::types::{GeneratorType, Modulator};
+use awe_synth::synth::fallback_bank::build_fallback_soundfont;
+use awe_synth::synth::mod_wheel::ModWheelVibrato;
+use awe_synth::synth::voice_manager::VoiceManager;
+use awe_synth::synth::zone_generators::PresetGenerators;
+
+fn cc1_to_vibrato(source_enum: u16, cents: i16) -> Modulator {
+    Modulator { source_enum, dest_enum: GeneratorType::VibLfoToPitch, amount: cents, amount_source_enum: 0, trans_enum: 0 }
+}
+
+/// Half a second of a note on channel 0 with the wheel all the way up
+fn render_with_wheel(vibrato: Option<ModWheelVibrato>) -> Vec<(f32, f32)> {
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_mod_wheel_vibrato(Some(0), vibrato);
+    manager.set_modulation_wheel(0, 127);
+    assert!(manager.note_on(69, 100, 0).is_some());
+    (0..22050).map(|_| manager.process()).collect()
+}
+
+#[test]
+fn test_default_mapping_keeps_the_old_range() {
+    let vibrato = ModWheelVibrato::default();
+    assert_eq!(vibrato.depth(0.0), 0.02);
+    assert!((vibrato.depth(1.0) - 0.15).abs() < 1e-6);
+    assert!((vibrato.semitones(1.0) - 0.3).abs() < 1e-6, "30 cents at full wheel");
+
+    let concave = ModWheelVibrato::new(0.0, 1.0, curve_from_name("concave").unwrap());
+    assert!(concave.depth(0.5) < ModWheelVibrato::new(0.0, 1.0, LINEAR_CURVE).depth(0.5));
+    let clamped = ModWheelVibrato::new(f32::NAN, 3.0, LINEAR_CURVE);
+    assert_eq!((clamped.min_depth, clamped.max_depth), (0.0, 1.0));
+}
+
+#[test]
+fn test_soundfont_cc1_modulators_set_the_range() {
+    let other = Modulator { dest_enum: GeneratorType::ModLfoToPitch, ..cc1_to_vibrato(0x0081, 50) };
+    assert_eq!(ModWheelVibrato::from_modulators(std::slice::from_ref(&other)), None);
+
+    let vibrato = ModWheelVibrato::from_modulators(&[other, cc1_to_vibrato(0x0081, 50)]).unwrap();
+    assert_eq!((vibrato.min_depth, vibrato.max_depth), (0.0, 0.25), "50 of 200 cents");
+    assert_eq!(vibrato.curve, LINEAR_CURVE);
+
+    // Concave, negative: type 1 in bits 10-15, direction bit 8
+    let shaped = ModWheelVibrato::from_modulators(&[cc1_to_vibrato(0x0581, 100)]).unwrap();
+    assert_eq!(shaped.curve.curve, CurveType::Concave);
+    assert!(shaped.curve.negative);
+
+    let mut bank = build_fallback_soundfont(44100.0);
+    assert_eq!(PresetGenerators::resolve(&bank.presets[0], &bank).mod_wheel_vibrato, None);
+    let instrument = bank.presets[0].preset_zones[0].instrument_id.unwrap() as usize;
+    bank.instruments[instrument].instrument_zones[0].modulators.push(cc1_to_vibrato(0x0081, 50));
+    bank.presets[0].preset_zones[0].modulators.push(cc1_to_vibrato(0x0081, 30));
+    let resolved = PresetGenerators::resolve(&bank.presets[0], &bank).mod_wheel_vibrato.unwrap();
+    assert!((resolved.max_depth - 0.4).abs() < 1e-6, "preset amount adds to the instrument's");
+}
+
+#[test]
+fn test_channel_mapping_drives_vibrato_and_clears_back_to_the_preset() {
+    let plain = render_with_wheel(None);
+    let wide = render_with_wheel(Some(ModWheelVibrato::new(0.0, 1.0, LINEAR_CURVE)));
+    assert_ne!(plain, wide, "full wheel swings the pitch");
+    assert_eq!(render_with_wheel(Some(ModWheelVibrato::new(0.0, 0.0, LINEAR_CURVE))), plain);
+
+    let mut manager = VoiceManager::new(44100.0);
+    let vibrato = ModWheelVibrato::default();
+    assert!(manager.set_mod_wheel_vibrato(None, Some(vibrato)));
+    assert_eq!(manager.mod_wheel_vibrato(9), Some(vibrato));
+    manager.reset_channels();
+    assert_eq!(manager.mod_wheel_vibrato(9), Some(vibrato), "host setting survives resets");
+    assert!(manager.set_mod_wheel_vibrato(Some(9), None));
+    assert_eq!(manager.mod_wheel_vibrato(9), None);
+    assert!(!manager.set_mod_wheel_vibrato(Some(16), None));
+}
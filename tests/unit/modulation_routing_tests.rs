@@ -0,0 +1,95 @@
+//! Unit tests for host modulation routes (matrix, curves, live controller sources)
+
+use awe_synth::effects::modulation::{
+    curve_from_name, ModulationDestination, ModulationMatrix, ModulationRoute, ModulationRouter, ModulationSource,
+    LINEAR_CURVE, MAX_MATRIX_ROUTES,
+};
+use awe_synth::synth::voice_manager::VoiceManager;
+
+const SAMPLE_RATE: f32 = 4000.0;
+
+fn peak(manager: &mut VoiceManager, samples: usize) -> f32 {
+    (0..samples).map(|_| {
+        let (left, right) = manager.process();
+        left.abs().max(right.abs())
+    }).fold(0.0, f32::max)
+}
+
+#[test]
+fn test_matrix_replaces_limits_and_removes_routes() {
+    let mut matrix = ModulationMatrix::new();
+    let wheel_to_cutoff = |amount| ModulationRoute::new(ModulationSource::ModWheel, ModulationDestination::FilterCutoff, amount, LINEAR_CURVE);
+    assert!(matrix.set_route(None, wheel_to_cutoff(0.5)));
+    assert!(matrix.set_route(None, wheel_to_cutoff(-0.25)));
+    assert_eq!(matrix.global_routes().len(), 1, "same source and destination replaces");
+    assert_eq!(matrix.global_routes()[0].depth, -0.25);
+
+    assert!(matrix.set_route(Some(3), ModulationRoute::new(ModulationSource::Aftertouch, ModulationDestination::Tremolo, 2.0, LINEAR_CURVE)));
+    assert_eq!(matrix.channel_routes(3)[0].depth, 1.0, "amount is clamped");
+    assert!(matrix.channel_routes(4).is_empty());
+    assert!(!matrix.set_route(Some(16), wheel_to_cutoff(0.5)));
+
+    let destinations = [ModulationDestination::Pitch, ModulationDestination::Amplitude, ModulationDestination::Tremolo, ModulationDestination::Vibrato];
+    let sources = [ModulationSource::Velocity, ModulationSource::KeyNumber];
+    for source in sources {
+        for destination in destinations {
+            matrix.set_route(None, ModulationRoute::new(source, destination, 0.1, LINEAR_CURVE));
+        }
+    }
+    assert_eq!(matrix.global_routes().len(), MAX_MATRIX_ROUTES);
+    assert!(!matrix.set_route(None, ModulationRoute::new(ModulationSource::Aftertouch, ModulationDestination::Pitch, 0.1, LINEAR_CURVE)));
+
+    assert!(matrix.remove_route(None, ModulationSource::ModWheel, ModulationDestination::FilterCutoff));
+    assert!(!matrix.remove_route(None, ModulationSource::ModWheel, ModulationDestination::FilterCutoff));
+    assert!(matrix.clear(Some(3)));
+    assert!(matrix.channel_routes(3).is_empty());
+    assert_eq!(matrix.global_routes().len(), MAX_MATRIX_ROUTES - 1, "clearing a channel leaves the global routes");
+}
+
+#[test]
+fn test_route_amount_applies_curve_and_full_scale() {
+    let mut router = ModulationRouter::new();
+    let wheel_to_pitch = ModulationRoute::new(ModulationSource::ModWheel, ModulationDestination::Pitch, 0.5, LINEAR_CURVE);
+    router.set_routes(&[wheel_to_pitch], &[]);
+    router.set_source_value(ModulationSource::ModWheel, 0.5);
+    assert!((router.route_amount(ModulationDestination::Pitch) - 3.0).abs() < 1e-5, "half wheel x half of 12 semitones");
+    assert_eq!(router.route_amount(ModulationDestination::FilterCutoff), 0.0);
+
+    let switched = ModulationRoute { curve: curve_from_name("switch").unwrap(), ..wheel_to_pitch };
+    router.set_routes(&[], &[switched]);
+    router.set_source_value(ModulationSource::ModWheel, 0.4);
+    assert_eq!(router.route_amount(ModulationDestination::Pitch), 0.0);
+    router.set_source_value(ModulationSource::ModWheel, 0.6);
+    assert_eq!(router.route_amount(ModulationDestination::Pitch), 6.0);
+
+    let concave = ModulationRoute { curve: curve_from_name("concave").unwrap(), ..wheel_to_pitch };
+    router.set_routes(&[concave], &[]);
+    router.set_source_value(ModulationSource::ModWheel, 0.5);
+    assert!(router.route_amount(ModulationDestination::Pitch) < 3.0, "concave stays low through mid travel");
+    assert!(curve_from_name("exponential").is_none());
+}
+
+#[test]
+fn test_aftertouch_route_reaches_sounding_and_new_notes() {
+    let mut manager = VoiceManager::new(SAMPLE_RATE);
+    manager.set_smoothing_time_ms(0.0);
+    manager.process_midi_control_change(0, 91, 0);
+    manager.note_on(60, 100, 0);
+    let open = peak(&mut manager, 400);
+    assert!(open > 0.01);
+
+    // Full pressure through a -100% amplitude route silences the channel
+    let route = ModulationRoute::new(ModulationSource::Aftertouch, ModulationDestination::Amplitude, -1.0, LINEAR_CURVE);
+    assert!(manager.set_modulation_route(Some(0), route));
+    assert!(peak(&mut manager, 400) > 0.01, "no pressure, no change");
+    manager.set_channel_pressure(0, 127);
+    assert!(peak(&mut manager, 400) < open * 0.01);
+
+    // A new note starts with the channel's pressure and routes
+    manager.note_on(64, 100, 0);
+    assert!(peak(&mut manager, 400) < open * 0.01);
+
+    assert!(manager.remove_modulation_route(Some(0), ModulationSource::Aftertouch, ModulationDestination::Amplitude));
+    assert!(peak(&mut manager, 400) > 0.01);
+    assert!(!manager.clear_modulation_routes(Some(16)));
+}
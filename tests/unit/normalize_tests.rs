@@ -0,0 +1,69 @@
+//! Unit tests for loudness-normalized export
+
+use awe_synth::audio::export::{encode, encode_with_options, ExportFormat, ExportOptions};
+use awe_synth::audio::loudness::measure;
+use awe_synth::audio::normalize::normalize;
+use awe_synth::audio::wav::WavFormat;
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+fn stereo_sine(frequency: f32, amplitude: f32, seconds: f32) -> Vec<f32> {
+    (0..(seconds * SAMPLE_RATE) as usize)
+        .flat_map(|i| {
+            let sample = amplitude * (2.0 * std::f32::consts::PI * frequency * i as f32 / SAMPLE_RATE).sin();
+            [sample, sample]
+        })
+        .collect()
+}
+
+#[test]
+fn test_quiet_render_is_raised_to_target() {
+    let mut samples = stereo_sine(440.0, 0.01, 4.0);
+    let report = normalize(&mut samples, 2, SAMPLE_RATE, -16.0, -1.0);
+    assert!(report.gain_db > 20.0);
+    assert!(!report.limited);
+    assert!((report.output_lufs.expect("measured") + 16.0).abs() < 0.1);
+    assert!((measure(&samples, 2, SAMPLE_RATE).integrated_lufs.expect("measured") + 16.0).abs() < 0.1);
+}
+
+#[test]
+fn test_hot_target_limits_peaks() {
+    // A sparse click over a quiet bed: reaching -10 LUFS pushes the clicks far over the ceiling
+    let mut samples = stereo_sine(220.0, 0.05, 4.0);
+    for frame in (0..samples.len() / 2).step_by(11025) {
+        samples[frame * 2] = 0.9;
+        samples[frame * 2 + 1] = -0.9;
+    }
+    let report = normalize(&mut samples, 2, SAMPLE_RATE, -10.0, -1.0);
+    assert!(report.limited);
+    assert!(report.output_true_peak_dbtp.expect("peak") <= -1.0 + 1e-3);
+    assert!((report.output_lufs.expect("measured") + 10.0).abs() < 0.5, "limited output {:?}", report.output_lufs);
+}
+
+#[test]
+fn test_silence_and_surround_are_left_alone() {
+    let mut silence = vec![0.0; 8820];
+    let report = normalize(&mut silence, 2, SAMPLE_RATE, -14.0, -1.0);
+    assert_eq!(report.measured_lufs, None);
+    assert_eq!(report.gain_db, 0.0);
+    assert!(silence.iter().all(|&sample| sample == 0.0));
+
+    let mut quad = vec![0.1; 4000];
+    normalize(&mut quad, 4, SAMPLE_RATE, -14.0, -1.0);
+    assert!(quad.iter().all(|&sample| sample == 0.1));
+}
+
+#[test]
+fn test_export_option_normalizes_before_encoding() {
+    let samples = stereo_sine(440.0, 0.01, 2.0);
+    let options = ExportOptions::from_json(r#"{"targetLufs": -14, "truePeakCeilingDbtp": -2}"#).expect("options");
+    assert_eq!(options.target_lufs, Some(-14.0));
+    assert_eq!(options.true_peak_ceiling_dbtp, Some(-2.0));
+
+    let float = ExportFormat::Wav(WavFormat::Float32);
+    let wav = encode_with_options(float, 44100, 2, &samples, options);
+    assert_ne!(wav, encode(float, 44100, 2, &samples));
+    let decoded: Vec<f32> = wav[58..].chunks(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+    assert_eq!(decoded.len(), samples.len());
+    assert!((measure(&decoded, 2, SAMPLE_RATE).integrated_lufs.expect("measured") + 14.0).abs() < 0.1);
+}
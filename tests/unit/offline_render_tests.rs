@@ -0,0 +1,76 @@
+//! Unit tests for offline render auto-stop (silence detection and tail cap)
+
+use awe_synth::audio::silence::{RenderStopReason, SilenceDetector, DEFAULT_TAIL_CAP_SECONDS, MAX_TAIL_CAP_SECONDS};
+use awe_synth::worklet::AudioWorkletBridge;
+
+const SAMPLE_RATE: f32 = 1000.0;
+
+#[test]
+fn test_detector_waits_for_idle_voices_and_silence() {
+    let mut detector = SilenceDetector::new(SAMPLE_RATE);
+    let quiet = [0.00001f32; 200]; // -100 dB, 100 frames
+    let loud = [0.1f32; 200];
+
+    detector.observe(&quiet, true, 0);
+    assert!(!detector.is_finished(), "off by default");
+
+    detector.configure(true, -90.0, 10.0);
+    for _ in 0..5 {
+        detector.observe(&quiet, false, 0);
+    }
+    assert_eq!(detector.status().tail_seconds, 0.0, "events still pending");
+    detector.observe(&loud, true, 0);
+    detector.observe(&quiet, true, 1);
+    detector.observe(&quiet, true, 0);
+    assert!(!detector.is_finished(), "a voice was still sounding");
+    detector.observe(&quiet, true, 0);
+    detector.observe(&quiet, true, 0);
+    assert!(detector.is_finished(), "250 ms of silence");
+    let status = detector.status();
+    assert_eq!(status.stop_reason, Some(RenderStopReason::Silence));
+    assert!((status.tail_seconds - 0.5).abs() < 1e-6);
+
+    detector.observe(&loud, false, 1);
+    assert!(!detector.is_finished(), "activity re-arms");
+}
+
+#[test]
+fn test_tail_cap_stops_a_render_that_never_decays() {
+    let mut detector = SilenceDetector::new(SAMPLE_RATE);
+    detector.configure(true, -90.0, 1.0);
+    let loud = [0.1f32; 200];
+    for _ in 0..9 {
+        detector.observe(&loud, true, 1);
+    }
+    assert!(!detector.is_finished());
+    detector.observe(&loud, true, 1);
+    assert_eq!(detector.status().stop_reason, Some(RenderStopReason::TailCap));
+
+    detector.configure(true, f32::NAN, 1e9);
+    let status = detector.status();
+    assert_eq!((status.threshold_db, status.tail_cap_seconds), (-90.0, MAX_TAIL_CAP_SECONDS));
+    assert!(!status.finished);
+    detector.configure(true, 6.0, f32::INFINITY);
+    assert_eq!((detector.status().threshold_db, detector.status().tail_cap_seconds), (0.0, DEFAULT_TAIL_CAP_SECONDS));
+}
+
+#[test]
+fn test_bridge_render_returns_zero_after_the_tail() {
+    let mut bridge = AudioWorkletBridge::new(44100.0);
+    bridge.set_offline_render(true, -90.0, 20.0);
+    bridge.queue_midi_event(0, 2, 0x92, 64, 100);
+    for _ in 0..100 {
+        assert_eq!(bridge.render_stereo_block(128), 128);
+    }
+    bridge.queue_midi_event(0, 2, 0x82, 64, 0);
+
+    let mut blocks = 0;
+    while bridge.render_stereo_block(128) > 0 {
+        blocks += 1;
+        assert!(blocks < 44100 * 30 / 128, "never stopped");
+    }
+    let status: serde_json::Value = serde_json::from_str(&bridge.get_offline_render_status()).unwrap();
+    assert_eq!(status["stopReason"], "silence");
+    assert!(status["tailSeconds"].as_f64().unwrap() < 5.0, "{}", status);
+    assert_eq!(bridge.render_stereo_block(128), 0, "stays stopped");
+}
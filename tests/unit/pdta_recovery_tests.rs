@@ -0,0 +1,176 @@
+//! Unit tests for pdta record recovery (missing terminal records, partial records, bad indices)
+
+use awe_synth::soundfont::types::SoundFont;
+use awe_synth::soundfont::SoundFontParser;
+use super::soundfont_stream_tests::{list_chunk, sub_chunk};
+
+fn name(name: &str) -> Vec<u8> {
+    let mut bytes = name.as_bytes().to_vec();
+    bytes.resize(20, 0);
+    bytes
+}
+
+fn preset_header(preset: &str, program: u16, bag: u16) -> Vec<u8> {
+    let mut record = name(preset);
+    record.extend(program.to_le_bytes());
+    record.extend(0u16.to_le_bytes());
+    record.extend(bag.to_le_bytes());
+    record.extend([0; 12]);
+    record
+}
+
+fn instrument_header(instrument: &str, bag: u16) -> Vec<u8> {
+    let mut record = name(instrument);
+    record.extend(bag.to_le_bytes());
+    record
+}
+
+fn sample_header(sample: &str, start: u32, end: u32) -> Vec<u8> {
+    let mut record = name(sample);
+    for value in [start, end, start + 4, end - 4, 44100] {
+        record.extend(value.to_le_bytes());
+    }
+    record.extend([60, 0, 0, 0, 1, 0]);
+    record
+}
+
+fn bags(indices: &[u16]) -> Vec<u8> {
+    indices.iter().flat_map(|&index| [index.to_le_bytes(), 0u16.to_le_bytes()].concat()).collect()
+}
+
+fn generators(records: &[(u16, u16)]) -> Vec<u8> {
+    records.iter().flat_map(|&(kind, amount)| [kind.to_le_bytes(), amount.to_le_bytes()].concat()).collect()
+}
+
+/// pdta sub-chunks of a two-preset, two-instrument, two-sample bank, terminal records included
+struct Pdta {
+    phdr: Vec<u8>,
+    pbag: Vec<u8>,
+    pgen: Vec<u8>,
+    inst: Vec<u8>,
+    ibag: Vec<u8>,
+    igen: Vec<u8>,
+    shdr: Vec<u8>,
+}
+
+impl Pdta {
+    fn complete() -> Self {
+        Self {
+            phdr: [preset_header("Preset A", 0, 0), preset_header("Preset B", 1, 1), preset_header("EOP", 0, 2)].concat(),
+            pbag: bags(&[0, 1, 2]),
+            pgen: generators(&[(41, 0), (41, 1), (0, 0)]),
+            inst: [instrument_header("Inst A", 0), instrument_header("Inst B", 1), instrument_header("EOI", 2)].concat(),
+            ibag: bags(&[0, 2, 4]),
+            igen: generators(&[(43, 0x7F00), (53, 0), (43, 0x7F00), (53, 1), (0, 0)]),
+            shdr: [sample_header("Low", 0, 32), sample_header("High", 32, 64), name("EOS"), vec![0; 26]].concat(),
+        }
+    }
+
+    /// The same bank as exported without any terminal record
+    fn unterminated() -> Self {
+        Self {
+            phdr: [preset_header("Preset A", 0, 0), preset_header("Preset B", 1, 1)].concat(),
+            pbag: bags(&[0, 1]),
+            pgen: generators(&[(41, 0), (41, 1)]),
+            inst: [instrument_header("Inst A", 0), instrument_header("Inst B", 1)].concat(),
+            ibag: bags(&[0, 2]),
+            igen: generators(&[(43, 0x7F00), (53, 0), (43, 0x7F00), (53, 1)]),
+            shdr: [sample_header("Low", 0, 32), sample_header("High", 32, 64)].concat(),
+        }
+    }
+
+    fn parse(&self) -> SoundFont {
+        let mut info = sub_chunk(b"ifil", &[0x02, 0x00, 0x01, 0x00]);
+        info.extend(sub_chunk(b"isng", b"EMU8000\0"));
+        info.extend(sub_chunk(b"INAM", b"Recovery"));
+        let samples: Vec<u8> = (0..64i16).flat_map(|s| (s * 100).to_le_bytes()).collect();
+        let mut pdta = Vec::new();
+        for (id, data) in [
+            (b"phdr", &self.phdr), (b"pbag", &self.pbag), (b"pmod", &vec![0; 10]), (b"pgen", &self.pgen),
+            (b"inst", &self.inst), (b"ibag", &self.ibag), (b"imod", &vec![0; 10]), (b"igen", &self.igen),
+            (b"shdr", &self.shdr),
+        ] {
+            pdta.extend(sub_chunk(id, data));
+        }
+
+        let mut body = b"sfbk".to_vec();
+        body.extend(list_chunk(b"INFO", &info));
+        body.extend(list_chunk(b"sdta", &sub_chunk(b"smpl", &samples)));
+        body.extend(list_chunk(b"pdta", &pdta));
+        let mut file = b"RIFF".to_vec();
+        file.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        file.extend(body);
+        SoundFontParser::parse_soundfont(&file).expect("bank parses")
+    }
+}
+
+/// Presets, instruments and samples as (name, zone targets)
+fn layout(soundfont: &SoundFont) -> Vec<(String, Vec<Option<u16>>)> {
+    let presets = soundfont.presets.iter()
+        .map(|preset| (preset.name.clone(), preset.preset_zones.iter().map(|zone| zone.instrument_id).collect()));
+    let instruments = soundfont.instruments.iter()
+        .map(|instrument| (instrument.name.clone(), instrument.instrument_zones.iter().map(|zone| zone.sample_id).collect()));
+    let samples = soundfont.samples.iter().map(|sample| (sample.name.clone(), Vec::new()));
+    presets.chain(instruments).chain(samples).collect()
+}
+
+fn expected_layout() -> Vec<(String, Vec<Option<u16>>)> {
+    vec![
+        ("Preset A".to_string(), vec![Some(0)]),
+        ("Preset B".to_string(), vec![Some(1)]),
+        ("Inst A".to_string(), vec![Some(0)]),
+        ("Inst B".to_string(), vec![Some(1)]),
+        ("Low".to_string(), vec![]),
+        ("High".to_string(), vec![]),
+    ]
+}
+
+#[test]
+fn test_each_header_gets_only_its_own_zones() {
+    let soundfont = Pdta::complete().parse();
+    assert_eq!(layout(&soundfont), expected_layout(), "terminal records are not presets, instruments or samples");
+    assert_eq!(soundfont.instruments[1].instrument_zones[0].generators.len(), 2);
+}
+
+#[test]
+fn test_missing_terminal_records_are_implied() {
+    assert_eq!(layout(&Pdta::unterminated().parse()), expected_layout());
+
+    // Terminal headers missing but terminal bags and generators present
+    let mut pdta = Pdta::complete();
+    pdta.phdr.truncate(2 * 38);
+    pdta.inst.truncate(2 * 22);
+    pdta.shdr.truncate(2 * 46);
+    assert_eq!(layout(&pdta.parse()), expected_layout());
+}
+
+#[test]
+fn test_trailing_partial_records_are_ignored() {
+    let mut pdta = Pdta::complete();
+    for chunk in [&mut pdta.phdr, &mut pdta.pbag, &mut pdta.pgen, &mut pdta.inst, &mut pdta.ibag, &mut pdta.igen, &mut pdta.shdr] {
+        chunk.extend([0xAA, 0xBB, 0xCC]);
+    }
+    assert_eq!(layout(&pdta.parse()), expected_layout());
+}
+
+#[test]
+fn test_bad_indices_drop_zones_not_the_bank() {
+    let mut pdta = Pdta::complete();
+    pdta.phdr = [preset_header("Preset A", 0, 0), preset_header("Preset B", 1, 40), preset_header("EOP", 0, 2)].concat();
+    pdta.ibag = bags(&[0, 300, 4]);
+    let soundfont = pdta.parse();
+
+    assert_eq!(soundfont.presets[0].preset_zones[0].instrument_id, Some(0));
+    assert!(soundfont.presets[1].preset_zones.is_empty(), "bag index past the end");
+    assert_eq!(soundfont.instruments.len(), 2);
+    assert!(soundfont.instruments[1].instrument_zones[0].generators.is_empty(), "generator index past the end");
+}
+
+#[test]
+fn test_unnamed_instruments_keep_their_index() {
+    let mut pdta = Pdta::complete();
+    pdta.inst = [instrument_header("", 0), instrument_header("Inst B", 1), instrument_header("EOI", 2)].concat();
+    let soundfont = pdta.parse();
+    assert_eq!(soundfont.instruments.len(), 2);
+    assert_eq!(soundfont.instruments[1].name, "Inst B", "Preset B's instrument 1 still resolves");
+}
@@ -0,0 +1,68 @@
+//! Unit tests for keyboard split/layer performance zones
+
+use awe_synth::synth::performance::{zone_channel, KeyboardPerformance, PerformanceZone, MAX_PERFORMANCE_ZONES};
+use awe_synth::synth::voice_manager::VoiceManager;
+
+#[test]
+fn test_zone_range_and_transpose() {
+    // Reversed keys are swapped, volume is clamped
+    let zone = PerformanceZone::new(59, 36, 0, 32, -12, 5.0);
+    assert_eq!((zone.low_key, zone.high_key), (36, 59));
+    assert_eq!(zone.volume, 2.0);
+
+    assert_eq!(zone.target_note(35), None);
+    assert_eq!(zone.target_note(36), Some(24));
+    assert_eq!(zone.target_note(59), Some(47));
+    assert_eq!(zone.target_note(60), None);
+
+    // Transposed past the MIDI range is dropped rather than wrapped
+    let high = PerformanceZone::new(100, 127, 0, 0, 24, 1.0);
+    assert_eq!(high.target_note(103), Some(127));
+    assert_eq!(high.target_note(104), None);
+
+    let mut performance = KeyboardPerformance::new();
+    performance.set_input_channel(Some(0));
+    assert!(!performance.takes_channel(0), "no zones, nothing to route");
+    assert!(performance.set_zone(1, Some(zone)));
+    assert!(!performance.set_zone(MAX_PERFORMANCE_ZONES, Some(zone)));
+    assert!(performance.takes_channel(0));
+    assert!(!performance.takes_channel(1));
+    assert_eq!(performance.channel_volume(zone_channel(1)), Some(2.0));
+    assert_eq!(performance.channel_volume(zone_channel(0)), None);
+    assert_eq!(performance.channel_volume(0), None);
+}
+
+#[test]
+fn test_split_and_layer_note_routing() {
+    let mut manager = VoiceManager::new(44100.0);
+    // Bass below middle C, pad over the upper half, overlapping on 48-59
+    assert!(manager.set_performance_zone(0, Some(PerformanceZone::new(0, 59, 0, 32, -12, 1.0))));
+    assert!(manager.set_performance_zone(1, Some(PerformanceZone::new(48, 127, 0, 88, 0, 0.5))));
+    manager.set_performance_input(Some(0));
+    assert!(manager.is_performance_channel(0));
+
+    assert_eq!(manager.performance_note_on(40, 100), 1, "split: bass only");
+    assert_eq!(manager.performance_note_on(72, 100), 1, "split: pad only");
+    assert_eq!(manager.performance_note_on(52, 100), 2, "overlap layers both zones");
+    assert_eq!(manager.get_active_voice_count(), 4);
+
+    // Plain MIDI Note Off for the sounding pitches leaves zone notes alone
+    manager.note_off(28);
+    manager.note_off(72);
+    for _ in 0..4410 {
+        manager.process();
+    }
+    assert_eq!(manager.get_active_voice_count(), 4);
+
+    manager.performance_note_off(40);
+    manager.performance_note_off(72);
+    manager.performance_note_off(52);
+    for _ in 0..44100 * 4 {
+        manager.process();
+    }
+    assert_eq!(manager.get_active_voice_count(), 0);
+
+    // Turning the mode off hands the channel back
+    manager.set_performance_input(None);
+    assert!(!manager.is_performance_channel(0));
+}
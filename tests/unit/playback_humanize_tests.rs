@@ -0,0 +1,83 @@
+//! Unit tests for playback humanize (velocity scatter, timing jitter, held events)
+
+use awe_synth::midi::humanize::{Humanizer, MAX_TIMING_JITTER_MS, MAX_VELOCITY_RANGE};
+use awe_synth::MidiEvent;
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+/// Every event, in the order it goes out, with the block time it was due in
+fn run(humanizer: &mut Humanizer, blocks: &[(u64, Vec<MidiEvent>)], end: u64) -> Vec<MidiEvent> {
+    let mut out = Vec::new();
+    let mut pending = blocks.iter().peekable();
+    for now in (0..=end).step_by(128) {
+        while let Some(event) = humanizer.next_due(now) {
+            out.push(event);
+        }
+        while let Some((_, events)) = pending.next_if(|(at, _)| *at <= now) {
+            out.extend(events.iter().filter_map(|&event| humanizer.schedule(event, now)));
+        }
+    }
+    out
+}
+
+#[test]
+fn test_off_by_default_passes_events_through() {
+    let mut humanizer = Humanizer::new(SAMPLE_RATE);
+    assert_eq!(humanizer.latency_samples(), 0);
+    let event = humanizer.schedule(MidiEvent::new(500, 0, 0x90, 60, 100), 500).unwrap();
+    assert_eq!((event.timestamp, event.data2), (500, 100));
+    assert!(!humanizer.set_channel(Some(16), 10, 10.0));
+    assert!(humanizer.set_channel(None, 200, 500.0));
+    assert_eq!(humanizer.channels()[7].velocity_range, MAX_VELOCITY_RANGE);
+    assert_eq!(humanizer.channels()[7].timing_ms, MAX_TIMING_JITTER_MS);
+}
+
+#[test]
+fn test_velocities_scatter_within_range() {
+    let mut humanizer = Humanizer::new(SAMPLE_RATE);
+    humanizer.set_channel(Some(0), 10, 0.0);
+    let velocities: Vec<u8> = (0..200)
+        .map(|_| humanizer.schedule(MidiEvent::new(0, 0, 0x90, 60, 100), 0).unwrap().data2)
+        .collect();
+    assert!(velocities.iter().all(|&v| (90..=110).contains(&v)));
+    assert!(velocities.iter().any(|&v| v < 97) && velocities.iter().any(|&v| v > 103), "spread both ways");
+    let other = humanizer.schedule(MidiEvent::new(0, 1, 0x90, 60, 100), 0).unwrap();
+    assert_eq!(other.data2, 100, "other channels are left alone");
+}
+
+#[test]
+fn test_timing_jitter_keeps_order_and_note_lengths() {
+    let mut humanizer = Humanizer::new(SAMPLE_RATE);
+    humanizer.set_channel(Some(0), 0, 20.0);
+    humanizer.set_seed(7);
+    let latency = humanizer.latency_samples();
+    assert_eq!(latency, 882);
+
+    // Program change then a note at the same block; eight short notes after it
+    let mut blocks = vec![(0, vec![MidiEvent::new(0, 0, 0xC0, 5, 0), MidiEvent::new(0, 0, 0x90, 60, 100)])];
+    blocks.push((256, vec![MidiEvent::new(256, 0, 0x80, 60, 0)]));
+    for i in 1..=8u64 {
+        blocks.push((i * 4096, vec![MidiEvent::new(0, 0, 0x90, 64, 100), MidiEvent::new(0, 1, 0x90, 40, 100)]));
+        blocks.push((i * 4096 + 128, vec![MidiEvent::new(0, 0, 0x80, 64, 0)]));
+    }
+    let out = run(&mut humanizer, &blocks, 40000);
+    assert_eq!(out.len(), 3 + 8 * 3, "nothing is lost");
+    assert!(out.windows(2).all(|pair| pair[0].timestamp <= pair[1].timestamp));
+    assert_eq!(out[0].message_type, 0xC0, "the note never lands before the program change");
+
+    let mut shifts = Vec::new();
+    for (on, off) in out.iter().filter(|e| e.message_type == 0x90 && e.data1 == 64)
+        .zip(out.iter().filter(|e| e.message_type == 0x80 && e.data1 == 64)) {
+        assert_eq!(off.timestamp - on.timestamp, 128, "note length survives");
+        shifts.push(on.timestamp % 4096);
+    }
+    assert!(shifts.iter().all(|&shift| shift <= 2 * latency));
+    assert!(shifts.iter().any(|&s| s < latency) && shifts.iter().any(|&s| s > latency), "early and late");
+    // Channel 1 has no window of its own but stays in time with the held-back channel
+    assert!(out.iter().filter(|e| e.channel == 1).all(|e| e.timestamp % 4096 == latency));
+
+    humanizer.schedule(MidiEvent::new(0, 0, 0x90, 60, 100), 50000);
+    assert_eq!(humanizer.pending_count(), 1);
+    humanizer.clear();
+    assert!(humanizer.next_due(u64::MAX).is_none());
+}
@@ -0,0 +1,27 @@
+//! Unit tests for preset pre-warming ahead of program changes
+
+use awe_synth::synth::fallback_bank::FALLBACK_DRUM_PRESET_INDEX;
+use awe_synth::synth::voice_manager::VoiceManager;
+
+#[test]
+fn test_pre_load_walks_zones_and_touches_samples() {
+    let mut manager = VoiceManager::new(44100.0);
+
+    let piano = manager.pre_load_preset(0, 0).expect("fallback bank resolves every program");
+    assert_eq!(piano.preset_index, 0);
+    assert_eq!((piano.zones, piano.samples), (1, 1));
+    assert!(piano.sample_bytes > 0);
+
+    // Drum kit: one zone per sound, each with its own sample
+    let kit = manager.pre_load_preset(128, 0).expect("standard kit");
+    assert_eq!(kit.preset_index, FALLBACK_DRUM_PRESET_INDEX);
+    assert!(kit.zones > 1);
+    assert!(kit.samples > 1 && kit.samples <= kit.zones, "shared samples are counted once");
+
+    // Pre-warming changes no channel or voice state
+    assert_eq!(manager.get_channel_state(0).map(|state| state.program), Some(0));
+    assert_eq!(manager.get_active_voice_count(), 0);
+
+    manager.set_fallback_bank_enabled(false);
+    assert!(manager.pre_load_preset(0, 0).is_none(), "nothing to resolve without a bank");
+}
@@ -0,0 +1,89 @@
+//! Unit tests for the preset level balance pass
+
+use awe_synth::synth::fallback_bank::build_fallback_soundfont;
+use awe_synth::synth::preset_balance::{PresetBalance, MAX_BALANCE_TRIM_DB};
+use awe_synth::synth::voice_manager::VoiceManager;
+use awe_synth::synth::zone_generators::PresetGenerators;
+use awe_synth::soundfont::types::SoundFont;
+
+/// Fallback bank with the piano family (programs 1-8) recorded 20 dB low
+fn bank_with_quiet_pianos() -> SoundFont {
+    let mut bank = build_fallback_soundfont(44100.0);
+    let instrument = bank.presets[0].preset_zones[0].instrument_id.unwrap() as usize;
+    let zone = &mut bank.instruments[instrument].instrument_zones[0];
+    let mut sample = bank.samples[zone.sample_id.unwrap() as usize].clone();
+    sample.sample_data = sample.sample_data.iter().map(|&value| value / 10).collect();
+    zone.sample_id = Some(bank.samples.len() as u16);
+    bank.samples.push(sample);
+    bank
+}
+
+fn analyze(bank: &SoundFont) -> PresetBalance {
+    let generators: Vec<PresetGenerators> = bank.presets.iter()
+        .map(|preset| PresetGenerators::resolve(preset, bank))
+        .collect();
+    PresetBalance::analyze(bank, &generators, 44100.0)
+}
+
+fn rms_of_note(manager: &mut VoiceManager, frames: usize) -> f32 {
+    manager.note_on(60, 100, 0);
+    let sum: f32 = (0..frames).map(|_| {
+        let (left, right) = manager.process();
+        left * left + right * right
+    }).sum();
+    (sum / frames as f32).sqrt()
+}
+
+#[test]
+fn test_quiet_presets_are_trimmed_up_to_the_cap() {
+    let balance = analyze(&bank_with_quiet_pianos());
+    let reference = balance.reference_db.expect("the fallback bank sounds");
+    assert_eq!(balance.presets.len(), 129);
+
+    for preset in &balance.presets {
+        assert!(preset.trim_db.abs() <= MAX_BALANCE_TRIM_DB);
+        if preset.bank == 128 {
+            assert!(preset.level_db.is_some());
+            assert_eq!(preset.trim_db, 0.0);
+        }
+    }
+    // 20 dB under the rest: measured that way, trimmed by the cap only
+    let piano = &balance.presets[0];
+    assert!(reference - piano.level_db.unwrap() > 19.0);
+    assert_eq!(piano.trim_db, MAX_BALANCE_TRIM_DB);
+    assert_eq!(balance.trim_db(0), MAX_BALANCE_TRIM_DB);
+    assert_eq!(balance.trim_db(1000), 0.0);
+}
+
+#[test]
+fn test_trim_applies_at_note_on_only_when_enabled() {
+    let mut plain = VoiceManager::new(44100.0);
+    plain.load_soundfont(bank_with_quiet_pianos()).unwrap();
+    assert!(plain.preset_balance().is_none());
+    let plain_rms = rms_of_note(&mut plain, 4096);
+
+    let mut balanced = VoiceManager::new(44100.0);
+    balanced.load_soundfont(bank_with_quiet_pianos()).unwrap();
+    balanced.set_preset_balance(true);
+    let balanced_rms = rms_of_note(&mut balanced, 4096);
+
+    // +12 dB is a factor of ~3.98
+    let ratio = balanced_rms / plain_rms;
+    assert!((ratio - 3.98).abs() < 0.1, "ratio {ratio}");
+
+    // Turning it off keeps the measurement but plays the next note untrimmed
+    balanced.set_preset_balance(false);
+    assert!(!balanced.is_preset_balance_enabled());
+    assert!(balanced.preset_balance().is_some());
+}
+
+#[test]
+fn test_loading_a_soundfont_reanalyzes_while_enabled() {
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_preset_balance(true);
+    // Nothing loaded: the fallback bank is measured (its families are close to level)
+    assert!(manager.preset_balance().unwrap().trim_db(0).abs() < 3.0);
+
+    manager.load_soundfont(bank_with_quiet_pianos()).unwrap();
+    assert_eq!(manager.preset_balance().unwrap().trim_db(0), MAX_BALANCE_TRIM_DB);
+}
@@ -0,0 +1,71 @@
+//! Unit tests for the preview channel (own program/volume, isolation from song channels)
+
+use awe_synth::synth::preview_channel::{PreviewChannel, MAX_PREVIEW_VOLUME};
+use awe_synth::synth::voice_manager::VoiceManager;
+
+fn run(manager: &mut VoiceManager, samples: usize) {
+    for _ in 0..samples {
+        manager.process();
+    }
+}
+
+#[test]
+fn test_preview_settings_are_clamped() {
+    let mut preview = PreviewChannel::new();
+    preview.set_volume(5.0);
+    assert_eq!(preview.volume, MAX_PREVIEW_VOLUME);
+    preview.set_volume(f32::NAN);
+    assert_eq!(preview.volume, MAX_PREVIEW_VOLUME);
+    preview.set_program(128, 200);
+    assert_eq!((preview.bank, preview.program), (128, 72));
+    preview.set_input_channel(Some(16));
+    assert_eq!(preview.input_channel, None);
+    preview.set_input_channel(Some(3));
+    assert!(preview.takes_channel(3) && !preview.takes_channel(4));
+}
+
+#[test]
+fn test_preview_notes_are_independent_of_song_channels() {
+    let mut manager = VoiceManager::new(44100.0);
+    assert!(manager.set_preview_program(0, 40));
+    assert_eq!(manager.preview_channel().program, 40);
+    assert!(manager.note_on(60, 100, 0).is_some());
+    assert!(manager.preview_note_on(60, 100).is_some());
+
+    // Song note-off and program change leave the preview note and program alone
+    manager.note_off(60);
+    manager.program_change(0, 0);
+    run(&mut manager, 44100 * 3);
+    assert_eq!(manager.get_active_voice_count(), 1);
+    assert_eq!(manager.preview_channel().program, 40);
+
+    manager.preview_note_off(60);
+    run(&mut manager, 44100 * 3);
+    assert_eq!(manager.get_active_voice_count(), 0);
+}
+
+#[test]
+fn test_audition_and_preview_share_the_channel_without_cutting_each_other() {
+    let mut manager = VoiceManager::new(44100.0);
+    assert!(manager.preview_note_on(48, 100).is_some());
+    assert!(manager.audition_preset(0, 0, 72, 100, 5000));
+    manager.stop_audition();
+    run(&mut manager, 44100 * 3);
+    assert_eq!(manager.get_active_voice_count(), 1, "held preview note survives the audition stop");
+
+    // Changing the live input releases held preview notes
+    manager.set_preview_input(Some(2));
+    assert!(manager.is_preview_input(2));
+    run(&mut manager, 44100 * 3);
+    assert_eq!(manager.get_active_voice_count(), 0);
+}
+
+#[test]
+fn test_preview_never_steals_song_voices() {
+    let mut manager = VoiceManager::new(44100.0);
+    for note in 0..32 {
+        assert!(manager.note_on(40 + note, 100, 0).is_some());
+    }
+    assert!(manager.preview_note_on(30, 100).is_none());
+    assert_eq!(manager.get_active_voice_count(), 32);
+}
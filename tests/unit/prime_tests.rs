@@ -0,0 +1,49 @@
+//! Unit tests for warm-start priming of the audio engine
+
+use awe_synth::worklet::{AudioWorkletBridge, MAX_PRIME_BLOCKS};
+
+const BLOCK: usize = 128;
+
+fn render(bridge: &mut AudioWorkletBridge) -> Vec<f32> {
+    assert_eq!(bridge.render_stereo_block(BLOCK), BLOCK);
+    // SAFETY: the bridge's render buffer holds at least BLOCK interleaved frames
+    unsafe { std::slice::from_raw_parts(bridge.render_buffer_ptr(), BLOCK * 2).to_vec() }
+}
+
+fn note_on(bridge: &mut AudioWorkletBridge) {
+    // Sent straight to the player: the shared event queue would hand the note to one bridge only
+    // SAFETY: the pointer is to the bridge's own player, which outlives this call
+    let player = unsafe { &mut *bridge.get_midi_player() };
+    player.send_midi_message(&[0x90, 60, 110]).unwrap();
+}
+
+#[test]
+fn test_priming_plays_like_idle_time() {
+    // The chorus LFO runs on through silence, so the reference idles for as long
+    let mut idle = AudioWorkletBridge::new(44100.0);
+    for _ in 0..4 {
+        assert!(render(&mut idle).iter().all(|&sample| sample == 0.0));
+    }
+    let mut primed = AudioWorkletBridge::new(44100.0);
+    assert!(primed.start_capture(10.0));
+    assert_eq!(primed.prime(4), 4 * BLOCK);
+    let status: serde_json::Value = serde_json::from_str(&primed.get_capture_status()).unwrap();
+    assert_eq!(status["seconds"], 0.0, "captures never see primed blocks");
+
+    note_on(&mut idle);
+    note_on(&mut primed);
+    for index in 0..16 {
+        assert_eq!(render(&mut idle), render(&mut primed), "block {} differs", index);
+    }
+}
+
+#[test]
+fn test_priming_waits_for_silence_and_is_capped() {
+    let mut bridge = AudioWorkletBridge::new(44100.0);
+    assert_eq!(bridge.prime(1000), MAX_PRIME_BLOCKS * BLOCK);
+    assert_eq!(bridge.prime(0), 0);
+
+    note_on(&mut bridge);
+    render(&mut bridge);
+    assert_eq!(bridge.prime(4), 0, "a sounding voice is never primed over");
+}
@@ -0,0 +1,49 @@
+//! Unit tests for the versioned bridge protocol
+
+use awe_synth::error::ErrorCode;
+use awe_synth::protocol::{execute, handle_command, reset_handshake, BridgeCommand, BridgeErrorCode, BridgeResponse,
+                          BRIDGE_PROTOCOL_VERSION};
+
+fn response_json(command: &str) -> serde_json::Value {
+    serde_json::from_str(&handle_command(command, None).to_json()).expect("response must be JSON")
+}
+
+#[test]
+fn test_invalid_command_is_rejected() {
+    let response = response_json(r#"{"type":"launchRockets"}"#);
+    assert_eq!(response["type"], "error");
+    assert_eq!(response["code"], "invalidCommand");
+}
+
+#[test]
+fn test_handshake_flow() {
+    reset_handshake();
+
+    let before = response_json(r#"{"type":"selectPreset","bank":0,"program":0}"#);
+    assert_eq!(before["code"], "handshakeRequired");
+
+    let mismatch = response_json(r#"{"type":"hello","protocolVersion":999}"#);
+    assert_eq!(mismatch["code"], "versionMismatch");
+
+    let hello = response_json(&format!(r#"{{"type":"hello","protocolVersion":{}}}"#, BRIDGE_PROTOCOL_VERSION));
+    assert_eq!(hello["type"], "hello");
+    assert_eq!(hello["protocolVersion"], BRIDGE_PROTOCOL_VERSION);
+    assert!(hello["capabilities"].as_array().unwrap().iter().any(|c| c == "transport"));
+
+    let missing = response_json(r#"{"type":"loadSoundFont"}"#);
+    assert_eq!(missing["code"], "missingPayload");
+}
+
+#[test]
+fn test_loose_exports_run_the_commands() {
+    // execute() skips the handshake; without a bridge the command itself fails
+    let response = execute(BridgeCommand::SetParam { name: "adaptiveMode".to_string(), value: 1.0 }, None);
+    assert!(matches!(response, BridgeResponse::Error { code: BridgeErrorCode::NotInitialized, .. }));
+
+    // The deprecated exports surface the same failure as a thrown ErrorEvent
+    let error = awe_synth::set_buffer_size_global(256).unwrap_err();
+    assert_eq!(error.code, ErrorCode::NotInitialized);
+    assert_eq!(error.export.as_deref(), Some("set_buffer_size_global"));
+    let error = awe_synth::queue_midi_event_global(0, 0, 0x90, 60, 100).unwrap_err();
+    assert_eq!(error.code, ErrorCode::NotInitialized);
+}
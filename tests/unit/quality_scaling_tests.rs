@@ -0,0 +1,85 @@
+//! Unit tests for quality scaling under CPU pressure (ladder order, hysteresis, bridge wiring)
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use awe_synth::audio::quality_scaler::{self, QualityChange, QualityLevel, QualityScaler, DEGRADE_HOLD_BLOCKS, RECOVER_BLOCKS};
+use awe_synth::synth::device_profile::{DeviceProfile, EffectsQuality, InterpolationQuality};
+use awe_synth::worklet::AudioWorkletBridge;
+
+/// Feed `blocks` blocks at `load`, collecting every move
+fn feed(scaler: &mut QualityScaler, load: f32, blocks: u32, profile: DeviceProfile) -> Vec<QualityChange> {
+    let baseline = profile.settings();
+    (0..blocks).filter_map(|_| scaler.observe(load, &baseline)).collect()
+}
+
+#[test]
+fn test_ladder_steps_down_in_order_relative_to_profile() {
+    let desktop = DeviceProfile::Desktop.settings();
+    let capped = QualityLevel::CappedPolyphony.apply(&desktop);
+    assert_eq!(capped.interpolation, InterpolationQuality::Linear);
+    assert_eq!(capped.effects, EffectsQuality::Reduced);
+    assert_eq!(capped.max_polyphony, desktop.max_polyphony / 2);
+    assert_eq!(capped.buffer_size, desktop.buffer_size, "buffer size is not part of the ladder");
+
+    let mut scaler = QualityScaler::new();
+    let changes = feed(&mut scaler, 0.95, DEGRADE_HOLD_BLOCKS * 5, DeviceProfile::Desktop);
+    let levels: Vec<QualityLevel> = changes.iter().map(|change| change.level).collect();
+    assert_eq!(levels, [QualityLevel::LinearInterpolation, QualityLevel::ReducedEffects, QualityLevel::CappedPolyphony]);
+    assert!(changes.iter().all(|change| change.load > 0.8));
+
+    // Low-end already runs linear interpolation: that rung is skipped
+    let mut scaler = QualityScaler::new();
+    let changes = feed(&mut scaler, 0.95, DEGRADE_HOLD_BLOCKS * 2, DeviceProfile::LowEnd);
+    assert_eq!(changes[0].previous, QualityLevel::Full);
+    assert_eq!(changes[0].level, QualityLevel::ReducedEffects);
+    assert_eq!(changes[0].settings.effects, EffectsQuality::Off);
+}
+
+#[test]
+fn test_hysteresis_between_thresholds() {
+    let mut scaler = QualityScaler::new();
+    feed(&mut scaler, 0.95, DEGRADE_HOLD_BLOCKS * 2, DeviceProfile::Mid);
+    let degraded = scaler.level();
+    assert!(degraded > QualityLevel::Full);
+
+    // Between the thresholds nothing moves
+    assert!(feed(&mut scaler, 0.65, RECOVER_BLOCKS * 2, DeviceProfile::Mid).is_empty());
+
+    // A brief rise above the recovery threshold resets the quiet count
+    feed(&mut scaler, 0.3, RECOVER_BLOCKS / 2, DeviceProfile::Mid);
+    feed(&mut scaler, 0.7, 8, DeviceProfile::Mid);
+    assert!(feed(&mut scaler, 0.3, RECOVER_BLOCKS * 3 / 4, DeviceProfile::Mid).is_empty());
+    assert_eq!(scaler.level(), degraded);
+
+    let changes = feed(&mut scaler, 0.3, RECOVER_BLOCKS * 3, DeviceProfile::Mid);
+    assert!(!changes.is_empty());
+    assert!(changes.iter().all(|change| change.level < change.previous), "recovery climbs one rung at a time");
+    assert_eq!(scaler.level(), QualityLevel::Full);
+}
+
+#[test]
+fn test_bridge_applies_steps_and_notifies() {
+    let received = Rc::new(RefCell::new(Vec::new()));
+    let sink = received.clone();
+    quality_scaler::set_listener(Some(Rc::new(move |change: &QualityChange| sink.borrow_mut().push(*change))));
+
+    let mut bridge = AudioWorkletBridge::new(44100.0);
+    assert!(bridge.set_device_profile("desktop"));
+    let block_ms = 128.0 / 44100.0 * 1000.0;
+    for _ in 0..DEGRADE_HOLD_BLOCKS * 4 {
+        bridge.record_processing_time(block_ms * 0.95, 128);
+    }
+    quality_scaler::set_listener(None);
+
+    assert_eq!(received.borrow().len(), 3);
+    let settings = bridge.device_profile_status().settings;
+    assert_eq!(settings, bridge.quality_scaling_status().settings);
+    assert_eq!(settings.interpolation, InterpolationQuality::Linear);
+    assert_eq!(settings.max_polyphony, DeviceProfile::Desktop.settings().max_polyphony / 2);
+    assert!(bridge.get_quality_scaling().contains("\"level\":\"cappedPolyphony\""));
+
+    bridge.set_quality_scaling(false);
+    assert_eq!(bridge.device_profile_status().settings, DeviceProfile::Desktop.settings());
+    bridge.record_processing_time(block_ms * 0.95, 128);
+    assert_eq!(bridge.quality_scaling_status().level, QualityLevel::Full);
+}
@@ -0,0 +1,146 @@
+//! Unit tests for sequence recording (punch region, pre-roll, merging a pass, takes)
+
+use awe_synth::midi::edit::{apply, TrackEdit};
+use awe_synth::midi::parser::{MetaEventType, MidiEvent, MidiEventType, MidiTrack};
+use awe_synth::midi::recording::{track_messages, RecordingFailure, RecordingPhase, SequenceRecorder, MAX_PRE_ROLL_BARS};
+use awe_synth::midi::sequencer::MidiSequencer;
+use super::smf_fixture::smf;
+
+const BAR: u64 = 1920; // 4/4 at 480 PPQ
+
+#[test]
+fn test_punch_region_and_pre_roll_start() {
+    let mut recorder = SequenceRecorder::new();
+    assert_eq!(recorder.start_tick(BAR), None, "no punch-in: record from where playback is");
+    assert!(!recorder.set_punch_region(Some(4 * BAR), Some(4 * BAR)));
+    assert!(recorder.set_punch_region(Some(4 * BAR), Some(6 * BAR)));
+
+    recorder.set_pre_roll_bars(2);
+    assert_eq!(recorder.start_tick(BAR), Some(2 * BAR));
+    recorder.set_pre_roll_bars(100);
+    assert_eq!(recorder.status().pre_roll_bars, MAX_PRE_ROLL_BARS);
+    assert_eq!(recorder.start_tick(BAR), Some(0), "pre-roll stops at the start of the song");
+}
+
+#[test]
+fn test_only_the_punch_region_is_kept() {
+    let mut recorder = SequenceRecorder::new();
+    recorder.set_punch_region(Some(1000), Some(2000));
+    recorder.arm(1);
+    assert_eq!(recorder.phase(), RecordingPhase::PreRoll);
+
+    assert_eq!(recorder.advance(500), None);
+    recorder.capture(500, [0x90, 60, 100]);
+    assert_eq!(recorder.status().events_captured, 0, "pre-roll is heard, not kept");
+
+    assert_eq!(recorder.advance(1000), None);
+    assert_eq!(recorder.phase(), RecordingPhase::Recording);
+    recorder.capture(1200, [0x90, 64, 90]);
+    recorder.capture(1500, [0xB0, 64, 127]);
+    assert_eq!(recorder.advance(2010), Some(2000), "punch-out ends the pass at its tick");
+    recorder.capture(2010, [0x80, 64, 0]);
+    assert_eq!(recorder.status().events_captured, 2);
+
+    let messages = recorder.finish(2000);
+    assert!(!recorder.is_active());
+    assert_eq!(messages, vec![(1200, [0x90, 64, 90]), (1500, [0xB0, 64, 127]), (2000, [0x80, 64, 0])]);
+}
+
+#[test]
+fn test_finished_pass_merges_into_the_track() {
+    let mut recorder = SequenceRecorder::new();
+    recorder.arm(0);
+    recorder.advance(0);
+    recorder.capture(100, [0x81, 48, 0]);   // Released after punch-in, struck before: dropped
+    recorder.capture(200, [0x91, 60, 100]);
+    recorder.capture(200, [0x91, 60, 0]);   // Same block: kept a tick after its note-on
+    recorder.capture(300, [0xC1, 5, 0]);
+    let messages = recorder.finish(960);
+    assert_eq!(messages, vec![(200, [0x91, 60, 100]), (201, [0x81, 60, 0]), (300, [0xC1, 5, 0])]);
+
+    let mut track = MidiTrack {
+        name: None,
+        events: vec![MidiEvent { delta_time: 960, absolute_time: 960, event_type: MidiEventType::MetaEvent(MetaEventType::EndOfTrack) }],
+    };
+    apply(&mut track, &TrackEdit::InsertMessages { messages }).unwrap();
+    let ticks: Vec<u64> = track.events.iter().map(|event| event.absolute_time).collect();
+    assert_eq!(ticks, vec![200, 201, 300, 960]);
+    assert!(matches!(track.events[2].event_type, MidiEventType::ProgramChange { channel: 1, program: 5 }));
+
+    // Anything but note, controller and program messages is refused whole
+    assert!(apply(&mut track, &TrackEdit::InsertMessages { messages: vec![(0, [0x90, 60, 1]), (0, [0xE0, 0, 64])] }).is_err());
+    assert_eq!(track.events.len(), 4);
+}
+
+#[test]
+fn test_takes_group_by_target_and_region() {
+    let mut recorder = SequenceRecorder::new();
+    recorder.set_take_mode(true);
+    recorder.set_punch_region(Some(BAR), Some(2 * BAR));
+    for track in [2, 3] {
+        recorder.arm(0);
+        recorder.finish(2 * BAR);
+        recorder.add_take(track);
+    }
+    recorder.set_punch_region(Some(3 * BAR), None);
+    recorder.arm(0);
+    recorder.finish(4 * BAR);
+    assert_eq!(recorder.add_take(4), 3, "add_take returns the new take's id");
+
+    let selected: Vec<(u32, bool)> = recorder.takes().iter().map(|take| (take.id, take.selected)).collect();
+    assert_eq!(selected, vec![(1, false), (2, true), (3, true)], "the newest take of each group plays");
+    assert!(recorder.select_take(1));
+    assert!(recorder.take(1).unwrap().selected && !recorder.take(2).unwrap().selected);
+    assert!(recorder.take(3).unwrap().selected, "other groups are left alone");
+    assert!(!recorder.select_take(9));
+
+    let group: Vec<u32> = recorder.takes_to_remove(2, true).iter().map(|take| take.id).collect();
+    assert_eq!(group, vec![1, 2]);
+
+    // Discarding the selected take hands over to the newest left in its group
+    recorder.remove_take(1);
+    assert!(recorder.take(2).unwrap().selected);
+    assert_eq!(recorder.take(2).unwrap().track, 2);
+    assert_eq!(recorder.take(3).unwrap().track, 3, "later take tracks move down");
+    assert!(recorder.is_take_track(3) && !recorder.is_take_track(4));
+}
+
+#[test]
+fn test_take_tracks_play_muted_and_merge_back() {
+    // SMF: one track, note 60 from 0 to 960, End of Track at 960
+    let data = smf(&[&[0x00, 0x90, 60, 100, 0x87, 0x40, 0x80, 60, 0, 0x00, 0xFF, 0x2F, 0x00]]);
+
+    let mut sequencer = MidiSequencer::new(44100.0);
+    sequencer.load_midi_file(&data).unwrap();
+
+    let mut recorder = SequenceRecorder::new();
+    recorder.set_take_mode(true);
+    let messages = vec![(0, [0x91, 64, 90]), (480, [0x81, 64, 0])];
+    let take = recorder.take_track(&messages, 960);
+    assert_eq!(take.name.as_deref(), Some("Take 1"));
+    assert_eq!(track_messages(&take), messages);
+
+    assert_eq!(sequencer.add_track(take), Ok(1));
+    assert!(sequencer.set_track_muted(1, true));
+    assert!(!sequencer.set_track_muted(2, true));
+    sequencer.play(0);
+    assert_eq!(sequencer.process(4410, 128).len(), 1, "the muted take's note-on is skipped");
+
+    assert_eq!(sequencer.remove_track(1), Ok(Vec::new()), "a muted take has nothing to release");
+    assert!(sequencer.remove_track(1).is_err());
+    assert!(!sequencer.is_track_muted(1));
+    assert_eq!(sequencer.remove_track(0), Ok(vec![(0, 60)]), "the song's note is still sounding");
+}
+
+#[test]
+fn test_refused_pass_is_kept_until_reported() {
+    let mut recorder = SequenceRecorder::new();
+    assert_eq!(recorder.take_failure(), None);
+
+    recorder.fail(RecordingFailure::NotMerged { track: 3, error: "Track index 3 out of range".to_string() });
+    assert_eq!(recorder.status().passes_failed, 1);
+    let failure = recorder.take_failure().unwrap();
+    assert_eq!(failure.to_string(), "Recording could not be merged into track 3: Track index 3 out of range");
+    assert_eq!(recorder.take_failure(), None, "a failure is reported once");
+    assert_eq!(recorder.status().passes_failed, 1, "the count stays");
+}
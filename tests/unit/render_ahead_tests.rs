@@ -0,0 +1,107 @@
+//! Unit tests for the render-ahead queue and underrun-driven auto-enable
+
+use awe_synth::audio::buffer_manager::{AudioBufferManager, REPEATED_UNDERRUNS};
+use awe_synth::audio::render_ahead::{RenderAhead, MAX_RENDER_AHEAD_BLOCKS};
+use awe_synth::worklet::AudioWorkletBridge;
+
+const BLOCK: usize = 128;
+
+fn render(bridge: &mut AudioWorkletBridge) -> Vec<f32> {
+    let frames = bridge.render_stereo_block(BLOCK);
+    assert_eq!(frames, BLOCK);
+    // SAFETY: the bridge's render buffer holds at least `frames` interleaved frames
+    unsafe { std::slice::from_raw_parts(bridge.render_buffer_ptr(), frames * 2).to_vec() }
+}
+
+#[test]
+fn test_queue_fills_and_drains_by_blocks() {
+    let mut ahead = RenderAhead::new();
+    assert!(!ahead.is_active());
+    assert!(!ahead.needs_block(BLOCK), "nothing is reserved while off");
+
+    ahead.set_depth_blocks(9);
+    assert_eq!(ahead.depth_blocks(), MAX_RENDER_AHEAD_BLOCKS);
+    ahead.set_depth_blocks(1);
+    let block: Vec<f32> = (0..BLOCK * 2).map(|index| index as f32).collect();
+    let click = vec![0.5; BLOCK];
+    ahead.push(&block, &click);
+    assert!(ahead.needs_block(BLOCK));
+    ahead.push(&block, &click);
+    assert!(!ahead.needs_block(BLOCK));
+
+    let mut stereo = vec![0.0; BLOCK * 2];
+    let mut click_out = vec![0.0; BLOCK];
+    assert_eq!(ahead.pop(BLOCK, &mut stereo, &mut click_out), BLOCK);
+    assert_eq!(stereo, block);
+    assert_eq!(click_out, click);
+    assert_eq!(ahead.queued_frames(), BLOCK);
+
+    // Switched off, the surplus still plays out
+    ahead.set_depth_blocks(0);
+    assert!(ahead.is_active());
+    assert!(!ahead.needs_block(BLOCK));
+    ahead.pop(BLOCK, &mut stereo, &mut click_out);
+    assert!(!ahead.is_active());
+
+    let status = ahead.status(44100.0);
+    assert_eq!((status.depth_blocks, status.queued_frames), (0, 0));
+}
+
+#[test]
+fn test_render_ahead_output_is_continuous() {
+    let mut direct = AudioWorkletBridge::new(44100.0);
+    let mut queued = AudioWorkletBridge::new(44100.0);
+    for bridge in [&mut direct, &mut queued] {
+        // Sent straight to the player: the shared event queue would hand the note to one bridge only
+        // SAFETY: the pointer is to the bridge's own player, which outlives this call
+        let player = unsafe { &mut *bridge.get_midi_player() };
+        player.send_midi_message(&[0x90, 60, 110]).unwrap();
+    }
+
+    for index in 0..24 {
+        match index {
+            4 => queued.set_render_ahead(2),
+            12 => queued.set_render_ahead(3),
+            18 => queued.set_render_ahead(0),
+            _ => {}
+        }
+        assert_eq!(render(&mut direct), render(&mut queued), "block {} differs", index);
+    }
+    assert!(render(&mut direct).iter().any(|&sample| sample != 0.0));
+    assert!(queued.get_render_ahead_status().contains("\"queuedFrames\":0"));
+}
+
+#[test]
+fn test_render_ahead_adds_latency_while_enabled() {
+    let mut bridge = AudioWorkletBridge::new(44100.0);
+    bridge.set_render_ahead(2);
+    for _ in 0..4 {
+        render(&mut bridge);
+    }
+    let status = bridge.render_ahead_status();
+    assert_eq!(status.queued_frames, 2 * BLOCK);
+    assert!((status.added_latency_ms - 2.0 * BLOCK as f32 / 44.1).abs() < 1e-3);
+}
+
+#[test]
+fn test_repeated_underruns_deepen_render_ahead() {
+    let mut manager = AudioBufferManager::new(None);
+    manager.set_sample_rate(1000.0);
+    assert!(!manager.record_underrun_at(0));
+    assert!(!manager.record_underrun_at(20_000), "underruns 20 s apart are not repeated");
+    assert!(!manager.record_underrun_at(21_000));
+    assert!(manager.record_underrun_at(22_000));
+    assert!(!manager.record_underrun_at(22_500), "the window starts over");
+
+    let mut bridge = AudioWorkletBridge::new(44100.0);
+    for _ in 0..REPEATED_UNDERRUNS {
+        bridge.record_underrun();
+    }
+    assert_eq!(bridge.render_ahead_status().depth_blocks, 1);
+
+    bridge.set_render_ahead_auto(false);
+    for _ in 0..REPEATED_UNDERRUNS {
+        bridge.record_underrun();
+    }
+    assert_eq!(bridge.render_ahead_status().depth_blocks, 1);
+}
@@ -0,0 +1,56 @@
+//! Unit tests for raw SoundFont sample preview playback
+
+use std::sync::Arc;
+use awe_synth::soundfont::types::{SampleType, SoundFontSample};
+use awe_synth::synth::sample_preview::SamplePreview;
+use awe_synth::synth::voice_manager::VoiceManager;
+
+/// 100-frame ramp at 44.1 kHz, root key 60, loop over frames 50-100
+fn ramp_sample() -> SoundFontSample {
+    SoundFontSample {
+        name: "Ramp".to_string(),
+        start_offset: 0,
+        end_offset: 100,
+        loop_start: 50,
+        loop_end: 100,
+        sample_rate: 44100,
+        original_pitch: 60,
+        pitch_correction: 0,
+        sample_link: 0,
+        sample_type: SampleType::MonoSample,
+        sample_data: (0..100).map(|i| i * 100).collect::<Vec<i16>>().into(),
+    }
+}
+
+#[test]
+fn test_preview_pitch_and_one_shot_end() {
+    let sample = ramp_sample();
+    let mut root = SamplePreview::new(&sample, 44100.0, 0.0, false, 1.0).unwrap();
+    let first: Vec<f32> = (0..3).map(|_| root.next_sample()).collect();
+    assert_eq!(first, vec![0.0, 100.0 / 32768.0, 200.0 / 32768.0], "root key plays sample for sample");
+
+    let mut octave_up = SamplePreview::new(&sample, 44100.0, 12.0, false, 1.0).unwrap();
+    octave_up.next_sample();
+    assert!((octave_up.next_sample() - 200.0 / 32768.0).abs() < 1e-6, "one octave up skips every other frame");
+    for _ in 0..60 {
+        octave_up.next_sample();
+    }
+    assert!(octave_up.is_finished());
+    assert_eq!(octave_up.next_sample(), 0.0);
+}
+
+#[test]
+fn test_looped_preview_stays_in_loop() {
+    let mut preview = SamplePreview::new(&ramp_sample(), 44100.0, 0.0, true, 2.0).unwrap();
+    for _ in 0..1000 {
+        let value = preview.next_sample() / 2.0 * 32768.0;
+        assert!(value.round() <= 9900.0);
+    }
+    assert!(!preview.is_finished());
+    let loop_value = preview.next_sample() / 2.0 * 32768.0;
+    assert!(loop_value >= 5000.0, "after the first pass only loop frames play");
+
+    let empty = SoundFontSample { sample_data: Arc::from(Vec::<i16>::new()), ..ramp_sample() };
+    assert!(SamplePreview::new(&empty, 44100.0, 0.0, true, 1.0).is_none());
+    assert!(!VoiceManager::new(44100.0).preview_sample(0, 0.0, false, 1.0), "needs a loaded SoundFont");
+}
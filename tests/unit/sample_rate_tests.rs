@@ -0,0 +1,49 @@
+//! Unit tests for declared sample rate validation
+
+use std::sync::Arc;
+use awe_synth::soundfont::sample_rates::{self, SampleRateWarning, DEFAULT_SAMPLE_RATE, MAX_SAMPLE_RATE, MIN_SAMPLE_RATE};
+use awe_synth::synth::fallback_bank::build_fallback_soundfont;
+use awe_synth::synth::multizone_voice::MultiZoneSampleVoice;
+
+#[test]
+fn test_corrected_rates() {
+    assert_eq!(sample_rates::corrected_rate(0), DEFAULT_SAMPLE_RATE);
+    assert_eq!(sample_rates::corrected_rate(22050), 22050);
+    assert_eq!(sample_rates::corrected_rate(192_000), 192_000);
+    assert_eq!(sample_rates::corrected_rate(192_001), MAX_SAMPLE_RATE);
+    assert_eq!(sample_rates::corrected_rate(u32::MAX), MAX_SAMPLE_RATE);
+    assert_eq!(sample_rates::corrected_rate(1), MIN_SAMPLE_RATE);
+}
+
+#[test]
+fn test_sanitize_records_each_corrected_sample() {
+    let mut bank = build_fallback_soundfont(44100.0);
+    bank.samples[0].sample_rate = 0;
+    bank.samples[1].sample_rate = 1_000_000;
+    let untouched = bank.samples[2].sample_rate;
+
+    let warnings = sample_rates::sanitize_sample_rates(&mut bank);
+    assert_eq!(warnings, vec![
+        SampleRateWarning { sample: 0, name: bank.samples[0].name.clone(), declared: 0, corrected: DEFAULT_SAMPLE_RATE },
+        SampleRateWarning { sample: 1, name: bank.samples[1].name.clone(), declared: 1_000_000, corrected: MAX_SAMPLE_RATE },
+    ]);
+    assert_eq!(bank.samples[0].sample_rate, DEFAULT_SAMPLE_RATE);
+    assert_eq!(bank.samples[2].sample_rate, untouched);
+    assert!(sample_rates::sanitize_sample_rates(&mut bank).is_empty(), "nothing left to correct");
+}
+
+#[test]
+fn test_bogus_rates_never_reach_the_output() {
+    let mut bank = build_fallback_soundfont(44100.0);
+    for (index, sample) in bank.samples.iter_mut().enumerate() {
+        sample.sample_rate = if index % 2 == 0 { 0 } else { u32::MAX };
+        sample.sample_data = Arc::from(vec![0i16; sample.sample_data.len()]); // Silent: exercises the rate-based fallback tone
+    }
+
+    let mut voice = MultiZoneSampleVoice::new(0, 44100.0);
+    voice.start_note(60, 100, 0, &bank, &bank.presets[0]).unwrap();
+    for _ in 0..4096 {
+        let (left, right) = voice.process();
+        assert!(left.is_finite() && right.is_finite());
+    }
+}
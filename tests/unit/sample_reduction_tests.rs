@@ -0,0 +1,104 @@
+//! Unit tests for load-time sample downsampling and truncation
+
+use awe_synth::soundfont::reduce::SampleReduction;
+use awe_synth::soundfont::types::{
+    Generator, GeneratorAmount, GeneratorType, InstrumentZone, SampleType, SoundFont, SoundFontHeader,
+    SoundFontInstrument, SoundFontSample, SoundFontVersion,
+};
+
+fn sample(name: &str, rate: u32, frames: usize, loop_points: (u32, u32)) -> SoundFontSample {
+    SoundFontSample {
+        name: name.to_string(),
+        start_offset: 0,
+        end_offset: frames as u32,
+        loop_start: loop_points.0,
+        loop_end: loop_points.1,
+        sample_rate: rate,
+        original_pitch: 60,
+        pitch_correction: 0,
+        sample_link: 0,
+        sample_type: SampleType::MonoSample,
+        sample_data: vec![1000i16; frames].into(),
+    }
+}
+
+/// A looped 44.1 kHz sample whose zone moves its loop start, and a 10 s one-shot at 22.05 kHz
+fn bank() -> SoundFont {
+    SoundFont {
+        header: SoundFontHeader {
+            version: SoundFontVersion { major: 2, minor: 1 },
+            name: "Reduce".to_string(),
+            engine: "EMU8000".to_string(),
+            tools: String::new(),
+            creation_date: String::new(),
+            author: String::new(),
+            product: String::new(),
+            copyright: String::new(),
+            comments: String::new(),
+            preset_count: 0,
+            instrument_count: 1,
+            sample_count: 2,
+        },
+        presets: vec![],
+        instruments: vec![SoundFontInstrument {
+            name: "Pad".to_string(),
+            instrument_bag_index: 0,
+            instrument_zones: vec![InstrumentZone {
+                generators: vec![
+                    Generator { generator_type: GeneratorType::StartloopAddrsOffset, amount: GeneratorAmount::Short(-400) },
+                    Generator { generator_type: GeneratorType::EndloopAddrsCoarseOffset, amount: GeneratorAmount::Short(1) },
+                ],
+                modulators: vec![],
+                sample_id: Some(0),
+                key_range: None,
+                velocity_range: None,
+            }],
+        }],
+        samples: vec![sample("Pad", 44100, 44100, (1000, 40000)), sample("Hit", 22050, 220500, (0, 0))],
+    }
+}
+
+fn short(zone: &InstrumentZone, kind: GeneratorType) -> Option<i16> {
+    zone.generators.iter().find(|g| g.generator_type == kind).and_then(|g| match g.amount {
+        GeneratorAmount::Short(value) => Some(value),
+        _ => None,
+    })
+}
+
+#[test]
+fn test_downsampling_rescales_loops_and_zone_offsets() {
+    let mut soundfont = bank();
+    let report = SampleReduction::from_limits(22050, 0.0).apply(&mut soundfont);
+    assert_eq!((report.samples_resampled, report.samples_truncated), (1, 0), "22.05 kHz sample is left alone");
+
+    let pad = &soundfont.samples[0];
+    assert_eq!((pad.sample_rate, pad.sample_data.len()), (22050, 22050));
+    assert_eq!((pad.loop_start, pad.loop_end), (500, 20000));
+    assert!(pad.sample_data.iter().all(|&s| s == 1000), "DC level survives the averaging");
+    assert_eq!(report.bytes_before - report.bytes_after, 22050 * 2);
+
+    let zone = &soundfont.instruments[0].instrument_zones[0];
+    assert_eq!(short(zone, GeneratorType::StartloopAddrsOffset), Some(-200));
+    // 1 x 32768 frames becomes 16384 frames, carried by a new fine generator
+    assert_eq!(short(zone, GeneratorType::EndloopAddrsCoarseOffset), Some(0));
+    assert_eq!(short(zone, GeneratorType::EndloopAddrsOffset), Some(16384));
+}
+
+#[test]
+fn test_truncation_keeps_loops_and_zero_disables() {
+    let mut soundfont = bank();
+    let report = SampleReduction::from_limits(0, 2.0).apply(&mut soundfont);
+    assert_eq!(report.samples_truncated, 1);
+    assert_eq!(soundfont.samples[1].sample_data.len(), 44100, "one-shot cut to 2 s");
+    assert_eq!(soundfont.samples[0].sample_data.len(), 44100, "1 s sample untouched");
+
+    let mut looped = bank();
+    SampleReduction::from_limits(0, 0.5).apply(&mut looped);
+    assert_eq!(looped.samples[0].sample_data.len(), 40000, "cut at the loop end, not inside the loop");
+
+    let mut untouched = bank();
+    let report = SampleReduction::from_limits(0, f32::NAN).apply(&mut untouched);
+    assert_eq!((report.samples_resampled, report.samples_truncated), (0, 0));
+    assert_eq!(report.bytes_before, report.bytes_after);
+    assert_eq!(SampleReduction::from_limits(4000, 0.0).target_sample_rate, Some(8000));
+}
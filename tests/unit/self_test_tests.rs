@@ -0,0 +1,59 @@
+//! Unit tests for the engine self-test
+
+use awe_synth::error::ErrorCode;
+use awe_synth::self_test::{self, SelfTestLevel, SelfTestStatus};
+use awe_synth::worklet::AudioWorkletBridge;
+
+#[test]
+fn test_self_test_level_lists() {
+    assert_eq!(SelfTestLevel::parse_list(""), Some(SelfTestLevel::ALL.to_vec()));
+    assert_eq!(SelfTestLevel::parse_list("all"), Some(SelfTestLevel::ALL.to_vec()));
+    assert_eq!(SelfTestLevel::parse_list("pipeline, engine,pipeline"),
+               Some(vec![SelfTestLevel::Pipeline, SelfTestLevel::Engine]), "listed order, no repeats");
+    assert_eq!(SelfTestLevel::parse_list("engine,gpu"), None);
+    let error = self_test::run_self_test("gpu").unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidArgument);
+    assert_eq!(error.export.as_deref(), Some("run_self_test"));
+    assert!(self_test::run_self_test("engine").unwrap().contains("\"failed\":0"));
+}
+
+#[test]
+fn test_engine_level_passes_without_a_bridge() {
+    let report = self_test::run(None, &[SelfTestLevel::Engine]);
+    assert_eq!(report.items.len(), 5);
+    for item in &report.items {
+        assert_eq!(item.status, SelfTestStatus::Pass, "{}: {}", item.name, item.message);
+        assert_eq!(item.level, SelfTestLevel::Engine);
+    }
+    assert_eq!((report.passed, report.failed, report.skipped), (5, 0, 0));
+
+    let release = &report.item("envelope").unwrap().measurements[0];
+    assert_eq!((release.name.as_str(), release.unit.as_str()), ("releaseTime", "ms"));
+    assert!(release.value > 0.0 && release.value < 2000.0);
+
+    let json = report.to_json();
+    assert!(json.contains(r#""status":"pass""#) && json.contains(r#""level":"engine""#), "{}", json);
+}
+
+#[test]
+fn test_bridge_levels_skip_what_cannot_run() {
+    let without = self_test::run(None, &[SelfTestLevel::SoundFont, SelfTestLevel::Pipeline]);
+    assert_eq!(without.item("sampleData").unwrap().status, SelfTestStatus::Skip);
+    assert_eq!(without.item("presetSynthesis").unwrap().status, SelfTestStatus::Skip);
+    assert_eq!(without.item("bridge").unwrap().status, SelfTestStatus::Fail);
+    assert_eq!(without.item("blockRender").unwrap().status, SelfTestStatus::Skip);
+
+    let mut bridge = AudioWorkletBridge::new(48000.0);
+    let report = self_test::run(Some(&mut bridge), &[SelfTestLevel::SoundFont, SelfTestLevel::Pipeline]);
+    let no_soundfont = report.item("presetSynthesis").unwrap();
+    assert_eq!((no_soundfont.status, no_soundfont.message.as_str()), (SelfTestStatus::Skip, "No SoundFont loaded"));
+    let ready = report.item("bridge").unwrap();
+    assert_eq!(ready.status, SelfTestStatus::Pass);
+    assert_eq!(ready.measurements[0].value, 48000.0);
+    // Skips if another test left events in the shared MIDI queue
+    let render = report.item("blockRender").unwrap();
+    assert_ne!(render.status, SelfTestStatus::Fail, "{}", render.message);
+    if render.status == SelfTestStatus::Pass {
+        assert_eq!(render.measurements[0].value, bridge.get_buffer_size() as f64);
+    }
+}
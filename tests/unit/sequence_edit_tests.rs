@@ -0,0 +1,110 @@
+//! Unit tests for MIDI sequence editing (insert/delete/move/quantize)
+
+use awe_synth::midi::edit::{apply, SequenceEvent, SequenceEventKind, TrackEdit};
+use awe_synth::midi::parser::{MetaEventType, MidiEvent, MidiEventType, MidiTrack};
+use awe_synth::midi::sequencer::MidiSequencer;
+use super::smf_fixture::smf;
+
+fn event(tick: u64, event_type: MidiEventType) -> MidiEvent {
+    MidiEvent { delta_time: 0, absolute_time: tick, event_type }
+}
+
+/// Two notes (60 at 0-480, 64 at 500-700) and End of Track at 960
+fn two_note_track() -> MidiTrack {
+    MidiTrack {
+        name: Some("Piano".to_string()),
+        events: vec![
+            event(0, MidiEventType::NoteOn { channel: 0, note: 60, velocity: 100 }),
+            event(480, MidiEventType::NoteOff { channel: 0, note: 60, velocity: 0 }),
+            event(500, MidiEventType::NoteOn { channel: 0, note: 64, velocity: 90 }),
+            event(700, MidiEventType::NoteOn { channel: 0, note: 64, velocity: 0 }),
+            event(960, MidiEventType::MetaEvent(MetaEventType::EndOfTrack)),
+        ],
+    }
+}
+
+fn ticks(track: &MidiTrack) -> Vec<u64> {
+    track.events.iter().map(|e| e.absolute_time).collect()
+}
+
+fn deltas_consistent(track: &MidiTrack) -> bool {
+    let mut previous = 0;
+    track.events.iter().all(|e| {
+        let ok = e.absolute_time - previous == e.delta_time as u64;
+        previous = e.absolute_time;
+        ok
+    })
+}
+
+#[test]
+fn test_listing_pairs_note_durations() {
+    let track = two_note_track();
+    let events = SequenceEvent::list(&track, 0, u64::MAX);
+    assert_eq!(events.len(), 5);
+    assert_eq!(events[0].kind, SequenceEventKind::NoteOn);
+    assert_eq!(events[0].duration_ticks, Some(480));
+    assert_eq!(events[2].duration_ticks, Some(200));
+    assert_eq!(events[3].kind, SequenceEventKind::NoteOff, "velocity-0 note-on lists as note-off");
+    assert_eq!(SequenceEvent::list(&track, 400, 600).iter().map(|e| e.index).collect::<Vec<_>>(), vec![1, 2]);
+}
+
+#[test]
+fn test_insert_keeps_order_and_end_of_track_last() {
+    let mut track = two_note_track();
+    apply(&mut track, &TrackEdit::InsertNote { tick: 900, duration: 240, channel: 1, note: 67, velocity: 80 }).unwrap();
+    apply(&mut track, &TrackEdit::InsertControlChange { tick: 480, channel: 0, controller: 64, value: 127 }).unwrap();
+
+    assert_eq!(ticks(&track), vec![0, 480, 480, 500, 700, 900, 1140, 1140]);
+    assert!(matches!(track.events[1].event_type, MidiEventType::NoteOff { .. }), "note-offs sort first at equal ticks");
+    assert!(matches!(track.events.last().unwrap().event_type, MidiEventType::MetaEvent(MetaEventType::EndOfTrack)));
+    assert!(deltas_consistent(&track));
+
+    assert!(apply(&mut track, &TrackEdit::InsertNote { tick: 0, duration: 0, channel: 0, note: 60, velocity: 100 }).is_err());
+    assert!(apply(&mut track, &TrackEdit::InsertControlChange { tick: 0, channel: 16, controller: 7, value: 100 }).is_err());
+}
+
+#[test]
+fn test_delete_and_move_carry_note_offs() {
+    let mut track = two_note_track();
+    apply(&mut track, &TrackEdit::Move { indices: vec![2], delta_ticks: -600 }).unwrap();
+    // Clamped to tick 0, the note keeps its 200-tick length
+    let listed = SequenceEvent::list(&track, 0, 1);
+    assert!(listed.iter().any(|e| e.note == Some(64) && e.duration_ticks == Some(200)));
+    assert!(deltas_consistent(&track));
+
+    let note_64 = listed.iter().find(|e| e.note == Some(64)).unwrap().index;
+    apply(&mut track, &TrackEdit::Delete { indices: vec![note_64] }).unwrap();
+    assert_eq!(ticks(&track), vec![0, 480, 960]);
+
+    assert!(apply(&mut track, &TrackEdit::Delete { indices: vec![2] }).is_err(), "End of Track is protected");
+    assert!(apply(&mut track, &TrackEdit::Delete { indices: vec![9] }).is_err());
+    assert_eq!(track.events.len(), 3, "failed edits leave the track untouched");
+}
+
+#[test]
+fn test_quantize_snaps_starts_and_keeps_lengths() {
+    let mut track = two_note_track();
+    apply(&mut track, &TrackEdit::Quantize { indices: vec![2], grid_ticks: 240 }).unwrap();
+    let note = SequenceEvent::list(&track, 0, u64::MAX).into_iter().find(|e| e.note == Some(64)).unwrap();
+    assert_eq!((note.tick, note.duration_ticks), (480, Some(200)));
+    assert!(apply(&mut track, &TrackEdit::Quantize { indices: vec![0], grid_ticks: 0 }).is_err());
+}
+
+#[test]
+fn test_sequencer_releases_sounding_note_when_its_note_off_is_deleted() {
+    // SMF: one track, note 60 from 0 to 960, End of Track at 960
+    let data = smf(&[&[0x00, 0x90, 60, 100, 0x87, 0x40, 0x80, 60, 0, 0x00, 0xFF, 0x2F, 0x00]]);
+
+    let mut sequencer = MidiSequencer::new(44100.0);
+    sequencer.load_midi_file(&data).unwrap();
+    sequencer.play(0);
+    assert_eq!(sequencer.process(4410, 128).len(), 1, "note-on dispatched");
+
+    // Moving the note keeps its note-off pending, so nothing needs releasing
+    let released = sequencer.edit_track(0, &TrackEdit::Move { indices: vec![0], delta_ticks: 10 }).unwrap();
+    assert!(released.is_empty());
+
+    let released = sequencer.edit_track(0, &TrackEdit::Delete { indices: vec![1] }).unwrap();
+    assert_eq!(released, vec![(0, 60)]);
+    assert!(sequencer.edit_track(3, &TrackEdit::Delete { indices: vec![0] }).is_err());
+}
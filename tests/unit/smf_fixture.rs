@@ -0,0 +1,18 @@
+//! Shared Standard MIDI File fixture for the unit tests
+
+/// SMF at 480 PPQ with one MTrk chunk per entry of `tracks`; each entry is raw
+/// delta-time + event bytes ending in End of Track. Format 0 for one track, 1 otherwise
+pub fn smf(tracks: &[&[u8]]) -> Vec<u8> {
+    let format: u16 = if tracks.len() == 1 { 0 } else { 1 };
+    let mut data = b"MThd".to_vec();
+    data.extend_from_slice(&6u32.to_be_bytes());
+    data.extend_from_slice(&format.to_be_bytes());
+    data.extend_from_slice(&(tracks.len() as u16).to_be_bytes());
+    data.extend_from_slice(&480u16.to_be_bytes());
+    for events in tracks {
+        data.extend_from_slice(b"MTrk");
+        data.extend_from_slice(&(events.len() as u32).to_be_bytes());
+        data.extend_from_slice(events);
+    }
+    data
+}
@@ -0,0 +1,111 @@
+//! Unit tests for the song setup region (SysEx parsing, region scan and skip on playback)
+
+use awe_synth::midi::chase::chase;
+use awe_synth::midi::parser::{MidiEvent, MidiEventType, MidiFile, MidiTrack};
+use awe_synth::midi::sequencer::{MidiSequencer, ProcessedEventType, ProcessedMidiEvent};
+use awe_synth::midi::setup::SongSetup;
+use super::smf_fixture::smf;
+
+const GS_RESET: [u8; 11] = [0xF0, 0x41, 0x10, 0x42, 0x12, 0x40, 0x00, 0x7F, 0x00, 0x41, 0xF7];
+
+fn event(tick: u64, event_type: MidiEventType) -> MidiEvent {
+    MidiEvent { delta_time: 0, absolute_time: tick, event_type }
+}
+
+fn cc(tick: u64, channel: u8, controller: u8, value: u8) -> MidiEvent {
+    event(tick, MidiEventType::ControlChange { channel, controller, value })
+}
+
+/// Events as (kind, data1, data2): 'S' SysEx, 'P' program, 'C' controller, 'N' note on
+fn summary(events: &[ProcessedMidiEvent]) -> Vec<(char, u8, u8)> {
+    events.iter().filter_map(|event| match event.event_type {
+        ProcessedEventType::SysEx { ref data } => Some(('S', data[0], data.len() as u8)),
+        ProcessedEventType::ProgramChange { program, .. } => Some(('P', program, 0)),
+        ProcessedEventType::ControlChange { controller, value, .. } => Some(('C', controller, value)),
+        ProcessedEventType::NoteOn { note, velocity, .. } => Some(('N', note, velocity)),
+        ProcessedEventType::NoteOff { .. } => None,
+    }).collect()
+}
+
+/// Format 0, 480 PPQ: GS Reset at 0, CC7 = 90 at 240, program 5 and a note at 480,
+/// CC7 = 60 at 960, end at 1920
+fn setup_song() -> Vec<u8> {
+    let mut events = vec![0x00, 0xF0, GS_RESET.len() as u8 - 1];
+    events.extend_from_slice(&GS_RESET[1..]);
+    events.extend_from_slice(&[
+        0x81, 0x70, 0xB0, 0x07, 90,
+        0x81, 0x70, 0xC0, 5,
+        0x00, 0x90, 60, 100,
+        0x83, 0x60, 0xB0, 0x07, 60,
+        0x87, 0x40, 0xFF, 0x2F, 0x00,
+    ]);
+    smf(&[&events])
+}
+
+#[test]
+fn test_sysex_is_parsed_and_the_track_continues() {
+    let file = MidiFile::parse(&setup_song()).unwrap();
+    let events = &file.tracks[0].events;
+    assert!(matches!(&events[0].event_type, MidiEventType::SysEx { data } if data[..] == GS_RESET[..]),
+            "F0 events keep their status byte");
+    assert!(events.iter().any(|event| matches!(event.event_type, MidiEventType::NoteOn { note: 60, .. })),
+            "a SysEx no longer ends the track");
+    assert_eq!(events.last().unwrap().absolute_time, 1920);
+}
+
+#[test]
+fn test_setup_region_runs_to_the_first_note() {
+    let track = MidiTrack {
+        name: None,
+        events: vec![
+            event(0, MidiEventType::SysEx { data: GS_RESET.to_vec() }),
+            event(0, MidiEventType::SysEx { data: vec![0xF0, 0x43, 0x10, 0x4C, 0x02, 0x01, 0x00, 0x01, 0xF7] }), // XG effect parameter
+            cc(120, 1, 0, 8),
+            event(240, MidiEventType::ProgramChange { channel: 1, program: 48 }),
+            event(480, MidiEventType::NoteOn { channel: 0, note: 60, velocity: 100 }),
+            cc(480, 0, 10, 20),     // Same tick as the first note: still setup
+            cc(960, 0, 7, 50),
+        ],
+    };
+    let file = MidiFile { format: 0, track_count: 1, division: 480, tracks: vec![track] };
+    let setup = SongSetup::scan(&file);
+    assert_eq!(setup.end_tick, 480);
+    assert_eq!(summary(&setup.events), vec![('S', 0xF0, 11), ('C', 0, 8), ('P', 48, 0), ('C', 10, 20)],
+               "unknown SysEx and events after the first note are left out");
+
+    let report = setup.report(true);
+    assert_eq!((report.sysex, report.program_changes, report.controllers), (1, 1, 2));
+    assert_eq!(report.channels, vec![0, 1]);
+
+    // A GS Reset makes the chase forget the channel state before it
+    let track = MidiTrack {
+        name: None,
+        events: vec![cc(0, 0, 7, 30), event(10, MidiEventType::SysEx { data: GS_RESET.to_vec() }), cc(20, 0, 10, 90)],
+    };
+    let file = MidiFile { format: 0, track_count: 1, division: 480, tracks: vec![track] };
+    assert_eq!(summary(&chase(&file, 100).events), vec![('S', 0xF0, 11), ('C', 10, 90)]);
+}
+
+#[test]
+fn test_setup_is_handed_out_once_and_skipped_when_due() {
+    let mut sequencer = MidiSequencer::new(44100.0);
+    sequencer.load_midi_file(&setup_song()).unwrap();
+
+    assert_eq!(summary(&sequencer.take_setup_events()), vec![('S', 0xF0, 11), ('C', 7, 90), ('P', 5, 0)]);
+    assert!(sequencer.take_setup_events().is_empty(), "setup events are handed out once per start");
+    sequencer.play(0);
+    let played: Vec<_> = (1..=60).flat_map(|block| sequencer.process(block * 1024, 1024)).collect();
+    assert_eq!(summary(&played), vec![('N', 60, 100), ('C', 7, 60)], "applied setup events are not played again");
+
+    // Stopping returns to the top, where the setup is due again
+    sequencer.stop();
+    assert_eq!(sequencer.take_setup_events().len(), 3);
+
+    // Disabled, the region plays as recorded
+    sequencer.stop();
+    sequencer.set_setup_enabled(false);
+    assert!(sequencer.take_setup_events().is_empty());
+    sequencer.play(0);
+    let played: Vec<_> = (1..=60).flat_map(|block| sequencer.process(block * 1024, 1024)).collect();
+    assert_eq!(summary(&played), vec![('S', 0xF0, 11), ('C', 7, 90), ('P', 5, 0), ('N', 60, 100), ('C', 7, 60)]);
+}
@@ -0,0 +1,81 @@
+//! Unit tests for SoundFont content hashing
+
+use awe_synth::soundfont::identity::{content_hash, SoundFontIdentity};
+use awe_synth::soundfont::reduce::SampleReduction;
+use awe_synth::soundfont::types::{
+    SampleType, SoundFont, SoundFontHeader, SoundFontPreset, SoundFontSample, SoundFontVersion,
+};
+
+fn bank() -> SoundFont {
+    SoundFont {
+        header: SoundFontHeader {
+            version: SoundFontVersion { major: 2, minor: 1 },
+            name: "Identity".to_string(),
+            engine: "EMU8000".to_string(),
+            tools: String::new(),
+            creation_date: String::new(),
+            author: String::new(),
+            product: String::new(),
+            copyright: String::new(),
+            comments: String::new(),
+            preset_count: 1,
+            instrument_count: 0,
+            sample_count: 1,
+        },
+        presets: vec![SoundFontPreset {
+            name: "Piano".to_string(),
+            program: 0,
+            bank: 0,
+            preset_bag_index: 0,
+            library: 0,
+            genre: 0,
+            morphology: 0,
+            preset_zones: vec![],
+        }],
+        instruments: vec![],
+        samples: vec![SoundFontSample {
+            name: "C4".to_string(),
+            start_offset: 0,
+            end_offset: 4,
+            loop_start: 0,
+            loop_end: 0,
+            sample_rate: 44100,
+            original_pitch: 60,
+            pitch_correction: 0,
+            sample_link: 0,
+            sample_type: SampleType::MonoSample,
+            sample_data: vec![0i16, 1000, -1000, 0].into(),
+        }],
+    }
+}
+
+#[test]
+fn test_hash_is_stable_and_content_sensitive() {
+    let identity = SoundFontIdentity::of(&bank());
+    assert_eq!(identity, SoundFontIdentity::of(&bank()));
+    assert_eq!(identity.hash.len(), 32);
+    assert_eq!((identity.version.as_str(), identity.sample_bytes), ("2.1", 8));
+
+    let mut renamed = bank();
+    renamed.presets[0].name = "Piano 2".to_string();
+    assert_ne!(content_hash(&renamed), content_hash(&bank()));
+
+    let mut edited = bank();
+    edited.samples[0].sample_data = vec![0i16, 1000, -999, 0].into();
+    assert_ne!(content_hash(&edited), content_hash(&bank()), "one PCM value changes the hash");
+
+    // File layout (chunk offsets) is not content
+    let mut moved = bank();
+    moved.samples[0].start_offset = 100;
+    moved.samples[0].end_offset = 104;
+    assert_eq!(content_hash(&moved), content_hash(&bank()));
+}
+
+#[test]
+fn test_identity_tracks_original_content_not_reduction() {
+    let original = SoundFontIdentity::of(&bank());
+    let mut reduced = bank();
+    SampleReduction::from_limits(22050, 0.0).apply(&mut reduced);
+    assert_ne!(content_hash(&reduced), content_hash(&bank()), "callers identify before reducing");
+    assert_eq!(original.samples, 1);
+}
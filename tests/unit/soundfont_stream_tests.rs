@@ -0,0 +1,110 @@
+//! Unit tests for incremental SoundFont loading (SoundFontStream)
+
+use std::mem::MaybeUninit;
+use awe_synth::soundfont::stream::SoundFontStream;
+
+pub fn list_chunk(form: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut chunk = b"LIST".to_vec();
+    chunk.extend_from_slice(&(body.len() as u32 + 4).to_le_bytes());
+    chunk.extend_from_slice(form);
+    chunk.extend_from_slice(body);
+    chunk
+}
+
+pub fn sub_chunk(id: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut chunk = id.to_vec();
+    chunk.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    chunk.extend_from_slice(data);
+    if data.len() % 2 == 1 {
+        chunk.push(0);
+    }
+    chunk
+}
+
+/// INFO and sdta sections only - enough for the parser to produce a header
+pub fn minimal_sf2() -> Vec<u8> {
+    let mut info = sub_chunk(b"ifil", &[0x02, 0x00, 0x01, 0x00]);
+    info.extend(sub_chunk(b"isng", b"EMU8000\0"));
+    info.extend(sub_chunk(b"INAM", b"Stream\0\0"));
+    let samples: Vec<u8> = (0..64i16).flat_map(|s| (s * 100).to_le_bytes()).collect();
+
+    let mut body = b"sfbk".to_vec();
+    body.extend(list_chunk(b"INFO", &info));
+    body.extend(list_chunk(b"sdta", &sub_chunk(b"smpl", &samples)));
+    let mut file = b"RIFF".to_vec();
+    file.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    file.extend(body);
+    file
+}
+
+#[test]
+fn test_stream_in_small_chunks_tracks_sections_and_parses() {
+    let file = minimal_sf2();
+    let mut stream = SoundFontStream::new();
+    assert_eq!(stream.progress(), None);
+
+    let mut saw_info_before_sdta = false;
+    for chunk in file.chunks(7) {
+        stream.push(chunk).unwrap();
+        if let Some(total) = stream.total_bytes() {
+            assert_eq!(total, file.len(), "total comes from the RIFF header");
+        }
+        if stream.has_section(b"INFO") && !stream.has_section(b"sdta") {
+            saw_info_before_sdta = true;
+        }
+    }
+    assert!(saw_info_before_sdta);
+    assert!(stream.has_section(b"sdta"));
+    assert!(!stream.has_section(b"pdta"));
+    assert_eq!(stream.bytes_received(), file.len());
+    assert_eq!(stream.progress(), Some(1.0));
+
+    let soundfont = stream.finish().unwrap();
+    assert_eq!(soundfont.header.name, "Stream");
+}
+
+#[test]
+fn test_stream_rejects_non_soundfont_after_header() {
+    let mut stream = SoundFontStream::new();
+    stream.push(b"RIFF\x10\x00\x00").unwrap(); // Header not complete yet
+    assert!(stream.push(b"\x00WAVEfmt ").is_err());
+
+    let mut stream = SoundFontStream::new();
+    assert!(stream.push(b"<!DOCTYPE html>").is_err());
+}
+
+#[test]
+fn test_truncated_stream_fails_to_finish() {
+    let file = minimal_sf2();
+    let mut stream = SoundFontStream::new();
+    stream.push(&file[..file.len() - 10]).unwrap();
+    assert!(stream.progress().unwrap() < 1.0);
+    assert!(stream.finish().is_err());
+
+    assert!(SoundFontStream::new().finish().is_err(), "no data at all");
+}
+
+/// Stands in for Uint8Array::copy_to_uninit
+fn copy_into<'a>(spare: &'a mut [MaybeUninit<u8>], data: &[u8]) -> &'a mut [u8] {
+    assert_eq!(spare.len(), data.len());
+    for (slot, &byte) in spare.iter_mut().zip(data) {
+        slot.write(byte);
+    }
+    // SAFETY: every byte was written above
+    unsafe { &mut *(spare as *mut [MaybeUninit<u8>] as *mut [u8]) }
+}
+
+#[test]
+fn test_push_from_copies_into_the_buffer() {
+    let file = minimal_sf2();
+    let mut stream = SoundFontStream::new();
+    for chunk in file.chunks(13) {
+        stream.push_from(chunk.len(), |spare| copy_into(spare, chunk)).unwrap();
+    }
+    assert_eq!(stream.bytes_received(), file.len());
+    assert!(stream.has_section(b"sdta"));
+    assert_eq!(stream.finish().unwrap().header.name, "Stream");
+
+    let mut stream = SoundFontStream::new();
+    assert!(stream.push_from(15, |spare| copy_into(spare, b"<!DOCTYPE html>")).is_err());
+}
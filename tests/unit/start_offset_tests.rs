@@ -0,0 +1,20 @@
+//! Unit tests for the per-channel randomized sample start offset (humanized hits)
+
+use awe_synth::synth::voice_manager::VoiceManager;
+
+#[test]
+fn test_start_offset_humanize_window() {
+    let mut manager = VoiceManager::new(44100.0);
+    assert!(manager.set_start_offset_window(Some(9), 20.0));
+    assert!(!manager.set_start_offset_window(Some(16), 20.0));
+    assert_eq!(manager.get_channel_state(9).map(|s| s.start_offset_window_ms), Some(20.0));
+
+    // Window is clamped and humanized notes still sound
+    assert!(manager.set_start_offset_window(None, 500.0));
+    assert_eq!(manager.get_channel_state(0).map(|s| s.start_offset_window_ms), Some(50.0));
+    for _ in 0..4 {
+        assert!(manager.note_on(38, 100, 9).is_some());
+    }
+    let peak = (0..1024).map(|_| manager.process().0.abs()).fold(0.0f32, f32::max);
+    assert!(peak > 0.0);
+}
@@ -0,0 +1,59 @@
+//! Unit tests for sustainVolEnv/sustainModEnv interpretation (EMU8000 sustain level targets)
+
+use awe_synth::synth::envelope::{self, DAHDSREnvelope, EnvelopeState, MAX_SUSTAIN_ATTENUATION_CB};
+use awe_synth::synth::mod_envelope;
+
+const SAMPLE_RATE: f32 = 4000.0;
+
+fn settle(sustain_centibels: i32) -> DAHDSREnvelope {
+    let mut envelope = DAHDSREnvelope::new(SAMPLE_RATE, -12000, -12000, -12000, -3600, sustain_centibels, -3600);
+    envelope.trigger();
+    for _ in 0..SAMPLE_RATE as usize {
+        envelope.process();
+    }
+    envelope
+}
+
+#[test]
+fn test_sustain_centibels_are_attenuation_below_peak() {
+    for (centibels, expected) in [(0, 1.0), (60, 0.501), (200, 0.1), (480, 0.00398), (960, 0.0000158)] {
+        let level = envelope::sustain_level_from_centibels(centibels);
+        assert!((level - expected).abs() <= expected * 0.01, "{} cB -> {}", centibels, level);
+    }
+}
+
+#[test]
+fn test_sustain_is_clamped_to_the_sf2_range() {
+    assert_eq!(envelope::sustain_level_from_centibels(MAX_SUSTAIN_ATTENUATION_CB), 0.0);
+    assert_eq!(envelope::sustain_level_from_centibels(1440), 0.0);
+    assert_eq!(envelope::sustain_level_from_centibels(i16::MAX as i32), 0.0);
+    assert_eq!(envelope::sustain_level_from_centibels(-100), 1.0);
+}
+
+#[test]
+fn test_volume_envelope_holds_its_sustain_target() {
+    for centibels in [0, 60, 200, 480] {
+        let envelope = settle(centibels);
+        assert_eq!(envelope.state, EnvelopeState::Sustain);
+        assert_eq!(envelope.current_level, envelope::sustain_level_from_centibels(centibels));
+    }
+}
+
+#[test]
+fn test_fully_attenuated_sustain_ends_after_decay() {
+    for centibels in [1000, 1440] {
+        let envelope = settle(centibels);
+        assert_eq!(envelope.state, EnvelopeState::Off, "{} cB", centibels);
+        assert_eq!(envelope.current_level, 0.0);
+    }
+}
+
+#[test]
+fn test_mod_sustain_decrease_maps_to_the_same_targets() {
+    for decrease in [0, 250, 500, 900, 1000, 1200] {
+        let centibels = mod_envelope::sustain_centibels(decrease);
+        assert!(centibels <= MAX_SUSTAIN_ATTENUATION_CB);
+        let level = envelope::sustain_level_from_centibels(centibels);
+        assert!((level - mod_envelope::sustain_fraction(decrease)).abs() < 0.01, "decrease {} -> {}", decrease, level);
+    }
+}
@@ -0,0 +1,115 @@
+//! Unit tests for the sustain pedal (CC64) and the channel mode messages that touch it
+
+use awe_synth::synth::envelope::EnvelopeState;
+use awe_synth::synth::voice_manager::VoiceManager;
+
+/// Volume envelope stage of the voice playing `note` on `channel`
+fn stage(manager: &VoiceManager, note: u8, channel: u8) -> Option<EnvelopeState> {
+    manager.voice_envelopes().iter()
+        .find(|voice| voice.active && voice.note == note && voice.channel == channel)
+        .map(|voice| voice.stage)
+}
+
+fn run(manager: &mut VoiceManager, samples: usize) {
+    for _ in 0..samples {
+        manager.process();
+    }
+}
+
+#[test]
+fn test_note_off_under_pedal_keeps_sounding() {
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_mixer_controller(0, 64, 127);
+    assert!(manager.note_on(60, 100, 0).is_some());
+    run(&mut manager, 441);
+
+    manager.note_off(60);
+    run(&mut manager, 441);
+    assert_ne!(stage(&manager, 60, 0), Some(EnvelopeState::Release));
+    assert!(stage(&manager, 60, 0).is_some());
+
+    // Lifting the pedal releases the held note
+    manager.set_mixer_controller(0, 64, 0);
+    assert_eq!(stage(&manager, 60, 0), Some(EnvelopeState::Release));
+}
+
+#[test]
+fn test_pedal_only_holds_released_keys() {
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_mixer_controller(0, 64, 127);
+    assert!(manager.note_on(60, 100, 0).is_some());
+    assert!(manager.note_on(64, 100, 0).is_some());
+    run(&mut manager, 441);
+    manager.note_off(60);
+
+    // Key 64 is still down: the pedal lifting leaves it alone
+    manager.set_mixer_controller(0, 64, 0);
+    assert_eq!(stage(&manager, 60, 0), Some(EnvelopeState::Release));
+    assert_ne!(stage(&manager, 64, 0), Some(EnvelopeState::Release));
+}
+
+#[test]
+fn test_pedal_is_per_channel() {
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_mixer_controller(1, 64, 127);
+    assert!(manager.note_on(60, 100, 0).is_some());
+    assert!(manager.note_on(60, 100, 1).is_some());
+    run(&mut manager, 441);
+
+    manager.note_off(60);
+    assert_eq!(stage(&manager, 60, 0), Some(EnvelopeState::Release));
+    assert_ne!(stage(&manager, 60, 1), Some(EnvelopeState::Release));
+}
+
+#[test]
+fn test_all_notes_off_respects_pedal() {
+    let mut manager = VoiceManager::new(44100.0);
+    assert!(manager.note_on(60, 100, 0).is_some());
+    assert!(manager.note_on(60, 100, 1).is_some());
+    run(&mut manager, 441);
+    manager.set_mixer_controller(1, 64, 127);
+
+    manager.all_notes_off(0);
+    manager.all_notes_off(1);
+    assert_eq!(stage(&manager, 60, 0), Some(EnvelopeState::Release));
+    assert_ne!(stage(&manager, 60, 1), Some(EnvelopeState::Release), "held by the pedal");
+
+    manager.set_mixer_controller(1, 64, 0);
+    assert_eq!(stage(&manager, 60, 1), Some(EnvelopeState::Release));
+}
+
+#[test]
+fn test_reset_controllers_lifts_pedal() {
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_mixer_controller(0, 64, 127);
+    manager.set_mixer_controller(0, 11, 40);
+    assert!(manager.note_on(60, 100, 0).is_some());
+    run(&mut manager, 441);
+    manager.note_off(60);
+
+    manager.reset_controllers(0);
+    let telemetry = manager.channel_telemetry()[0];
+    assert!(!telemetry.sustain);
+    assert_eq!(telemetry.expression, 127);
+    assert_eq!(stage(&manager, 60, 0), Some(EnvelopeState::Release));
+}
+
+#[test]
+fn test_retriggered_note_is_not_held() {
+    let mut manager = VoiceManager::new(44100.0);
+    manager.set_mixer_controller(0, 64, 127);
+    assert!(manager.note_on(60, 100, 0).is_some());
+    run(&mut manager, 441);
+    manager.note_off(60);
+    manager.set_mixer_controller(0, 64, 0);
+
+    // A fresh note after the pedal cycle starts unheld
+    assert!(manager.note_on(60, 100, 0).is_some());
+    run(&mut manager, 441);
+    manager.set_mixer_controller(0, 64, 127);
+    manager.set_mixer_controller(0, 64, 0);
+    let sounding = manager.voice_envelopes().iter()
+        .filter(|voice| voice.active && voice.note == 60 && voice.stage != EnvelopeState::Release)
+        .count();
+    assert_eq!(sounding, 1);
+}
@@ -0,0 +1,98 @@
+//! Unit tests for take export (SMF writer, quantize, swing and legato)
+
+use awe_synth::midi::parser::{MetaEventType, MidiEvent, MidiEventType, MidiFile, MidiTrack};
+use awe_synth::midi::take_export::{apply_options, export_take, MidiExportOptions};
+
+fn event(tick: u64, event_type: MidiEventType) -> MidiEvent {
+    MidiEvent { delta_time: 0, absolute_time: tick, event_type }
+}
+
+fn note(on: u64, off: u64, note: u8) -> [MidiEvent; 2] {
+    [
+        event(on, MidiEventType::NoteOn { channel: 0, note, velocity: 100 }),
+        event(off, MidiEventType::NoteOff { channel: 0, note, velocity: 0 }),
+    ]
+}
+
+/// Loosely played take: notes near 0, 120, 240 and 360 at a 120-tick grid, plus a controller
+fn take() -> MidiTrack {
+    let mut events: Vec<MidiEvent> = [note(5, 60, 60), note(110, 170, 62), note(250, 300, 64), note(355, 400, 65)]
+        .into_iter()
+        .flatten()
+        .collect();
+    events.push(event(130, MidiEventType::ControlChange { channel: 0, controller: 64, value: 127 }));
+    events.sort_by_key(|event| event.absolute_time);
+    events.push(event(480, MidiEventType::MetaEvent(MetaEventType::EndOfTrack)));
+    MidiTrack { name: Some("Take 1".to_string()), events }
+}
+
+/// (tick, note, on) of the note events
+fn notes(track: &MidiTrack) -> Vec<(u64, u8, bool)> {
+    track.events.iter().filter_map(|event| match event.event_type {
+        MidiEventType::NoteOn { note, velocity, .. } if velocity > 0 => Some((event.absolute_time, note, true)),
+        MidiEventType::NoteOff { note, .. } | MidiEventType::NoteOn { note, .. } => Some((event.absolute_time, note, false)),
+        _ => None,
+    }).collect()
+}
+
+#[test]
+fn test_quantize_and_swing_move_note_starts_only() {
+    let options = MidiExportOptions::from_json(r#"{"quantizeTicks": 120}"#).unwrap();
+    let straight = apply_options(&take(), &options);
+    assert_eq!(notes(&straight), vec![
+        (0, 60, true), (55, 60, false),
+        (120, 62, true), (180, 62, false),
+        (240, 64, true), (290, 64, false),
+        (360, 65, true), (405, 65, false),
+    ], "starts snap to the grid and notes keep their length");
+    assert!(straight.events.iter().any(|event| event.absolute_time == 130
+        && matches!(event.event_type, MidiEventType::ControlChange { controller: 64, .. })), "controllers keep their ticks");
+
+    // 66.7%: the off-beat of each 240-tick pair lands at 160
+    let swung = apply_options(&take(), &MidiExportOptions::from_json(r#"{"quantizeTicks": 120, "swing": 66.7}"#).unwrap());
+    let starts: Vec<u64> = notes(&swung).into_iter().filter(|&(_, _, on)| on).map(|(tick, _, _)| tick).collect();
+    assert_eq!(starts, vec![0, 160, 240, 400]);
+
+    // Out-of-range swing is clamped, bad JSON refused
+    assert_eq!(MidiExportOptions::from_json(r#"{"swing": 90}"#).unwrap().swing, 75.0);
+    assert!(MidiExportOptions::from_json("{").is_none());
+}
+
+#[test]
+fn test_legato_fills_gaps_to_the_next_note() {
+    let options = MidiExportOptions::from_json(r#"{"quantizeTicks": 120, "legato": 1.0}"#).unwrap();
+    let joined = apply_options(&take(), &options);
+    assert_eq!(notes(&joined), vec![
+        (0, 60, true), (120, 60, false), (120, 62, true),
+        (240, 62, false), (240, 64, true),
+        (360, 64, false), (360, 65, true), (405, 65, false),
+    ], "releases meet the next start (the last note is left as played)");
+
+    let half = apply_options(&take(), &MidiExportOptions { legato: 0.5, ..options });
+    assert_eq!(notes(&half)[1], (88, 60, false), "half the 65-tick gap is filled");
+}
+
+#[test]
+fn test_export_writes_a_parseable_file_and_leaves_the_take_alone() {
+    let conductor = MidiTrack {
+        name: None,
+        events: vec![
+            event(0, MidiEventType::MetaEvent(MetaEventType::TimeSignature { numerator: 3, denominator: 4, clocks_per_click: 24, notes_per_quarter: 8 })),
+            event(240, MidiEventType::MetaEvent(MetaEventType::SetTempo { microseconds_per_quarter: 400_000 })),
+            event(480, MidiEventType::MetaEvent(MetaEventType::EndOfTrack)),
+        ],
+    };
+    let song = MidiFile { format: 1, track_count: 2, division: 480, tracks: vec![conductor, take()] };
+    let options = MidiExportOptions::from_json(r#"{"quantizeTicks": 120}"#).unwrap();
+    let bytes = export_take(&song, &song.tracks[1], &options);
+
+    let exported = MidiFile::parse(&bytes).unwrap();
+    assert_eq!((exported.format, exported.division, exported.tracks.len()), (1, 480, 2));
+    assert!(exported.tracks[0].events.iter().any(|event| event.absolute_time == 240
+        && matches!(event.event_type, MidiEventType::MetaEvent(MetaEventType::SetTempo { microseconds_per_quarter: 400_000 }))));
+    assert!(exported.tracks[0].events.iter().any(|event|
+        matches!(event.event_type, MidiEventType::MetaEvent(MetaEventType::TimeSignature { numerator: 3, denominator: 4, .. }))));
+    assert_eq!(exported.tracks[1].name.as_deref(), Some("Take 1"));
+    assert_eq!(notes(&exported.tracks[1]), notes(&apply_options(&song.tracks[1], &options)));
+    assert_eq!(notes(&song.tracks[1]), notes(&take()), "the take keeps what was played");
+}
@@ -0,0 +1,106 @@
+//! Unit tests for tempo multiplier glides and scheduled tempo ramps
+
+use awe_synth::midi::sequencer::MidiSequencer;
+use awe_synth::midi::tempo_ramp::{TempoControl, TEMPO_GLIDE_SECONDS};
+use super::smf_fixture::smf;
+
+const SAMPLE_RATE: f64 = 44100.0;
+const BLOCK: u64 = 128;
+
+/// 100 quarters of nothing at 480 PPQ and the default 120 BPM (960 ticks per second)
+fn long_smf() -> Vec<u8> {
+    let events = [0x82, 0xF7, 0x00, 0xFF, 0x2F, 0x00];
+    smf(&[&events])
+}
+
+/// Run the sequencer block by block for `seconds`, returning the sample reached
+fn run(sequencer: &mut MidiSequencer, sample: u64, seconds: f64) -> u64 {
+    let end = sample + (seconds * SAMPLE_RATE) as u64;
+    let mut sample = sample;
+    while sample < end {
+        sample += BLOCK;
+        sequencer.process(sample, BLOCK as usize);
+    }
+    sample
+}
+
+fn playing() -> MidiSequencer {
+    let mut sequencer = MidiSequencer::new(SAMPLE_RATE);
+    sequencer.load_midi_file(&long_smf()).unwrap();
+    sequencer.play(0);
+    sequencer
+}
+
+#[test]
+fn test_multiplier_change_keeps_position() {
+    let mut sequencer = playing();
+    let sample = run(&mut sequencer, 0, 2.0);
+    let before = sequencer.get_current_tick();
+    assert!((before as f64 - 1920.0).abs() < 10.0);
+
+    sequencer.set_tempo_multiplier(2.0);
+    let sample = run(&mut sequencer, sample, 0.01);
+    assert!(sequencer.get_current_tick() - before < 20, "no jump when the multiplier changes");
+
+    // After the glide, a second of playback covers twice the ticks
+    let sample = run(&mut sequencer, sample, TEMPO_GLIDE_SECONDS);
+    let start = sequencer.get_current_tick();
+    run(&mut sequencer, sample, 1.0);
+    assert!((sequencer.get_current_tick() - start) as f64 > 1900.0);
+    assert!((sequencer.get_current_tempo_bpm() - 240.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_glide_and_range() {
+    let mut control = TempoControl::new();
+    control.set(3.0);
+    let mid = control.advance(0, TEMPO_GLIDE_SECONDS / 2.0);
+    assert!(mid > 1.0 && mid < 3.0);
+    assert!((control.multiplier() - 2.0).abs() < 1e-9);
+    control.advance(0, TEMPO_GLIDE_SECONDS);
+    assert_eq!(control.multiplier(), 3.0);
+
+    control.set(10.0);
+    control.advance(0, 1.0);
+    assert_eq!(control.multiplier(), 4.0);
+    control.set(0.0);
+    control.advance(0, 1.0);
+    assert_eq!(control.multiplier(), 0.25);
+    control.set(f64::NAN);
+    control.advance(0, 1.0);
+    assert_eq!(control.multiplier(), 0.25);
+}
+
+#[test]
+fn test_scheduled_ramp_follows_ticks() {
+    let mut sequencer = playing();
+    assert!(sequencer.schedule_tempo_ramp(960, 2880, 2.0));
+    assert!(!sequencer.schedule_tempo_ramp(500, 400, 2.0));
+
+    let mut sample = run(&mut sequencer, 0, 0.9);
+    assert_eq!(sequencer.get_tempo_multiplier(), 1.0, "ramp waits for its start tick");
+    while sequencer.get_current_tick() < 1920 {
+        sample = run(&mut sequencer, sample, 0.01);
+    }
+    assert!((sequencer.get_tempo_multiplier() - 1.5).abs() < 0.02, "halfway: {}", sequencer.get_tempo_multiplier());
+    while sequencer.get_current_tick() < 2900 {
+        sample = run(&mut sequencer, sample, 0.01);
+    }
+    assert_eq!(sequencer.get_tempo_multiplier(), 2.0);
+    assert!(sequencer.tempo_ramps().is_empty(), "ramps run once");
+}
+
+#[test]
+fn test_manual_change_cancels_running_ramp_only() {
+    let mut control = TempoControl::new();
+    assert!(control.schedule_ramp(0, 1000, 0.5));
+    assert!(control.schedule_ramp(5000, 6000, 2.0));
+    control.advance(100, 0.01);
+    assert!(control.multiplier() < 1.0);
+
+    control.set(1.0);
+    assert_eq!(control.ramps().len(), 1);
+    assert_eq!(control.ramps()[0].start_tick, 5000);
+    control.clear_ramps();
+    assert!(control.ramps().is_empty());
+}
@@ -0,0 +1,63 @@
+//! Unit tests for the chord, scale and note name helpers
+
+use awe_synth::midi::test_sequences::{MidiTestSequenceGenerator, NoteNameUtils};
+use awe_synth::midi::theory::{self, ChordQuality, Scale};
+
+#[test]
+fn test_chords_and_inversions() {
+    assert_eq!(ChordQuality::Major.notes(60, 0), Some(vec![60, 64, 67]));
+    assert_eq!(ChordQuality::Major.notes(60, 1), Some(vec![64, 67, 72]));
+    assert_eq!(ChordQuality::Major.notes(60, 2), Some(vec![67, 72, 76]));
+    assert_eq!(ChordQuality::Major.notes(60, 3), None, "a triad has two inversions");
+    assert_eq!(ChordQuality::from_name("m7b5"), Some(ChordQuality::HalfDiminished7));
+    assert_eq!(ChordQuality::from_name("7").unwrap().notes(55, 0), Some(vec![55, 59, 62, 65]));
+    assert_eq!(ChordQuality::Major.notes(125, 0), None, "past note 127");
+    assert_eq!(theory::build_chord(57, "m", 0), vec![57, 60, 64]);
+    assert!(theory::build_chord(57, "lydian", 0).is_empty());
+}
+
+#[test]
+fn test_scales_and_snapping() {
+    assert_eq!(Scale::Major.notes(60, 1), vec![60, 62, 64, 65, 67, 69, 71, 72]);
+    assert_eq!(Scale::MinorPentatonic.notes(57, 2).len(), 11);
+    assert_eq!(Scale::Major.notes(120, 1), vec![120, 122, 124, 125, 127], "stops at 127");
+
+    // D dorian contains no F#; snapping goes to the nearest tone, ties downward
+    assert!(Scale::Dorian.contains(62, 65));
+    assert!(!Scale::Dorian.contains(62, 66));
+    assert_eq!(Scale::Dorian.snap(62, 66), 65);
+    assert_eq!(Scale::Major.snap(0, 61), 60);
+    assert_eq!(Scale::Major.snap(0, 70), 69);
+    assert_eq!(Scale::Chromatic.snap(0, 70), 70);
+    assert_eq!(theory::snap_note_to_scale(70, 0, "nope"), 255);
+
+    let sequence = MidiTestSequenceGenerator::new(44100.0).generate_scale(57, Scale::NaturalMinor, None);
+    assert_eq!(sequence.name, "A3 NaturalMinor Scale");
+    assert_eq!(sequence.notes, vec![57, 59, 60, 62, 64, 65, 67, 69]);
+}
+
+#[test]
+fn test_note_names_and_frequencies() {
+    assert_eq!(theory::note_name(63, false), "D#4");
+    assert_eq!(theory::note_name(63, true), "Eb4");
+    assert_eq!(theory::note_name(0, false), "C-1");
+    assert_eq!(theory::parse_note_name("Eb3"), Some(51));
+    assert_eq!(theory::parse_note_name("f#5"), Some(78));
+    assert_eq!(theory::parse_note_name("C-1"), Some(0));
+    assert_eq!(theory::parse_note_name("B#3"), Some(60));
+    assert_eq!(theory::parse_note_name("Cb4"), Some(59));
+    assert_eq!(theory::parse_note_name("G9"), Some(127));
+    assert_eq!(theory::parse_note_name("G#9"), None);
+    assert_eq!(theory::parse_note_name("H2"), None);
+
+    // The existing helpers keep their results and now accept flats
+    assert_eq!(NoteNameUtils::midi_to_note_name(60), "C4");
+    assert_eq!(NoteNameUtils::note_name_to_midi("C#4"), Some(61));
+    assert_eq!(NoteNameUtils::note_name_to_midi("Db4"), Some(61));
+
+    assert_eq!(theory::note_frequency(69), 440.0);
+    assert!((theory::note_frequency(60) - 261.63).abs() < 0.01);
+    assert_eq!(theory::frequency_note(261.0), Some(60));
+    assert_eq!(theory::frequency_note(0.0), None);
+    assert_eq!(theory::frequency_note(20000.0), None);
+}
@@ -0,0 +1,55 @@
+//! Unit tests for transport fades (master bus ramps for play/stop with fade)
+
+use awe_synth::audio::fade::{FadeState, MasterFade};
+
+#[test]
+fn test_fade_in_ramps_linearly_to_unity() {
+    let mut fade = MasterFade::new(1000.0);
+    fade.fade_in(10.0);
+    assert_eq!(fade.state(), FadeState::FadingIn);
+    assert_eq!(fade.status().remaining_samples, 10);
+
+    let gains: Vec<f32> = (0..10).map(|_| fade.next(true).0).collect();
+    for (index, gain) in gains.iter().enumerate() {
+        assert!((gain - (index + 1) as f32 / 10.0).abs() < 1e-5, "sample {} gain {}", index, gain);
+    }
+    assert_eq!(fade.state(), FadeState::Idle);
+    assert_eq!(fade.next(true), (1.0, false));
+}
+
+#[test]
+fn test_fade_out_lands_once_and_holds_silence_until_voices_end() {
+    let mut fade = MasterFade::new(1000.0);
+    fade.fade_out(4.0);
+    let results: Vec<(f32, bool)> = (0..4).map(|_| fade.next(true)).collect();
+    assert_eq!(results.iter().filter(|(_, finished)| *finished).count(), 1);
+    assert_eq!(results[3], (0.0, true), "silence on the last sample of the fade");
+    assert!(results[0].0 < 1.0 && results[0].0 > results[1].0);
+
+    assert_eq!(fade.state(), FadeState::Silent);
+    assert_eq!(fade.next(true), (0.0, false));
+    assert_eq!(fade.next(false), (0.0, false));
+    assert_eq!(fade.state(), FadeState::Idle);
+    assert_eq!(fade.next(false), (1.0, false));
+}
+
+#[test]
+fn test_reversing_a_fade_continues_from_current_level() {
+    let mut fade = MasterFade::new(1000.0);
+    fade.fade_out(10.0);
+    for _ in 0..5 {
+        fade.next(true);
+    }
+    let level = fade.status().gain;
+    assert!((level - 0.5).abs() < 1e-5);
+
+    fade.fade_in(10.0);
+    let (first, _) = fade.next(true);
+    assert!(first > level && first - level < 0.1, "no jump back to silence, got {}", first);
+
+    // Zero-length fades complete on the next sample
+    fade.fade_out(0.0);
+    assert_eq!(fade.next(true), (0.0, true));
+    fade.cancel();
+    assert_eq!(fade.status().gain, 1.0);
+}
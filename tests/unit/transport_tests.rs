@@ -0,0 +1,64 @@
+//! Unit tests for MIDI Machine Control and Song Position Pointer transport handling
+
+use awe_synth::midi::transport::{midi_beats_to_ticks, parse_mmc, parse_song_position, TransportCommand};
+use awe_synth::MidiPlayer;
+use super::smf_fixture::smf;
+
+const STOPPED: u8 = 0;
+const PLAYING: u8 = 1;
+const PAUSED: u8 = 2;
+
+/// One track, 480 PPQ, End of Track after 16 quarters (8 s at 120 BPM)
+fn sixteen_quarter_smf() -> Vec<u8> {
+    let events = [0xBC, 0x00, 0xFF, 0x2F, 0x00]; // Delta 7680
+    smf(&[&events])
+}
+
+#[test]
+fn test_parse_mmc_commands() {
+    assert_eq!(parse_mmc(&[0xF0, 0x7F, 0x7F, 0x06, 0x02, 0xF7]), Some(TransportCommand::Play));
+    assert_eq!(parse_mmc(&[0xF0, 0x7F, 0x10, 0x06, 0x03, 0xF7]), Some(TransportCommand::Play));
+    assert_eq!(parse_mmc(&[0xF0, 0x7F, 0x7F, 0x06, 0x01, 0xF7]), Some(TransportCommand::Stop));
+    assert_eq!(parse_mmc(&[0xF0, 0x7F, 0x7F, 0x06, 0x09, 0xF7]), Some(TransportCommand::Pause));
+    assert_eq!(parse_mmc(&[0xF0, 0x7F, 0x7F, 0x06, 0x05, 0xF7]), None, "rewind is not mapped");
+    assert_eq!(parse_mmc(&[0xF0, 0x7E, 0x7F, 0x09, 0x01, 0xF7]), None, "GM System On is not MMC");
+
+    // 25 fps (rate bits 01): 0h 1m 2s, 12.5 frames
+    let locate = [0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01, 0x20, 1, 2, 12, 50, 0xF7];
+    match parse_mmc(&locate) {
+        Some(TransportCommand::LocateSeconds(seconds)) => assert!((seconds - 62.5).abs() < 1e-9),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_song_position_pointer() {
+    assert_eq!(parse_song_position(&[0xF2, 0x00, 0x01]), Some(TransportCommand::LocateBeats(128)));
+    assert_eq!(parse_song_position(&[0xF2, 0x80, 0x00]), None);
+    assert_eq!(parse_song_position(&[0xF8]), None);
+    assert_eq!(midi_beats_to_ticks(4, 480), 480, "four MIDI beats per quarter note");
+}
+
+#[test]
+fn test_player_transport_follows_mmc_and_spp() {
+    let mut player = MidiPlayer::new();
+    player.load_midi_file(&sixteen_quarter_smf()).unwrap();
+
+    assert!(player.handle_sysex(&[0xF0, 0x7F, 0x7F, 0x06, 0x02, 0xF7]));
+    assert_eq!(player.get_playback_state(), PLAYING);
+    assert!(player.handle_sysex(&[0xF0, 0x7F, 0x7F, 0x06, 0x09, 0xF7]));
+    assert_eq!(player.get_playback_state(), PAUSED);
+
+    // SPP 32 = 8 quarters = halfway
+    player.send_midi_message(&[0xF2, 32, 0]).unwrap();
+    assert!((player.get_position() - 0.5).abs() < 1e-9);
+
+    // Locate to 2 s of 8 s (30 fps, rate bits 11)
+    assert!(player.handle_sysex(&[0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01, 0x60, 0, 2, 0, 0, 0xF7]));
+    assert!((player.get_position_seconds() - 2.0).abs() < 1e-6);
+    assert_eq!(player.get_playback_state(), PAUSED, "locate keeps the transport state");
+
+    assert!(player.handle_sysex(&[0xF0, 0x7F, 0x7F, 0x06, 0x01, 0xF7]));
+    assert_eq!(player.get_playback_state(), STOPPED);
+    assert_eq!(player.get_position(), 0.0);
+}
@@ -0,0 +1,52 @@
+//! Unit tests for per-channel velocity remapping
+
+use awe_synth::synth::velocity_map::VelocityMap;
+use awe_synth::synth::voice_manager::VoiceManager;
+
+#[test]
+fn test_velocity_map_range_and_curve() {
+    assert!(VelocityMap::default().is_identity());
+    assert_eq!(VelocityMap::IDENTITY.apply(37), 37);
+
+    let compress = VelocityMap::new(64, 127, 1.0);
+    assert_eq!(compress.apply(1), 64);
+    assert_eq!(compress.apply(127), 127);
+    assert_eq!(compress.apply(64), 96);
+
+    // Curves bend the middle, never the ends
+    let soft = VelocityMap::new(1, 127, 0.5);
+    let hard = VelocityMap::new(1, 127, 2.0);
+    assert!(soft.apply(32) > 32 && hard.apply(32) < 32);
+    assert_eq!((soft.apply(1), soft.apply(127)), (1, 127));
+
+    // Reversed or out-of-range settings are brought into range
+    let clamped = VelocityMap::new(0, 0, f32::NAN);
+    assert_eq!((clamped.min, clamped.max, clamped.curve), (1, 1, 1.0));
+    assert_eq!(VelocityMap::new(90, 20, 50.0).max, 90);
+
+    // 14-bit velocities land on the same 7-bit step as plain ones
+    for velocity in [1u8, 40, 100, 127] {
+        let high_res = compress.apply_14bit((velocity as u16) << 7);
+        assert_eq!((high_res >> 7) as u8, compress.apply(velocity));
+    }
+}
+
+#[test]
+fn test_remapped_velocity_reaches_the_voice() {
+    let peak = |manager: &mut VoiceManager, velocity: u8, channel: u8| {
+        manager.note_on(60, velocity, channel);
+        (0..2205).map(|_| manager.process().0.abs()).fold(0.0f32, f32::max)
+    };
+    let mut plain = VoiceManager::new(44100.0);
+    let full = peak(&mut plain, 127, 0);
+
+    let mut mapped = VoiceManager::new(44100.0);
+    assert!(mapped.set_velocity_map(0, VelocityMap::new(127, 127, 1.0)));
+    assert!(!mapped.set_velocity_map(16, VelocityMap::IDENTITY));
+    let soft_but_mapped = peak(&mut mapped, 20, 0);
+    assert!((soft_but_mapped - full).abs() < 1e-4, "{} vs {}", soft_but_mapped, full);
+
+    // Other channels are untouched, and the map is part of the state snapshot
+    assert!(mapped.velocity_map(1).unwrap().is_identity());
+    assert_eq!(mapped.state_snapshot().velocity_maps[0], VelocityMap::new(127, 127, 1.0));
+}
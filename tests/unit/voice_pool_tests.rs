@@ -0,0 +1,55 @@
+//! Unit tests for voice warm-up and the fixed zone pool
+
+use awe_synth::synth::fallback_bank::build_fallback_soundfont;
+use awe_synth::synth::multizone_voice::{MultiZoneSampleVoice, MAX_ACTIVE_ZONES};
+use awe_synth::synth::voice_manager::{VoiceManager, MAX_VOICES};
+use awe_synth::synth::zone_generators::PresetGenerators;
+
+#[test]
+fn test_warmed_voice_plays_like_a_fresh_one() {
+    let bank = build_fallback_soundfont(44100.0);
+    let preset = &bank.presets[0];
+    let generators = PresetGenerators::resolve(preset, &bank);
+
+    let mut warmed = MultiZoneSampleVoice::new(0, 44100.0);
+    warmed.warm_up(&bank, &bank.presets[40], &PresetGenerators::resolve(&bank.presets[40], &bank));
+    assert!(!warmed.is_active());
+    assert_eq!(warmed.get_zone_count(), 0);
+    assert_eq!(warmed.process(), (0.0, 0.0));
+
+    let mut fresh = MultiZoneSampleVoice::new(1, 44100.0);
+    warmed.start_note(64, 90, 2, &bank, preset).unwrap();
+    fresh.start_note_with_generators(64, 90 << 7, 2, &bank, preset, &generators).unwrap();
+    for _ in 0..4096 {
+        assert_eq!(warmed.process(), fresh.process());
+    }
+}
+
+#[test]
+fn test_warm_up_is_silent_and_uncounted() {
+    let mut manager = VoiceManager::new(44100.0);
+    assert_eq!(manager.get_active_voice_count(), 0);
+    assert_eq!(manager.voice_stats().notes_started, 0);
+
+    manager.load_soundfont(build_fallback_soundfont(44100.0)).unwrap();
+    assert_eq!(manager.get_active_voice_count(), 0);
+    assert_eq!(manager.process(), (0.0, 0.0));
+
+    // Sounding voices are left alone
+    assert!(manager.note_on(60, 100, 0).is_some());
+    assert_eq!(manager.warm_up_voices(), MAX_VOICES - 1);
+    assert_eq!(manager.get_active_voice_count(), 1);
+    assert_eq!(manager.voice_stats().notes_started, 1);
+}
+
+#[test]
+fn test_zone_pool_never_grows() {
+    let mut bank = build_fallback_soundfont(44100.0);
+    let instrument = bank.presets[0].preset_zones[0].instrument_id.unwrap() as usize;
+    let zone = bank.instruments[instrument].instrument_zones[0].clone();
+    bank.instruments[instrument].instrument_zones.extend(std::iter::repeat_n(zone, MAX_ACTIVE_ZONES + 4));
+
+    let mut voice = MultiZoneSampleVoice::new(0, 44100.0);
+    voice.start_note(60, 100, 0, &bank, &bank.presets[0]).unwrap();
+    assert_eq!(voice.get_zone_count(), MAX_ACTIVE_ZONES);
+}
@@ -0,0 +1,72 @@
+//! Unit tests for voice aging statistics (lifetimes, steals, polyphony over time)
+
+use awe_synth::synth::voice_manager::{VoiceManager, MAX_VOICES};
+use awe_synth::synth::voice_stats::{VoiceStats, LIFETIME_BUCKET_SECONDS};
+
+const SAMPLE_RATE: f32 = 4000.0;
+
+fn run(manager: &mut VoiceManager, seconds: f32) {
+    for _ in 0..(seconds * SAMPLE_RATE) as usize {
+        manager.process();
+    }
+}
+
+#[test]
+fn test_lifetime_histogram_and_polyphony_percentiles() {
+    let mut stats = VoiceStats::new(1000.0);
+    stats.voice_finished(0.05);
+    stats.voice_finished(0.1);
+    stats.voice_finished(3.0);
+    stats.voice_stolen(30.0);
+    // 90 samples at 2 voices, 10 at 8
+    (0..90).for_each(|_| stats.sample(2));
+    (0..10).for_each(|_| stats.sample(8));
+
+    let report = stats.report();
+    assert_eq!(report.lifetime_histogram.len(), LIFETIME_BUCKET_SECONDS.len() + 1);
+    assert_eq!(report.lifetime_histogram[0].count, 2, "bucket bounds are inclusive");
+    assert_eq!(report.lifetime_histogram[5].up_to_seconds, Some(5.0));
+    assert_eq!(report.lifetime_histogram[5].count, 1);
+    assert_eq!(report.lifetime_histogram.last().unwrap().up_to_seconds, None);
+    assert_eq!(report.lifetime_histogram.last().unwrap().count, 1);
+    assert_eq!((report.notes_finished, report.steals), (3, 1));
+    assert!((report.mean_lifetime_seconds - 33.15 / 4.0).abs() < 1e-4);
+    assert_eq!(report.longest_lifetime_seconds, 30.0);
+
+    assert!((report.elapsed_seconds - 0.1).abs() < 1e-9);
+    assert!((report.average_polyphony - 2.6).abs() < 1e-5);
+    assert_eq!(report.peak_polyphony, 8);
+    assert_eq!(report.polyphony_95th, 8);
+
+    stats.reset();
+    let empty = stats.report();
+    assert_eq!((empty.notes_finished, empty.peak_polyphony, empty.polyphony_95th), (0, 0, 0));
+    assert_eq!(empty.average_polyphony, 0.0);
+}
+
+#[test]
+fn test_voice_manager_tracks_lifetimes_and_steals() {
+    let mut manager = VoiceManager::new(SAMPLE_RATE);
+    manager.note_on(60, 100, 0);
+    run(&mut manager, 0.5);
+    manager.note_off(60);
+    run(&mut manager, 5.0);
+    assert_eq!(manager.get_active_voice_count(), 0);
+
+    let report = manager.voice_stats();
+    assert_eq!((report.notes_started, report.notes_finished, report.steals), (1, 1, 0));
+    assert!(report.longest_lifetime_seconds > 0.5, "lifetime includes the release, got {}", report.longest_lifetime_seconds);
+    assert_eq!(report.peak_polyphony, 1);
+    assert!(report.average_polyphony > 0.0 && report.average_polyphony < 1.0);
+
+    // One note more than the pool steals exactly one voice
+    manager.reset_voice_stats();
+    for note in 0..=MAX_VOICES as u8 {
+        manager.note_on(30 + note, 100, 1);
+    }
+    run(&mut manager, 0.01);
+    let report = manager.voice_stats();
+    assert_eq!(report.notes_started, MAX_VOICES as u64 + 1);
+    assert_eq!(report.steals, 1);
+    assert_eq!(report.peak_polyphony, MAX_VOICES);
+}
@@ -0,0 +1,51 @@
+//! Unit tests for the hanging-note watchdog
+
+use awe_synth::synth::voice_manager::VoiceManager;
+
+const SAMPLE_RATE: f32 = 4000.0;
+
+fn run(manager: &mut VoiceManager, seconds: f32) {
+    for _ in 0..(seconds * SAMPLE_RATE) as usize {
+        manager.process();
+    }
+}
+
+#[test]
+fn test_watchdog_releases_and_reports_stuck_notes() {
+    let mut manager = VoiceManager::new(SAMPLE_RATE);
+    manager.set_note_watchdog(true, 2.0);
+    manager.note_on(60, 100, 3);
+    let released_in_time = manager.note_on(64, 100, 3).unwrap();
+    run(&mut manager, 1.0);
+    manager.note_off(64);
+    assert!(manager.take_hung_notes().is_empty());
+
+    // Scans run every 1024 samples, so a release lands shortly after the timeout
+    run(&mut manager, 1.5);
+    let hung = manager.take_hung_notes();
+    assert_eq!(hung.len(), 1, "only the note that never got its Note Off");
+    assert_eq!((hung[0].channel, hung[0].note), (3, 60));
+    assert_ne!(hung[0].voice, released_in_time);
+    assert!(hung[0].held_seconds >= 2.0 && hung[0].held_seconds < 2.0 + 1024.0 / SAMPLE_RATE + 0.01);
+    assert!(manager.take_hung_notes().is_empty(), "each release is reported once");
+
+    run(&mut manager, 5.0);
+    assert_eq!(manager.get_active_voice_count(), 0);
+}
+
+#[test]
+fn test_watchdog_is_off_by_default_and_clamps_timeout() {
+    let mut manager = VoiceManager::new(SAMPLE_RATE);
+    assert!(!manager.note_watchdog().is_enabled());
+    manager.note_on(60, 100, 0);
+    run(&mut manager, 3.0);
+    assert!(manager.take_hung_notes().is_empty());
+    assert_eq!(manager.get_active_voice_count(), 1);
+
+    manager.set_note_watchdog(true, 0.01);
+    assert_eq!(manager.note_watchdog().timeout_seconds(), 1.0);
+    manager.set_note_watchdog(true, f32::NAN);
+    assert_eq!(manager.note_watchdog().timeout_seconds(), 1.0, "non-finite keeps the timeout");
+    run(&mut manager, 0.5);
+    assert_eq!(manager.take_hung_notes().len(), 1);
+}
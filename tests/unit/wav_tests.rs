@@ -0,0 +1,60 @@
+//! Unit tests for the WAV writer
+
+use awe_synth::audio::wav::{encode_wav, WavFormat, WavSpec, WavWriter};
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+#[test]
+fn test_pcm16_header_and_clipping() {
+    let wav = encode_wav(WavSpec::new(44100, 2, WavFormat::Pcm16), &[0.0, 1.0, -1.0, 2.0]);
+    assert_eq!(&wav[0..4], b"RIFF");
+    assert_eq!(u32_at(&wav, 4) as usize, wav.len() - 8);
+    assert_eq!(&wav[8..16], b"WAVEfmt ");
+    assert_eq!(u16_at(&wav, 20), 1); // PCM
+    assert_eq!(u16_at(&wav, 22), 2);
+    assert_eq!(u32_at(&wav, 24), 44100);
+    assert_eq!(u32_at(&wav, 28), 44100 * 4);
+    assert_eq!(u16_at(&wav, 32), 4);
+    assert_eq!(u16_at(&wav, 34), 16);
+    assert_eq!(&wav[36..40], b"data");
+    assert_eq!(u32_at(&wav, 40), 8);
+    let samples: Vec<i16> = wav[44..].chunks(2).map(|b| i16::from_le_bytes([b[0], b[1]])).collect();
+    assert_eq!(samples, vec![0, 32767, -32767, 32767]);
+}
+
+#[test]
+fn test_pcm24_and_float_layouts() {
+    let wav = encode_wav(WavSpec::new(48000, 1, WavFormat::Pcm24), &[0.5, -1.0]);
+    assert_eq!(u16_at(&wav, 34), 24);
+    assert_eq!(u32_at(&wav, 40), 6);
+    assert_eq!(&wav[44..47], &[0x00, 0x00, 0x40]); // 0.5 * 8388607 rounds to 0x400000
+    assert_eq!(&wav[47..50], &[0x01, 0x00, 0x80]); // -8388607
+
+    let wav = encode_wav(WavSpec::new(48000, 1, WavFormat::Float32), &[0.25, 1.5]);
+    assert_eq!(u32_at(&wav, 16), 18);
+    assert_eq!(u16_at(&wav, 20), 3); // IEEE float
+    assert_eq!(&wav[38..42], b"fact");
+    assert_eq!(u32_at(&wav, 46), 2);
+    assert_eq!(&wav[50..54], b"data");
+    assert_eq!(f32::from_le_bytes([wav[58], wav[59], wav[60], wav[61]]), 0.25);
+    assert_eq!(f32::from_le_bytes([wav[62], wav[63], wav[64], wav[65]]), 1.5); // Float is not clipped
+}
+
+#[test]
+fn test_incremental_writer_drops_partial_frame() {
+    let mut writer = WavWriter::new(WavSpec::new(22050, 2, WavFormat::Pcm16));
+    writer.write_samples(&[0.1, 0.2]);
+    writer.write_samples(&[0.3, 0.4, 0.5]);
+    assert_eq!(writer.frames_written(), 2);
+    let wav = writer.finish();
+    assert_eq!(u32_at(&wav, 40), 8);
+    assert_eq!(wav.len(), 44 + 8);
+    assert_eq!(WavFormat::from_name("FLOAT32"), Some(WavFormat::Float32));
+    assert_eq!(WavFormat::from_name("pcm8"), None);
+}
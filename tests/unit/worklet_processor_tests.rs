@@ -0,0 +1,37 @@
+//! Unit tests for the embedded AudioWorklet processor source and protocol info
+
+use awe_synth::protocol::{BRIDGE_MIN_PROTOCOL_VERSION, BRIDGE_PROTOCOL_VERSION};
+use awe_synth::worklet::{get_worklet_processor_source, get_worklet_protocol_info, WORKLET_PROCESSOR_NAME};
+
+#[test]
+fn test_processor_source_placeholders_are_substituted() {
+    let url = "https://example.com/pkg/awe_synth.js";
+    let source = get_worklet_processor_source(url);
+    assert!(!source.contains("__AWE_"), "a placeholder was left in the source");
+    assert!(source.contains(&format!("from '{}'", url)));
+    assert!(source.contains(&format!("const PROTOCOL_VERSION = {};", BRIDGE_PROTOCOL_VERSION)));
+    assert!(source.contains(&format!("registerProcessor('{}'", WORKLET_PROCESSOR_NAME)));
+}
+
+#[test]
+fn test_protocol_info_matches_the_processor() {
+    let info: serde_json::Value = serde_json::from_str(&get_worklet_protocol_info()).unwrap();
+    assert_eq!(info["protocolVersion"], BRIDGE_PROTOCOL_VERSION);
+    assert_eq!(info["minProtocolVersion"], BRIDGE_MIN_PROTOCOL_VERSION);
+    assert_eq!(info["processorName"], WORKLET_PROCESSOR_NAME);
+
+    // Every inbound message type the info lists is handled by the processor
+    let source = get_worklet_processor_source("awe_synth.js");
+    for message in info["inbound"].as_array().unwrap() {
+        assert!(source.contains(&format!("'{}'", message.as_str().unwrap())), "{} is not handled", message);
+    }
+}
+
+#[test]
+fn test_processor_negotiates_through_the_bridge() {
+    // The init message's version goes through a bridge hello; no second version check
+    let source = get_worklet_processor_source("awe_synth.js");
+    assert!(source.contains("type: 'hello', protocolVersion: message.protocolVersion"));
+    assert!(!source.contains("message.protocolVersion !== PROTOCOL_VERSION"));
+    assert!(!source.contains("queue_midi_event_global"));
+}
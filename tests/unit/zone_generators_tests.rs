@@ -0,0 +1,118 @@
+//! Unit tests for generators resolved ahead of note-on (per-pair resolution, preset offsets, cache lifetime)
+
+use awe_synth::soundfont::types::{Generator, GeneratorAmount, GeneratorType, PresetZone};
+use awe_synth::synth::fallback_bank::build_fallback_soundfont;
+use awe_synth::synth::multizone_voice::MultiZoneSampleVoice;
+use awe_synth::synth::voice_manager::VoiceManager;
+use awe_synth::synth::zone_generators::{PresetGenerators, ZoneGenerators};
+
+fn short(generator_type: GeneratorType, value: i16) -> Generator {
+    Generator { generator_type, amount: GeneratorAmount::Short(value) }
+}
+
+fn global_zone(generators: Vec<Generator>) -> PresetZone {
+    PresetZone { generators, modulators: Vec::new(), instrument_id: None, key_range: None, velocity_range: None }
+}
+
+fn rms(voice: &mut MultiZoneSampleVoice, frames: usize) -> f32 {
+    let sum: f32 = (0..frames).map(|_| voice.process()).map(|(left, right)| left * left + right * right).sum();
+    (sum / (frames * 2) as f32).sqrt()
+}
+
+#[test]
+fn test_preset_values_add_to_instrument_values() {
+    let mut bank = build_fallback_soundfont(44100.0);
+    bank.presets[0].preset_zones.insert(0, global_zone(vec![
+        short(GeneratorType::AttackVolEnv, 100),
+        short(GeneratorType::HoldVolEnv, 500),
+        short(GeneratorType::InitialAttenuation, 60),
+        short(GeneratorType::DelayVibLfo, 2400),
+        short(GeneratorType::SustainVolEnv, 2000),
+    ]));
+
+    let generators = PresetGenerators::resolve(&bank.presets[0], &bank).zone(1, 0);
+    let defaults = ZoneGenerators::default();
+    assert_eq!(generators.attack_vol_env, -11959 + 100, "Piano instrument attack plus the preset's offset");
+    assert_eq!(generators.hold_vol_env, defaults.hold_vol_env + 500, "offset applies to the default too");
+    assert_eq!(generators.release_vol_env, -2786, "no preset offset");
+    assert_eq!(generators.initial_attenuation, 60);
+    assert_eq!(generators.delay_vib_lfo, defaults.delay_vib_lfo + 2400);
+    assert_eq!(generators.delay_mod_lfo, defaults.delay_mod_lfo);
+    assert_eq!(generators.sustain_vol_env, 1440, "sums stay within the SF2 range");
+}
+
+#[test]
+fn test_notes_read_the_selected_zone() {
+    let mut bank = build_fallback_soundfont(44100.0);
+    let kit = bank.presets.len() - 1;
+    let instrument = bank.presets[kit].preset_zones[0].instrument_id.unwrap() as usize;
+    let zones = &mut bank.instruments[instrument].instrument_zones;
+    zones[1].generators.push(short(GeneratorType::InitialAttenuation, 200));
+    zones[1].generators.push(short(GeneratorType::FineTune, 50));
+    let (quiet_note, plain_note) = (zones[1].key_range.as_ref().unwrap().low, zones[0].key_range.as_ref().unwrap().low);
+
+    let generators = PresetGenerators::resolve(&bank.presets[kit], &bank);
+    assert_eq!(generators.zone(0, 1).initial_attenuation, 200);
+    assert_eq!(generators.zone(0, 1).tuning_cents(), 50);
+    assert_eq!(generators.zone(0, 0), generators.zone(0, 2), "other zones keep their own values");
+
+    let reference = build_fallback_soundfont(44100.0);
+    let level = |bank: &awe_synth::soundfont::types::SoundFont, note: u8| {
+        let mut voice = MultiZoneSampleVoice::new(0, 44100.0);
+        voice.start_note(note, 100, 9, bank, &bank.presets[kit]).unwrap();
+        rms(&mut voice, 2048)
+    };
+    let ratio = level(&bank, quiet_note) / level(&reference, quiet_note);
+    assert!((ratio - 0.1).abs() < 0.01, "20 dB down, got {}", ratio);
+    assert_eq!(level(&bank, plain_note), level(&reference, plain_note));
+}
+
+#[test]
+fn test_loop_offsets_are_resolved_per_pair() {
+    let mut bank = build_fallback_soundfont(44100.0);
+    let kit = bank.presets.len() - 1;
+    let instrument = bank.presets[kit].preset_zones[0].instrument_id.unwrap() as usize;
+    bank.instruments[instrument].instrument_zones[1].generators.push(short(GeneratorType::EndloopAddrsOffset, -8));
+    bank.instruments[instrument].instrument_zones[2].generators.push(short(GeneratorType::StartloopAddrsCoarseOffset, 1));
+    bank.presets[kit].preset_zones[0].generators.push(short(GeneratorType::StartloopAddrsOffset, 4));
+
+    let generators = PresetGenerators::resolve(&bank.presets[kit], &bank);
+    let offsets = |zone: ZoneGenerators| (zone.loop_start_offset, zone.loop_end_offset);
+    assert_eq!(offsets(generators.zone(0, 0)), (4, 0));
+    assert_eq!(offsets(generators.zone(0, 1)), (4, -8));
+    assert_eq!(generators.zone(0, 2).loop_start_offset, 32768 + 4);
+    assert_eq!(generators.zone(5, 0), ZoneGenerators::default(), "unknown pair");
+}
+
+#[test]
+fn test_cached_generators_start_the_same_note_as_a_direct_start() {
+    let bank = build_fallback_soundfont(44100.0);
+    let preset = &bank.presets[0];
+    let generators = PresetGenerators::resolve(preset, &bank);
+
+    let mut direct = MultiZoneSampleVoice::new(0, 44100.0);
+    let mut cached = MultiZoneSampleVoice::new(1, 44100.0);
+    direct.start_note(60, 100, 0, &bank, preset).unwrap();
+    cached.start_note_with_generators(60, 100 << 7, 0, &bank, preset, &generators).unwrap();
+    for _ in 0..4096 {
+        assert_eq!(direct.process(), cached.process());
+    }
+}
+
+#[test]
+fn test_voice_manager_resolves_every_preset_of_the_playing_bank() {
+    let mut manager = VoiceManager::new(44100.0);
+    let fallback = build_fallback_soundfont(44100.0);
+    assert_eq!(manager.preset_generators(20), Some(&PresetGenerators::resolve(&fallback.presets[20], &fallback)));
+
+    let mut bank = build_fallback_soundfont(44100.0);
+    bank.presets.truncate(8);
+    bank.presets[3].preset_zones[0].generators.push(short(GeneratorType::InitialAttenuation, 120));
+    manager.load_soundfont(bank).unwrap();
+    assert!(manager.preset_generators(20).is_none(), "presets of the previous bank are gone");
+    assert_eq!(manager.preset_generators(3).unwrap().zone(0, 0).initial_attenuation, 120);
+
+    // Program changes only select: nothing is resolved on the audio thread
+    assert_eq!(manager.program_change(1, 3), Some(3));
+    assert!(manager.note_on(60, 100, 1).is_some());
+}
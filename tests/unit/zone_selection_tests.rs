@@ -0,0 +1,33 @@
+//! Unit tests for zone selection strategy names and the serialized analysis
+
+use awe_synth::synth::fallback_bank::build_fallback_soundfont;
+use awe_synth::synth::voice_manager::{VoiceManager, ZoneSelectionStrategy};
+
+#[test]
+fn test_strategy_names_round_trip() {
+    for strategy in [
+        ZoneSelectionStrategy::AllMatching,
+        ZoneSelectionStrategy::RoundRobin,
+        ZoneSelectionStrategy::FirstMatch,
+        ZoneSelectionStrategy::Random,
+        ZoneSelectionStrategy::Priority,
+    ] {
+        assert_eq!(ZoneSelectionStrategy::from_name(strategy.name()), Some(strategy.clone()));
+        assert_eq!(serde_json::to_string(&strategy).unwrap(), format!("\"{}\"", strategy.name()));
+    }
+    assert_eq!(ZoneSelectionStrategy::from_name("AllMatching"), None);
+}
+
+#[test]
+fn test_analysis_serializes_selected_zones() {
+    let mut manager = VoiceManager::new(44100.0);
+    manager.load_soundfont(build_fallback_soundfont(44100.0)).unwrap();
+    manager.set_zone_selection_strategy(ZoneSelectionStrategy::FirstMatch);
+
+    let analysis = manager.analyze_zone_selection(60, 100);
+    assert_eq!(analysis.selected_zones, 1);
+    assert!(!analysis.round_robin_enabled);
+    let json = serde_json::to_string(&analysis).unwrap();
+    assert!(json.contains("\"strategy\":\"firstMatch\""));
+    assert!(json.contains("\"zoneDetails\":[{\"sampleName\":"));
+}
@@ -6,31 +6,174 @@ import { systemHealthManager } from '../utils/SystemHealthManager'
 interface WasmModule {
   init_all_systems: (sampleRate: number) => boolean
   init_audio_worklet: (sampleRate: number) => boolean
+  init_audio_worklet_with_config: (config: string) => void
+  apply_engine_config_global: (config: string) => void
+  prime_audio_engine_global: (blocks: number) => number
   queue_midi_event_global: (timestamp: number, channel: number, messageType: number, data1: number, data2: number) => void
   reset_audio_state_global: () => void
   get_system_status: () => string
   process_audio_buffer: (length: number) => Float32Array
   process_stereo_buffer_global: (length: number) => Float32Array
+  set_metronome_enabled_global: (enabled: boolean) => boolean
+  set_metronome_levels_global: (main_level: number, bus_level: number) => boolean
+  set_punch_region_global: (punch_in?: number, punch_out?: number) => void
+  set_pre_roll_bars_global: (bars: number) => void
+  start_recording_global: (track: number) => void
+  stop_recording_global: () => number
+  get_recording_status_global: () => string
+  set_take_recording_global: (enabled: boolean) => void
+  get_takes_global: () => string
+  select_take_global: (id: number) => void
+  audition_take_global: (id: number) => void
+  commit_take_global: (id: number) => void
+  discard_take_global: (id: number) => void
+  export_take_midi_global: (id: number, options_json: string) => Uint8Array
+  sample_to_context_time_global: (sample: number) => number
+  context_time_to_sample_global: (context_time: number) => number
+  get_clock_status_global: () => string
+  set_debug_solo_global: (mode: 'off' | 'voice' | 'channel', index: number) => boolean
+  set_key_tuning: (table: Float32Array, channel: number) => boolean
+  reset_key_tuning: (channel: number) => boolean
+  set_master_tuning_global: (a4_hz: number) => boolean
+  get_master_tuning_global: () => number
+  set_synthesis_mode_global: (mode: 'modern' | 'hardware') => boolean
+  get_synthesis_mode_global: () => string
+  set_enhancements_global: (enabled: boolean) => boolean
+  get_enhancements_global: () => boolean
+  play_sample_preview: (sampleIndex: number, semitones: number, looped: boolean, gain: number) => boolean
+  stop_sample_preview: () => boolean
+  get_channel_telemetry_ptr_global: () => number
+  get_channel_telemetry_global: () => string
+  get_channel_state_global: (channel: number) => string
+  set_channel_drum_mode_global: (channel: number, mode: 'auto' | 'drum' | 'melodic') => boolean
+  get_channel_drum_mode_global: (channel: number) => string
+  set_envelope_monitor_rate_global: (rateHz: number) => boolean
+  get_envelope_monitor_ptr_global: () => number
+  get_envelope_monitor_updates_global: () => number
+  get_voice_envelopes_global: () => string
   // SoundFont functions
   init_soundfont_module: () => string
   validate_soundfont_header: (data: Uint8Array) => string
   parse_soundfont_file: (data: Uint8Array) => string
   load_soundfont_into_player: (data: Uint8Array) => string
+  set_load_event_callback: (callback?: (eventJson: string) => void) => void
+  set_error_callback: (callback?: (eventJson: string) => void) => void
+  set_soundfont_load_options_global: (targetSampleRate: number, maxSampleSeconds: number) => void
+  get_sample_reduction_report_global: () => string
+  get_sample_rate_warnings_global: () => string
+  set_loop_repair_global: (enabled: boolean) => void
+  get_loop_repair_global: () => boolean
+  get_loop_repairs_global: () => string
+  set_loop_snap_window_global: (window: number) => boolean
+  get_loop_snap_window_global: () => number
+  get_loop_adjustments_global: () => string
+  get_soundfont_identity_global: () => string
+  identify_soundfont: (data: Uint8Array) => string
+  set_controller_smoothing_global: (timeMs: number) => boolean
+  get_controller_smoothing_global: () => number
+  set_auto_gain_enabled_global: (enabled: boolean) => boolean
+  get_auto_gain_global: () => number
+  set_performance_zone_global: (index: number, lowKey: number, highKey: number, bank: number, program: number, transpose: number, volume: number) => boolean
+  clear_performance_zone_global: (index: number) => boolean
+  set_performance_input_global: (channel: number) => boolean
+  pre_load_preset: (bank: number, program: number) => string
+  set_preset_balance_global: (enabled: boolean) => boolean
+  get_preset_balance_global: () => string
+  get_drum_kit_map_global: (bank: number, program: number) => string
+  set_note_watchdog_global: (enabled: boolean, timeoutSeconds: number) => boolean
+  set_note_watchdog_callback: (callback?: (noteJson: string) => void) => void
+  set_channel_trim_global: (channel: number, db: number) => boolean
+  get_channel_trim_global: (channel: number) => number
+  set_velocity_map_global: (channel: number, min: number, max: number, curve: number) => boolean
+  reset_velocity_map_global: (channel: number) => boolean
+  get_state_snapshot_global: () => string
+  restore_state_snapshot_global: (json: string) => boolean
+  get_voice_stats_global: () => string
+  reset_voice_stats_global: () => boolean
+  set_modulation_route_global: (channel: number, source: string, destination: string, amount: number, curve: string) => boolean
+  remove_modulation_route_global: (channel: number, source: string, destination: string) => boolean
+  clear_modulation_routes_global: (channel: number) => boolean
+  set_filter_envelope_global: (channel: number, shape: string) => boolean
+  set_mod_wheel_vibrato_global: (channel: number, min_depth: number, max_depth: number, curve: string) => boolean
+  get_loudness_global: () => string
+  reset_loudness_global: () => boolean
+  set_lfo_phase_mode_global: (mode: string, seed: number) => boolean
+  set_playback_humanize_global: (channel: number, velocity_range: number, timing_ms: number) => boolean
+  set_playback_humanize_seed_global: (seed: number) => boolean
+  get_playback_humanize_global: () => string
+  set_automation_lane_global: (channel: number, parameter: string, ticks: Uint32Array, values: Float32Array, interpolation: string) => boolean
+  remove_automation_lane_global: (channel: number, parameter: string) => boolean
+  clear_automation_global: () => boolean
+  get_automation_lanes_global: () => string
+  set_song_setup_enabled_global: (enabled: boolean) => boolean
+  get_song_setup_global: () => string
+  flush_effects_global: () => boolean
+  set_effects_cc_mapping_global: (controller: number, parameter: string, min: number, max: number) => boolean
+  get_effects_cc_map_global: () => string
+  get_midi_trace_global: (channel: number, types: string) => string
+  set_midi_trace_global: (enabled: boolean, capacity: number) => boolean
+  clear_midi_trace_global: () => boolean
+  set_render_ahead_global: (blocks: number) => boolean
+  set_render_ahead_auto_global: (enabled: boolean) => boolean
+  get_render_ahead_status_global: () => string
+  set_offline_render_global: (enabled: boolean, thresholdDb: number, tailCapSeconds: number) => void
+  get_offline_render_status_global: () => string
+  start_batch_render_global: (files: Uint8Array[], options_json: string) => void
+  step_batch_render_global: (max_frames: number) => boolean
+  cancel_batch_render_global: () => boolean
+  take_batch_output_global: (index: number) => Uint8Array
+  get_batch_render_status_global: () => string
+  render_ab_comparison_global: (requestJson: string) => string
+  take_ab_render_global: (pass: string) => Float32Array
+  set_batch_event_callback: (callback?: (eventJson: string) => void) => void
+  set_device_profile_global: (name: string) => boolean
+  get_device_profile_global: () => string
+  set_quality_scaling_global: (enabled: boolean) => boolean
+  get_quality_scaling_global: () => string
+  set_quality_scaling_callback: (callback?: (changeJson: string) => void) => void
+  set_deadline_watchdog_global: (enabled: boolean, warnLoad: number, scaleQuality: boolean) => boolean
+  get_deadline_status_global: () => string
+  set_deadline_warning_callback: (callback?: (warningJson: string) => void) => void
+  set_preview_program_global: (bank: number, program: number) => boolean
+  set_preview_volume_global: (volume: number) => boolean
+  set_preview_input_global: (channel: number) => boolean
+  preview_note_on_global: (note: number, velocity: number) => boolean
+  preview_note_off_global: (note: number) => boolean
+  get_preview_channel_global: () => string
+  set_zone_selection_strategy_global: (strategy: string) => boolean
+  get_zone_selection_strategy_global: () => string
+  set_round_robin_global: (enabled: boolean) => boolean
+  analyze_zone_selection_global: (note: number, velocity: number) => string
+  set_velocity_crossfade_global: (curve: string, width: number) => boolean
+  get_velocity_crossfade_global: () => string
+  // Only present in builds with the "fetch" feature
+  load_soundfont_from_url?: (url: string, progress_callback?: (bytesReceived: number, totalBytes?: number) => void) => Promise<string>
+  cancel_soundfont_load?: () => void
   select_preset_global: (bank: number, program: number) => string
   get_current_preset_info_global: () => string
-  test_soundfont_synthesis: () => string
   // Pipeline and diagnostic functions
   get_pipeline_status_global: () => string
   get_soundfont_info: () => string
   quick_c_major_test: () => string
+  generate_scale_test: (tonic: number, scale: string, config_json?: string) => string
+  build_chord: (root: number, quality: string, inversion: number) => Uint8Array
+  build_scale: (tonic: number, scale: string, octaves: number) => Uint8Array
+  snap_note_to_scale: (note: number, tonic: number, scale: string) => number
+  midi_note_to_flat_name: (note: number) => string
+  midi_note_to_frequency: (note: number) => number
+  frequency_to_midi_note: (frequency: number) => number
   get_buffer_metrics_global: () => string
   get_version_info: () => string
-  test_soundfont_memory: () => string
-  test_audio_synthesis_pipeline: () => string
+  run_self_test: (levels: string) => string
   // New structured diagnostic functions
   diagnose_audio_pipeline: () => string
   diagnose_soundfont_data: () => string
-  get_all_soundfont_samples: () => string
+  get_soundfont_samples_page: (offset: number, limit: number, name_filter: string, waveform_points: number) => string
+  get_soundfont_presets_page: (offset: number, limit: number, name_filter: string) => string
+  get_gm_names: () => string
+  set_preset_effects_sends: (bank: number, program: number, reverb: number, chorus: number) => boolean
+  clear_preset_effects_sends: (bank: number, program: number) => boolean
+  get_sample_waveform: (sample_index: number, points: number) => Float32Array
   get_sample_data_by_index: (index: number) => Float32Array | undefined
   diagnose_midi_processing: () => string
   get_system_diagnostics: () => string
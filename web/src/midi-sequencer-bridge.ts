@@ -12,12 +12,17 @@ const log = (message: string) => DEBUG_LOGGERS.midiFile.log(message);
 // WASM module types (these will be available after WASM loads)
 interface WasmMidiPlayer {
     new(): WasmMidiPlayer;
-    load_midi_file(data: Uint8Array): boolean;
+    load_midi_file(data: Uint8Array): void;  // Throws ErrorEvent JSON if the file does not parse
     play(): void;
     pause(): void;
     stop(): void;
     seek(position: number): void;
     set_tempo_multiplier(multiplier: number): void;
+    get_tempo_multiplier(): number;
+    schedule_tempo_ramp(startTick: number, endTick: number, target: number): boolean;
+    ramp_tempo_multiplier(target: number, beats: number): boolean;
+    clear_tempo_ramps(): void;
+    get_current_tick(): number;
     get_playback_state(): number;
     get_position(): number;
     get_position_seconds(): number;
@@ -134,24 +139,24 @@ export class MidiSequencerBridge {
         log(`Loading MIDI file: ${info.name} (${info.size} bytes)`);
 
         // Load file into WASM player
-        const success = this.wasmPlayer.load_midi_file(data);
-        
-        if (success) {
-            log('MIDI file loaded into sequencer successfully');
-            
-            // Update playback controls with file info
-            const durationSeconds = this.wasmPlayer.get_duration_seconds();
-            const originalTempo = this.wasmPlayer.get_original_tempo_bpm();
-            
-            this.playbackControls.setEnabled(true);
-            this.playbackControls.setDuration(durationSeconds);
-            this.playbackControls.setOriginalTempo(originalTempo);
-            
-            log(`MIDI file ready: ${durationSeconds.toFixed(1)}s, ${originalTempo.toFixed(1)} BPM`);
-        } else {
-            log('Failed to load MIDI file into sequencer');
+        try {
+            this.wasmPlayer.load_midi_file(data);
+        } catch (error) {
+            log(`Failed to load MIDI file into sequencer: ${error}`);
             this.playbackControls.setEnabled(false);
+            return;
         }
+        log('MIDI file loaded into sequencer successfully');
+        
+        // Update playback controls with file info
+        const durationSeconds = this.wasmPlayer.get_duration_seconds();
+        const originalTempo = this.wasmPlayer.get_original_tempo_bpm();
+        
+        this.playbackControls.setEnabled(true);
+        this.playbackControls.setDuration(durationSeconds);
+        this.playbackControls.setOriginalTempo(originalTempo);
+        
+        log(`MIDI file ready: ${durationSeconds.toFixed(1)}s, ${originalTempo.toFixed(1)} BPM`);
     }
 
     /**
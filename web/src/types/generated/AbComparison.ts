@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How far two renders are apart (render_ab_comparison_global JSON)
+ */
+export type AbComparison = { frames: number, sampleRate: number, startSeconds: number, aRmsDb: number | null, bRmsDb: number | null, differenceRmsDb: number | null, differencePeakDb: number | null, nullDepthDb: number | null, correlation: number | null, identical: boolean, };
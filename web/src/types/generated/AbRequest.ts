@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AbSnapshot } from "./AbSnapshot";
+
+/**
+ * What to render and the two snapshots to compare (JSON)
+ */
+export type AbRequest = { startSeconds: number, seconds: number, a: AbSnapshot, b: AbSnapshot, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Settings one pass renders with (JSON; missing fields keep the engine's)
+ */
+export type AbSnapshot = { interpolation?: string, synthesisMode?: string, enhancements?: boolean, effects?: string, maxPolyphony?: number, };
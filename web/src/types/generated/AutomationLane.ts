@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AutomationInterpolation } from "./AutomationInterpolation";
+import type { AutomationParameter } from "./AutomationParameter";
+import type { AutomationPoint } from "./AutomationPoint";
+
+/**
+ * One parameter of one channel over the song (get_automation_lanes_global)
+ */
+export type AutomationLane = { channel: number, parameter: AutomationParameter, interpolation: AutomationInterpolation, points: Array<AutomationPoint>, };
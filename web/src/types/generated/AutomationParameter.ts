@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What a lane drives, with the range of its values
+ */
+export type AutomationParameter = "volume" | "pan" | "cutoff";
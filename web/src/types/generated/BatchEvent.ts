@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RenderStopReason } from "./RenderStopReason";
+
+/**
+ * One batch conversion notification
+ */
+export type BatchEvent = { "type": "started", files: number, } | { "type": "fileStarted", index: number, } | { "type": "progress", index: number, percent: number, overallPercent: number, } | { "type": "fileComplete", index: number, seconds: number, bytes: number, truncated: boolean, stopReason?: RenderStopReason, } | { "type": "fileFailed", index: number, message: string, } | { "type": "complete", converted: number, failed: number, cancelled: boolean, };
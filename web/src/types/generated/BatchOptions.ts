@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Render settings for every file of a batch (JSON; missing fields take the defaults)
+ */
+export type BatchOptions = { format: string, thresholdDb: number, tailCapSeconds: number, maxSeconds: number, dither: boolean, noiseShaping: boolean, targetLufs: number | null, truePeakCeilingDbtp: number | null, };
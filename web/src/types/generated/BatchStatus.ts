@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Batch state (get_batch_render_status_global)
+ */
+export type BatchStatus = { running: boolean, files: number, current?: number, converted: number, failed: number, cancelled: boolean, };
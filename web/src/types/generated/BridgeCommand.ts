@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TransportAction } from "./TransportAction";
+
+/**
+ * Commands sent from main thread to the engine
+ */
+export type BridgeCommand = { "type": "hello", protocolVersion: number, } | { "type": "init", sampleRate: number, } | { "type": "loadSoundFont" } | { "type": "selectPreset", bank: number, program: number, } | { "type": "loadMidiFile" } | { "type": "midi", timestamp?: number, channel: number, messageType: number, data1: number, data2: number, } | { "type": "transport", action: TransportAction, value?: number, } | { "type": "setParam", name: string, value: number, } | { "type": "getTelemetry" };
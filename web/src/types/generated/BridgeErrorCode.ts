@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Error codes returned in `error` responses
+ */
+export type BridgeErrorCode = "invalidCommand" | "handshakeRequired" | "versionMismatch" | "notInitialized" | "missingPayload" | "loadFailed" | "unknownParam" | "invalidValue";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BridgeErrorCode } from "./BridgeErrorCode";
+
+/**
+ * Responses sent from the engine back to the main thread
+ */
+export type BridgeResponse = { "type": "hello", protocolVersion: number, minProtocolVersion: number, capabilities: Array<string>, } | { "type": "ack", command: string, } | { "type": "error", code: BridgeErrorCode, message: string, } | { "type": "telemetry", sampleRate: number, bufferSize: number, activeVoices: number, soundfontLoaded: boolean, playbackState: number, positionSeconds: number, durationSeconds: number, };
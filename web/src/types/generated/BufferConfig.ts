@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BufferSize } from "./BufferSize";
+import type { CpuUsage } from "./CpuUsage";
+import type { Stability } from "./Stability";
+
+/**
+ * Buffer size configuration options
+ */
+export type BufferConfig = { size: BufferSize, latency_ms: number, cpu_usage: CpuUsage, stability: Stability, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Buffer performance metrics
+ */
+export type BufferMetrics = { average_processing_time: number, max_processing_time: number, underruns: number, overruns: number, samples_processed: number, uptime_ms: number, };
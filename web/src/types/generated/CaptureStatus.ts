@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Capture state reported by get_capture_status
+ */
+export type CaptureStatus = { recording: boolean, seconds: number, maxSeconds: number, reachedCap: boolean, };
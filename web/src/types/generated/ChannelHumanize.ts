@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Humanize settings of one channel (get_playback_humanize_global)
+ */
+export type ChannelHumanize = { velocityRange: number, timingMs: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Controller snapshot of one channel (get_channel_state_global)
+ */
+export type ChannelStateReport = { channel: number, bankMsb: number, bankLsb: number, program: number, presetIndex: number | null, isDrum: boolean, volume: number, pan: number, expression: number, sustain: boolean, modulation: number, pressure: number, reverbSend: number, chorusSend: number, soundControllers: [number, number, number, number, number, number, number, number, number, number], pitchBend: number, rpn: number | null, nrpn: number | null, fineTuningCents: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One channel's state (get_channel_telemetry_global)
+ */
+export type ChannelTelemetry = { channel: number, activeVoices: number, program: number, volume: number, pan: number, expression: number, sustain: boolean, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Chorus modulation and return level (fields left out take the EMU8000 defaults)
+ */
+export type ChorusConfig = { rate: number, depth: number, feedback: number, stereoSpread: number, returnLevel: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Drift estimate reported to JS (get_clock_status_global)
+ */
+export type ClockStatus = { driftPpm: number, residualMs: number, observations: number, resyncs: number, locked: boolean, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Watchdog settings and counters (get_deadline_status_global)
+ */
+export type DeadlineStatus = { enabled: boolean, warnLoad: number, scaleQuality: boolean, lastLoad: number, peakLoad: number, blocks: number, nearMisses: number, overruns: number, warnings: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A block that came close to (or past) its deadline (JSON for the warning callback)
+ */
+export type DeadlineWarning = { elapsedMs: number, budgetMs: number, load: number, frames: number, activeVoices: number, nearMisses: number, overruns: number, };
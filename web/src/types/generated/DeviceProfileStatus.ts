@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DeviceProfile } from "./DeviceProfile";
+import type { ProfileSettings } from "./ProfileSettings";
+
+/**
+ * Active profile and how it was chosen (get_device_profile_global)
+ */
+export type DeviceProfileStatus = { profile: DeviceProfile | null, autoDetect: boolean, settings: ProfileSettings, };
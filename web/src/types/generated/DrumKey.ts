@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One key of the map
+ */
+export type DrumKey = { key: number, gmName?: string, covered: boolean, sample?: string, };
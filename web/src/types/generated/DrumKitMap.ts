@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DrumKey } from "./DrumKey";
+
+/**
+ * Key map of one preset (get_drum_kit_map_global)
+ */
+export type DrumKitMap = { presetIndex: number, presetName: string, bank: number, program: number, coveredKeys: number, silentGmKeys: number, keys: Array<DrumKey>, };
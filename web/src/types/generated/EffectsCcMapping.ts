@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EffectsParameter } from "./EffectsParameter";
+
+/**
+ * One controller's mapping (get_effects_cc_map_global)
+ */
+export type EffectsCcMapping = { controller: number, parameter: EffectsParameter, min: number, max: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A global effect parameter a controller can drive, with its range
+ */
+export type EffectsParameter = "reverbRoomSize" | "reverbDamping" | "reverbDiffusion" | "reverbReturn" | "chorusRate" | "chorusDepth" | "chorusFeedback" | "chorusSpread" | "chorusReturn";
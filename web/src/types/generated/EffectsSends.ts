@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Reverb and chorus send levels, 0.0-1.0
+ */
+export type EffectsSends = { reverb: number, chorus: number, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChorusConfig } from "./ChorusConfig";
+import type { ReverbConfig } from "./ReverbConfig";
+import type { VelocityMap } from "./VelocityMap";
+
+/**
+ * Engine settings applied together; absent fields are left as they are
+ */
+export type EngineConfig = { sampleRate?: number, deviceProfile?: string, polyphony?: number, reverb?: ReverbConfig, chorus?: ChorusConfig, velocityCurve?: VelocityMap, systemMode?: string, fallbackBank?: boolean, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { VelocityMap } from "./VelocityMap";
+
+/**
+ * Restorable engine settings (get_state_snapshot_global JSON)
+ */
+export type EngineSnapshot = { version: number, channelTrimsDb: Array<number>, velocityMaps: Array<VelocityMap>, };
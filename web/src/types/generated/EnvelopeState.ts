@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * EMU8000 envelope states for 6-stage DAHDSR envelope
+ */
+export type EnvelopeState = "off" | "delay" | "attack" | "hold" | "decay" | "sustain" | "release";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ErrorCode } from "./ErrorCode";
+
+/**
+ * Structured export failure (error callback payload and thrown value)
+ */
+export type ErrorEvent = { code: ErrorCode, message: string, export?: string, };
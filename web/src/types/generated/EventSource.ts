@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where a traced event came from
+ */
+export type EventSource = "live" | "sequencer" | "chase" | "setup";
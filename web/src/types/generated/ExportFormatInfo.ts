@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Entry of get_export_formats
+ */
+export type ExportFormatInfo = { name: string, mimeType: string, };
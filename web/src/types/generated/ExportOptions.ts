@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Encoder settings beyond the format (JSON for the options exports; missing fields are off)
+ */
+export type ExportOptions = { dither: boolean, noiseShaping: boolean, targetLufs: number | null, truePeakCeilingDbtp: number | null, };
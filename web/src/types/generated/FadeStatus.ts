@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FadeState } from "./FadeState";
+
+/**
+ * Fade state and level (JSON in pipeline stats)
+ */
+export type FadeStatus = { state: FadeState, gain: number, remainingSamples: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One drum kit of the kit map
+ */
+export type GmDrumKit = { program: number, name: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GmDrumKit } from "./GmDrumKit";
+
+/**
+ * Every GM program and drum kit name (get_gm_names)
+ */
+export type GmNames = { programs: Array<string>, drumKits: Array<GmDrumKit>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A voice the watchdog released (JSON for the watchdog callback)
+ */
+export type HungNote = { voice: number, channel: number, note: number, heldSeconds: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Sample interpolation used by voices
+ */
+export type InterpolationQuality = "linear" | "cubic";
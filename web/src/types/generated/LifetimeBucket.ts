@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Voices that ended after at most `up_to_seconds` (None = longer than the last bound)
+ */
+export type LifetimeBucket = { upToSeconds: number | null, count: bigint, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Machine-readable reason in `failed` events
+ */
+export type LoadErrorCode = "network" | "cancelled" | "invalidFormat" | "corruptData" | "notInitialized" | "engineRejected";
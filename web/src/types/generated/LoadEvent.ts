@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LoadErrorCode } from "./LoadErrorCode";
+import type { LoadKind } from "./LoadKind";
+import type { LoadStage } from "./LoadStage";
+
+/**
+ * One load lifecycle notification
+ */
+export type LoadEvent = { "type": "started", kind: LoadKind, totalBytes?: number, } | { "type": "progress", kind: LoadKind, stage: LoadStage, percent: number, } | { "type": "samplesLoaded", count: number, } | { "type": "presetsIndexed", presets: number, instruments: number, } | { "type": "complete", kind: LoadKind, } | { "type": "failed", kind: LoadKind, code: LoadErrorCode, message: string, };
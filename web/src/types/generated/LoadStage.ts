@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Phase a `progress` event belongs to
+ */
+export type LoadStage = "download" | "container" | "info" | "samples" | "presets" | "tracks" | "engine";
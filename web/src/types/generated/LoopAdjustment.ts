@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A sample whose loop points were moved (get_loop_adjustments_global)
+ */
+export type LoopAdjustment = { sample: number, name: string, loopStartBefore: number, loopEndBefore: number, loopStart: number, loopEnd: number, };
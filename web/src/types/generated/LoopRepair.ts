@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A sample whose loop points were nudged (get_loop_repairs_global)
+ * Declared points are the header's, relative to the sample start (negative = before it)
+ */
+export type LoopRepair = { sample: number, name: string, declaredStart: number, declaredEnd: number, loopStart: number, loopEnd: number, };
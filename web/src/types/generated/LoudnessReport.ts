@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Loudness readings (JSON for get_loudness_global); None until enough audio or while silent
+ */
+export type LoudnessReport = { momentaryLufs: number | null, shortTermLufs: number | null, integratedLufs: number | null, maxMomentaryLufs: number | null, truePeakDbtp: number | null, elapsedSeconds: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A number a check measured
+ */
+export type Measurement = { name: string, value: number, unit: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Snapshot of memory usage and growth history
+ */
+export type MemoryStats = { currentBytes: number, pages: number, reservedBytes: number, growthEvents: number, growthEventsWhilePlaying: number, lastGrowthBytes: number, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type MidiEventData = { timestamp: number, channel: number, message_type: number, data1: number, data2: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Take export settings (JSON; missing fields leave the take as played)
+ */
+export type MidiExportOptions = { quantizeTicks: number, swing: number, legato: number, };
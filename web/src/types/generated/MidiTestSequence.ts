@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MidiEventData } from "./MidiEventData";
+import type { TestSequenceConfig } from "./TestSequenceConfig";
+
+/**
+ * Test sequence for audio pipeline validation
+ */
+export type MidiTestSequence = { name: string, notes: Array<number>, events: Array<MidiEventData>, config: TestSequenceConfig, total_duration_ms: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What normalization did (JSON)
+ */
+export type NormalizeReport = { measuredLufs: number | null, gainDb: number, limited: boolean, outputLufs: number | null, outputTruePeakDbtp: number | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RenderStopReason } from "./RenderStopReason";
+
+/**
+ * Offline render mode and progress (get_offline_render_status_global)
+ */
+export type OfflineRenderStatus = { enabled: boolean, thresholdDb: number, tailCapSeconds: number, tailSeconds: number, finished: boolean, stopReason?: RenderStopReason, };
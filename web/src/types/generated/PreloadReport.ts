@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What pre_load_preset touched (JSON)
+ */
+export type PreloadReport = { presetIndex: number, presetName: string, zones: number, samples: number, sampleBytes: number, };
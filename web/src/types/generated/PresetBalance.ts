@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PresetLevel } from "./PresetLevel";
+
+/**
+ * Result of a balance pass over one bank (JSON)
+ */
+export type PresetBalance = { referenceDb: number | null, presets: Array<PresetLevel>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EffectsSends } from "./EffectsSends";
+
+/**
+ * One preset as listed in the catalog
+ */
+export type PresetCatalogEntry = { index: number, name: string, bank: number, program: number, gmName?: string, zones: number, sends: EffectsSends, sendsVary: boolean, sendsOverride?: EffectsSends, };
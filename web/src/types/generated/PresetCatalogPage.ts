@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PresetCatalogEntry } from "./PresetCatalogEntry";
+
+/**
+ * One page of the preset catalog (get_soundfont_presets_page)
+ */
+export type PresetCatalogPage = { success: boolean, error?: string, totalPresets: number, matchingPresets: number, offset: number, presets: Array<PresetCatalogEntry>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Level and trim of one preset (JSON)
+ */
+export type PresetLevel = { presetIndex: number, name: string, bank: number, program: number, levelDb: number | null, trimDb: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Preview channel settings (JSON for get_preview_channel_global)
+ */
+export type PreviewChannel = { bank: number, program: number, volume: number, inputChannel: number | null, };
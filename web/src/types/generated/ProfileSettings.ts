@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EffectsQuality } from "./EffectsQuality";
+import type { InterpolationQuality } from "./InterpolationQuality";
+
+/**
+ * What a profile configures
+ */
+export type ProfileSettings = { bufferSize: number, interpolation: InterpolationQuality, maxPolyphony: number, effects: EffectsQuality, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProfileSettings } from "./ProfileSettings";
+import type { QualityLevel } from "./QualityLevel";
+
+/**
+ * A move on the ladder (JSON for the quality callback)
+ */
+export type QualityChange = { previous: QualityLevel, level: QualityLevel, load: number, settings: ProfileSettings, };
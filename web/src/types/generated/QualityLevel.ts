@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Rungs of the ladder, best quality first
+ */
+export type QualityLevel = "full" | "linearInterpolation" | "reducedEffects" | "cappedPolyphony";
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProfileSettings } from "./ProfileSettings";
+import type { QualityLevel } from "./QualityLevel";
+
+/**
+ * Scaler state (get_quality_scaling_global)
+ */
+export type QualityScalingStatus = { enabled: boolean, level: QualityLevel, load: number, settings: ProfileSettings, };
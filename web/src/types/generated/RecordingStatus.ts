@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RecordingPhase } from "./RecordingPhase";
+
+/**
+ * Recording settings and progress (get_recording_status_global)
+ */
+export type RecordingStatus = { phase: RecordingPhase, track: number, punchIn: number | null, punchOut: number | null, preRollBars: number, eventsCaptured: number, eventsDropped: number, passesFailed: number, };
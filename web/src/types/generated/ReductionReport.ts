@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What the last load's reduction did (get_sample_reduction_report_global)
+ */
+export type ReductionReport = { samplesResampled: number, samplesTruncated: number, bytesBefore: number, bytesAfter: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Render-ahead state (JSON)
+ */
+export type RenderAheadStatus = { depthBlocks: number, autoEnable: boolean, queuedFrames: number, addedLatencyMs: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SampleWaveform } from "./SampleWaveform";
+
+/**
+ * One sample as listed in the catalog
+ */
+export type SampleCatalogEntry = { index: number, name: string, length: number, sampleRate: number, originalPitch: number, pitchCorrection: number, loopStart: number, loopEnd: number, hasValidLoop: boolean, rawLoopStart: number, rawLoopEnd: number, peak: number, hasData: boolean, waveform?: SampleWaveform, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SampleCatalogEntry } from "./SampleCatalogEntry";
+
+/**
+ * One page of the sample catalog (get_soundfont_samples_page)
+ */
+export type SampleCatalogPage = { success: boolean, error?: string, totalSamples: number, matchingSamples: number, offset: number, samples: Array<SampleCatalogEntry>, };
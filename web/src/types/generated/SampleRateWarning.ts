@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A sample whose declared rate was replaced (get_sample_rate_warnings_global)
+ */
+export type SampleRateWarning = { sample: number, name: string, declared: number, corrected: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Min/max overview of a sample, normalized to -1.0..1.0
+ */
+export type SampleWaveform = { min: Array<number>, max: Array<number>, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Measurement } from "./Measurement";
+import type { SelfTestLevel } from "./SelfTestLevel";
+import type { SelfTestStatus } from "./SelfTestStatus";
+
+/**
+ * Outcome of one check
+ */
+export type SelfTestItem = { level: SelfTestLevel, name: string, status: SelfTestStatus, message: string, measurements: Array<Measurement>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SelfTestItem } from "./SelfTestItem";
+
+/**
+ * Every check of a run, in the order they ran (run_self_test)
+ */
+export type SelfTestReport = { passed: number, failed: number, skipped: number, items: Array<SelfTestItem>, };
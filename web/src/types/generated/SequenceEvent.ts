@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SequenceEventKind } from "./SequenceEventKind";
+
+/**
+ * Listing entry for one track event (get_sequence_events_global)
+ */
+export type SequenceEvent = { index: number, tick: number, kind: SequenceEventKind, channel?: number, note?: number, velocity?: number, controller?: number, value?: number, durationTicks?: number, };
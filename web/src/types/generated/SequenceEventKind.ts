@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Event kind in `SequenceEvent` listings
+ */
+export type SequenceEventKind = "noteOn" | "noteOff" | "programChange" | "controlChange" | "pitchBend" | "meta" | "sysEx";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Listing entry for one track (get_sequence_tracks_global)
+ */
+export type SequenceTrack = { index: number, name?: string, eventCount: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What the loaded song's setup region holds (get_song_setup_global)
+ */
+export type SongSetupReport = { enabled: boolean, endTick: number, sysex: number, programChanges: number, controllers: number, channels: Array<number>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Identity of the loaded SoundFont (get_soundfont_identity_global)
+ */
+export type SoundFontIdentity = { hash: string, name: string, version: string, presets: number, instruments: number, samples: number, sampleBytes: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A recorded pass kept as a track of its own (get_takes_global)
+ */
+export type Take = { id: number, track: number, target: number, punchIn: number | null, punchOut: number | null, preRollBars: number, selected: boolean, };
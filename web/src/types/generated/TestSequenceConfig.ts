@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * MIDI test sequence configuration
+ */
+export type TestSequenceConfig = { channel: number, velocity: number, note_duration_ms: number, note_gap_ms: number, start_timestamp: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EventSource } from "./EventSource";
+
+/**
+ * Trace entry as reported (JSON)
+ */
+export type TraceEntryReport = { sample: number, source: EventSource, channel: number, messageType: string, data1: number, data2: number, action: string, };
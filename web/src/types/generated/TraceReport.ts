@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TraceCounter } from "./TraceCounter";
+
+/**
+ * All counters keyed by event name (get_audio_trace_counters)
+ */
+export type TraceReport = { enabled: boolean, events: { [key in string]?: TraceCounter }, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Transport actions for the `transport` command
+ */
+export type TransportAction = "play" | "pause" | "stop" | "seek" | "tempo" | "playWithFade" | "stopWithFade";
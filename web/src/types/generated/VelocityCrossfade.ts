@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CrossfadeCurve } from "./CrossfadeCurve";
+
+/**
+ * Curve and width used for velocity range crossfades
+ */
+export type VelocityCrossfade = { curve: CrossfadeCurve, width: number, };
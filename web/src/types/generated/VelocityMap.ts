@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Velocity range and curve for one channel
+ */
+export type VelocityMap = { min: number, max: number, curve: number, };
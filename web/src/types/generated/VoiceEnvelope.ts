@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EnvelopeState } from "./EnvelopeState";
+
+/**
+ * One voice's volume envelope (get_voice_envelopes_global)
+ */
+export type VoiceEnvelope = { voice: number, active: boolean, note: number, channel: number, stage: EnvelopeState, progress: number, level: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LifetimeBucket } from "./LifetimeBucket";
+
+/**
+ * Voice statistics since the last reset (get_voice_stats_global)
+ */
+export type VoiceStatsReport = { elapsedSeconds: number, notesStarted: bigint, notesFinished: bigint, steals: bigint, droppedNotes: bigint, meanLifetimeSeconds: number, longestLifetimeSeconds: number, lifetimeHistogram: Array<LifetimeBucket>, averagePolyphony: number, peakPolyphony: number, polyphony95th: number, };
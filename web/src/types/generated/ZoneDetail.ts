@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Details about a specific zone for analysis
+ */
+export type ZoneDetail = { sampleName: string, presetName: string, instrumentName: string, weight: number, originalPitch: number, sampleRate: number, };
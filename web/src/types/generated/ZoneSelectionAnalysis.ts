@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ZoneDetail } from "./ZoneDetail";
+import type { ZoneSelectionStrategy } from "./ZoneSelectionStrategy";
+
+/**
+ * Analysis information for zone selection debugging
+ */
+export type ZoneSelectionAnalysis = { note: number, velocity: number, totalMatchingZones: number, selectedZones: number, strategy: ZoneSelectionStrategy, roundRobinEnabled: boolean, zoneDetails: Array<ZoneDetail>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Zone selection strategies for multi-sample instruments
+ */
+export type ZoneSelectionStrategy = "allMatching" | "roundRobin" | "firstMatch" | "random" | "priority";